@@ -640,6 +640,18 @@ pub(crate) fn is_valid_pending_word(word: felt252, len: Bytes31Index) -> bool {
     data < bound
 }
 
+/// Returns the data array of a string literal, copied from its full words stored as a boxed
+/// constant.
+/// Used by the compiler when pooling string literals, as identical boxed constants share their
+/// storage.
+pub(crate) fn string_literal_data<const SIZE: usize>(
+    words: Box<[bytes31; SIZE]>,
+) -> Array<bytes31> {
+    let mut data = array![];
+    data.append_span((@words.as_snapshot()).span());
+    data
+}
+
 impl ByteArrayAdd of Add<ByteArray> {
     /// Concatenates two `ByteArray` and returns the resulting `ByteArray`.
     ///
@@ -84,6 +84,17 @@ pub(crate) extern fn felt252_dict_squash<T>(
     dict: Felt252Dict<T>,
 ) -> SquashedFelt252Dict<T> implicits(RangeCheck, GasBuiltin, SegmentArena) nopanic;
 
+// Squashes `COUNT` dictionaries at once, sharing the fixed costs of squashing between them.
+// The declaration matches `COUNT = 2`, as used by `Felt252DictTrait::squash_with()`.
+//
+// NOTE: Never use this libfunc directly. Use Felt252DictTrait::squash_with() instead, for the same
+// reasons as `felt252_dict_squash`.
+extern fn felt252_dict_squash_many<T, const COUNT: felt252>(
+    dict0: Felt252Dict<T>, dict1: Felt252Dict<T>,
+) -> (
+    SquashedFelt252Dict<T>, SquashedFelt252Dict<T>,
+) implicits(RangeCheck, GasBuiltin, SegmentArena) nopanic;
+
 extern fn squashed_felt252_dict_entries<T>(
     dict: SquashedFelt252Dict<T>,
 ) -> Array<(felt252, T, T)> nopanic;
@@ -130,6 +141,26 @@ pub trait Felt252DictTrait<T> {
     /// ```
     fn squash(self: Felt252Dict<T>) -> SquashedFelt252Dict<T> nopanic;
 
+    /// Squashes a dictionary together with another one, and returns the associated
+    /// `SquashedFelt252Dict`s in the same order.
+    ///
+    /// Cheaper than squashing each of the dictionaries separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::dict::Felt252Dict;
+    ///
+    /// let mut dict: Felt252Dict<u8> = Default::default();
+    /// let mut other: Felt252Dict<u8> = Default::default();
+    /// dict.insert(0, 10);
+    /// other.insert(1, 20);
+    /// let (squashed_dict, squashed_other) = dict.squash_with(other);
+    /// ```
+    fn squash_with(
+        self: Felt252Dict<T>, other: Felt252Dict<T>,
+    ) -> (SquashedFelt252Dict<T>, SquashedFelt252Dict<T>) nopanic;
+
     /// Retrieves the last entry for a certain key.
     /// This method takes ownership of the dictionary and returns the entry to update,
     /// as well as the previous value at the given key.
@@ -167,6 +198,13 @@ impl Felt252DictImpl<T, +Felt252DictValue<T>> of Felt252DictTrait<T> {
         felt252_dict_squash(self)
     }
 
+    #[inline(never)]
+    fn squash_with(
+        self: Felt252Dict<T>, other: Felt252Dict<T>,
+    ) -> (SquashedFelt252Dict<T>, SquashedFelt252Dict<T>) nopanic {
+        felt252_dict_squash_many::<T, 2>(self, other)
+    }
+
     #[inline]
     fn entry(self: Felt252Dict<T>, key: felt252) -> (Felt252DictEntry<T>, T) nopanic {
         felt252_dict_entry_get(self, key)
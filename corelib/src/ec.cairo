@@ -151,6 +151,12 @@ extern fn ec_state_add_mul(
     ref s: EcState, scalar: felt252, p: NonZeroEcPoint,
 ) implicits(EcOp) nopanic;
 
+/// Adds the products `p0 * scalar0` and `p1 * scalar1` to the state.
+/// The declaration matches `COUNT = 2`; redeclare the libfunc to add more products at once.
+extern fn ec_state_add_mul_many<const COUNT: felt252>(
+    ref s: EcState, scalar0: felt252, p0: NonZeroEcPoint, scalar1: felt252, p1: NonZeroEcPoint,
+) implicits(EcOp) nopanic;
+
 /// Finalizes the EC computation and returns the result (returns `None` if the result is the
 /// zero point).
 extern fn ec_state_try_finalize_nz(s: EcState) -> Option<NonZeroEcPoint> nopanic;
@@ -200,6 +206,27 @@ pub impl EcStateImpl of EcStateTrait {
         ec_state_add_mul(ref self, :scalar, :p);
     }
 
+    /// Adds the products `p0 * scalar0` and `p1 * scalar1` to the state.
+    ///
+    /// Cheaper than calling `add_mul` twice.
+    ///
+    /// # Arguments
+    ///
+    /// * `scalar0` - The scalar to multiply the first point by
+    /// * `p0` - The first non-zero point to multiply and add
+    /// * `scalar1` - The scalar to multiply the second point by
+    /// * `p1` - The second non-zero point to multiply and add
+    #[inline]
+    fn add_mul_two(
+        ref self: EcState,
+        scalar0: felt252,
+        p0: NonZeroEcPoint,
+        scalar1: felt252,
+        p1: NonZeroEcPoint,
+    ) nopanic {
+        ec_state_add_mul_many::<2>(ref self, :scalar0, :p0, :scalar1, :p1);
+    }
+
     /// Finalizes the EC computation and returns the result as a non-zero point.
     ///
     /// # Returns
@@ -496,3 +496,22 @@ impl DefaultFixedSizedArray<T> of Default<[T; 0]> {
 
 impl FixedSizedArrayDrop<T, +Drop<T>, const N: usize> of Drop<[T; N]>;
 impl FixedSizedArrayCopy<T, +Copy<T>, const N: usize> of Copy<[T; N]>;
+
+impl FixedSizeArrayIndexView<T, const N: usize> of crate::ops::IndexView<[T; N], usize> {
+    type Target = @T;
+
+    /// Returns a snapshot of the element at the given index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let arr: [u8; 3] = [1, 2, 3];
+    /// let element: @u8 = arr[0];
+    /// assert!(element == @1);
+    /// ```
+    #[inline]
+    fn index(self: @[T; N], index: usize) -> @T {
+        let boxed: Box<@[T; N]> = BoxTrait::new(self);
+        boxed.span()[index]
+    }
+}
@@ -32,6 +32,13 @@ pub struct Formatter {
 /// ```
 pub trait Display<T> {
     fn fmt(self: @T, ref f: Formatter) -> Result<(), Error>;
+
+    /// Returns the alignment of the formatted value when padded without an explicit alignment,
+    /// e.g. `{:5}`.
+    #[doc(hidden)]
+    fn default_alignment(self: @T) -> Alignment {
+        Alignment::Left
+    }
 }
 
 impl DisplayByteArray of Display<ByteArray> {
@@ -57,6 +64,10 @@ impl DisplayInteger<
         self.append_formatted_to_byte_array(ref f.buffer, base.try_into().unwrap());
         Ok(())
     }
+
+    fn default_alignment(self: @T) -> Alignment {
+        Alignment::Right
+    }
 }
 
 impl DisplaySignedInteger<
@@ -74,6 +85,10 @@ impl DisplaySignedInteger<
         }
         abs.fmt(ref f)
     }
+
+    fn default_alignment(self: @Signed) -> Alignment {
+        Alignment::Right
+    }
 }
 
 impl DisplayNonZero<T, +Display<T>, +Copy<T>, +Drop<T>> of Display<NonZero<T>> {
@@ -81,6 +96,11 @@ impl DisplayNonZero<T, +Display<T>, +Copy<T>, +Drop<T>> of Display<NonZero<T>> {
         let value: T = (*self).into();
         write!(f, "{value}")
     }
+
+    fn default_alignment(self: @NonZero<T>) -> Alignment {
+        let value: T = (*self).into();
+        Display::default_alignment(@value)
+    }
 }
 
 impl DisplayBool of Display<bool> {
@@ -97,6 +117,10 @@ impl DisplaySnapshot<T, +Display<T>> of Display<@T> {
     fn fmt(self: @@T, ref f: Formatter) -> Result<(), Error> {
         Display::fmt(*self, ref f)
     }
+
+    fn default_alignment(self: @@T) -> Alignment {
+        Display::default_alignment(*self)
+    }
 }
 
 /// A trait for debug formatting, using the empty format ("{:?}").
@@ -109,6 +133,13 @@ impl DisplaySnapshot<T, +Display<T>> of Display<@T> {
 /// ```
 pub trait Debug<T> {
     fn fmt(self: @T, ref f: Formatter) -> Result<(), Error>;
+
+    /// Returns the alignment of the formatted value when padded without an explicit alignment,
+    /// e.g. `{:5}`.
+    #[doc(hidden)]
+    fn default_alignment(self: @T) -> Alignment {
+        Alignment::Left
+    }
 }
 
 impl DebugByteArray of Debug<ByteArray> {
@@ -135,6 +166,10 @@ impl DebugInteger<
     fn fmt(self: @T, ref f: Formatter) -> Result<(), Error> {
         Display::fmt(self, ref f)
     }
+
+    fn default_alignment(self: @T) -> Alignment {
+        Alignment::Right
+    }
 }
 
 impl DebugSignedInteger<
@@ -148,6 +183,10 @@ impl DebugSignedInteger<
     fn fmt(self: @Signed, ref f: Formatter) -> Result<(), Error> {
         Display::fmt(self, ref f)
     }
+
+    fn default_alignment(self: @Signed) -> Alignment {
+        Alignment::Right
+    }
 }
 
 impl DebugNonZero<T, +Debug<T>, +Copy<T>, +Drop<T>> of Debug<NonZero<T>> {
@@ -155,6 +194,11 @@ impl DebugNonZero<T, +Debug<T>, +Copy<T>, +Drop<T>> of Debug<NonZero<T>> {
         let value: T = (*self).into();
         write!(f, "{value:?}")
     }
+
+    fn default_alignment(self: @NonZero<T>) -> Alignment {
+        let value: T = (*self).into();
+        Debug::default_alignment(@value)
+    }
 }
 
 impl DebugBool of Debug<bool> {
@@ -340,18 +384,33 @@ pub mod into_felt252_based {
         fn fmt(self: @T, ref f: crate::fmt::Formatter) -> Result<(), crate::fmt::Error> {
             crate::fmt::DebugInteger::<felt252>::fmt(@(*self).into(), ref f)
         }
+
+        fn default_alignment(self: @T) -> crate::fmt::Alignment {
+            crate::fmt::Alignment::Right
+        }
     }
 
     pub impl LowerHexImpl<T, +Into<T, felt252>, +Copy<T>> of core::fmt::LowerHex<T> {
         fn fmt(self: @T, ref f: core::fmt::Formatter) -> Result<(), core::fmt::Error> {
             core::fmt::LowerHexInteger::<felt252>::fmt(@(*self).into(), ref f)
         }
+
+        fn default_alignment(self: @T) -> core::fmt::Alignment {
+            core::fmt::Alignment::Right
+        }
     }
 }
 
 /// A trait for hex formatting in lower case, using the empty format ("{:x}").
 pub trait LowerHex<T> {
     fn fmt(self: @T, ref f: Formatter) -> Result<(), Error>;
+
+    /// Returns the alignment of the formatted value when padded without an explicit alignment,
+    /// e.g. `{:5}`.
+    #[doc(hidden)]
+    fn default_alignment(self: @T) -> Alignment {
+        Alignment::Left
+    }
 }
 
 impl LowerHexInteger<
@@ -362,6 +421,10 @@ impl LowerHexInteger<
         self.append_formatted_to_byte_array(ref f.buffer, base.try_into().unwrap());
         Ok(())
     }
+
+    fn default_alignment(self: @T) -> Alignment {
+        Alignment::Right
+    }
 }
 
 impl LowerHexNonZero<T, +LowerHex<T>, +Copy<T>, +Drop<T>> of LowerHex<NonZero<T>> {
@@ -369,4 +432,45 @@ impl LowerHexNonZero<T, +LowerHex<T>, +Copy<T>, +Drop<T>> of LowerHex<NonZero<T>
         let value: T = (*self).into();
         write!(f, "{value:x}")
     }
+
+    fn default_alignment(self: @NonZero<T>) -> Alignment {
+        let value: T = (*self).into();
+        LowerHex::default_alignment(@value)
+    }
+}
+
+/// The alignment of a padded value, as given in a format string (`<`, `^` or `>`).
+#[doc(hidden)]
+#[derive(Copy, Drop)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+/// Appends `value` to the formatter, padded with the `fill` byte up to `width` bytes.
+///
+/// Used by the formatting macros for placeholders with a width, e.g. `{:*^10}`.
+#[doc(hidden)]
+pub fn write_padded(
+    ref f: Formatter, value: @ByteArray, fill: u8, alignment: Alignment, width: usize,
+) {
+    let len = value.len();
+    if len >= width {
+        f.buffer.append(value);
+        return;
+    }
+    let padding = width - len;
+    let (before, after) = match alignment {
+        Alignment::Left => (0, padding),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+        Alignment::Right => (padding, 0),
+    };
+    for _ in 0..before {
+        f.buffer.append_byte(fill);
+    }
+    f.buffer.append(value);
+    for _ in 0..after {
+        f.buffer.append_byte(fill);
+    }
 }
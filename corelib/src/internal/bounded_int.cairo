@@ -200,6 +200,40 @@ extern fn bounded_int_constrain<T, const BOUNDARY: felt252, impl H: ConstrainHel
     value: T,
 ) -> Result<H::LowT, H::HighT> implicits(RangeCheck) nopanic;
 
+/// A helper trait for adding two `BoundedInt` instances, and constraining the result.
+pub trait AddConstrainHelper<Lhs, Rhs, const BOUNDARY: felt252> {
+    type LowT;
+    type HighT;
+}
+extern fn bounded_int_add_constrain<
+    Lhs, Rhs, const BOUNDARY: felt252, impl H: AddConstrainHelper<Lhs, Rhs, BOUNDARY>,
+>(
+    lhs: Lhs, rhs: Rhs,
+) -> Result<H::LowT, H::HighT> implicits(RangeCheck) nopanic;
+
+/// A helper trait for multiplying two `BoundedInt` instances, and constraining the result.
+pub trait MulConstrainHelper<Lhs, Rhs, const BOUNDARY: felt252> {
+    type LowT;
+    type HighT;
+}
+extern fn bounded_int_mul_constrain<
+    Lhs, Rhs, const BOUNDARY: felt252, impl H: MulConstrainHelper<Lhs, Rhs, BOUNDARY>,
+>(
+    lhs: Lhs, rhs: Rhs,
+) -> Result<H::LowT, H::HighT> implicits(RangeCheck) nopanic;
+
+/// A helper trait for dividing two `BoundedInt` instances, and constraining the quotient.
+pub trait DivRemConstrainHelper<Lhs, Rhs, const BOUNDARY: felt252> {
+    type LowDivT;
+    type HighDivT;
+    type RemT;
+}
+extern fn bounded_int_div_rem_constrain<
+    Lhs, Rhs, const BOUNDARY: felt252, impl H: DivRemConstrainHelper<Lhs, Rhs, BOUNDARY>,
+>(
+    lhs: Lhs, rhs: NonZero<Rhs>,
+) -> Result<(H::LowDivT, H::RemT), (H::HighDivT, H::RemT)> implicits(RangeCheck) nopanic;
+
 /// A helper trait for trimming a `BoundedInt` instance's min value.
 pub trait TrimMinHelper<T> {
     type Target;
@@ -341,7 +375,9 @@ impl NonZeroMulMinusOneNegateHelper<
 }
 
 pub use {
-    bounded_int_add as add, bounded_int_constrain as constrain, bounded_int_div_rem as div_rem,
-    bounded_int_is_zero as is_zero, bounded_int_mul as mul, bounded_int_sub as sub,
+    bounded_int_add as add, bounded_int_add_constrain as add_constrain,
+    bounded_int_constrain as constrain, bounded_int_div_rem as div_rem,
+    bounded_int_div_rem_constrain as div_rem_constrain, bounded_int_is_zero as is_zero,
+    bounded_int_mul as mul, bounded_int_mul_constrain as mul_constrain, bounded_int_sub as sub,
     bounded_int_trim_max as trim_max, bounded_int_trim_min as trim_min,
 };
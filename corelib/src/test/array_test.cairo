@@ -138,6 +138,21 @@ fn test_fixed_size_array() {
     assert_eq!(z, 12);
 }
 
+#[test]
+fn test_fixed_size_array_index() {
+    let arr = [10, 11, 12];
+    assert_eq!(*arr[0], 10);
+    assert_eq!(*arr[1], 11);
+    assert_eq!(*arr[2], 12);
+}
+
+#[test]
+#[should_panic(expected: ('Index out of bounds',))]
+fn test_fixed_size_array_index_out_of_bounds() {
+    let arr = [10, 11, 12];
+    let _ = arr[3];
+}
+
 fn consume<const N: usize>(_arr: [felt252; N]) {}
 
 #[test]
@@ -184,3 +184,18 @@ fn test_array_from_squash_dict() {
         dict.squash().into_entries(), array![(0, 0, 0), (1, 0, 1), (2, 0, 2), (3, 0, 3), (4, 0, 4)],
     );
 }
+
+#[test]
+fn test_dict_squash_with() {
+    let mut dict0: Felt252Dict<u32> = (0..3_u32).into_iter().map(|x| (x.into(), x)).collect();
+    let mut dict1: Felt252Dict<u32> = Default::default();
+    let mut dict2: Felt252Dict<u32> = Default::default();
+    dict1.insert(7, 1);
+    dict2.insert(5, 2);
+    dict2.insert(5, 3);
+    dict0.insert(1, 4);
+    assert_eq!(dict1.squash().into_entries(), array![(7, 0, 1)]);
+    let (squashed2, squashed0) = dict2.squash_with(dict0);
+    assert_eq!(squashed2.into_entries(), array![(5, 0, 3)]);
+    assert_eq!(squashed0.into_entries(), array![(0, 0, 0), (1, 0, 4), (2, 0, 2)]);
+}
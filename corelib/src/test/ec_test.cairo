@@ -146,3 +146,27 @@ fn test_ec_mul() {
         'ec_mul failed (y).',
     );
 }
+
+#[test]
+fn test_ec_state_add_mul_two() {
+    let p = EcPointTrait::new_from_x(1).unwrap();
+    let q = p.mul(3);
+    let p_nz = p.try_into().unwrap();
+    let q_nz = q.try_into().unwrap();
+
+    let mut state = EcStateTrait::init();
+    state.add_mul_two(5, p_nz, 7, q_nz);
+    let (x, y) = state.finalize_nz().expect('zero point').coordinates();
+
+    let mut expected_state = EcStateTrait::init();
+    expected_state.add_mul(5, p_nz);
+    expected_state.add_mul(7, q_nz);
+    let (expected_x, expected_y) = expected_state.finalize_nz().expect('zero point').coordinates();
+    assert_eq(@x, @expected_x, 'bad add_mul_two x');
+    assert_eq(@y, @expected_y, 'bad add_mul_two y');
+
+    // The result equals `26 * p`.
+    let (mul_x, mul_y) = p.mul(26).try_into().unwrap().coordinates();
+    assert_eq(@x, @mul_x, 'bad 26p x');
+    assert_eq(@y, @mul_y, 'bad 26p y');
+}
@@ -29,6 +29,46 @@ fn test_format() {
     );
 }
 
+#[test]
+fn test_format_inline_expressions() {
+    let (x, y) = (3_u32, 4_u32);
+    assert_eq!(format!("{x + y}"), "7");
+    assert_eq!(format!("{x * y:x}"), "c");
+    assert_eq!(format!("{(x, y):?}"), "(3, 4)");
+    assert_eq!(format!("{core::cmp::max(x, y)}"), "4");
+    let values = array![1_u8, 2, 3];
+    assert_eq!(format!("{values.len()}_{*values[1]}"), "3_2");
+}
+
+#[test]
+fn test_format_width_and_fill() {
+    let ba: ByteArray = "ab";
+    assert_eq!(format!("[{ba:5}]"), "[ab   ]");
+    assert_eq!(format!("[{ba:<5}]"), "[ab   ]");
+    assert_eq!(format!("[{ba:>5}]"), "[   ab]");
+    assert_eq!(format!("[{ba:^5}]"), "[ ab  ]");
+    assert_eq!(format!("[{ba:*^6}]"), "[**ab**]");
+    assert_eq!(format!("[{ba:1}]"), "[ab]");
+    assert_eq!(format!("[{:0>4}]", 7_u8), "[0007]");
+    assert_eq!(format!("[{:->6x}]", 255_u32), "[----ff]");
+    assert_eq!(format!("[{:>18?}]", Some(1_u8)), "[   Option::Some(1)]");
+    assert_eq!(format!("[{1 + 2: >3}]"), "[  3]");
+}
+
+#[test]
+fn test_format_default_alignment() {
+    let (x, y) = (7_u8, -3_i16);
+    assert_eq!(format!("[{x:3}|{y:3}|{x:<3}]"), "[  7| -3|7  ]");
+    assert_eq!(format!("[{:4x}|{:4?}]", 255_u32, 12_felt252), "[  ff|  12]");
+    let nz: NonZero<u8> = 5_u8.try_into().unwrap();
+    assert_eq!(format!("[{nz:2}|{@x:2}|{true:5}]"), "[ 5| 7|true ]");
+    assert_eq!(format_padded(x), "[  7]");
+}
+
+fn format_padded<T, +core::fmt::Display<T>, +Drop<T>>(value: T) -> ByteArray {
+    format!("[{value:3}]")
+}
+
 #[derive(Debug, Drop)]
 struct StructExample {
     felt_value: felt252,
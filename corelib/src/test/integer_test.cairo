@@ -1938,8 +1938,8 @@ fn test_signed_int_neg_i128_min() {
 #[feature("bounded-int-utils")]
 mod bounded_int {
     use crate::internal::bounded_int::{
-        self, AddHelper, BoundedInt, ConstrainHelper, DivRemHelper, MulHelper, SubHelper, UnitInt,
-        downcast, upcast,
+        self, AddConstrainHelper, AddHelper, BoundedInt, ConstrainHelper, DivRemConstrainHelper,
+        DivRemHelper, MulConstrainHelper, MulHelper, SubHelper, UnitInt, downcast, upcast,
     };
 
     const U128_UPPER: felt252 = 0x100000000000000000000000000000000;
@@ -2203,6 +2203,73 @@ mod bounded_int {
         assert!(test_constrain_helper::<u129, U128_UPPER>(upcast(bi_const::<U129_MAX>())));
     }
 
+    impl U8AddConstrain of AddConstrainHelper<u8, u8, 0x100> {
+        type LowT = BoundedInt<0, 0xff>;
+        type HighT = BoundedInt<0x100, 0x1fe>;
+    }
+    /// Adds `lhs` and `rhs`, returning the sum and whether it is at least `0x100`.
+    fn add_constrain_helper(lhs: u8, rhs: u8) -> (felt252, bool) {
+        match bounded_int::add_constrain::<_, _, 0x100>(lhs, rhs) {
+            Ok(result) => (upcast(result), false),
+            Err(result) => (upcast(result), true),
+        }
+    }
+
+    #[test]
+    fn test_add_constrain() {
+        assert!(add_constrain_helper(0, 0) == (0, false));
+        assert!(add_constrain_helper(0x80, 0x7f) == (0xff, false));
+        assert!(add_constrain_helper(0x80, 0x80) == (0x100, true));
+        assert!(add_constrain_helper(0xff, 0xff) == (0x1fe, true));
+    }
+
+    impl I8MulConstrain of MulConstrainHelper<i8, i8, 0> {
+        type LowT = BoundedInt<{ 127 * -128 }, -1>;
+        type HighT = BoundedInt<0, { 128 * 128 }>;
+    }
+    /// Multiplies `lhs` and `rhs`, returning the product and whether it is non-negative.
+    fn mul_constrain_helper(lhs: i8, rhs: i8) -> (felt252, bool) {
+        match bounded_int::mul_constrain::<_, _, 0>(lhs, rhs) {
+            Ok(result) => (upcast(result), false),
+            Err(result) => (upcast(result), true),
+        }
+    }
+
+    #[test]
+    fn test_mul_constrain() {
+        assert!(mul_constrain_helper(-1, 1) == (-1, false));
+        assert!(mul_constrain_helper(127, -128) == (127 * -128, false));
+        assert!(mul_constrain_helper(0, -128) == (0, true));
+        assert!(mul_constrain_helper(-128, -128) == (128 * 128, true));
+    }
+
+    impl SmallNumDivRemConstrain of DivRemConstrainHelper<
+        BoundedInt<128, 255>, BoundedInt<3, 8>, 32,
+    > {
+        type LowDivT = BoundedInt<16, 31>;
+        type HighDivT = BoundedInt<32, 85>;
+        type RemT = BoundedInt<0, 7>;
+    }
+    /// Divides `a` by `b`, returning the quotient, remainder and whether the quotient is at least
+    /// 32.
+    fn div_rem_constrain_helper(a: u128, b: u128) -> (felt252, felt252, bool) {
+        let a = bi_value::<128, 255>(a);
+        let b = bounded_int_wrap_non_zero(bi_value::<3, 8>(b));
+        match bounded_int::div_rem_constrain::<_, _, 32>(a, b) {
+            Ok((q, r)) => (upcast(q), upcast(r), false),
+            Err((q, r)) => (upcast(q), upcast(r), true),
+        }
+    }
+
+    #[test]
+    fn test_div_rem_constrain() {
+        assert!(div_rem_constrain_helper(128, 3) == (42, 2, true));
+        assert!(div_rem_constrain_helper(255, 3) == (85, 0, true));
+        assert!(div_rem_constrain_helper(128, 8) == (16, 0, false));
+        assert!(div_rem_constrain_helper(255, 8) == (31, 7, false));
+        assert!(div_rem_constrain_helper(192, 6) == (32, 0, true));
+    }
+
     #[test]
     fn test_trim() {
         use core::internal::OptionRev;
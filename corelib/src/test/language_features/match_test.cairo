@@ -24,3 +24,78 @@ fn test_match_extern_multilevel() {
     }
     panic!("Match expression did not return - this should be unreachable");
 }
+
+fn classify_u8(x: u8) -> felt252 {
+    match x {
+        0 => 'zero',
+        1..=9 => 'digit',
+        10..100 => 'two digits',
+        100..=255 => 'three digits',
+    }
+}
+
+#[test]
+fn test_match_range_patterns() {
+    assert_eq!(classify_u8(0), 'zero');
+    assert_eq!(classify_u8(1), 'digit');
+    assert_eq!(classify_u8(9), 'digit');
+    assert_eq!(classify_u8(10), 'two digits');
+    assert_eq!(classify_u8(99), 'two digits');
+    assert_eq!(classify_u8(100), 'three digits');
+    assert_eq!(classify_u8(255), 'three digits');
+}
+
+fn bucket_i32(x: i32) -> felt252 {
+    match x {
+        0..10 => 'small',
+        10..=1000 => 'medium',
+        _ => 'other',
+    }
+}
+
+#[test]
+fn test_match_range_patterns_signed() {
+    assert_eq!(bucket_i32(-5), 'other');
+    assert_eq!(bucket_i32(0), 'small');
+    assert_eq!(bucket_i32(9), 'small');
+    assert_eq!(bucket_i32(10), 'medium');
+    assert_eq!(bucket_i32(1000), 'medium');
+    assert_eq!(bucket_i32(1001), 'other');
+}
+
+fn describe_option(x: Option<u32>, limit: u32) -> felt252 {
+    match x {
+        Some(v) if v > limit => 'above',
+        Some(0) => 'zero',
+        Some(v) if v == limit => 'at limit',
+        Some(_) => 'below',
+        None => 'none',
+    }
+}
+
+#[test]
+fn test_match_guards() {
+    assert_eq!(describe_option(Some(11), 10), 'above');
+    assert_eq!(describe_option(Some(0), 10), 'zero');
+    assert_eq!(describe_option(Some(0), 0), 'zero');
+    assert_eq!(describe_option(Some(10), 10), 'at limit');
+    assert_eq!(describe_option(Some(3), 10), 'below');
+    assert_eq!(describe_option(None, 10), 'none');
+}
+
+#[test]
+fn test_match_guard_with_range() {
+    let mut evens = 0_u8;
+    let mut odds = 0_u8;
+    let mut others = 0_u8;
+    for x in 0..20_u8 {
+        match x {
+            1..=9 if x % 2 == 0 => { evens += 1; },
+            1..=9 => { odds += 1; },
+            _ => { others += 1; },
+        }
+    }
+    assert_eq!(evens, 4);
+    assert_eq!(odds, 5);
+    assert_eq!(others, 11);
+}
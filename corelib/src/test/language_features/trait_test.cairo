@@ -15,3 +15,58 @@ fn test_default_implementation_and_override() {
     assert!(MyTrait::foo(5_u64) == 5);
     assert!(MyTrait::foo(10_u32) == 11);
 }
+
+trait WithDefaults<T> {
+    type Item = T;
+    const SIZE: u32 = 2;
+    const DOUBLE_SIZE: u32 = Self::SIZE * 2;
+    fn wrap(item: Self::Item) -> Self::Item {
+        item
+    }
+}
+impl WithDefaultsU8 of WithDefaults<u8>;
+impl WithDefaultsU16 of WithDefaults<u16> {
+    type Item = u32;
+    const SIZE: u32 = 5;
+}
+
+#[test]
+fn test_default_items_and_override() {
+    let item: WithDefaultsU8::Item = 3_u8;
+    assert!(WithDefaultsU8::wrap(item) == 3);
+    assert!(WithDefaultsU8::SIZE == 2);
+    assert!(WithDefaultsU8::DOUBLE_SIZE == 4);
+    let item: WithDefaultsU16::Item = 7_u32;
+    assert!(WithDefaultsU16::wrap(item) == 7);
+    assert!(WithDefaultsU16::SIZE == 5);
+    assert!(WithDefaultsU16::DOUBLE_SIZE == 10);
+}
+
+trait Describe<T> {
+    fn describe(self: @T) -> felt252;
+}
+
+#[fallback]
+impl DescribeAny<T> of Describe<T> {
+    fn describe(self: @T) -> felt252 {
+        'any'
+    }
+}
+
+impl DescribeU8 of Describe<u8> {
+    fn describe(self: @u8) -> felt252 {
+        'u8'
+    }
+}
+
+fn describe_generic<T, +Describe<T>>(value: @T) -> felt252 {
+    value.describe()
+}
+
+#[test]
+fn test_fallback_impl_and_specific_impl() {
+    assert!(1_u8.describe() == 'u8');
+    assert!(1_u16.describe() == 'any');
+    assert!(describe_generic(@1_u8) == 'u8');
+    assert!(describe_generic(@1_u16) == 'any');
+}
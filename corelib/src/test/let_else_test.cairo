@@ -43,3 +43,18 @@ fn test_let_else_break() {
 
     assert_eq!(s, 109);
 }
+
+fn sum_pair(pair: Option<(u8, u8)>) -> Option<u8> {
+    let Some((a, 0..=9)) = pair else {
+        return None;
+    };
+    let (_, b) = pair.unwrap();
+    Some(a + b)
+}
+
+#[test]
+fn test_let_else_nested_pattern() {
+    assert_eq!(sum_pair(Some((3, 4))), Some(7));
+    assert_eq!(sum_pair(Some((3, 14))), None);
+    assert_eq!(sum_pair(None), None);
+}
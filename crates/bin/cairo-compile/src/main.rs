@@ -2,6 +2,8 @@ use std::fs;
 use std::path::PathBuf;
 
 use anyhow::Context;
+use cairo_lang_compiler::determinism::verify_deterministic_build;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::check_compiler_path;
 use cairo_lang_compiler::{CompilerConfig, compile_cairo_project_at_path};
 use cairo_lang_utils::logging::init_logging;
@@ -48,6 +50,33 @@ struct Args {
     /// Overrides inlining behavior.
     #[arg(short, long, default_value = "default")]
     inlining_strategy: InliningStrategy,
+    /// Compiles the project twice, with different parallelism, and fails if the outputs differ.
+    #[arg(long, default_value_t = false)]
+    verify_deterministic: bool,
+    /// Explains failed and ambiguous impl resolutions in the diagnostics.
+    #[arg(long, default_value_t = false)]
+    trait_solver_trace: bool,
+    /// Shows the generated code and the code it was expanded from in diagnostics in plugin
+    /// generated code.
+    #[arg(long, default_value_t = false)]
+    expansion_backtrace: bool,
+    /// Reports warnings as errors, unless their level is configured otherwise.
+    #[arg(long, default_value_t = false)]
+    deny_warnings: bool,
+    /// Prints the diagnostics as JSON, one diagnostic per line, including suggested fixes.
+    #[arg(long, default_value_t = false)]
+    json_diagnostics: bool,
+}
+
+/// Creates the diagnostics reporter according to the arguments.
+fn diagnostics_reporter(args: &Args) -> DiagnosticsReporter<'static> {
+    let reporter = if args.json_diagnostics {
+        DiagnosticsReporter::callback(|diagnostic| eprintln!("{}", diagnostic.to_json()))
+    } else {
+        DiagnosticsReporter::stderr()
+    };
+    let reporter = if args.deny_warnings { reporter.deny_warnings() } else { reporter };
+    if args.expansion_backtrace { reporter.with_expansion_backtrace() } else { reporter }
 }
 
 fn main() -> anyhow::Result<()> {
@@ -59,11 +88,17 @@ fn main() -> anyhow::Result<()> {
     // Check if args.path is a file or a directory.
     check_compiler_path(args.single_file, &args.path)?;
 
-    let sierra_program = compile_cairo_project_at_path(
-        &args.path,
-        CompilerConfig { replace_ids: args.replace_ids, ..CompilerConfig::default() },
-        args.inlining_strategy.into(),
-    )?;
+    let compiler_config = || CompilerConfig {
+        replace_ids: args.replace_ids,
+        trait_solver_trace: args.trait_solver_trace,
+        diagnostics_reporter: diagnostics_reporter(&args),
+        ..CompilerConfig::default()
+    };
+    let sierra_program = if args.verify_deterministic {
+        verify_deterministic_build(&args.path, compiler_config, args.inlining_strategy.into())?
+    } else {
+        compile_cairo_project_at_path(&args.path, compiler_config(), args.inlining_strategy.into())?
+    };
 
     match args.output {
         Some(path) => {
@@ -9,7 +9,9 @@ use cairo_lang_executable::compile::{
     CompileExecutableResult, ExecutableConfig, compile_executable_in_prepared_db, prepare_db,
 };
 use cairo_lang_executable::executable::{EntryPointKind, Executable, NOT_RETURNING_HEADER_SIZE};
-use cairo_lang_execute_utils::{program_and_hints_from_executable, user_args_from_flags};
+use cairo_lang_execute_utils::{
+    load_executable, program_and_hints_from_executable, user_args_from_flags,
+};
 use cairo_lang_filesystem::ids::CrateInput;
 use cairo_lang_runnable_utils::builder::RunnableBuilder;
 use cairo_lang_runner::casm_run::format_for_panic;
@@ -92,6 +94,9 @@ struct BuildArgs {
     /// more difficult to debug.
     #[arg(long, conflicts_with = "prebuilt")]
     unsafe_panic: bool,
+    /// Includes a source map from the bytecode to the Cairo code in the executable's debug info.
+    #[arg(long, conflicts_with = "prebuilt")]
+    source_map: bool,
     /// The path to the executable function.
     ///
     /// Not required if there is only a single executable function in the project.
@@ -186,10 +191,7 @@ fn main() -> anyhow::Result<()> {
 
     let (db, opt_debug_data, executable) = {
         if args.prebuilt {
-            let executable: Executable =
-                serde_json::from_reader(std::fs::File::open(&args.input_path)?)
-                    .with_context(|| "Failed reading prebuilt executable.")?;
-            (None, None, Some(executable))
+            (None, None, Some(load_executable(&args.input_path)?))
         } else {
             // Check if args.path is a file or a directory.
             check_compiler_path(args.build.single_file, &args.input_path)?;
@@ -204,6 +206,7 @@ fn main() -> anyhow::Result<()> {
                 allow_syscalls: args.build.allow_syscalls,
                 unsafe_panic: args.build.unsafe_panic,
                 builtin_list: None,
+                source_map: args.build.source_map,
             };
 
             let mut db = prepare_db(&config)?;
@@ -265,6 +268,8 @@ fn main() -> anyhow::Result<()> {
         no_temporary_segments: false,
         markers: Default::default(),
         panic_traceback: Default::default(),
+        syscall_interceptor: None,
+        captured_output: None,
     };
     let dynamic_layout_params = match args.run.cairo_layout_params_file {
         Some(file) => Some(CairoLayoutParams::from_file(&file)?),
@@ -60,6 +60,9 @@ struct FormatterArgs {
     /// Enable duplicates in `use` items.
     #[arg(long)]
     allow_duplicates: Option<bool>,
+    /// Controls whether a trailing comma is added to lists broken into lines. Defaults to true.
+    #[arg(long)]
+    trailing_comma: Option<bool>,
     /// A list of files and directories to format. Use "-" for stdin.
     files: Vec<String>,
 }
@@ -210,7 +213,8 @@ fn main() -> ExitCode {
         .fixed_array_breaking_behavior(args.fixed_array_line_breaking.map(Into::into))
         .macro_call_breaking_behavior(args.macro_call_breaking_behavior.map(Into::into))
         .merge_use_items(args.merge_use_items)
-        .allow_duplicate_uses(args.allow_duplicates);
+        .allow_duplicate_uses(args.allow_duplicates)
+        .trailing_comma(args.trailing_comma.map(Into::into));
     let fmt = CairoFormatter::new(config);
 
     eprintln_if_verbose(
@@ -1,5 +1,6 @@
 //! Compiles and runs a Cairo program.
 
+use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
@@ -10,9 +11,13 @@ use cairo_lang_diagnostics::ToOption;
 use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
 use cairo_lang_filesystem::ids::CrateInput;
 use cairo_lang_runner::casm_run::format_next_item;
+use cairo_lang_runner::cost_profile::CostMetric;
 use cairo_lang_runner::profiling::ProfilingInfoProcessor;
+use cairo_lang_runner::trace::JsonLinesTraceSink;
 use cairo_lang_runner::{ProfilingInfoCollectionConfig, SierraCasmRunner, StarknetState};
+use cairo_lang_sierra::instrumentation::instrument_program;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::debug_info::SourceMap;
 use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
 use cairo_lang_sierra_generator::replace_ids::{DebugReplacer, SierraIdReplacer};
 use cairo_lang_starknet::contract::{find_contracts, get_contracts_info};
@@ -44,6 +49,18 @@ struct Args {
     /// Whether to run the profiler.
     #[arg(long, default_value_t = false)]
     run_profiler: bool,
+    /// A file to write the execution trace to, as a JSON object per step, attributed to the Cairo
+    /// code the executed instructions were compiled from.
+    #[arg(long)]
+    trace_file: Option<PathBuf>,
+    /// A file to write the gas spent in each function stack to, in the collapsed stacks format of
+    /// flamegraph tools.
+    #[arg(long)]
+    flamegraph_file: Option<PathBuf>,
+    /// A file to write the number of calls and loop back-edges of each function to, as JSON.
+    /// Runs a version of the program instrumented with counters.
+    #[arg(long)]
+    execution_profile_file: Option<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -86,14 +103,39 @@ fn main() -> anyhow::Result<()> {
     let contracts = find_contracts(db, &main_crate_ids);
     let contracts_info = get_contracts_info(db, contracts, &replacer)?;
     let sierra_program = replacer.apply(&sierra_program);
+    let instrumented =
+        args.execution_profile_file.is_some().then(|| instrument_program(&sierra_program));
 
     let runner = SierraCasmRunner::new(
-        sierra_program.clone(),
+        instrumented.as_ref().map_or(&sierra_program, |instrumented| &instrumented.program).clone(),
         if args.available_gas.is_some() { Some(Default::default()) } else { None },
         contracts_info,
         if args.run_profiler { Some(ProfilingInfoCollectionConfig::default()) } else { None },
     )
     .with_context(|| "Failed setting up runner.")?;
+    let runner = match &args.trace_file {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create trace file: {}", path.display()))?;
+            let source_map = SourceMap::new(
+                runner
+                    .casm_program()
+                    .debug_info
+                    .sierra_statement_info
+                    .iter()
+                    .map(|info| (info.start_offset, info.end_offset)),
+                &debug_info.statements_locations.extract_statements_source_code_locations(db),
+                0,
+            );
+            runner.with_trace(JsonLinesTraceSink(BufWriter::new(file)), Some(source_map))
+        }
+        None => runner,
+    };
+    let runner = if args.flamegraph_file.is_some() { runner.with_cost_profile() } else { runner };
+    let runner = match &instrumented {
+        Some(instrumented) => runner.with_probes(&instrumented.probes),
+        None => runner,
+    };
     let result = runner
         .run_function_with_starknet_context(
             runner.find_function("::main")?,
@@ -119,6 +161,20 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
+    if let Some(path) = &args.flamegraph_file {
+        let cost_profile = result.cost_profile.as_ref().context("Cost profile not found.")?;
+        std::fs::write(path, cost_profile.to_collapsed_stacks(&sierra_program, CostMetric::Gas))
+            .with_context(|| format!("Failed to write flamegraph file: {}", path.display()))?;
+    }
+
+    if let (Some(path), Some(instrumented)) = (&args.execution_profile_file, &instrumented) {
+        let probe_hits = result.probe_hits.as_ref().context("Probe hits not found.")?;
+        let profile = instrumented.profile(probe_hits);
+        std::fs::write(path, serde_json::to_string_pretty(&profile)?).with_context(|| {
+            format!("Failed to write execution profile file: {}", path.display())
+        })?;
+    }
+
     match result.value {
         cairo_lang_runner::RunResultValue::Success(values) => {
             println!("Run completed successfully, returning {values:?}")
@@ -134,7 +190,13 @@ fn main() -> anyhow::Result<()> {
                 first = false;
                 print!("{}", item.quote_if_string());
             }
-            println!("].")
+            println!("].");
+            if let Some(failure) = result.failure.filter(|failure| !failure.call_stack.is_empty()) {
+                println!("Call stack:");
+                for frame in failure.call_stack {
+                    println!("    {}", frame.function);
+                }
+            }
         }
     }
     if let Some(gas) = result.gas_counter {
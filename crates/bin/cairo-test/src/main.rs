@@ -24,7 +24,8 @@ struct Args {
     /// Allows the compilation to succeed with warnings.
     #[arg(long)]
     allow_warnings: bool,
-    /// The filter for the tests, running only tests containing the filter string.
+    /// The filter for the tests, running only tests containing the filter string, or, if it
+    /// contains `*`, tests whose names match it as a pattern, e.g. `*::math::test_*`.
     #[arg(short, long, default_value_t = String::default())]
     filter: String,
     /// Whether to run ignored tests as well.
@@ -46,6 +47,20 @@ struct Args {
     /// Whether to print resource usage after each test.
     #[arg(long, default_value_t = false)]
     print_resource_usage: bool,
+    /// Writes the line and branch coverage of the tests to the given file, in the LCOV format.
+    #[arg(long)]
+    coverage: Option<PathBuf>,
+    /// The number of tests to run concurrently. Defaults to the number of available CPUs.
+    #[arg(short, long)]
+    jobs: Option<usize>,
+    /// Writes a JSON report of the tests - the status, gas usage and duration of each - to the
+    /// given file.
+    #[arg(long)]
+    json_report: Option<PathBuf>,
+    /// The seed of the values generated for fuzz tests without a seed of their own. Defaults to a
+    /// random seed.
+    #[arg(long)]
+    fuzz_seed: Option<u64>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -61,6 +76,10 @@ fn main() -> anyhow::Result<()> {
         profiler_config: args.run_profiler.try_into().ok(),
         gas_enabled: !args.gas_disabled,
         print_resource_usage: args.print_resource_usage,
+        coverage_output: args.coverage,
+        jobs: args.jobs,
+        json_report: args.json_report,
+        fuzz_seed: args.fuzz_seed,
     };
 
     let runner = TestRunner::new(&args.path, args.starknet, args.allow_warnings, config)?;
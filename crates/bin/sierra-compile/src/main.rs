@@ -22,6 +22,17 @@ struct Args {
     file: String,
     /// The output file path.
     output: String,
+    /// Whether to print the number of local variable slots in the frame of each function.
+    #[arg(long, default_value_t = false)]
+    frame_sizes: bool,
+    /// Whether to write a listing labeled by the function entry points and annotated with the
+    /// bytecode offset and symbolized target of each instruction, instead of the plain CASM.
+    #[arg(long, default_value_t = false)]
+    symbolized_listing: bool,
+    /// Whether to coalesce the `ap` updates of the CASM code into the `ap++` flags of the
+    /// instructions around them.
+    #[arg(long, default_value_t = false)]
+    coalesce_ap_updates: bool,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -49,9 +60,25 @@ fn main() -> anyhow::Result<()> {
         &program,
         &program_info,
         &metadata,
-        SierraToCasmConfig { gas_usage_check: true, max_bytecode_size: usize::MAX },
+        SierraToCasmConfig {
+            gas_usage_check: true,
+            max_bytecode_size: usize::MAX,
+            record_variable_locations: false,
+            coalesce_ap_updates: args.coalesce_ap_updates,
+        },
     )
     .with_context(|| "Compilation failed.")?;
 
-    fs::write(args.output, cairo_program.to_string()).with_context(|| "Failed to write output.")
+    if args.frame_sizes {
+        for (function_id, frame_size) in cairo_program.debug_info.frame_sizes.iter() {
+            println!("{function_id}: frame of {frame_size} felts.");
+        }
+    }
+
+    let output = if args.symbolized_listing {
+        cairo_program.symbolized_listing(&cairo_program.function_symbols(&program))
+    } else {
+        cairo_program.to_string()
+    };
+    fs::write(args.output, output).with_context(|| "Failed to write output.")
 }
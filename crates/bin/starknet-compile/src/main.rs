@@ -6,6 +6,8 @@ use cairo_lang_compiler::CompilerConfig;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::check_compiler_path;
 use cairo_lang_starknet::compile::starknet_compile;
+use cairo_lang_starknet::storage_layout::storage_layout_path;
+use cairo_lang_starknet::syscall_summary::syscall_summary_path;
 use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
 use clap::Parser;
 
@@ -40,6 +42,12 @@ struct Args {
     /// A file of the allowed libfuncs list to use.
     #[arg(long)]
     allowed_libfuncs_list_file: Option<String>,
+    /// A file to write the storage layout of the contract to, as JSON.
+    #[arg(long)]
+    storage_layout: Option<PathBuf>,
+    /// A file to write the syscalls reachable from each entry point of the contract to, as JSON.
+    #[arg(long)]
+    syscall_summary: Option<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -57,8 +65,8 @@ fn main() -> anyhow::Result<()> {
         diagnostics_reporter = diagnostics_reporter.allow_warnings();
     }
     let res = starknet_compile(
-        args.path,
-        args.contract_path,
+        args.path.clone(),
+        args.contract_path.clone(),
         Some(CompilerConfig {
             replace_ids: args.replace_ids,
             diagnostics_reporter,
@@ -71,6 +79,34 @@ fn main() -> anyhow::Result<()> {
         Some(path) => fs::write(path, res).with_context(|| "Failed to write output.")?,
         None => println!("{res}"),
     }
+    if let Some(layout_file) = args.storage_layout {
+        // Diagnostics were already reported by the compilation.
+        let layout = storage_layout_path(
+            &args.path,
+            args.contract_path.as_deref(),
+            CompilerConfig {
+                diagnostics_reporter: DiagnosticsReporter::ignoring(),
+                ..CompilerConfig::default()
+            },
+        )?;
+        let layout = serde_json::to_string_pretty(&layout)
+            .with_context(|| "Storage layout serialization failed.")?;
+        fs::write(layout_file, layout).with_context(|| "Failed to write storage layout.")?;
+    }
+    if let Some(summary_file) = args.syscall_summary {
+        // Diagnostics were already reported by the compilation.
+        let summary = syscall_summary_path(
+            &args.path,
+            args.contract_path.as_deref(),
+            CompilerConfig {
+                diagnostics_reporter: DiagnosticsReporter::ignoring(),
+                ..CompilerConfig::default()
+            },
+        )?;
+        let summary = serde_json::to_string_pretty(&summary)
+            .with_context(|| "Syscall summary serialization failed.")?;
+        fs::write(summary_file, summary).with_context(|| "Failed to write syscall summary.")?;
+    }
 
     Ok(())
 }
@@ -3,6 +3,7 @@ use std::fs;
 use anyhow::Context;
 use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::class_size::ClassSizeReport;
 use cairo_lang_starknet_classes::contract_class::{ContractClass, ContractEntryPoints};
 use cairo_lang_utils::bigint::BigUintAsHex;
 use clap::Parser;
@@ -33,6 +34,13 @@ struct Args {
     /// The max bytecode size.
     #[arg(long, default_value_t = 180000)]
     max_bytecode_size: usize,
+    /// Print a report of the bytecode felts per entry point and per source function to stderr.
+    #[arg(long, default_value_t = false)]
+    size_report: bool,
+    /// Fail the compilation if an entry point, with all the functions it may call, compiles to
+    /// more felts than this budget.
+    #[arg(long)]
+    max_entry_point_felts: Option<usize>,
 }
 
 /// Same as `ContractClass` - but ignores `abi` in deserialization.
@@ -69,15 +77,36 @@ fn main() -> anyhow::Result<()> {
         entry_points_by_type,
         abi: None,
     };
-    let extracted = contract_class.extract_sierra_program(false)?;
+    let with_size_report = args.size_report || args.max_entry_point_felts.is_some();
+    // Debug names are only needed for the names of the functions in the size report.
+    let extracted = contract_class.extract_sierra_program(with_size_report)?;
     extracted.validate_version_compatible(list_selector)?;
-    let casm_contract = CasmContractClass::from_contract_class(
+    let program = extracted.program.clone();
+    let entry_points = contract_class.entry_points_by_type.clone();
+    let annotations =
+        contract_class.sierra_program_debug_info.as_ref().map(|info| info.annotations.clone());
+    let (casm_contract, debug_info) = CasmContractClass::from_contract_class_with_debug_info(
         contract_class,
         extracted,
         args.add_pythonic_hints,
         args.max_bytecode_size,
     )
     .with_context(|| "Compilation failed.")?;
+    if with_size_report {
+        let report = ClassSizeReport::new(
+            &program,
+            &entry_points,
+            annotations.as_ref(),
+            &casm_contract,
+            &debug_info,
+        );
+        if args.size_report {
+            eprint!("{report}");
+        }
+        if let Some(max_entry_point_felts) = args.max_entry_point_felts {
+            report.check_budget(max_entry_point_felts).with_context(|| "Compilation failed.")?;
+        }
+    }
 
     let res = serde_json::to_string_pretty(&casm_contract)
         .with_context(|| "Casm contract Serialization failed.")?;
@@ -1,5 +1,5 @@
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 
 use num_bigint::BigInt;
 
@@ -100,6 +100,91 @@ pub struct AssembledCairoProgram {
     pub hints: Vec<(usize, Vec<Hint>)>,
 }
 
+/// A bytecode word holding an absolute code address, which must be patched when the program is
+/// not loaded at address 0.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeRelocation {
+    /// The offset of the word within the bytecode.
+    pub word_offset: usize,
+    /// The code offset the word points to, relative to the start of the program.
+    pub target: usize,
+}
+
+/// A named offset within the bytecode of a program, such as the entry point of a function.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeSymbol {
+    /// The name of the symbol.
+    pub name: String,
+    /// The offset of the symbol within the bytecode.
+    pub offset: usize,
+}
+
+/// An assembled Cairo program along with its relocation and symbol tables, allowing it to be
+/// safely patched, concatenated with other programs, or loaded at any address.
+///
+/// The bytecode of `program` is assembled as if the program is loaded at address 0.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct RelocatableCairoProgram {
+    /// The assembled program.
+    pub program: AssembledCairoProgram,
+    /// The words of the bytecode holding absolute code addresses.
+    pub relocations: Vec<CodeRelocation>,
+    /// The symbols of the program, sorted by offset.
+    pub symbols: Vec<CodeSymbol>,
+}
+impl RelocatableCairoProgram {
+    /// Appends `other` to the end of this program, shifting its offsets accordingly.
+    pub fn append(&mut self, other: RelocatableCairoProgram) {
+        let base = self.program.bytecode.len();
+        let RelocatableCairoProgram { program, relocations, symbols } = other;
+        self.program.bytecode.extend(program.bytecode);
+        self.program.hints.extend(program.hints.into_iter().map(|(pc, hints)| (base + pc, hints)));
+        for relocation in relocations {
+            let relocation = CodeRelocation {
+                word_offset: base + relocation.word_offset,
+                target: base + relocation.target,
+            };
+            self.program.bytecode[relocation.word_offset] = relocation.target.into();
+            self.relocations.push(relocation);
+        }
+        self.symbols.extend(
+            symbols
+                .into_iter()
+                .map(|symbol| CodeSymbol { name: symbol.name, offset: base + symbol.offset }),
+        );
+    }
+
+    /// Returns the assembled program, as loaded at address `base`.
+    pub fn relocated(&self, base: usize) -> AssembledCairoProgram {
+        let mut program = self.program.clone();
+        for relocation in &self.relocations {
+            program.bytecode[relocation.word_offset] = (base + relocation.target).into();
+        }
+        program
+    }
+
+    /// Returns the symbol with the given name.
+    pub fn symbol(&self, name: &str) -> Option<&CodeSymbol> {
+        self.symbols.iter().find(|symbol| symbol.name == name)
+    }
+
+    /// Returns the last symbol at or before `offset`, and the distance of `offset` from it.
+    pub fn symbolize(&self, offset: usize) -> Option<(&CodeSymbol, usize)> {
+        symbolize(&self.symbols, offset)
+    }
+}
+
+/// Returns the last symbol at or before `offset` out of `symbols` sorted by offset, and the
+/// distance of `offset` from it.
+pub fn symbolize(symbols: &[CodeSymbol], offset: usize) -> Option<(&CodeSymbol, usize)> {
+    let idx = symbols.partition_point(|symbol| symbol.offset <= offset).checked_sub(1)?;
+    let symbol = &symbols[idx];
+    Some((symbol, offset - symbol.offset))
+}
+
 impl Instruction {
     pub fn assemble(&self) -> InstructionRepr {
         match &self.body {
@@ -1,9 +1,15 @@
+#[cfg(not(feature = "std"))]
+use alloc::{string::ToString, vec, vec::Vec};
+
 use cairo_lang_test_utils::test;
 use num_bigint::ToBigInt;
 use pretty_assertions::assert_eq;
 
 use super::InstructionRepr;
-use crate::assembler::{ApUpdate, FpUpdate, Op1Addr, Opcode, OpcodeExtension, PcUpdate, Res};
+use crate::assembler::{
+    ApUpdate, AssembledCairoProgram, CodeRelocation, CodeSymbol, FpUpdate, Op1Addr, Opcode,
+    OpcodeExtension, PcUpdate, RelocatableCairoProgram, Res,
+};
 use crate::casm;
 use crate::inline::CasmContext;
 use crate::operand::Register;
@@ -279,3 +285,56 @@ fn test_instruction_with_hint() {
         },
     );
 }
+
+/// Assembles the instructions of `casm` into a relocatable program with a single symbol at its
+/// start.
+fn assemble_relocatable(
+    casm: CasmContext,
+    name: &str,
+    relocations: Vec<CodeRelocation>,
+) -> RelocatableCairoProgram {
+    let bytecode =
+        casm.instructions.iter().flat_map(|instruction| instruction.assemble().encode()).collect();
+    RelocatableCairoProgram {
+        program: AssembledCairoProgram { bytecode, hints: vec![] },
+        relocations,
+        symbols: vec![CodeSymbol { name: name.to_string(), offset: 0 }],
+    }
+}
+
+#[test]
+fn test_relocatable_program_append_and_relocate() {
+    let mut program = assemble_relocatable(
+        casm! {
+            [ap + 0] = 1, ap++;
+            jmp abs 4;
+            ret;
+        },
+        "a",
+        vec![CodeRelocation { word_offset: 3, target: 4 }],
+    );
+    program.append(assemble_relocatable(
+        casm! {
+            jmp abs 2;
+            ret;
+        },
+        "b",
+        vec![CodeRelocation { word_offset: 1, target: 2 }],
+    ));
+    assert_eq!(
+        program.relocations,
+        vec![
+            CodeRelocation { word_offset: 3, target: 4 },
+            CodeRelocation { word_offset: 6, target: 7 }
+        ]
+    );
+    assert_eq!(program.program.bytecode[6], 7.into());
+    assert_eq!(program.symbol("b"), Some(&CodeSymbol { name: "b".to_string(), offset: 5 }));
+    assert_eq!(program.symbolize(6), Some((&program.symbols[1], 1)));
+    assert_eq!(program.symbolize(4), Some((&program.symbols[0], 4)));
+
+    let relocated = program.relocated(100);
+    assert_eq!(relocated.bytecode[3], 104.into());
+    assert_eq!(relocated.bytecode[6], 107.into());
+    assert_eq!(relocated.bytecode[..3], program.program.bytecode[..3]);
+}
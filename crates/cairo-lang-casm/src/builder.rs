@@ -11,7 +11,7 @@ use crate::instructions::{
     AddApInstruction, AssertEqInstruction, CallInstruction, Instruction, InstructionBody,
     JnzInstruction, JumpInstruction, RetInstruction,
 };
-use crate::operand::{BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand};
+use crate::operand::{CellRef, DerefOrImmediate, Operation, Register, ResOperand};
 use crate::{cell_ref, deref_or_immediate};
 
 #[cfg(test)]
@@ -250,16 +250,12 @@ impl CasmBuilder {
                         }
                         CellExpression::Immediate(imm) => imm.clone().into(),
                         CellExpression::BinOp { op, a: other, b } => match op {
-                            CellOperator::Add => ResOperand::BinOp(BinOpOperand {
-                                op: Operation::Add,
-                                a: *other,
-                                b: b.clone(),
-                            }),
-                            CellOperator::Mul => ResOperand::BinOp(BinOpOperand {
-                                op: Operation::Mul,
-                                a: *other,
-                                b: b.clone(),
-                            }),
+                            CellOperator::Add => {
+                                ResOperand::bin_op(Operation::Add, *other, b.clone())
+                            }
+                            CellOperator::Mul => {
+                                ResOperand::bin_op(Operation::Mul, *other, b.clone())
+                            }
                             CellOperator::Sub | CellOperator::Div => {
                                 panic!("hints to non ResOperand references are not supported.")
                             }
@@ -285,22 +281,10 @@ impl CasmBuilder {
             }
             CellExpression::Immediate(imm) => (a, imm.clone().into()),
             CellExpression::BinOp { op, a: other, b } => match op {
-                CellOperator::Add => (
-                    a,
-                    ResOperand::BinOp(BinOpOperand { op: Operation::Add, a: *other, b: b.clone() }),
-                ),
-                CellOperator::Mul => (
-                    a,
-                    ResOperand::BinOp(BinOpOperand { op: Operation::Mul, a: *other, b: b.clone() }),
-                ),
-                CellOperator::Sub => (
-                    other,
-                    ResOperand::BinOp(BinOpOperand { op: Operation::Add, a: *a, b: b.clone() }),
-                ),
-                CellOperator::Div => (
-                    other,
-                    ResOperand::BinOp(BinOpOperand { op: Operation::Mul, a: *a, b: b.clone() }),
-                ),
+                CellOperator::Add => (a, ResOperand::bin_op(Operation::Add, *other, b.clone())),
+                CellOperator::Mul => (a, ResOperand::bin_op(Operation::Mul, *other, b.clone())),
+                CellOperator::Sub => (other, ResOperand::bin_op(Operation::Add, *a, b.clone())),
+                CellOperator::Div => (other, ResOperand::bin_op(Operation::Mul, *a, b.clone())),
             },
         };
         let ap_change = self.main_state.ap_change;
@@ -534,7 +518,7 @@ impl CasmBuilder {
                             (false, false)
                         }
                     }
-                    DerefOrImmediate::Immediate(_) => (true, true),
+                    DerefOrImmediate::Immediate(_) => cell_to_var_flags(a),
                 },
             };
             if function_var {
@@ -599,11 +583,7 @@ impl CasmBuilder {
         let instruction = self.next_instruction(
             InstructionBody::AssertEq(AssertEqInstruction {
                 a: cell,
-                b: ResOperand::BinOp(BinOpOperand {
-                    op: Operation::Add,
-                    a: cell,
-                    b: DerefOrImmediate::Immediate(BigInt::one().into()),
-                }),
+                b: ResOperand::bin_op(Operation::Add, cell, 1),
             }),
             false,
         );
@@ -940,6 +920,38 @@ macro_rules! casm_build_extend {
         let $dst = $builder.duplicate_var($src);
         $crate::casm_build_extend!($builder, $($tok)*)
     };
+    ($builder:expr, repeat ($item:pat in $iter:expr) { $($body:tt)* } $($tok:tt)*) => {
+        for $item in $iter {
+            $crate::casm_build_extend!($builder, $($body)*);
+        }
+        $crate::casm_build_extend!($builder, $($tok)*)
+    };
+    ($builder:expr, let $vars:ident = repeat ($item:pat in $iter:expr) {
+            $($body:tt)*
+        } => $result:ident; $($tok:tt)*) => {
+        let $vars = {
+            let mut __vars = vec![];
+            for $item in $iter {
+                $crate::casm_build_extend!($builder, $($body)*);
+                __vars.push($result);
+            }
+            __vars
+        };
+        $crate::casm_build_extend!($builder, $($tok)*)
+    };
+    ($builder:expr, let $acc:ident = fold ($prev:ident = $init:ident, $item:pat in $iter:expr) {
+            $($body:tt)*
+        } => $next:ident; $($tok:tt)*) => {
+        let $acc = {
+            let mut $prev = $init;
+            for $item in $iter {
+                $crate::casm_build_extend!($builder, $($body)*);
+                $prev = $next;
+            }
+            $prev
+        };
+        $crate::casm_build_extend!($builder, $($tok)*)
+    };
     ($builder:expr, jump $target:ident; $($tok:tt)*) => {
         $builder.jump(core::stringify!($target));
         $crate::casm_build_extend!($builder, $($tok)*)
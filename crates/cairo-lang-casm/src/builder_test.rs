@@ -376,3 +376,118 @@ fn test_future_label() {
         "}
     );
 }
+
+#[test]
+fn test_repeat() {
+    let mut builder = CasmBuilder::default();
+    casm_build_extend! {builder,
+        tempvar ptr;
+        hint AllocSegment {} into {dst: ptr};
+        let values = repeat (i in 0..3) {
+            const value = i * 10;
+            tempvar var = value;
+        } => var;
+        repeat ((i, var) in values.into_iter().enumerate()) {
+            assert var = ptr[i as i16];
+        }
+    };
+    let CasmBuildResult { instructions, branches: [(_, awaiting_relocations)] } =
+        builder.build(["Fallthrough"]);
+    assert!(awaiting_relocations.is_empty());
+    assert_eq!(
+        join(instructions.iter().map(|inst| format!("{inst};\n")), ""),
+        indoc! {"
+            %{ memory[ap + 0] = segments.add() %}
+            [ap + 1] = 0, ap++;
+            [ap + 1] = 10, ap++;
+            [ap + 1] = 20, ap++;
+            [ap + -2] = [[ap + -3] + 0], ap++;
+            [ap + -2] = [[ap + -4] + 1];
+            [ap + -1] = [[ap + -4] + 2];
+        "}
+    );
+}
+
+#[test]
+fn test_fold() {
+    let mut builder = CasmBuilder::default();
+    casm_build_extend! {builder,
+        const one = 1;
+        tempvar initial = one;
+        let total = fold (sum = initial, i in 2..4) {
+            const value = i;
+            tempvar next = sum * value;
+        } => next;
+        tempvar result = total;
+    };
+    let CasmBuildResult { instructions, branches: [(_, awaiting_relocations)] } =
+        builder.build(["Fallthrough"]);
+    assert!(awaiting_relocations.is_empty());
+    assert_eq!(
+        join(instructions.iter().map(|inst| format!("{inst};\n")), ""),
+        indoc! {"
+            [ap + 0] = 1, ap++;
+            [ap + 0] = [ap + -1] * 2, ap++;
+            [ap + 0] = [ap + -1] * 3, ap++;
+            [ap + 0] = [ap + -1], ap++;
+        "}
+    );
+}
+
+#[test]
+fn test_call_ap_based_bin_op() {
+    let mut builder = CasmBuilder::default();
+    let fp_based = builder.add_var(CellExpression::from_res_operand(res!([fp - 4] + 5)));
+    casm_build_extend! {builder,
+        const five = 5;
+        tempvar a = five;
+        let ap_based = a + five;
+        let () = call FUNC;
+    };
+    // The AP based expression is invalidated by the call, while the FP based one is kept.
+    assert!(!builder.main_state.vars.contains_key(&ap_based));
+    assert!(builder.main_state.vars.contains_key(&fp_based));
+    casm_build_extend! {builder,
+        ret;
+        FUNC:
+        tempvar b = ap_based;
+        ret;
+    };
+    let CasmBuildResult { instructions, branches: [] } = builder.build([]);
+    assert_eq!(
+        join(instructions.iter().map(|inst| format!("{inst};\n")), ""),
+        indoc! {"
+            [ap + 0] = 5, ap++;
+            call rel 3;
+            ret;
+            [ap + 0] = [fp + -3] + 5, ap++;
+            ret;
+        "}
+    );
+}
+
+#[test]
+fn test_simplified_assertions() {
+    let mut builder = CasmBuilder::default();
+    let fp_at_minus_3 = builder.add_var(CellExpression::from_res_operand(res!([fp - 3])));
+    let fp_at_minus_4 = builder.add_var(CellExpression::from_res_operand(res!([fp - 4])));
+    casm_build_extend! {builder,
+        const zero = 0;
+        const one = 1;
+        let sum = fp_at_minus_3 + zero;
+        let product = fp_at_minus_3 * one;
+        let diff = fp_at_minus_4 - zero;
+        assert fp_at_minus_4 = sum;
+        assert fp_at_minus_4 = product;
+        assert fp_at_minus_3 = diff;
+    };
+    let CasmBuildResult { instructions, .. } = builder.build(["Fallthrough"]);
+    assert_eq!(
+        join(instructions.iter().map(|inst| format!("{inst};\n")), ""),
+        indoc! {"
+            [fp + -4] = [fp + -3];
+            [fp + -4] = [fp + -3];
+            [fp + -4] = [fp + -3];
+        "}
+    );
+}
@@ -0,0 +1,96 @@
+//! Configurable formatting of CASM.
+//!
+//! The [Display](core::fmt::Display) implementations of the CASM types use the legacy format,
+//! which is kept for compatibility with existing outputs (e.g. golden test files). Use
+//! [FormatWithConfig::display_with_config] for other formats.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String};
+use core::fmt::{Display, Formatter, Write};
+
+use crate::instructions::{Instruction, instruction_offsets};
+
+#[cfg(test)]
+#[path = "format_test.rs"]
+mod test;
+
+/// The configuration of the formatting of CASM.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CasmFormatConfig {
+    /// Whether negative offsets are written as subtractions, e.g. `[fp - 3]`, rather than as
+    /// additions of negative numbers, e.g. `[fp + -3]`.
+    pub canonical_offsets: bool,
+    /// Whether to omit the hints of instructions.
+    pub compact: bool,
+}
+impl CasmFormatConfig {
+    /// The legacy format, used by the [Display] implementations.
+    pub const LEGACY: Self = Self { canonical_offsets: false, compact: false };
+    /// The canonical format.
+    pub const CANONICAL: Self = Self { canonical_offsets: true, compact: false };
+
+    /// Writes `+ offset` or `- offset`, according to the sign of the offset and the configuration.
+    pub(crate) fn write_offset(&self, f: &mut Formatter<'_>, offset: i16) -> core::fmt::Result {
+        if self.canonical_offsets && offset < 0 {
+            write!(f, "- {}", offset.unsigned_abs())
+        } else {
+            write!(f, "+ {offset}")
+        }
+    }
+}
+
+/// A CASM object which can be formatted according to a [CasmFormatConfig].
+pub trait FormatWithConfig {
+    /// Formats the object according to `config`.
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result;
+
+    /// Returns a wrapper of the object, displaying it according to `config`.
+    fn display_with_config(&self, config: CasmFormatConfig) -> WithConfig<'_, Self> {
+        WithConfig { value: self, config }
+    }
+}
+
+/// A wrapper of a CASM object, displaying it according to a [CasmFormatConfig].
+pub struct WithConfig<'a, T: ?Sized> {
+    value: &'a T,
+    config: CasmFormatConfig,
+}
+impl<T: FormatWithConfig + ?Sized> Display for WithConfig<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.value.fmt_with_config(f, &self.config)
+    }
+}
+
+/// Formats `instructions` as a column-aligned listing, where each instruction is prefixed by its
+/// pc, and its hints (unless `config.compact` is set) are listed before it, e.g.:
+/// ```text
+/// 0: [ap + 0] = [fp - 3], ap++;
+///    %{ memory[ap + 0] = segments.add() %}
+/// 1: [ap + 0] = 5, ap++;
+/// 3: ret;
+/// ```
+pub fn format_listing(instructions: &[Instruction], config: &CasmFormatConfig) -> String {
+    let body_config = CasmFormatConfig { compact: true, ..*config };
+    let mut pcs = instruction_offsets(instructions);
+    // The last offset is the end of the code, and not of an instruction.
+    pcs.pop();
+    let width = pcs.last().map_or(1, |pc| format!("{pc}").len());
+
+    let mut listing = String::new();
+    for (pc, instruction) in pcs.into_iter().zip(instructions) {
+        if !config.compact {
+            for hint in &instruction.hints {
+                for line in format!("{}", hint.display_with_config(*config)).lines() {
+                    writeln!(listing, "{:width$}  {line}", "").unwrap();
+                }
+            }
+        }
+        writeln!(listing, "{pc:>width$}: {};", instruction.display_with_config(body_config))
+            .unwrap();
+    }
+    listing
+}
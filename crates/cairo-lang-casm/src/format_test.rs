@@ -0,0 +1,139 @@
+#[cfg(not(feature = "std"))]
+use alloc::{string::ToString, vec};
+
+use cairo_lang_test_utils::test;
+use indoc::indoc;
+
+use super::{CasmFormatConfig, FormatWithConfig, format_listing};
+use crate::cell_ref;
+use crate::hints::{CoreHint, Hint};
+use crate::instructions::{
+    AssertEqInstruction, Instruction, InstructionBody, JnzInstruction, RetInstruction,
+};
+use crate::operand::{BinOpOperand, DerefOrImmediate, Operation, ResOperand};
+
+#[test]
+fn test_canonical_offsets() {
+    let config = CasmFormatConfig::CANONICAL;
+    assert_eq!(cell_ref!([fp - 3]).display_with_config(config).to_string(), "[fp - 3]");
+    assert_eq!(cell_ref!([ap + 5]).display_with_config(config).to_string(), "[ap + 5]");
+    assert_eq!(
+        ResOperand::DoubleDeref(cell_ref!([ap - 1]), -2).display_with_config(config).to_string(),
+        "[[ap - 1] - 2]"
+    );
+    let bin_op = BinOpOperand {
+        op: Operation::Add,
+        a: cell_ref!([fp + -4]),
+        b: DerefOrImmediate::Deref(cell_ref!([ap + -32768])),
+    };
+    assert_eq!(bin_op.display_with_config(config).to_string(), "[fp - 4] + [ap - 32768]");
+    // The legacy format is kept as the default display.
+    assert_eq!(bin_op.to_string(), "[fp + -4] + [ap + -32768]");
+}
+
+#[test]
+fn test_compact_instruction() {
+    let instruction = Instruction {
+        body: InstructionBody::Jnz(JnzInstruction {
+            jump_offset: DerefOrImmediate::from(-4),
+            condition: cell_ref!([fp - 5]),
+        }),
+        inc_ap: false,
+        hints: vec![CoreHint::AllocSegment { dst: cell_ref!([ap + 0]) }.into()],
+    };
+    assert_eq!(
+        instruction.display_with_config(CasmFormatConfig::CANONICAL).to_string(),
+        indoc! {"
+            %{ memory[ap + 0] = segments.add() %}
+            jmp rel -4 if [fp - 5] != 0"
+        }
+    );
+    assert_eq!(
+        instruction
+            .display_with_config(CasmFormatConfig { canonical_offsets: true, compact: true })
+            .to_string(),
+        "jmp rel -4 if [fp - 5] != 0"
+    );
+}
+
+#[test]
+fn test_canonical_hint_operands() {
+    let hint: Hint = CoreHint::TestLessThan {
+        lhs: ResOperand::Deref(cell_ref!([fp - 4])),
+        rhs: ResOperand::DoubleDeref(cell_ref!([ap - 1]), -2),
+        dst: cell_ref!([ap + 0]),
+    }
+    .into();
+    assert_eq!(
+        hint.display_with_config(CasmFormatConfig::CANONICAL).to_string(),
+        "%{ memory[ap + 0] = memory[fp - 4] < memory[memory[ap - 1] - 2] %}"
+    );
+    assert_eq!(
+        hint.display_with_config(CasmFormatConfig::LEGACY).to_string(),
+        "%{ memory[ap + 0] = memory[fp + -4] < memory[memory[ap + -1] + -2] %}"
+    );
+}
+
+#[test]
+fn test_listing() {
+    let mut instructions = vec![];
+    for i in 0..5 {
+        instructions.push(Instruction::new(
+            InstructionBody::AssertEq(AssertEqInstruction {
+                a: cell_ref!([ap + 0]),
+                b: ResOperand::Deref(cell_ref!([fp - 3])),
+            }),
+            true,
+        ));
+        instructions.push(Instruction {
+            body: InstructionBody::AssertEq(AssertEqInstruction {
+                a: cell_ref!([ap + 0]),
+                b: ResOperand::from(i),
+            }),
+            inc_ap: true,
+            hints: vec![CoreHint::AllocSegment { dst: cell_ref!([ap + 0]) }.into()],
+        });
+    }
+    instructions.push(Instruction::new(InstructionBody::Ret(RetInstruction {}), false));
+
+    assert_eq!(
+        format_listing(&instructions[6..], &CasmFormatConfig::CANONICAL),
+        indoc! {"
+             0: [ap + 0] = [fp - 3], ap++;
+                %{ memory[ap + 0] = segments.add() %}
+             1: [ap + 0] = 3, ap++;
+             3: [ap + 0] = [fp - 3], ap++;
+                %{ memory[ap + 0] = segments.add() %}
+             4: [ap + 0] = 4, ap++;
+             6: ret;
+        "}
+    );
+    assert_eq!(
+        format_listing(&instructions[6..], &CasmFormatConfig::LEGACY),
+        indoc! {"
+             0: [ap + 0] = [fp + -3], ap++;
+                %{ memory[ap + 0] = segments.add() %}
+             1: [ap + 0] = 3, ap++;
+             3: [ap + 0] = [fp + -3], ap++;
+                %{ memory[ap + 0] = segments.add() %}
+             4: [ap + 0] = 4, ap++;
+             6: ret;
+        "}
+    );
+    assert_eq!(
+        format_listing(&instructions, &CasmFormatConfig { canonical_offsets: true, compact: true }),
+        indoc! {"
+             0: [ap + 0] = [fp - 3], ap++;
+             1: [ap + 0] = 0, ap++;
+             3: [ap + 0] = [fp - 3], ap++;
+             4: [ap + 0] = 1, ap++;
+             6: [ap + 0] = [fp - 3], ap++;
+             7: [ap + 0] = 2, ap++;
+             9: [ap + 0] = [fp - 3], ap++;
+            10: [ap + 0] = 3, ap++;
+            12: [ap + 0] = [fp - 3], ap++;
+            13: [ap + 0] = 4, ap++;
+            15: ret;
+        "}
+    );
+}
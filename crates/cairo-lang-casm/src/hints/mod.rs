@@ -8,6 +8,7 @@ use core::fmt::{Display, Formatter};
 use cairo_lang_utils::bigint::BigIntAsHex;
 use indoc::formatdoc;
 
+use crate::format::{CasmFormatConfig, FormatWithConfig};
 use crate::operand::{CellRef, DerefOrImmediate, ResOperand};
 
 #[cfg(test)]
@@ -39,6 +40,29 @@ impl Hint {
     }
 }
 
+impl FormatWithConfig for Hint {
+    /// Formats the hint as `%{ <pythonic hint> %}`. With canonical offsets, the offsets of the
+    /// operands in the pythonic hint code are canonical as well, e.g. `memory[fp - 3]`.
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
+        let mut hint_str = self.get_pythonic_hint();
+        if config.canonical_offsets {
+            // The operands are written into the pythonic code using their legacy format, so the
+            // only additions of negative numbers in the code are their offsets.
+            hint_str = hint_str.replace("+ -", "- ");
+        }
+        // Skip leading and trailing space if hint starts with `\n`.
+        if hint_str.starts_with('\n') {
+            write!(f, "%{{{hint_str}%}}")
+        } else {
+            write!(f, "%{{ {hint_str} %}}")
+        }
+    }
+}
+
 impl From<CoreHint> for Hint {
     fn from(value: CoreHint) -> Self {
         Hint::Core(value.into())
@@ -22,6 +22,12 @@ macro_rules! casm {
 #[macro_export]
 macro_rules! casm_extend {
     ($ctx:ident,) => {};
+    ($ctx:ident, repeat ($item:pat in $iter:expr) { $($body:tt)* } $($tok:tt)*) => {
+        for $item in $iter {
+            $crate::casm_extend!($ctx, $($body)*);
+        }
+        $crate::casm_extend!($ctx, $($tok)*)
+    };
     ($ctx:ident, $dst:tt = $a:tt $(+ $b0:tt)? $(* $b1:tt)? $(,$ap:ident++)? ; $($tok:tt)*) => {
         let body = $crate::instructions::InstructionBody::AssertEq(
             $crate::instructions::AssertEqInstruction {
@@ -76,3 +76,23 @@ fn test_assert() {
             ret"}
     );
 }
+
+#[test]
+fn test_repeat() {
+    let ctx = casm! {
+        repeat (i in 0i16..3) {
+            [fp + i] = i, ap++;
+        }
+        ret;
+    };
+
+    let code = join(ctx.instructions.iter().map(Instruction::to_string), "\n");
+    assert_eq!(
+        code,
+        indoc! {"
+            [fp + 0] = 0, ap++
+            [fp + 1] = 1, ap++
+            [fp + 2] = 2, ap++
+            ret"}
+    );
+}
@@ -1,8 +1,11 @@
 #[cfg(not(feature = "std"))]
 use alloc::{vec, vec::Vec};
-use core::fmt::Display;
+use core::fmt::{Display, Formatter};
 
-use crate::hints::{Hint, PythonicHint};
+use cairo_lang_utils::bigint::BigIntAsHex;
+
+use crate::format::{CasmFormatConfig, FormatWithConfig};
+use crate::hints::Hint;
 use crate::operand::{CellRef, DerefOrImmediate, ResOperand};
 
 #[cfg(test)]
@@ -33,18 +36,49 @@ impl InstructionBody {
             InstructionBody::Blake2sCompress(insn) => insn.op_size(),
         }
     }
+
+    /// Returns the immediate value encoded in the word following the instruction word, if any.
+    pub fn immediate(&self) -> Option<&BigIntAsHex> {
+        match self {
+            InstructionBody::AddAp(insn) => res_operand_immediate(&insn.operand),
+            InstructionBody::AssertEq(insn) | InstructionBody::QM31AssertEq(insn) => {
+                res_operand_immediate(&insn.b)
+            }
+            InstructionBody::Call(insn) => try_extract_immediate(&insn.target),
+            InstructionBody::Jump(insn) => try_extract_immediate(&insn.target),
+            InstructionBody::Jnz(insn) => try_extract_immediate(&insn.jump_offset),
+            InstructionBody::Ret(_) | InstructionBody::Blake2sCompress(_) => None,
+        }
+    }
+
+    /// Returns whether the instruction carries an immediate value, encoded in the word following
+    /// the instruction word.
+    pub fn has_immediate(&self) -> bool {
+        self.immediate().is_some()
+    }
 }
 impl Display for InstructionBody {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for InstructionBody {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
         match self {
-            InstructionBody::AddAp(insn) => write!(f, "{insn}",),
-            InstructionBody::AssertEq(insn) => write!(f, "{insn}",),
-            InstructionBody::QM31AssertEq(insn) => write!(f, "{{QM31}} {insn}",),
-            InstructionBody::Call(insn) => write!(f, "{insn}",),
-            InstructionBody::Jnz(insn) => write!(f, "{insn}",),
-            InstructionBody::Jump(insn) => write!(f, "{insn}",),
-            InstructionBody::Ret(insn) => write!(f, "{insn}",),
-            InstructionBody::Blake2sCompress(insn) => write!(f, "{insn}",),
+            InstructionBody::AddAp(insn) => insn.fmt_with_config(f, config),
+            InstructionBody::AssertEq(insn) => insn.fmt_with_config(f, config),
+            InstructionBody::QM31AssertEq(insn) => {
+                write!(f, "{{QM31}} {}", insn.display_with_config(*config))
+            }
+            InstructionBody::Call(insn) => insn.fmt_with_config(f, config),
+            InstructionBody::Jnz(insn) => insn.fmt_with_config(f, config),
+            InstructionBody::Jump(insn) => insn.fmt_with_config(f, config),
+            InstructionBody::Ret(insn) => write!(f, "{insn}"),
+            InstructionBody::Blake2sCompress(insn) => insn.fmt_with_config(f, config),
         }
     }
 }
@@ -60,21 +94,71 @@ impl Instruction {
     pub fn new(body: InstructionBody, inc_ap: bool) -> Self {
         Self { body, inc_ap, hints: vec![] }
     }
+
+    /// Returns the number of felts the instruction is encoded into - the instruction word, and the
+    /// immediate value if there is one.
+    pub fn encoded_size(&self) -> usize {
+        self.body.op_size()
+    }
+
+    /// Returns whether the instruction carries an immediate value, see
+    /// [InstructionBody::has_immediate].
+    pub fn has_immediate(&self) -> bool {
+        self.body.has_immediate()
+    }
+
+    /// Returns whether the instruction has hints, which run before it is executed.
+    pub fn has_hints(&self) -> bool {
+        !self.hints.is_empty()
+    }
+}
+
+/// Returns the code offset of each of `instructions`, followed by the total size of their code.
+pub fn instruction_offsets<'a>(
+    instructions: impl IntoIterator<Item = &'a Instruction>,
+) -> Vec<usize> {
+    let mut offsets = vec![0];
+    for instruction in instructions {
+        offsets.push(offsets.last().unwrap() + instruction.encoded_size());
+    }
+    offsets
+}
+
+/// Returns the immediate value of `operand`, if it is one.
+fn try_extract_immediate(operand: &DerefOrImmediate) -> Option<&BigIntAsHex> {
+    match operand {
+        DerefOrImmediate::Immediate(imm) => Some(imm),
+        DerefOrImmediate::Deref(_) => None,
+    }
+}
+
+/// Returns the immediate value encoded with an instruction with the res operand `operand`, if any.
+fn res_operand_immediate(operand: &ResOperand) -> Option<&BigIntAsHex> {
+    match operand {
+        ResOperand::Immediate(imm) => Some(imm),
+        ResOperand::BinOp(op) => try_extract_immediate(&op.b),
+        ResOperand::Deref(_) | ResOperand::DoubleDeref(_, _) => None,
+    }
 }
 
 impl Display for Instruction {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        for hint in &self.hints {
-            let hint_str = hint.get_pythonic_hint();
-            // Skip leading and trailing space if hint starts with `\n`.
-            if hint_str.starts_with('\n') {
-                writeln!(f, "%{{{hint_str}%}}")
-            } else {
-                writeln!(f, "%{{ {hint_str} %}}")
-            }?
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for Instruction {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
+        if !config.compact {
+            for hint in &self.hints {
+                writeln!(f, "{}", hint.display_with_config(*config))?;
+            }
         }
 
-        write!(f, "{}", self.body)?;
+        self.body.fmt_with_config(f, config)?;
         if self.inc_ap {
             write!(f, ", ap++")?
         };
@@ -89,8 +173,22 @@ pub struct CallInstruction {
     pub relative: bool,
 }
 impl Display for CallInstruction {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "call {} {}", if self.relative { "rel" } else { "abs" }, self.target,)
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for CallInstruction {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
+        write!(
+            f,
+            "call {} {}",
+            if self.relative { "rel" } else { "abs" },
+            self.target.display_with_config(*config)
+        )
     }
 }
 impl CallInstruction {
@@ -117,8 +215,22 @@ impl JumpInstruction {
     }
 }
 impl Display for JumpInstruction {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "jmp {} {}", if self.relative { "rel" } else { "abs" }, self.target,)
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for JumpInstruction {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
+        write!(
+            f,
+            "jmp {} {}",
+            if self.relative { "rel" } else { "abs" },
+            self.target.display_with_config(*config)
+        )
     }
 }
 
@@ -137,8 +249,22 @@ impl JnzInstruction {
     }
 }
 impl Display for JnzInstruction {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "jmp rel {} if {} != 0", self.jump_offset, self.condition)
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for JnzInstruction {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
+        write!(
+            f,
+            "jmp rel {} if {} != 0",
+            self.jump_offset.display_with_config(*config),
+            self.condition.display_with_config(*config)
+        )
     }
 }
 
@@ -168,8 +294,22 @@ impl AssertEqInstruction {
     }
 }
 impl Display for AssertEqInstruction {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{} = {}", self.a, self.b)
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for AssertEqInstruction {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
+        write!(
+            f,
+            "{} = {}",
+            self.a.display_with_config(*config),
+            self.b.display_with_config(*config)
+        )
     }
 }
 
@@ -177,7 +317,7 @@ impl Display for AssertEqInstruction {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct RetInstruction {}
 impl Display for RetInstruction {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "ret")
     }
 }
@@ -199,8 +339,17 @@ impl AddApInstruction {
     }
 }
 impl Display for AddApInstruction {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "ap += {}", self.operand)
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for AddApInstruction {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
+        write!(f, "ap += {}", self.operand.display_with_config(*config))
     }
 }
 
@@ -218,11 +367,23 @@ impl Blake2sCompressInstruction {
     }
 }
 impl Display for Blake2sCompressInstruction {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for Blake2sCompressInstruction {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
         write!(
             f,
             "blake2s[state={}, message={}, byte_count={}, finalize={}] => [ap + 0]",
-            self.state, self.message, self.byte_count, self.finalize
+            self.state.display_with_config(*config),
+            self.message.display_with_config(*config),
+            self.byte_count.display_with_config(*config),
+            self.finalize
         )
     }
 }
@@ -3,14 +3,15 @@ use alloc::{string::ToString, vec};
 
 use cairo_lang_test_utils::test;
 use indoc::indoc;
+use itertools::Itertools;
 
-use crate::cell_ref;
 use crate::hints::CoreHint;
 use crate::instructions::{
     AddApInstruction, AssertEqInstruction, CallInstruction, Instruction, InstructionBody,
-    JnzInstruction, JumpInstruction, RetInstruction,
+    JnzInstruction, JumpInstruction, RetInstruction, instruction_offsets,
 };
 use crate::operand::{DerefOrImmediate, ResOperand};
+use crate::{casm, cell_ref};
 
 #[test]
 fn test_jump_format() {
@@ -101,3 +102,58 @@ fn test_instruction_with_hint() {
         }
     );
 }
+
+#[test]
+fn test_instruction_metadata() {
+    let mut instructions = casm! {
+        %{ memory[ap + 0] = segments.add() %}
+        [ap + 0] = [fp + -3], ap++;
+        [ap + 0] = 5, ap++;
+        [ap + 0] = [fp + -3] * 7, ap++;
+        [ap + 0] = [fp + -3] + [ap + -1], ap++;
+        [ap + 0] = [[fp + -3] + 2], ap++;
+        ap += 3;
+        ap += [fp + -4];
+        jmp rel 4 if [fp + -3] != 0;
+        jmp rel [ap + -1] if [fp + -3] != 0;
+        jmp abs 2;
+        call rel 3;
+        call abs [fp + -5];
+        ret;
+    }
+    .instructions;
+    let expected_immediates = [
+        None,
+        Some(5),
+        Some(7),
+        None,
+        None,
+        Some(3),
+        None,
+        Some(4),
+        None,
+        Some(2),
+        Some(3),
+        None,
+        None,
+    ];
+    for (instruction, expected_immediate) in instructions.iter().zip_eq(expected_immediates) {
+        assert_eq!(
+            instruction.body.immediate().map(|imm| imm.value.clone()),
+            expected_immediate.map(Into::into),
+            "{instruction}"
+        );
+        assert_eq!(instruction.has_immediate(), expected_immediate.is_some(), "{instruction}");
+        assert_eq!(
+            instruction.encoded_size(),
+            instruction.assemble().encode().len(),
+            "{instruction}"
+        );
+    }
+    assert!(instructions[0].has_hints());
+    assert!(!instructions[1].has_hints());
+
+    instructions.truncate(4);
+    assert_eq!(instruction_offsets(&instructions), vec![0, 1, 3, 5, 6]);
+    assert_eq!(instruction_offsets(&[]), vec![0]);
+}
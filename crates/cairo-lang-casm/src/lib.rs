@@ -11,7 +11,9 @@ pub mod assembler;
 pub mod builder;
 pub mod cell_expression;
 pub mod encoder;
+pub mod format;
 pub mod hints;
 pub mod inline;
 pub mod instructions;
 pub mod operand;
+pub mod schedule;
@@ -1,6 +1,10 @@
-use core::fmt::Display;
+use core::fmt::{Display, Formatter};
 
 use cairo_lang_utils::bigint::BigIntAsHex;
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+
+use crate::format::{CasmFormatConfig, FormatWithConfig};
 
 #[cfg(test)]
 #[path = "operand_test.rs"]
@@ -20,7 +24,7 @@ pub enum Register {
     FP,
 }
 impl Display for Register {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             Register::AP => write!(f, "ap"),
             Register::FP => write!(f, "fp"),
@@ -47,12 +51,68 @@ pub enum ResOperand {
     BinOp(BinOpOperand),
 }
 impl Display for ResOperand {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for ResOperand {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
         match self {
-            ResOperand::Deref(operand) => write!(f, "{operand}"),
-            ResOperand::DoubleDeref(operand, offset) => write!(f, "[{operand} + {offset}]"),
+            ResOperand::Deref(operand) => operand.fmt_with_config(f, config),
+            ResOperand::DoubleDeref(operand, offset) => {
+                write!(f, "[{} ", operand.display_with_config(*config))?;
+                config.write_offset(f, *offset)?;
+                write!(f, "]")
+            }
             ResOperand::Immediate(operand) => write!(f, "{}", operand.value),
-            ResOperand::BinOp(operand) => write!(f, "{operand}"),
+            ResOperand::BinOp(operand) => operand.fmt_with_config(f, config),
+        }
+    }
+}
+impl ResOperand {
+    /// Returns the operand `a op b`, simplified:
+    /// - An operation on two immediates is folded into an immediate (not reduced modulo the prime).
+    /// - An immediate first operand is swapped with the second, as the operations are commutative.
+    /// - `x + 0` and `x * 1` are simplified to `x`.
+    pub fn bin_op(
+        op: Operation,
+        a: impl Into<DerefOrImmediate>,
+        b: impl Into<DerefOrImmediate>,
+    ) -> Self {
+        match (a.into(), b.into()) {
+            (DerefOrImmediate::Immediate(a), DerefOrImmediate::Immediate(b)) => {
+                ResOperand::Immediate(op.apply(&a.value, &b.value).into())
+            }
+            (DerefOrImmediate::Deref(a), b)
+            | (b @ DerefOrImmediate::Immediate(_), DerefOrImmediate::Deref(a)) => {
+                BinOpOperand { op, a, b }.simplified()
+            }
+        }
+    }
+
+    /// Returns the operand with its binary operation simplified, see [BinOpOperand::simplified].
+    pub fn simplified(self) -> Self {
+        match self {
+            ResOperand::BinOp(operand) => operand.simplified(),
+            _ => self,
+        }
+    }
+
+    /// Returns the operand referring to the cell `offset` cells after the cell this operand refers
+    /// to, merging `offset` into the inner offset: `[reg + x]` becomes `[reg + (x + offset)]`, and
+    /// `[[cell] + x]` becomes `[[cell] + (x + offset)]`.
+    /// Returns `None` if the operand does not refer to a cell, or if the merged offset overflows.
+    pub fn checked_add_offset(&self, offset: i16) -> Option<Self> {
+        match self {
+            ResOperand::Deref(cell) => Some(ResOperand::Deref(cell.checked_add_offset(offset)?)),
+            ResOperand::DoubleDeref(cell, inner) => {
+                Some(ResOperand::DoubleDeref(*cell, inner.checked_add(offset)?))
+            }
+            ResOperand::Immediate(_) | ResOperand::BinOp(_) => None,
         }
     }
 }
@@ -83,9 +143,27 @@ pub struct CellRef {
     pub register: Register,
     pub offset: i16,
 }
+impl CellRef {
+    /// Returns the cell `offset` cells after this cell, or `None` if the resulting offset
+    /// overflows.
+    pub fn checked_add_offset(self, offset: i16) -> Option<Self> {
+        Some(CellRef { register: self.register, offset: self.offset.checked_add(offset)? })
+    }
+}
 impl Display for CellRef {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "[{} + {}]", self.register, self.offset)
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for CellRef {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
+        write!(f, "[{} ", self.register)?;
+        config.write_offset(f, self.offset)?;
+        write!(f, "]")
     }
 }
 
@@ -103,9 +181,18 @@ pub enum DerefOrImmediate {
     Immediate(BigIntAsHex),
 }
 impl Display for DerefOrImmediate {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for DerefOrImmediate {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
         match self {
-            DerefOrImmediate::Deref(operand) => write!(f, "{operand}"),
+            DerefOrImmediate::Deref(operand) => operand.fmt_with_config(f, config),
             DerefOrImmediate::Immediate(operand) => write!(f, "{}", operand.value),
         }
     }
@@ -134,8 +221,17 @@ pub enum Operation {
     #[cfg_attr(feature = "parity-scale-codec", codec(index = 1))]
     Mul,
 }
+impl Operation {
+    /// Returns the result of applying the operation on `a` and `b`.
+    pub fn apply(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        match self {
+            Operation::Add => a + b,
+            Operation::Mul => a * b,
+        }
+    }
+}
 impl Display for Operation {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             Operation::Add => write!(f, "+"),
             Operation::Mul => write!(f, "*"),
@@ -155,8 +251,38 @@ pub struct BinOpOperand {
     pub a: CellRef,
     pub b: DerefOrImmediate,
 }
+impl BinOpOperand {
+    /// Returns the operand simplified to `a` if it is of the form `a + 0` or `a * 1`, and the
+    /// operand itself otherwise.
+    pub fn simplified(self) -> ResOperand {
+        match (&self.op, &self.b) {
+            (Operation::Add, DerefOrImmediate::Immediate(imm)) if imm.value.is_zero() => {
+                ResOperand::Deref(self.a)
+            }
+            (Operation::Mul, DerefOrImmediate::Immediate(imm)) if imm.value.is_one() => {
+                ResOperand::Deref(self.a)
+            }
+            _ => ResOperand::BinOp(self),
+        }
+    }
+}
 impl Display for BinOpOperand {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{} {} {}", self.a, self.op, self.b)
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with_config(f, &CasmFormatConfig::LEGACY)
+    }
+}
+impl FormatWithConfig for BinOpOperand {
+    fn fmt_with_config(
+        &self,
+        f: &mut Formatter<'_>,
+        config: &CasmFormatConfig,
+    ) -> core::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.a.display_with_config(*config),
+            self.op,
+            self.b.display_with_config(*config)
+        )
     }
 }
@@ -33,3 +33,41 @@ fn test_bin_op_format() {
     };
     assert_eq!(bin_op.to_string(), "[fp + -3] * 1400")
 }
+
+#[test]
+fn test_bin_op_folds_immediates() {
+    assert_eq!(ResOperand::bin_op(Operation::Add, 3, 4), ResOperand::from(7));
+    assert_eq!(ResOperand::bin_op(Operation::Mul, -3, 4), ResOperand::from(-12));
+}
+
+#[test]
+fn test_bin_op_simplification() {
+    let cell = cell_ref!([fp - 3]);
+    assert_eq!(ResOperand::bin_op(Operation::Add, cell, 0), ResOperand::Deref(cell));
+    assert_eq!(ResOperand::bin_op(Operation::Add, 0, cell), ResOperand::Deref(cell));
+    assert_eq!(ResOperand::bin_op(Operation::Mul, cell, 1), ResOperand::Deref(cell));
+    assert_eq!(ResOperand::bin_op(Operation::Mul, 1, cell), ResOperand::Deref(cell));
+    assert_eq!(ResOperand::bin_op(Operation::Mul, 5, cell).to_string(), "[fp + -3] * 5");
+    assert_eq!(
+        ResOperand::bin_op(Operation::Add, cell, cell_ref!([ap + 1])).to_string(),
+        "[fp + -3] + [ap + 1]"
+    );
+    let bin_op = BinOpOperand { op: Operation::Mul, a: cell, b: DerefOrImmediate::from(0) };
+    assert_eq!(ResOperand::BinOp(bin_op.clone()).simplified(), ResOperand::BinOp(bin_op));
+}
+
+#[test]
+fn test_checked_add_offset() {
+    assert_eq!(cell_ref!([ap + 5]).checked_add_offset(-7), Some(cell_ref!([ap - 2])));
+    assert_eq!(cell_ref!([fp + i16::MAX]).checked_add_offset(1), None);
+    assert_eq!(
+        ResOperand::Deref(cell_ref!([fp - 3])).checked_add_offset(2),
+        Some(ResOperand::Deref(cell_ref!([fp - 1])))
+    );
+    assert_eq!(
+        ResOperand::DoubleDeref(cell_ref!([ap + 1]), 2).checked_add_offset(3),
+        Some(ResOperand::DoubleDeref(cell_ref!([ap + 1]), 5))
+    );
+    assert_eq!(ResOperand::DoubleDeref(cell_ref!([ap + 1]), i16::MIN).checked_add_offset(-1), None);
+    assert_eq!(ResOperand::from(3).checked_add_offset(1), None);
+}
@@ -0,0 +1,419 @@
+//! Scheduling of `ap` updates in CASM code.
+//!
+//! Coalesces `ap += k` instructions into the `ap++` flags of the instructions following them,
+//! reducing the number of executed instructions. The `ap` based offsets of every instruction
+//! executed while `ap` differs from its original value are rebased, so that all instructions access
+//! the exact same memory cells as before.
+//!
+//! To give an `ap += k` more instructions to spread over, `assert_eq` instructions preceding it
+//! within its basic block are moved after it, if they are independent of the instructions they are
+//! moved across - that is, if no memory cell may be accessed by both.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use crate::instructions::{
+    AddApInstruction, AssertEqInstruction, Instruction, InstructionBody, JumpInstruction,
+    instruction_offsets,
+};
+use crate::operand::{BinOpOperand, CellRef, DerefOrImmediate, Register, ResOperand};
+
+#[cfg(test)]
+#[path = "schedule_test.rs"]
+mod test;
+
+/// A constraint on the scheduling of an instruction.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Constraint {
+    /// The instruction may be rebased, coalesced and moved within its basic block.
+    #[default]
+    Free,
+    /// The instruction starts a basic block, e.g. it is a jump target, so `ap` must have its
+    /// original value before it.
+    BlockStart,
+    /// The instruction must be kept unchanged, with `ap` having its original value before it, and
+    /// no instruction may be moved across it - e.g. it has an immediate relative to its own
+    /// offset.
+    Fixed,
+}
+
+/// Instructions with their `ap` updates scheduled.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledInstructions {
+    /// The scheduled instructions.
+    pub instructions: Vec<Instruction>,
+    /// The index in `instructions` at which the code of each original instruction starts,
+    /// followed by the number of scheduled instructions. The indices are non-decreasing, and the
+    /// index of a block start is the start of the scheduled code of its block.
+    pub new_indices: Vec<usize>,
+}
+
+/// Coalesces the `ap` updates of `instructions` within each of its basic blocks.
+///
+/// Every `ap += k` with an immediate `k` is delayed, and its increments are spread over the
+/// `ap++` flags of the following hint-free `assert_eq` instructions that lack one. Any remainder is
+/// flushed as a single `ap += r` before the end of the basic block - that is, before a jump, call,
+/// return, hinted instruction or jump target - so `ap` is unchanged at the boundaries of blocks.
+/// Relative jumps within the code are then adjusted to the new instruction offsets.
+///
+/// Assumes `instructions` is a complete code section - no jumps into it from outside of it other
+/// than to its start. Returns the instructions unchanged if any of its jump targets are unknown
+/// (absolute or dereferenced jumps), or do not fall on an instruction boundary.
+pub fn coalesce_ap_updates(instructions: &[Instruction]) -> Vec<Instruction> {
+    let Some(jump_targets) = relative_jump_targets(instructions) else {
+        return instructions.to_vec();
+    };
+    let mut constraints = vec![Constraint::Free; instructions.len()];
+    for target in jump_targets.iter().flatten() {
+        if let Some(constraint) = constraints.get_mut(*target) {
+            *constraint = Constraint::BlockStart;
+        }
+    }
+    let ScheduledInstructions { instructions: mut result, new_indices } =
+        schedule_ap_updates(instructions, &constraints);
+
+    let new_offsets = instruction_offsets(&result);
+    for (idx, target) in jump_targets.into_iter().enumerate() {
+        let Some(target) = target else { continue };
+        let new_idx = new_indices[idx];
+        let jump_offset =
+            BigInt::from(new_offsets[new_indices[target]]) - BigInt::from(new_offsets[new_idx]);
+        match &mut result[new_idx].body {
+            InstructionBody::Jump(JumpInstruction { target, .. }) => {
+                *target = DerefOrImmediate::from(jump_offset)
+            }
+            InstructionBody::Jnz(jnz) => jnz.jump_offset = DerefOrImmediate::from(jump_offset),
+            InstructionBody::Call(call) => call.target = DerefOrImmediate::from(jump_offset),
+            _ => unreachable!("Only jumps and calls have jump targets."),
+        }
+    }
+    result
+}
+
+/// Schedules the `ap` updates of `instructions` as [coalesce_ap_updates] does, given the
+/// constraint of each instruction, without adjusting any jumps.
+///
+/// Jumps, calls and returns always end basic blocks, and the caller is responsible for marking the
+/// jump targets as block starts, and for fixing the jumps using the returned indices.
+pub fn schedule_ap_updates(
+    instructions: &[Instruction],
+    constraints: &[Constraint],
+) -> ScheduledInstructions {
+    assert_eq!(instructions.len(), constraints.len(), "Wrong number of constraints.");
+    let (reordered, positions) = sink_asserts(instructions, constraints);
+    // The index in `reordered` at which the code of each original instruction starts.
+    let mut starts = positions;
+    starts.push(instructions.len());
+    for idx in (0..instructions.len()).rev() {
+        starts[idx] = starts[idx].min(starts[idx + 1]);
+    }
+    // Instructions are only moved within their basic blocks, so the block starts and the fixed
+    // instructions keep their indices.
+    let ScheduledInstructions { instructions, new_indices } =
+        spread_ap_updates(&reordered, constraints);
+    ScheduledInstructions {
+        instructions,
+        new_indices: starts.into_iter().map(|start| new_indices[start]).collect(),
+    }
+}
+
+/// Moves hint-free `assert_eq` instructions without an `ap++` flag from before every `ap += k`
+/// to right after it, so they can absorb its increments. Returns the reordered instructions, with
+/// their `ap` based offsets rebased, and the index of each original instruction in them.
+///
+/// Instructions are only moved within the segments of consecutive hint-free `assert_eq` and
+/// `ap += k` instructions of a basic block.
+fn sink_asserts(
+    instructions: &[Instruction],
+    constraints: &[Constraint],
+) -> (Vec<Instruction>, Vec<usize>) {
+    let is_movable = |idx: usize| {
+        constraints[idx] != Constraint::Fixed
+            && instructions[idx].hints.is_empty()
+            && (matches!(instructions[idx].body, InstructionBody::AssertEq(_))
+                || ap_update(&instructions[idx]).is_some())
+    };
+    let mut reordered = Vec::with_capacity(instructions.len());
+    let mut positions = vec![0; instructions.len()];
+    let mut start = 0;
+    while start < instructions.len() {
+        let end = if is_movable(start) {
+            (start + 1..instructions.len())
+                .find(|idx| !is_movable(*idx) || constraints[*idx] != Constraint::Free)
+                .unwrap_or(instructions.len())
+        } else {
+            start + 1
+        };
+        for (idx, instruction) in sink_asserts_in_segment(&instructions[start..end]) {
+            positions[start + idx] = reordered.len();
+            reordered.push(instruction);
+        }
+        start = end;
+    }
+    (reordered, positions)
+}
+
+/// Sinks the asserts of a segment of hint-free `assert_eq` and `ap += k` instructions, as
+/// described in [sink_asserts]. Returns the instructions of the segment in their new order, along
+/// with their original indices in the segment.
+fn sink_asserts_in_segment(segment: &[Instruction]) -> Vec<(usize, Instruction)> {
+    // The `ap` change from the start of the segment before each instruction.
+    let mut ap_changes = Vec::with_capacity(segment.len());
+    let mut ap_change: i32 = 0;
+    for instruction in segment {
+        ap_changes.push(ap_change);
+        ap_change += ap_update(instruction).map_or(i32::from(instruction.inc_ap), i32::from);
+    }
+    let accesses = segment
+        .iter()
+        .zip(&ap_changes)
+        .map(|(instruction, ap_change)| memory_accesses(instruction, *ap_change))
+        .collect::<Vec<_>>();
+    let is_absorbing = |idx: usize| {
+        !segment[idx].inc_ap && matches!(segment[idx].body, InstructionBody::AssertEq(_))
+    };
+
+    // The asserts moved after each `ap += k`, rebased to their new `ap`.
+    let mut sunk: Vec<Vec<(usize, Instruction)>> = vec![vec![]; segment.len()];
+    let mut is_sunk = vec![false; segment.len()];
+    let ap_update_indices =
+        (0..segment.len()).filter(|idx| ap_update(&segment[*idx]).is_some()).collect::<Vec<_>>();
+    for (i, &update_idx) in ap_update_indices.iter().enumerate() {
+        let prev_update = if i == 0 { None } else { Some(ap_update_indices[i - 1]) };
+        let next_update = ap_update_indices.get(i + 1).copied().unwrap_or(segment.len());
+        let k = ap_update(&segment[update_idx]).unwrap();
+        let n_absorbing_after =
+            (update_idx + 1..next_update).filter(|idx| is_absorbing(*idx)).count();
+        let mut n_missing = usize::try_from(k).unwrap().saturating_sub(n_absorbing_after);
+        // The `ap` change after the `ap += k`, where the moved asserts are placed.
+        let new_ap_change = ap_changes[update_idx] + i32::from(k);
+        // The instructions staying between the candidates and the `ap += k`.
+        let mut stayed: Vec<usize> = vec![];
+        for idx in (prev_update.map_or(0, |idx| idx + 1)..update_idx).rev() {
+            let shift = i16::try_from(ap_changes[idx] - new_ap_change).ok();
+            let rebased = match (&segment[idx].body, shift) {
+                (InstructionBody::AssertEq(assert_eq), Some(shift))
+                    if n_missing > 0
+                        && is_absorbing(idx)
+                        && stayed
+                            .iter()
+                            .all(|other| !conflicts(&accesses[idx], &accesses[*other])) =>
+                {
+                    rebase_assert_eq(assert_eq, shift)
+                }
+                _ => None,
+            };
+            match rebased {
+                Some(rebased) => {
+                    n_missing -= 1;
+                    is_sunk[idx] = true;
+                    let instruction = Instruction::new(InstructionBody::AssertEq(rebased), false);
+                    sunk[update_idx].push((idx, instruction));
+                }
+                None => stayed.push(idx),
+            }
+        }
+        // Keep the original order of the moved asserts.
+        sunk[update_idx].reverse();
+    }
+
+    let mut result = Vec::with_capacity(segment.len());
+    for (idx, instruction) in segment.iter().enumerate() {
+        if !is_sunk[idx] {
+            result.push((idx, instruction.clone()));
+        }
+        result.append(&mut sunk[idx]);
+    }
+    result
+}
+
+/// Returns the increment of `instruction` if it is a hint-free `ap += k` with an immediate `k`
+/// which is non-negative and fits an offset.
+fn ap_update(instruction: &Instruction) -> Option<i16> {
+    if !instruction.hints.is_empty() {
+        return None;
+    }
+    let InstructionBody::AddAp(AddApInstruction { operand: ResOperand::Immediate(k) }) =
+        &instruction.body
+    else {
+        return None;
+    };
+    k.value.to_i16().filter(|k| *k >= 0)
+}
+
+/// A memory cell that may be accessed by an instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MemoryAccess {
+    /// An `ap` based cell, by its offset from the `ap` at the start of the segment.
+    Ap(i32),
+    /// An `fp` based cell, by its offset from `fp`.
+    Fp(i16),
+    /// A cell through a pointer, which may be any cell.
+    Any,
+}
+
+/// Returns the memory cells that may be accessed by `instruction`, executed at an `ap` change of
+/// `ap_change` from the start of its segment.
+fn memory_accesses(instruction: &Instruction, ap_change: i32) -> Vec<MemoryAccess> {
+    let access = |cell: &CellRef| match cell.register {
+        Register::AP => MemoryAccess::Ap(ap_change + i32::from(cell.offset)),
+        Register::FP => MemoryAccess::Fp(cell.offset),
+    };
+    let InstructionBody::AssertEq(assert_eq) = &instruction.body else {
+        return vec![];
+    };
+    let mut accesses = vec![access(&assert_eq.a)];
+    match &assert_eq.b {
+        ResOperand::Deref(cell) => accesses.push(access(cell)),
+        ResOperand::DoubleDeref(cell, _) => accesses.extend([access(cell), MemoryAccess::Any]),
+        ResOperand::Immediate(_) => {}
+        ResOperand::BinOp(bin_op) => {
+            accesses.push(access(&bin_op.a));
+            if let DerefOrImmediate::Deref(cell) = &bin_op.b {
+                accesses.push(access(cell));
+            }
+        }
+    }
+    accesses
+}
+
+/// Returns whether two instructions with the given memory accesses may access the same cell, in
+/// which case they may depend on each other and are not reordered.
+///
+/// The distance between `ap` and `fp` is unknown within a segment, so an `ap` based cell and an
+/// `fp` based cell may be the same one.
+fn conflicts(accesses: &[MemoryAccess], other_accesses: &[MemoryAccess]) -> bool {
+    accesses.iter().any(|access| {
+        other_accesses.iter().any(|other| match (access, other) {
+            (MemoryAccess::Ap(a), MemoryAccess::Ap(b)) => a == b,
+            (MemoryAccess::Fp(a), MemoryAccess::Fp(b)) => a == b,
+            _ => true,
+        })
+    })
+}
+
+/// Spreads the `ap` updates over the `ap++` flags of the following asserts, as described in
+/// [coalesce_ap_updates], without adjusting any jumps.
+fn spread_ap_updates(
+    instructions: &[Instruction],
+    constraints: &[Constraint],
+) -> ScheduledInstructions {
+    let mut result = Vec::with_capacity(instructions.len());
+    // The index in `result` of the position of each original instruction.
+    let mut new_indices = Vec::with_capacity(instructions.len() + 1);
+    // The amount by which the updated `ap` lags behind the original `ap`.
+    let mut pending: i16 = 0;
+    for (idx, instruction) in instructions.iter().enumerate() {
+        if constraints[idx] != Constraint::Free {
+            flush(&mut result, &mut pending);
+        }
+        if constraints[idx] != Constraint::Fixed && instruction.hints.is_empty() {
+            if let Some(new_pending) = ap_update(instruction).and_then(|k| pending.checked_add(k)) {
+                new_indices.push(result.len());
+                pending = new_pending;
+                continue;
+            }
+            if let InstructionBody::AssertEq(assert_eq) = &instruction.body
+                && let Some(rebased) = rebase_assert_eq(assert_eq, pending)
+            {
+                new_indices.push(result.len());
+                let lag = pending + i16::from(instruction.inc_ap);
+                let inc_ap = lag > 0;
+                pending = lag - i16::from(inc_ap);
+                result.push(Instruction::new(InstructionBody::AssertEq(rebased), inc_ap));
+                continue;
+            }
+        }
+        flush(&mut result, &mut pending);
+        new_indices.push(result.len());
+        result.push(instruction.clone());
+    }
+    flush(&mut result, &mut pending);
+    new_indices.push(result.len());
+    ScheduledInstructions { instructions: result, new_indices }
+}
+
+/// Returns the index of the instruction targeted by each relative jump or call in
+/// `instructions`, or `None` if any of the targets is unknown or not on an instruction boundary.
+/// A target of `instructions.len()` stands for the end of the code.
+fn relative_jump_targets(instructions: &[Instruction]) -> Option<Vec<Option<usize>>> {
+    let offsets = instruction_offsets(instructions);
+    instructions
+        .iter()
+        .zip(&offsets)
+        .map(|(instruction, offset)| {
+            let jump_offset = match &instruction.body {
+                InstructionBody::Jump(jump) => {
+                    relative_immediate(&jump.target, jump.relative).ok_or(())?
+                }
+                InstructionBody::Jnz(jnz) => relative_immediate(&jnz.jump_offset, true).ok_or(())?,
+                InstructionBody::Call(call) => {
+                    relative_immediate(&call.target, call.relative).ok_or(())?
+                }
+                _ => return Ok(None),
+            };
+            let target = (BigInt::from(*offset) + jump_offset).to_usize().ok_or(())?;
+            offsets.binary_search(&target).map(Some).map_err(|_| ())
+        })
+        .collect::<Result<_, ()>>()
+        .ok()
+}
+
+/// Returns the immediate jump offset of a relative jump target.
+fn relative_immediate(target: &DerefOrImmediate, relative: bool) -> Option<&BigInt> {
+    match target {
+        DerefOrImmediate::Immediate(value) if relative => Some(&value.value),
+        _ => None,
+    }
+}
+
+/// Adds an `ap += pending` instruction if `ap` lags behind, and resets the lag.
+fn flush(result: &mut Vec<Instruction>, pending: &mut i16) {
+    if *pending > 0 {
+        result.push(Instruction::new(
+            InstructionBody::AddAp(AddApInstruction {
+                operand: ResOperand::Immediate(BigInt::from(*pending).into()),
+            }),
+            false,
+        ));
+    }
+    *pending = 0;
+}
+
+/// Rebases the `ap` based cells of `assert_eq` for an `ap` lagging by `pending` (or leading by
+/// `-pending` if negative), or returns `None` if an offset overflows.
+fn rebase_assert_eq(assert_eq: &AssertEqInstruction, pending: i16) -> Option<AssertEqInstruction> {
+    Some(AssertEqInstruction {
+        a: rebase_cell(assert_eq.a, pending)?,
+        b: match &assert_eq.b {
+            ResOperand::Deref(cell) => ResOperand::Deref(rebase_cell(*cell, pending)?),
+            ResOperand::DoubleDeref(cell, offset) => {
+                ResOperand::DoubleDeref(rebase_cell(*cell, pending)?, *offset)
+            }
+            ResOperand::Immediate(value) => ResOperand::Immediate(value.clone()),
+            ResOperand::BinOp(bin_op) => ResOperand::BinOp(BinOpOperand {
+                op: bin_op.op.clone(),
+                a: rebase_cell(bin_op.a, pending)?,
+                b: match &bin_op.b {
+                    DerefOrImmediate::Deref(cell) => {
+                        DerefOrImmediate::Deref(rebase_cell(*cell, pending)?)
+                    }
+                    DerefOrImmediate::Immediate(value) => {
+                        DerefOrImmediate::Immediate(value.clone())
+                    }
+                },
+            }),
+        },
+    })
+}
+
+/// Rebases an `ap` based cell for an `ap` lagging by `pending`.
+fn rebase_cell(cell: CellRef, pending: i16) -> Option<CellRef> {
+    match cell.register {
+        Register::AP => cell.checked_add_offset(pending),
+        Register::FP => Some(cell),
+    }
+}
@@ -0,0 +1,201 @@
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+
+use indoc::indoc;
+use itertools::join;
+use pretty_assertions::assert_eq;
+
+use super::{Constraint, ScheduledInstructions, coalesce_ap_updates, schedule_ap_updates};
+use crate::casm;
+use crate::inline::CasmContext;
+use crate::instructions::Instruction;
+
+/// Returns the code of the given instructions.
+fn code(instructions: &[Instruction]) -> String {
+    join(instructions.iter().map(|inst| inst.to_string()), "\n")
+}
+
+/// Coalesces the `ap` updates of `ctx` and returns the resulting code.
+fn coalesce(ctx: &CasmContext) -> String {
+    code(&coalesce_ap_updates(&ctx.instructions))
+}
+
+#[test]
+fn test_ap_update_spread_over_following_asserts() {
+    let ctx = casm! {
+        ap += 2;
+        [fp + 1] = [ap - 1];
+        [fp + 2] = [ap - 2] + [fp + 3];
+        [fp + 4] = 5;
+        ret;
+    };
+    assert_eq!(
+        coalesce(&ctx),
+        indoc! {"
+            [fp + 1] = [ap + 1], ap++
+            [fp + 2] = [ap + -1] + [fp + 3], ap++
+            [fp + 4] = 5
+            ret"}
+    );
+}
+
+#[test]
+fn test_consecutive_ap_updates_merged() {
+    let ctx = casm! {
+        ap += 1;
+        [ap + 0] = 5, ap++;
+        ap += 2;
+        ret;
+    };
+    assert_eq!(
+        coalesce(&ctx),
+        indoc! {"
+            [ap + 1] = 5, ap++
+            ap += 3
+            ret"}
+    );
+}
+
+#[test]
+fn test_relative_jumps_adjusted() {
+    let loop_offset = -4;
+    let ctx = casm! {
+        jmp rel 6;
+        ap += 1;
+        [fp + 1] = [ap - 1];
+        [fp + 2] = [fp + 3];
+        jmp rel loop_offset if [fp + 2] != 0;
+        ret;
+    };
+    assert_eq!(
+        coalesce(&ctx),
+        indoc! {"
+            jmp rel 4
+            [fp + 1] = [ap + 0], ap++
+            [fp + 2] = [fp + 3]
+            jmp rel -2 if [fp + 2] != 0
+            ret"}
+    );
+}
+
+#[test]
+fn test_ap_update_flushed_at_jump_target() {
+    let ctx = casm! {
+        ap += 2;
+        [fp + 1] = [ap - 1];
+        jmp rel 3 if [fp + 1] != 0;
+        [fp + 2] = [ap - 1];
+        ret;
+    };
+    assert_eq!(
+        coalesce(&ctx),
+        indoc! {"
+            [fp + 1] = [ap + 1], ap++
+            ap += 1
+            jmp rel 3 if [fp + 1] != 0
+            [fp + 2] = [ap + -1]
+            ret"}
+    );
+}
+
+#[test]
+fn test_hinted_instruction_not_rebased() {
+    let ctx = casm! {
+        ap += 1;
+        %{ memory[ap + 0] = segments.add() %}
+        [fp + 1] = [ap + 0];
+        ret;
+    };
+    assert_eq!(coalesce(&ctx), code(&ctx.instructions));
+}
+
+#[test]
+fn test_unknown_jump_targets_unchanged() {
+    let ctx = casm! {
+        ap += 1;
+        [fp + 1] = [ap - 1];
+        jmp abs 0;
+    };
+    assert_eq!(coalesce(&ctx), code(&ctx.instructions));
+}
+
+#[test]
+fn test_assert_moved_after_ap_update() {
+    let ctx = casm! {
+        [fp + 1] = [fp + 2];
+        ap += 1;
+        [ap + 0] = 5, ap++;
+        ret;
+    };
+    assert_eq!(
+        coalesce(&ctx),
+        indoc! {"
+            [fp + 1] = [fp + 2], ap++
+            [ap + 0] = 5, ap++
+            ret"}
+    );
+}
+
+#[test]
+fn test_independent_assert_moved_across_assert() {
+    let ctx = casm! {
+        [ap + 1] = 7;
+        [ap + 0] = 5, ap++;
+        ap += 1;
+        [ap + 0] = 3, ap++;
+        ret;
+    };
+    assert_eq!(
+        coalesce(&ctx),
+        indoc! {"
+            [ap + 0] = 5, ap++
+            [ap + 0] = 7, ap++
+            [ap + 0] = 3, ap++
+            ret"}
+    );
+}
+
+#[test]
+fn test_dependent_assert_not_moved() {
+    let ctx = casm! {
+        [ap + 1] = [ap + 0];
+        [ap + 0] = 5, ap++;
+        ap += 1;
+        [ap + 0] = 3, ap++;
+        ret;
+    };
+    assert_eq!(
+        coalesce(&ctx),
+        indoc! {"
+            [ap + 1] = [ap + 0]
+            [ap + 0] = 5, ap++
+            [ap + 1] = 3, ap++
+            ap += 1
+            ret"}
+    );
+}
+
+#[test]
+fn test_fixed_instruction_kept() {
+    let ctx = casm! {
+        [fp + 1] = [fp + 2];
+        ap += 2;
+        [ap + 0] = [ap + -1] + 5, ap++;
+        [fp + 3] = [ap + -1];
+        ret;
+    };
+    let constraints =
+        [Constraint::Free, Constraint::Free, Constraint::Fixed, Constraint::Free, Constraint::Free];
+    let ScheduledInstructions { instructions, new_indices } =
+        schedule_ap_updates(&ctx.instructions, &constraints);
+    assert_eq!(
+        code(&instructions),
+        indoc! {"
+            [fp + 1] = [fp + 2], ap++
+            ap += 1
+            [ap + 0] = [ap + -1] + 5, ap++
+            [fp + 3] = [ap + -1]
+            ret"}
+    );
+    assert_eq!(new_indices, [0, 0, 2, 3, 4, 5]);
+}
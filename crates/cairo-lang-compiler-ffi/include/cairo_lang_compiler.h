@@ -0,0 +1,40 @@
+/*
+ * A C ABI for embedding the Cairo compiler.
+ *
+ * All the functions get NUL terminated UTF-8 strings. `options_json` may be NULL for the default
+ * options, or a JSON object with any of the fields:
+ *   "replace_ids": bool, "allow_warnings": bool, "avoid_inlining": bool,
+ *   "contract_path": string, "starknet": bool.
+ *
+ * The returned strings are JSON objects of the form:
+ *   {"success": bool, "output": ..., "diagnostics": [{"severity", "code", "message"}], "error": ...}
+ * and must be released with `cairo_string_free`.
+ */
+#ifndef CAIRO_LANG_COMPILER_H
+#define CAIRO_LANG_COMPILER_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Compiles the Cairo project at `path` into a Sierra program (the `output` string). */
+char *cairo_compile_project(const char *path, const char *options_json);
+
+/* Compiles a Starknet contract of the Cairo project at `path` into a contract class (the `output`
+ * object). */
+char *cairo_compile_contract(const char *path, const char *options_json);
+
+/* Collects the diagnostics of the Cairo project at `path`, without generating code. */
+char *cairo_project_diagnostics(const char *path, const char *options_json);
+
+/* Releases a string returned by one of the functions above. NULL is ignored. */
+void cairo_string_free(char *string);
+
+/* Returns the version of the compiler. Must not be released. */
+const char *cairo_compiler_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CAIRO_LANG_COMPILER_H */
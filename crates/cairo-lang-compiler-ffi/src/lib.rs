@@ -0,0 +1,262 @@
+//! A C ABI for embedding the Cairo compiler in non-Rust toolchains.
+//!
+//! All the functions get NUL terminated UTF-8 strings, and return a NUL terminated JSON string of a
+//! [CompileResult], which must be released with [cairo_string_free]. The options are given as a
+//! JSON string of [CompileOptions], and may be null for the default options.
+//! See `include/cairo_lang_compiler.h` for the C declarations.
+
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_compiler::project::setup_project;
+use cairo_lang_compiler::{CompilerConfig, compile_cairo_project_at_path};
+use cairo_lang_diagnostics::FormattedDiagnosticEntry;
+use cairo_lang_lowering::optimizations::config::Optimizations;
+use cairo_lang_lowering::utils::InliningStrategy;
+use cairo_lang_starknet::compile::compile_path;
+use cairo_lang_starknet::starknet_plugin_suite;
+use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod test;
+
+/// The options of the compilation, given as a JSON object. Missing fields get their default value.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CompileOptions {
+    /// Replaces the Sierra ids with human-readable ones.
+    pub replace_ids: bool,
+    /// Allows the compilation to succeed with warnings.
+    pub allow_warnings: bool,
+    /// Inlines only the functions annotated with `#[inline(always)]`.
+    pub avoid_inlining: bool,
+    /// The full path of the contract to compile. Required if the project has multiple contracts.
+    pub contract_path: Option<String>,
+    /// Adds the Starknet plugins when collecting the diagnostics of a project.
+    pub starknet: bool,
+}
+
+impl CompileOptions {
+    fn inlining_strategy(&self) -> InliningStrategy {
+        if self.avoid_inlining { InliningStrategy::Avoid } else { InliningStrategy::Default }
+    }
+}
+
+/// The result of a call, returned as a JSON object.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CompileResult {
+    /// Whether the call succeeded.
+    pub success: bool,
+    /// The output of a successful compilation - the Sierra program text of a project, or the
+    /// contract class of a contract.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<serde_json::Value>,
+    /// The diagnostics reported during the call.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The reason of a failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A diagnostic reported by the compiler.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// The severity of the diagnostic - `error` or `warning`.
+    pub severity: String,
+    /// The error code of the diagnostic, if it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// The formatted message of the diagnostic, including its location.
+    pub message: String,
+}
+
+impl From<FormattedDiagnosticEntry> for Diagnostic {
+    fn from(entry: FormattedDiagnosticEntry) -> Self {
+        Self {
+            severity: entry.severity().to_string(),
+            code: entry.error_code().map(|code| code.to_string()),
+            message: entry.message().to_string(),
+        }
+    }
+}
+
+/// Runs `f` with a diagnostics reporter collecting the diagnostics into the result.
+fn collect_diagnostics(
+    options: &CompileOptions,
+    f: impl FnOnce(DiagnosticsReporter<'_>) -> Result<Option<serde_json::Value>>,
+) -> CompileResult {
+    let mut diagnostics = vec![];
+    let mut reporter = DiagnosticsReporter::callback(|entry| {
+        if !entry.is_empty() {
+            diagnostics.push(entry.into());
+        }
+    });
+    if options.allow_warnings {
+        reporter = reporter.allow_warnings();
+    }
+    match f(reporter) {
+        Ok(output) => CompileResult { success: true, output, diagnostics, error: None },
+        Err(err) => CompileResult {
+            success: false,
+            output: None,
+            diagnostics,
+            error: Some(err.to_string()),
+        },
+    }
+}
+
+/// Compiles the Cairo project at `path` - a `.cairo` file or a directory with a
+/// `cairo_project.toml` file - into a Sierra program.
+pub fn compile_project(path: &Path, options: &CompileOptions) -> CompileResult {
+    collect_diagnostics(options, |diagnostics_reporter| {
+        let program = compile_cairo_project_at_path(
+            path,
+            CompilerConfig {
+                replace_ids: options.replace_ids,
+                diagnostics_reporter,
+                ..CompilerConfig::default()
+            },
+            options.inlining_strategy(),
+        )?;
+        Ok(Some(program.to_string().into()))
+    })
+}
+
+/// Compiles a Starknet contract of the Cairo project at `path` into a contract class.
+pub fn compile_contract(path: &Path, options: &CompileOptions) -> CompileResult {
+    collect_diagnostics(options, |diagnostics_reporter| {
+        let contract = compile_path(
+            path,
+            options.contract_path.as_deref(),
+            CompilerConfig {
+                replace_ids: options.replace_ids,
+                diagnostics_reporter,
+                ..CompilerConfig::default()
+            },
+            options.inlining_strategy(),
+        )?;
+        contract
+            .extract_sierra_program(false)?
+            .validate_version_compatible(ListSelector::default())?;
+        Ok(Some(serde_json::to_value(&contract).with_context(|| "Serialization failed.")?))
+    })
+}
+
+/// Collects the diagnostics of the Cairo project at `path`, without generating code.
+pub fn project_diagnostics(path: &Path, options: &CompileOptions) -> CompileResult {
+    collect_diagnostics(options, |diagnostics_reporter| {
+        let mut builder = RootDatabase::builder();
+        builder
+            .with_optimizations(Optimizations::enabled_with_default_movable_functions(
+                options.inlining_strategy(),
+            ))
+            .detect_corelib();
+        if options.starknet {
+            builder.with_default_plugin_suite(starknet_plugin_suite());
+        }
+        let mut db = builder.build()?;
+        let main_crate_inputs = setup_project(&mut db, path)?;
+        diagnostics_reporter.with_crates(&main_crate_inputs).ensure(&db)?;
+        Ok(None)
+    })
+}
+
+/// Parses the C arguments, runs `f` and returns its result as a JSON C string.
+///
+/// # Safety
+/// `path` and `options_json` must each be null or a valid pointer to a NUL terminated string.
+unsafe fn call_with_json_result(
+    path: *const c_char,
+    options_json: *const c_char,
+    f: fn(&Path, &CompileOptions) -> CompileResult,
+) -> *mut c_char {
+    let parse_args = || -> Result<(String, CompileOptions)> {
+        anyhow::ensure!(!path.is_null(), "The project path is null.");
+        // SAFETY: Guaranteed by the caller.
+        let path = unsafe { CStr::from_ptr(path) }.to_str()?.to_string();
+        let options = if options_json.is_null() {
+            CompileOptions::default()
+        } else {
+            // SAFETY: Guaranteed by the caller.
+            let options_json = unsafe { CStr::from_ptr(options_json) }.to_str()?;
+            serde_json::from_str(options_json).with_context(|| "Invalid compile options.")?
+        };
+        Ok((path, options))
+    };
+    let result = match parse_args() {
+        Ok((path, options)) => catch_unwind(AssertUnwindSafe(|| f(Path::new(&path), &options)))
+            .unwrap_or_else(|_| CompileResult {
+                error: Some("The compiler panicked.".into()),
+                ..CompileResult::default()
+            }),
+        Err(err) => CompileResult { error: Some(format!("{err:#}")), ..CompileResult::default() },
+    };
+    // Serialized JSON escapes NUL characters, so it never contains one.
+    let json = serde_json::to_string(&result).expect("Failed to serialize the result.");
+    CString::new(json).expect("JSON contains a NUL character.").into_raw()
+}
+
+/// Compiles the Cairo project at `path` into a Sierra program. See [compile_project].
+///
+/// # Safety
+/// `path` must be a valid pointer to a NUL terminated string, and `options_json` must be null or a
+/// valid pointer to a NUL terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cairo_compile_project(
+    path: *const c_char,
+    options_json: *const c_char,
+) -> *mut c_char {
+    // SAFETY: Guaranteed by the caller.
+    unsafe { call_with_json_result(path, options_json, compile_project) }
+}
+
+/// Compiles a Starknet contract of the Cairo project at `path`. See [compile_contract].
+///
+/// # Safety
+/// `path` must be a valid pointer to a NUL terminated string, and `options_json` must be null or a
+/// valid pointer to a NUL terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cairo_compile_contract(
+    path: *const c_char,
+    options_json: *const c_char,
+) -> *mut c_char {
+    // SAFETY: Guaranteed by the caller.
+    unsafe { call_with_json_result(path, options_json, compile_contract) }
+}
+
+/// Collects the diagnostics of the Cairo project at `path`. See [project_diagnostics].
+///
+/// # Safety
+/// `path` must be a valid pointer to a NUL terminated string, and `options_json` must be null or a
+/// valid pointer to a NUL terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cairo_project_diagnostics(
+    path: *const c_char,
+    options_json: *const c_char,
+) -> *mut c_char {
+    // SAFETY: Guaranteed by the caller.
+    unsafe { call_with_json_result(path, options_json, project_diagnostics) }
+}
+
+/// Releases a string returned by one of the functions of this library. Null is ignored.
+///
+/// # Safety
+/// `string` must be null or a string returned by this library that was not released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cairo_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: Guaranteed by the caller.
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Returns the version of the compiler, as a static NUL terminated string.
+#[unsafe(no_mangle)]
+pub extern "C" fn cairo_compiler_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
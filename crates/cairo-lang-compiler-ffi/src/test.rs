@@ -0,0 +1,103 @@
+use std::ffi::{CStr, CString, c_char};
+use std::path::PathBuf;
+use std::ptr::null;
+
+use serde_json::{Value, json};
+
+use super::{
+    cairo_compile_contract, cairo_compile_project, cairo_compiler_version,
+    cairo_project_diagnostics, cairo_string_free,
+};
+
+/// Calls an FFI function the way a C caller would, and returns the parsed JSON result.
+fn call(
+    f: unsafe extern "C" fn(*const c_char, *const c_char) -> *mut c_char,
+    path: &str,
+    options_json: Option<&str>,
+) -> Value {
+    let path = CString::new(path).unwrap();
+    let options_json = options_json.map(|options| CString::new(options).unwrap());
+    let options_ptr = options_json.as_ref().map_or(null(), |options| options.as_ptr());
+    // SAFETY: The arguments are valid NUL terminated strings, and the result is released once.
+    unsafe {
+        let result = f(path.as_ptr(), options_ptr);
+        let json = serde_json::from_str(CStr::from_ptr(result).to_str().unwrap()).unwrap();
+        cairo_string_free(result);
+        json
+    }
+}
+
+fn test_data_path(name: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(name).to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_compile_project() {
+    let result = call(
+        cairo_compile_project,
+        &test_data_path("../../examples/fib.cairo"),
+        Some(r#"{"replace_ids": true}"#),
+    );
+    assert_eq!(result["success"], true, "{result}");
+    assert_eq!(result["diagnostics"], json!([]));
+    assert!(result["output"].as_str().unwrap().contains("fib::fib::fib"));
+}
+
+#[test]
+fn test_compile_project_with_diagnostics() {
+    let result = call(cairo_compile_project, &test_data_path("test_data/diagnostics.cairo"), None);
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"], "Compilation failed.");
+    let diagnostics = result["diagnostics"].as_array().unwrap();
+    assert_eq!(
+        diagnostics.iter().map(|diagnostic| &diagnostic["severity"]).collect::<Vec<_>>(),
+        ["error", "warning"]
+    );
+    assert_eq!(diagnostics[0]["code"], "E0006");
+    assert!(diagnostics[0]["message"].as_str().unwrap().contains("undefined_variable"));
+}
+
+#[test]
+fn test_project_diagnostics() {
+    let path = test_data_path("test_data/hello_starknet.cairo");
+    let result = call(cairo_project_diagnostics, &path, None);
+    assert_eq!(result["success"], false);
+    assert!(!result["diagnostics"].as_array().unwrap().is_empty());
+
+    let result = call(cairo_project_diagnostics, &path, Some(r#"{"starknet": true}"#));
+    assert_eq!(result, json!({"success": true, "diagnostics": []}));
+}
+
+#[test]
+fn test_compile_contract() {
+    let result =
+        call(cairo_compile_contract, &test_data_path("test_data/hello_starknet.cairo"), None);
+    assert_eq!(result["success"], true, "{result}");
+    let entry_points = &result["output"]["entry_points_by_type"]["EXTERNAL"];
+    assert_eq!(entry_points.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let result = call(cairo_compile_project, "fib.cairo", Some(r#"{"unknown": true}"#));
+    assert_eq!(result["success"], false);
+    assert!(result["error"].as_str().unwrap().starts_with("Invalid compile options."));
+
+    // SAFETY: Null arguments are allowed.
+    let result = unsafe { cairo_compile_project(null(), null()) };
+    // SAFETY: The result is a valid NUL terminated string, released once.
+    let json: Value =
+        serde_json::from_str(unsafe { CStr::from_ptr(result) }.to_str().unwrap()).unwrap();
+    unsafe { cairo_string_free(result) };
+    assert_eq!(
+        json,
+        json!({"success": false, "diagnostics": [], "error": "The project path is null."})
+    );
+}
+
+#[test]
+fn test_compiler_version() {
+    // SAFETY: The version is a static NUL terminated string.
+    let version = unsafe { CStr::from_ptr(cairo_compiler_version()) };
+    assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+}
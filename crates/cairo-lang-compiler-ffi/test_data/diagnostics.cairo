@@ -0,0 +1,4 @@
+fn main() -> felt252 {
+    let unused = 1;
+    undefined_variable
+}
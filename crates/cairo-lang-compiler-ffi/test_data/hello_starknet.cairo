@@ -0,0 +1,12 @@
+#[starknet::contract]
+mod hello_starknet {
+    #[storage]
+    struct Storage {
+        balance: felt252,
+    }
+
+    #[external(v0)]
+    fn increase_balance(ref self: ContractState, amount: felt252) {
+        self.balance.write(self.balance.read() + amount);
+    }
+}
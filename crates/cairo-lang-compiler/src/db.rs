@@ -1,14 +1,21 @@
+use std::sync::Arc;
+
 use anyhow::{Result, anyhow, bail};
 use cairo_lang_defs::db::{init_defs_group, init_external_files};
 use cairo_lang_diagnostics::Maybe;
+use cairo_lang_filesystem::backend::FsBackendRef;
 use cairo_lang_filesystem::cfg::CfgSet;
-use cairo_lang_filesystem::db::{CORELIB_VERSION, FilesGroup, init_dev_corelib, init_files_group};
-use cairo_lang_filesystem::detect::detect_corelib;
-use cairo_lang_filesystem::flag::{Flag, FlagsGroup};
-use cairo_lang_filesystem::ids::{CrateId, FlagLongId};
-use cairo_lang_lowering::db::init_lowering_group;
+use cairo_lang_filesystem::db::{
+    CORELIB_VERSION, FilesGroup, init_dev_corelib, init_dev_corelib_from_root, init_files_group,
+    set_fs_backend,
+};
+use cairo_lang_filesystem::detect::detect_corelib_in;
+use cairo_lang_filesystem::flag::{CompilerFlags, FlagsGroup};
+use cairo_lang_filesystem::ids::{CrateId, DirectoryInput};
+use cairo_lang_lowering::db::{init_lowering_group, set_lowering_plugins};
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
 use cairo_lang_lowering::optimizations::config::Optimizations;
+use cairo_lang_lowering::plugin::LoweringPlugin;
 use cairo_lang_lowering::utils::InliningStrategy;
 use cairo_lang_project::ProjectConfig;
 use cairo_lang_runnable_utils::builder::RunnableBuilder;
@@ -58,7 +65,7 @@ fn estimate_code_size(
         }
     };
     let casm = builder.casm_program();
-    let total_size = casm.instructions.iter().map(|inst| inst.body.op_size()).sum::<usize>();
+    let total_size = casm.instructions.iter().map(|inst| inst.encoded_size()).sum::<usize>();
 
     // The size of a dummy function is currently 3 felts. call (2) + ret (1).
     const DUMMY_FUNCTION_SIZE: usize = 3;
@@ -117,12 +124,13 @@ impl Default for RootDatabase {
 pub struct RootDatabaseBuilder {
     default_plugin_suite: PluginSuite,
     detect_corelib: bool,
-    auto_withdraw_gas: bool,
-    panic_backtrace: bool,
-    unsafe_panic: bool,
+    in_memory_corelib: Option<DirectoryInput>,
+    flags: CompilerFlags,
     project_config: Option<Box<ProjectConfig>>,
     cfg_set: Option<CfgSet>,
     optimizations: Optimizations,
+    lowering_plugins: Vec<Arc<dyn LoweringPlugin>>,
+    fs_backend: Option<FsBackendRef>,
 }
 
 impl RootDatabaseBuilder {
@@ -130,14 +138,15 @@ impl RootDatabaseBuilder {
         Self {
             default_plugin_suite: get_default_plugin_suite(),
             detect_corelib: false,
-            auto_withdraw_gas: true,
-            panic_backtrace: false,
-            unsafe_panic: false,
+            in_memory_corelib: None,
+            flags: CompilerFlags::default(),
             project_config: None,
             cfg_set: None,
             optimizations: Optimizations::enabled_with_default_movable_functions(
                 InliningStrategy::Default,
             ),
+            lowering_plugins: vec![],
+            fs_backend: None,
         }
     }
 
@@ -156,11 +165,36 @@ impl RootDatabaseBuilder {
         self
     }
 
+    /// Adds a lowering plugin. Lowering plugins of the same stage are applied in the order they
+    /// were added.
+    pub fn with_lowering_plugin(&mut self, plugin: Arc<dyn LoweringPlugin>) -> &mut Self {
+        self.lowering_plugins.push(plugin);
+        self
+    }
+
     pub fn detect_corelib(&mut self) -> &mut Self {
         self.detect_corelib = true;
         self
     }
 
+    /// Uses a corelib from memory instead of one on disk, so tools bundling the corelib need not
+    /// unpack it. Takes precedence over [Self::detect_corelib].
+    /// See [cairo_lang_filesystem::db::init_dev_corelib_from_memory] for the format of `files`.
+    pub fn with_in_memory_corelib(
+        &mut self,
+        files: impl IntoIterator<Item = (impl AsRef<str>, impl Into<Arc<str>>)>,
+    ) -> &mut Self {
+        self.in_memory_corelib = Some(DirectoryInput::from_virtual_files(files));
+        self
+    }
+
+    /// Reads the on-disk files, including the detected corelib, from `backend` instead of the OS
+    /// filesystem. See [cairo_lang_filesystem::db::set_fs_backend].
+    pub fn with_fs_backend(&mut self, backend: FsBackendRef) -> &mut Self {
+        self.fs_backend = Some(backend);
+        self
+    }
+
     pub fn with_project_config(&mut self, config: ProjectConfig) -> &mut Self {
         self.project_config = Some(Box::new(config));
         self
@@ -172,17 +206,38 @@ impl RootDatabaseBuilder {
     }
 
     pub fn skip_auto_withdraw_gas(&mut self) -> &mut Self {
-        self.auto_withdraw_gas = false;
+        self.flags.add_withdraw_gas = Some(false);
         self
     }
 
     pub fn with_panic_backtrace(&mut self) -> &mut Self {
-        self.panic_backtrace = true;
+        self.flags.panic_backtrace = Some(true);
         self
     }
 
     pub fn with_unsafe_panic(&mut self) -> &mut Self {
-        self.unsafe_panic = true;
+        self.flags.unsafe_panic = Some(true);
+        self
+    }
+
+    /// Compiles functions with errors into stubs that panic, so that the rest of the program can
+    /// still be compiled. See [crate::CompilerConfig::error_tolerant].
+    pub fn with_error_tolerance(&mut self) -> &mut Self {
+        self.flags.error_tolerant = Some(true);
+        self
+    }
+
+    /// Adds notes to failed and ambiguous impl resolution diagnostics, explaining the outcome of
+    /// each candidate impl.
+    pub fn with_trait_solver_trace(&mut self) -> &mut Self {
+        self.flags.trait_solver_trace = Some(true);
+        self
+    }
+
+    /// Sets the compilation flags set in `flags`, overriding the values set so far and the flags of
+    /// the project config.
+    pub fn with_flags(&mut self, flags: &CompilerFlags) -> &mut Self {
+        self.flags = self.flags.overridden_by(flags);
         self
     }
 
@@ -194,22 +249,28 @@ impl RootDatabaseBuilder {
         let mut db =
             RootDatabase::new(self.default_plugin_suite.clone(), self.optimizations.clone());
 
+        set_lowering_plugins(&mut db, self.lowering_plugins.clone())?;
+
+        if let Some(backend) = &self.fs_backend {
+            set_fs_backend(&mut db, backend.clone());
+        }
+
         if let Some(cfg_set) = &self.cfg_set {
             db.use_cfg(cfg_set);
         }
 
-        if self.detect_corelib {
-            let path =
-                detect_corelib().ok_or_else(|| anyhow!("Failed to find development corelib."))?;
+        if let Some(corelib) = &self.in_memory_corelib {
+            init_dev_corelib_from_root(&mut db, corelib.clone());
+        } else if self.detect_corelib {
+            let path = detect_corelib_in(db.fs_backend())
+                .ok_or_else(|| anyhow!("Failed to find development corelib."))?;
             init_dev_corelib(&mut db, path)
         }
-
-        let add_withdraw_gas_flag_id = FlagLongId(Flag::ADD_WITHDRAW_GAS.into());
-        db.set_flag(add_withdraw_gas_flag_id, Some(Flag::AddWithdrawGas(self.auto_withdraw_gas)));
-        let panic_backtrace_flag_id = FlagLongId(Flag::PANIC_BACKTRACE.into());
-        db.set_flag(panic_backtrace_flag_id, Some(Flag::PanicBacktrace(self.panic_backtrace)));
-        let unsafe_panic_flag_id = FlagLongId(Flag::UNSAFE_PANIC.into());
-        db.set_flag(unsafe_panic_flag_id, Some(Flag::UnsafePanic(self.unsafe_panic)));
+        // The flags set on the builder take precedence over the flags of the project config.
+        let project_flags = self.project_config.as_ref().map(|config| &config.content.flags);
+        db.set_compiler_flags(
+            project_flags.cloned().unwrap_or_default().overridden_by(&self.flags),
+        );
 
         if let Some(config) = &self.project_config {
             update_crate_roots_from_project_config(&mut db, config.as_ref());
@@ -0,0 +1,158 @@
+//! Verification that the compilation of a project is reproducible.
+//!
+//! The project is compiled twice, each time from a fresh database (and therefore with fresh hash
+//! seeds and interning order): once on a single thread, and once on a multi-threaded pool, with the
+//! parallel database warmup done by [ensure_diagnostics]. The Sierra programs, and the CASM
+//! programs compiled from them, are compared line by line as emitted - so differences in the ids or
+//! in the order of the declarations are reported as well.
+
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::ids::CrateInput;
+use cairo_lang_lowering::utils::InliningStrategy;
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::find_all_free_function_ids;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use thiserror::Error;
+
+use crate::progress::ProgressReporter;
+use crate::{
+    CompilerConfig, build_casm, check_diagnostics_result, ensure_diagnostics, prepare_db_at_path,
+    should_warmup, warmup_functions_blocking,
+};
+
+#[cfg(test)]
+#[path = "determinism_test.rs"]
+mod test;
+
+/// The minimal number of threads used for the parallel compilation run.
+const MIN_PARALLEL_THREADS: usize = 2;
+
+/// An output of a compilation compared by [verify_deterministic_build].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuildOutput {
+    /// The Sierra program.
+    Sierra,
+    /// The CASM program compiled from the Sierra program.
+    Casm,
+}
+impl fmt::Display for BuildOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildOutput::Sierra => write!(f, "Sierra"),
+            BuildOutput::Casm => write!(f, "CASM"),
+        }
+    }
+}
+
+/// Two compilations of the same project produced different outputs.
+#[derive(Error, Debug, Eq, PartialEq)]
+#[error(
+    "Non-deterministic build: {output} outputs differ at line {line}.\nFirst build:  \
+     `{first}`\nSecond build: `{second}`"
+)]
+pub struct NonDeterministicBuildError {
+    /// The output in which the compilations differ.
+    pub output: BuildOutput,
+    /// The 1-based line in the output text where the outputs first differ.
+    pub line: usize,
+    /// The line of the first build (empty if the first output is shorter).
+    pub first: String,
+    /// The line of the second build (empty if the second output is shorter).
+    pub second: String,
+}
+
+/// Compiles the Cairo project at the given path twice and verifies that both compilations produce
+/// the same Sierra program, and the same CASM program.
+///
+/// The first compilation runs on a single thread, the second one on a multi-threaded pool with a
+/// parallel warmup of the database, so differences caused by the order in which queries are
+/// computed, or by hash iteration order, are detected.
+/// # Arguments
+/// * `path` - The path to the project.
+/// * `compiler_config` - Creates the compiler configuration for each of the compilations.
+/// * `inlining_strategy` - The inlining strategy to use.
+/// # Returns
+/// * `Ok(Program)` - The program of the first compilation.
+/// * `Err(anyhow::Error)` - Compilation failed, or the outputs differ (in which case the error is a
+///   [NonDeterministicBuildError]).
+pub fn verify_deterministic_build<'a>(
+    path: &Path,
+    compiler_config: impl Fn() -> CompilerConfig<'a> + Sync,
+    inlining_strategy: InliningStrategy,
+) -> Result<Program> {
+    let build_in_pool = |num_threads: usize| -> Result<(Program, String)> {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .context("Failed to create a thread pool.")?
+            .install(|| build(path, compiler_config(), inlining_strategy))
+    };
+    let (first_program, first_casm) = build_in_pool(1)?;
+    let (second_program, second_casm) =
+        build_in_pool(rayon::current_num_threads().max(MIN_PARALLEL_THREADS))?;
+    compare_outputs(BuildOutput::Sierra, &first_program.to_string(), &second_program.to_string())?;
+    compare_outputs(BuildOutput::Casm, &first_casm, &second_casm)?;
+    Ok(first_program)
+}
+
+/// Compiles the project at the given path to Sierra, and the Sierra program to CASM, on the current
+/// thread pool. On a multi-threaded pool, the database is warmed up in parallel - the diagnostics
+/// as by [ensure_diagnostics], and then the Sierra code of the functions.
+fn build(
+    path: &Path,
+    mut compiler_config: CompilerConfig<'_>,
+    inlining_strategy: InliningStrategy,
+) -> Result<(Program, String)> {
+    let (db, main_crate_ids) = prepare_db_at_path(path, &compiler_config, inlining_strategy)?;
+    let main_crate_ids = CrateInput::into_crate_ids(&db, main_crate_ids);
+    let diagnostics_result = ensure_diagnostics(&db, &mut compiler_config.diagnostics_reporter);
+    check_diagnostics_result(&db, &compiler_config, diagnostics_result.map_err(Into::into))?;
+    if should_warmup()
+        && let Ok(function_ids) = find_all_free_function_ids(&db, main_crate_ids.clone())
+    {
+        let progress_reporter = ProgressReporter::default();
+        warmup_functions_blocking(&db, function_ids, &Default::default(), &progress_reporter);
+    }
+    let mut program = db
+        .get_sierra_program(main_crate_ids)
+        .to_option()
+        .context("Compilation failed without any diagnostics")?
+        .program
+        .clone();
+    if compiler_config.replace_ids {
+        program = replace_sierra_ids_in_program(&db, &program);
+    }
+    let casm = build_casm(&program, false).context("Failed to compile the program to CASM.")?;
+    Ok((program, casm.casm_program().to_string()))
+}
+
+/// Compares two texts of the given output line by line.
+/// Returns the first difference found, if any.
+pub fn compare_outputs(
+    output: BuildOutput,
+    first: &str,
+    second: &str,
+) -> Result<(), NonDeterministicBuildError> {
+    let (mut first_lines, mut second_lines) = (first.lines(), second.lines());
+    let mut line = 0;
+    loop {
+        line += 1;
+        match (first_lines.next(), second_lines.next()) {
+            (None, None) => return Ok(()),
+            (first, second) if first == second => {}
+            (first, second) => {
+                return Err(NonDeterministicBuildError {
+                    output,
+                    line,
+                    first: first.unwrap_or_default().to_string(),
+                    second: second.unwrap_or_default().to_string(),
+                });
+            }
+        }
+    }
+}
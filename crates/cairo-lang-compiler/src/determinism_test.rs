@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use cairo_lang_lowering::utils::InliningStrategy;
+
+use super::{BuildOutput, NonDeterministicBuildError, compare_outputs, verify_deterministic_build};
+use crate::CompilerConfig;
+use crate::diagnostics::DiagnosticsReporter;
+
+#[test]
+fn example_build_is_deterministic() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../examples/fib.cairo");
+    let config = || CompilerConfig {
+        diagnostics_reporter: DiagnosticsReporter::stderr(),
+        ..CompilerConfig::default()
+    };
+    verify_deterministic_build(&path, config, InliningStrategy::Default).unwrap();
+}
+
+#[test]
+fn identical_outputs_are_equal() {
+    let program = "type [0] = felt252;
+        libfunc [0] = felt252_const<1>;
+        [0]() -> ([0]);
+        return([0]);
+        [0]@0() -> ([0]);";
+    assert_eq!(compare_outputs(BuildOutput::Sierra, program, program), Ok(()));
+}
+
+#[test]
+fn renumbered_programs_are_reported() {
+    let first = "type [3] = felt252;
+        libfunc [7] = felt252_const<1>;
+        [7]() -> ([0]);";
+    let second = "type [3] = felt252;
+        libfunc [2] = felt252_const<1>;
+        [2]() -> ([0]);";
+    assert_eq!(
+        compare_outputs(BuildOutput::Sierra, first, second),
+        Err(NonDeterministicBuildError {
+            output: BuildOutput::Sierra,
+            line: 2,
+            first: "        libfunc [7] = felt252_const<1>;".into(),
+            second: "        libfunc [2] = felt252_const<1>;".into(),
+        })
+    );
+}
+
+#[test]
+fn different_lengths_are_reported() {
+    let first = "[ap + 0] = 1, ap++;\nret;";
+    let second = "[ap + 0] = 1, ap++;";
+    assert_eq!(
+        compare_outputs(BuildOutput::Casm, first, second),
+        Err(NonDeterministicBuildError {
+            output: BuildOutput::Casm,
+            line: 2,
+            first: "ret;".into(),
+            second: "".into(),
+        })
+    );
+}
@@ -1,15 +1,17 @@
 use std::fmt::Write;
 
 use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::diagnostic_utils::expansion_backtrace_notes;
 use cairo_lang_defs::ids::ModuleId;
 use cairo_lang_diagnostics::{
     DiagnosticEntry, Diagnostics, FormattedDiagnosticEntry, PluginFileDiagnosticNotes, Severity,
 };
-use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::db::{FilesGroup, LintLevel};
 use cairo_lang_filesystem::ids::{CrateId, CrateInput, FileLongId};
 use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_parser::db::ParserGroup;
 use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::lint::configured_warning_level;
 use cairo_lang_utils::Intern;
 use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use salsa::Database;
@@ -49,10 +51,22 @@ pub struct DiagnosticsReporter<'a> {
     crates: Option<Vec<CrateInput>>,
     /// If true, compilation will not fail due to warnings.
     allow_warnings: bool,
+    /// If true, warnings are reported as errors, unless their level is configured otherwise.
+    deny_warnings: bool,
     /// If true, will ignore diagnostics from LoweringGroup during the ensure function.
     skip_lowering_diagnostics: bool,
+    /// If true, diagnostics in plugin generated code will include the expansion backtrace of the
+    /// generated code.
+    expansion_backtrace: bool,
+    /// If set, diagnostics with the same root cause are grouped, showing up to this many of the
+    /// related locations of each group. See [Diagnostics::get_diagnostic_groups].
+    max_related_locations: Option<usize>,
 }
 
+/// The default number of related locations shown for a group of diagnostics with the same root
+/// cause.
+pub const DEFAULT_MAX_RELATED_LOCATIONS: usize = 3;
+
 impl DiagnosticsReporter<'_> {
     /// Creates a reporter which does not print or collect diagnostics at all.
     pub fn ignoring() -> Self {
@@ -62,7 +76,10 @@ impl DiagnosticsReporter<'_> {
             ignore_all_warnings: false,
             ignore_warnings_crate_ids: vec![],
             allow_warnings: false,
+            deny_warnings: false,
             skip_lowering_diagnostics: false,
+            expansion_backtrace: false,
+            max_related_locations: Some(DEFAULT_MAX_RELATED_LOCATIONS),
         }
     }
 
@@ -107,7 +124,10 @@ impl<'a> DiagnosticsReporter<'a> {
             ignore_all_warnings: false,
             ignore_warnings_crate_ids: vec![],
             allow_warnings: false,
+            deny_warnings: false,
             skip_lowering_diagnostics: false,
+            expansion_backtrace: false,
+            max_related_locations: Some(DEFAULT_MAX_RELATED_LOCATIONS),
         }
     }
 
@@ -132,6 +152,27 @@ impl<'a> DiagnosticsReporter<'a> {
         self
     }
 
+    /// Reports warnings as errors, failing the compilation. Warnings whose level is configured by
+    /// the `lints` crate setting or by lint attributes are reported according to their level.
+    pub fn deny_warnings(mut self) -> Self {
+        self.deny_warnings = true;
+        self
+    }
+
+    /// Shows the expansion backtrace of diagnostics in plugin generated code - the generated code
+    /// and the code it was expanded from.
+    pub fn with_expansion_backtrace(mut self) -> Self {
+        self.expansion_backtrace = true;
+        self
+    }
+
+    /// Sets the number of related locations shown for a group of diagnostics with the same root
+    /// cause. If `None`, every diagnostic is reported separately.
+    pub fn with_max_related_locations(mut self, max_related_locations: Option<usize>) -> Self {
+        self.max_related_locations = max_related_locations;
+        self
+    }
+
     /// Ignores warnings in all Cairo crates.
     pub fn ignore_all_warnings(mut self) -> Self {
         self.ignore_all_warnings = true;
@@ -196,6 +237,7 @@ impl<'a> DiagnosticsReporter<'a> {
                         if processed_file_ids.insert(file_id) {
                             found_diagnostics |= self.check_diag_group(
                                 db.as_dyn_database(),
+                                crate_id,
                                 db.file_syntax_diagnostics(file_id).clone(),
                                 ignore_warnings_in_crate,
                                 diagnostic_notes,
@@ -207,6 +249,7 @@ impl<'a> DiagnosticsReporter<'a> {
                 if let Ok(group) = db.module_semantic_diagnostics(*module_id) {
                     found_diagnostics |= self.check_diag_group(
                         db.as_dyn_database(),
+                        crate_id,
                         group,
                         ignore_warnings_in_crate,
                         diagnostic_notes,
@@ -220,6 +263,7 @@ impl<'a> DiagnosticsReporter<'a> {
                 if let Ok(group) = db.module_lowering_diagnostics(*module_id) {
                     found_diagnostics |= self.check_diag_group(
                         db.as_dyn_database(),
+                        crate_id,
                         group,
                         ignore_warnings_in_crate,
                         diagnostic_notes,
@@ -235,18 +279,46 @@ impl<'a> DiagnosticsReporter<'a> {
     fn check_diag_group<'db, TEntry: DiagnosticEntry<'db> + salsa::Update>(
         &mut self,
         db: &'db dyn Database,
+        crate_id: CrateId<'db>,
         group: Diagnostics<'db, TEntry>,
         skip_warnings: bool,
         file_notes: &PluginFileDiagnosticNotes<'db>,
     ) -> bool {
         let mut found: bool = false;
-        for entry in group.format_with_severity(db, file_notes) {
+        let entries = group.format_with_severity_and_notes(
+            db,
+            file_notes,
+            |location| {
+                if self.expansion_backtrace {
+                    expansion_backtrace_notes(db, location)
+                } else {
+                    vec![]
+                }
+            },
+            |entry| {
+                let severity = entry.severity();
+                // Warnings of crates whose warnings are ignored are not configured, so are skipped.
+                if severity != Severity::Warning || skip_warnings {
+                    return Some(severity);
+                }
+                match configured_warning_level(db, crate_id, entry.location(db), entry.error_code())
+                {
+                    Some(LintLevel::Allow) => None,
+                    Some(LintLevel::Warn) => Some(Severity::Warning),
+                    Some(LintLevel::Deny) => Some(Severity::Error),
+                    None if self.deny_warnings => Some(Severity::Error),
+                    None => Some(Severity::Warning),
+                }
+            },
+            self.max_related_locations,
+        );
+        for entry in entries {
             if skip_warnings && entry.severity() == Severity::Warning {
                 continue;
             }
             if !entry.is_empty() {
+                found |= !self.allow_warnings || entry.severity() == Severity::Error;
                 self.callback.on_diagnostic(entry);
-                found |= !self.allow_warnings || group.check_error_free().is_err();
             }
         }
         found
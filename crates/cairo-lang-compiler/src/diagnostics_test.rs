@@ -1,9 +1,11 @@
-use cairo_lang_filesystem::db::CrateConfiguration;
+use cairo_lang_filesystem::db::{CrateConfiguration, DependencySettings, FilesGroup};
 use cairo_lang_filesystem::ids::{CrateId, Directory, SmolStrId};
-use cairo_lang_filesystem::set_crate_config;
+use cairo_lang_filesystem::{override_file_content, set_crate_config};
+use cairo_lang_semantic::test_utils::setup_test_crate_ex;
+use indoc::indoc;
 
 use crate::db::RootDatabase;
-use crate::diagnostics::get_diagnostics_as_string;
+use crate::diagnostics::{DiagnosticsReporter, get_diagnostics_as_string};
 
 #[test]
 fn test_diagnostics() {
@@ -18,3 +20,158 @@ fn test_diagnostics() {
 
     assert_eq!(get_diagnostics_as_string(&db, None), "error: no/such/path/lib.cairo not found\n");
 }
+
+#[test]
+fn test_crate_dependency_cycle() {
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    for (name, dep) in [("a", "b"), ("b", "a")] {
+        let db_ref = &mut db;
+        let root = Directory::Real(name.into());
+        let file_id = root.file(db_ref, "lib.cairo");
+        override_file_content!(db_ref, file_id, Some("fn foo() {}\n".into()));
+        let mut config = CrateConfiguration::default_for_root(root);
+        config.settings.dependencies =
+            [(dep.to_string(), DependencySettings { discriminator: None })].into();
+        let crate_id = CrateId::plain(db_ref, SmolStrId::from(db_ref, name));
+        set_crate_config!(db_ref, crate_id, Some(config));
+    }
+
+    assert_eq!(
+        get_diagnostics_as_string(&db, None),
+        indoc! {"
+            error[E2197]: Cyclic dependency between crates: a -> b -> a.
+             --> a/lib.cairo:1:1
+            fn foo() {}
+            ^
+
+            error[E2197]: Cyclic dependency between crates: b -> a -> b.
+             --> b/lib.cairo:1:1
+            fn foo() {}
+            ^
+
+        "}
+    );
+}
+
+#[test]
+fn test_configured_warning_levels() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate_ex(
+        &db,
+        indoc! {"
+            fn crate_level() {
+                let a = 1;
+            }
+
+            #[allow(E0001)]
+            fn allowed() {
+                let b = 1;
+            }
+
+            #[warn(warnings)]
+            mod warned {
+                fn inner() {
+                    let c = 1;
+                }
+                #[deny(E0001)]
+                fn denied() {
+                    let d = 1;
+                }
+            }
+        "},
+        Some(indoc! {r#"
+            edition = "2024_07"
+
+            [lints]
+            E0001 = "deny"
+        "#}),
+        None,
+    );
+    let crate_input = crate_id.long(&db).clone().into_crate_input(&db);
+
+    let mut output = String::new();
+    let result = DiagnosticsReporter::write_to_string(&mut output)
+        .with_crates(&[crate_input])
+        .allow_warnings()
+        .ensure(&db);
+    assert!(result.is_err());
+    assert_eq!(
+        output,
+        indoc! {"
+            error[E0001]: Unused variable. Consider ignoring by prefixing with `_`.
+             --> lib.cairo:2:9
+                let a = 1;
+                    ^
+
+            warning[E0001]: Unused variable. Consider ignoring by prefixing with `_`.
+             --> lib.cairo:13:13
+                    let c = 1;
+                        ^
+
+            error[E0001]: Unused variable. Consider ignoring by prefixing with `_`.
+             --> lib.cairo:17:13
+                    let d = 1;
+                        ^
+
+        "}
+    );
+}
+
+#[test]
+fn test_deny_warnings() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate_ex(
+        &db,
+        indoc! {"
+            fn denied() {
+                let a = 1;
+            }
+
+            #[warn(unused_variables)]
+            fn lint_warned() {
+                let b = 1;
+            }
+
+            #[allow(warnings)]
+            fn allowed() {
+                let c = 1;
+            }
+        "},
+        None,
+        None,
+    );
+    let crates = [crate_id.long(&db).clone().into_crate_input(&db)];
+
+    let mut warnings_output = String::new();
+    assert!(
+        DiagnosticsReporter::write_to_string(&mut warnings_output)
+            .with_crates(&crates)
+            .allow_warnings()
+            .ensure(&db)
+            .is_ok()
+    );
+    let mut output = String::new();
+    assert!(
+        DiagnosticsReporter::write_to_string(&mut output)
+            .with_crates(&crates)
+            .allow_warnings()
+            .deny_warnings()
+            .ensure(&db)
+            .is_err()
+    );
+    assert_eq!(
+        output,
+        indoc! {"
+            error[E0001]: Unused variable. Consider ignoring by prefixing with `_`.
+             --> lib.cairo:2:9
+                let a = 1;
+                    ^
+
+            error[E0001]: Unused variable. Consider ignoring by prefixing with `_`.
+             --> lib.cairo:7:9
+                let b = 1;
+                    ^
+
+        "}
+    );
+}
@@ -0,0 +1,142 @@
+//! Eviction of stale inputs from long-lived databases, e.g. in watch-mode or IDE sessions.
+//!
+//! A session keeps the overrides and the recorded edits of every file it ever changed.
+//! [InputsTracker] tracks the session revisions in which files were changed, and evicts the inputs
+//! of the files left untouched, without changing the content of any file - so all the derived
+//! values stay correct. The files changed through the file macros (e.g.
+//! [override_file_content](cairo_lang_filesystem::override_file_content)) are
+//! touched automatically. The values of the heavy per-file queries, e.g. the syntax of old versions
+//! of a file, are evicted by salsa as least recently used.
+
+use cairo_lang_filesystem::db::{FilesGroup, files_group_input, take_touched_files};
+use cairo_lang_filesystem::ids::FileInput;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use salsa::{Database, Setter};
+
+#[cfg(test)]
+#[path = "eviction_test.rs"]
+mod test;
+
+/// Tracks the session revisions in which the files of a long-lived database were touched.
+///
+/// A session revision is a batch of changes, e.g. a save in watch mode or a request in an IDE,
+/// started with [InputsTracker::next_revision].
+#[derive(Clone, Debug, Default)]
+pub struct InputsTracker {
+    /// The current session revision.
+    revision: usize,
+    /// The session revision in which each tracked file was last touched.
+    last_touched: OrderedHashMap<FileInput, usize>,
+}
+
+/// The inputs removed by [InputsTracker::evict].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EvictedInputs {
+    /// The files whose recorded edits were removed.
+    pub edits: Vec<FileInput>,
+    /// The files whose content overrides were removed.
+    pub overrides: Vec<FileInput>,
+}
+
+impl InputsTracker {
+    /// Creates a tracker, at the first session revision.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current session revision.
+    pub fn revision(&self) -> usize {
+        self.revision
+    }
+
+    /// Starts a new session revision. The files changed in the database since the last session
+    /// revision started are marked as touched in it.
+    pub fn next_revision(&mut self, db: &mut dyn Database) {
+        self.touch_changed_files(db);
+        self.revision += 1;
+    }
+
+    /// Marks the file as touched in the current session revision, e.g. when it is opened. Files
+    /// changed through the file macros are touched without it.
+    pub fn touch(&mut self, file: FileInput) {
+        self.last_touched.insert(file, self.revision);
+    }
+
+    /// Marks the files changed in the database since the last call as touched in the current
+    /// session revision.
+    fn touch_changed_files(&mut self, db: &mut dyn Database) {
+        for file in take_touched_files(db) {
+            self.touch(file);
+        }
+    }
+
+    /// Returns the tracked files not touched in the last `max_age` session revisions.
+    pub fn stale_files(&self, max_age: usize) -> Vec<FileInput> {
+        self.last_touched
+            .iter()
+            .filter(|(_, last_touched)| *last_touched + max_age < self.revision)
+            .map(|(file, _)| file.clone())
+            .collect()
+    }
+
+    /// Evicts the inputs of the tracked files not touched in the last `max_age` session revisions,
+    /// and stops tracking them.
+    ///
+    /// The recorded edits of the files are removed, as they are only used for reparsing the files
+    /// incrementally. The content overrides of on-disk files are removed only if they are identical
+    /// to the content on disk, so the content of the files is unchanged.
+    pub fn evict(&mut self, db: &mut dyn Database, max_age: usize) -> EvictedInputs {
+        self.touch_changed_files(db);
+        let stale_files = self.stale_files(max_age);
+        for file in &stale_files {
+            self.last_touched.swap_remove(file);
+        }
+
+        let input = files_group_input(db);
+        let mut edits = input.file_edits(db).clone().unwrap_or_default();
+        let mut overrides = input.file_overrides(db).clone().unwrap_or_default();
+        let mut evicted = EvictedInputs::default();
+        for file in stale_files {
+            if edits.swap_remove(&file).is_some() {
+                evicted.edits.push(file.clone());
+            }
+            if let FileInput::OnDisk(path) = &file
+                && let Some(content) = overrides.get(&file)
+                && db.fs_backend().read_to_string(path).as_deref() == Some(&**content)
+            {
+                overrides.swap_remove(&file);
+                evicted.overrides.push(file);
+            }
+        }
+
+        if !evicted.edits.is_empty() {
+            input.set_file_edits(db).to(Some(edits));
+        }
+        if !evicted.overrides.is_empty() {
+            input.set_file_overrides(db).to(Some(overrides));
+        }
+        evicted
+    }
+}
+
+/// Returns the number of bytes held by the recorded edits and content overrides of the database,
+/// to decide when an eviction is due.
+pub fn file_inputs_bytes(db: &dyn Database) -> usize {
+    let input = files_group_input(db);
+    let edits_bytes: usize = input
+        .file_edits(db)
+        .iter()
+        .flat_map(|edits| edits.iter())
+        .map(|(_, edits)| {
+            edits.base_content.len()
+                + edits.edits.iter().map(|edit| edit.new_text.len()).sum::<usize>()
+        })
+        .sum();
+    let overrides_bytes: usize = input
+        .file_overrides(db)
+        .iter()
+        .flat_map(|overrides| overrides.iter())
+        .map(|(_, content)| content.len())
+        .sum();
+    edits_bytes + overrides_bytes
+}
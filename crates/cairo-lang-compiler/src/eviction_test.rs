@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use cairo_lang_filesystem::backend::MemoryFsBackend;
+use cairo_lang_filesystem::db::{FilesGroup, set_fs_backend};
+use cairo_lang_filesystem::ids::{Directory, FileInput};
+use cairo_lang_filesystem::span::{TextEdit, TextOffset, TextSpan};
+use cairo_lang_filesystem::{apply_file_edit, override_file_content};
+
+use super::{EvictedInputs, InputsTracker, file_inputs_bytes};
+use crate::db::RootDatabase;
+
+const CONTENT: &str = "fn foo() {}\n";
+
+/// Returns a database whose filesystem holds `src/lib.cairo` and `src/other.cairo`.
+fn setup_db() -> RootDatabase {
+    let mut db = RootDatabase::empty();
+    let backend = MemoryFsBackend::new()
+        .with_file("src/lib.cairo", CONTENT)
+        .with_file("src/other.cairo", CONTENT);
+    set_fs_backend(&mut db, Arc::new(backend));
+    db
+}
+
+/// Overrides the content of the file and edits it.
+fn edit_file(db: &mut RootDatabase, name: &str, content: &str) -> FileInput {
+    let db_ref = db;
+    let file_id = Directory::Real("src".into()).file(db_ref, name);
+    override_file_content!(db_ref, file_id, Some(content.into()));
+    let file_id = Directory::Real("src".into()).file(db_ref, name);
+    apply_file_edit!(db_ref, file_id, &TextEdit::new(TextSpan::cursor(TextOffset::START), ""))
+        .unwrap();
+    let file_id = Directory::Real("src".into()).file(db_ref, name);
+    db_ref.file_input(file_id).clone()
+}
+
+#[test]
+fn evicts_untouched_files() {
+    let mut db = setup_db();
+    let mut tracker = InputsTracker::new();
+    // The edited files are touched without calling `touch`.
+    let lib = edit_file(&mut db, "lib.cairo", CONTENT);
+    let other = edit_file(&mut db, "other.cairo", "fn bar() {}\n");
+    let bytes_before = file_inputs_bytes(&db);
+
+    tracker.next_revision(&mut db);
+    tracker.touch(lib.clone());
+    assert_eq!(tracker.evict(&mut db, 1), EvictedInputs::default());
+
+    tracker.next_revision(&mut db);
+    assert_eq!(tracker.stale_files(1), vec![other.clone()]);
+    let evicted = tracker.evict(&mut db, 1);
+    // The override of `other.cairo` differs from its content on disk, so it is kept.
+    assert_eq!(evicted, EvictedInputs { edits: vec![other.clone()], overrides: vec![] });
+    assert!(file_inputs_bytes(&db) < bytes_before);
+    assert!(tracker.stale_files(0).iter().all(|file| *file != other));
+
+    let other_id = Directory::Real("src".into()).file(&db, "other.cairo");
+    assert!(db.file_edits(other_id).is_none());
+    assert_eq!(db.file_content(other_id).unwrap(), "fn bar() {}\n");
+
+    tracker.next_revision(&mut db);
+    let evicted = tracker.evict(&mut db, 1);
+    assert_eq!(evicted, EvictedInputs { edits: vec![lib.clone()], overrides: vec![lib.clone()] });
+    let lib_id = Directory::Real("src".into()).file(&db, "lib.cairo");
+    assert!(db.file_edits(lib_id).is_none());
+    assert!(!db.file_overrides().contains_key(&lib_id));
+    assert_eq!(db.file_content(lib_id).unwrap(), CONTENT);
+
+    // Editing the file again touches it, so it is not stale.
+    edit_file(&mut db, "lib.cairo", "fn baz() {}\n");
+    tracker.next_revision(&mut db);
+    assert_eq!(tracker.stale_files(1), vec![]);
+    tracker.next_revision(&mut db);
+    assert_eq!(tracker.stale_files(1), vec![lib]);
+}
@@ -8,32 +8,52 @@ use std::sync::Mutex;
 use ::cairo_lang_diagnostics::ToOption;
 use anyhow::{Context, Result};
 use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_filesystem::db::{CORELIB_CRATE_NAME, CrateIdentifier, FilesGroup};
+use cairo_lang_filesystem::flag::FlagsGroup;
 use cairo_lang_filesystem::ids::{CrateId, CrateInput};
 use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
 use cairo_lang_lowering::optimizations::config::Optimizations;
 use cairo_lang_lowering::utils::InliningStrategy;
 use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_runnable_utils::builder::{BuildError, RunnableBuilder};
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_sierra::debug_info::{Annotations, DebugInfo};
+use cairo_lang_sierra::extensions::NamedType;
+use cairo_lang_sierra::extensions::gas::GasBuiltinType;
 use cairo_lang_sierra::program::{Program, ProgramArtifact};
 use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::debug_info::{
+    SerializableAllFunctionsDebugInfo, VariableLocations,
+};
 use cairo_lang_sierra_generator::executables::{collect_executables, find_executable_function_ids};
 use cairo_lang_sierra_generator::program_generator::{
     SierraProgramWithDebug, find_all_free_function_ids, try_get_function_with_body_id,
 };
 use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_sierra_to_casm::metadata::MetadataComputationConfig;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use cairo_lang_utils::{CloneableDatabase, Intern};
 use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use salsa::Database;
 
-use crate::db::RootDatabase;
+use crate::db::{RootDatabase, RootDatabaseBuilder};
 use crate::diagnostics::{DiagnosticsError, DiagnosticsReporter};
-use crate::project::{ProjectConfig, get_main_crate_ids_from_project, setup_project};
+use crate::memory::{CompilationPhase, MemoryProfiler};
+use crate::progress::{CompilationCancelled, ProgressEvent, ProgressReporter};
+use crate::project::{
+    CrateCfgSettings, ProjectConfig, get_main_crate_ids_from_project, setup_project,
+    update_crate_cfgs, validate_project_config,
+};
 
 pub mod db;
+pub mod determinism;
 pub mod diagnostics;
+pub mod eviction;
+pub mod memory;
+pub mod progress;
 pub mod project;
 
 #[cfg(test)]
@@ -44,6 +64,12 @@ mod test;
 pub struct CompilerConfig<'a> {
     pub diagnostics_reporter: DiagnosticsReporter<'a>,
 
+    /// Reports the compilation progress and allows cancelling it.
+    pub progress_reporter: ProgressReporter<'a>,
+
+    /// Records the memory usage of the database at the end of each compilation phase.
+    pub memory_profiler: MemoryProfiler,
+
     /// Replaces Sierra IDs with human-readable ones.
     pub replace_ids: bool,
 
@@ -58,6 +84,44 @@ pub struct CompilerConfig<'a> {
     /// Adds a mapping used by [cairo-debugger](https://github.com/software-mansion-labs/cairo-debugger)
     /// to [Annotations] in [DebugInfo] in the compiled tests.
     pub add_functions_debug_info: bool,
+
+    /// The level of debug info added to [Annotations] in [DebugInfo], in addition to the mappings
+    /// requested by the flags above.
+    pub debug_info: DebugInfoLevel,
+
+    /// Compiles functions with errors into stubs that panic, instead of failing the compilation.
+    /// The diagnostics are still reported, and the rest of the program is compiled as usual.
+    ///
+    /// A prepared database must be built with [db::RootDatabaseBuilder::with_error_tolerance] -
+    /// the functions building the database do it according to this flag.
+    pub error_tolerant: bool,
+
+    /// Adds notes to failed and ambiguous impl resolution diagnostics, explaining the outcome of
+    /// each candidate impl.
+    ///
+    /// A prepared database must be built with [db::RootDatabaseBuilder::with_trait_solver_trace].
+    pub trait_solver_trace: bool,
+
+    /// The `#[cfg(...)]` settings per crate name, e.g. the `test` option or custom features.
+    ///
+    /// They are applied with [project::update_crate_cfgs] by the functions setting up the
+    /// project - for a prepared database, the function should be called directly.
+    pub crate_cfgs: OrderedHashMap<String, CrateCfgSettings>,
+}
+
+/// The level of debug info emitted into compiled program artifacts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DebugInfoLevel {
+    /// No debug info.
+    #[default]
+    None,
+    /// The Cairo code locations of the Sierra statements (as with
+    /// [CompilerConfig::add_statements_code_locations]).
+    LineTables,
+    /// The line tables, the mapping from Sierra variables to Cairo variables (as with
+    /// [CompilerConfig::add_functions_debug_info]), and the memory cells of the Cairo variables
+    /// alive in each CASM pc range (see [VariableLocations]).
+    Full,
 }
 
 /// Compiles a Cairo project at the given path.
@@ -75,13 +139,7 @@ pub fn compile_cairo_project_at_path(
     compiler_config: CompilerConfig<'_>,
     inlining_strategy: InliningStrategy,
 ) -> Result<Program> {
-    let mut db = RootDatabase::builder()
-        .with_optimizations(Optimizations::enabled_with_default_movable_functions(
-            inlining_strategy,
-        ))
-        .detect_corelib()
-        .build()?;
-    let main_crate_ids = setup_project(&mut db, path)?;
+    let (db, main_crate_ids) = prepare_db_at_path(path, &compiler_config, inlining_strategy)?;
     compile_prepared_db_program(
         &db,
         CrateInput::into_crate_ids(&db, main_crate_ids),
@@ -89,6 +147,58 @@ pub fn compile_cairo_project_at_path(
     )
 }
 
+/// Builds a database for the Cairo project at the given path, returning it along with the main
+/// crates of the project. See [compile_cairo_project_at_path].
+fn prepare_db_at_path(
+    path: &Path,
+    compiler_config: &CompilerConfig<'_>,
+    inlining_strategy: InliningStrategy,
+) -> Result<(RootDatabase, Vec<CrateInput>)> {
+    let mut builder = RootDatabase::builder();
+    builder
+        .with_optimizations(Optimizations::enabled_with_default_movable_functions(
+            inlining_strategy,
+        ))
+        .detect_corelib();
+    apply_config(&mut builder, compiler_config);
+    let mut db = builder.build()?;
+    let main_crate_ids = setup_project(&mut db, path)?;
+    update_crate_cfgs(&mut db, &compiler_config.crate_cfgs)?;
+    Ok((db, main_crate_ids))
+}
+
+/// Compiles a Cairo project described by a project config, which may be built programmatically
+/// (see [ProjectConfig::new]) with multiple crates, per-crate settings and dependencies.
+/// The config is validated with [validate_project_config] before compiling. The development corelib
+/// is used unless the config has a root for the `core` crate.
+/// # Arguments
+/// * `project_config` - The project configuration.
+/// * `compiler_config` - The compiler configuration.
+/// * `inlining_strategy` - The inlining strategy to use.
+/// # Returns
+/// * `Ok(Program)` - The compiled program.
+/// * `Err(anyhow::Error)` - Compilation failed.
+pub fn compile_cairo_project(
+    project_config: ProjectConfig,
+    compiler_config: CompilerConfig<'_>,
+    inlining_strategy: InliningStrategy,
+) -> Result<Program> {
+    let mut builder = RootDatabase::builder();
+    builder.with_optimizations(Optimizations::enabled_with_default_movable_functions(
+        inlining_strategy,
+    ));
+    if !project_config.content.crate_roots.contains_key(&CrateIdentifier::from(CORELIB_CRATE_NAME))
+    {
+        builder.detect_corelib();
+    }
+    apply_config(&mut builder, &compiler_config);
+    let mut db = builder.with_project_config(project_config.clone()).build()?;
+    validate_project_config(db.fs_backend(), &project_config)?;
+    update_crate_cfgs(&mut db, &compiler_config.crate_cfgs)?;
+    let main_crate_ids = get_main_crate_ids_from_project(&db, &project_config);
+    compile_prepared_db_program(&db, main_crate_ids, compiler_config)
+}
+
 /// Compiles a Cairo project.
 /// The project must be a valid Cairo project.
 /// This function is a wrapper over [`RootDatabase::builder()`] and [`compile_prepared_db_program`].
@@ -102,17 +212,30 @@ pub fn compile(
     project_config: ProjectConfig,
     compiler_config: CompilerConfig<'_>,
 ) -> Result<Program> {
-    let db = RootDatabase::builder()
+    let mut builder = RootDatabase::builder();
+    builder
         .with_optimizations(Optimizations::enabled_with_default_movable_functions(
             InliningStrategy::Default,
         ))
-        .with_project_config(project_config.clone())
-        .build()?;
+        .with_project_config(project_config.clone());
+    apply_config(&mut builder, &compiler_config);
+    let mut db = builder.build()?;
+    update_crate_cfgs(&mut db, &compiler_config.crate_cfgs)?;
     let main_crate_ids = get_main_crate_ids_from_project(&db, &project_config);
 
     compile_prepared_db_program(&db, main_crate_ids, compiler_config)
 }
 
+/// Applies the settings of `compiler_config` that must be set when building the database.
+fn apply_config(builder: &mut RootDatabaseBuilder, compiler_config: &CompilerConfig<'_>) {
+    if compiler_config.error_tolerant {
+        builder.with_error_tolerance();
+    }
+    if compiler_config.trait_solver_trace {
+        builder.with_trait_solver_trace();
+    }
+}
+
 /// Runs Cairo compiler.
 ///
 /// # Arguments
@@ -151,13 +274,31 @@ pub fn compile_prepared_db<'db>(
     main_crate_ids: Vec<CrateId<'db>>,
     mut compiler_config: CompilerConfig<'_>,
 ) -> Result<SierraProgramWithDebug<'db>> {
-    compiler_config.diagnostics_reporter.ensure(db)?;
-
-    let mut sierra_program_with_debug = db
-        .get_sierra_program(main_crate_ids)
+    let progress_reporter = &compiler_config.progress_reporter;
+    if progress_reporter.is_reporting() {
+        let crates = compiler_config.diagnostics_reporter.crates_of_interest(db);
+        progress_reporter
+            .run(db, || warmup_diagnostics_sequential(db, crates, progress_reporter))??;
+    }
+    let diagnostics_result =
+        progress_reporter.run(db, || compiler_config.diagnostics_reporter.ensure(db))?;
+    check_diagnostics_result(db, &compiler_config, diagnostics_result.map_err(Into::into))?;
+    compiler_config.memory_profiler.record(db, CompilationPhase::Diagnostics);
+
+    if progress_reporter.is_reporting()
+        && let Ok(function_ids) = find_all_free_function_ids(db, main_crate_ids.clone())
+    {
+        let entry_points = entry_point_ids(db, main_crate_ids.clone());
+        progress_reporter.run(db, || {
+            warmup_functions_sequential(db, function_ids, &entry_points, progress_reporter)
+        })?;
+    }
+    let mut sierra_program_with_debug = progress_reporter
+        .run(db, || db.get_sierra_program(main_crate_ids))?
         .to_option()
         .context("Compilation failed without any diagnostics")?
         .clone();
+    compiler_config.memory_profiler.record(db, CompilationPhase::SierraGeneration);
 
     if compiler_config.replace_ids {
         sierra_program_with_debug.program =
@@ -167,6 +308,76 @@ pub fn compile_prepared_db<'db>(
     Ok(sierra_program_with_debug)
 }
 
+/// Compiles the program to CASM, optionally recording the locations of the Sierra variables.
+/// Gas usage is computed only for programs using gas, same as when running them.
+fn build_casm(
+    program: &Program,
+    record_variable_locations: bool,
+) -> std::result::Result<RunnableBuilder, BuildError> {
+    let uses_gas = program
+        .type_declarations
+        .iter()
+        .any(|declaration| declaration.long_id.generic_id == GasBuiltinType::ID);
+    RunnableBuilder::new_ex(
+        program.clone(),
+        uses_gas.then(MetadataComputationConfig::default),
+        record_variable_locations,
+        false,
+    )
+}
+
+/// Compiles the program to CASM and returns the memory cells of the Cairo variables alive in each
+/// CASM pc range. The pcs are relative to the start of the CASM code of the program.
+///
+/// Returns `None`, with a warning in the log, if the program cannot be compiled to CASM, e.g. if
+/// its gas usage cannot be computed. Such a program is valid Sierra, so its compilation does not
+/// fail.
+fn extract_variable_locations(
+    program: &Program,
+    functions_debug_info: &SerializableAllFunctionsDebugInfo,
+) -> Option<VariableLocations> {
+    let builder = build_casm(program, true)
+        .inspect_err(|err| {
+            log::warn!(
+                "Failed to compile the program to CASM, omitting the variable locations from the \
+                 debug info: {err}"
+            );
+        })
+        .ok()?;
+    let statement_variables =
+        builder.casm_program().debug_info.sierra_statement_info.iter().map(|info| {
+            let variables = info
+                .variable_locations
+                .iter()
+                .map(|(var_id, expression)| {
+                    (var_id.clone(), expression.cells.iter().map(ToString::to_string).collect())
+                })
+                .collect();
+            ((info.start_offset, info.end_offset), variables)
+        });
+    Some(VariableLocations::new(program, statement_variables, functions_debug_info))
+}
+
+/// Checks the result of ensuring there are no error diagnostics before generating code.
+///
+/// Errors are tolerated by an error tolerant database, as the functions with errors are compiled
+/// into stubs that panic. Cancellation is never tolerated.
+fn check_diagnostics_result(
+    db: &dyn Database,
+    compiler_config: &CompilerConfig<'_>,
+    diagnostics_result: Result<()>,
+) -> Result<()> {
+    let error_tolerant = db.flag_error_tolerant();
+    anyhow::ensure!(
+        error_tolerant || !compiler_config.error_tolerant,
+        "Error tolerant compilation requires a database built with error tolerance."
+    );
+    match diagnostics_result {
+        Err(err) if error_tolerant && err.is::<DiagnosticsError>() => Ok(()),
+        result => result,
+    }
+}
+
 /// Checks if parallelism is available for the warmup.
 fn should_warmup() -> bool {
     rayon::current_num_threads() > 1
@@ -190,8 +401,12 @@ pub fn ensure_diagnostics(
         let crates = diagnostic_reporter.crates_of_interest(db);
         let warmup_db = db.dyn_clone();
         let ensure_db = db.dyn_clone();
+        let progress_reporter = ProgressReporter::default();
         rayon::join(
-            move || warmup_diagnostics_blocking(warmup_db.as_ref(), crates),
+            move || {
+                // The default reporter cannot be cancelled.
+                let _ = warmup_diagnostics_blocking(warmup_db.as_ref(), crates, &progress_reporter);
+            },
             move || diagnostic_reporter.ensure(ensure_db.as_ref()),
         )
         .1
@@ -200,23 +415,84 @@ pub fn ensure_diagnostics(
     }
 }
 
+/// Similar to [ensure_diagnostics], but also reports the parsed files to `progress_reporter`.
+/// Returns `Err` with either [DiagnosticsError] or [CompilationCancelled] on failure.
+///
+/// The warmup is performed whenever progress is reported, even if parallelism is not available,
+/// as it is the step that reports the parsed files.
+pub fn ensure_diagnostics_with_progress(
+    db: &dyn CloneableDatabase,
+    diagnostic_reporter: &mut DiagnosticsReporter<'_>,
+    progress_reporter: &ProgressReporter<'_>,
+) -> Result<()> {
+    if should_warmup() || progress_reporter.is_reporting() {
+        let crates = diagnostic_reporter.crates_of_interest(db);
+        let warmup_db = db.dyn_clone();
+        let ensure_db = db.dyn_clone();
+        let (warmup_result, ensure_result) = rayon::join(
+            move || warmup_diagnostics_blocking(warmup_db.as_ref(), crates, progress_reporter),
+            move || {
+                let ensure_db = ensure_db.as_ref();
+                progress_reporter.run(ensure_db, || diagnostic_reporter.ensure(ensure_db))
+            },
+        );
+        warmup_result?;
+        Ok(ensure_result??)
+    } else {
+        Ok(progress_reporter.run(db, || diagnostic_reporter.ensure(db))??)
+    }
+}
+
 /// Spawns threads to compute the diagnostics queries, making sure later calls for these queries
 /// would be faster as the queries were already computed.
-fn warmup_diagnostics_blocking(db: &dyn CloneableDatabase, crates: Vec<CrateInput>) {
+fn warmup_diagnostics_blocking(
+    db: &dyn CloneableDatabase,
+    crates: Vec<CrateInput>,
+    progress_reporter: &ProgressReporter<'_>,
+) -> std::result::Result<(), CompilationCancelled> {
     crates.into_par_iter().for_each_with(db.dyn_clone(), |db, crate_input| {
         let db = db.as_ref();
         let crate_id = crate_input.into_crate_long_id(db).intern(db);
-        db.crate_modules(crate_id).into_par_iter().for_each_with(
-            db.dyn_clone(),
-            |db, module_id| {
-                for file_id in db.module_files(*module_id).unwrap_or_default().iter().copied() {
-                    db.file_syntax_diagnostics(file_id);
-                }
-                let _ = db.module_semantic_diagnostics(*module_id);
-                let _ = db.module_lowering_diagnostics(*module_id);
-            },
-        );
+        db.crate_modules(crate_id)
+            .into_par_iter()
+            .for_each_with(db.dyn_clone(), |db, module_id| {
+                warmup_module_diagnostics(db.as_ref(), *module_id, progress_reporter)
+            });
     });
+    progress_reporter.check_cancelled()
+}
+
+/// Similar to [warmup_diagnostics_blocking], but computes the queries on the current thread, for
+/// databases that cannot be cloned.
+fn warmup_diagnostics_sequential(
+    db: &dyn Database,
+    crates: Vec<CrateInput>,
+    progress_reporter: &ProgressReporter<'_>,
+) -> std::result::Result<(), CompilationCancelled> {
+    for crate_input in crates {
+        let crate_id = crate_input.into_crate_long_id(db).intern(db);
+        for module_id in db.crate_modules(crate_id) {
+            warmup_module_diagnostics(db, *module_id, progress_reporter);
+        }
+    }
+    progress_reporter.check_cancelled()
+}
+
+/// Computes the diagnostics queries of a module, reporting its parsed files.
+fn warmup_module_diagnostics<'db>(
+    db: &'db dyn Database,
+    module_id: ModuleId<'db>,
+    progress_reporter: &ProgressReporter<'_>,
+) {
+    if progress_reporter.is_cancelled() {
+        return;
+    }
+    for file_id in db.module_files(module_id).unwrap_or_default().iter().copied() {
+        db.file_syntax_diagnostics(file_id);
+        progress_reporter.report(ProgressEvent::FileParsed);
+    }
+    let _ = db.module_semantic_diagnostics(module_id);
+    let _ = db.module_lowering_diagnostics(module_id);
 }
 
 /// Spawns threads to compute the `function_with_body_sierra` query and all dependent queries for
@@ -226,6 +502,8 @@ fn warmup_diagnostics_blocking(db: &dyn CloneableDatabase, crates: Vec<CrateInpu
 fn warmup_functions_blocking<'db>(
     db: &dyn CloneableDatabase,
     requested_function_ids: Vec<ConcreteFunctionWithBodyId<'db>>,
+    entry_points: &UnorderedHashSet<salsa::Id>,
+    progress_reporter: &ProgressReporter<'_>,
 ) {
     let processed_function_ids = &Mutex::new(UnorderedHashSet::<salsa::Id>::default());
     requested_function_ids.into_par_iter().for_each_with(db.dyn_clone(), move |db, func_id| {
@@ -233,11 +511,17 @@ fn warmup_functions_blocking<'db>(
             processed_function_ids: &Mutex<UnorderedHashSet<salsa::Id>>,
             db: &dyn CloneableDatabase,
             func_id: ConcreteFunctionWithBodyId<'db>,
+            entry_points: &UnorderedHashSet<salsa::Id>,
+            progress_reporter: &ProgressReporter<'_>,
         ) {
+            if progress_reporter.is_cancelled() {
+                return;
+            }
             if processed_function_ids.lock().unwrap().insert(func_id.as_intern_id()) {
                 let Ok(function) = db.function_with_body_sierra(func_id) else {
                     return;
                 };
+                report_function_lowered(func_id, entry_points, progress_reporter);
                 function.body.par_iter().for_each_with(db.dyn_clone(), move |db, statement| {
                     let related_function_id: ConcreteFunctionWithBodyId<'_> =
                         if let Some(r_id) = try_get_function_with_body_id(db.as_ref(), statement) {
@@ -246,25 +530,126 @@ fn warmup_functions_blocking<'db>(
                             return;
                         };
 
-                    handle_func_inner(processed_function_ids, db.as_ref(), related_function_id);
+                    handle_func_inner(
+                        processed_function_ids,
+                        db.as_ref(),
+                        related_function_id,
+                        entry_points,
+                        progress_reporter,
+                    );
                 });
             }
         }
-        handle_func_inner(processed_function_ids, db.as_ref(), func_id)
+        handle_func_inner(
+            processed_function_ids,
+            db.as_ref(),
+            func_id,
+            entry_points,
+            progress_reporter,
+        )
     });
 }
 
+/// Similar to [warmup_functions_blocking], but computes the queries on the current thread, for
+/// databases that cannot be cloned.
+fn warmup_functions_sequential<'db>(
+    db: &'db dyn Database,
+    requested_function_ids: Vec<ConcreteFunctionWithBodyId<'db>>,
+    entry_points: &UnorderedHashSet<salsa::Id>,
+    progress_reporter: &ProgressReporter<'_>,
+) {
+    let mut processed_function_ids = UnorderedHashSet::<salsa::Id>::default();
+    let mut function_ids = requested_function_ids;
+    while let Some(func_id) = function_ids.pop() {
+        if progress_reporter.is_cancelled() {
+            return;
+        }
+        if !processed_function_ids.insert(func_id.as_intern_id()) {
+            continue;
+        }
+        let Ok(function) = db.function_with_body_sierra(func_id) else {
+            continue;
+        };
+        report_function_lowered(func_id, entry_points, progress_reporter);
+        function_ids.extend(
+            function
+                .body
+                .iter()
+                .filter_map(|statement| try_get_function_with_body_id(db, statement)),
+        );
+    }
+}
+
+/// Reports that the Sierra code of a function was generated, and also that an entry point was
+/// compiled if the function is one of `entry_points`.
+fn report_function_lowered(
+    func_id: ConcreteFunctionWithBodyId<'_>,
+    entry_points: &UnorderedHashSet<salsa::Id>,
+    progress_reporter: &ProgressReporter<'_>,
+) {
+    progress_reporter.report(ProgressEvent::FunctionLowered);
+    if entry_points.contains(&func_id.as_intern_id()) {
+        progress_reporter.report(ProgressEvent::EntryPointCompiled);
+    }
+}
+
+/// Returns the ids of the executable functions of the given crates, the entry points reported by
+/// [ProgressEvent::EntryPointCompiled].
+fn entry_point_ids<'db>(
+    db: &'db dyn Database,
+    main_crate_ids: Vec<CrateId<'db>>,
+) -> UnorderedHashSet<salsa::Id> {
+    find_executable_function_ids(db, main_crate_ids)
+        .keys()
+        .map(|func_id| func_id.as_intern_id())
+        .collect()
+}
+
 /// Checks if there are diagnostics in the database and if there are none, returns
 /// the [SierraProgramWithDebug] object of the requested functions.
 pub fn get_sierra_program_for_functions<'db>(
     db: &'db dyn CloneableDatabase,
     requested_function_ids: Vec<ConcreteFunctionWithBodyId<'db>>,
 ) -> Result<&'db SierraProgramWithDebug<'db>> {
-    if should_warmup() {
+    get_sierra_program_for_functions_with_progress(
+        db,
+        requested_function_ids,
+        &ProgressReporter::default(),
+    )
+}
+
+/// Similar to [get_sierra_program_for_functions], but also reports the lowered functions to
+/// `progress_reporter`, and stops early if the compilation was cancelled.
+///
+/// The warmup is performed whenever progress is reported, even if parallelism is not available,
+/// as it is the step that reports the lowered functions.
+pub fn get_sierra_program_for_functions_with_progress<'db>(
+    db: &'db dyn CloneableDatabase,
+    requested_function_ids: Vec<ConcreteFunctionWithBodyId<'db>>,
+    progress_reporter: &ProgressReporter<'_>,
+) -> Result<&'db SierraProgramWithDebug<'db>> {
+    sierra_program_for_functions_with_progress(
+        db,
+        requested_function_ids,
+        &UnorderedHashSet::default(),
+        progress_reporter,
+    )
+}
+
+/// Implementation of [get_sierra_program_for_functions_with_progress], which also reports the
+/// compiled `entry_points`.
+fn sierra_program_for_functions_with_progress<'db>(
+    db: &'db dyn CloneableDatabase,
+    requested_function_ids: Vec<ConcreteFunctionWithBodyId<'db>>,
+    entry_points: &UnorderedHashSet<salsa::Id>,
+    progress_reporter: &ProgressReporter<'_>,
+) -> Result<&'db SierraProgramWithDebug<'db>> {
+    if should_warmup() || progress_reporter.is_reporting() {
         let requested_function_ids = requested_function_ids.clone();
-        warmup_functions_blocking(db, requested_function_ids);
+        warmup_functions_blocking(db, requested_function_ids, entry_points, progress_reporter);
     }
-    db.get_sierra_program_for_functions(requested_function_ids)
+    progress_reporter
+        .run(db, || db.get_sierra_program_for_functions(requested_function_ids))?
         .to_option()
         .context("Compilation failed without any diagnostics.")
 }
@@ -288,7 +673,13 @@ pub fn compile_prepared_db_program_artifact<'db>(
     main_crate_ids: Vec<CrateId<'db>>,
     mut compiler_config: CompilerConfig<'_>,
 ) -> Result<ProgramArtifact> {
-    ensure_diagnostics(db, &mut compiler_config.diagnostics_reporter)?;
+    let diagnostics_result = ensure_diagnostics_with_progress(
+        db,
+        &mut compiler_config.diagnostics_reporter,
+        &compiler_config.progress_reporter,
+    );
+    check_diagnostics_result(db, &compiler_config, diagnostics_result)?;
+    compiler_config.memory_profiler.record(db, CompilationPhase::Diagnostics);
 
     let executable_functions = find_executable_function_ids(db, main_crate_ids.clone());
 
@@ -303,8 +694,9 @@ pub fn compile_prepared_db_program_artifact<'db>(
         executable_functions.keys().cloned().collect()
     };
 
+    let entry_points = executable_functions.keys().map(|func_id| func_id.as_intern_id()).collect();
     let mut program_artifact =
-        compile_prepared_db_program_artifact_for_functions(db, function_ids, compiler_config)?;
+        compile_program_artifact_for_functions(db, function_ids, &entry_points, &compiler_config)?;
 
     // Calculate executable function Sierra ids.
     let executables = collect_executables(db, executable_functions, &program_artifact.program);
@@ -331,8 +723,30 @@ pub fn compile_prepared_db_program_artifact_for_functions<'db>(
     requested_function_ids: Vec<ConcreteFunctionWithBodyId<'db>>,
     compiler_config: CompilerConfig<'_>,
 ) -> Result<ProgramArtifact> {
-    let mut sierra_program_with_debug =
-        get_sierra_program_for_functions(db, requested_function_ids)?.clone();
+    compile_program_artifact_for_functions(
+        db,
+        requested_function_ids,
+        &UnorderedHashSet::default(),
+        &compiler_config,
+    )
+}
+
+/// Implementation of [compile_prepared_db_program_artifact_for_functions], borrowing the compiler
+/// configuration, and reporting the compiled `entry_points`.
+fn compile_program_artifact_for_functions<'db>(
+    db: &'db dyn CloneableDatabase,
+    requested_function_ids: Vec<ConcreteFunctionWithBodyId<'db>>,
+    entry_points: &UnorderedHashSet<salsa::Id>,
+    compiler_config: &CompilerConfig<'_>,
+) -> Result<ProgramArtifact> {
+    let mut sierra_program_with_debug = sierra_program_for_functions_with_progress(
+        db,
+        requested_function_ids,
+        entry_points,
+        &compiler_config.progress_reporter,
+    )?
+    .clone();
+    compiler_config.memory_profiler.record(db, CompilationPhase::SierraGeneration);
 
     if compiler_config.replace_ids {
         sierra_program_with_debug.program =
@@ -350,7 +764,9 @@ pub fn compile_prepared_db_program_artifact_for_functions<'db>(
         ))
     };
 
-    if compiler_config.add_statements_code_locations {
+    if compiler_config.add_statements_code_locations
+        || compiler_config.debug_info >= DebugInfoLevel::LineTables
+    {
         annotations.extend(Annotations::from(
             sierra_program_with_debug
                 .debug_info
@@ -359,10 +775,20 @@ pub fn compile_prepared_db_program_artifact_for_functions<'db>(
         ))
     };
 
-    if compiler_config.add_functions_debug_info {
-        annotations.extend(Annotations::from(
-            sierra_program_with_debug.debug_info.functions_info.extract_serializable_debug_info(db),
-        ))
+    if compiler_config.add_functions_debug_info
+        || compiler_config.debug_info == DebugInfoLevel::Full
+    {
+        let functions_debug_info =
+            sierra_program_with_debug.debug_info.functions_info.extract_serializable_debug_info(db);
+        if compiler_config.debug_info == DebugInfoLevel::Full
+            && let Some(variable_locations) = extract_variable_locations(
+                &sierra_program_with_debug.program,
+                &functions_debug_info,
+            )
+        {
+            annotations.extend(Annotations::from(variable_locations));
+        }
+        annotations.extend(Annotations::from(functions_debug_info))
     }
 
     let debug_info = DebugInfo {
@@ -0,0 +1,260 @@
+//! Memory usage statistics of the compiler database.
+//!
+//! [MemoryStats] reports the size of the interner tables and of the memoized query values of a
+//! database, and [MemoryProfiler] records the memory in use at the end of each compilation phase.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use cairo_lang_utils::ordered_hash_map::{Entry, OrderedHashMap};
+use itertools::Itertools;
+use salsa::Database;
+
+#[cfg(test)]
+#[path = "memory_test.rs"]
+mod test;
+
+/// The memory used by the instances of a salsa ingredient - an interned, input or tracked struct,
+/// or the memoized values of a query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IngredientMemory {
+    /// The name of the struct or query.
+    pub name: &'static str,
+    /// The number of instances (for a query - of memoized values).
+    pub count: usize,
+    /// The size of the values in bytes, including their heap allocations when salsa can compute
+    /// them.
+    pub value_bytes: usize,
+    /// The size of the salsa bookkeeping of the instances, in bytes.
+    pub metadata_bytes: usize,
+}
+impl IngredientMemory {
+    /// Returns the total size in bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.value_bytes + self.metadata_bytes
+    }
+
+    fn new(name: &'static str, info: &salsa::IngredientInfo) -> Self {
+        Self {
+            name,
+            count: info.count(),
+            value_bytes: info.size_of_fields() + info.heap_size_of_fields().unwrap_or_default(),
+            metadata_bytes: info.size_of_metadata(),
+        }
+    }
+}
+
+/// A snapshot of the memory used by a database.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// The interned, input and tracked structs, largest first.
+    pub structs: Vec<IngredientMemory>,
+    /// The memoized values of each query, largest first.
+    pub queries: Vec<IngredientMemory>,
+    /// The crate of the values of each query group, as `(crate name, query names)`.
+    query_groups: OrderedHashMap<String, Vec<&'static str>>,
+}
+impl MemoryStats {
+    /// Collects the memory statistics of the database.
+    ///
+    /// Iterates over all the values stored in the database, so it should not be called in a hot
+    /// loop.
+    pub fn collect(db: &dyn Database) -> Self {
+        let info = db.memory_usage();
+        // Ingredients of different structs may share a name, so they are merged by name.
+        let mut structs_by_name = OrderedHashMap::<&'static str, IngredientMemory>::default();
+        for info in info.structs.iter().filter(|info| info.count() > 0) {
+            let memory = IngredientMemory::new(info.debug_name(), info);
+            match structs_by_name.entry(memory.name) {
+                Entry::Occupied(mut entry) => {
+                    let existing = entry.get_mut();
+                    existing.count += memory.count;
+                    existing.value_bytes += memory.value_bytes;
+                    existing.metadata_bytes += memory.metadata_bytes;
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(memory);
+                }
+            }
+        }
+        let mut structs = structs_by_name.into_iter().map(|(_, memory)| memory).collect_vec();
+        let mut query_groups = OrderedHashMap::<String, Vec<&'static str>>::default();
+        let mut queries = info
+            .queries
+            .iter()
+            .sorted_by_key(|(name, _)| **name)
+            .map(|(name, info)| {
+                query_groups.entry(value_crate(info.debug_name())).or_default().push(name);
+                IngredientMemory::new(name, info)
+            })
+            .collect_vec();
+        sort_by_size(&mut structs);
+        sort_by_size(&mut queries);
+        Self { structs, queries, query_groups }
+    }
+
+    /// Returns the size in bytes of the interned, input and tracked structs.
+    pub fn structs_bytes(&self) -> usize {
+        self.structs.iter().map(IngredientMemory::total_bytes).sum()
+    }
+
+    /// Returns the size in bytes of the memoized query values.
+    pub fn queries_bytes(&self) -> usize {
+        self.queries.iter().map(IngredientMemory::total_bytes).sum()
+    }
+
+    /// Returns the total size in bytes of the database.
+    pub fn total_bytes(&self) -> usize {
+        self.structs_bytes() + self.queries_bytes()
+    }
+
+    /// Returns the size in bytes of the memoized values per query group, largest first.
+    ///
+    /// Queries are grouped by the compiler crate defining the type of their values, e.g.
+    /// `cairo_lang_semantic` for the queries computing semantic models.
+    pub fn query_groups_bytes(&self) -> Vec<(&str, usize)> {
+        let bytes_by_query: OrderedHashMap<_, _> =
+            self.queries.iter().map(|query| (query.name, query.total_bytes())).collect();
+        self.query_groups
+            .iter()
+            .map(|(group, queries)| {
+                (group.as_str(), queries.iter().map(|query| bytes_by_query[query]).sum())
+            })
+            .sorted_by_key(|(group, bytes)| (std::cmp::Reverse(*bytes), *group))
+            .collect()
+    }
+
+    /// Returns the structs whose number of instances grew since `earlier`, with the growth, largest
+    /// first.
+    ///
+    /// Interned values are never freed, so steady growth across revisions of a long-lived database
+    /// points at values that are interned anew on each change.
+    pub fn structs_growth(&self, earlier: &MemoryStats) -> Vec<(&'static str, usize)> {
+        let earlier_counts: OrderedHashMap<_, _> =
+            earlier.structs.iter().map(|memory| (memory.name, memory.count)).collect();
+        self.structs
+            .iter()
+            .filter_map(|memory| {
+                let earlier_count = earlier_counts.get(&memory.name).copied().unwrap_or_default();
+                let growth = memory.count.checked_sub(earlier_count)?;
+                (growth > 0).then_some((memory.name, growth))
+            })
+            .sorted_by_key(|(name, growth)| (std::cmp::Reverse(*growth), *name))
+            .collect()
+    }
+}
+impl fmt::Display for MemoryStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Total: {} bytes.", self.total_bytes())?;
+        writeln!(f, "Structs: {} bytes.", self.structs_bytes())?;
+        for memory in &self.structs {
+            writeln!(
+                f,
+                "  {}: {} bytes in {} instances.",
+                memory.name,
+                memory.total_bytes(),
+                memory.count
+            )?;
+        }
+        writeln!(f, "Queries: {} bytes.", self.queries_bytes())?;
+        for (group, bytes) in self.query_groups_bytes() {
+            writeln!(f, "  {group}: {bytes} bytes.")?;
+        }
+        for memory in &self.queries {
+            writeln!(
+                f,
+                "  {}: {} bytes in {} values.",
+                memory.name,
+                memory.total_bytes(),
+                memory.count
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Sorts the ingredients by their total size, largest first.
+fn sort_by_size(memories: &mut [IngredientMemory]) {
+    memories.sort_by_key(|memory| (std::cmp::Reverse(memory.total_bytes()), memory.name));
+}
+
+/// Returns the name of the first compiler crate in a type name, e.g. `cairo_lang_semantic` for
+/// `core::result::Result<cairo_lang_semantic::items::constant::ConstantData, ...>`.
+fn value_crate(type_name: &str) -> String {
+    type_name
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .find(|segment| segment.starts_with("cairo_lang_"))
+        .unwrap_or("other")
+        .to_string()
+}
+
+/// A compilation phase at whose end memory usage is recorded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompilationPhase {
+    /// Parsing, semantic analysis and lowering of the crates for their diagnostics.
+    Diagnostics,
+    /// Lowering of the compiled functions and generation of the Sierra program.
+    SierraGeneration,
+}
+
+/// The memory in use at the end of a compilation phase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PhaseMemory {
+    pub phase: CompilationPhase,
+    /// The memory used by the database.
+    pub stats: MemoryStats,
+    /// The peak resident memory of the process so far, in bytes, where the platform reports it.
+    pub peak_resident_bytes: Option<usize>,
+}
+
+/// Records the memory usage at the end of each phase of a compilation.
+///
+/// Clones of a profiler share the recorded phases, so a clone can be kept to read them after the
+/// compilation. The default profiler records nothing.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryProfiler(Option<Arc<Mutex<Vec<PhaseMemory>>>>);
+
+impl MemoryProfiler {
+    /// Creates a profiler which records the memory usage of each phase.
+    pub fn enabled() -> Self {
+        Self(Some(Default::default()))
+    }
+
+    /// Returns whether memory usage is recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Records the memory usage of `db` at the end of `phase`.
+    pub fn record(&self, db: &dyn Database, phase: CompilationPhase) {
+        let Some(phases) = &self.0 else {
+            return;
+        };
+        let phase_memory = PhaseMemory {
+            phase,
+            stats: MemoryStats::collect(db),
+            peak_resident_bytes: peak_resident_bytes(),
+        };
+        phases.lock().unwrap().push(phase_memory);
+    }
+
+    /// Returns the phases recorded so far, in order.
+    pub fn phases(&self) -> Vec<PhaseMemory> {
+        self.0.as_ref().map(|phases| phases.lock().unwrap().clone()).unwrap_or_default()
+    }
+}
+
+/// Returns the peak resident memory of the process, in bytes.
+#[cfg(target_os = "linux")]
+fn peak_resident_bytes() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find_map(|line| line.strip_prefix("VmHWM:"))?;
+    let kilobytes: usize = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Returns the peak resident memory of the process, in bytes.
+#[cfg(not(target_os = "linux"))]
+fn peak_resident_bytes() -> Option<usize> {
+    None
+}
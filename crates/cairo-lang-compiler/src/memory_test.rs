@@ -0,0 +1,66 @@
+use cairo_lang_semantic::test_utils::setup_test_crate;
+use indoc::indoc;
+
+use super::{CompilationPhase, MemoryProfiler, MemoryStats, value_crate};
+use crate::db::RootDatabase;
+use crate::{CompilerConfig, compile_prepared_db_program_artifact};
+
+const CONTENT: &str = indoc! {"
+    fn main() -> felt252 { 12 }
+"};
+
+#[test]
+fn records_phases() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate(&db, CONTENT);
+    let profiler = MemoryProfiler::enabled();
+    let config = CompilerConfig { memory_profiler: profiler.clone(), ..CompilerConfig::default() };
+    compile_prepared_db_program_artifact(&db, vec![crate_id], config).unwrap();
+    let phases = profiler.phases();
+    assert_eq!(
+        phases.iter().map(|phase| phase.phase).collect::<Vec<_>>(),
+        vec![CompilationPhase::Diagnostics, CompilationPhase::SierraGeneration]
+    );
+    assert!(phases[0].stats.total_bytes() <= phases[1].stats.total_bytes());
+    assert!(phases[1].stats.queries.iter().any(|query| query.count > 0));
+}
+
+#[test]
+fn disabled_profiler_records_nothing() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate(&db, CONTENT);
+    let profiler = MemoryProfiler::default();
+    let config = CompilerConfig { memory_profiler: profiler.clone(), ..CompilerConfig::default() };
+    compile_prepared_db_program_artifact(&db, vec![crate_id], config).unwrap();
+    assert!(!profiler.is_enabled());
+    assert!(profiler.phases().is_empty());
+}
+
+#[test]
+fn stats_are_sorted_and_grouped() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let empty = MemoryStats::collect(&db);
+    let crate_id = setup_test_crate(&db, CONTENT);
+    compile_prepared_db_program_artifact(&db, vec![crate_id], CompilerConfig::default()).unwrap();
+    let stats = MemoryStats::collect(&db);
+
+    assert!(stats.queries.is_sorted_by_key(|query| std::cmp::Reverse(query.total_bytes())));
+    assert!(stats.structs.is_sorted_by_key(|memory| std::cmp::Reverse(memory.total_bytes())));
+    let groups = stats.query_groups_bytes();
+    assert!(groups.iter().any(|(group, _)| *group == "cairo_lang_semantic"));
+    assert_eq!(groups.iter().map(|(_, bytes)| bytes).sum::<usize>(), stats.queries_bytes());
+    assert!(!stats.structs_growth(&empty).is_empty());
+    assert!(stats.structs_growth(&stats).is_empty());
+}
+
+#[test]
+fn value_crate_names() {
+    assert_eq!(
+        value_crate(
+            "core::result::Result<cairo_lang_semantic::items::constant::ConstantData, \
+             cairo_lang_diagnostics::DiagnosticAdded>"
+        ),
+        "cairo_lang_semantic"
+    );
+    assert_eq!(value_crate("alloc::vec::Vec<usize>"), "other");
+}
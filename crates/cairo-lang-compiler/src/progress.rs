@@ -0,0 +1,177 @@
+//! Cooperative cancellation and progress reporting for long-running compilations.
+
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use salsa::Database;
+use thiserror::Error;
+
+#[cfg(test)]
+#[path = "progress_test.rs"]
+mod test;
+
+/// The compilation was aborted using a [CancellationToken].
+#[derive(Error, Debug, Eq, PartialEq)]
+#[error("Compilation cancelled.")]
+pub struct CompilationCancelled;
+
+/// A compilation step that was completed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgressEvent {
+    /// A file was parsed.
+    FileParsed,
+    /// A function was lowered and compiled to Sierra.
+    FunctionLowered,
+    /// An entry point (executable function) was compiled to Sierra.
+    EntryPointCompiled,
+}
+
+/// The number of compilation steps completed so far.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Progress {
+    pub files_parsed: usize,
+    pub functions_lowered: usize,
+    pub entry_points_compiled: usize,
+}
+
+impl Progress {
+    fn record(&mut self, event: ProgressEvent) {
+        match event {
+            ProgressEvent::FileParsed => self.files_parsed += 1,
+            ProgressEvent::FunctionLowered => self.functions_lowered += 1,
+            ProgressEvent::EntryPointCompiled => self.entry_points_compiled += 1,
+        }
+    }
+}
+
+/// A handle for aborting a running compilation from another thread.
+///
+/// Clones of a token share the same cancellation state.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<CancellationState>);
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    /// Tokens of the database handles currently running the compilation, by their attachment id,
+    /// cancelled together with this token.
+    db_tokens: Mutex<Vec<(usize, salsa::CancellationToken)>>,
+    /// The id of the next attached database handle.
+    next_attachment_id: AtomicUsize,
+}
+
+impl CancellationToken {
+    /// Creates a new, not yet cancelled, token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the cancellation of the compilations using this token.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Relaxed);
+        for (_, db_token) in self.0.db_tokens.lock().unwrap().iter() {
+            db_token.cancel();
+        }
+    }
+
+    /// Returns whether a cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Links the token to the given database handle, so that a cancellation also interrupts the
+    /// queries currently running on it. The link is removed when the returned guard is dropped.
+    fn attach(&self, db: &dyn Database) -> AttachedDatabase<'_> {
+        let db_token = db.cancellation_token();
+        if self.is_cancelled() {
+            db_token.cancel();
+        }
+        let id = self.0.next_attachment_id.fetch_add(1, Ordering::Relaxed);
+        self.0.db_tokens.lock().unwrap().push((id, db_token));
+        AttachedDatabase { state: &self.0, id }
+    }
+}
+
+/// A database handle linked to a [CancellationToken], unlinked when dropped.
+#[must_use]
+struct AttachedDatabase<'a> {
+    state: &'a CancellationState,
+    id: usize,
+}
+
+impl Drop for AttachedDatabase<'_> {
+    fn drop(&mut self) {
+        self.state.db_tokens.lock().unwrap().retain(|(id, _)| *id != self.id);
+    }
+}
+
+/// Reports the progress of a compilation, and allows cancelling it.
+#[derive(Default)]
+pub struct ProgressReporter<'a> {
+    callback: Option<Box<dyn Fn(ProgressEvent, Progress) + Send + Sync + 'a>>,
+    cancellation: CancellationToken,
+    progress: Mutex<Progress>,
+}
+
+impl<'a> ProgressReporter<'a> {
+    /// Creates a reporter which calls `callback` after each completed step, with the event and the
+    /// accumulated progress.
+    pub fn callback(callback: impl Fn(ProgressEvent, Progress) + Send + Sync + 'a) -> Self {
+        Self { callback: Some(Box::new(callback)), ..Self::default() }
+    }
+
+    /// Makes the compilation abort once `token` is cancelled.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Returns whether progress events are consumed by a callback.
+    pub fn is_reporting(&self) -> bool {
+        self.callback.is_some()
+    }
+
+    /// Returns the progress accumulated so far.
+    pub fn progress(&self) -> Progress {
+        *self.progress.lock().unwrap()
+    }
+
+    /// Records a completed step and passes it to the callback.
+    pub fn report(&self, event: ProgressEvent) {
+        let progress = {
+            let mut progress = self.progress.lock().unwrap();
+            progress.record(event);
+            *progress
+        };
+        if let Some(callback) = &self.callback {
+            callback(event, progress);
+        }
+    }
+
+    /// Returns whether the compilation should stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Returns `Err` if the compilation should stop.
+    pub fn check_cancelled(&self) -> Result<(), CompilationCancelled> {
+        if self.is_cancelled() { Err(CompilationCancelled) } else { Ok(()) }
+    }
+
+    /// Runs `f`, which queries `db`, aborting it if the compilation is cancelled in the meantime.
+    pub fn run<T>(
+        &self,
+        db: &dyn Database,
+        f: impl FnOnce() -> T,
+    ) -> Result<T, CompilationCancelled> {
+        self.check_cancelled()?;
+        let _attached = self.cancellation.attach(db);
+        match salsa::Cancelled::catch(AssertUnwindSafe(f)) {
+            Ok(value) => Ok(value),
+            Err(_) if self.is_cancelled() => Err(CompilationCancelled),
+            // Cancellations not requested through the token are left to the caller to handle.
+            Err(cancelled) => std::panic::resume_unwind(Box::new(cancelled)),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::Mutex;
+
+use cairo_lang_semantic::test_utils::setup_test_crate;
+use indoc::indoc;
+
+use super::{CancellationToken, CompilationCancelled, Progress, ProgressEvent, ProgressReporter};
+use crate::db::RootDatabase;
+use crate::diagnostics::DiagnosticsReporter;
+use crate::{CompilerConfig, compile_prepared_db, compile_prepared_db_program_artifact};
+
+const CONTENT: &str = indoc! {"
+    #[inline(never)]
+    fn x() -> felt252 { 12 }
+
+    fn main() -> felt252 { x() }
+"};
+
+#[test]
+fn reports_progress() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate(&db, CONTENT);
+    let events = Mutex::new(vec![]);
+    let config = CompilerConfig {
+        diagnostics_reporter: DiagnosticsReporter::stderr()
+            .with_crates(&[crate_id.long(&db).clone().into_crate_input(&db)]),
+        progress_reporter: ProgressReporter::callback(|event, _| {
+            events.lock().unwrap().push(event)
+        }),
+        ..CompilerConfig::default()
+    };
+    compile_prepared_db_program_artifact(&db, vec![crate_id], config).unwrap();
+    let events = events.into_inner().unwrap();
+    assert_eq!(events.iter().filter(|e| **e == ProgressEvent::FileParsed).count(), 1);
+    // `main`, `x`, and the other free functions of the crate.
+    assert!(events.iter().filter(|e| **e == ProgressEvent::FunctionLowered).count() >= 2);
+    assert!(!events.contains(&ProgressEvent::EntryPointCompiled));
+}
+
+#[test]
+fn reports_progress_of_prepared_db_compilation() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate(&db, CONTENT);
+    let token = CancellationToken::new();
+    let events = Mutex::new(vec![]);
+    let config = CompilerConfig {
+        diagnostics_reporter: DiagnosticsReporter::stderr()
+            .with_crates(&[crate_id.long(&db).clone().into_crate_input(&db)]),
+        progress_reporter: ProgressReporter::callback(|event, _| {
+            events.lock().unwrap().push(event)
+        })
+        .with_cancellation(token.clone()),
+        ..CompilerConfig::default()
+    };
+    compile_prepared_db(&db, vec![crate_id], config).unwrap();
+    let events = events.into_inner().unwrap();
+    assert_eq!(events.iter().filter(|e| **e == ProgressEvent::FileParsed).count(), 1);
+    assert!(events.iter().filter(|e| **e == ProgressEvent::FunctionLowered).count() >= 2);
+    // The database handles are detached from the token once the compilation is done.
+    assert!(token.0.db_tokens.lock().unwrap().is_empty());
+}
+
+#[test]
+fn accumulates_progress() {
+    let reporter = ProgressReporter::default();
+    reporter.report(ProgressEvent::FileParsed);
+    reporter.report(ProgressEvent::FunctionLowered);
+    reporter.report(ProgressEvent::FunctionLowered);
+    assert_eq!(
+        reporter.progress(),
+        Progress { files_parsed: 1, functions_lowered: 2, entry_points_compiled: 0 }
+    );
+}
+
+#[test]
+fn cancelled_compilation_fails() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = setup_test_crate(&db, CONTENT);
+    let token = CancellationToken::new();
+    token.cancel();
+    let config = CompilerConfig {
+        progress_reporter: ProgressReporter::default().with_cancellation(token),
+        ..CompilerConfig::default()
+    };
+    let err = compile_prepared_db_program_artifact(&db, vec![crate_id], config).unwrap_err();
+    assert_eq!(err.downcast_ref::<CompilationCancelled>(), Some(&CompilationCancelled));
+}
@@ -1,15 +1,21 @@
+use std::collections::BTreeSet;
 use std::ffi::OsStr;
 use std::path::Path;
 
 use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_filesystem::backend::{FsBackend, OsFsBackend};
+use cairo_lang_filesystem::cfg::CfgSet;
 use cairo_lang_filesystem::db::{
     CORELIB_CRATE_NAME, CrateConfiguration, CrateIdentifier, CrateSettings, FilesGroup,
+    update_crate_cfg,
 };
+use cairo_lang_filesystem::flag::FlagsError;
 use cairo_lang_filesystem::ids::{CrateId, CrateInput, CrateLongId, Directory, SmolStrId};
 use cairo_lang_filesystem::{override_file_content, set_crate_config};
 pub use cairo_lang_project::*;
 use cairo_lang_utils::Intern;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use salsa::Database;
 
 #[derive(thiserror::Error, Debug)]
@@ -22,6 +28,28 @@ pub enum ProjectError {
     BadPath { path: String },
     #[error("Failed to load project config: {0}")]
     LoadProjectError(DeserializationError),
+    #[error("Crate `{crate_identifier}` is marked as a main crate, but it has no root.")]
+    UnknownMainCrate { crate_identifier: String },
+    #[error("Couldn't find the root of crate `{crate_identifier}`: {path} is not a directory.")]
+    NoSuchCrateRoot { crate_identifier: String, path: String },
+    #[error("Crate `{crate_identifier}` depends on `{dependency}`, which is not in the project.")]
+    UnknownDependency { crate_identifier: String, dependency: String },
+    #[error("Got `cfg` settings for crate `{crate_name}`, which is not in the project.")]
+    UnknownCfgCrate { crate_name: String },
+    #[error("Invalid flags for crate `{crate_identifier}`: {error}")]
+    InvalidCrateFlags { crate_identifier: String, error: FlagsError },
+}
+
+/// The `#[cfg(...)]` settings of a crate, applied on top of its settings in the project.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CrateCfgSettings {
+    /// The options added to the `#[cfg(...)]` configuration of the crate, e.g.
+    /// `Cfg::kv("feature", "my_feature")`.
+    pub cfg_set: CfgSet,
+    /// The names of the options the crate is expected to check, added to
+    /// [CrateSettings::expected_cfg_names]. Checking other names is reported as a warning.
+    /// If `None`, the names are checked only if the project sets expected names for the crate.
+    pub expected_cfg_names: Option<BTreeSet<String>>,
 }
 
 /// Sets up the DB to compile the file at the given path.
@@ -36,11 +64,13 @@ pub fn setup_single_file_project(
             return Err(ProjectError::BadFileExtension);
         }
     }
-    if !path.exists() {
+    if !db.fs_backend().exists(path) {
         return Err(ProjectError::NoSuchFile { path: path.to_string_lossy().to_string() });
     }
     let bad_path_err = || ProjectError::BadPath { path: path.to_string_lossy().to_string() };
-    let canonical = path.canonicalize().map_err(|_| bad_path_err())?;
+    // Paths of other filesystem backends may not exist on disk, and are only made absolute.
+    let canonical =
+        path.canonicalize().or_else(|_| std::path::absolute(path)).map_err(|_| bad_path_err())?;
     let file_dir = canonical.parent().ok_or_else(bad_path_err)?;
     let file_stem = path.file_stem().and_then(OsStr::to_str).ok_or_else(bad_path_err)?;
     if file_stem == "lib" {
@@ -95,11 +125,31 @@ pub fn update_crate_root(
     );
 }
 
+/// Applies the `#[cfg(...)]` settings to the crates in the DB, by crate name.
+/// Must be called after the crates are set up.
+pub fn update_crate_cfgs(
+    db: &mut dyn Database,
+    crate_cfgs: &OrderedHashMap<String, CrateCfgSettings>,
+) -> Result<(), ProjectError> {
+    for (crate_name, cfg_settings) in crate_cfgs.iter() {
+        if !db.crates().iter().any(|crate_id| crate_id.long(db).name().long(db) == crate_name) {
+            return Err(ProjectError::UnknownCfgCrate { crate_name: crate_name.clone() });
+        }
+        update_crate_cfg(
+            db,
+            crate_name,
+            &cfg_settings.cfg_set,
+            cfg_settings.expected_cfg_names.as_ref(),
+        );
+    }
+    Ok(())
+}
+
 /// Sets up the DB to compile the project at the given path.
 /// The path can be either a directory with a Cairo project file or a `.cairo` file.
 /// Returns the IDs of the project crates.
 pub fn setup_project(db: &mut dyn Database, path: &Path) -> Result<Vec<CrateInput>, ProjectError> {
-    if path.is_dir() {
+    if db.fs_backend().is_dir(path) {
         let config = ProjectConfig::from_directory(path).map_err(ProjectError::LoadProjectError)?;
         let main_crate_ids: Vec<_> = get_main_crate_ids_from_project(db, &config)
             .into_iter()
@@ -114,11 +164,20 @@ pub fn setup_project(db: &mut dyn Database, path: &Path) -> Result<Vec<CrateInpu
 
 /// Checks that the given path is a valid compiler path.
 pub fn check_compiler_path(single_file: bool, path: &Path) -> anyhow::Result<()> {
-    if path.is_file() {
+    check_compiler_path_in(&OsFsBackend, single_file, path)
+}
+
+/// Checks that the given path is a valid compiler path in the given filesystem.
+pub fn check_compiler_path_in(
+    fs: &dyn FsBackend,
+    single_file: bool,
+    path: &Path,
+) -> anyhow::Result<()> {
+    if fs.is_file(path) {
         if !single_file {
             anyhow::bail!("The given path is a file, but --single-file was not supplied.");
         }
-    } else if path.is_dir() {
+    } else if fs.is_dir(path) {
         if single_file {
             anyhow::bail!("The given path is a directory, but --single-file was supplied.");
         }
@@ -128,14 +187,62 @@ pub fn check_compiler_path(single_file: bool, path: &Path) -> anyhow::Result<()>
     Ok(())
 }
 
+/// Validates that a project config is self-contained: main crates and crate dependencies refer to
+/// crates of the project, and all crate roots exist.
+/// The core crate is allowed as a dependency even if it is not part of the project.
+/// The crate roots are looked up in `fs`, usually [FilesGroup::fs_backend].
+pub fn validate_project_config(
+    fs: &dyn FsBackend,
+    config: &ProjectConfig,
+) -> Result<(), ProjectError> {
+    let crate_roots = &config.content.crate_roots;
+    for crate_identifier in config.main_crates() {
+        if !crate_roots.contains_key(crate_identifier) {
+            return Err(ProjectError::UnknownMainCrate {
+                crate_identifier: crate_identifier.clone().into(),
+            });
+        }
+    }
+    for (crate_identifier, root) in crate_roots.iter() {
+        let path = config.absolute_crate_root(root);
+        if !fs.is_dir(&path) {
+            return Err(ProjectError::NoSuchCrateRoot {
+                crate_identifier: crate_identifier.clone().into(),
+                path: path.to_string_lossy().to_string(),
+            });
+        }
+        let settings = config.content.crates_config.get(crate_identifier);
+        settings.flags.validate_crate_overrides().map_err(|error| {
+            ProjectError::InvalidCrateFlags {
+                crate_identifier: crate_identifier.clone().into(),
+                error,
+            }
+        })?;
+        for (dependency, dependency_settings) in &settings.dependencies {
+            if dependency == CORELIB_CRATE_NAME {
+                continue;
+            }
+            let dependency_identifier = CrateIdentifier::from(
+                dependency_settings.discriminator.as_deref().unwrap_or(dependency),
+            );
+            if !crate_roots.contains_key(&dependency_identifier) {
+                return Err(ProjectError::UnknownDependency {
+                    crate_identifier: crate_identifier.clone().into(),
+                    dependency: dependency.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn get_main_crate_ids_from_project<'db>(
     db: &'db dyn Database,
     config: &ProjectConfig,
 ) -> Vec<CrateId<'db>> {
     config
-        .content
-        .crate_roots
-        .keys()
+        .main_crates()
+        .into_iter()
         .map(|crate_identifier| get_crate_id_and_settings(db, crate_identifier, config).0)
         .collect()
 }
@@ -1,14 +1,32 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
 use cairo_lang_defs::plugin::{MacroPlugin, MacroPluginMetadata, PluginResult};
-use cairo_lang_filesystem::ids::SmolStrId;
+use cairo_lang_filesystem::backend::{MemoryFsBackend, OsFsBackend};
+use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
+use cairo_lang_filesystem::db::{CrateSettings, DependencySettings, FilesGroup};
+use cairo_lang_filesystem::detect::detect_corelib;
+use cairo_lang_filesystem::flag::CompilerFlags;
+use cairo_lang_filesystem::ids::{CrateId, CrateInput, Directory, SmolStrId};
+use cairo_lang_lowering::optimizations::config::Optimizations;
+use cairo_lang_lowering::utils::InliningStrategy;
 use cairo_lang_semantic::plugin::PluginSuite;
 use cairo_lang_semantic::test_utils::setup_test_crate;
+use cairo_lang_sierra_generator::debug_info::{VARIABLE_LOCATIONS_NAMESPACE, VariableLocations};
 use cairo_lang_syntax::node::ast::ModuleItem;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use indoc::indoc;
 use salsa::Database;
 use smol_str::SmolStr;
 
-use crate::db::RootDatabase;
-use crate::{CompilerConfig, compile_prepared_db_program_artifact};
+use crate::db::{RootDatabase, RootDatabaseBuilder};
+use crate::diagnostics::DiagnosticsReporter;
+use crate::project::{
+    CrateCfgSettings, ProjectConfig, check_compiler_path_in, setup_project, validate_project_config,
+};
+use crate::{
+    CompilerConfig, DebugInfoLevel, compile_cairo_project, compile_prepared_db_program_artifact,
+};
 
 #[derive(Debug, Default)]
 pub struct MockExecutablePlugin {}
@@ -61,3 +79,242 @@ fn can_collect_executables() {
     assert_eq!(artifact.program.funcs[0].id.debug_name, Some(SmolStr::new("test::test")));
     assert_eq!(artifact.program.funcs[1].id.debug_name, Some(SmolStr::new("test::x")));
 }
+
+#[test]
+fn can_compile_multi_crate_project_config() {
+    let base_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data/multi_crate");
+    let app_settings = CrateSettings {
+        dependencies: [(
+            "math".to_string(),
+            DependencySettings { discriminator: Some("math".into()) },
+        )]
+        .into(),
+        ..Default::default()
+    };
+    let project_config = ProjectConfig::new(base_path)
+        .with_crate("app", "app", Some(app_settings))
+        .with_crate("math", "math", None)
+        .with_main_crates(["app"]);
+    let config = CompilerConfig { replace_ids: true, ..CompilerConfig::default() };
+    let program = compile_cairo_project(project_config, config, InliningStrategy::Default).unwrap();
+    let function_names: Vec<_> =
+        program.funcs.iter().map(|f| f.id.debug_name.clone().unwrap()).collect();
+    assert_eq!(function_names, vec![SmolStr::new("app::main"), SmolStr::new("math::double")]);
+}
+
+#[test]
+fn rejects_unknown_dependency() {
+    let base_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data/multi_crate");
+    let app_settings = CrateSettings {
+        dependencies: [("math".to_string(), DependencySettings { discriminator: None })].into(),
+        ..Default::default()
+    };
+    let project_config = ProjectConfig::new(base_path).with_crate("app", "app", Some(app_settings));
+    assert_eq!(
+        validate_project_config(&OsFsBackend, &project_config).unwrap_err().to_string(),
+        "Crate `app` depends on `math`, which is not in the project."
+    );
+}
+
+#[test]
+fn rejects_program_wide_crate_flags() {
+    let base_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data/multi_crate");
+    let app_settings = CrateSettings {
+        flags: CompilerFlags { unsafe_panic: Some(true), ..Default::default() },
+        ..Default::default()
+    };
+    let project_config = ProjectConfig::new(base_path).with_crate("app", "app", Some(app_settings));
+    assert_eq!(
+        validate_project_config(&OsFsBackend, &project_config).unwrap_err().to_string(),
+        "Invalid flags for crate `app`: Flag `unsafe_panic` affects the whole program, and cannot \
+         be overridden per crate."
+    );
+}
+
+#[test]
+fn project_in_fs_backend() {
+    let backend = MemoryFsBackend::new()
+        .with_file("/virtual/single.cairo", "fn main() {}\n")
+        .with_file("/virtual/project/src/lib.cairo", "");
+    assert!(check_compiler_path_in(&backend, true, Path::new("/virtual/single.cairo")).is_ok());
+    assert!(check_compiler_path_in(&backend, false, Path::new("/virtual/project")).is_ok());
+    assert!(check_compiler_path_in(&backend, true, Path::new("/virtual/missing.cairo")).is_err());
+
+    let mut db = RootDatabase::builder().with_fs_backend(Arc::new(backend)).build().unwrap();
+    let crate_inputs = setup_project(&mut db, Path::new("/virtual/single.cairo")).unwrap();
+    let crate_id = CrateInput::into_crate_ids(&db, crate_inputs)[0];
+    let root = Directory::Real("/virtual".into());
+    assert_eq!(db.crate_config(crate_id).unwrap().root, root);
+    assert_eq!(db.file_content(root.file(&db, "single.cairo")), Some("fn main() {}\n"));
+    assert_eq!(
+        setup_project(&mut db, Path::new("/virtual/missing.cairo")).unwrap_err().to_string(),
+        "Couldn't read /virtual/missing.cairo: No such file."
+    );
+}
+
+#[test]
+fn crate_cfgs() {
+    let base_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data/cfg_features");
+    let compile = |crate_name: &str, cfg_set: CfgSet| {
+        let project_config = ProjectConfig::new(base_path.clone())
+            .with_crate("app", "app", None)
+            .with_main_crates(["app"]);
+        let mut diagnostics = String::new();
+        let cfg_settings =
+            CrateCfgSettings { cfg_set, expected_cfg_names: Some(["feature".to_string()].into()) };
+        let config = CompilerConfig {
+            diagnostics_reporter: DiagnosticsReporter::write_to_string(&mut diagnostics)
+                .allow_warnings(),
+            replace_ids: true,
+            crate_cfgs: [(crate_name.to_string(), cfg_settings)].into_iter().collect(),
+            ..CompilerConfig::default()
+        };
+        let program = compile_cairo_project(project_config, config, InliningStrategy::Default);
+        (program, diagnostics)
+    };
+    let returned_value = |cfg_set| {
+        let (program, diagnostics) = compile("app", cfg_set);
+        assert!(diagnostics.contains("Unexpected `cfg` name: `unknown`."), "{diagnostics}");
+        program.unwrap().to_string().contains("Const<felt252, 2>")
+    };
+
+    assert!(returned_value(CfgSet::from_iter([Cfg::kv("feature", "double")])));
+    assert!(!returned_value(CfgSet::new()));
+    assert_eq!(
+        compile("missing", CfgSet::new()).0.unwrap_err().to_string(),
+        "Got `cfg` settings for crate `missing`, which is not in the project."
+    );
+}
+
+/// Collects the files under `dir`, with their `/` separated paths relative to it.
+fn collect_files(dir: &Path, prefix: &str, files: &mut Vec<(String, String)>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let name = format!("{prefix}{}", path.file_name().unwrap().to_str().unwrap());
+        if path.is_dir() {
+            collect_files(&path, &format!("{name}/"), files);
+        } else {
+            files.push((name, std::fs::read_to_string(&path).unwrap()));
+        }
+    }
+}
+
+#[test]
+fn in_memory_corelib() {
+    let mut corelib_files = vec![];
+    collect_files(&detect_corelib().unwrap(), "", &mut corelib_files);
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../examples/fib.cairo");
+    let compile = |builder: &mut RootDatabaseBuilder| {
+        let mut db = builder.build().unwrap();
+        let crate_inputs = setup_project(&mut db, &path).unwrap();
+        let crate_ids = CrateInput::into_crate_ids(&db, crate_inputs);
+        let config = CompilerConfig { replace_ids: true, ..CompilerConfig::default() };
+        let program = compile_prepared_db_program_artifact(&db, crate_ids, config).unwrap().program;
+        let core_root = db.crate_config(CrateId::core(&db)).unwrap().root.clone();
+        (program, matches!(core_root, Directory::Virtual { .. }))
+    };
+
+    let (program, is_virtual) =
+        compile(RootDatabase::builder().detect_corelib().with_in_memory_corelib(corelib_files));
+    assert!(is_virtual);
+    assert_eq!((program, false), compile(RootDatabase::builder().detect_corelib()));
+}
+
+#[test]
+fn debug_info_levels() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../examples/fib.cairo");
+    let mut db = RootDatabase::builder()
+        .detect_corelib()
+        .with_optimizations(Optimizations::enabled_with_default_movable_functions(
+            InliningStrategy::Avoid,
+        ))
+        .build()
+        .unwrap();
+    let crate_inputs = setup_project(&mut db, &path).unwrap();
+    let crate_ids = CrateInput::into_crate_ids(&db, crate_inputs);
+    let annotations = |debug_info| {
+        let config = CompilerConfig { debug_info, ..CompilerConfig::default() };
+        compile_prepared_db_program_artifact(&db, crate_ids.clone(), config)
+            .unwrap()
+            .debug_info
+            .unwrap()
+            .annotations
+    };
+
+    assert!(annotations(DebugInfoLevel::None).is_empty());
+    assert_eq!(
+        annotations(DebugInfoLevel::LineTables).keys().collect::<Vec<_>>(),
+        vec!["github.com/software-mansion/cairo-coverage"]
+    );
+
+    let annotations = annotations(DebugInfoLevel::Full);
+    let variable_locations: VariableLocations =
+        serde_json::from_value(annotations[VARIABLE_LOCATIONS_NAMESPACE].clone()).unwrap();
+    let variable_names: OrderedHashSet<_> = variable_locations
+        .entries
+        .iter()
+        .flat_map(|entry| &entry.variables)
+        .map(|variable| variable.name.as_str())
+        .collect();
+    for name in ["a", "b", "n"] {
+        assert!(variable_names.contains(name), "Missing `{name}` in {variable_names:?}.");
+    }
+    // The parameters are on the stack of the caller at the entry of the function.
+    let entry = variable_locations.entry_at(0).unwrap();
+    let mut cells: Vec<_> = entry
+        .variables
+        .iter()
+        .map(|variable| (variable.name.as_str(), &variable.cells[..]))
+        .collect();
+    cells.sort();
+    assert_eq!(
+        cells,
+        vec![
+            ("a", &["[fp + -5]".to_string()][..]),
+            ("b", &["[fp + -4]".to_string()][..]),
+            ("n", &["[fp + -3]".to_string()][..]),
+        ]
+    );
+}
+
+#[test]
+fn error_tolerant_compilation() {
+    let content = indoc! {r#"
+        fn main() -> felt252 { helper() + 1 }
+
+        #[inline(never)]
+        fn helper() -> felt252 { undefined_variable }
+    "#};
+    let compile = |error_tolerant: bool| {
+        let mut builder = RootDatabase::builder();
+        builder.detect_corelib();
+        if error_tolerant {
+            builder.with_error_tolerance();
+        }
+        let db = builder.build().unwrap();
+        let crate_id = setup_test_crate(&db, content);
+        let mut diagnostics = String::new();
+        let config = CompilerConfig {
+            diagnostics_reporter: DiagnosticsReporter::write_to_string(&mut diagnostics)
+                .with_crates(&[crate_id.long(&db).clone().into_crate_input(&db)]),
+            replace_ids: true,
+            error_tolerant,
+            ..CompilerConfig::default()
+        };
+        let program = compile_prepared_db_program_artifact(&db, vec![crate_id], config)
+            .map(|artifact| artifact.program);
+        (program, diagnostics)
+    };
+
+    let (program, diagnostics) = compile(false);
+    assert_eq!(program.unwrap_err().to_string(), "Compilation failed.");
+    assert!(diagnostics.contains("Identifier not found."), "{diagnostics}");
+
+    let (program, diagnostics) = compile(true);
+    let function_names: Vec<_> =
+        program.unwrap().funcs.iter().map(|f| f.id.debug_name.clone().unwrap()).collect();
+    // The stub of `helper` panics with a constant message.
+    assert_eq!(function_names[..2], [SmolStr::new("test::main"), SmolStr::new("test::helper")]);
+    assert!(function_names[2].starts_with("core::panic_with_const_felt252::"));
+    assert!(diagnostics.contains("Identifier not found."), "{diagnostics}");
+}
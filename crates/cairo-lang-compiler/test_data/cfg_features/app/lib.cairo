@@ -0,0 +1,18 @@
+fn main() -> felt252 {
+    value()
+}
+
+#[cfg(feature: 'double')]
+#[inline(never)]
+fn value() -> felt252 {
+    2
+}
+
+#[cfg(not(feature: 'double'))]
+#[inline(never)]
+fn value() -> felt252 {
+    1
+}
+
+#[cfg(unknown)]
+fn unused() {}
@@ -0,0 +1,8 @@
+#[inline(never)]
+pub fn double(x: felt252) -> felt252 {
+    x + x
+}
+
+pub fn unused() -> felt252 {
+    0
+}
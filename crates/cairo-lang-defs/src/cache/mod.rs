@@ -61,7 +61,7 @@ impl CachedCrateMetadata {
         });
         let compiler_version = env!("CARGO_PKG_VERSION").to_string();
         let global_flags =
-            db.flags().iter().map(|(flag_id, flag)| (flag_id.long(db).0.clone(), *flag)).collect();
+            db.compiler_flags().iter().map(|flag| (flag.name().to_string(), flag)).collect();
         Self { settings, compiler_version, global_flags }
     }
 }
@@ -1,4 +1,5 @@
 use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use cairo_lang_diagnostics::{
@@ -10,9 +11,10 @@ use cairo_lang_filesystem::ids::{
 };
 use cairo_lang_parser::db::ParserGroup;
 use cairo_lang_syntax::attribute::consts::{
-    ALLOW_ATTR, ALLOW_ATTR_ATTR, DEPRECATED_ATTR, FEATURE_ATTR, FMT_SKIP_ATTR,
-    IMPLICIT_PRECEDENCE_ATTR, INLINE_ATTR, INTERNAL_ATTR, MUST_USE_ATTR, PATH_ATTR, PHANTOM_ATTR,
-    STARKNET_INTERFACE_ATTR, UNSTABLE_ATTR,
+    ALLOW_ATTR, ALLOW_ATTR_ATTR, DENY_ATTR, DEPRECATED_ATTR, FALLBACK_ATTR, FEATURE_ATTR,
+    FMT_SKIP_ATTR, IMPLICIT_PRECEDENCE_ATTR, INLINE_ATTR, INTERNAL_ATTR, MUST_USE_ATTR,
+    NO_EARLY_UNSAFE_PANIC_ATTR, PATH_ATTR, PHANTOM_ATTR, STARKNET_INTERFACE_ATTR, UNSTABLE_ATTR,
+    WARN_ATTR,
 };
 use cairo_lang_syntax::attribute::structured::AttributeStructurize;
 use cairo_lang_syntax::node::ast::MaybeModuleBody;
@@ -27,7 +29,10 @@ use salsa::{Database, Setter};
 
 use crate::cache::{DefCacheLoadingData, load_cached_crate_modules};
 use crate::ids::*;
-use crate::plugin::{DynGeneratedFileAuxData, MacroPlugin, MacroPluginMetadata, PluginDiagnostic};
+use crate::plugin::{
+    DynGeneratedFileAuxData, InlineMacroExprPlugin, MacroPlugin, MacroPluginMetadata,
+    PluginDiagnostic,
+};
 use crate::plugin_utils::try_extract_unnamed_arg;
 
 #[salsa::input]
@@ -184,6 +189,18 @@ pub trait DefsGroup: Database {
         module_dir(self.as_dyn_database(), module_id)
     }
 
+    /// A stable hash of the module and its submodules, recursively: of the contents of their
+    /// files, and of the settings and plugin versions of the crate. Serves as the key of caches of
+    /// artifacts of the module, as it is stable across runs.
+    fn module_fingerprint<'db>(&'db self, module_id: ModuleId<'db>) -> Maybe<u64> {
+        module_fingerprint(self.as_dyn_database(), (), module_id)
+    }
+    /// A stable hash of the crate - the fingerprint of its root module.
+    /// See [DefsGroup::module_fingerprint].
+    fn crate_fingerprint<'db>(&'db self, crate_id: CrateId<'db>) -> Maybe<u64> {
+        self.module_fingerprint(ModuleId::CrateRoot(crate_id))
+    }
+
     // File to module.
     fn crate_modules<'db>(&'db self, crate_id: CrateId<'db>) -> &'db [ModuleId<'db>] {
         crate_modules(self.as_dyn_database(), crate_id)
@@ -459,10 +476,14 @@ fn allowed_attributes<'db>(
         DEPRECATED_ATTR,
         INTERNAL_ATTR,
         ALLOW_ATTR,
+        WARN_ATTR,
+        DENY_ATTR,
         ALLOW_ATTR_ATTR,
         FEATURE_ATTR,
         PHANTOM_ATTR,
         IMPLICIT_PRECEDENCE_ATTR,
+        NO_EARLY_UNSAFE_PANIC_ATTR,
+        FALLBACK_ATTR,
         FMT_SKIP_ATTR,
         PATH_ATTR,
         // TODO(orizi): Remove this once `starknet` is removed from corelib.
@@ -480,7 +501,7 @@ fn allowed_attributes<'db>(
 // TODO(eytan-starkware): Untrack this
 #[salsa::tracked(returns(ref))]
 fn allowed_statement_attributes<'db>(db: &'db dyn Database) -> OrderedHashSet<SmolStrId<'db>> {
-    let all_attributes = [FMT_SKIP_ATTR, ALLOW_ATTR, FEATURE_ATTR];
+    let all_attributes = [FMT_SKIP_ATTR, ALLOW_ATTR, WARN_ATTR, DENY_ATTR, FEATURE_ATTR];
     OrderedHashSet::from_iter(all_attributes.map(|attr| SmolStrId::from(db, attr)))
 }
 
@@ -607,6 +628,37 @@ fn module_dir<'db>(db: &'db dyn Database, module_id: ModuleId<'db>) -> Maybe<&'d
     module_dir_helper(db, (), module_id).maybe_as_ref()
 }
 
+/// Returns a stable hash of the settings and the plugins of the crate.
+#[salsa::tracked]
+fn crate_plugins_fingerprint<'db>(db: &'db dyn Database, crate_id: CrateId<'db>) -> u64 {
+    let mut hasher = xxhash_rust::xxh3::Xxh3::default();
+    db.crate_settings_fingerprint(crate_id).hash(&mut hasher);
+    for plugin in db.crate_macro_plugins(crate_id) {
+        plugin.long(db).version().hash(&mut hasher);
+    }
+    for (name, plugin) in db.crate_inline_macro_plugins(crate_id).iter() {
+        name.hash(&mut hasher);
+        plugin.long(db).version().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[salsa::tracked]
+fn module_fingerprint<'db>(
+    db: &'db dyn Database,
+    _tracked: Tracked,
+    module_id: ModuleId<'db>,
+) -> Maybe<u64> {
+    let mut hasher = xxhash_rust::xxh3::Xxh3::default();
+    crate_plugins_fingerprint(db, module_id.owning_crate(db)).hash(&mut hasher);
+    db.file_fingerprint(db.module_main_file(module_id)?).hash(&mut hasher);
+    for submodule_id in db.module_submodules_ids(module_id)? {
+        submodule_id.name(db).long(db).hash(&mut hasher);
+        db.module_fingerprint(ModuleId::Submodule(*submodule_id)).ok().hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
+
 /// Appends all the modules under the given module, including nested modules.
 fn collect_modules_under<'db>(
     db: &'db dyn Database,
@@ -1153,16 +1205,17 @@ fn module_sub_files<'db>(
     // TODO(orizi): Actually extract the allowed features per module.
     let allowed_features = Default::default();
 
-    let cfg_set = db
-        .crate_config(crate_id)
-        .and_then(|cfg| cfg.settings.cfg_set.as_ref())
-        .unwrap_or(db.cfg_set());
+    let crate_settings = db.crate_config(crate_id).map(|cfg| &cfg.settings);
+    let cfg_set =
+        crate_settings.and_then(|settings| settings.cfg_set.as_ref()).unwrap_or(db.cfg_set());
     let edition = db
         .crate_config(module_id.owning_crate(db))
         .map(|cfg| cfg.settings.edition)
         .unwrap_or_default();
     let metadata = MacroPluginMetadata {
         cfg_set,
+        expected_cfg_names: crate_settings
+            .and_then(|settings| settings.expected_cfg_names.as_ref()),
         declared_derives: db.declared_derives(crate_id),
         allowed_features: &allowed_features,
         edition,
@@ -1,6 +1,8 @@
 use std::fmt;
 
 use cairo_lang_debug::DebugWithDb;
+use cairo_lang_diagnostics::DiagnosticNote;
+use cairo_lang_filesystem::db::{get_parent_and_mapping, translate_location};
 use cairo_lang_filesystem::ids::{FileId, SpanInFile};
 use cairo_lang_filesystem::span::{TextSpan, TextWidth};
 use cairo_lang_proc_macros::HeapSize;
@@ -86,3 +88,51 @@ impl<'db> DebugWithDb<'db> for StableLocation<'db> {
         diag_location.fmt_location(f, db)
     }
 }
+
+/// A step in the expansion of plugin generated code: a location in a generated file, along with the
+/// code the file was generated from.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ExpansionFrame<'db> {
+    /// The location in the generated file.
+    pub generated: SpanInFile<'db>,
+    /// The code the file was generated from, e.g. the item an attribute was applied on, or an
+    /// inline macro call.
+    pub call_site: SpanInFile<'db>,
+}
+
+/// Returns the expansion backtrace of a location, from the innermost generated file to the
+/// outermost one. The backtrace is empty if the location is in user code.
+pub fn expansion_backtrace<'db>(
+    db: &'db dyn Database,
+    mut location: SpanInFile<'db>,
+) -> Vec<ExpansionFrame<'db>> {
+    let mut frames = vec![];
+    while let Some((call_site, code_mappings)) = get_parent_and_mapping(db, location.file_id) {
+        frames.push(ExpansionFrame { generated: location, call_site });
+        location = SpanInFile {
+            file_id: call_site.file_id,
+            span: translate_location(code_mappings, location.span).unwrap_or(call_site.span),
+        };
+    }
+    frames
+}
+
+/// Returns diagnostic notes showing the expansion backtrace of a location - for each generated
+/// file, the generated code at the location and the code it was expanded from.
+pub fn expansion_backtrace_notes<'db>(
+    db: &'db dyn Database,
+    location: SpanInFile<'db>,
+) -> Vec<DiagnosticNote<'db>> {
+    expansion_backtrace(db, location)
+        .into_iter()
+        .flat_map(|frame| {
+            [
+                DiagnosticNote::text_only(format!(
+                    "in the generated code:\n  --> {:?}",
+                    frame.generated.debug(db)
+                )),
+                DiagnosticNote::with_location("expanded from".into(), frame.call_site),
+            ]
+        })
+        .collect()
+}
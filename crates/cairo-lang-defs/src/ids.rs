@@ -401,6 +401,10 @@ impl MacroPlugin for MacroPluginLongId {
         self.0.phantom_type_attributes(db)
     }
 
+    fn version(&self) -> String {
+        self.0.version()
+    }
+
     fn plugin_type_id(&self) -> std::any::TypeId {
         // Ensure the implementation for `MacroPluginLongId` returns the same value
         // as the underlying plugin object.
@@ -444,6 +448,10 @@ impl InlineMacroExprPlugin for InlineMacroExprPluginLongId {
         self.0.documentation()
     }
 
+    fn version(&self) -> String {
+        self.0.version()
+    }
+
     fn plugin_type_id(&self) -> std::any::TypeId {
         // Ensure the implementation for `InlineMacroExprPluginLongId` returns the same value
         // as the underlying plugin object.
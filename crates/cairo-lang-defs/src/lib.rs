@@ -10,3 +10,4 @@ pub mod plugin;
 pub mod plugin_utils;
 #[cfg(test)]
 mod test;
+pub mod token_stream;
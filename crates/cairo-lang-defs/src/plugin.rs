@@ -1,4 +1,5 @@
 use std::any::{self, Any};
+use std::collections::BTreeSet;
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::sync::Arc;
@@ -171,6 +172,9 @@ impl<'db> PluginDiagnostic<'db> {
 pub struct MacroPluginMetadata<'a> {
     /// Config set of the crate to which the current item belongs.
     pub cfg_set: &'a CfgSet,
+    /// The `#[cfg(...)]` names the crate to which the current item belongs is expected to check,
+    /// if they are checked (see [cairo_lang_filesystem::db::CrateSettings::expected_cfg_names]).
+    pub expected_cfg_names: Option<&'a BTreeSet<String>>,
     /// The possible derives declared by any plugin.
     pub declared_derives: &'a OrderedHashSet<SmolStrId<'a>>,
     /// The allowed features at the macro activation site.
@@ -222,6 +226,14 @@ pub trait MacroPlugin: std::fmt::Debug + Sync + Send + Any {
         Vec::new()
     }
 
+    /// Identifies the version and configuration of the plugin, i.e. the code it generates, for
+    /// the fingerprints of the crates it is used in. Changing it invalidates caches keyed by them.
+    /// Defaults to the debug representation of the plugin, as the compiler version is a part of the
+    /// fingerprints anyway.
+    fn version(&self) -> String {
+        format!("{self:?}")
+    }
+
     /// A `TypeId` of the plugin, used to compare the concrete types
     /// of plugins given as trait objects.
     fn plugin_type_id(&self) -> any::TypeId {
@@ -253,6 +265,14 @@ pub trait InlineMacroExprPlugin: std::fmt::Debug + Sync + Send + Any {
         None
     }
 
+    /// Identifies the version and configuration of the plugin, i.e. the code it generates, for
+    /// the fingerprints of the crates it is used in. Changing it invalidates caches keyed by them.
+    /// Defaults to the debug representation of the plugin, as the compiler version is a part of the
+    /// fingerprints anyway.
+    fn version(&self) -> String {
+        format!("{self:?}")
+    }
+
     /// A `TypeId` of the plugin, used to compare the concrete types
     /// of plugins given as trait objects.
     fn plugin_type_id(&self) -> any::TypeId {
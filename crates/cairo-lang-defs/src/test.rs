@@ -2,8 +2,11 @@ use std::fmt::Write as _;
 use std::sync::Arc;
 
 use cairo_lang_debug::debug::DebugWithDb;
-use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroup, init_files_group};
-use cairo_lang_filesystem::ids::{CrateId, Directory, FileLongId, SmolStrId};
+use cairo_lang_filesystem::db::{
+    CrateConfiguration, Edition, FilesGroup, ext_as_virtual, init_files_group,
+};
+use cairo_lang_filesystem::ids::{CrateId, Directory, FileLongId, SmolStrId, SpanInFile};
+use cairo_lang_filesystem::span::{TextOffset, TextSpan, TextWidth};
 use cairo_lang_filesystem::{override_file_content, set_crate_config};
 use cairo_lang_parser::db::ParserGroup;
 use cairo_lang_syntax::node::helpers::QueryAttrs;
@@ -13,9 +16,11 @@ use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::{Intern, extract_matches, try_extract_matches};
 use indoc::indoc;
+use itertools::Itertools;
 use salsa::{Database, Setter};
 
 use crate::db::{DefsGroup, defs_group_input, init_defs_group, init_external_files};
+use crate::diagnostic_utils::{expansion_backtrace, expansion_backtrace_notes};
 use crate::ids::{
     GenericParamLongId, MacroPluginLongId, ModuleId, ModuleItemId, NamedLanguageElementId,
     SubmoduleLongId,
@@ -23,6 +28,10 @@ use crate::ids::{
 use crate::plugin::{
     MacroPlugin, MacroPluginMetadata, PluginDiagnostic, PluginGeneratedFile, PluginResult,
 };
+use crate::token_stream::{
+    TokenStream, TokenStreamAttributeMacro, TokenStreamAttributeMacroPlugin, TokenStreamDiagnostic,
+    TokenStreamMacroResult,
+};
 
 #[salsa::db]
 #[derive(Clone)]
@@ -42,7 +51,11 @@ impl Default for DatabaseForTesting {
             MacroPluginLongId(Arc::new(FooToBarPlugin)),
             MacroPluginLongId(Arc::new(RemoveOrigPlugin)),
             MacroPluginLongId(Arc::new(DummyPlugin)),
+            MacroPluginLongId(Arc::new(TokenStreamAttributeMacroPlugin(WrapMacro))),
         ]));
+        defs_group_input(&res)
+            .set_default_inline_macro_plugins(&mut res)
+            .to(Some(OrderedHashMap::default()));
         res
     }
 }
@@ -193,6 +206,72 @@ fn test_submodules() {
     );
 }
 
+#[test]
+fn test_fingerprints() {
+    let fingerprints = |db: &DatabaseForTesting| {
+        let module_id = ModuleId::CrateRoot(get_crate_id(db));
+        let submodule_id =
+            ModuleId::Submodule(*db.module_submodules_ids(module_id).unwrap().first().unwrap());
+        (
+            db.crate_fingerprint(get_crate_id(db)).unwrap(),
+            db.module_fingerprint(submodule_id).unwrap(),
+        )
+    };
+    let setup = |db: &mut DatabaseForTesting| {
+        let crate_id = get_crate_id(db);
+        let root = Directory::Real("src".into());
+        set_crate_config!(db, crate_id, Some(CrateConfiguration::default_for_root(root)));
+        set_file_content!(db, "src/lib.cairo", "mod submod;\nfn bar() {}");
+        set_file_content!(db, "src/submod.cairo", "fn foo() {}");
+    };
+
+    let mut db_val = DatabaseForTesting::default();
+    let db = &mut db_val;
+    setup(db);
+    let (crate_fingerprint, submodule_fingerprint) = fingerprints(db);
+
+    // Fingerprints are stable across databases.
+    let mut other_db = DatabaseForTesting::default();
+    setup(&mut other_db);
+    assert_eq!(fingerprints(&other_db), (crate_fingerprint, submodule_fingerprint));
+
+    // Changing files outside of the crate does not change its fingerprints.
+    set_file_content!(db, "src/unused.cairo", "fn baz() {}");
+    assert_eq!(fingerprints(db), (crate_fingerprint, submodule_fingerprint));
+
+    // Changing the content of a module changes its fingerprint and the fingerprints of its
+    // ancestors only.
+    set_file_content!(db, "src/lib.cairo", "mod submod;\nfn bar2() {}");
+    let (changed_crate_fingerprint, same_submodule_fingerprint) = fingerprints(db);
+    assert_ne!(changed_crate_fingerprint, crate_fingerprint);
+    assert_eq!(same_submodule_fingerprint, submodule_fingerprint);
+    set_file_content!(db, "src/lib.cairo", "mod submod;\nfn bar() {}");
+    set_file_content!(db, "src/submod.cairo", "fn foo2() {}");
+    let (changed_crate_fingerprint, changed_submodule_fingerprint) = fingerprints(db);
+    assert_ne!(changed_crate_fingerprint, crate_fingerprint);
+    assert_ne!(changed_submodule_fingerprint, submodule_fingerprint);
+
+    // Restoring the content restores the fingerprints.
+    set_file_content!(db, "src/submod.cairo", "fn foo() {}");
+    assert_eq!(fingerprints(db), (crate_fingerprint, submodule_fingerprint));
+
+    // Changing the settings or the plugins of the crate changes all of its fingerprints.
+    let crate_id = get_crate_id(db);
+    let mut config = CrateConfiguration::default_for_root(Directory::Real("src".into()));
+    config.settings.edition = Edition::latest();
+    set_crate_config!(db, crate_id, Some(config));
+    let (changed_crate_fingerprint, changed_submodule_fingerprint) = fingerprints(db);
+    assert_ne!(changed_crate_fingerprint, crate_fingerprint);
+    assert_ne!(changed_submodule_fingerprint, submodule_fingerprint);
+    setup(db);
+    defs_group_input(db)
+        .set_default_macro_plugins(db)
+        .to(Some(vec![MacroPluginLongId(Arc::new(DummyPlugin))]));
+    let (changed_crate_fingerprint, changed_submodule_fingerprint) = fingerprints(db);
+    assert_ne!(changed_crate_fingerprint, crate_fingerprint);
+    assert_ne!(changed_submodule_fingerprint, submodule_fingerprint);
+}
+
 #[derive(Debug)]
 struct DummyPlugin;
 impl MacroPlugin for DummyPlugin {
@@ -466,3 +545,171 @@ fn test_plugin_chain() {
          ExternTypeId(test::B)]"
     )
 }
+
+/// Wraps an item marked with `#[wrap(name)]` in a module named `name`.
+#[derive(Debug)]
+struct WrapMacro;
+impl TokenStreamAttributeMacro for WrapMacro {
+    fn attribute_name(&self) -> &'static str {
+        "wrap"
+    }
+
+    fn generate_tokens(
+        &self,
+        arguments: TokenStream,
+        item: TokenStream,
+        _metadata: &MacroPluginMetadata<'_>,
+    ) -> TokenStreamMacroResult {
+        let [name] = arguments.tokens() else {
+            return TokenStreamMacroResult {
+                diagnostics: vec![TokenStreamDiagnostic::error(
+                    None,
+                    "Expected a single argument.".into(),
+                )],
+                ..Default::default()
+            };
+        };
+        if !name.content.chars().all(|c| c.is_ascii_alphabetic()) {
+            return TokenStreamMacroResult {
+                diagnostics: vec![TokenStreamDiagnostic::error(
+                    name.origin,
+                    "Expected a module name.".into(),
+                )],
+                ..Default::default()
+            };
+        }
+        let patches = [
+            ("name".to_string(), TokenStream::new(vec![name.clone()])),
+            ("item".to_string(), item),
+        ]
+        .into();
+        TokenStreamMacroResult {
+            code: Some(TokenStream::interpolate("mod $name$ {\n    $item$\n}\n", &patches)),
+            diagnostics: vec![],
+            remove_original_item: true,
+        }
+    }
+}
+
+#[test]
+fn test_token_stream_macro() {
+    let mut db_val = DatabaseForTesting::default();
+    let db = &mut db_val;
+    let crate_id = get_crate_id(db);
+    let root = Directory::Real("src".into());
+    set_crate_config!(db, crate_id, Some(CrateConfiguration::default_for_root(root)));
+
+    set_file_content!(db, "src/lib.cairo", "#[wrap(inner)]\n#[inline] extern type   A;");
+    let crate_id = get_crate_id(db);
+    let module_id = ModuleId::CrateRoot(crate_id);
+    let db_ref: &dyn Database = &*db;
+    let module_data = module_id.module_data(db_ref).unwrap();
+    assert_eq!(
+        format!("{:?}", module_data.items(db_ref).debug(db_ref)),
+        "[SubmoduleId(test::inner)]"
+    );
+
+    let generated_file = module_data.files(db_ref)[1];
+    let FileLongId::External(external_id) = generated_file.long(db_ref) else {
+        panic!("Expected a generated file.");
+    };
+    let virtual_file = ext_as_virtual(db_ref, *external_id);
+    let content = virtual_file.content.long(db_ref).as_str();
+    assert_eq!(content, "mod inner {\n    #[inline] extern type A;\n}\n");
+    // The tokens of the item are mapped to their location in the original file.
+    let generated_span = TextSpan::new_with_width(
+        TextOffset::START.add_width(TextWidth::at(content, content.find('A').unwrap())),
+        TextWidth::new_for_testing(1),
+    );
+    let original_span = virtual_file
+        .code_mappings
+        .iter()
+        .find_map(|mapping| mapping.translate(generated_span))
+        .unwrap();
+    assert_eq!(
+        original_span,
+        TextSpan::new_with_width(
+            TextWidth::new_for_testing(39).as_offset(),
+            TextWidth::new_for_testing(1),
+        )
+    );
+}
+
+#[test]
+fn test_token_stream_macro_diagnostics() {
+    let mut db_val = DatabaseForTesting::default();
+    let db = &mut db_val;
+    let crate_id = get_crate_id(db);
+    let root = Directory::Real("src".into());
+    set_crate_config!(db, crate_id, Some(CrateConfiguration::default_for_root(root)));
+
+    set_file_content!(db, "src/lib.cairo", "#[wrap(1)] extern type A;");
+    let crate_id = get_crate_id(db);
+    let module_id = ModuleId::CrateRoot(crate_id);
+    let db_ref: &dyn Database = &*db;
+    let diagnostics = module_id.module_data(db_ref).unwrap().plugin_diagnostics(db_ref);
+    let [(_, diagnostic)] = diagnostics.as_slice() else {
+        panic!("Expected a single diagnostic.");
+    };
+    assert_eq!(diagnostic.message, "Expected a module name.");
+    // The diagnostic points to the argument, relative to the start of the item.
+    assert_eq!(
+        diagnostic.inner_span,
+        Some((TextWidth::new_for_testing(7), TextWidth::new_for_testing(1)))
+    );
+}
+
+#[test]
+fn test_expansion_backtrace() {
+    let mut db_val = DatabaseForTesting::default();
+    let db = &mut db_val;
+    let crate_id = get_crate_id(db);
+    let root = Directory::Real("src".into());
+    set_crate_config!(db, crate_id, Some(CrateConfiguration::default_for_root(root)));
+
+    set_file_content!(db, "src/lib.cairo", "#[wrap(inner)]\nextern type A;");
+    let crate_id = get_crate_id(db);
+    let module_id = ModuleId::CrateRoot(crate_id);
+    let db_ref: &dyn Database = &*db;
+    let module_data = module_id.module_data(db_ref).unwrap();
+    let generated_file = module_data.files(db_ref)[1];
+    let content = db_ref.file_content(generated_file).unwrap();
+    let generated = SpanInFile {
+        file_id: generated_file,
+        span: TextSpan::new_with_width(
+            TextOffset::START.add_width(TextWidth::at(content, content.find("mod").unwrap())),
+            TextWidth::new_for_testing(3),
+        ),
+    };
+
+    let [frame] = expansion_backtrace(db_ref, generated)[..] else {
+        panic!("Expected a single expansion frame.");
+    };
+    assert_eq!(frame.generated, generated);
+    assert_eq!(frame.call_site.file_id, module_data.files(db_ref)[0]);
+    assert_eq!(
+        frame.call_site.span,
+        TextSpan::new_with_width(TextOffset::START, TextWidth::new_for_testing(29))
+    );
+    assert!(expansion_backtrace(db_ref, frame.call_site).is_empty());
+
+    let notes = expansion_backtrace_notes(db_ref, generated)
+        .iter()
+        .map(|note| format!("note: {:?}\n", note.debug(db_ref)))
+        .join("");
+    assert_eq!(
+        notes,
+        indoc! {"
+            note: in the generated code:
+              --> src/lib.cairo:1:1: 2:15[wrap]:1:1
+            mod inner {
+            ^^^
+            note: expanded from:
+              --> src/lib.cairo:1:1-2:14
+              #[wrap(inner)]
+             _^
+            | extern type A;
+            |______________^
+        "}
+    );
+}
@@ -0,0 +1,261 @@
+use std::fmt::{Display, Formatter};
+
+use cairo_lang_diagnostics::Severity;
+use cairo_lang_filesystem::ids::{CodeMapping, CodeOrigin, SmolStrId};
+use cairo_lang_filesystem::span::{TextOffset, TextSpan, TextWidth};
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::{SyntaxNode, TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use salsa::Database;
+
+use crate::plugin::{
+    MacroPlugin, MacroPluginMetadata, PluginDiagnostic, PluginGeneratedFile, PluginResult,
+};
+
+/// A token of a [TokenStream]: a piece of code, along with the user code it was taken from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub content: String,
+    /// The span of the user code the token was taken from, or `None` if the token was generated.
+    /// Generated tokens are mapped to the code the macro was applied on.
+    pub origin: Option<TextSpan>,
+}
+impl Token {
+    pub fn new(content: impl Into<String>, origin: TextSpan) -> Self {
+        Self { content: content.into(), origin: Some(origin) }
+    }
+
+    pub fn generated(content: impl Into<String>) -> Self {
+        Self { content: content.into(), origin: None }
+    }
+}
+
+/// A stream of tokens, used as the input and output of token stream macros.
+///
+/// Unlike a string of code, each token keeps the span of the user code it was taken from, so
+/// diagnostics on code generated from it are reported on the user code.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenStream {
+    tokens: Vec<Token>,
+}
+impl TokenStream {
+    pub fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens }
+    }
+
+    /// Creates a token stream of the terminals of a syntax node. Trivia is replaced by a single
+    /// generated space.
+    pub fn from_syntax_node(db: &dyn Database, node: SyntaxNode<'_>) -> Self {
+        Self::from_syntax_node_filtered(db, node, |_| true)
+    }
+
+    /// Creates a token stream of the terminals of a syntax node whose spans are accepted by
+    /// `include`.
+    fn from_syntax_node_filtered(
+        db: &dyn Database,
+        node: SyntaxNode<'_>,
+        include: impl Fn(TextSpan) -> bool,
+    ) -> Self {
+        let mut stream = Self::default();
+        for terminal in node.descendants(db).filter(|node| node.kind(db).is_terminal()) {
+            let span = terminal.span_without_trivia(db);
+            if span.width() == TextWidth::ZERO || !include(span) {
+                continue;
+            }
+            let has_leading_trivia = terminal.span(db).start != span.start;
+            if has_leading_trivia && !stream.is_empty() && !stream.ends_with_space() {
+                stream.push(Token::generated(" "));
+            }
+            stream.push(Token::new(terminal.get_text_without_trivia(db).long(db).as_str(), span));
+            if terminal.span(db).end != span.end {
+                stream.push(Token::generated(" "));
+            }
+        }
+        if stream.ends_with_space() {
+            stream.tokens.pop();
+        }
+        stream
+    }
+
+    /// Creates a token stream by interpolating code with token streams, keeping the origins of
+    /// their tokens. Each substring of the form `$<name>$` is replaced with the token stream named
+    /// `name` in `patches`. A `$$` substring is replaced with `$`. The rest of the code is
+    /// generated.
+    pub fn interpolate(code: &str, patches: &UnorderedHashMap<String, TokenStream>) -> Self {
+        let mut stream = Self::default();
+        let mut parts = code.split('$');
+        let mut pending_text = parts.next().unwrap_or_default().to_string();
+        while let Some(name) = parts.next() {
+            if name.is_empty() {
+                pending_text.push('$');
+            } else {
+                if !pending_text.is_empty() {
+                    stream.push(Token::generated(std::mem::take(&mut pending_text)));
+                }
+                let patch = patches.get(name).unwrap_or_else(|| panic!("No patch named {name}."));
+                stream.extend(patch.clone());
+            }
+            pending_text.push_str(parts.next().unwrap_or_default());
+        }
+        if !pending_text.is_empty() {
+            stream.push(Token::generated(pending_text));
+        }
+        stream
+    }
+
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub fn push(&mut self, token: Token) {
+        self.tokens.push(token);
+    }
+
+    pub fn extend(&mut self, other: TokenStream) {
+        self.tokens.extend(other.tokens);
+    }
+
+    fn ends_with_space(&self) -> bool {
+        self.tokens.last().is_some_and(|token| token.origin.is_none() && token.content == " ")
+    }
+
+    /// Builds the code of the token stream and its code mappings. Generated tokens are mapped to
+    /// `call_site`.
+    pub fn build(self, call_site: TextSpan) -> (String, Vec<CodeMapping>) {
+        let mut code = String::new();
+        let mut code_mappings = vec![];
+        for token in self.tokens {
+            let start = TextOffset::from_str(&code);
+            code.push_str(&token.content);
+            let Some(origin) = token.origin else {
+                continue;
+            };
+            let span = TextSpan::new(start, TextOffset::from_str(&code));
+            let origin = if span.width() == origin.width() {
+                CodeOrigin::Start(origin.start)
+            } else {
+                CodeOrigin::Span(origin)
+            };
+            code_mappings.push(CodeMapping { span, origin });
+        }
+        code_mappings.push(CodeMapping {
+            span: TextSpan::from_str(&code),
+            origin: CodeOrigin::Span(call_site),
+        });
+        (code, code_mappings)
+    }
+}
+impl Display for TokenStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.tokens.iter().try_for_each(|token| f.write_str(&token.content))
+    }
+}
+
+/// A diagnostic reported by a token stream macro.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenStreamDiagnostic {
+    /// The span of the user code the diagnostic is reported on, usually the origin of a token. If
+    /// `None`, the diagnostic is reported on the whole item the macro was applied on.
+    pub span: Option<TextSpan>,
+    pub message: String,
+    pub severity: Severity,
+}
+impl TokenStreamDiagnostic {
+    pub fn error(span: Option<TextSpan>, message: String) -> Self {
+        Self { span, message, severity: Severity::Error }
+    }
+
+    pub fn warning(span: Option<TextSpan>, message: String) -> Self {
+        Self { span, message, severity: Severity::Warning }
+    }
+}
+
+/// Result of a token stream macro code generation.
+#[derive(Default)]
+pub struct TokenStreamMacroResult {
+    /// The generated code.
+    pub code: Option<TokenStream>,
+    pub diagnostics: Vec<TokenStreamDiagnostic>,
+    /// If true - the original item should be removed, if false - it should remain as is.
+    pub remove_original_item: bool,
+}
+
+/// A trait for an attribute macro operating on token streams, rather than on the syntax tree.
+///
+/// Wrap it with [TokenStreamAttributeMacroPlugin] to use it as a [MacroPlugin].
+pub trait TokenStreamAttributeMacro: std::fmt::Debug + Sync + Send + 'static {
+    /// The name of the attribute the macro is applied with.
+    fn attribute_name(&self) -> &'static str;
+
+    /// Generates code for an item the attribute is applied on.
+    /// `arguments` are the tokens inside the parentheses of the attribute, and `item` are the
+    /// tokens of the item, without the attribute.
+    fn generate_tokens(
+        &self,
+        arguments: TokenStream,
+        item: TokenStream,
+        metadata: &MacroPluginMetadata<'_>,
+    ) -> TokenStreamMacroResult;
+}
+
+/// A [MacroPlugin] running a [TokenStreamAttributeMacro] on the items with its attribute.
+#[derive(Debug)]
+pub struct TokenStreamAttributeMacroPlugin<T: TokenStreamAttributeMacro>(pub T);
+impl<T: TokenStreamAttributeMacro> MacroPlugin for TokenStreamAttributeMacroPlugin<T> {
+    fn generate_code<'db>(
+        &self,
+        db: &'db dyn Database,
+        item_ast: ast::ModuleItem<'db>,
+        metadata: &MacroPluginMetadata<'_>,
+    ) -> PluginResult<'db> {
+        let name = self.0.attribute_name();
+        let Some(attr) = item_ast.find_attr(db, name) else {
+            return PluginResult::default();
+        };
+        let arguments = match attr.arguments(db) {
+            ast::OptionArgListParenthesized::ArgListParenthesized(arguments) => {
+                TokenStream::from_syntax_node(db, arguments.arguments(db).as_syntax_node())
+            }
+            ast::OptionArgListParenthesized::Empty(_) => TokenStream::default(),
+        };
+        let attr_span = attr.as_syntax_node().span_without_trivia(db);
+        let item_node = item_ast.as_syntax_node();
+        let item =
+            TokenStream::from_syntax_node_filtered(db, item_node, |span| !attr_span.contains(span));
+
+        let result = self.0.generate_tokens(arguments, item, metadata);
+        let item_ptr = item_ast.stable_ptr(db).untyped();
+        let item_offset = item_node.offset(db);
+        let diagnostics = result
+            .diagnostics
+            .into_iter()
+            .map(|diagnostic| PluginDiagnostic {
+                stable_ptr: item_ptr,
+                message: diagnostic.message,
+                severity: diagnostic.severity,
+                inner_span: diagnostic.span.map(|span| (span.start - item_offset, span.width())),
+                error_code: None,
+            })
+            .collect();
+        let code = result.code.map(|code| {
+            let (content, code_mappings) = code.build(item_node.span_without_trivia(db));
+            PluginGeneratedFile {
+                name: name.into(),
+                content,
+                code_mappings,
+                aux_data: None,
+                diagnostics_note: Some(format!("this error originates in the attribute: `{name}`")),
+                is_unhygienic: false,
+            }
+        });
+        PluginResult { code, diagnostics, remove_original_item: result.remove_original_item }
+    }
+
+    fn declared_attributes<'db>(&self, db: &'db dyn Database) -> Vec<SmolStrId<'db>> {
+        vec![SmolStrId::from(db, self.0.attribute_name())]
+    }
+}
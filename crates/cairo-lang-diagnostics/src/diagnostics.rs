@@ -3,14 +3,16 @@ use std::hash::Hash;
 use std::sync::Arc;
 
 use cairo_lang_debug::debug::DebugWithDb;
-use cairo_lang_filesystem::db::get_originating_location;
+use cairo_lang_filesystem::db::{FilesGroup, get_originating_location};
 use cairo_lang_filesystem::ids::{FileId, SpanInFile};
 use cairo_lang_proc_macros::HeapSize;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
-use itertools::Itertools;
+use itertools::{Itertools, chain};
 use salsa::Database;
+use serde::Serialize;
 
 use crate::error_code::{ErrorCode, OptionErrorCodeExt};
+use crate::suggestion::{DiagnosticSuggestion, FormattedSuggestion};
 
 #[cfg(test)]
 #[path = "diagnostics_test.rs"]
@@ -45,6 +47,10 @@ pub trait DiagnosticEntry<'db>: Clone + fmt::Debug + Eq + Hash {
     fn error_code(&self) -> Option<ErrorCode> {
         None
     }
+    /// Returns suggested fixes of the diagnostic.
+    fn suggestions(&self, _db: &'db dyn Database) -> Vec<DiagnosticSuggestion<'db>> {
+        vec![]
+    }
     /// Returns true if the two should be regarded as the same kind when filtering duplicate
     /// diagnostics.
     fn is_same_kind(&self, other: &Self) -> bool;
@@ -221,11 +227,18 @@ pub struct FormattedDiagnosticEntry {
     severity: Severity,
     error_code: Option<ErrorCode>,
     message: String,
+    suggestions: Vec<FormattedSuggestion>,
 }
 
 impl FormattedDiagnosticEntry {
     pub fn new(severity: Severity, error_code: Option<ErrorCode>, message: String) -> Self {
-        Self { severity, error_code, message }
+        Self { severity, error_code, message, suggestions: vec![] }
+    }
+
+    /// Attaches suggested fixes to the entry.
+    pub fn with_suggestions(mut self, suggestions: Vec<FormattedSuggestion>) -> Self {
+        self.suggestions = suggestions;
+        self
     }
 
     pub fn is_empty(&self) -> bool {
@@ -243,6 +256,28 @@ impl FormattedDiagnosticEntry {
     pub fn message(&self) -> &str {
         &self.message
     }
+
+    pub fn suggestions(&self) -> &[FormattedSuggestion] {
+        &self.suggestions
+    }
+
+    /// Serializes the entry to a single line of JSON, for consumption by tools.
+    pub fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct JsonEntry<'a> {
+            severity: String,
+            code: Option<&'a str>,
+            message: &'a str,
+            suggestions: &'a [FormattedSuggestion],
+        }
+        serde_json::to_string(&JsonEntry {
+            severity: self.severity.to_string(),
+            code: self.error_code.as_ref().map(ErrorCode::as_str),
+            message: &self.message,
+            suggestions: &self.suggestions,
+        })
+        .expect("Serializing a diagnostic should never fail.")
+    }
 }
 
 impl fmt::Display for FormattedDiagnosticEntry {
@@ -257,6 +292,17 @@ impl fmt::Display for FormattedDiagnosticEntry {
     }
 }
 
+/// A group of diagnostics with the same root cause, see [Diagnostics::get_diagnostic_groups].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiagnosticGroup<TEntry> {
+    /// The first occurrence of the diagnostic.
+    pub primary: TEntry,
+    /// The other occurrences of the diagnostic.
+    pub related: Vec<TEntry>,
+    /// The severity the diagnostics are reported with.
+    pub severity: Severity,
+}
+
 /// A set of diagnostic entries that arose during a computation.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
 pub struct Diagnostics<'db, TEntry: DiagnosticEntry<'db> + salsa::Update>(
@@ -287,11 +333,38 @@ impl<'db, TEntry: DiagnosticEntry<'db> + salsa::Update> Diagnostics<'db, TEntry>
         &self,
         db: &'db dyn Database,
         file_notes: &OrderedHashMap<FileId<'db>, DiagnosticNote<'db>>,
+    ) -> Vec<FormattedDiagnosticEntry> {
+        self.format_with_severity_and_notes(
+            db,
+            file_notes,
+            |_| vec![],
+            |entry| Some(entry.severity()),
+            None,
+        )
+    }
+
+    /// Same as [Self::format_with_severity], also adding the notes returned by `location_notes` for
+    /// the location of each entry.
+    ///
+    /// The entries are reported with the severity returned by `severity`, and skipped if it returns
+    /// `None` - allowing the severity of diagnostics to be configured.
+    ///
+    /// If `max_related_locations` is set, entries with the same root cause are grouped, see
+    /// [Self::get_diagnostic_groups]. Each group is formatted as its first entry, listing up to
+    /// `max_related_locations` of the locations of the rest, and the count of the others.
+    pub fn format_with_severity_and_notes(
+        &self,
+        db: &'db dyn Database,
+        file_notes: &OrderedHashMap<FileId<'db>, DiagnosticNote<'db>>,
+        location_notes: impl Fn(SpanInFile<'db>) -> Vec<DiagnosticNote<'db>>,
+        severity: impl Fn(&TEntry) -> Option<Severity>,
+        max_related_locations: Option<usize>,
     ) -> Vec<FormattedDiagnosticEntry> {
         let mut res: Vec<FormattedDiagnosticEntry> = Vec::new();
 
         let files_db: &'db dyn Database = db;
-        for entry in &self.get_diagnostics_without_duplicates(db) {
+        let groups = self.collect_groups(db, severity, max_related_locations.is_some());
+        for DiagnosticGroup { primary: entry, related, severity } in &groups {
             let mut msg = String::new();
             let diag_location = entry.location(db);
             let (user_location, parent_file_notes) =
@@ -314,10 +387,33 @@ impl<'db, TEntry: DiagnosticEntry<'db> + salsa::Update> Diagnostics<'db, TEntry>
             for note in parent_file_notes {
                 msg += &format!("note: {:?}\n", note.debug(files_db))
             }
+            for note in location_notes(diag_location) {
+                msg += &format!("note: {:?}\n", note.debug(files_db))
+            }
+            let max_related_locations = max_related_locations.unwrap_or_default();
+            for related_entry in related.iter().take(max_related_locations) {
+                let note = DiagnosticNote::with_location(
+                    "The same diagnostic also occurs here".into(),
+                    related_entry.location(db),
+                );
+                msg += &format!("note: {:?}\n", note.debug(files_db))
+            }
+            match related.len().saturating_sub(max_related_locations) {
+                0 => {}
+                1 => msg += "note: The same diagnostic occurs in 1 more location.\n",
+                hidden => {
+                    msg +=
+                        &format!("note: The same diagnostic occurs in {hidden} more locations.\n")
+                }
+            }
             msg += "\n";
 
-            let formatted =
-                FormattedDiagnosticEntry::new(entry.severity(), entry.error_code(), msg);
+            let suggestions = chain!([entry], related)
+                .flat_map(|entry| entry.suggestions(db))
+                .filter_map(|suggestion| suggestion.format(files_db))
+                .collect();
+            let formatted = FormattedDiagnosticEntry::new(*severity, entry.error_code(), msg)
+                .with_suggestions(suggestions);
             res.push(formatted);
         }
         res
@@ -379,6 +475,57 @@ impl<'db, TEntry: DiagnosticEntry<'db> + salsa::Update> Diagnostics<'db, TEntry>
         diagnostic_without_dup.into_iter().map(|(_, diag)| diag.clone()).collect()
     }
 
+    /// Returns the diagnostics without duplicates, grouped by their root cause: diagnostics of the
+    /// same severity, code and message, reported on the same (non-empty) code, are assumed to have
+    /// the same cause - e.g. an unresolved type used in multiple places. Each group starts at the
+    /// first occurrence of its diagnostic.
+    pub fn get_diagnostic_groups(&self, db: &'db dyn Database) -> Vec<DiagnosticGroup<TEntry>> {
+        self.collect_groups(db, |entry| Some(entry.severity()), true)
+    }
+
+    /// Collects the diagnostics without duplicates into groups, with the severities given by
+    /// `severity`, skipping the diagnostics for which it returns `None`.
+    /// If `group_by_cause` is false, each group contains a single diagnostic.
+    fn collect_groups(
+        &self,
+        db: &'db dyn Database,
+        severity: impl Fn(&TEntry) -> Option<Severity>,
+        group_by_cause: bool,
+    ) -> Vec<DiagnosticGroup<TEntry>> {
+        let mut groups: Vec<DiagnosticGroup<TEntry>> = vec![];
+        let mut group_indices: OrderedHashMap<_, usize> = OrderedHashMap::default();
+        for entry in self.get_diagnostics_without_duplicates(db) {
+            let Some(severity) = severity(&entry) else {
+                continue;
+            };
+            let location = entry.location(db).user_location(db);
+            // Empty spans have no code to compare, so are never grouped.
+            let content = if !group_by_cause || location.span.start == location.span.end {
+                None
+            } else {
+                db.file_content(location.file_id)
+            };
+            let Some(content) = content else {
+                groups.push(DiagnosticGroup { primary: entry, related: vec![], severity });
+                continue;
+            };
+            let key = (
+                severity,
+                entry.error_code(),
+                entry.format(db),
+                location.span.take(content).to_string(),
+            );
+            match group_indices.get(&key) {
+                Some(index) => groups[*index].related.push(entry),
+                None => {
+                    group_indices.insert(key, groups.len());
+                    groups.push(DiagnosticGroup { primary: entry, related: vec![], severity });
+                }
+            }
+        }
+        groups
+    }
+
     /// Merges two sets of diagnostics.
     pub fn merge(self, other: Self) -> Self {
         let mut builder = DiagnosticsBuilder::default();
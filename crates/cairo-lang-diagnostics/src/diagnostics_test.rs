@@ -9,6 +9,7 @@ use indoc::indoc;
 use salsa::Database;
 
 use super::{DiagnosticEntry, DiagnosticsBuilder};
+use crate::{Applicability, DiagnosticSuggestion};
 
 // Test diagnostic.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
@@ -27,16 +28,59 @@ impl<'db> DiagnosticEntry<'db> for SimpleDiag<'db> {
         }
     }
 
+    fn suggestions(&self, _db: &'db dyn Database) -> Vec<DiagnosticSuggestion<'db>> {
+        vec![DiagnosticSuggestion::new(
+            "replace with `xyz`".into(),
+            SpanInFile {
+                file_id: self.file_id,
+                span: TextSpan::new(
+                    TextWidth::new_for_testing(1).as_offset(),
+                    TextWidth::new_for_testing(3).as_offset(),
+                ),
+            },
+            "xyz".into(),
+            Applicability::MachineApplicable,
+        )]
+    }
+
+    fn is_same_kind(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+// Test diagnostic, located at a single char.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
+struct CharDiag<'db> {
+    file_id: FileId<'db>,
+    offset: u32,
+}
+impl<'db> DiagnosticEntry<'db> for CharDiag<'db> {
+    fn format(&self, _db: &dyn Database) -> String {
+        "Char diagnostic.".into()
+    }
+
+    fn location(&self, _db: &'db dyn Database) -> SpanInFile<'db> {
+        let start = TextWidth::new_for_testing(self.offset).as_offset();
+        SpanInFile {
+            file_id: self.file_id,
+            span: TextSpan::new_with_width(start, TextWidth::new_for_testing(1)),
+        }
+    }
+
     fn is_same_kind(&self, _other: &Self) -> bool {
         true
     }
 }
 
 fn setup<'db>(db: &'db FilesDatabaseForTesting) -> FileId<'db> {
+    setup_with_content(db, "abcd\nefg.\n")
+}
+
+fn setup_with_content<'db>(db: &'db FilesDatabaseForTesting, content: &str) -> FileId<'db> {
     FileLongId::Virtual(VirtualFile {
         parent: None,
         name: SmolStrId::from(db, "dummy_file.sierra"),
-        content: SmolStrId::from(db, "abcd\nefg.\n"),
+        content: SmolStrId::from(db, content),
         code_mappings: [].into(),
         kind: FileKind::Module,
         original_item_removed: false,
@@ -66,3 +110,79 @@ fn test_diagnostics() {
         " }
     );
 }
+
+#[test]
+fn test_diagnostics_suggestions() {
+    let db_val = FilesDatabaseForTesting::default();
+    let file_id = setup(&db_val);
+
+    let mut diagnostics: DiagnosticsBuilder<'_, SimpleDiag<'_>> = DiagnosticsBuilder::default();
+    diagnostics.add(SimpleDiag { file_id });
+    let formatted = diagnostics.build().format_with_severity(&db_val, &Default::default());
+    let [entry] = &formatted[..] else {
+        panic!("Expected a single entry.");
+    };
+    assert_eq!(
+        entry.to_json(),
+        "{\"severity\":\"error\",\"code\":null,\"message\":\"Simple diagnostic.\\n --> \
+         dummy_file.sierra:1:1-2:1\\n  abcd\\n _^\\n| \
+         efg.\\n|_^\\n\\n\",\"suggestions\":[{\"message\":\"replace with \
+         `xyz`\",\"file\":\"dummy_file.sierra\",\"span\":{\"start_line\":0,\"start_col\":1,\"\
+         end_line\":0,\"end_col\":3},\"replacement\":\"xyz\",\"applicability\":\"\
+         MachineApplicable\"}]}"
+    );
+}
+
+#[test]
+fn test_diagnostics_grouping() {
+    let db_val = FilesDatabaseForTesting::default();
+    let file_id = setup_with_content(&db_val, "x y x x x x\n");
+
+    let mut diagnostics: DiagnosticsBuilder<'_, CharDiag<'_>> = DiagnosticsBuilder::default();
+    for offset in [0, 2, 4, 6, 8, 10] {
+        diagnostics.add(CharDiag { file_id, offset });
+    }
+    let diagnostics = diagnostics.build();
+
+    let groups = diagnostics.get_diagnostic_groups(&db_val);
+    assert_eq!(
+        groups.iter().map(|group| (group.primary.offset, group.related.len())).collect::<Vec<_>>(),
+        vec![(0, 4), (2, 0)]
+    );
+
+    let formatted = diagnostics
+        .format_with_severity_and_notes(
+            &db_val,
+            &Default::default(),
+            |_| vec![],
+            |entry| Some(entry.severity()),
+            Some(2),
+        )
+        .into_iter()
+        .map(|entry| entry.to_string())
+        .collect::<String>();
+    assert_eq!(
+        formatted,
+        indoc! { "
+            error: Char diagnostic.
+             --> dummy_file.sierra:1:1
+            x y x x x x
+            ^
+            note: The same diagnostic also occurs here:
+              --> dummy_file.sierra:1:5
+            x y x x x x
+                ^
+            note: The same diagnostic also occurs here:
+              --> dummy_file.sierra:1:7
+            x y x x x x
+                  ^
+            note: The same diagnostic occurs in 2 more locations.
+
+            error: Char diagnostic.
+             --> dummy_file.sierra:1:3
+            x y x x x x
+              ^
+
+        " }
+    );
+}
@@ -2,11 +2,15 @@
 //! source files.
 
 pub use diagnostics::{
-    DiagnosticAdded, DiagnosticEntry, DiagnosticNote, Diagnostics, DiagnosticsBuilder,
-    FormattedDiagnosticEntry, Maybe, MaybeAsRef, PluginFileDiagnosticNotes, Severity, ToMaybe,
-    ToOption, UserLocationWithPluginNotes, format_diagnostics, skip_diagnostic,
+    DiagnosticAdded, DiagnosticEntry, DiagnosticGroup, DiagnosticNote, Diagnostics,
+    DiagnosticsBuilder, FormattedDiagnosticEntry, Maybe, MaybeAsRef, PluginFileDiagnosticNotes,
+    Severity, ToMaybe, ToOption, UserLocationWithPluginNotes, format_diagnostics, skip_diagnostic,
 };
 pub use error_code::{ErrorCode, OptionErrorCodeExt};
+pub use suggestion::{
+    Applicability, DiagnosticSuggestion, FormattedSpan, FormattedSuggestion, find_similar_name,
+};
 
 mod diagnostics;
 mod error_code;
+mod suggestion;
@@ -0,0 +1,123 @@
+use cairo_lang_filesystem::ids::SpanInFile;
+use cairo_lang_filesystem::span::TextPositionSpan;
+use salsa::Database;
+use serde::Serialize;
+
+#[cfg(test)]
+#[path = "suggestion_test.rs"]
+mod test;
+
+/// How confident a suggestion is, i.e. whether tools may apply it without user review.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended, and can be applied automatically.
+    MachineApplicable,
+    /// The suggestion may be what the user intended, but it is uncertain.
+    MaybeIncorrect,
+}
+
+/// A suggested fix of a diagnostic - replacing the code at a location with a new text.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DiagnosticSuggestion<'a> {
+    /// A short description of the fix, e.g. "consider importing the trait".
+    pub message: String,
+    /// The replaced code. An empty span means the replacement is inserted at its location.
+    pub location: SpanInFile<'a>,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+impl<'a> DiagnosticSuggestion<'a> {
+    pub fn new(
+        message: String,
+        location: SpanInFile<'a>,
+        replacement: String,
+        applicability: Applicability,
+    ) -> Self {
+        Self { message, location, replacement, applicability }
+    }
+
+    /// Formats the suggestion, locating it in the user code.
+    /// Returns `None` if the location is in generated code, which the user cannot edit.
+    pub fn format(&self, db: &'a dyn Database) -> Option<FormattedSuggestion> {
+        if self.location.user_location(db) != self.location {
+            return None;
+        }
+        Some(FormattedSuggestion {
+            message: self.message.clone(),
+            file: self.location.file_id.long(db).full_path(db),
+            span: self.location.span.position_in_file(db, self.location.file_id)?.into(),
+            replacement: self.replacement.clone(),
+            applicability: self.applicability,
+        })
+    }
+}
+
+/// A [DiagnosticSuggestion] located in a user file, detached from the database.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FormattedSuggestion {
+    pub message: String,
+    /// The path of the file the suggestion applies to.
+    pub file: String,
+    /// The replaced code, as 0-based lines and columns.
+    pub span: FormattedSpan,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+/// A span of text in a file, as 0-based lines and columns.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FormattedSpan {
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+impl From<TextPositionSpan> for FormattedSpan {
+    fn from(span: TextPositionSpan) -> Self {
+        Self {
+            start_line: span.start.line,
+            start_col: span.start.col,
+            end_line: span.end.line,
+            end_col: span.end.col,
+        }
+    }
+}
+
+/// Returns the candidate most similar to `name` by edit distance, if it is close enough to be a
+/// likely misspelling of it.
+pub fn find_similar_name<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Returns the edit distance between two strings, counted in chars. Insertions, deletions,
+/// substitutions and transpositions of adjacent chars each count as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // `rows[i][j]` is the distance between the first `i` chars of `a` and the first `j` of `b`.
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i];
+        for j in 1..=b.len() {
+            let mut distance = (rows[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]))
+                .min(rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            row.push(distance);
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
@@ -0,0 +1,28 @@
+use test_case::test_case;
+
+use super::{edit_distance, find_similar_name};
+
+#[test_case("", "", 0; "empty")]
+#[test_case("abc", "abc", 0; "equal")]
+#[test_case("abc", "abd", 1; "substitution")]
+#[test_case("abc", "abcd", 1; "insertion")]
+#[test_case("abc", "ac", 1; "deletion")]
+#[test_case("abcd", "abdc", 1; "transposition")]
+#[test_case("kitten", "sitting", 3; "mixed")]
+fn test_edit_distance(a: &str, b: &str, expected: usize) {
+    assert_eq!(edit_distance(a, b), expected);
+    assert_eq!(edit_distance(b, a), expected);
+}
+
+#[test]
+fn test_find_similar_name() {
+    let candidates = ["value", "values", "count", "x"];
+    assert_eq!(find_similar_name("vlaue", candidates), Some("value"));
+    assert_eq!(find_similar_name("valeu", candidates), Some("value"));
+    assert_eq!(find_similar_name("vlaeu", candidates), None);
+    assert_eq!(find_similar_name("valu", candidates), Some("value"));
+    assert_eq!(find_similar_name("countt", candidates), Some("count"));
+    assert_eq!(find_similar_name("y", candidates), Some("x"));
+    assert_eq!(find_similar_name("value", candidates), Some("values"));
+    assert_eq!(find_similar_name("unrelated", candidates), None);
+}
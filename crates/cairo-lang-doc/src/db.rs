@@ -11,8 +11,11 @@ use itertools::{Itertools, intersperse};
 use salsa::Database;
 
 use crate::documentable_item::DocumentableItemId;
+use crate::link_resolution::{ResolvedCommentLink, resolve_documentation_link};
 use crate::location_links::LocationLink;
-use crate::parser::{DocumentationCommentToken, MarkdownLink, parse_documentation_comment};
+use crate::parser::{
+    CommentLinkToken, DocumentationCommentToken, MarkdownLink, parse_documentation_comment,
+};
 
 pub trait DocGroup: Database {
     // TODO(mkaput): Support #[doc] attribute. This will be a bigger chunk of work because it would
@@ -31,6 +34,24 @@ pub trait DocGroup: Database {
         get_item_documentation_as_tokens(self.as_dyn_database(), (), item_id)
     }
 
+    /// Gets the links in the documentation of an item, each with the item it points to, if the
+    /// link is a path that resolves to a documentable item.
+    fn get_item_documentation_links<'db>(
+        &'db self,
+        item_id: DocumentableItemId<'db>,
+    ) -> Vec<ResolvedCommentLink<'db>> {
+        get_item_documentation_links(self.as_dyn_database(), (), item_id)
+    }
+
+    /// Resolves the item a link in the documentation of `item_id` points to.
+    fn resolve_documentation_link<'db>(
+        &'db self,
+        item_id: DocumentableItemId<'db>,
+        link: &CommentLinkToken,
+    ) -> Option<DocumentableItemId<'db>> {
+        resolve_documentation_link(self.as_dyn_database(), item_id, link)
+    }
+
     /// Gets the signature of an item (i.e., the item without its body).
     fn get_item_signature<'db>(&'db self, item_id: DocumentableItemId<'db>) -> Option<String> {
         self.get_item_signature_with_links(item_id).0
@@ -122,6 +143,28 @@ fn get_item_documentation_as_tokens<'db>(
     Some(intersperse(result, separator_token).flatten().collect())
 }
 
+#[salsa::tracked]
+fn get_item_documentation_links<'db>(
+    db: &'db dyn Database,
+    _tracked: Tracked,
+    item_id: DocumentableItemId<'db>,
+) -> Vec<ResolvedCommentLink<'db>> {
+    let Some(tokens) = db.get_item_documentation_as_tokens(item_id) else {
+        return vec![];
+    };
+    tokens
+        .into_iter()
+        .filter_map(|token| match token {
+            DocumentationCommentToken::Content(_) => None,
+            DocumentationCommentToken::Link(link) => Some(link),
+        })
+        .map(|link| ResolvedCommentLink {
+            resolved_item: resolve_documentation_link(db, item_id, &link),
+            link,
+        })
+        .collect()
+}
+
 /// Gets the crate level documentation.
 fn get_crate_root_module_documentation<'db>(
     db: &'db dyn Database,
@@ -139,6 +139,7 @@ pub fn get_syntactic_visibility(semantic_visibility: &Visibility) -> &str {
     match semantic_visibility {
         Visibility::Public => "pub ",
         Visibility::PublicInCrate => "pub(crate) ",
+        Visibility::PublicInSuper => "pub(super) ",
         Visibility::Private => "",
     }
 }
@@ -2,6 +2,7 @@ pub mod db;
 pub mod documentable_formatter;
 pub mod documentable_item;
 pub mod helpers;
+pub mod link_resolution;
 pub mod location_links;
 pub mod parser;
 pub mod signature_data;
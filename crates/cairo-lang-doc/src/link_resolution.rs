@@ -0,0 +1,175 @@
+use cairo_lang_defs::ids::{
+    GenericTypeId, ImplItemId, LanguageElementId, LookupItemId, ModuleId, ModuleItemId, TraitItemId,
+};
+use cairo_lang_diagnostics::DiagnosticsBuilder;
+use cairo_lang_filesystem::ids::{FileKind, FileLongId, SmolStrId, VirtualFile};
+use cairo_lang_parser::parser::Parser;
+use cairo_lang_semantic::diagnostic::{NotFoundItemType, SemanticDiagnostics};
+use cairo_lang_semantic::expr::inference::InferenceId;
+use cairo_lang_semantic::items::functions::GenericFunctionId;
+use cairo_lang_semantic::items::imp::{ImplLongId, ImplSemantic};
+use cairo_lang_semantic::items::structure::StructSemantic;
+use cairo_lang_semantic::items::trt::TraitSemantic;
+use cairo_lang_semantic::resolve::{ResolutionContext, ResolvedGenericItem, Resolver};
+use cairo_lang_syntax::node::ast;
+use cairo_lang_syntax::node::helpers::GetIdentifier;
+use cairo_lang_utils::Intern;
+use salsa::Database;
+
+use crate::documentable_item::DocumentableItemId;
+use crate::parser::CommentLinkToken;
+
+/// A link found in the documentation of an item, together with the item it points to.
+#[derive(Debug, PartialEq, Clone, Eq, salsa::Update)]
+pub struct ResolvedCommentLink<'db> {
+    /// The link, as parsed from the documentation.
+    pub link: CommentLinkToken,
+    /// The item the link points to. Not present when the link is not a path to an item, or when
+    /// the path could not be resolved.
+    pub resolved_item: Option<DocumentableItemId<'db>>,
+}
+
+/// Resolves the path a documentation link points to, in the context of the documented item.
+///
+/// Paths are resolved like paths written in code in the module containing the item, e.g.
+/// `[MyTrait::foo]` or `[super::MyStruct]`. Struct members and impl items are supported as well,
+/// e.g. `[MyStruct::member]` or `[MyImpl::foo]`.
+pub fn resolve_documentation_link<'db>(
+    db: &'db dyn Database,
+    item_id: DocumentableItemId<'db>,
+    link: &CommentLinkToken,
+) -> Option<DocumentableItemId<'db>> {
+    let path_text = link.md_link.dest_text.as_deref()?;
+    let module_id = documentation_context_module(db, item_id);
+    let segments = parse_link_path(db, path_text)?.segments(db).elements_vec(db);
+
+    if let Some(item) = resolve_path_segments(db, module_id, segments.clone()) {
+        return documentable_from_resolved_item(db, item);
+    }
+    // The path may lead to an item which the generic path resolution does not reach, i.e., a
+    // struct member or an item of a trait or an impl.
+    let (item_segment, container_segments) = segments.split_last()?;
+    if container_segments.is_empty() {
+        return None;
+    }
+    let name = item_segment.identifier(db);
+    match resolve_path_segments(db, module_id, container_segments.to_vec())? {
+        ResolvedGenericItem::GenericType(GenericTypeId::Struct(struct_id)) => {
+            let member = db.struct_members(struct_id).ok()?.get(&name)?;
+            Some(DocumentableItemId::Member(member.id))
+        }
+        ResolvedGenericItem::Trait(trait_id) => {
+            let trait_item_id = db.trait_item_by_name(trait_id, name).ok()??;
+            Some(DocumentableItemId::from(LookupItemId::TraitItem(trait_item_id)))
+        }
+        ResolvedGenericItem::Impl(impl_def_id) => {
+            let impl_item_info = db.impl_item_info_by_name(impl_def_id, name).ok()??;
+            Some(DocumentableItemId::from(LookupItemId::ImplItem(impl_item_info.id)))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the module in which paths in the documentation of the item are resolved.
+fn documentation_context_module<'db>(
+    db: &'db dyn Database,
+    item_id: DocumentableItemId<'db>,
+) -> ModuleId<'db> {
+    match item_id {
+        DocumentableItemId::Crate(crate_id) => ModuleId::CrateRoot(crate_id),
+        DocumentableItemId::LookupItem(lookup_item_id) => lookup_item_id.parent_module(db),
+        DocumentableItemId::Member(member_id) => member_id.parent_module(db),
+        DocumentableItemId::Variant(variant_id) => variant_id.parent_module(db),
+    }
+}
+
+/// Parses the text of a link destination as a path expression.
+fn parse_link_path<'db>(db: &'db dyn Database, path_text: &str) -> Option<ast::ExprPath<'db>> {
+    let content = SmolStrId::from(db, path_text);
+    let file_id = FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: SmolStrId::from(db, "documentation_link"),
+        content,
+        code_mappings: [].into(),
+        kind: FileKind::Expr,
+        original_item_removed: false,
+    })
+    .intern(db);
+    let mut diagnostics = DiagnosticsBuilder::default();
+    let expr = Parser::parse_file_expr(db, &mut diagnostics, file_id, content.long(db));
+    if diagnostics.build().check_error_free().is_err() {
+        return None;
+    }
+    match expr {
+        ast::Expr::Path(path) => Some(path),
+        _ => None,
+    }
+}
+
+/// Resolves path segments in the given module, ignoring any diagnostics.
+fn resolve_path_segments<'db>(
+    db: &'db dyn Database,
+    module_id: ModuleId<'db>,
+    segments: Vec<ast::PathSegment<'db>>,
+) -> Option<ResolvedGenericItem<'db>> {
+    let mut resolver = Resolver::new(db, module_id, InferenceId::NoContext);
+    let mut diagnostics = SemanticDiagnostics::new(module_id);
+    resolver
+        .resolve_generic_path(
+            &mut diagnostics,
+            segments,
+            NotFoundItemType::Identifier,
+            ResolutionContext::Default,
+        )
+        .ok()
+}
+
+/// Returns the [`DocumentableItemId`] of a resolved item, if it has documentation of its own.
+fn documentable_from_resolved_item<'db>(
+    db: &'db dyn Database,
+    item: ResolvedGenericItem<'db>,
+) -> Option<DocumentableItemId<'db>> {
+    let module_item_id = match item {
+        ResolvedGenericItem::GenericConstant(id) => ModuleItemId::Constant(id),
+        ResolvedGenericItem::Module(ModuleId::CrateRoot(crate_id)) => {
+            return Some(DocumentableItemId::Crate(crate_id));
+        }
+        ResolvedGenericItem::Module(ModuleId::Submodule(id)) => ModuleItemId::Submodule(id),
+        ResolvedGenericItem::Module(ModuleId::MacroCall { .. }) => return None,
+        ResolvedGenericItem::GenericFunction(GenericFunctionId::Free(id)) => {
+            ModuleItemId::FreeFunction(id)
+        }
+        ResolvedGenericItem::GenericFunction(GenericFunctionId::Extern(id)) => {
+            ModuleItemId::ExternFunction(id)
+        }
+        ResolvedGenericItem::GenericFunction(GenericFunctionId::Impl(impl_function)) => {
+            // Prefer the function as written in the impl, falling back to the trait function.
+            let impl_function_id = match impl_function.impl_id.long(db) {
+                ImplLongId::Concrete(concrete_impl_id) => {
+                    concrete_impl_id.get_impl_function(db, impl_function.function).ok().flatten()
+                }
+                _ => None,
+            };
+            return Some(DocumentableItemId::from(match impl_function_id {
+                Some(id) => LookupItemId::ImplItem(ImplItemId::Function(id)),
+                None => LookupItemId::TraitItem(TraitItemId::Function(impl_function.function)),
+            }));
+        }
+        ResolvedGenericItem::GenericType(GenericTypeId::Struct(id)) => ModuleItemId::Struct(id),
+        ResolvedGenericItem::GenericType(GenericTypeId::Enum(id)) => ModuleItemId::Enum(id),
+        ResolvedGenericItem::GenericType(GenericTypeId::Extern(id)) => ModuleItemId::ExternType(id),
+        ResolvedGenericItem::GenericTypeAlias(id) => ModuleItemId::TypeAlias(id),
+        ResolvedGenericItem::GenericImplAlias(id) => ModuleItemId::ImplAlias(id),
+        ResolvedGenericItem::Variant(variant) => {
+            return Some(DocumentableItemId::Variant(variant.id));
+        }
+        ResolvedGenericItem::Trait(id) => ModuleItemId::Trait(id),
+        ResolvedGenericItem::Impl(id) => ModuleItemId::Impl(id),
+        ResolvedGenericItem::Macro(id) => ModuleItemId::MacroDeclaration(id),
+        ResolvedGenericItem::TraitItem(id) => {
+            return Some(DocumentableItemId::from(LookupItemId::TraitItem(id)));
+        }
+        ResolvedGenericItem::Variable(_) => return None,
+    };
+    Some(DocumentableItemId::from(LookupItemId::ModuleItem(module_item_id)))
+}
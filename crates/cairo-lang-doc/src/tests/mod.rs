@@ -1,4 +1,5 @@
 pub mod test;
 pub mod test_documentable_formatter;
 pub mod test_documentation_comment_parser_links;
+pub mod test_link_resolution;
 pub mod test_utils;
@@ -0,0 +1,62 @@
+use cairo_lang_defs::ids::{LookupItemId, ModuleId, ModuleItemId, TopLevelLanguageElementId};
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use salsa::Database;
+
+use super::test_utils::{TestDatabase, setup_test_module, test_crate_id};
+use crate::db::DocGroup;
+use crate::documentable_item::DocumentableItemId;
+
+cairo_lang_test_utils::test_file_test!(
+    link_resolution,
+    "src/tests/test-data",
+    {
+        link_resolution: "link_resolution.txt",
+    },
+    link_resolution_runner
+);
+
+fn documentable_item_path<'db>(db: &'db dyn Database, item_id: DocumentableItemId<'db>) -> String {
+    match item_id {
+        DocumentableItemId::Crate(crate_id) => crate_id.long(db).name().long(db).to_string(),
+        DocumentableItemId::LookupItem(LookupItemId::ModuleItem(id)) => id.full_path(db),
+        DocumentableItemId::LookupItem(LookupItemId::TraitItem(id)) => id.full_path(db),
+        DocumentableItemId::LookupItem(LookupItemId::ImplItem(id)) => id.full_path(db),
+        DocumentableItemId::Member(id) => id.full_path(db),
+        DocumentableItemId::Variant(id) => id.full_path(db),
+    }
+}
+
+fn write_module_links<'db>(db: &'db dyn Database, module_id: ModuleId<'db>, output: &mut String) {
+    for item_id in module_id.module_data(db).unwrap().items(db).iter() {
+        let documentable_item_id = DocumentableItemId::from(LookupItemId::ModuleItem(*item_id));
+        let links = db.get_item_documentation_links(documentable_item_id);
+        if !links.is_empty() {
+            output.push_str(&format!("{}:\n", documentable_item_path(db, documentable_item_id)));
+        }
+        for link in links {
+            let resolved = match link.resolved_item {
+                Some(resolved_item) => documentable_item_path(db, resolved_item),
+                None => "<unresolved>".to_string(),
+            };
+            output.push_str(&format!("  {} -> {resolved}\n", link.link));
+        }
+        if let ModuleItemId::Submodule(submodule_id) = item_id {
+            write_module_links(db, ModuleId::Submodule(*submodule_id), output);
+        }
+    }
+}
+
+fn link_resolution_runner(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let mut db_val = TestDatabase::new().unwrap();
+    setup_test_module(&mut db_val, inputs["cairo_code"].as_str());
+    let db = &db_val;
+
+    let mut output = String::new();
+    write_module_links(db, ModuleId::CrateRoot(test_crate_id(db)), &mut output);
+
+    TestRunnerResult::success(OrderedHashMap::from([("resolved_links".into(), output)]))
+}
@@ -13,7 +13,7 @@ use cairo_lang_semantic::plugin::{AnalyzerPlugin, PluginSuite};
 use cairo_lang_semantic::{GenericArgumentId, Mutability, corelib};
 use cairo_lang_syntax::attribute::consts::IMPLICIT_PRECEDENCE_ATTR;
 use cairo_lang_syntax::node::helpers::{OptionWrappedGenericParamListHelper, QueryAttrs};
-use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_syntax::node::{Terminal, TypedStablePtr, TypedSyntaxNode, ast};
 use indoc::formatdoc;
 use itertools::Itertools;
 use salsa::Database;
@@ -213,7 +213,11 @@ impl MacroPlugin for ExecutablePlugin {
                 content,
                 code_mappings,
                 aux_data: None,
-                diagnostics_note: Default::default(),
+                diagnostics_note: Some(format!(
+                    "this error originates in the wrapper of the `#[{EXECUTABLE_ATTR}]` function \
+                     `{}`, which deserializes its params and serializes its result using `Serde`",
+                    name.text(db).long(db)
+                )),
                 is_unhygienic: false,
             }),
             diagnostics,
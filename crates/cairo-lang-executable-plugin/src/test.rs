@@ -3,10 +3,12 @@ use std::sync::{LazyLock, Mutex};
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
 use cairo_lang_plugins::test_utils::expand_module_text;
+use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::test_utils::setup_test_module;
 use cairo_lang_test_utils::parse_test_file::{TestFileRunner, TestRunnerResult};
 use cairo_lang_test_utils::{get_direct_or_file_content, verify_diagnostics_expectation};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
 
 use crate::executable_plugin_suite;
 
@@ -39,8 +41,17 @@ impl TestFileRunner for ExpandExecutableTestRunner {
     ) -> TestRunnerResult {
         let db = SHARED_DB.lock().unwrap().snapshot();
         let (_, cairo_code) = get_direct_or_file_content(&inputs["cairo_code"]);
-        let (test_module, semantic_diagnostics) = setup_test_module(&db, &cairo_code).split();
+        let (test_module, _) = setup_test_module(&db, &cairo_code).split();
         let result = expand_module_text(&db, test_module.module_id, &mut vec![]);
+        // Formatting with the notes of the generated files, to include the wrapper notes.
+        let file_notes = test_module.module_id.module_data(&db).unwrap().diagnostics_notes(&db);
+        let semantic_diagnostics = db
+            .module_semantic_diagnostics(test_module.module_id)
+            .unwrap()
+            .format_with_severity(&db, file_notes)
+            .iter()
+            .map(ToString::to_string)
+            .join("");
         let error = verify_diagnostics_expectation(args, &semantic_diagnostics);
         TestRunnerResult {
             outputs: OrderedHashMap::from([
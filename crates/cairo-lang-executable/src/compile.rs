@@ -16,7 +16,7 @@ use cairo_lang_runnable_utils::builder::{
     CasmProgramWrapperInfo, EntryCodeConfig, RunnableBuilder,
 };
 use cairo_lang_sierra_generator::db::SierraGenGroup;
-use cairo_lang_sierra_generator::debug_info::SierraProgramDebugInfo;
+use cairo_lang_sierra_generator::debug_info::{SierraProgramDebugInfo, SourceMap};
 use cairo_lang_sierra_generator::executables::find_executable_function_ids;
 use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
 use cairo_lang_sierra_to_casm::compiler::CairoProgram;
@@ -31,6 +31,9 @@ pub struct CompiledFunction {
     pub program: CairoProgram,
     /// The wrapper information for the program.
     pub wrapper: CasmProgramWrapperInfo,
+    /// The source map of the compiled program, if requested (see [ExecutableConfig::source_map]).
+    /// The pcs are relative to the start of the Sierra based code.
+    pub source_map: Option<SourceMap>,
 }
 impl std::fmt::Display for CompiledFunction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -68,6 +71,9 @@ pub struct ExecutableConfig {
     /// An optional list of builtins to use in the entry code (if its none the builtins will be
     /// inferred from the param_types).
     pub builtin_list: Option<Vec<BuiltinName>>,
+
+    /// If true, a source map from the CASM code to the Cairo code will be generated.
+    pub source_map: bool,
 }
 
 /// Represents the output of compiling an executable.
@@ -250,6 +256,15 @@ pub fn compile_executable_function_in_prepared_db<'db>(
         &executable_func,
         EntryCodeConfig::executable(allow_unsound, config.builtin_list),
     )?;
-    let compiled_function = CompiledFunction { program: builder.casm_program().clone(), wrapper };
+    let source_map = config.source_map.then(|| {
+        let program = builder.casm_program();
+        SourceMap::new(
+            program.debug_info.sierra_statement_info.iter().map(|s| (s.start_offset, s.end_offset)),
+            &debug_info.statements_locations.extract_statements_source_code_locations(db),
+            0,
+        )
+    });
+    let compiled_function =
+        CompiledFunction { program: builder.casm_program().clone(), wrapper, source_map };
     Ok(CompileExecutableResult { compiled_function, builder, debug_info: debug_info.clone() })
 }
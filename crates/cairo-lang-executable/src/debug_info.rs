@@ -1,3 +1,4 @@
+use cairo_lang_sierra_generator::debug_info::SourceMap;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use serde::{Deserialize, Serialize};
 
@@ -43,6 +44,22 @@ pub struct ProgramInformation {
     pub program_offset: usize,
 }
 
+/// The namespace of the [SourceMap] annotation.
+pub const SOURCE_MAP_NAMESPACE: &str = "github.com/starkware-libs/cairo/source-map";
+
+impl DebugInfo {
+    /// Adds the source map of the executable to the annotations.
+    pub fn set_source_map(&mut self, source_map: SourceMap) {
+        self.annotations
+            .insert(SOURCE_MAP_NAMESPACE.to_string(), serde_json::to_value(source_map).unwrap());
+    }
+
+    /// Returns the source map of the executable, if it was compiled with one.
+    pub fn source_map(&self) -> Option<SourceMap> {
+        serde_json::from_value(self.annotations.get(SOURCE_MAP_NAMESPACE)?.clone()).ok()
+    }
+}
+
 impl From<ProgramInformation> for Annotations {
     fn from(value: ProgramInformation) -> Self {
         let mapping = serde_json::to_value(value).unwrap();
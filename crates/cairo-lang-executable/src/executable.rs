@@ -1,7 +1,13 @@
+use std::collections::BTreeMap;
+
 use cairo_lang_casm::assembler::AssembledCairoProgram;
 use cairo_lang_casm::casm;
+use cairo_lang_casm::hints::Hint;
+use cairo_lang_casm::instructions::Instruction;
+use cairo_lang_utils::bigint::{deserialize_big_ints, serialize_big_ints};
 use cairo_vm::types::builtin_name::BuiltinName;
 use itertools::chain;
+use num_bigint::BigInt;
 use serde::{Deserialize, Serialize};
 
 use crate::compile::CompiledFunction;
@@ -9,16 +15,39 @@ use crate::debug_info::{Annotations, DebugInfo, ProgramInformation};
 
 pub const NOT_RETURNING_HEADER_SIZE: usize = 6;
 
+/// The version of the executable format written by this compiler.
+///
+/// Executables serialized before the format was versioned are read as version 0.
+pub const EXECUTABLE_FORMAT_VERSION: u32 = 1;
+
 /// Structure to hold the executable representation of a program.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "SerializedExecutable", into = "SerializedExecutable")]
 pub struct Executable {
+    /// The version of the executable format.
+    pub version: u32,
     /// The bytecode of the program.
     pub program: AssembledCairoProgram,
     /// The available entrypoints for the program.
     pub entrypoints: Vec<ExecutableEntryPoint>,
     /// Debug information for the assembled program.
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub debug_info: Option<DebugInfo>,
+    /// The hints of the program that are unknown to this version of the tooling, such as hints
+    /// added by a newer compiler.
+    ///
+    /// These are kept as is, so that serializing the executable again preserves them.
+    pub unknown_hints: Vec<UnknownHint>,
+}
+
+/// A serialized hint of an executable that is not a known [Hint].
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownHint {
+    /// The bytecode offset of the instruction the hint is attached to.
+    pub pc: usize,
+    /// The index of the hint among the hints attached to the instruction.
+    pub index: usize,
+    /// The serialized hint.
+    pub value: serde_json::Value,
 }
 
 impl Executable {
@@ -30,7 +59,16 @@ impl Executable {
             jmp rel 0;
         };
         assert_eq!(non_returning_header.current_code_offset, NOT_RETURNING_HEADER_SIZE);
+        let program_offset = NOT_RETURNING_HEADER_SIZE
+            + compiled.wrapper.header.iter().map(Instruction::encoded_size).sum::<usize>();
+        let mut debug_info =
+            DebugInfo { annotations: Annotations::from(ProgramInformation { program_offset }) };
+        if let Some(mut source_map) = compiled.source_map {
+            source_map.offset_pcs(program_offset);
+            debug_info.set_source_map(source_map);
+        }
         Self {
+            version: EXECUTABLE_FORMAT_VERSION,
             program: compiled.program.assemble_ex(
                 chain!(&non_returning_header.instructions, &compiled.wrapper.header),
                 &compiled.wrapper.footer,
@@ -47,17 +85,85 @@ impl Executable {
                     kind: EntryPointKind::Bootloader,
                 },
             ],
-            debug_info: Some(DebugInfo {
-                annotations: Annotations::from(ProgramInformation {
-                    program_offset: NOT_RETURNING_HEADER_SIZE
-                        + compiled
-                            .wrapper
-                            .header
-                            .iter()
-                            .map(|inst| inst.body.op_size())
-                            .sum::<usize>(),
-                }),
-            }),
+            debug_info: Some(debug_info),
+            unknown_hints: vec![],
+        }
+    }
+}
+
+/// The serialized form of an [Executable], where the hints are kept as raw values.
+#[derive(Serialize, Deserialize)]
+struct SerializedExecutable {
+    #[serde(default)]
+    version: u32,
+    program: SerializedProgram,
+    entrypoints: Vec<ExecutableEntryPoint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    debug_info: Option<DebugInfo>,
+}
+
+/// The serialized form of an [AssembledCairoProgram], where the hints are kept as raw values.
+#[derive(Serialize, Deserialize)]
+struct SerializedProgram {
+    #[serde(serialize_with = "serialize_big_ints", deserialize_with = "deserialize_big_ints")]
+    bytecode: Vec<BigInt>,
+    hints: Vec<(usize, Vec<serde_json::Value>)>,
+}
+
+impl From<SerializedExecutable> for Executable {
+    fn from(serialized: SerializedExecutable) -> Self {
+        let SerializedExecutable { version, program, entrypoints, debug_info } = serialized;
+        let mut hints = vec![];
+        let mut unknown_hints = vec![];
+        for (pc, values) in program.hints {
+            let mut known_hints = vec![];
+            for (index, value) in values.into_iter().enumerate() {
+                match Hint::deserialize(&value) {
+                    Ok(hint) => known_hints.push(hint),
+                    Err(_) => unknown_hints.push(UnknownHint { pc, index, value }),
+                }
+            }
+            if !known_hints.is_empty() {
+                hints.push((pc, known_hints));
+            }
+        }
+        Self {
+            version,
+            program: AssembledCairoProgram { bytecode: program.bytecode, hints },
+            entrypoints,
+            debug_info,
+            unknown_hints,
+        }
+    }
+}
+
+impl From<Executable> for SerializedExecutable {
+    fn from(executable: Executable) -> Self {
+        let Executable { version, program, entrypoints, debug_info, unknown_hints } = executable;
+        let mut hints: BTreeMap<usize, Vec<serde_json::Value>> = program
+            .hints
+            .into_iter()
+            .map(|(pc, hints)| {
+                let values = hints
+                    .iter()
+                    .map(|hint| serde_json::to_value(hint).expect("Hints are serializable."))
+                    .collect();
+                (pc, values)
+            })
+            .collect();
+        // Unknown hints are sorted by their position, so inserting them in order restores it.
+        for UnknownHint { pc, index, value } in unknown_hints {
+            let values = hints.entry(pc).or_default();
+            values.insert(index.min(values.len()), value);
+        }
+        Self {
+            version,
+            program: SerializedProgram {
+                bytecode: program.bytecode,
+                hints: hints.into_iter().collect(),
+            },
+            entrypoints,
+            debug_info,
         }
     }
 }
@@ -1,5 +1,8 @@
 use std::sync::{LazyLock, Mutex};
 
+use cairo_lang_casm::assembler::AssembledCairoProgram;
+use cairo_lang_casm::cell_ref;
+use cairo_lang_casm::hints::{CoreHint, Hint};
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_executable_plugin::executable_plugin_suite;
@@ -11,8 +14,13 @@ use cairo_lang_test_utils::{
 };
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_vm::types::builtin_name::BuiltinName;
+use num_bigint::BigInt;
+use serde_json::json;
 
 use crate::compile::{self, ExecutableConfig};
+use crate::executable::{
+    EXECUTABLE_FORMAT_VERSION, Executable, NOT_RETURNING_HEADER_SIZE, UnknownHint,
+};
 
 /// Salsa database configured to find the corelib, when reused by different tests should be able to
 /// use the cached queries that rely on the corelib's code, which vastly reduces the tests runtime.
@@ -93,3 +101,77 @@ cairo_lang_test_utils::test_file_test_with_runner!(
     },
     CompileExecutableTestRunner
 );
+
+#[test]
+fn source_map_is_embedded_in_executable() {
+    let db = SHARED_DB.lock().unwrap().snapshot();
+    let test_module = setup_test_module(
+        &db,
+        "#[executable]\nfn main(a: felt252) -> felt252 {\n    a * a + 1\n}\n",
+    )
+    .unwrap();
+    let config = ExecutableConfig { source_map: true, ..ExecutableConfig::default() };
+    let compiled = compile::compile_executable_in_prepared_db(
+        &db,
+        None,
+        vec![test_module.crate_id],
+        DiagnosticsReporter::stderr(),
+        config,
+    )
+    .unwrap()
+    .compiled_function;
+    let relative_source_map = compiled.source_map.clone().unwrap();
+    let header_size = NOT_RETURNING_HEADER_SIZE
+        + compiled.wrapper.header.iter().map(|inst| inst.body.op_size()).sum::<usize>();
+    let executable = Executable::new(compiled);
+    let source_map = executable.debug_info.unwrap().source_map().unwrap();
+    let first_entry = &source_map.entries[0];
+    assert_eq!(first_entry.pc_start, relative_source_map.entries[0].pc_start + header_size);
+    assert!(source_map.files.contains(&"lib.cairo".to_string()));
+    assert!(source_map.entries.iter().any(|entry| !entry.locations.is_empty()));
+}
+
+/// Returns an executable with a single known hint, attached to its first instruction.
+fn executable_with_hint() -> Executable {
+    let hint = Hint::Core(CoreHint::AllocSegment { dst: cell_ref!([ap + 0]) }.into());
+    Executable {
+        version: EXECUTABLE_FORMAT_VERSION,
+        program: AssembledCairoProgram {
+            bytecode: vec![BigInt::from(1), BigInt::from(2), BigInt::from(3)],
+            hints: vec![(0, vec![hint])],
+        },
+        entrypoints: vec![],
+        debug_info: None,
+        unknown_hints: vec![],
+    }
+}
+
+#[test]
+fn serialization_preserves_unknown_hints() {
+    let mut json = serde_json::to_value(executable_with_hint()).unwrap();
+    assert_eq!(json["version"], json!(EXECUTABLE_FORMAT_VERSION));
+    let known_hint = json["program"]["hints"][0][1][0].clone();
+    let unknown_hint = json!({"FutureHint": {"dst": {"register": "AP", "offset": 0}}});
+    json["program"]["hints"] = json!([[0, [unknown_hint, known_hint]], [2, [unknown_hint]]]);
+
+    let executable: Executable = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(executable.program.hints, executable_with_hint().program.hints);
+    assert_eq!(
+        executable.unknown_hints,
+        vec![
+            UnknownHint { pc: 0, index: 0, value: unknown_hint.clone() },
+            UnknownHint { pc: 2, index: 0, value: unknown_hint },
+        ]
+    );
+    assert_eq!(serde_json::to_value(executable).unwrap(), json);
+}
+
+#[test]
+fn deserialize_unversioned_executable() {
+    let mut json = serde_json::to_value(executable_with_hint()).unwrap();
+    json.as_object_mut().unwrap().remove("version");
+    let executable: Executable = serde_json::from_value(json).unwrap();
+    assert_eq!(executable.version, 0);
+    assert_eq!(executable.program.hints, executable_with_hint().program.hints);
+    assert!(executable.unknown_hints.is_empty());
+}
@@ -1,16 +1,96 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 use cairo_lang_casm::hints::Hint;
-use cairo_lang_executable::executable::{EntryPointKind, Executable, ExecutableEntryPoint};
-use cairo_lang_runner::{Arg, build_hints_dict};
+use cairo_lang_executable::executable::{
+    EXECUTABLE_FORMAT_VERSION, EntryPointKind, Executable, ExecutableEntryPoint,
+};
+use cairo_lang_runner::casm_run::format_for_panic;
+use cairo_lang_runner::{Arg, CairoHintProcessor, build_hints_dict};
 use cairo_lang_utils::bigint::BigUintAsHex;
 use cairo_vm::Felt252;
+use cairo_vm::cairo_run::{CairoRunConfig, cairo_run_program};
+use cairo_vm::types::builtin_name::BuiltinName;
+use cairo_vm::types::layout_name::LayoutName;
 use cairo_vm::types::program::Program;
-use cairo_vm::types::relocatable::MaybeRelocatable;
+use cairo_vm::types::relocatable::{MaybeRelocatable, Relocatable};
 use num_bigint::BigInt;
 
+#[cfg(test)]
+mod test;
+
+/// Loads a prebuilt executable from the file at `path`.
+pub fn load_executable(path: &Path) -> anyhow::Result<Executable> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed opening executable file `{}`.", path.display()))?;
+    serde_json::from_reader(std::io::BufReader::new(file))
+        .with_context(|| "Failed reading prebuilt executable.")
+}
+
+/// Runs the entrypoint of kind `kind` of `executable` with the given user arguments, and returns
+/// the values written to the output builtin.
+///
+/// Runs on the `all_cairo` layout, without collecting a trace - for full control over the run, use
+/// [program_and_hints_from_executable] directly.
+pub fn run_executable(
+    executable: &Executable,
+    kind: EntryPointKind,
+    user_args: Vec<Arg>,
+) -> anyhow::Result<Vec<Felt252>> {
+    let entrypoint = executable
+        .entrypoints
+        .iter()
+        .find(|e| e.kind == kind)
+        .with_context(|| format!("{kind:?} entrypoint not found"))?;
+    let (program, string_to_hint) = program_and_hints_from_executable(executable, entrypoint)?;
+    let mut hint_processor = CairoHintProcessor {
+        runner: None,
+        user_args: vec![vec![Arg::Array(user_args)]],
+        string_to_hint,
+        starknet_state: Default::default(),
+        run_resources: Default::default(),
+        syscalls_used_resources: Default::default(),
+        no_temporary_segments: false,
+        markers: Default::default(),
+        panic_traceback: Default::default(),
+        syscall_interceptor: None,
+        captured_output: None,
+    };
+    let standalone = kind == EntryPointKind::Standalone;
+    let cairo_run_config = CairoRunConfig {
+        layout: LayoutName::all_cairo,
+        proof_mode: standalone,
+        fill_holes: standalone,
+        ..Default::default()
+    };
+    let mut runner = match cairo_run_program(&program, &cairo_run_config, &mut hint_processor) {
+        Ok(runner) => runner,
+        Err(err) => {
+            if let Some(panic_data) = hint_processor.markers.last() {
+                return Err(err).with_context(|| format_for_panic(panic_data.iter().copied()));
+            }
+            return Err(err).context("Failed running program.");
+        }
+    };
+    let Some(output_builtin) =
+        runner.vm.builtin_runners.iter().find(|builtin| builtin.name() == BuiltinName::output)
+    else {
+        return Ok(vec![]);
+    };
+    let segment_index = output_builtin.base();
+    let output_size = runner.vm.segments.compute_effective_sizes()[segment_index];
+    (0..output_size)
+        .map(|offset| {
+            runner
+                .vm
+                .get_integer(Relocatable::from((segment_index as isize, offset)))
+                .map(|value| *value)
+                .with_context(|| format!("Invalid output value at offset {offset}."))
+        })
+        .collect()
+}
+
 // Returns a tuple containing:
 /// 1. The Program instance - set according to the type of the required entrypoint.
 /// 2. The hint by string mapping.
@@ -20,6 +100,17 @@ pub fn program_and_hints_from_executable(
     executable: &Executable,
     entrypoint: &ExecutableEntryPoint,
 ) -> anyhow::Result<(Program, HashMap<String, Hint>)> {
+    if !executable.unknown_hints.is_empty() {
+        let mut pcs = executable.unknown_hints.iter().map(|hint| hint.pc).collect::<Vec<_>>();
+        pcs.dedup();
+        anyhow::bail!(
+            "The executable (format version {}) contains hints unknown to this version of the \
+             tooling (supporting up to format version {}), at pcs: {}.",
+            executable.version,
+            EXECUTABLE_FORMAT_VERSION,
+            pcs.iter().map(|pc| pc.to_string()).collect::<Vec<_>>().join(", ")
+        );
+    }
     let data: Vec<MaybeRelocatable> =
         executable.program.bytecode.iter().map(Felt252::from).map(MaybeRelocatable::from).collect();
     let (hints, string_to_hint) = build_hints_dict(&executable.program.hints);
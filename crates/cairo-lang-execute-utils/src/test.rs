@@ -0,0 +1,40 @@
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_executable::compile::{
+    ExecutableConfig, compile_executable_in_prepared_db, prepare_db,
+};
+use cairo_lang_executable::executable::{EntryPointKind, Executable};
+use cairo_lang_runner::Arg;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_vm::Felt252;
+use test_case::test_case;
+
+use crate::{load_executable, run_executable};
+
+#[test_case(EntryPointKind::Bootloader; "bootloader")]
+#[test_case(EntryPointKind::Standalone; "standalone")]
+fn load_and_run_executable(kind: EntryPointKind) {
+    let config = ExecutableConfig::default();
+    let db = prepare_db(&config).unwrap();
+    let test_module = setup_test_module(
+        &db,
+        "#[executable]\nfn main(a: felt252, b: felt252) -> felt252 {\n    a * b + 1\n}\n",
+    )
+    .unwrap();
+    let compiled = compile_executable_in_prepared_db(
+        &db,
+        None,
+        vec![test_module.crate_id],
+        DiagnosticsReporter::stderr(),
+        config,
+    )
+    .unwrap()
+    .compiled_function;
+    let path = std::env::temp_dir()
+        .join(format!("load_and_run_executable_{kind:?}_{}.json", std::process::id()));
+    std::fs::write(&path, serde_json::to_string(&Executable::new(compiled)).unwrap()).unwrap();
+    let executable = load_executable(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let args = vec![Arg::Value(Felt252::from(3)), Arg::Value(Felt252::from(4))];
+    assert_eq!(run_executable(&executable.unwrap(), kind, args).unwrap(), vec![Felt252::from(13)]);
+}
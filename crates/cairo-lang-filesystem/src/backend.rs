@@ -0,0 +1,180 @@
+//! The backends of the filesystem - the sources of the content of on-disk files and directories.
+//!
+//! By default the OS filesystem is used. Embedders may plug in other sources (e.g. an archive, or
+//! a remote storage) by implementing [FsBackend] and setting it with
+//! [set_fs_backend](crate::db::set_fs_backend).
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[cfg(test)]
+#[path = "backend_test.rs"]
+mod test;
+
+/// A source of the content of on-disk files and directories, i.e. of the paths of
+/// [FileLongId::OnDisk](crate::ids::FileLongId::OnDisk), [Directory::Real](crate::ids::Directory)
+/// and [BlobLongId::OnDisk](crate::ids::BlobLongId::OnDisk).
+pub trait FsBackend: Debug + Send + Sync {
+    /// Returns the content of the file at the path, or `None` if it cannot be read.
+    fn read(&self, path: &Path) -> Option<Vec<u8>>;
+
+    /// Returns whether there is a file or a directory at the path.
+    fn exists(&self, path: &Path) -> bool;
+
+    /// Returns the paths of the entries of the directory at the path, files and directories, in
+    /// sorted order. Returns `None` if there is no such directory.
+    fn read_dir(&self, path: &Path) -> Option<Vec<PathBuf>>;
+
+    /// Returns whether there is a file at the path.
+    fn is_file(&self, path: &Path) -> bool {
+        self.read(path).is_some()
+    }
+
+    /// Returns whether there is a directory at the path.
+    fn is_dir(&self, path: &Path) -> bool {
+        self.read_dir(path).is_some()
+    }
+
+    /// Returns the content of the file at the path, or `None` if it cannot be read or is not valid
+    /// UTF-8.
+    fn read_to_string(&self, path: &Path) -> Option<String> {
+        String::from_utf8(self.read(path)?).ok()
+    }
+}
+
+/// The shared handle of a [FsBackend].
+pub type FsBackendRef = Arc<dyn FsBackend>;
+
+/// The OS filesystem.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OsFsBackend;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FsBackend for OsFsBackend {
+    fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        std::fs::read(path).ok()
+    }
+
+    fn read_to_string(&self, path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn read_dir(&self, path: &Path) -> Option<Vec<PathBuf>> {
+        let mut entries = std::fs::read_dir(path)
+            .ok()?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        entries.sort();
+        Some(entries)
+    }
+}
+
+/// There is no disk on `wasm32`, so the content of on-disk files must be provided through another
+/// backend, or through the file overrides.
+#[cfg(target_arch = "wasm32")]
+impl FsBackend for OsFsBackend {
+    fn read(&self, _path: &Path) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn exists(&self, _path: &Path) -> bool {
+        false
+    }
+
+    fn read_dir(&self, _path: &Path) -> Option<Vec<PathBuf>> {
+        None
+    }
+
+    fn is_file(&self, _path: &Path) -> bool {
+        false
+    }
+
+    fn is_dir(&self, _path: &Path) -> bool {
+        false
+    }
+}
+
+/// A filesystem held in memory. Directories are implied by the paths of the files in them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryFsBackend {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemoryFsBackend {
+    /// Returns an empty filesystem.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file to the filesystem, replacing the existing file at the path, if any.
+    pub fn add_file(&mut self, path: impl Into<PathBuf>, content: impl Into<Vec<u8>>) {
+        self.files.insert(path.into(), content.into());
+    }
+
+    /// Returns the filesystem with the given file added. See [Self::add_file].
+    pub fn with_file(mut self, path: impl Into<PathBuf>, content: impl Into<Vec<u8>>) -> Self {
+        self.add_file(path, content);
+        self
+    }
+
+    /// Removes the file at the path from the filesystem, returning its content.
+    pub fn remove_file(&mut self, path: &Path) -> Option<Vec<u8>> {
+        self.files.remove(path)
+    }
+
+    /// Returns the paths of the files under the directory at the path, recursively.
+    fn files_under<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = &'a PathBuf> {
+        self.files
+            .range(path.to_path_buf()..)
+            .map(|(file, _)| file)
+            .take_while(move |file| file.starts_with(path))
+    }
+}
+
+impl FsBackend for MemoryFsBackend {
+    fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        self.files.get(path).cloned()
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files_under(path).next().is_some()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.files_under(path).any(|file| file != path)
+    }
+
+    fn read_dir(&self, path: &Path) -> Option<Vec<PathBuf>> {
+        let mut entries = vec![];
+        for file in self.files_under(path) {
+            // Files directly in the directory, or the directories containing the other files.
+            let Some(name) = file.strip_prefix(path).ok()?.components().next() else {
+                continue;
+            };
+            let entry = path.join(name);
+            if entries.last() != Some(&entry) {
+                entries.push(entry);
+            }
+        }
+        if entries.is_empty() { None } else { Some(entries) }
+    }
+}
@@ -0,0 +1,83 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use cairo_lang_test_utils::test;
+use cairo_lang_utils::Intern;
+
+use super::{FsBackend, MemoryFsBackend};
+use crate::db::{FilesGroup, set_fs_backend};
+use crate::ids::{BlobLongId, Directory};
+use crate::override_file_content;
+use crate::test_utils::FilesDatabaseForTesting;
+
+fn memory_fs() -> MemoryFsBackend {
+    MemoryFsBackend::new()
+        .with_file("src/lib.cairo", "mod a;\n")
+        .with_file("src/a.cairo", "fn a() {}\n")
+        .with_file("src/a/b.cairo", "fn b() {}\n")
+        .with_file("src/blob.bin", [0xff, 0x00])
+}
+
+#[test]
+fn test_memory_fs_backend() {
+    let fs = memory_fs();
+
+    assert_eq!(fs.read_to_string(Path::new("src/a.cairo")).as_deref(), Some("fn a() {}\n"));
+    assert_eq!(fs.read(Path::new("src/blob.bin")), Some(vec![0xff, 0x00]));
+    assert_eq!(fs.read_to_string(Path::new("src/blob.bin")), None);
+    assert_eq!(fs.read(Path::new("src/c.cairo")), None);
+    assert_eq!(fs.read(Path::new("src")), None);
+
+    assert!(fs.exists(Path::new("src")));
+    assert!(fs.exists(Path::new("src/a")));
+    assert!(fs.exists(Path::new("src/a/b.cairo")));
+    assert!(!fs.exists(Path::new("src/c.cairo")));
+    assert!(!fs.exists(Path::new("sr")));
+
+    assert!(fs.is_file(Path::new("src/a.cairo")));
+    assert!(!fs.is_file(Path::new("src/a")));
+    assert!(fs.is_dir(Path::new("src/a")));
+    assert!(!fs.is_dir(Path::new("src/a.cairo")));
+    assert!(!fs.is_dir(Path::new("tests")));
+
+    assert_eq!(
+        fs.read_dir(Path::new("src")),
+        Some(
+            ["src/a", "src/a.cairo", "src/blob.bin", "src/lib.cairo"]
+                .into_iter()
+                .map(PathBuf::from)
+                .collect()
+        )
+    );
+    assert_eq!(fs.read_dir(Path::new("src/a")), Some(vec![PathBuf::from("src/a/b.cairo")]));
+    assert_eq!(fs.read_dir(Path::new("src/lib.cairo")), None);
+    assert_eq!(fs.read_dir(Path::new("tests")), None);
+}
+
+#[test]
+fn test_fs_backend_in_db() {
+    let mut db = FilesDatabaseForTesting::default();
+    set_fs_backend(&mut db, Arc::new(memory_fs()));
+
+    let directory = Directory::Real("src".into());
+    assert_eq!(db.file_content(directory.file(&db, "a.cairo")), Some("fn a() {}\n"));
+    assert_eq!(db.file_content(directory.file(&db, "c.cairo")), None);
+    let blob_id = BlobLongId::OnDisk("src/blob.bin".into()).intern(&db);
+    assert_eq!(db.blob_content(blob_id), Some(&[0xff, 0x00][..]));
+    assert_eq!(
+        db.directory_entries(&directory),
+        Some(vec!["a".into(), "a.cairo".into(), "blob.bin".into(), "lib.cairo".into()])
+    );
+    assert_eq!(db.directory_entries(&directory.subdir("a")), Some(vec!["b.cairo".into()]));
+
+    // Overrides still take precedence over the backend.
+    let db_ref = &mut db;
+    let file_id = directory.file(db_ref, "a.cairo");
+    override_file_content!(db_ref, file_id, Some("fn overridden() {}\n".into()));
+    assert_eq!(db.file_content(directory.file(&db, "a.cairo")), Some("fn overridden() {}\n"));
+
+    // Replacing the backend updates the content of the files.
+    set_fs_backend(&mut db, Arc::new(MemoryFsBackend::new().with_file("src/c.cairo", "")));
+    assert_eq!(db.file_content(directory.file(&db, "c.cairo")), Some(""));
+    assert_eq!(db.directory_entries(&directory), Some(vec!["c.cairo".into()]));
+}
@@ -1,23 +1,28 @@
-use std::collections::BTreeMap;
-use std::fs;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use cairo_lang_utils::Intern;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use itertools::Itertools;
 use salsa::{Database, Setter};
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
+use crate::backend::{FsBackend, FsBackendRef, OsFsBackend};
 use crate::cfg::CfgSet;
-use crate::flag::Flag;
+use crate::flag::{CompilerFlags, FlagsGroup};
 use crate::ids::{
     ArcStr, BlobId, BlobLongId, CodeMapping, CodeOrigin, CrateId, CrateInput, CrateLongId,
-    Directory, DirectoryInput, FileId, FileInput, FileLongId, FlagLongId, SmolStrId, SpanInFile,
-    Tracked, VirtualFile,
+    Directory, DirectoryInput, FileId, FileInput, FileLongId, SmolStrId, SpanInFile, Tracked,
+    VirtualFile,
+};
+use crate::span::{
+    FileSummary, InvalidTextEdit, TextEdit, TextEditMapping, TextOffset, TextSpan, TextWidth,
 };
-use crate::span::{FileSummary, TextOffset, TextSpan, TextWidth};
 
 #[cfg(test)]
 #[path = "db_test.rs"]
@@ -111,6 +116,26 @@ pub struct CrateSettings {
     pub version: Option<Version>,
     /// The `#[cfg(...)]` configuration.
     pub cfg_set: Option<CfgSet>,
+    /// The names of the `#[cfg(...)]` options the crate is expected to check.
+    ///
+    /// If set, checking any other name is reported as a warning - except for names of options in
+    /// the crate's configuration, and names set by the compiler tools (such as `test`).
+    pub expected_cfg_names: Option<BTreeSet<String>>,
+    /// The levels of lints in the crate by lint (or lint group) name, overriding their default
+    /// levels. Overridden by the `#[allow(...)]`, `#[warn(...)]` and `#[deny(...)]` attributes.
+    /// Warnings may be configured the same way by their diagnostic code (e.g. `E0001`), or all
+    /// together by the `warnings` lint group.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lints: BTreeMap<String, LintLevel>,
+    /// Whether to skip the early insertion of `unsafe_panic` calls in the crate's functions when
+    /// the `unsafe_panic` flag is set, e.g. for code relying on the behavior of externs that do
+    /// not return. Functions may also opt out by the `#[no_early_unsafe_panic]` attribute.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    pub no_early_unsafe_panic: bool,
+    /// The overrides of the compilation flags for the crate. Only flags that may differ between
+    /// crates may be overridden, see [crate::flag::Flag::is_crate_overridable].
+    #[serde(default, skip_serializing_if = "CompilerFlags::is_empty")]
+    pub flags: CompilerFlags,
     /// The crate's dependencies.
     #[serde(default)]
     pub dependencies: BTreeMap<String, DependencySettings>,
@@ -119,6 +144,31 @@ pub struct CrateSettings {
     pub experimental_features: ExperimentalFeaturesConfig,
 }
 
+/// The level of a lint - whether its diagnostics are ignored, or reported as warnings or errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, salsa::Update)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+/// The dependency graph of the crates in the project.
+#[derive(Clone, Debug, Default, PartialEq, Eq, salsa::Update)]
+pub struct CrateGraph<'db> {
+    /// The configured crates, in the order of [FilesGroup::crates].
+    pub crates: OrderedHashMap<CrateId<'db>, CrateGraphNode<'db>>,
+}
+
+/// A crate in the [CrateGraph].
+#[derive(Clone, Debug, PartialEq, Eq, salsa::Update)]
+pub struct CrateGraphNode<'db> {
+    /// The settings of the crate.
+    pub settings: CrateSettings,
+    /// The crates the crate declares as dependencies, which may not be configured themselves.
+    pub dependencies: Vec<CrateId<'db>>,
+}
+
 /// Tracked function to return the default settings for a crate.
 /// This is used to initialize the default settings once, and return it by reference.
 #[salsa::tracked(returns(ref))]
@@ -218,6 +268,10 @@ pub struct ExperimentalFeaturesConfig {
     /// Allows using representation pointer types (&T), which desugar to BoxTrait<@T>.
     #[serde(default)]
     pub repr_ptrs: bool,
+    /// Allows marking impls with `#[fallback]`, which are selected only if no other impl solves
+    /// the trait.
+    #[serde(default)]
+    pub fallback_impls: bool,
 }
 
 /// Function to get a virtual file from an external id.
@@ -237,17 +291,39 @@ pub struct FilesGroupInput {
     // TODO(yuval): consider moving this to a separate crate, or rename this crate.
     /// The compilation flags.
     #[returns(ref)]
-    pub flags: Option<OrderedHashMap<FlagLongId, Flag>>,
+    pub flags: Option<CompilerFlags>,
     /// The `#[cfg(...)]` options.
     #[returns(ref)]
     pub cfg_set: Option<CfgSet>,
     #[returns(ref)]
     pub ext_as_virtual_obj: Option<ExtAsVirtual>,
+    /// Edits applied to overridden file contents. Used for incremental processing of the files.
+    #[returns(ref)]
+    pub file_edits: Option<OrderedHashMap<FileInput, FileEdits>>,
+    /// The source of the content of on-disk files and directories. The OS filesystem if not set.
+    #[returns(ref)]
+    pub fs_backend: Option<FsBackendRef>,
+    /// The files overridden or edited since they were last taken by [take_touched_files].
+    #[returns(ref)]
+    pub touched_files: Option<OrderedHashSet<FileInput>>,
 }
 
 #[salsa::tracked]
 pub fn files_group_input(db: &dyn Database) -> FilesGroupInput {
-    FilesGroupInput::new(db, None, None, None, None, None)
+    FilesGroupInput::new(db, None, None, None, None, None, None, None, None)
+}
+
+/// The maximal number of edits recorded for a file. When reached, the edits are recorded again from
+/// the content of the file before the new edits, so the edits are not accumulated forever.
+pub const MAX_RECORDED_FILE_EDITS: usize = 64;
+
+/// The latest edits applied to the content of a file, since it was last set in full.
+#[derive(Clone, Debug, PartialEq, Eq, salsa::Update)]
+pub struct FileEdits {
+    /// The content of the file before the edits.
+    pub base_content: Arc<str>,
+    /// The edits, in the order they were applied.
+    pub edits: Vec<TextEdit>,
 }
 
 /// Queries over the files group.
@@ -275,15 +351,68 @@ pub trait FilesGroup: Database {
         crate_config(self.as_dyn_database(), crate_id)
     }
 
+    /// The crates the crate declares as dependencies in its settings, whether they are configured
+    /// or not.
+    fn declared_crate_dependencies<'db>(&'db self, crate_id: CrateId<'db>) -> &'db [CrateId<'db>] {
+        declared_crate_dependencies(self.as_dyn_database(), crate_id)
+    }
+
+    /// The dependency graph of the crates in the project.
+    fn crate_dependency_graph<'db>(&'db self) -> &'db CrateGraph<'db> {
+        crate_dependency_graph(self.as_dyn_database())
+    }
+
+    /// A shortest cycle of dependencies through the crate, if it is a part of one. The cycle
+    /// starts and ends with the crate, e.g. `[a, b, a]` if `a` and `b` depend on each other.
+    fn crate_dependency_cycle<'db>(
+        &'db self,
+        crate_id: CrateId<'db>,
+    ) -> Option<&'db [CrateId<'db>]> {
+        crate_dependency_cycle(self.as_dyn_database(), crate_id).as_deref()
+    }
+
+    /// A stable hash of the content of the file, or `None` if it has no content.
+    ///
+    /// Unlike the ids of the database, fingerprints are stable across runs, so they may be used as
+    /// keys of on-disk caches.
+    fn file_fingerprint<'db>(&'db self, file_id: FileId<'db>) -> Option<u64> {
+        file_fingerprint(self.as_dyn_database(), file_id)
+    }
+
+    /// A stable hash of everything the compilation of the crate depends on, other than its files:
+    /// the compiler version, the crate settings, the global cfg set and the compilation flags.
+    fn crate_settings_fingerprint<'db>(&'db self, crate_id: CrateId<'db>) -> u64 {
+        crate_settings_fingerprint(self.as_dyn_database(), crate_id)
+    }
+
     /// Query for the file contents. This takes overrides into consideration.
     fn file_content<'db>(&'db self, file_id: FileId<'db>) -> Option<&'db str> {
         file_content(self.as_dyn_database(), file_id).as_ref().map(|content| content.as_ref())
     }
 
+    /// The edits applied to the content of the file since it was last set in full, if any.
+    fn file_edits<'db>(&'db self, file_id: FileId<'db>) -> Option<&'db FileEdits> {
+        file_edits(self.as_dyn_database(), file_id).as_ref()
+    }
+
     fn file_summary<'db>(&'db self, file_id: FileId<'db>) -> Option<&'db FileSummary> {
         file_summary(self.as_dyn_database(), file_id)
     }
 
+    /// The source of the content of on-disk files and directories.
+    fn fs_backend(&self) -> &dyn FsBackend {
+        match files_group_input(self.as_dyn_database()).fs_backend(self.as_dyn_database()) {
+            Some(backend) => backend.as_ref(),
+            None => &OsFsBackend,
+        }
+    }
+
+    /// The names of the entries of the directory, files and directories, in sorted order.
+    /// Returns `None` if there is no such directory.
+    fn directory_entries(&self, directory: &Directory<'_>) -> Option<Vec<String>> {
+        directory_entries(self.as_dyn_database(), directory)
+    }
+
     /// Query for the blob content.
     fn blob_content<'db>(&'db self, blob_id: BlobId<'db>) -> Option<&'db [u8]> {
         blob_content(self.as_dyn_database(), blob_id)
@@ -322,6 +451,13 @@ pub fn init_files_group<'db>(db: &mut (dyn Database + 'db)) {
     inp.set_crate_configs(db).to(Some(Default::default()));
     inp.set_flags(db).to(Some(Default::default()));
     inp.set_cfg_set(db).to(Some(Default::default()));
+    inp.set_file_edits(db).to(Some(Default::default()));
+    inp.set_touched_files(db).to(Some(Default::default()));
+}
+
+/// Sets the source of the content of on-disk files and directories, instead of the OS filesystem.
+pub fn set_fs_backend(db: &mut dyn Database, backend: FsBackendRef) {
+    files_group_input(db).set_fs_backend(db).to(Some(backend));
 }
 
 pub fn set_crate_configs_input(
@@ -383,14 +519,34 @@ fn crate_configuration_input<'db>(
 }
 
 pub fn init_dev_corelib(db: &mut dyn salsa::Database, core_lib_dir: PathBuf) {
+    init_dev_corelib_from_root(db, DirectoryInput::Real(core_lib_dir));
+}
+
+/// Initializes the corelib from files in memory, rather than from a directory on disk - for tools
+/// bundling the corelib.
+/// The files are given by their `/` separated paths relative to the corelib source directory (e.g.
+/// `lib.cairo` or `starknet/info.cairo`) and their content.
+pub fn init_dev_corelib_from_memory(
+    db: &mut dyn salsa::Database,
+    files: impl IntoIterator<Item = (impl AsRef<str>, impl Into<Arc<str>>)>,
+) {
+    init_dev_corelib_from_root(db, DirectoryInput::from_virtual_files(files));
+}
+
+/// Initializes the corelib with the given root directory, either on disk or virtual.
+pub fn init_dev_corelib_from_root(db: &mut dyn salsa::Database, root: DirectoryInput) {
     let core = CrateLongId::core(db).intern(db);
     let root = CrateConfiguration {
-        root: Directory::Real(core_lib_dir),
+        root: root.into_directory(db),
         settings: CrateSettings {
             name: None,
             edition: Edition::V2025_12,
             version: Version::parse(CORELIB_VERSION).ok(),
             cfg_set: Default::default(),
+            expected_cfg_names: Default::default(),
+            lints: Default::default(),
+            no_early_unsafe_panic: false,
+            flags: Default::default(),
             dependencies: Default::default(),
             experimental_features: ExperimentalFeaturesConfig {
                 negative_impls: true,
@@ -398,6 +554,7 @@ pub fn init_dev_corelib(db: &mut dyn salsa::Database, core_lib_dir: PathBuf) {
                 coupons: true,
                 user_defined_inline_macros: true,
                 repr_ptrs: true,
+                fallback_impls: true,
             },
         },
         cache_file: None,
@@ -431,6 +588,35 @@ macro_rules! set_crate_config {
     };
 }
 
+/// Adds `cfg_set` to the `#[cfg(...)]` configuration of the crates named `crate_name`, and adds
+/// `expected_cfg_names` to the names they are expected to check.
+/// A crate without a configuration of its own gets a copy of the global one, extended with
+/// `cfg_set`.
+pub fn update_crate_cfg(
+    db: &mut dyn Database,
+    crate_name: &str,
+    cfg_set: &CfgSet,
+    expected_cfg_names: Option<&BTreeSet<String>>,
+) {
+    let db_ref: &dyn Database = db;
+    let global_cfg_set = cfg_set_helper(db_ref);
+    let mut crate_configs = files_group_input(db_ref).crate_configs(db_ref).clone().unwrap();
+    for (crate_input, config) in crate_configs.iter_mut() {
+        if !matches!(crate_input, CrateInput::Real { name, .. } if name == crate_name) {
+            continue;
+        }
+        let settings = &mut config.settings;
+        settings.cfg_set = Some(settings.cfg_set.as_ref().unwrap_or(global_cfg_set).union(cfg_set));
+        if let Some(expected_cfg_names) = expected_cfg_names {
+            settings
+                .expected_cfg_names
+                .get_or_insert_default()
+                .extend(expected_cfg_names.iter().cloned());
+        }
+    }
+    set_crate_configs_input(db, Some(crate_configs));
+}
+
 /// Updates file overrides input for standalone use.
 pub fn update_file_overrides_input_helper(
     db: &dyn Database,
@@ -446,16 +632,187 @@ pub fn update_file_overrides_input_helper(
     overrides
 }
 
+/// Returns the recorded file edits without the edits of `file`, or `None` if it has none.
+pub fn remove_file_edits_input_helper(
+    db: &dyn Database,
+    file: &FileInput,
+) -> Option<OrderedHashMap<FileInput, FileEdits>> {
+    let db_ref: &dyn Database = db;
+    let edits = files_group_input(db_ref).file_edits(db_ref).as_ref().unwrap();
+    if !edits.contains_key(file) {
+        return None;
+    }
+    let mut edits = edits.clone();
+    edits.swap_remove(file);
+    Some(edits)
+}
+
+/// Returns the touched files with `file` added, or `None` if it is already touched.
+pub fn touch_file_input_helper(
+    db: &dyn Database,
+    file: &FileInput,
+) -> Option<OrderedHashSet<FileInput>> {
+    let db_ref: &dyn Database = db;
+    let touched = files_group_input(db_ref).touched_files(db_ref).as_ref();
+    if touched.is_some_and(|touched| touched.contains(file)) {
+        return None;
+    }
+    let mut touched = touched.cloned().unwrap_or_default();
+    touched.insert(file.clone());
+    Some(touched)
+}
+
+/// Marks the file as touched, see [take_touched_files].
+#[macro_export]
+macro_rules! touch_file {
+    ($self:expr, $file:expr) => {
+        if let Some(touched) = $crate::db::touch_file_input_helper($self, $file) {
+            salsa::Setter::to(
+                $crate::db::files_group_input($self).set_touched_files($self),
+                Some(touched),
+            );
+        }
+    };
+}
+
+/// Returns the files overridden or edited through [override_file_content], [apply_file_edit] and
+/// [apply_file_edits] since the last call, and clears them.
+///
+/// Long-lived databases use it to track the files in use, see e.g. the eviction of stale inputs in
+/// `cairo-lang-compiler`. The touched files are otherwise only accumulated, one entry per file.
+pub fn take_touched_files(db: &mut dyn Database) -> Vec<FileInput> {
+    let input = files_group_input(db);
+    let touched = input.touched_files(db).clone().unwrap_or_default();
+    if touched.is_empty() {
+        return vec![];
+    }
+    input.set_touched_files(db).to(Some(Default::default()));
+    touched.into_iter().collect()
+}
+
 /// Overrides file content. None value removes the override.
 #[macro_export]
 macro_rules! override_file_content {
     ($self:expr, $file:expr, $content:expr) => {
         let file = $self.file_input($file).clone();
+        $crate::touch_file!($self, &file);
+        let edits = $crate::db::remove_file_edits_input_helper($self, &file);
         let overrides = $crate::db::update_file_overrides_input_helper($self, file, $content);
         salsa::Setter::to(
             $crate::db::files_group_input($self).set_file_overrides($self),
             Some(overrides),
         );
+        if let Some(edits) = edits {
+            salsa::Setter::to(
+                $crate::db::files_group_input($self).set_file_edits($self),
+                Some(edits),
+            );
+        }
+    };
+}
+
+/// The file overrides and the recorded file edits, to set as inputs after a file edit.
+pub type FileEditInputs =
+    (OrderedHashMap<FileInput, Arc<str>>, OrderedHashMap<FileInput, FileEdits>);
+
+/// Returns the file overrides and the recorded file edits, after applying `edit` to the content of
+/// `file`, or an error if the span of the edit is not valid in the content.
+pub fn apply_file_edit_input_helper(
+    db: &dyn Database,
+    file: FileId<'_>,
+    edit: &TextEdit,
+) -> Result<FileEditInputs, InvalidTextEdit> {
+    let mapping = TextEditMapping::new([edit.clone()]).expect("A single edit cannot overlap.");
+    apply_file_edits_input_helper(db, file, &mapping)
+}
+
+/// Returns the file overrides and the recorded file edits, after applying the edits of `mapping`
+/// to the content of `file`, or an error if the span of an edit is not valid in the content.
+pub fn apply_file_edits_input_helper(
+    db: &dyn Database,
+    file: FileId<'_>,
+    mapping: &TextEditMapping,
+) -> Result<FileEditInputs, InvalidTextEdit> {
+    let db_ref: &dyn Database = db;
+    let prev_content = file_content(db_ref, file).clone().expect("Edited file must have content.");
+    let content: Arc<str> = mapping.apply(&prev_content)?.into();
+    let file = db_ref.file_input(file).clone();
+    let mut edits = files_group_input(db_ref).file_edits(db_ref).clone().unwrap();
+    let new_edits = mapping.sequential_edits().cloned();
+    match edits.get_mut(&file) {
+        Some(file_edits)
+            if file_edits.edits.len() + mapping.edits().len() <= MAX_RECORDED_FILE_EDITS =>
+        {
+            file_edits.edits.extend(new_edits);
+        }
+        _ if mapping.edits().len() <= MAX_RECORDED_FILE_EDITS => {
+            // The content before the edits is the base, so the edits may still be used to process
+            // the file incrementally.
+            edits.insert(
+                file.clone(),
+                FileEdits { base_content: prev_content, edits: new_edits.collect() },
+            );
+        }
+        _ => {
+            edits.insert(file.clone(), FileEdits { base_content: content.clone(), edits: vec![] });
+        }
+    }
+    Ok((update_file_overrides_input_helper(db_ref, file, Some(content)), edits))
+}
+
+/// Applies an edit to the content of a file, overriding it. Unlike [override_file_content], the
+/// edit is recorded, so the file may be processed incrementally, e.g. only its edited parts are
+/// reparsed.
+///
+/// Evaluates to an [InvalidTextEdit] error, leaving the file unchanged, if the span of the edit is
+/// not valid in the content of the file.
+#[macro_export]
+macro_rules! apply_file_edit {
+    ($self:expr, $file:expr, $edit:expr) => {
+        match $crate::db::apply_file_edit_input_helper($self, $file, $edit) {
+            Ok((overrides, edits)) => {
+                let file = $self.file_input($file).clone();
+                salsa::Setter::to(
+                    $crate::db::files_group_input($self).set_file_overrides($self),
+                    Some(overrides),
+                );
+                salsa::Setter::to(
+                    $crate::db::files_group_input($self).set_file_edits($self),
+                    Some(edits),
+                );
+                $crate::touch_file!($self, &file);
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    };
+}
+
+/// Applies the edits of a [TextEditMapping] to the content of a file at once, overriding it. The
+/// edits are recorded as with [apply_file_edit], and the mapping translates the spans computed for
+/// the content before the edits to the content after them.
+///
+/// Evaluates to an [InvalidTextEdit] error, leaving the file unchanged, if the span of an edit is
+/// not valid in the content of the file.
+#[macro_export]
+macro_rules! apply_file_edits {
+    ($self:expr, $file:expr, $mapping:expr) => {
+        match $crate::db::apply_file_edits_input_helper($self, $file, $mapping) {
+            Ok((overrides, edits)) => {
+                let file = $self.file_input($file).clone();
+                salsa::Setter::to(
+                    $crate::db::files_group_input($self).set_file_overrides($self),
+                    Some(overrides),
+                );
+                salsa::Setter::to(
+                    $crate::db::files_group_input($self).set_file_edits($self),
+                    Some(edits),
+                );
+                $crate::touch_file!($self, &file);
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
     };
 }
 
@@ -501,6 +858,90 @@ fn crate_config<'db>(
     crate_config_helper(db, crt).as_ref()
 }
 
+#[salsa::tracked(returns(ref))]
+fn declared_crate_dependencies<'db>(
+    db: &'db dyn Database,
+    crate_id: CrateId<'db>,
+) -> Vec<CrateId<'db>> {
+    let Some(config) = db.crate_config(crate_id) else {
+        return vec![];
+    };
+    config
+        .settings
+        .dependencies
+        .iter()
+        .map(|(name, dep)| {
+            CrateLongId::Real {
+                name: SmolStrId::from(db, name.as_str()),
+                discriminator: dep.discriminator.clone(),
+            }
+            .intern(db)
+        })
+        .collect()
+}
+
+#[salsa::tracked(returns(ref))]
+fn crate_dependency_graph<'db>(db: &'db dyn Database) -> CrateGraph<'db> {
+    CrateGraph {
+        crates: db
+            .crates()
+            .iter()
+            .filter_map(|crate_id| {
+                let settings = db.crate_config(*crate_id)?.settings.clone();
+                let dependencies = db.declared_crate_dependencies(*crate_id).to_vec();
+                Some((*crate_id, CrateGraphNode { settings, dependencies }))
+            })
+            .collect(),
+    }
+}
+
+#[salsa::tracked(returns(ref))]
+fn crate_dependency_cycle<'db>(
+    db: &'db dyn Database,
+    crate_id: CrateId<'db>,
+) -> Option<Vec<CrateId<'db>>> {
+    // Breadth first search for the crate, from its dependencies, keeping the crate each crate was
+    // reached from.
+    let mut reached_from = OrderedHashMap::<CrateId<'db>, CrateId<'db>>::default();
+    let mut queue = std::collections::VecDeque::from([crate_id]);
+    while let Some(current) = queue.pop_front() {
+        for dep in db.declared_crate_dependencies(current) {
+            if reached_from.contains_key(dep) {
+                continue;
+            }
+            reached_from.insert(*dep, current);
+            if *dep == crate_id {
+                let mut cycle = vec![crate_id];
+                let mut current = current;
+                while current != crate_id {
+                    cycle.push(current);
+                    current = reached_from[&current];
+                }
+                cycle.push(crate_id);
+                cycle.reverse();
+                return Some(cycle);
+            }
+            queue.push_back(*dep);
+        }
+    }
+    None
+}
+
+#[salsa::tracked]
+fn file_fingerprint<'db>(db: &'db dyn Database, file_id: FileId<'db>) -> Option<u64> {
+    Some(xxhash_rust::xxh3::xxh3_64(db.file_content(file_id)?.as_bytes()))
+}
+
+#[salsa::tracked]
+fn crate_settings_fingerprint<'db>(db: &'db dyn Database, crate_id: CrateId<'db>) -> u64 {
+    let mut hasher = xxhash_rust::xxh3::Xxh3::default();
+    CORELIB_VERSION.hash(&mut hasher);
+    db.crate_config(crate_id).map(|config| &config.settings).hash(&mut hasher);
+    files_group_input(db).cfg_set(db).hash(&mut hasher);
+    db.compiler_flags().hash(&mut hasher);
+    hasher.finish()
+}
+
 #[salsa::tracked]
 fn priv_raw_file_content<'db>(db: &'db dyn Database, file: FileId<'db>) -> Option<SmolStrId<'db>> {
     match file.long(db) {
@@ -509,18 +950,39 @@ fn priv_raw_file_content<'db>(db: &'db dyn Database, file: FileId<'db>) -> Optio
             // will re-execute only this single query if the file content did not change.
             db.report_untracked_read();
 
-            match fs::read_to_string(path) {
-                Ok(content) => Some(SmolStrId::new(db, SmolStr::new(content))),
-                Err(_) => None,
-            }
+            db.fs_backend()
+                .read_to_string(path)
+                .map(|content| SmolStrId::new(db, SmolStr::new(content)))
         }
         FileLongId::Virtual(virt) => Some(virt.content),
         FileLongId::External(external_id) => Some(ext_as_virtual(db, *external_id).content),
     }
 }
 
+/// Implementation of [FilesGroup::directory_entries].
+fn directory_entries(db: &dyn Database, directory: &Directory<'_>) -> Option<Vec<String>> {
+    match directory {
+        Directory::Real(path) => {
+            // As with the content of on-disk files, the entries are not tracked by salsa.
+            db.report_untracked_read();
+            let entries = db.fs_backend().read_dir(path)?;
+            Some(
+                entries
+                    .iter()
+                    .filter_map(|entry| Some(entry.file_name()?.to_string_lossy().into_owned()))
+                    .collect(),
+            )
+        }
+        Directory::Virtual { files, dirs } => {
+            Some(files.keys().chain(dirs.keys()).sorted().cloned().collect())
+        }
+    }
+}
+
 /// Tracked function to return the content of a file as a string.
-#[salsa::tracked(returns(ref))]
+///
+/// The summaries are cheap to recompute, so only those of the recently used files are kept.
+#[salsa::tracked(returns(ref), lru = 128, heap_size = file_summary_heap_size)]
 fn file_summary_helper<'db>(db: &'db dyn Database, file: FileId<'db>) -> Option<FileSummary> {
     let content = db.file_content(file)?;
     let mut line_offsets = vec![TextOffset::START];
@@ -534,6 +996,11 @@ fn file_summary_helper<'db>(db: &'db dyn Database, file: FileId<'db>) -> Option<
     Some(FileSummary { line_offsets, last_offset: offset })
 }
 
+/// Returns the size of the heap allocations of a file summary, for the memory usage reports.
+fn file_summary_heap_size(summary: &Option<FileSummary>) -> usize {
+    summary.as_ref().map_or(0, |summary| summary.line_offsets.capacity() * size_of::<TextOffset>())
+}
+
 /// Query implementation of [FilesGroup::file_content].
 #[salsa::tracked(returns(ref))]
 fn file_content<'db>(db: &'db dyn Database, file_id: FileId<'db>) -> Option<Arc<str>> {
@@ -543,6 +1010,12 @@ fn file_content<'db>(db: &'db dyn Database, file_id: FileId<'db>) -> Option<Arc<
     })
 }
 
+#[salsa::tracked(returns(ref))]
+fn file_edits<'db>(db: &'db dyn Database, file_id: FileId<'db>) -> Option<FileEdits> {
+    let edits = files_group_input(db).file_edits(db).as_ref().expect("file_edits is not set");
+    edits.get(db.file_input(file_id)).cloned()
+}
+
 /// Returns a reference to the content of a file as a string.
 /// This is a wrapper around the tracked function `file_summary_helper` to return a
 /// reference to a type unsupported by salsa tracked functions.
@@ -553,7 +1026,13 @@ fn file_summary<'db>(db: &'db dyn Database, file: FileId<'db>) -> Option<&'db Fi
 /// Tracked function to return the blob's content.
 #[salsa::tracked(returns(ref))]
 fn blob_content_helper<'db>(db: &'db dyn Database, blob: BlobId<'db>) -> Option<Vec<u8>> {
-    blob.long(db).content()
+    match blob.long(db) {
+        BlobLongId::OnDisk(path) => {
+            db.report_untracked_read();
+            db.fs_backend().read(path)
+        }
+        BlobLongId::Virtual(content) => Some(content.clone()),
+    }
 }
 
 /// Wrapper around the tracked function `blob_content_helper` to return a
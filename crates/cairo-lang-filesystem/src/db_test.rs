@@ -3,11 +3,12 @@ use cairo_lang_utils::Intern;
 
 use super::FilesGroup;
 use crate::cfg::{Cfg, CfgSet};
-use crate::db::CrateConfiguration;
-use crate::flag::{Flag, FlagsGroup};
-use crate::ids::{CrateLongId, Directory, FlagId, FlagLongId, SmolStrId};
+use crate::db::{CrateConfiguration, DependencySettings, take_touched_files, update_crate_cfg};
+use crate::flag::{CompilerFlags, Flag, FlagsError, FlagsGroup};
+use crate::ids::{CrateLongId, Directory, DirectoryInput, SmolStrId};
+use crate::span::{InvalidTextEdit, TextEdit, TextEditMapping, TextOffset, TextSpan, TextWidth};
 use crate::test_utils::FilesDatabaseForTesting;
-use crate::{override_file_content, set_crate_config};
+use crate::{apply_file_edit, apply_file_edits, override_file_content, set_crate_config};
 
 #[test]
 fn test_filesystem() {
@@ -33,16 +34,231 @@ fn test_filesystem() {
     assert_eq!(db.file_content(file_id).unwrap(), "content\n");
 }
 
+#[test]
+fn test_file_edits() {
+    let mut db = FilesDatabaseForTesting::default();
+
+    let directory = Directory::Real("src".into());
+    let db_ref = &mut db;
+    let file_id = directory.file(db_ref, "lib.cairo");
+    override_file_content!(db_ref, file_id, Some("fn foo() {}\n".into()));
+    let file_id = directory.file(db_ref, "lib.cairo");
+    let edit =
+        TextEdit::new(TextSpan::new_with_width(TextOffset::START, TextWidth::from_str("fn")), "");
+    apply_file_edit!(db_ref, file_id, &edit).unwrap();
+
+    // The content of the file before the first edit is recorded as the base of the edits.
+    let file_id = directory.file(&db, "lib.cairo");
+    assert_eq!(db.file_content(file_id).unwrap(), " foo() {}\n");
+    let file_edits = db.file_edits(file_id).unwrap();
+    assert_eq!(&*file_edits.base_content, "fn foo() {}\n");
+    assert_eq!(file_edits.edits, vec![edit.clone()]);
+
+    let db_ref = &mut db;
+    let file_id = directory.file(db_ref, "lib.cairo");
+    let edit2 = TextEdit::new(TextSpan::cursor(TextOffset::START), "fn");
+    apply_file_edit!(db_ref, file_id, &edit2).unwrap();
+    let file_id = directory.file(&db, "lib.cairo");
+    assert_eq!(db.file_content(file_id).unwrap(), "fn foo() {}\n");
+    let file_edits = db.file_edits(file_id).unwrap();
+    assert_eq!(&*file_edits.base_content, "fn foo() {}\n");
+    assert_eq!(file_edits.edits, vec![edit, edit2]);
+
+    // An edit out of the content is rejected, and leaves the file unchanged.
+    let db_ref = &mut db;
+    let file_id = directory.file(db_ref, "lib.cairo");
+    let out_of_bounds = TextEdit::new(
+        TextSpan::cursor(TextOffset::START.add_width(TextWidth::new_for_testing(100))),
+        "x",
+    );
+    assert_eq!(apply_file_edit!(db_ref, file_id, &out_of_bounds), Err(InvalidTextEdit));
+    let file_id = directory.file(&db, "lib.cairo");
+    assert_eq!(db.file_content(file_id).unwrap(), "fn foo() {}\n");
+    assert_eq!(db.file_edits(file_id).unwrap().edits.len(), 2);
+
+    // Overriding the content discards the edits.
+    let db_ref = &mut db;
+    let file_id = directory.file(db_ref, "lib.cairo");
+    override_file_content!(db_ref, file_id, Some("fn bar() {}\n".into()));
+    let file_id = directory.file(&db, "lib.cairo");
+    assert!(db.file_edits(file_id).is_none());
+}
+
+#[test]
+fn test_apply_file_edits() {
+    let mut db = FilesDatabaseForTesting::default();
+
+    let directory = Directory::Real("src".into());
+    let db_ref = &mut db;
+    let file_id = directory.file(db_ref, "lib.cairo");
+    override_file_content!(db_ref, file_id, Some("fn foo() {}\n".into()));
+    let file_id = directory.file(db_ref, "lib.cairo");
+    apply_file_edit!(db_ref, file_id, &TextEdit::new(TextSpan::cursor(TextOffset::START), ""))
+        .unwrap();
+
+    let at = |start: u32, end: u32| {
+        TextSpan::new(
+            TextOffset::START.add_width(TextWidth::new_for_testing(start)),
+            TextOffset::START.add_width(TextWidth::new_for_testing(end)),
+        )
+    };
+    let mapping = TextEditMapping::new([
+        TextEdit::new(at(3, 6), "bar"),
+        TextEdit::new(at(11, 11), " x"),
+        TextEdit::new(at(0, 0), "pub "),
+    ])
+    .unwrap();
+    let db_ref = &mut db;
+    let file_id = directory.file(db_ref, "lib.cairo");
+    apply_file_edits!(db_ref, file_id, &mapping).unwrap();
+
+    let file_id = directory.file(&db, "lib.cairo");
+    assert_eq!(db.file_content(file_id).unwrap(), "pub fn bar() {} x\n");
+    // The recorded edits are applied sequentially to the base content.
+    let file_edits = db.file_edits(file_id).unwrap();
+    assert_eq!(file_edits.edits.len(), 4);
+    let replayed = file_edits
+        .edits
+        .iter()
+        .try_fold(file_edits.base_content.to_string(), |content, edit| edit.apply(&content));
+    assert_eq!(replayed.unwrap(), "pub fn bar() {} x\n");
+    // Spans in the old content map to the new content.
+    assert_eq!(mapping.map_span(at(7, 8)), Some(at(11, 12)));
+}
+
+#[test]
+fn test_touched_files() {
+    let mut db = FilesDatabaseForTesting::default();
+
+    let directory = Directory::Real("src".into());
+    let db_ref = &mut db;
+    let lib = directory.file(db_ref, "lib.cairo");
+    override_file_content!(db_ref, lib, Some("fn foo() {}\n".into()));
+    let other = directory.file(db_ref, "other.cairo");
+    override_file_content!(db_ref, other, Some("fn bar() {}\n".into()));
+    let lib = directory.file(db_ref, "lib.cairo");
+    apply_file_edit!(db_ref, lib, &TextEdit::new(TextSpan::cursor(TextOffset::START), "")).unwrap();
+    let lib_input = db_ref.file_input(directory.file(db_ref, "lib.cairo")).clone();
+    let other_input = db_ref.file_input(directory.file(db_ref, "other.cairo")).clone();
+    assert_eq!(take_touched_files(db_ref), vec![lib_input.clone(), other_input]);
+    assert_eq!(take_touched_files(db_ref), vec![]);
+
+    // An invalid edit leaves the file untouched.
+    let lib = directory.file(db_ref, "lib.cairo");
+    let out_of_bounds = TextEdit::new(
+        TextSpan::cursor(TextOffset::START.add_width(TextWidth::new_for_testing(100))),
+        "x",
+    );
+    assert_eq!(apply_file_edit!(db_ref, lib, &out_of_bounds), Err(InvalidTextEdit));
+    assert_eq!(take_touched_files(db_ref), vec![]);
+    let lib = directory.file(db_ref, "lib.cairo");
+    apply_file_edit!(db_ref, lib, &TextEdit::new(TextSpan::cursor(TextOffset::START), "")).unwrap();
+    assert_eq!(take_touched_files(db_ref), vec![lib_input]);
+}
+
+#[test]
+fn test_crate_dependency_graph() {
+    let mut db = FilesDatabaseForTesting::default();
+
+    // `a` depends on `b` and `c`, `b` on `c`, `c` on `a`, and `d` on the unconfigured `e`.
+    for (name, deps) in [("a", &["b", "c"][..]), ("b", &["c"]), ("c", &["a"]), ("d", &["e"])] {
+        let db_ref = &mut db;
+        let mut config = CrateConfiguration::default_for_root(Directory::Real(name.into()));
+        config.settings.dependencies = deps
+            .iter()
+            .map(|dep| (dep.to_string(), DependencySettings { discriminator: None }))
+            .collect();
+        let crt = CrateLongId::plain(SmolStrId::from(db_ref, name)).intern(db_ref);
+        set_crate_config!(db_ref, crt, Some(config));
+    }
+
+    let crt = |name: &str| CrateLongId::plain(SmolStrId::from(&db, name)).intern(&db);
+    let graph = db.crate_dependency_graph();
+    assert_eq!(
+        graph.crates.keys().copied().collect::<Vec<_>>(),
+        [crt("a"), crt("b"), crt("c"), crt("d")]
+    );
+    assert_eq!(graph.crates[&crt("a")].dependencies, [crt("b"), crt("c")]);
+    assert_eq!(graph.crates[&crt("d")].dependencies, [crt("e")]);
+    assert_eq!(graph.crates[&crt("d")].settings.dependencies.len(), 1);
+
+    assert_eq!(db.crate_dependency_cycle(crt("a")), Some(&[crt("a"), crt("c"), crt("a")][..]));
+    assert_eq!(
+        db.crate_dependency_cycle(crt("b")),
+        Some(&[crt("b"), crt("c"), crt("a"), crt("b")][..])
+    );
+    assert_eq!(db.crate_dependency_cycle(crt("d")), None);
+    assert_eq!(db.crate_dependency_cycle(crt("e")), None);
+}
+
 #[test]
 fn test_flags() {
     let mut db = FilesDatabaseForTesting::default();
+    assert!(db.flag_add_withdraw_gas());
 
-    let add_withdraw_gas_flag_id = FlagLongId(Flag::ADD_WITHDRAW_GAS.into());
-    db.set_flag(add_withdraw_gas_flag_id.clone(), Some(Flag::AddWithdrawGas(false)));
-    let id = add_withdraw_gas_flag_id.intern(&db);
+    db.set_flag(Flag::AddWithdrawGas(false));
+    db.set_flag(Flag::PanicBacktrace(true));
+    assert!(!db.flag_add_withdraw_gas());
+    assert_eq!(
+        db.compiler_flags().iter().collect::<Vec<_>>(),
+        [Flag::AddWithdrawGas(false), Flag::PanicBacktrace(true)]
+    );
+
+    // Crates override the flags that may differ between crates.
+    {
+        let db_ref = &mut db;
+        let mut config = CrateConfiguration::default_for_root(Directory::Real("a".into()));
+        config.settings.flags = CompilerFlags {
+            panic_backtrace: Some(false),
+            numeric_match_optimization_min_arms_threshold: Some(3),
+            ..Default::default()
+        };
+        let crt = CrateLongId::plain(SmolStrId::from(db_ref, "a")).intern(db_ref);
+        set_crate_config!(db_ref, crt, Some(config));
+    }
+    let crt = CrateLongId::plain(SmolStrId::from(&db, "a")).intern(&db);
+    let other_crt = CrateLongId::plain(SmolStrId::from(&db, "b")).intern(&db);
+    assert!(!db.flag_panic_backtrace(crt));
+    assert!(db.flag_panic_backtrace(other_crt));
+    assert_eq!(db.flag_numeric_match_optimization_min_arms_threshold(crt), Some(3));
+    assert_eq!(db.flag_numeric_match_optimization_min_arms_threshold(other_crt), None);
+}
+
+#[test]
+fn test_compiler_flags_config() {
+    let mut flags: CompilerFlags = toml::from_str("unsafe_panic = true").unwrap();
+    flags.set_by_name("numeric_match_optimization_min_arms_threshold", "4").unwrap();
+    assert_eq!(
+        flags,
+        CompilerFlags::from_iter([
+            Flag::UnsafePanic(true),
+            Flag::NumericMatchOptimizationMinArmsThreshold(4)
+        ])
+    );
+    assert_eq!(
+        flags.set_by_name("unsafe_panic", "yes"),
+        Err(FlagsError::InvalidValue { flag: Flag::UNSAFE_PANIC, value: "yes".into() })
+    );
+    assert_eq!(
+        flags.set_by_name("unsafe_panics", "true"),
+        Err(FlagsError::UnknownFlag("unsafe_panics".into()))
+    );
+    assert!(toml::from_str::<CompilerFlags>("unsafe_panics = true").is_err());
 
-    assert_eq!(db.get_flag(id), Some(Flag::AddWithdrawGas(false)));
-    assert!(db.get_flag(FlagId::new(&db, FlagLongId("non_existing_flag".into()))).is_none());
+    assert_eq!(
+        flags.validate_crate_overrides(),
+        Err(FlagsError::NotCrateOverridable(Flag::UNSAFE_PANIC))
+    );
+    let overrides = CompilerFlags { panic_backtrace: Some(true), ..Default::default() };
+    assert_eq!(overrides.validate_crate_overrides(), Ok(()));
+    assert_eq!(
+        flags.overridden_by(&overrides).iter().collect::<Vec<_>>(),
+        [
+            Flag::NumericMatchOptimizationMinArmsThreshold(4),
+            Flag::PanicBacktrace(true),
+            Flag::UnsafePanic(true)
+        ]
+    );
 }
 
 #[test]
@@ -58,3 +274,57 @@ fn test_cfgs() {
         &CfgSet::from_iter([Cfg::name("test"), Cfg::kv("k", "v1"), Cfg::kv("k", "v2")])
     )
 }
+
+#[test]
+fn test_crate_cfg() {
+    let mut db = FilesDatabaseForTesting::default();
+    db.use_cfg(&CfgSet::from_iter([Cfg::name("global")]));
+    let db_ref = &mut db;
+    for name in ["my_crate", "other_crate"] {
+        let crt = CrateLongId::plain(SmolStrId::from(db_ref, name)).intern(db_ref);
+        let config = CrateConfiguration::default_for_root(Directory::Real(name.into()));
+        set_crate_config!(db_ref, crt, Some(config));
+    }
+
+    let expected_cfg_names = ["feature".to_string()].into_iter().collect();
+    update_crate_cfg(
+        db_ref,
+        "my_crate",
+        &CfgSet::from_iter([Cfg::kv("feature", "a")]),
+        Some(&expected_cfg_names),
+    );
+    update_crate_cfg(db_ref, "my_crate", &CfgSet::from_iter([Cfg::kv("feature", "b")]), None);
+
+    let crt = CrateLongId::plain(SmolStrId::from(&db, "my_crate")).intern(&db);
+    let settings = &db.crate_config(crt).unwrap().settings;
+    assert_eq!(
+        settings.cfg_set,
+        Some(CfgSet::from_iter([
+            Cfg::name("global"),
+            Cfg::kv("feature", "a"),
+            Cfg::kv("feature", "b")
+        ]))
+    );
+    assert_eq!(settings.expected_cfg_names, Some(expected_cfg_names));
+
+    let other_crt = CrateLongId::plain(SmolStrId::from(&db, "other_crate")).intern(&db);
+    let other_settings = &db.crate_config(other_crt).unwrap().settings;
+    assert_eq!(other_settings.cfg_set, None);
+    assert_eq!(other_settings.expected_cfg_names, None);
+}
+
+#[test]
+fn test_virtual_files_directory() {
+    let db = FilesDatabaseForTesting::default();
+    let directory = DirectoryInput::from_virtual_files([
+        ("lib.cairo", "mod a;"),
+        ("a.cairo", "mod b;"),
+        ("a/b.cairo", "fn f() {}"),
+    ])
+    .into_directory(&db);
+
+    assert_eq!(db.file_content(directory.file(&db, "lib.cairo")).unwrap(), "mod a;");
+    assert_eq!(db.file_content(directory.file(&db, "a.cairo")).unwrap(), "mod b;");
+    assert_eq!(db.file_content(directory.subdir("a").file(&db, "b.cairo")).unwrap(), "fn f() {}");
+    assert!(db.file_content(directory.file(&db, "missing.cairo")).is_none());
+}
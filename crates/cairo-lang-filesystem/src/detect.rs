@@ -1,6 +1,18 @@
 use std::path::PathBuf;
 
+use crate::backend::{FsBackend, OsFsBackend};
+
+/// Detects the path of the corelib, by looking for a `corelib/src` directory next to the compiler.
+///
+/// Always returns `None` on `wasm32`, where the corelib must be provided as a virtual crate or
+/// through the file overrides.
 pub fn detect_corelib() -> Option<PathBuf> {
+    detect_corelib_in(&OsFsBackend)
+}
+
+/// Detects the path of the corelib like [detect_corelib], looking for the `corelib/src` directory
+/// in the given filesystem.
+pub fn detect_corelib_in(fs: &dyn FsBackend) -> Option<PathBuf> {
     for (base, up_options) in [
         // This is the directory of Cargo.toml of the current crate.
         // This is used for development of the compiler.
@@ -18,7 +30,7 @@ pub fn detect_corelib() -> Option<PathBuf> {
             }
             path.push("corelib");
             path.push("src");
-            if path.exists() {
+            if fs.exists(&path) {
                 return Some(path);
             }
         }
@@ -1,10 +1,8 @@
-use cairo_lang_utils::Intern;
-use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use salsa::{Database, Setter};
 use serde::{Deserialize, Serialize};
 
-use crate::db::files_group_input;
-use crate::ids::{FlagId, FlagLongId};
+use crate::db::{FilesGroup, files_group_input};
+use crate::ids::CrateId;
 
 /// A compilation flag.
 #[derive(PartialEq, Eq, Debug, Copy, Clone, Serialize, Deserialize, Hash, salsa::Update)]
@@ -27,6 +25,16 @@ pub enum Flag {
     ///
     /// Default is false.
     FutureSierra(bool),
+    /// Whether to compile functions with errors into stubs that panic, instead of failing the
+    /// compilation of their callers.
+    ///
+    /// Default is false - as the resulting program does not match the source.
+    ErrorTolerant(bool),
+    /// Whether to explain failed and ambiguous impl resolutions, by listing the candidate impls
+    /// considered and why each of them was rejected, as notes of the diagnostic.
+    ///
+    /// Default is false - as tracing the resolution requires re-solving the trait.
+    TraitSolverTrace(bool),
 }
 impl Flag {
     pub const ADD_WITHDRAW_GAS: &'static str = "add_withdraw_gas";
@@ -35,108 +43,293 @@ impl Flag {
     pub const PANIC_BACKTRACE: &'static str = "panic_backtrace";
     pub const UNSAFE_PANIC: &'static str = "unsafe_panic";
     pub const FUTURE_SIERRA: &'static str = "future_sierra";
+    pub const ERROR_TOLERANT: &'static str = "error_tolerant";
+    pub const TRAIT_SOLVER_TRACE: &'static str = "trait_solver_trace";
+
+    /// Returns the name of the flag.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Flag::AddWithdrawGas(_) => Flag::ADD_WITHDRAW_GAS,
+            Flag::NumericMatchOptimizationMinArmsThreshold(_) => {
+                Flag::NUMERIC_MATCH_OPTIMIZATION_MIN_ARMS_THRESHOLD
+            }
+            Flag::PanicBacktrace(_) => Flag::PANIC_BACKTRACE,
+            Flag::UnsafePanic(_) => Flag::UNSAFE_PANIC,
+            Flag::FutureSierra(_) => Flag::FUTURE_SIERRA,
+            Flag::ErrorTolerant(_) => Flag::ERROR_TOLERANT,
+            Flag::TraitSolverTrace(_) => Flag::TRAIT_SOLVER_TRACE,
+        }
+    }
+
+    /// Returns whether the flag may be overridden per crate.
+    ///
+    /// The other flags affect the program as a whole - e.g. `unsafe_panic` changes the signatures
+    /// of the functions, so all the crates must agree on them.
+    pub fn is_crate_overridable(&self) -> bool {
+        matches!(self, Flag::NumericMatchOptimizationMinArmsThreshold(_) | Flag::PanicBacktrace(_))
+    }
+}
+
+/// The compilation flags - each set to a value, or unset to use its default.
+///
+/// Loadable from manifests, e.g. as the `[flags]` section of a Cairo project file, where unknown
+/// flags are rejected.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, salsa::Update)]
+#[serde(deny_unknown_fields)]
+pub struct CompilerFlags {
+    /// See [Flag::AddWithdrawGas].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub add_withdraw_gas: Option<bool>,
+    /// See [Flag::NumericMatchOptimizationMinArmsThreshold].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numeric_match_optimization_min_arms_threshold: Option<usize>,
+    /// See [Flag::PanicBacktrace].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panic_backtrace: Option<bool>,
+    /// See [Flag::UnsafePanic].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unsafe_panic: Option<bool>,
+    /// See [Flag::FutureSierra].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub future_sierra: Option<bool>,
+    /// See [Flag::ErrorTolerant].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_tolerant: Option<bool>,
+    /// See [Flag::TraitSolverTrace].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trait_solver_trace: Option<bool>,
 }
+impl CompilerFlags {
+    /// Sets the value of a flag.
+    pub fn set(&mut self, flag: Flag) {
+        match flag {
+            Flag::AddWithdrawGas(value) => self.add_withdraw_gas = Some(value),
+            Flag::NumericMatchOptimizationMinArmsThreshold(value) => {
+                self.numeric_match_optimization_min_arms_threshold = Some(value)
+            }
+            Flag::PanicBacktrace(value) => self.panic_backtrace = Some(value),
+            Flag::UnsafePanic(value) => self.unsafe_panic = Some(value),
+            Flag::FutureSierra(value) => self.future_sierra = Some(value),
+            Flag::ErrorTolerant(value) => self.error_tolerant = Some(value),
+            Flag::TraitSolverTrace(value) => self.trait_solver_trace = Some(value),
+        }
+    }
+
+    /// Sets the value of a flag given by name, parsing the value - e.g. for flags given as command
+    /// line arguments.
+    pub fn set_by_name(&mut self, name: &str, value: &str) -> Result<(), FlagsError> {
+        fn parse<T: std::str::FromStr>(name: &'static str, value: &str) -> Result<T, FlagsError> {
+            value
+                .parse()
+                .map_err(|_| FlagsError::InvalidValue { flag: name, value: value.to_string() })
+        }
+        let flag = match name {
+            Flag::ADD_WITHDRAW_GAS => Flag::AddWithdrawGas(parse(Flag::ADD_WITHDRAW_GAS, value)?),
+            Flag::NUMERIC_MATCH_OPTIMIZATION_MIN_ARMS_THRESHOLD => {
+                Flag::NumericMatchOptimizationMinArmsThreshold(parse(
+                    Flag::NUMERIC_MATCH_OPTIMIZATION_MIN_ARMS_THRESHOLD,
+                    value,
+                )?)
+            }
+            Flag::PANIC_BACKTRACE => Flag::PanicBacktrace(parse(Flag::PANIC_BACKTRACE, value)?),
+            Flag::UNSAFE_PANIC => Flag::UnsafePanic(parse(Flag::UNSAFE_PANIC, value)?),
+            Flag::FUTURE_SIERRA => Flag::FutureSierra(parse(Flag::FUTURE_SIERRA, value)?),
+            Flag::ERROR_TOLERANT => Flag::ErrorTolerant(parse(Flag::ERROR_TOLERANT, value)?),
+            Flag::TRAIT_SOLVER_TRACE => {
+                Flag::TraitSolverTrace(parse(Flag::TRAIT_SOLVER_TRACE, value)?)
+            }
+            _ => return Err(FlagsError::UnknownFlag(name.to_string())),
+        };
+        self.set(flag);
+        Ok(())
+    }
+
+    /// Returns an iterator over the set flags.
+    pub fn iter(&self) -> impl Iterator<Item = Flag> {
+        [
+            self.add_withdraw_gas.map(Flag::AddWithdrawGas),
+            self.numeric_match_optimization_min_arms_threshold
+                .map(Flag::NumericMatchOptimizationMinArmsThreshold),
+            self.panic_backtrace.map(Flag::PanicBacktrace),
+            self.unsafe_panic.map(Flag::UnsafePanic),
+            self.future_sierra.map(Flag::FutureSierra),
+            self.error_tolerant.map(Flag::ErrorTolerant),
+            self.trait_solver_trace.map(Flag::TraitSolverTrace),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Returns true if no flag is set.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Returns these flags, with the flags set in `overrides` replacing their values.
+    pub fn overridden_by(&self, overrides: &CompilerFlags) -> CompilerFlags {
+        let mut flags = self.clone();
+        for flag in overrides.iter() {
+            flags.set(flag);
+        }
+        flags
+    }
 
-/// Extracts the value of a flag given the flag string and the expected variant.
-/// Returns `None` if the flag is missing.
-/// Panics if there is a variant mismatch.
-macro_rules! extract_flag_value {
-    ($db:ident, $flag:ident, $variant:ident) => {{
-        let flag = FlagId::new($db, FlagLongId(Flag::$flag.into()));
-        match $db.get_flag(flag) {
-            None => None,
-            Some(Flag::$variant(value)) => Some(value),
-            Some(other) => panic!("Unexpected flag variant for `{}`: {other:?}", Flag::$flag),
+    /// Validates that these flags may be used as the overrides of a crate - that is, only flags
+    /// that may differ between crates are set.
+    pub fn validate_crate_overrides(&self) -> Result<(), FlagsError> {
+        match self.iter().find(|flag| !flag.is_crate_overridable()) {
+            Some(flag) => Err(FlagsError::NotCrateOverridable(flag.name())),
+            None => Ok(()),
         }
-    }};
+    }
+}
+impl FromIterator<Flag> for CompilerFlags {
+    fn from_iter<T: IntoIterator<Item = Flag>>(iter: T) -> Self {
+        let mut flags = Self::default();
+        for flag in iter {
+            flags.set(flag);
+        }
+        flags
+    }
+}
+
+/// An error in the configuration of the compilation flags.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum FlagsError {
+    #[error("Unknown flag `{0}`.")]
+    UnknownFlag(String),
+    #[error("Invalid value `{value}` for flag `{flag}`.")]
+    InvalidValue { flag: &'static str, value: String },
+    #[error("Flag `{0}` affects the whole program, and cannot be overridden per crate.")]
+    NotCrateOverridable(&'static str),
+}
+
+/// Returns the compilation flags.
+fn compiler_flags(db: &dyn Database) -> &CompilerFlags {
+    files_group_input(db).flags(db).as_ref().expect("flags is not set")
+}
+
+/// Returns the compilation flags of the crate - the global flags, with the overrides of the crate
+/// applied.
+#[salsa::tracked(returns(ref))]
+fn crate_flags<'db>(db: &'db dyn Database, crate_id: CrateId<'db>) -> CompilerFlags {
+    let flags = compiler_flags(db);
+    match db.crate_config(crate_id) {
+        Some(config) => flags.overridden_by(&config.settings.flags),
+        None => flags.clone(),
+    }
 }
 
 /// Returns the value of the `add_withdraw_gas` flag, or `true` if the flag is not set.
 #[salsa::tracked]
 fn flag_add_withdraw_gas(db: &dyn Database) -> bool {
-    extract_flag_value!(db, ADD_WITHDRAW_GAS, AddWithdrawGas).unwrap_or(true)
+    compiler_flags(db).add_withdraw_gas.unwrap_or(true)
 }
 
-/// Returns the value of the `numeric_match_optimization_min_arms_threshold` flag, or `None` if the
-/// flag is not set.
+/// Returns the value of the `numeric_match_optimization_min_arms_threshold` flag for the crate, or
+/// `None` if the flag is not set.
 #[salsa::tracked]
-fn flag_numeric_match_optimization_min_arms_threshold(db: &dyn salsa::Database) -> Option<usize> {
-    extract_flag_value!(
-        db,
-        NUMERIC_MATCH_OPTIMIZATION_MIN_ARMS_THRESHOLD,
-        NumericMatchOptimizationMinArmsThreshold
-    )
+fn flag_numeric_match_optimization_min_arms_threshold<'db>(
+    db: &'db dyn Database,
+    crate_id: CrateId<'db>,
+) -> Option<usize> {
+    crate_flags(db, crate_id).numeric_match_optimization_min_arms_threshold
 }
 
-/// Returns the value of the `panic_backtrace` flag, or `false` if the flag is not set.
+/// Returns the value of the `panic_backtrace` flag for the crate, or `false` if the flag is not
+/// set.
 #[salsa::tracked]
-fn flag_panic_backtrace(db: &dyn salsa::Database) -> bool {
-    extract_flag_value!(db, PANIC_BACKTRACE, PanicBacktrace).unwrap_or_default()
+fn flag_panic_backtrace<'db>(db: &'db dyn Database, crate_id: CrateId<'db>) -> bool {
+    crate_flags(db, crate_id).panic_backtrace.unwrap_or_default()
 }
 
 /// Returns the value of the `unsafe_panic` flag, or `false` if the flag is not set.
 #[salsa::tracked]
-fn flag_unsafe_panic(db: &dyn salsa::Database) -> bool {
-    extract_flag_value!(db, UNSAFE_PANIC, UnsafePanic).unwrap_or_default()
+fn flag_unsafe_panic(db: &dyn Database) -> bool {
+    compiler_flags(db).unsafe_panic.unwrap_or_default()
+}
+
+/// Returns whether `unsafe_panic` calls should be inserted early in the functions of the crate -
+/// which is the case if the `unsafe_panic` flag is set, unless the crate opted out in its settings.
+#[salsa::tracked]
+fn flag_early_unsafe_panic<'db>(db: &'db dyn Database, crate_id: CrateId<'db>) -> bool {
+    flag_unsafe_panic(db)
+        && !db.crate_config(crate_id).is_some_and(|config| config.settings.no_early_unsafe_panic)
 }
 
 /// Returns the value of the `future_sierra` flag, or `false` if the flag is not set.
 #[salsa::tracked]
-fn flag_future_sierra(db: &dyn salsa::Database) -> bool {
-    extract_flag_value!(db, FUTURE_SIERRA, FutureSierra).unwrap_or_default()
+fn flag_future_sierra(db: &dyn Database) -> bool {
+    compiler_flags(db).future_sierra.unwrap_or_default()
 }
 
-#[salsa::tracked(returns(ref))]
-pub fn flags<'db>(db: &'db dyn Database) -> OrderedHashMap<FlagId<'db>, Flag> {
-    let inp = files_group_input(db).flags(db).as_ref().expect("flags is not set");
-    inp.iter().map(|(flag_id, flag)| (flag_id.clone().intern(db), *flag)).collect()
+/// Returns the value of the `error_tolerant` flag, or `false` if the flag is not set.
+#[salsa::tracked]
+fn flag_error_tolerant(db: &dyn Database) -> bool {
+    compiler_flags(db).error_tolerant.unwrap_or_default()
 }
 
-/// Returns a reference to the flag value.
+/// Returns the value of the `trait_solver_trace` flag, or `false` if the flag is not set.
 #[salsa::tracked]
-fn get_flag<'db>(db: &'db dyn Database, id: FlagId<'db>) -> Option<Flag> {
-    db.flags().get(&id).copied()
+fn flag_trait_solver_trace(db: &dyn Database) -> bool {
+    compiler_flags(db).trait_solver_trace.unwrap_or_default()
 }
 
 pub trait FlagsGroup: Database {
-    /// Interned version of `flags_input`.
-    fn flags<'db>(&'db self) -> &'db OrderedHashMap<FlagId<'db>, Flag> {
-        flags(self.as_dyn_database())
+    /// Returns the compilation flags.
+    fn compiler_flags(&self) -> &CompilerFlags {
+        compiler_flags(self.as_dyn_database())
     }
-    /// Query to get a compilation flag by its ID.
-    fn get_flag<'db>(&'db self, id: FlagId<'db>) -> Option<Flag> {
-        get_flag(self.as_dyn_database(), id)
+    /// Returns the compilation flags of the crate - the global flags, with the overrides of the
+    /// crate applied.
+    fn crate_flags<'db>(&'db self, crate_id: CrateId<'db>) -> &'db CompilerFlags {
+        crate_flags(self.as_dyn_database(), crate_id)
     }
-
-    /// Sets the given flag value. None value removes the flag.
-    fn set_flag(&mut self, flag: FlagLongId, value: Option<Flag>) {
+    /// Sets the compilation flags, replacing all the previously set flags.
+    fn set_compiler_flags(&mut self, flags: CompilerFlags) {
         let db_ref = self.as_dyn_database();
-        let mut flags = files_group_input(db_ref).flags(db_ref).clone().unwrap();
-        match value {
-            Some(value) => flags.insert(flag, value),
-            None => flags.swap_remove(&flag),
-        };
         files_group_input(db_ref).set_flags(self).to(Some(flags));
     }
+    /// Sets the value of a single flag.
+    fn set_flag(&mut self, flag: Flag) {
+        let mut flags = self.compiler_flags().clone();
+        flags.set(flag);
+        self.set_compiler_flags(flags);
+    }
     /// Returns the value of the `add_withdraw_gas` flag.
     fn flag_add_withdraw_gas(&self) -> bool {
         flag_add_withdraw_gas(self.as_dyn_database())
     }
-    /// Returns the value of the `numeric_match_optimization_min_arms_threshold` flag.
-    fn flag_numeric_match_optimization_min_arms_threshold(&self) -> Option<usize> {
-        flag_numeric_match_optimization_min_arms_threshold(self.as_dyn_database())
+    /// Returns the value of the `numeric_match_optimization_min_arms_threshold` flag for the crate.
+    fn flag_numeric_match_optimization_min_arms_threshold<'db>(
+        &'db self,
+        crate_id: CrateId<'db>,
+    ) -> Option<usize> {
+        flag_numeric_match_optimization_min_arms_threshold(self.as_dyn_database(), crate_id)
     }
-    /// Returns the value of the `panic_backtrace` flag.
-    fn flag_panic_backtrace(&self) -> bool {
-        flag_panic_backtrace(self.as_dyn_database())
+    /// Returns the value of the `panic_backtrace` flag for the crate.
+    fn flag_panic_backtrace<'db>(&'db self, crate_id: CrateId<'db>) -> bool {
+        flag_panic_backtrace(self.as_dyn_database(), crate_id)
     }
     /// Returns the value of the `unsafe_panic` flag.
     fn flag_unsafe_panic(&self) -> bool {
         flag_unsafe_panic(self.as_dyn_database())
     }
+    /// Returns whether `unsafe_panic` calls should be inserted early in the functions of the crate.
+    fn flag_early_unsafe_panic<'db>(&'db self, crate_id: CrateId<'db>) -> bool {
+        flag_early_unsafe_panic(self.as_dyn_database(), crate_id)
+    }
     /// Returns the value of the `future_sierra` flag.
     fn flag_future_sierra(&self) -> bool {
         flag_future_sierra(self.as_dyn_database())
     }
+    /// Returns the value of the `error_tolerant` flag.
+    fn flag_error_tolerant(&self) -> bool {
+        flag_error_tolerant(self.as_dyn_database())
+    }
+    /// Returns the value of the `trait_solver_trace` flag.
+    fn flag_trait_solver_trace(&self) -> bool {
+        flag_trait_solver_trace(self.as_dyn_database())
+    }
 }
 impl<T: Database + ?Sized> FlagsGroup for T {}
@@ -127,11 +127,6 @@ impl UnstableSalsaId for CrateId<'_> {
     }
 }
 
-/// The long ID for a compilation flag.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, HeapSize)]
-pub struct FlagLongId(pub String);
-define_short_id!(FlagId, FlagLongId);
-
 /// Same as `FileLongId`, but without the interning inside virtual files.
 /// This is used to avoid the need to intern the file id inside salsa database inputs.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -350,6 +345,43 @@ pub enum DirectoryInput {
 }
 
 impl DirectoryInput {
+    /// Creates a virtual directory from files in memory, given by their `/` separated paths
+    /// relative to the directory (e.g. `lib.cairo` or `starknet/info.cairo`) and their content.
+    /// The subdirectories are created as needed.
+    pub fn from_virtual_files(
+        files: impl IntoIterator<Item = (impl AsRef<str>, impl Into<Arc<str>>)>,
+    ) -> Self {
+        let empty = || DirectoryInput::Virtual { files: BTreeMap::new(), dirs: BTreeMap::new() };
+        let mut root = empty();
+        for (path, content) in files {
+            let path = path.as_ref();
+            let (dir_path, file_name) = match path.rsplit_once('/') {
+                Some((dir_path, file_name)) => (Some(dir_path), file_name),
+                None => (None, path),
+            };
+            let mut dir = &mut root;
+            for dir_name in dir_path.into_iter().flat_map(|dir_path| dir_path.split('/')) {
+                let DirectoryInput::Virtual { dirs, .. } = dir else {
+                    unreachable!("Subdirectories of a virtual directory are virtual.");
+                };
+                dir = dirs.entry(dir_name.to_string()).or_insert_with(|| Box::new(empty()));
+            }
+            let DirectoryInput::Virtual { files, .. } = dir else {
+                unreachable!("Subdirectories of a virtual directory are virtual.");
+            };
+            let file = VirtualFileInput {
+                parent: None,
+                name: path.to_string(),
+                content: content.into(),
+                code_mappings: [].into(),
+                kind: FileKind::Module,
+                original_item_removed: false,
+            };
+            files.insert(file_name.to_string(), FileInput::Virtual(file));
+        }
+        root
+    }
+
     /// Converts the input into a [`Directory`].
     pub fn into_directory(self, db: &dyn Database) -> Directory<'_> {
         match self {
@@ -502,15 +534,6 @@ pub enum BlobLongId {
     Virtual(Vec<u8>),
 }
 
-impl BlobLongId {
-    pub fn content(&self) -> Option<Vec<u8>> {
-        match self {
-            BlobLongId::OnDisk(path) => std::fs::read(path).ok(),
-            BlobLongId::Virtual(content) => Some(content.clone()),
-        }
-    }
-}
-
 define_short_id!(BlobId, BlobLongId);
 
 impl<'db> BlobId<'db> {
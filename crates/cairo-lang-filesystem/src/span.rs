@@ -1,9 +1,11 @@
 use std::iter::Sum;
 use std::ops::{Add, Range, Sub};
+use std::sync::Arc;
 
 use cairo_lang_proc_macros::HeapSize;
 use salsa::Database;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::db::FilesGroup;
 use crate::ids::FileId;
@@ -203,6 +205,143 @@ impl TextSpan {
     }
 }
 
+/// An error of applying a [TextEdit] whose span is not within the edited text, or does not start
+/// and end on character boundaries of it.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+#[error("The span of the edit is not a valid span of the edited text.")]
+pub struct InvalidTextEdit;
+
+/// An edit of a text, replacing the text in a span with a new text.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, salsa::Update)]
+pub struct TextEdit {
+    /// The span of the replaced text, in the text before the edit.
+    pub span: TextSpan,
+    /// The text replacing the span.
+    pub new_text: Arc<str>,
+}
+impl TextEdit {
+    pub fn new(span: TextSpan, new_text: impl Into<Arc<str>>) -> Self {
+        Self { span, new_text: new_text.into() }
+    }
+    /// Returns the span of the new text, in the text after the edit.
+    pub fn new_span(&self) -> TextSpan {
+        TextSpan::new_with_width(self.span.start, TextWidth::from_str(&self.new_text))
+    }
+    /// Applies the edit to the given text.
+    pub fn apply(&self, content: &str) -> Result<String, InvalidTextEdit> {
+        let range = self.span.to_str_range();
+        let replaced = content.get(range.clone()).ok_or(InvalidTextEdit)?;
+        let mut result =
+            String::with_capacity(content.len() - replaced.len() + self.new_text.len());
+        result.push_str(&content[..range.start]);
+        result.push_str(&self.new_text);
+        result.push_str(&content[range.end..]);
+        Ok(result)
+    }
+}
+
+/// A set of non-overlapping edits of a text, applied together, with the mapping between the offsets
+/// of the text before the edits and after them.
+///
+/// Allows translating spans computed for an old version of a text (e.g. of diagnostics) to the new
+/// version, and back, without recomputing them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextEditMapping {
+    /// The edits, sorted by their spans, which are in the text before the edits.
+    edits: Vec<TextEdit>,
+    /// The spans of the new texts of the edits, in the text after the edits.
+    new_spans: Vec<TextSpan>,
+}
+impl TextEditMapping {
+    /// Creates the mapping of the given edits, whose spans are all in the text before the edits.
+    /// Insertions at the same offset are applied in the given order.
+    ///
+    /// Returns `None` if the spans of the edits overlap.
+    pub fn new(edits: impl IntoIterator<Item = TextEdit>) -> Option<Self> {
+        let mut edits: Vec<TextEdit> = edits.into_iter().collect();
+        edits.sort_by_key(|edit| edit.span.start);
+        let mut new_spans = Vec::with_capacity(edits.len());
+        let (mut removed, mut added) = (TextWidth::default(), TextWidth::default());
+        let mut prev_end = TextOffset::START;
+        for edit in &edits {
+            if edit.span.start < prev_end {
+                return None;
+            }
+            prev_end = edit.span.end;
+            let new_start = edit.span.start.add_width(added).sub_width(removed);
+            let new_span = TextSpan::new_with_width(new_start, TextWidth::from_str(&edit.new_text));
+            removed = removed + edit.span.width();
+            added = added + new_span.width();
+            new_spans.push(new_span);
+        }
+        Some(Self { edits, new_spans })
+    }
+
+    /// The edits, sorted by their spans.
+    pub fn edits(&self) -> &[TextEdit] {
+        &self.edits
+    }
+
+    /// Applies the edits to the given text.
+    pub fn apply(&self, content: &str) -> Result<String, InvalidTextEdit> {
+        self.sequential_edits().try_fold(content.to_string(), |content, edit| edit.apply(&content))
+    }
+
+    /// Returns the edits as a sequence of edits, each applying to the text resulting from the
+    /// previous ones. Equivalent to applying all the edits together.
+    pub fn sequential_edits(&self) -> impl Iterator<Item = &TextEdit> {
+        // Applying the edits from the last, each edit does not move the spans of the edits before.
+        self.edits.iter().rev()
+    }
+
+    /// Maps a span in the text before the edits to the text after them.
+    ///
+    /// Returns `None` if the edits changed the text of the span, or inserted text inside it. A span
+    /// starting at an insertion is moved after the inserted text.
+    pub fn map_span(&self, span: TextSpan) -> Option<TextSpan> {
+        map_span(span, self.edits.iter().map(|edit| edit.span).zip(self.new_spans.iter().copied()))
+    }
+
+    /// Maps a span in the text after the edits to the text before them.
+    /// See [Self::map_span].
+    pub fn unmap_span(&self, span: TextSpan) -> Option<TextSpan> {
+        map_span(span, self.new_spans.iter().copied().zip(self.edits.iter().map(|edit| edit.span)))
+    }
+
+    /// Maps an offset in the text before the edits to the text after them.
+    /// Returns `None` if the offset is inside a replaced span.
+    pub fn map_offset(&self, offset: TextOffset) -> Option<TextOffset> {
+        self.map_span(TextSpan::cursor(offset)).map(|span| span.start)
+    }
+
+    /// Maps an offset in the text after the edits to the text before them.
+    /// Returns `None` if the offset is inside an inserted text.
+    pub fn unmap_offset(&self, offset: TextOffset) -> Option<TextOffset> {
+        self.unmap_span(TextSpan::cursor(offset)).map(|span| span.start)
+    }
+}
+
+/// Maps a span by the given replacements of spans, sorted by their replaced spans.
+fn map_span(
+    span: TextSpan,
+    replacements: impl Iterator<Item = (TextSpan, TextSpan)>,
+) -> Option<TextSpan> {
+    let mut mapped = span;
+    for (from, to) in replacements {
+        if from.end <= span.start {
+            // Before the span - moves the span.
+            mapped.start = mapped.start.add_width(to.width()).sub_width(from.width());
+            mapped.end = mapped.end.add_width(to.width()).sub_width(from.width());
+        } else if from.start >= span.end {
+            // After the span, as are the rest of the replacements.
+            break;
+        } else {
+            return None;
+        }
+    }
+    Some(mapped)
+}
+
 /// Human-readable position inside a file, in lines and characters.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct TextPosition {
@@ -2,7 +2,7 @@ use cairo_lang_utils::Intern;
 
 use super::TextOffset;
 use crate::ids::{FileId, FileKind, FileLongId, SmolStrId, VirtualFile};
-use crate::span::{TextPosition, TextWidth};
+use crate::span::{InvalidTextEdit, TextEdit, TextEditMapping, TextPosition, TextSpan, TextWidth};
 use crate::test_utils::FilesDatabaseForTesting;
 
 const TEST_STRING: &str = "01\n23\u{1230}\r\n456\n\n\r\n789";
@@ -130,3 +130,78 @@ fn test_position_offset_in_file() {
         Some(TextOffset(TextWidth(TEST_STRING.len() as u32)))
     );
 }
+
+fn span(start: u32, end: u32) -> TextSpan {
+    TextSpan::new(TextOffset(TextWidth(start)), TextOffset(TextWidth(end)))
+}
+
+#[test]
+fn test_text_edit_mapping() {
+    let content = "fn foo(a: u32) -> u32 { a }";
+    // Given out of order, in the coordinates of the content before the edits.
+    let mapping = TextEditMapping::new([
+        TextEdit::new(span(24, 25), "a + 1"),
+        TextEdit::new(span(3, 6), "f"),
+        TextEdit::new(span(7, 7), "mut "),
+    ])
+    .unwrap();
+    assert_eq!(mapping.apply(content).unwrap(), "fn f(mut a: u32) -> u32 { a + 1 }");
+
+    // Spans before, between and after the edits are moved.
+    assert_eq!(mapping.map_span(span(0, 2)), Some(span(0, 2)));
+    assert_eq!(mapping.map_span(span(10, 13)), Some(span(12, 15)));
+    assert_eq!(mapping.map_span(span(26, 27)), Some(span(32, 33)));
+    // A span starting at an insertion is moved after it, and one ending at it is kept.
+    assert_eq!(mapping.map_span(span(7, 8)), Some(span(9, 10)));
+    assert_eq!(mapping.map_span(span(6, 7)), Some(span(4, 5)));
+    // Spans changed by the edits are not mapped.
+    assert_eq!(mapping.map_span(span(3, 6)), None);
+    assert_eq!(mapping.map_span(span(5, 10)), None);
+    assert_eq!(mapping.map_span(span(6, 14)), None);
+    assert_eq!(mapping.map_offset(TextOffset(TextWidth(4))), None);
+
+    // Unmapping is the inverse of mapping.
+    for (old, new) in
+        [(span(0, 2), span(0, 2)), (span(10, 13), span(12, 15)), (span(26, 27), span(32, 33))]
+    {
+        assert_eq!(mapping.unmap_span(new), Some(old));
+    }
+    assert_eq!(mapping.unmap_span(span(25, 30)), None);
+    assert_eq!(mapping.unmap_offset(TextOffset(TextWidth(6))), None);
+    assert_eq!(mapping.unmap_offset(TextOffset(TextWidth(9))), Some(TextOffset(TextWidth(7))));
+}
+
+#[test]
+fn test_text_edit_mapping_overlap() {
+    assert!(
+        TextEditMapping::new([TextEdit::new(span(2, 5), ""), TextEdit::new(span(4, 6), "")])
+            .is_none()
+    );
+    assert!(
+        TextEditMapping::new([TextEdit::new(span(2, 5), ""), TextEdit::new(span(2, 2), "x")])
+            .is_none()
+    );
+    // Adjacent edits and insertions at the same offset do not overlap, and insertions are applied
+    // in the given order.
+    let mapping = TextEditMapping::new([
+        TextEdit::new(span(1, 1), "a"),
+        TextEdit::new(span(1, 1), "b"),
+        TextEdit::new(span(1, 2), "c"),
+    ])
+    .unwrap();
+    assert_eq!(mapping.apply("xyz").unwrap(), "xabcz");
+}
+
+#[test]
+fn test_invalid_text_edit() {
+    let content = "fn ü() {}";
+    assert_eq!(TextEdit::new(span(3, 5), "u").apply(content), Ok("fn u() {}".to_string()));
+    // Spans out of the text, or not on character boundaries, are rejected.
+    assert_eq!(TextEdit::new(span(8, 11), "").apply(content), Err(InvalidTextEdit));
+    assert_eq!(TextEdit::new(span(4, 5), "").apply(content), Err(InvalidTextEdit));
+    assert_eq!(TextEdit::new(span(3, 4), "").apply(content), Err(InvalidTextEdit));
+    let mapping =
+        TextEditMapping::new([TextEdit::new(span(0, 0), "x"), TextEdit::new(span(4, 4), "")])
+            .unwrap();
+    assert_eq!(mapping.apply(content), Err(InvalidTextEdit));
+}
@@ -18,7 +18,7 @@ use itertools::{Itertools, chain};
 use salsa::Database;
 use syntax::node::kind::SyntaxKind;
 
-use crate::FormatterConfig;
+use crate::{FormatterConfig, TrailingCommaBehavior};
 
 /// Represents a tree structure for organizing and merging `use` statements.
 #[derive(Default, Debug)]
@@ -1264,7 +1264,10 @@ impl<'a> FormatterImpl<'a> {
         self.is_last_element_comment = false;
     }
     fn append_break_line_point(&mut self, properties: Option<BreakLinePointProperties>) {
-        if let Some(properties) = properties {
+        if let Some(mut properties) = properties {
+            if self.config.trailing_comma == TrailingCommaBehavior::Never {
+                properties.unset_comma_if_broken();
+            }
             self.line_state.line_buffer.push_break_line_point(properties);
             self.line_state.prevent_next_space = true;
         }
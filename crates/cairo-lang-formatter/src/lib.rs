@@ -7,7 +7,9 @@ pub mod node_properties;
 
 use cairo_lang_diagnostics::DiagnosticsBuilder;
 use cairo_lang_filesystem::ids::{FileKind, FileLongId, SmolStrId, VirtualFile};
+use cairo_lang_filesystem::span::{TextEdit, TextOffset, TextSpan, TextWidth};
 use cairo_lang_parser::parser::Parser;
+use cairo_lang_syntax::node::kind::SyntaxKind;
 use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode};
 use cairo_lang_utils::Intern;
 use salsa::Database;
@@ -44,6 +46,21 @@ pub fn get_formatted_file(
 /// # Returns
 /// * `String` - The formatted code.
 pub fn format_string(db: &dyn Database, content: String) -> String {
+    format_string_with_config(db, content, FormatterConfig::default())
+}
+
+/// Formats Cairo code given as a string, according to the given configuration.
+/// # Arguments
+/// * `db` - The syntax group.
+/// * `content` - The code to format.
+/// * `config` - The formatter configuration.
+/// # Returns
+/// * `String` - The formatted code.
+pub fn format_string_with_config(
+    db: &dyn Database,
+    content: String,
+    config: FormatterConfig,
+) -> String {
     let virtual_file = FileLongId::Virtual(VirtualFile {
         parent: None,
         name: SmolStrId::from(db, "string_to_format"),
@@ -56,7 +73,106 @@ pub fn format_string(db: &dyn Database, content: String) -> String {
     let mut diagnostics = DiagnosticsBuilder::default();
     let syntax_root =
         Parser::parse_file(db, &mut diagnostics, virtual_file, content.as_str()).as_syntax_node();
-    get_formatted_file(db, &syntax_root, FormatterConfig::default())
+    get_formatted_file(db, &syntax_root, config)
+}
+
+/// Formats only the items of a file which intersect the given range, leaving the rest of the file
+/// untouched.
+/// # Arguments
+/// * `db` - The syntax group.
+/// * `syntax_root` - The syntax root of the file.
+/// * `range` - The range to format.
+/// * `config` - The formatter configuration.
+/// # Returns
+/// * `Vec<TextEdit>` - The edits to apply to the file, ordered by their position.
+///
+/// The formatted items are the elements of the innermost item or statement list containing the
+/// range, which intersect it. Each of them must start on a line of its own, otherwise the next
+/// enclosing list is tried. If there is no such list, the whole file is formatted.
+pub fn format_range(
+    db: &dyn Database,
+    syntax_root: &SyntaxNode<'_>,
+    range: TextSpan,
+    config: FormatterConfig,
+) -> Vec<TextEdit> {
+    let content = syntax_root.get_text(db);
+    let token = syntax_root.token_at_offset(db, range.start);
+    let lists = token.iter().flat_map(|token| {
+        token.ancestors(db).filter(|node| {
+            matches!(
+                node.kind(db),
+                SyntaxKind::ModuleItemList
+                    | SyntaxKind::ImplItemList
+                    | SyntaxKind::TraitItemList
+                    | SyntaxKind::StatementList
+            )
+        })
+    });
+    for list in lists {
+        if !list.span(db).contains(range) {
+            continue;
+        }
+        let edits: Option<Vec<_>> = list
+            .get_children(db)
+            .iter()
+            .filter(|element| intersects(element.span_without_trivia(db), range))
+            .map(|element| format_list_element(db, content, element, &config))
+            .collect();
+        if let Some(edits) = edits {
+            return edits;
+        }
+    }
+    vec![TextEdit::new(TextSpan::from_str(content), get_formatted_file(db, syntax_root, config))]
+}
+
+/// Returns whether a span of an element intersects the range to format. An empty range intersects
+/// the spans containing it.
+fn intersects(span: TextSpan, range: TextSpan) -> bool {
+    if range.width() == TextWidth::ZERO {
+        span.contains(range)
+    } else {
+        span.start < range.end && range.start < span.end
+    }
+}
+
+/// Formats a single element of a list, indented as it is in the file. Returns `None` if the element
+/// does not start on a line of its own, or does not end with a newline.
+fn format_list_element(
+    db: &dyn Database,
+    content: &str,
+    element: &SyntaxNode<'_>,
+    config: &FormatterConfig,
+) -> Option<TextEdit> {
+    let span = element.span(db);
+    let text = span.take(content);
+    if !text.ends_with('\n') {
+        return None;
+    }
+    // Empty lines before the element are kept as is, as the formatter drops them.
+    let element_start = span.start.as_u32() as usize + text.find(|c: char| !c.is_whitespace())?;
+    let line_start = content[..element_start].rfind('\n').map_or(0, |index| index + 1);
+    if line_start < span.start.as_u32() as usize {
+        return None;
+    }
+    let indent = content[line_start..element_start].chars().count();
+    let config = FormatterConfig {
+        max_line_length: config.max_line_length.saturating_sub(indent),
+        ..config.clone()
+    };
+    let formatted = FormatterImpl::new(db, config).get_formatted_string(element);
+    let new_text: String =
+        formatted
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    "\n".into()
+                } else {
+                    format!("{}{line}\n", " ".repeat(indent))
+                }
+            })
+            .collect();
+    let start = TextOffset::START.add_width(TextWidth::at(content, line_start));
+    Some(TextEdit::new(TextSpan::new(start, span.end), new_text))
 }
 
 /// This enum is used to control how multi-element collections (i.e. arrays, tuples)
@@ -82,6 +198,23 @@ impl From<bool> for CollectionsBreakingBehavior {
     }
 }
 
+/// This enum is used to control whether a trailing comma is added to a list broken into lines.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrailingCommaBehavior {
+    /// Adds a trailing comma after the last element of a list broken into lines.
+    #[default]
+    Vertical,
+    /// Never adds a trailing comma, and removes the existing ones.
+    Never,
+}
+
+/// Impl TrailingCommaBehavior from bool, where true is `Vertical` and false is `Never`.
+impl From<bool> for TrailingCommaBehavior {
+    fn from(b: bool) -> Self {
+        if b { TrailingCommaBehavior::Vertical } else { TrailingCommaBehavior::Never }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BreakingBehaviorConfig {
     pub tuple: CollectionsBreakingBehavior,
@@ -97,6 +230,8 @@ pub struct FormatterConfig {
     pub breaking_behavior: BreakingBehaviorConfig,
     pub merge_use_items: bool,
     pub allow_duplicate_uses: bool,
+    #[serde(default)]
+    pub trailing_comma: TrailingCommaBehavior,
 }
 
 // Config params
@@ -112,6 +247,7 @@ impl FormatterConfig {
         breaking_behavior: BreakingBehaviorConfig,
         merge_use_items: bool,
         allow_duplicate_uses: bool,
+        trailing_comma: TrailingCommaBehavior,
     ) -> Self {
         Self {
             tab_size,
@@ -120,9 +256,24 @@ impl FormatterConfig {
             breaking_behavior,
             merge_use_items,
             allow_duplicate_uses,
+            trailing_comma,
         }
     }
 
+    pub fn tab_size(mut self, tab_size: Option<usize>) -> Self {
+        if let Some(tab_size) = tab_size {
+            self.tab_size = tab_size;
+        }
+        self
+    }
+
+    pub fn max_line_length(mut self, max_line_length: Option<usize>) -> Self {
+        if let Some(max_line_length) = max_line_length {
+            self.max_line_length = max_line_length;
+        }
+        self
+    }
+
     pub fn sort_module_level_items(mut self, sort_module_level_items: Option<bool>) -> Self {
         if let Some(sort) = sort_module_level_items {
             self.sort_module_level_items = sort;
@@ -171,6 +322,12 @@ impl FormatterConfig {
         }
         self
     }
+    pub fn trailing_comma(mut self, behavior: Option<TrailingCommaBehavior>) -> Self {
+        if let Some(behavior) = behavior {
+            self.trailing_comma = behavior;
+        }
+        self
+    }
 }
 impl Default for FormatterConfig {
     fn default() -> Self {
@@ -185,6 +342,7 @@ impl Default for FormatterConfig {
             },
             merge_use_items: true,
             allow_duplicate_uses: false,
+            trailing_comma: TrailingCommaBehavior::Vertical,
         }
     }
 }
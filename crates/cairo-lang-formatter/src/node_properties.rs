@@ -31,6 +31,11 @@ impl<'a> SyntaxNodeFormat for SyntaxNode<'a> {
             {
                 true
             }
+            SyntaxKind::TokenDotDot | SyntaxKind::TokenDotDotEq
+                if self.grandparent_kind(db) == Some(SyntaxKind::PatternRange) =>
+            {
+                true
+            }
             SyntaxKind::TokenLParen
                 if matches!(self.grandparent_kind(db), Some(SyntaxKind::FunctionSignature))
                     | matches!(
@@ -111,7 +116,10 @@ impl<'a> SyntaxNodeFormat for SyntaxNode<'a> {
             | SyntaxKind::TokenImplicits
             | SyntaxKind::TokenDollar => true,
             SyntaxKind::TerminalDotDot | SyntaxKind::TerminalDotDotEq
-                if matches!(self.parent_kind(db), Some(SyntaxKind::ExprBinary)) =>
+                if matches!(
+                    self.parent_kind(db),
+                    Some(SyntaxKind::ExprBinary | SyntaxKind::PatternRange)
+                ) =>
             {
                 true
             }
@@ -1,11 +1,18 @@
 use std::fs;
 use std::path::PathBuf;
 
+use cairo_lang_filesystem::span::{TextOffset, TextSpan, TextWidth};
 use cairo_lang_parser::utils::{SimpleParserDatabase, get_syntax_root_and_diagnostics_from_file};
+use cairo_lang_syntax::node::SyntaxNode;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use indoc::indoc;
 use pretty_assertions::assert_eq;
 use test_case::test_case;
 
-use crate::{FormatterConfig, get_formatted_file};
+use crate::{
+    FormatterConfig, TrailingCommaBehavior, format_range, format_string, format_string_with_config,
+    get_formatted_file,
+};
 
 #[test_case(
     "test_data/cairo_files/test1.cairo",
@@ -14,7 +21,8 @@ use crate::{FormatterConfig, get_formatted_file};
     false,
     false,
     false,
-    false
+    false,
+    true
 )]
 #[test_case(
     "test_data/cairo_files/linebreaking.cairo",
@@ -23,7 +31,8 @@ use crate::{FormatterConfig, get_formatted_file};
     false,
     false,
     false,
-    false
+    false,
+    true
 )]
 #[test_case(
     "test_data/cairo_files/attrs.cairo",
@@ -32,7 +41,8 @@ use crate::{FormatterConfig, get_formatted_file};
     false,
     false,
     false,
-    false
+    false,
+    true
 )]
 #[test_case(
     "test_data/cairo_files/use_sorting.cairo",
@@ -41,7 +51,8 @@ use crate::{FormatterConfig, get_formatted_file};
     false,
     false,
     false,
-    false
+    false,
+    true
 )]
 #[test_case(
     "test_data/cairo_files/fmt_skip.cairo",
@@ -50,7 +61,8 @@ use crate::{FormatterConfig, get_formatted_file};
     false,
     false,
     false,
-    false
+    false,
+    true
 )]
 #[test_case(
     "test_data/cairo_files/sorted_mod_use.cairo",
@@ -59,7 +71,8 @@ use crate::{FormatterConfig, get_formatted_file};
     false,
     false,
     false,
-    false
+    false,
+    true
 )]
 #[test_case(
     "test_data/cairo_files/sort_inner_use.cairo",
@@ -68,7 +81,8 @@ use crate::{FormatterConfig, get_formatted_file};
     false,
     false,
     false,
-    false
+    false,
+    true
 )]
 // TODO (Dean): Move these tests to our main test infrastructure.
 #[test_case(
@@ -78,7 +92,8 @@ use crate::{FormatterConfig, get_formatted_file};
     false,
     false,
     false,
-    false
+    false,
+    true
 )]
 #[test_case(
     "test_data/cairo_files/sort_line_by_line.cairo",
@@ -87,7 +102,8 @@ use crate::{FormatterConfig, get_formatted_file};
     true,
     true,
     false,
-    false
+    false,
+    true
 )]
 #[test_case(
     "test_data/cairo_files/use_merge.cairo",
@@ -96,7 +112,8 @@ use crate::{FormatterConfig, get_formatted_file};
     false,
     false,
     true,
-    false
+    false,
+    true
 )]
 #[test_case(
     "test_data/cairo_files/use_merge_with_dup.cairo",
@@ -105,8 +122,20 @@ use crate::{FormatterConfig, get_formatted_file};
     false,
     false,
     true,
+    true,
     true
 )]
+#[test_case(
+    "test_data/cairo_files/trailing_comma.cairo",
+    "test_data/expected_results/trailing_comma.cairo",
+    false,
+    false,
+    false,
+    false,
+    false,
+    false
+)]
+#[allow(clippy::too_many_arguments)]
 fn format_and_compare_file(
     unformatted_filename: &str,
     expected_filename: &str,
@@ -115,6 +144,7 @@ fn format_and_compare_file(
     fixed_array_line_breaking: bool,
     merge_use_statements: bool,
     allow_duplicate_uses: bool,
+    trailing_comma: bool,
 ) {
     let db_val = SimpleParserDatabase::default();
     let db = &db_val;
@@ -133,10 +163,169 @@ fn format_and_compare_file(
         .tuple_breaking_behavior(Some(tuple_line_breaking.into()))
         .fixed_array_breaking_behavior(Some(fixed_array_line_breaking.into()))
         .merge_use_items(Some(merge_use_statements))
-        .allow_duplicate_uses(Some(allow_duplicate_uses));
+        .allow_duplicate_uses(Some(allow_duplicate_uses))
+        .trailing_comma(Some(trailing_comma.into()));
 
     let formatted_file = get_formatted_file(db, &syntax_root, config);
     let expected_file =
         fs::read_to_string(expected_filename).expect("Expected file does not exist.");
     assert_eq!(formatted_file, expected_file);
+
+    let formatted_root = db.parse_virtual(&formatted_file).unwrap();
+    assert_eq!(attached_comments(db, &formatted_root), attached_comments(db, &syntax_root));
+}
+
+#[test]
+fn format_string_with_max_line_length() {
+    let db = SimpleParserDatabase::default();
+    let content = "fn foo(a: felt252, b: felt252) {}";
+    assert_eq!(format_string(&db, content.to_string()), "fn foo(a: felt252, b: felt252) {}\n");
+    let config = FormatterConfig::default().max_line_length(Some(20)).tab_size(Some(2));
+    assert_eq!(
+        format_string_with_config(&db, content.to_string(), config.clone()),
+        "fn foo(\n  a: felt252,\n  b: felt252,\n) {}\n"
+    );
+    assert_eq!(
+        format_string_with_config(
+            &db,
+            content.to_string(),
+            config.trailing_comma(Some(TrailingCommaBehavior::Never))
+        ),
+        "fn foo(\n  a: felt252,\n  b: felt252\n) {}\n"
+    );
+}
+
+/// Returns the words of the comments in the code, each along with the kind of the comment and the
+/// text of the first token following it. Commas are skipped, since the formatter may add or remove
+/// trailing commas. The result is sorted, as the formatter may reorder module items.
+fn attached_comments(db: &SimpleParserDatabase, root: &SyntaxNode<'_>) -> Vec<(String, String)> {
+    let mut pending_words = vec![];
+    let mut result = vec![];
+    for node in root.descendants(db) {
+        let kind = node.kind(db);
+        let Some(text) = node.text(db) else { continue };
+        let text = text.long(db).as_str();
+        match kind {
+            SyntaxKind::TokenSingleLineComment
+            | SyntaxKind::TokenSingleLineDocComment
+            | SyntaxKind::TokenSingleLineInnerComment => {
+                let (prefix, content) =
+                    text.split_at(text.find(|c| c != '/' && c != '!').unwrap_or(text.len()));
+                pending_words
+                    .extend(content.split_whitespace().map(|word| format!("{prefix} {word}")));
+            }
+            SyntaxKind::TokenWhitespace | SyntaxKind::TokenNewline | SyntaxKind::TokenComma => {}
+            _ => result.extend(pending_words.drain(..).map(|word| (word, text.to_string()))),
+        }
+    }
+    result.extend(pending_words.drain(..).map(|word| (word, String::new())));
+    result.sort();
+    result
+}
+
+const RANGE_FORMATTING_CONTENT: &str = indoc! {"
+    mod   inner{
+        fn   foo( ) {
+
+
+            let   x=1;
+            // A comment.
+            let   y=x;
+        }
+
+        fn   bar( ) {let   z  =  2;}
+    }
+"};
+
+/// Formats the range of [RANGE_FORMATTING_CONTENT] starting at the first occurrence of
+/// `range_start` and ending after the first occurrence of `range_end`.
+fn format_range_of_content(range_start: &str, range_end: &str) -> String {
+    let db = SimpleParserDatabase::default();
+    let content = RANGE_FORMATTING_CONTENT;
+    let syntax_root = db.parse_virtual(content).unwrap();
+    let start = content.find(range_start).unwrap();
+    let end = content.find(range_end).unwrap() + range_end.len();
+    let range = TextSpan::new(
+        TextOffset::START.add_width(TextWidth::at(content, start)),
+        TextOffset::START.add_width(TextWidth::at(content, end)),
+    );
+    let edits = format_range(&db, &syntax_root, range, FormatterConfig::default());
+    edits.iter().rev().try_fold(content.to_string(), |content, edit| edit.apply(&content)).unwrap()
+}
+
+#[test]
+fn format_range_of_statement() {
+    assert_eq!(
+        format_range_of_content("y=", "y="),
+        indoc! {"
+            mod   inner{
+                fn   foo( ) {
+
+
+                    let   x=1;
+                    // A comment.
+                    let y = x;
+                }
+
+                fn   bar( ) {let   z  =  2;}
+            }
+        "}
+    );
+    // A range over several statements formats all of them, including the comments between them.
+    assert_eq!(
+        format_range_of_content("x=1", "y=x"),
+        indoc! {"
+            mod   inner{
+                fn   foo( ) {
+
+
+                    let x = 1;
+                    // A comment.
+                    let y = x;
+                }
+
+                fn   bar( ) {let   z  =  2;}
+            }
+        "}
+    );
+}
+
+#[test]
+fn format_range_of_item() {
+    // The statement does not start on a line of its own, so the whole function is formatted.
+    assert_eq!(
+        format_range_of_content("z", "z"),
+        indoc! {"
+            mod   inner{
+                fn   foo( ) {
+
+
+                    let   x=1;
+                    // A comment.
+                    let   y=x;
+                }
+
+                fn bar() {
+                    let z = 2;
+                }
+            }
+        "}
+    );
+    // The module is not inside any list other than the file's item list.
+    assert_eq!(
+        format_range_of_content("inner", "inner"),
+        indoc! {"
+            mod inner {
+                fn foo() {
+                    let x = 1;
+                    // A comment.
+                    let y = x;
+                }
+
+                fn bar() {
+                    let z = 2;
+                }
+            }
+        "}
+    );
 }
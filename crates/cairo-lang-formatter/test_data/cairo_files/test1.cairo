@@ -54,6 +54,15 @@ fn fib(a: Box::<felt252>, b: Box::<felt252>, n: Box::<felt252>) -> Box::<felt252
 // A trailing comment.
 }
 
+fn match_ranges_and_guards(n: u8) -> u8 {
+    match n {
+        0 => 1,
+        1 ..= 9 | 20..30 => 2,
+        x if   x > 100 => 3,
+        _ => 4,
+    }
+}
+
 fn if_let_test() {
     if let (x, y) = (x + x + x + x + x + x + x + x + x + x + x + x + x + x + x + x + x + x,y + y + y + y + y + y) {
         if_let_block_content();
@@ -77,3 +86,8 @@ fn semicolon_preservation_test() {
     if true { return; };
     foo();
 }
+
+trait TraitWithDefaults {
+    type   Item=u32;
+    const SIZE:usize    =   3;
+}
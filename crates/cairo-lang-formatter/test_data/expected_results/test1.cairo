@@ -60,6 +60,15 @@ fn fib(a: Box<felt252>, b: Box<felt252>, n: Box<felt252>) -> Box<felt252> {
     // A trailing comment.
 }
 
+fn match_ranges_and_guards(n: u8) -> u8 {
+    match n {
+        0 => 1,
+        1..=9 | 20..30 => 2,
+        x if x > 100 => 3,
+        _ => 4,
+    }
+}
+
 fn if_let_test() {
     if let (x, y) =
         (
@@ -93,3 +102,8 @@ fn semicolon_preservation_test() {
     }
     foo();
 }
+
+trait TraitWithDefaults {
+    type Item = u32;
+    const SIZE: usize = 3;
+}
@@ -0,0 +1,18 @@
+// A comment before the function.
+fn foo(
+    first_argument: felt252, second_argument: felt252, third_argument: felt252, fourth: felt252
+) -> felt252 {
+    let x = bar(
+        first_argument, second_argument, third_argument, fourth, first_argument, second_argument
+    );
+    // A comment inside a broken list.
+    let s = MyStruct { first_member: first_argument, second_member: second_argument, third: x };
+    let t = (1, 2);
+    let short = bar(1, 2); // A trailing comment.
+    x
+}
+
+enum MyEnum {
+    First,
+    Second // A comment after the last variant.
+}
@@ -14,7 +14,7 @@ use crate::db::LoweringGroup;
 use crate::ids::{ConcreteFunctionWithBodyId, LocationId, SemanticFunctionIdEx};
 use crate::{
     Block, BlockEnd, BlockId, Lowered, MatchArm, MatchEnumInfo, MatchExternInfo, MatchInfo,
-    Statement, StatementCall, VarUsage, Variable,
+    Statement, StatementCall, VarUsage, Variable, VariableArena,
 };
 
 /// Main function for the add_withdraw_gas lowering phase. Adds a `withdraw_gas` statement to the
@@ -41,7 +41,7 @@ fn add_withdraw_gas_to_function<'db>(
 ) -> Maybe<()> {
     let location = LocationId::from_stable_location(db, function.stable_location(db)?)
         .with_auto_generation_note(db, "withdraw_gas");
-    let panic_block = create_panic_block(db, lowered, location)?;
+    let panic_block = create_panic_block(db, &mut lowered.variables, location, "Out of gas")?;
 
     let old_root_block = lowered.blocks.root_block()?.clone();
     let old_root_new_id = lowered.blocks.push(old_root_block);
@@ -86,14 +86,16 @@ fn add_withdraw_gas_to_function<'db>(
     Ok(())
 }
 
-/// Creates the panic block for the case `withdraw_gas` failure.
-fn create_panic_block<'db>(
+/// Creates a block panicking with the given short string, e.g. for the case of `withdraw_gas`
+/// failure.
+pub(crate) fn create_panic_block<'db>(
     db: &'db dyn Database,
-    lowered: &mut Lowered<'db>,
+    variables: &mut VariableArena<'db>,
     location: LocationId<'db>,
+    message: &str,
 ) -> Maybe<Block<'db>> {
     let never_ty = never_ty(db);
-    let never_var = lowered.variables.alloc(Variable::with_default_context(db, never_ty, location));
+    let never_var = variables.alloc(Variable::with_default_context(db, never_ty, location));
 
     let gas_panic_fn = get_function_id(
         db,
@@ -101,7 +103,7 @@ fn create_panic_block<'db>(
         SmolStrId::from(db, "panic_with_const_felt252"),
         vec![GenericArgumentId::Constant(
             ConstValue::Int(
-                BigInt::from_bytes_be(Sign::Plus, "Out of gas".as_bytes()),
+                BigInt::from_bytes_be(Sign::Plus, message.as_bytes()),
                 db.core_info().felt252,
             )
             .intern(db),
@@ -114,7 +116,7 @@ fn create_panic_block<'db>(
         ConcreteTypeId::Enum
     );
 
-    // The block consists of calling  panic_with_const_felt252::<'Out of gas'> and matching on its
+    // The block consists of calling  panic_with_const_felt252::<'message'> and matching on its
     // `never` result.
     Ok(Block {
         statements: vec![Statement::Call(StatementCall {
@@ -3,6 +3,7 @@
 
 use std::collections::HashMap;
 
+use crate::analysis::block_order::BlockOrder;
 use crate::analysis::{Analyzer, DataflowAnalyzer, Direction, Edge, StatementLocation};
 use crate::{Block, BlockEnd, BlockId, Lowered, MatchInfo, Statement, VarRemapping, VarUsage};
 
@@ -19,12 +20,8 @@ impl<'db, 'a, TAnalyzer: Analyzer<'db, 'a>> BackAnalysis<'db, 'a, TAnalyzer> {
     }
     /// Gets the analysis info for the entire function.
     pub fn get_root_info(&mut self) -> TAnalyzer::Info {
-        let mut dfs_stack = vec![BlockId::root()];
-        while let Some(block_id) = dfs_stack.last() {
-            let end = &self.lowered.blocks[*block_id].end;
-            if !self.add_missing_dependency_blocks(&mut dfs_stack, end) {
-                self.calc_block_info(dfs_stack.pop().unwrap());
-            }
+        for block_id in BlockOrder::new(self.lowered).postorder() {
+            self.calc_block_info(*block_id);
         }
         self.block_info.remove(&BlockId::root()).unwrap()
     }
@@ -45,35 +42,6 @@ impl<'db, 'a, TAnalyzer: Analyzer<'db, 'a>> BackAnalysis<'db, 'a, TAnalyzer> {
         self.block_info.insert(block_id, info);
     }
 
-    /// Adds to the DFS stack the dependent blocks that are not yet in cache - returns whether if
-    /// there are any such blocks.
-    fn add_missing_dependency_blocks(
-        &self,
-        dfs_stack: &mut Vec<BlockId>,
-        block_end: &'a BlockEnd<'_>,
-    ) -> bool {
-        match block_end {
-            BlockEnd::NotSet => unreachable!(),
-            BlockEnd::Goto(target_block_id, _)
-                if !self.block_info.contains_key(target_block_id) =>
-            {
-                dfs_stack.push(*target_block_id);
-                true
-            }
-            BlockEnd::Goto(_, _) | BlockEnd::Return(..) | BlockEnd::Panic(_) => false,
-            BlockEnd::Match { info } => {
-                let mut missing_cache = false;
-                for arm in info.arms() {
-                    if !self.block_info.contains_key(&arm.block_id) {
-                        dfs_stack.push(arm.block_id);
-                        missing_cache = true;
-                    }
-                }
-                missing_cache
-            }
-        }
-    }
-
     /// Gets the analysis info from the block's end onwards.
     fn get_end_info(&mut self, block_id: BlockId) -> TAnalyzer::Info {
         let block_end = &self.lowered.blocks[block_id].end;
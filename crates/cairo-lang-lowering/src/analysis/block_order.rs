@@ -0,0 +1,75 @@
+//! Stable orderings of the blocks of a lowered function.
+//!
+//! The orderings are computed by a depth-first search from the root block, visiting the arms of a
+//! match from the last to the first, so that the reverse postorder lists them in their order. The
+//! orderings only depend on the structure of the function. Blocks unreachable from the root are
+//! excluded from the orderings, and are available separately.
+
+use crate::{BlockEnd, BlockId, Lowered};
+
+/// The orderings of the blocks of a lowered function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockOrder {
+    /// The reachable blocks in postorder - every block appears after all of its successors.
+    postorder: Vec<BlockId>,
+    /// The blocks unreachable from the root, by increasing id.
+    unreachable: Vec<BlockId>,
+}
+
+impl BlockOrder {
+    /// Computes the block orderings of a lowered function.
+    pub fn new(lowered: &Lowered<'_>) -> Self {
+        let n_blocks = lowered.blocks.len();
+        if n_blocks == 0 {
+            return Self { postorder: vec![], unreachable: vec![] };
+        }
+        let mut visited = vec![false; n_blocks];
+        let mut postorder = Vec::with_capacity(n_blocks);
+        // The stack of the search - each block with the number of its successors already pushed.
+        let mut stack = vec![(BlockId::root(), 0)];
+        visited[0] = true;
+        while let Some((block_id, next_successor)) = stack.last_mut() {
+            let block_id = *block_id;
+            let successor = match &lowered.blocks[block_id].end {
+                BlockEnd::Goto(target, _) => (*next_successor == 0).then_some(*target),
+                BlockEnd::Match { info } => {
+                    let arms = info.arms();
+                    arms.len().checked_sub(*next_successor + 1).map(|idx| arms[idx].block_id)
+                }
+                BlockEnd::Return(..) | BlockEnd::Panic(_) | BlockEnd::NotSet => None,
+            };
+            *next_successor += 1;
+            match successor {
+                Some(successor) => {
+                    if !visited[successor.0] {
+                        visited[successor.0] = true;
+                        stack.push((successor, 0));
+                    }
+                }
+                None => {
+                    postorder.push(block_id);
+                    stack.pop();
+                }
+            }
+        }
+        let unreachable = (0..n_blocks).filter(|idx| !visited[*idx]).map(BlockId).collect();
+        Self { postorder, unreachable }
+    }
+
+    /// Returns the reachable blocks in postorder - every block appears after all of its
+    /// successors, as required by backward analyses.
+    pub fn postorder(&self) -> &[BlockId] {
+        &self.postorder
+    }
+
+    /// Returns the reachable blocks in reverse postorder - every block appears before all of its
+    /// successors, starting with the root, as required by forward analyses.
+    pub fn reverse_postorder(&self) -> impl DoubleEndedIterator<Item = BlockId> + '_ {
+        self.postorder.iter().rev().copied()
+    }
+
+    /// Returns the blocks unreachable from the root, by increasing id.
+    pub fn unreachable(&self) -> &[BlockId] {
+        &self.unreachable
+    }
+}
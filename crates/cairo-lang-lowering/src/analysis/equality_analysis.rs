@@ -4,8 +4,14 @@
 //! program. Two variables are equivalent if they hold the same value. Additionally, the analysis
 //! tracks `Box`/unbox and snapshot/desnap relationships between equivalence classes.
 
+#[cfg(test)]
+#[path = "equality_analysis_lattice_test.rs"]
+mod lattice_test;
+
 use std::fmt;
 
+use cairo_lang_utils::copy_on_write::CopyOnWrite;
+use cairo_lang_utils::dense_map::DenseMap;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 
 use crate::analysis::core::Edge;
@@ -13,7 +19,7 @@ use crate::analysis::{DataflowAnalyzer, Direction, ForwardDataflowAnalysis};
 use crate::{BlockEnd, BlockId, Lowered, Statement, VariableId};
 
 /// Tracks relationships between equivalence classes.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default)]
 struct ClassInfo {
     /// If this class has a boxed version, the representative of that class.
     boxed_class: Option<VariableId>,
@@ -65,36 +71,35 @@ impl ClassInfo {
 /// This is the `Info` type for the dataflow analysis. Each block gets its own
 /// `EqualityState` representing what we know at that point in the program.
 ///
-/// Uses sparse HashMaps for efficiency - only variables that have been touched
-/// by the analysis are stored.
+/// Only variables that have been touched by the analysis are stored. The maps are shared between
+/// clones of the state until modified, as states are cloned along every edge.
 #[derive(Clone, Default)]
 pub struct EqualityState {
     /// Union-find parent map. If a variable is not in the map, it is its own representative.
-    union_find: OrderedHashMap<VariableId, VariableId>,
+    union_find: CopyOnWrite<DenseMap<VariableId, VariableId>>,
 
     /// For each equivalence class representative, track relationships only if they exist.
-    class_info: OrderedHashMap<VariableId, ClassInfo>,
+    class_info: CopyOnWrite<DenseMap<VariableId, ClassInfo>>,
 }
 
 impl EqualityState {
     /// Gets the parent of a variable, defaulting to itself (root) if not in the map.
     fn get_parent(&self, var: VariableId) -> VariableId {
-        self.union_find.get(&var).copied().unwrap_or(var)
-    }
-
-    /// Gets the class info for a variable, returning a default if not present.
-    fn get_class_info(&self, var: VariableId) -> ClassInfo {
-        self.class_info.get(&var).cloned().unwrap_or_default()
+        self.union_find.get(var).copied().unwrap_or(var)
     }
 
     /// Finds the representative of a variable's equivalence class.
-    /// Uses path compression for efficiency.
+    /// Uses path compression for efficiency, unless the union-find map is shared with other states,
+    /// in which case the lookup does not copy the map just to compress it.
     fn find(&mut self, var: VariableId) -> VariableId {
+        if self.union_find.is_shared() {
+            return self.find_immut(var);
+        }
         let parent = self.get_parent(var);
         if parent != var {
             let root = self.find(parent);
             // Path compression: point directly to root.
-            self.union_find.insert(var, root);
+            self.union_find.to_mut().insert(var, root);
             root
         } else {
             var
@@ -124,20 +129,22 @@ impl EqualityState {
             if root_a.index() < root_b.index() { (root_a, root_b) } else { (root_b, root_a) };
 
         // Ensure new_root is in the map (as its own parent).
-        self.union_find.entry(new_root).or_insert(new_root);
+        let union_find = self.union_find.to_mut();
+        union_find.get_or_insert_with(new_root, || new_root);
         // Update old_root to point to new_root.
-        self.union_find.insert(old_root, new_root);
+        union_find.insert(old_root, new_root);
 
         // Merge class info: since A == B, we have Box(A) == Box(B), @A == @B, etc.
         // Recursive unions inside merge() only affect related classes (which have strictly
         // one-step increment in information in forward analysis), so they never deposit class_info
         // back at new_root.
-        let old_info = self.class_info.swap_remove(&old_root).unwrap_or_default();
-        let new_info = self.class_info.swap_remove(&new_root).unwrap_or_default();
+        let class_info = self.class_info.to_mut();
+        let old_info = class_info.remove(old_root).unwrap_or_default();
+        let new_info = class_info.remove(new_root).unwrap_or_default();
         let merged = new_info.merge(old_info, &mut |a, b| self.union(a, b));
         if !merged.is_empty() {
             let final_root = self.find(new_root);
-            self.class_info.insert(final_root, merged);
+            self.class_info.to_mut().insert(final_root, merged);
         }
 
         self.find(new_root)
@@ -150,8 +157,7 @@ impl EqualityState {
         field: fn(&mut ClassInfo) -> &mut Option<VariableId>,
     ) -> Option<VariableId> {
         let rep = self.find(var);
-        let mut info = self.get_class_info(rep);
-        let related = (*field(&mut info))?;
+        let related = self.class_info.get(rep).copied().and_then(|mut info| *field(&mut info))?;
         Some(self.find(related))
     }
 
@@ -176,8 +182,9 @@ impl EqualityState {
         let rep_a = self.find(var_a);
         let rep_b = self.find(var_b);
 
-        *field_a_to_b(self.class_info.entry(rep_a).or_default()) = Some(rep_b);
-        *field_b_to_a(self.class_info.entry(rep_b).or_default()) = Some(rep_a);
+        let class_info = self.class_info.to_mut();
+        *field_a_to_b(class_info.get_or_insert_with(rep_a, Default::default)) = Some(rep_b);
+        *field_b_to_a(class_info.get_or_insert_with(rep_b, Default::default)) = Some(rep_a);
     }
 
     /// Sets a box relationship: boxed_var = Box(unboxed_var).
@@ -205,7 +212,7 @@ impl fmt::Debug for EqualityState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let v = |id: VariableId| format!("v{}", self.find_immut(id).index());
         let mut lines = Vec::<String>::new();
-        for (&rep, info) in self.class_info.iter() {
+        for (rep, info) in self.class_info.iter() {
             if let Some(s) = info.snapshot_class {
                 lines.push(format!("@{} = {}", v(rep), v(s)));
             }
@@ -213,7 +220,7 @@ impl fmt::Debug for EqualityState {
                 lines.push(format!("Box({}) = {}", v(rep), v(b)));
             }
         }
-        for &var in self.union_find.keys() {
+        for var in self.union_find.keys() {
             let rep = self.find_immut(var);
             if var != rep {
                 lines.push(format!("v{} = v{}", rep.index(), var.index()));
@@ -245,7 +252,7 @@ fn merge_referenced_vars<'a>(
     info1: &'a EqualityState,
     info2: &'a EqualityState,
 ) -> impl Iterator<Item = VariableId> + 'a {
-    let union_find_vars = info1.union_find.keys().chain(info2.union_find.keys()).copied();
+    let union_find_vars = info1.union_find.keys().chain(info2.union_find.keys());
 
     let class_info_vars = info1
         .class_info
@@ -279,9 +286,9 @@ fn merge_class_relationships(
         })
     }
 
-    for (&var, class1) in info1.class_info.iter() {
+    for (var, class1) in info1.class_info.iter() {
         for &(var_rep2, intersection_var) in intersections.get(&var).unwrap_or(&vec![]) {
-            let Some(class2) = info2.class_info.get(&var_rep2) else {
+            let Some(class2) = info2.class_info.get(var_rep2) else {
                 continue;
             };
 
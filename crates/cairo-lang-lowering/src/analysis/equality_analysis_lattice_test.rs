@@ -0,0 +1,203 @@
+//! Property tests for the lattice laws of the equality analysis states.
+
+use itertools::Itertools;
+use proptest::prelude::*;
+use proptest::test_runner::TestRunner;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::{EqualityAnalysis, EqualityState};
+use crate::analysis::DataflowAnalyzer;
+use crate::test_utils::LoweringDatabaseForTesting;
+use crate::test_utils::lowered_generator::{LoweredGeneratorConfig, generate_lowered};
+use crate::{BlockId, Lowered, VariableArena, VariableId};
+
+/// The number of layers of variables. Relationships are only set from a variable to a variable in
+/// the next layer (as `Box<T>` and `@T` are different types than `T`), and unions are only applied
+/// within a layer.
+const LAYERS: usize = 3;
+/// The number of variables in each layer.
+const LAYER_WIDTH: usize = 4;
+
+/// An operation applied on an equality state, on indices of variables.
+#[derive(Clone, Debug)]
+enum Operation {
+    Union(usize, usize),
+    Box { unboxed: usize, boxed: usize },
+    Snapshot { original: usize, snapshot: usize },
+}
+
+/// Returns a strategy for the sequence of operations building an equality state.
+fn operations_strategy() -> impl Strategy<Value = Vec<Operation>> {
+    let var = |layer: usize, index: usize| layer * LAYER_WIDTH + index;
+    let operation = prop_oneof![
+        (0..LAYERS, 0..LAYER_WIDTH, 0..LAYER_WIDTH)
+            .prop_map(move |(layer, a, b)| Operation::Union(var(layer, a), var(layer, b))),
+        (0..LAYERS - 1, 0..LAYER_WIDTH, 0..LAYER_WIDTH).prop_map(move |(layer, a, b)| {
+            Operation::Box { unboxed: var(layer, a), boxed: var(layer + 1, b) }
+        }),
+        (0..LAYERS - 1, 0..LAYER_WIDTH, 0..LAYER_WIDTH).prop_map(move |(layer, a, b)| {
+            Operation::Snapshot { original: var(layer, a), snapshot: var(layer + 1, b) }
+        }),
+    ];
+    prop::collection::vec(operation, 0..12)
+}
+
+/// The context of the property tests - a lowered body to pass to the analyzer, and the variables
+/// the operations apply on.
+struct TestContext<'db> {
+    lowered: Lowered<'db>,
+    vars: Vec<VariableId>,
+}
+
+impl<'db> TestContext<'db> {
+    fn new(db: &'db LoweringDatabaseForTesting) -> Self {
+        let config = LoweredGeneratorConfig { max_match_depth: 0, ..Default::default() };
+        let lowered = generate_lowered(db, &mut StdRng::seed_from_u64(0), &config);
+        let var = &lowered.variables[lowered.parameters[0]];
+        let mut variables = VariableArena::default();
+        let vars = (0..LAYERS * LAYER_WIDTH).map(|_| variables.alloc(var.clone())).collect();
+        Self { lowered, vars }
+    }
+
+    /// Builds an equality state by applying `operations` on an empty state.
+    fn build_state(&self, operations: &[Operation]) -> EqualityState {
+        let mut state = EqualityState::default();
+        for operation in operations {
+            match *operation {
+                Operation::Union(a, b) => {
+                    state.union(self.vars[a], self.vars[b]);
+                }
+                Operation::Box { unboxed, boxed } => {
+                    state.set_box_relationship(self.vars[unboxed], self.vars[boxed])
+                }
+                Operation::Snapshot { original, snapshot } => {
+                    state.set_snapshot_relationship(self.vars[original], self.vars[snapshot])
+                }
+            }
+        }
+        state
+    }
+
+    fn merge(&self, info1: &EqualityState, info2: &EqualityState) -> EqualityState {
+        EqualityAnalysis.merge(&self.lowered, (BlockId::root(), 0), info1.clone(), info2.clone())
+    }
+
+    /// Returns the pairs of distinct variables which are equal in `state`.
+    fn equal_pairs(&self, state: &EqualityState) -> Vec<(VariableId, VariableId)> {
+        self.vars
+            .iter()
+            .tuple_combinations()
+            .filter(|(a, b)| state.find_immut(**a) == state.find_immut(**b))
+            .map(|(a, b)| (*a, *b))
+            .collect()
+    }
+}
+
+/// Runs `test` on `num_states` states, built from generated operations.
+fn run_on_states(
+    test: impl Fn(&TestContext<'_>, &[EqualityState]) -> Result<(), TestCaseError>,
+    num_states: usize,
+) {
+    let db = LoweringDatabaseForTesting::default();
+    let ctx = TestContext::new(&db);
+    TestRunner::default()
+        .run(&prop::collection::vec(operations_strategy(), num_states), |operations| {
+            let states = operations.iter().map(|ops| ctx.build_state(ops)).collect_vec();
+            test(&ctx, &states)
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_merge_is_commutative() {
+    run_on_states(
+        |ctx, states| {
+            let [a, b] = states else { unreachable!() };
+            prop_assert_eq!(format!("{:?}", ctx.merge(a, b)), format!("{:?}", ctx.merge(b, a)));
+            Ok(())
+        },
+        2,
+    );
+}
+
+#[test]
+fn test_merge_is_associative() {
+    run_on_states(
+        |ctx, states| {
+            let [a, b, c] = states else { unreachable!() };
+            prop_assert_eq!(
+                format!("{:?}", ctx.merge(&ctx.merge(a, b), c)),
+                format!("{:?}", ctx.merge(a, &ctx.merge(b, c)))
+            );
+            Ok(())
+        },
+        3,
+    );
+}
+
+#[test]
+fn test_merge_is_idempotent() {
+    run_on_states(
+        |ctx, states| {
+            let [a] = states else { unreachable!() };
+            prop_assert_eq!(format!("{:?}", ctx.merge(a, a)), format!("{a:?}"));
+            Ok(())
+        },
+        1,
+    );
+}
+
+#[test]
+fn test_merge_intersects_equalities() {
+    run_on_states(
+        |ctx, states| {
+            let [a, b] = states else { unreachable!() };
+            let merged_pairs = ctx.equal_pairs(&ctx.merge(a, b));
+            let b_pairs = ctx.equal_pairs(b);
+            let expected_pairs =
+                ctx.equal_pairs(a).into_iter().filter(|pair| b_pairs.contains(pair)).collect_vec();
+            prop_assert_eq!(merged_pairs, expected_pairs);
+            Ok(())
+        },
+        2,
+    );
+}
+
+#[test]
+fn test_representatives_are_lowest_ids() {
+    /// Asserts that the representative of each variable is the lowest variable of its class.
+    fn check_representatives(
+        ctx: &TestContext<'_>,
+        state: &mut EqualityState,
+    ) -> Result<(), TestCaseError> {
+        for var in &ctx.vars {
+            let rep = state.find_immut(*var);
+            prop_assert_eq!(state.find(*var), rep);
+            prop_assert_eq!(state.find(rep), rep);
+            let lowest = ctx.vars.iter().filter(|v| state.find_immut(**v) == rep).min().unwrap();
+            prop_assert_eq!(*lowest, rep);
+        }
+        Ok(())
+    }
+
+    run_on_states(
+        |ctx, states| {
+            let [state] = states else { unreachable!() };
+            check_representatives(ctx, &mut state.clone())?;
+            let same_layer_pairs =
+                ctx.vars.chunks(LAYER_WIDTH).flat_map(|layer| layer.iter().tuple_combinations());
+            for (a, b) in same_layer_pairs {
+                // A union may also unite the related classes, and through them additional
+                // variables, so the new representative may be lower than both.
+                let mut state = state.clone();
+                let rep = state.union(*a, *b);
+                prop_assert_eq!(state.find(*a), rep);
+                prop_assert_eq!(state.find(*b), rep);
+                check_representatives(ctx, &mut state)?;
+            }
+            Ok(())
+        },
+        1,
+    );
+}
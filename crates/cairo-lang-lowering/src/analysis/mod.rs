@@ -4,7 +4,9 @@
 //! optimization passes and semantic checks.
 
 pub mod backward;
+pub mod block_order;
 pub use backward::{BackAnalysis, DataflowBackAnalysis};
+pub use block_order::BlockOrder;
 
 pub mod core;
 pub use core::{DataflowAnalyzer, Direction, Edge, StatementLocation};
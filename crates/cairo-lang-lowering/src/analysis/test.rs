@@ -6,6 +6,7 @@ use cairo_lang_semantic::test_utils::setup_test_function;
 use cairo_lang_utils::Intern;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 
+use super::block_order::BlockOrder;
 use super::core::{DataflowAnalyzer, Direction, StatementLocation};
 use super::forward::ForwardDataflowAnalysis;
 use crate::db::LoweringGroup;
@@ -189,3 +190,66 @@ fn test_forward_with_branching() {
         assert!(exit_info[block_id.0].is_some(), "Block {:?} should have exit info", block_id);
     }
 }
+
+#[test]
+fn test_block_order() {
+    let db = LoweringDatabaseForTesting::default();
+    let inputs = OrderedHashMap::from([
+        (
+            "function_code".to_string(),
+            "fn foo(x: bool, y: bool) -> felt252 {
+                let a = if x { 1 } else { 2 };
+                let b = if y { a } else { 3 };
+                a + b
+            }"
+            .to_string(),
+        ),
+        ("function_name".to_string(), "foo".to_string()),
+        ("module_code".to_string(), "".to_string()),
+    ]);
+    let (test_function, _) = setup_test_function(&db, &inputs).split();
+    let mut lowered = db
+        .function_with_body_lowering(
+            FunctionWithBodyLongId::Semantic(test_function.function_id).intern(&db),
+        )
+        .unwrap()
+        .clone();
+    // Add an unreachable block jumping into the function.
+    let BlockEnd::Match { info } = &lowered.blocks[BlockId::root()].end else {
+        panic!("Expected the root block to end with a match.");
+    };
+    let target = info.arms()[0].block_id;
+    let unreachable = lowered
+        .blocks
+        .push(Block { statements: vec![], end: BlockEnd::Goto(target, Default::default()) });
+
+    let order = BlockOrder::new(&lowered);
+    assert_eq!(order.unreachable(), [unreachable]);
+    let rpo = order.reverse_postorder().collect::<Vec<_>>();
+    assert_eq!(rpo.len(), lowered.blocks.len() - 1);
+    assert_eq!(rpo[0], BlockId::root());
+    assert_eq!(order.postorder().iter().rev().copied().collect::<Vec<_>>(), rpo);
+    // Every block appears before its successors, and the arms of a match are in order.
+    let position = |block_id: BlockId| rpo.iter().position(|b| *b == block_id).unwrap();
+    for block_id in &rpo {
+        match &lowered.blocks[*block_id].end {
+            BlockEnd::Goto(target, _) => assert!(position(*block_id) < position(*target)),
+            BlockEnd::Match { info } => {
+                let arm_positions =
+                    info.arms().iter().map(|arm| position(arm.block_id)).collect::<Vec<_>>();
+                assert!(
+                    arm_positions.iter().all(|arm_position| position(*block_id) < *arm_position)
+                );
+                assert!(arm_positions.is_sorted());
+            }
+            _ => {}
+        }
+    }
+
+    // The forward analysis processes all the reachable blocks, despite the unreachable
+    // predecessor.
+    let mut analysis = ForwardDataflowAnalysis::new(&lowered, ReachabilityAnalyzer::default());
+    let exit_info = analysis.run();
+    assert_eq!(analysis.analyzer.reachable_blocks, rpo.iter().copied().collect());
+    assert!(exit_info[unreachable.0].is_none());
+}
@@ -1,4 +1,5 @@
 //! This module provides the Demand utility struct used for analyzing usage of variables.
+use cairo_lang_utils::copy_on_write::CopyOnWrite;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 
 /// A reporting trait that reports each variables dup, drop and last_use positions.
@@ -31,7 +32,9 @@ impl<Var> DemandReporter<Var> for EmptyDemandReporter {
 /// Needs to be updated in backwards order.
 #[derive(Clone)]
 pub struct Demand<Var: std::hash::Hash + Eq + Copy, UsePosition, Aux: Clone + Default = ()> {
-    pub vars: OrderedHashMap<Var, UsePosition>,
+    /// The demanded variables, with their next usage positions. Shared between clones of the
+    /// demand until modified.
+    pub vars: CopyOnWrite<OrderedHashMap<Var, UsePosition>>,
     pub aux: Aux,
 }
 impl<Var: std::hash::Hash + Eq + Copy, UsePosition, Aux: Clone + Default> Default
@@ -66,8 +69,9 @@ impl<Var: std::hash::Hash + Eq + Copy, UsePosition: Copy, Aux: Clone + Default +
         for (dst, (src, position)) in remapping.rev() {
             let src = (*src).into();
             let dst = (*dst).into();
-            if let Some(dest_next_usage_position) = self.vars.swap_remove(&dst) {
-                if let Some(next_usage_position) = self.vars.insert(src, dest_next_usage_position) {
+            let vars = self.vars.to_mut();
+            if let Some(dest_next_usage_position) = vars.swap_remove(&dst) {
+                if let Some(next_usage_position) = vars.insert(src, dest_next_usage_position) {
                     reporter.dup(position, src, next_usage_position);
                 } else {
                     reporter.last_use(position, src);
@@ -91,7 +95,7 @@ impl<Var: std::hash::Hash + Eq + Copy, UsePosition: Copy, Aux: Clone + Default +
     ) {
         for (var, position) in vars.rev() {
             let var = (*var).into();
-            if let Some(next_usage_position) = self.vars.insert(var, position) {
+            if let Some(next_usage_position) = self.vars.to_mut().insert(var, position) {
                 // Variable already used. If it's not dup, that is an issue.
                 reporter.dup(position, var, next_usage_position);
             } else {
@@ -108,9 +112,12 @@ impl<Var: std::hash::Hash + Eq + Copy, UsePosition: Copy, Aux: Clone + Default +
         position: T::IntroducePosition,
     ) {
         for var in vars {
-            if self.vars.swap_remove(&(*var).into()).is_none() {
+            let var = (*var).into();
+            if self.vars.contains_key(&var) {
+                self.vars.to_mut().swap_remove(&var);
+            } else {
                 // Variable introduced, but not demanded. If it's not drop, that is an issue.
-                reporter.drop_aux(position, (*var).into(), self.aux.clone());
+                reporter.drop_aux(position, var, self.aux.clone());
             }
         }
     }
@@ -125,7 +132,8 @@ impl<Var: std::hash::Hash + Eq + Copy, UsePosition: Copy, Aux: Clone + Default +
         for (arm_demand, _) in demands {
             vars.extend(arm_demand.vars.iter().map(|(var, position)| (*var, *position)));
         }
-        let demand = Self { vars, aux: Aux::merge(demands.iter().map(|(d, _)| &d.aux)) };
+        let demand =
+            Self { vars: vars.into(), aux: Aux::merge(demands.iter().map(|(d, _)| &d.aux)) };
         // Check each var.
         for var in demand.vars.keys() {
             for (arm_demand, position) in demands {
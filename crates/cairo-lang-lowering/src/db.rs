@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs as defs;
 use cairo_lang_defs::db::DefsGroup;
@@ -29,6 +31,7 @@ use crate::cache::load_cached_crate_functions;
 use crate::concretize::concretize_lowered;
 use crate::destructs::add_destructs;
 use crate::diagnostic::{LoweringDiagnostic, LoweringDiagnosticKind};
+use crate::error_tolerance::error_stub_lowering;
 use crate::ids::{ConcreteFunctionWithBodyId, FunctionId, FunctionLongId, GenericOrSpecialized};
 use crate::inline::get_inline_diagnostics;
 use crate::inline::statements_weights::{ApproxCasmInlineWeight, InlineWeight};
@@ -37,6 +40,9 @@ use crate::optimizations::config::Optimizations;
 use crate::optimizations::scrub_units::scrub_units;
 use crate::optimizations::strategy::OptimizationStrategyId;
 use crate::panic::lower_panics;
+use crate::plugin::{
+    LoweringPlugin, LoweringPluginError, apply_lowering_plugins, validate_lowering_plugins,
+};
 use crate::specialization::specialized_function_lowered;
 use crate::{
     BlockEnd, BlockId, DependencyType, Location, Lowered, LoweringStage, MatchInfo, Statement, ids,
@@ -50,11 +56,14 @@ pub struct LoweringGroupInput {
     /// A configurable function to get estimated size of the function with the given id.
     #[returns(ref)]
     code_size_estimator: Option<CodeSizeEstimator>,
+    /// The lowering plugins, applied in the order of registration.
+    #[returns(ref)]
+    pub lowering_plugins: Option<Vec<Arc<dyn LoweringPlugin>>>,
 }
 
 #[salsa::tracked(returns(ref))]
 pub fn lowering_group_input(db: &dyn Database) -> LoweringGroupInput {
-    LoweringGroupInput::new(db, None, None)
+    LoweringGroupInput::new(db, None, None, None)
 }
 
 /// Trait for information over the lowering.
@@ -368,6 +377,12 @@ pub trait LoweringGroup: Database {
         lowering_group_input(db).optimizations(db).as_ref().unwrap()
     }
 
+    /// Returns the registered lowering plugins, in the order of their registration.
+    fn lowering_plugins(&self) -> &[Arc<dyn LoweringPlugin>] {
+        let db = self.as_dyn_database();
+        lowering_group_input(db).lowering_plugins(db).as_deref().unwrap_or_default()
+    }
+
     /// Returns the final optimization strategy that is applied on top of
     /// inlined_function_optimization_strategy.
     fn final_optimization_strategy<'db>(&'db self) -> OptimizationStrategyId<'db> {
@@ -401,6 +416,16 @@ pub fn init_lowering_group(
     lowering_group_input(db).set_code_size_estimator(db).to(code_size_estimator);
 }
 
+/// Registers the lowering plugins of the database, replacing the previously registered ones.
+pub fn set_lowering_plugins(
+    db: &mut (dyn Database + 'static),
+    plugins: Vec<Arc<dyn LoweringPlugin>>,
+) -> Result<(), LoweringPluginError> {
+    validate_lowering_plugins(&plugins)?;
+    lowering_group_input(db).set_lowering_plugins(db).to(Some(plugins));
+    Ok(())
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 pub struct GenericSCCRepresentative<'db>(pub ids::FunctionWithBodyId<'db>);
 
@@ -464,14 +489,13 @@ fn lowered_body<'db>(
     function: ids::ConcreteFunctionWithBodyId<'db>,
     stage: LoweringStage,
 ) -> Maybe<Lowered<'db>> {
-    Ok(match stage {
+    let mut lowered = match stage {
         LoweringStage::Monomorphized => match function.generic_or_specialized(db) {
             GenericOrSpecialized::Generic(generic_function_id) => {
-                db.function_with_body_lowering_diagnostics(generic_function_id)?
-                    .check_error_free()?;
-                let mut lowered = db.function_with_body_lowering(generic_function_id)?.clone();
-                concretize_lowered(db, &mut lowered, &function.substitution(db)?)?;
-                lowered
+                match concrete_generic_function_lowered(db, function, generic_function_id) {
+                    Ok(lowered) => lowered,
+                    Err(diag_added) => error_stub_lowering(db, function).ok_or(diag_added)?,
+                }
             }
             GenericOrSpecialized::Specialized(specialized) => {
                 specialized_function_lowered(db, specialized)?
@@ -495,7 +519,22 @@ fn lowered_body<'db>(
             db.final_optimization_strategy().apply_strategy(db, function, &mut lowered)?;
             lowered
         }
-    })
+    };
+    apply_lowering_plugins(db, function, stage, &mut lowered)?;
+    Ok(lowered)
+}
+
+/// Returns the lowering of a concrete function with a generic body, by concretizing the lowering of
+/// the generic body. Fails if the generic body has errors.
+fn concrete_generic_function_lowered<'db>(
+    db: &'db dyn Database,
+    function: ConcreteFunctionWithBodyId<'db>,
+    generic_function_id: ids::FunctionWithBodyId<'db>,
+) -> Maybe<Lowered<'db>> {
+    db.function_with_body_lowering_diagnostics(generic_function_id)?.check_error_free()?;
+    let mut lowered = db.function_with_body_lowering(generic_function_id)?.clone();
+    concretize_lowered(db, &mut lowered, &function.substitution(db)?)?;
+    Ok(lowered)
 }
 
 /// Given the lowering of a function, returns the set of direct dependencies of that function,
@@ -7,6 +7,7 @@ use cairo_lang_semantic as semantic;
 use cairo_lang_semantic::corelib::LiteralError;
 use cairo_lang_semantic::expr::inference::InferenceError;
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use itertools::Itertools;
 use salsa::Database;
 
 use crate::Location;
@@ -173,8 +174,11 @@ impl<'db> MatchError<'db> {
                 | MatchDiagnostic::UnsupportedMatchArmNonSequential,
                 MatchKind::IfLet | MatchKind::WhileLet(_, _),
             ) => unreachable!("Numeric values are not supported in if/while-let conditions."),
-            (MatchDiagnostic::NonExhaustiveMatch(variant), MatchKind::Match) => {
-                format!("Match is non-exhaustive: `{variant}` not covered.")
+            (MatchDiagnostic::NonExhaustiveMatch(patterns), MatchKind::Match) => {
+                format!(
+                    "Match is non-exhaustive: {} not covered.",
+                    format_missing_patterns(patterns)
+                )
             }
             (MatchDiagnostic::NonExhaustiveMatch(_), MatchKind::IfLet) => {
                 unreachable!("If-let is not required to be exhaustive.")
@@ -200,6 +204,15 @@ impl<'db> MatchError<'db> {
             (MatchDiagnostic::UnsupportedNumericInLetCondition, MatchKind::WhileLet(_, _)) => {
                 "Numeric values are not supported in while-let conditions.".into()
             }
+            (MatchDiagnostic::UnsupportedRangePatternType(ty), _) => {
+                format!("Range patterns are not supported for type `{ty}`.")
+            }
+            (MatchDiagnostic::NonCopyableVariableInGuard(name), _) => {
+                format!(
+                    "Variable `{name}` is not copyable, and cannot be used in a match arm guard, \
+                     as the value may be matched by the following arms."
+                )
+            }
         }
     }
 }
@@ -272,9 +285,33 @@ pub enum MatchDiagnostic {
     UnsupportedMatchArmNotATuple,
 
     UnreachableMatchArm,
-    NonExhaustiveMatch(String),
+    /// A match not covering all the values, with the patterns not covered.
+    NonExhaustiveMatch(Vec<String>),
 
     UnsupportedMatchArmNotALiteral,
     UnsupportedMatchArmNonSequential,
     UnsupportedNumericInLetCondition,
+    /// A range pattern on a type that is not a bounded integer, with the formatted type.
+    UnsupportedRangePatternType(String),
+    /// A guard using a variable of a non-copyable type bound by the patterns of its arm, with the
+    /// name of the variable.
+    NonCopyableVariableInGuard(String),
+}
+
+/// The maximal number of missing patterns listed in a non-exhaustive match diagnostic.
+const MAX_LISTED_MISSING_PATTERNS: usize = 3;
+
+/// Formats the patterns not covered by a match, e.g. "`A::B`, `A::C` and 2 more".
+fn format_missing_patterns(patterns: &[String]) -> String {
+    let listed =
+        patterns.iter().take(MAX_LISTED_MISSING_PATTERNS).map(|p| format!("`{p}`")).collect_vec();
+    let n_unlisted = patterns.len() - listed.len();
+    if n_unlisted > 0 {
+        return format!("{} and {n_unlisted} more", listed.join(", "));
+    }
+    match listed.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+        None => "`_`".into(),
+    }
 }
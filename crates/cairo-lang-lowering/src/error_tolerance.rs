@@ -0,0 +1,54 @@
+//! Error tolerance - compiling functions with errors into panicking stubs, so that the rest of the
+//! program can still be compiled.
+
+use cairo_lang_filesystem::flag::FlagsGroup;
+use salsa::Database;
+
+use crate::add_withdraw_gas::create_panic_block;
+use crate::blocks::BlocksBuilder;
+use crate::ids::{ConcreteFunctionWithBodyId, LocationId};
+use crate::{Lowered, Variable, VariableArena};
+
+#[cfg(test)]
+#[path = "error_tolerance_test.rs"]
+mod test;
+
+/// The panic message of the stubs replacing functions with errors.
+pub const ERROR_STUB_PANIC_MESSAGE: &str = "Function has errors";
+
+/// Returns a lowering of `function` that panics with [ERROR_STUB_PANIC_MESSAGE], to replace its
+/// lowering when it has errors and the `error_tolerant` flag is set.
+///
+/// Returns `None` if the function cannot be stubbed - if the flag is not set, if the signature of
+/// the function has errors, or if the function cannot panic.
+pub fn error_stub_lowering<'db>(
+    db: &'db dyn Database,
+    function: ConcreteFunctionWithBodyId<'db>,
+) -> Option<Lowered<'db>> {
+    if !db.flag_error_tolerant() {
+        return None;
+    }
+    let signature = function.signature(db).ok()?;
+    if !signature.panicable {
+        return None;
+    }
+    let location = LocationId::from_stable_location(db, function.stable_location(db).ok()?)
+        .with_auto_generation_note(db, "error stub");
+    let mut variables = VariableArena::default();
+    let parameters = signature
+        .params
+        .iter()
+        .map(|param| variables.alloc(Variable::with_default_context(db, param.ty, location)))
+        .collect();
+    let panic_block =
+        create_panic_block(db, &mut variables, location, ERROR_STUB_PANIC_MESSAGE).ok()?;
+    let mut blocks = BlocksBuilder::new();
+    blocks.alloc(panic_block);
+    Some(Lowered {
+        diagnostics: Default::default(),
+        signature,
+        variables,
+        blocks: blocks.build()?,
+        parameters,
+    })
+}
@@ -0,0 +1,78 @@
+use cairo_lang_filesystem::flag::{Flag, FlagsGroup};
+use cairo_lang_semantic::test_utils::setup_test_function_ex;
+use indoc::formatdoc;
+use num_bigint::{BigInt, Sign};
+
+use super::ERROR_STUB_PANIC_MESSAGE;
+use crate::db::LoweringGroup;
+use crate::ids::ConcreteFunctionWithBodyId;
+use crate::test_utils::{LoweringDatabaseForTesting, formatted_lowered};
+use crate::{BlockEnd, BlockId, LoweringStage};
+
+/// Lowers `test::foo` defined by `function_code`, with the `error_tolerant` flag set to the given
+/// value, and returns the formatted lowering at the given stage.
+fn lower_foo(function_code: &str, error_tolerant: bool, stage: LoweringStage) -> String {
+    let mut db = LoweringDatabaseForTesting::new();
+    db.set_flag(Flag::ErrorTolerant(error_tolerant));
+    let (test_function, diagnostics) =
+        setup_test_function_ex(&db, function_code, "foo", "", None, None).split();
+    assert!(!diagnostics.is_empty(), "Expected the function to have errors.");
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(&db, test_function.concrete_function_id);
+    formatted_lowered(&db, db.lowered_body(function_id, stage).ok())
+}
+
+#[test]
+fn test_error_stub() {
+    let lowered = lower_foo(
+        "fn foo(a: felt252, ref b: u8) -> felt252 { a + undefined }",
+        true,
+        LoweringStage::Monomorphized,
+    );
+    let message = BigInt::from_bytes_be(Sign::Plus, ERROR_STUB_PANIC_MESSAGE.as_bytes());
+    assert_eq!(
+        lowered,
+        formatdoc! {"
+            Parameters: v0: core::felt252, v1: core::integer::u8
+            blk0 (root):
+            Statements:
+              (v2: core::never) <- core::panic_with_const_felt252::<{message}>()
+            End:
+              Match(match_enum(v2) {{
+              }})
+
+        "}
+    );
+}
+
+#[test]
+fn test_error_stub_final_lowering() {
+    let mut db = LoweringDatabaseForTesting::new();
+    db.set_flag(Flag::ErrorTolerant(true));
+    let (test_function, _) =
+        setup_test_function_ex(&db, "fn foo() -> u8 { undefined }", "foo", "", None, None).split();
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(&db, test_function.concrete_function_id);
+    let lowered = db.lowered_body(function_id, LoweringStage::Final).unwrap();
+    assert!(matches!(lowered.blocks[BlockId::root()].end, BlockEnd::Return(..)));
+}
+
+#[test]
+fn test_no_error_stub_without_flag() {
+    let lowered = lower_foo(
+        "fn foo(a: felt252) -> felt252 { a + undefined }",
+        false,
+        LoweringStage::Monomorphized,
+    );
+    assert!(lowered.starts_with("<Failed lowering function"), "{lowered}");
+}
+
+#[test]
+fn test_no_error_stub_for_nopanic_function() {
+    let lowered = lower_foo(
+        "fn foo(a: felt252) -> felt252 nopanic { a + undefined }",
+        true,
+        LoweringStage::Monomorphized,
+    );
+    assert!(lowered.starts_with("<Failed lowering function"), "{lowered}");
+}
@@ -1,4 +1,5 @@
 pub mod concrete_function_node;
 pub mod cycles;
 pub mod feedback_set;
+pub mod reachable_externs;
 pub mod strongly_connected_components;
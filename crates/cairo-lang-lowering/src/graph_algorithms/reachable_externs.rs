@@ -0,0 +1,37 @@
+use cairo_lang_defs::ids::ExternFunctionId;
+use cairo_lang_diagnostics::Maybe;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use salsa::Database;
+
+use crate::db::LoweringGroup;
+use crate::ids::ConcreteFunctionWithBodyId;
+use crate::{DependencyType, LoweringStage};
+
+/// Returns the number of call sites of each extern function in the functions statically reachable
+/// from `function_id` at the given stage, including `function_id` itself.
+///
+/// Every reachable function is visited once, so the calls of a function reachable through several
+/// call paths are counted once.
+pub fn reachable_extern_calls<'db>(
+    db: &'db dyn Database,
+    function_id: ConcreteFunctionWithBodyId<'db>,
+    stage: LoweringStage,
+) -> Maybe<OrderedHashMap<ExternFunctionId<'db>, usize>> {
+    let mut extern_calls = OrderedHashMap::<ExternFunctionId<'db>, usize>::default();
+    let mut visited = OrderedHashSet::<ConcreteFunctionWithBodyId<'db>>::default();
+    let mut stack = vec![function_id];
+    while let Some(function_id) = stack.pop() {
+        if !visited.insert(function_id) {
+            continue;
+        }
+        for callee in db.lowered_direct_callees(function_id, DependencyType::Call, stage)? {
+            if let Some(body) = callee.body(db)? {
+                stack.push(body);
+            } else if let Some((extern_function, _)) = callee.get_extern(db) {
+                *extern_calls.entry(extern_function).or_default() += 1;
+            }
+        }
+    }
+    Ok(extern_calls)
+}
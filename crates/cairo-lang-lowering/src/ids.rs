@@ -3,8 +3,10 @@ use cairo_lang_defs::ids::{
     NamedLanguageElementId, TopLevelLanguageElementId, TraitFunctionId, UnstableSalsaId,
 };
 use cairo_lang_diagnostics::{DiagnosticAdded, DiagnosticNote, Maybe};
+use cairo_lang_filesystem::flag::FlagsGroup;
 use cairo_lang_proc_macros::{DebugWithDb, HeapSize, SemanticObject};
 use cairo_lang_semantic::corelib::CorelibSemantic;
+use cairo_lang_semantic::items::function_with_body::FunctionWithBodySemantic;
 use cairo_lang_semantic::items::functions::{FunctionsSemantic, ImplGenericFunctionId};
 use cairo_lang_semantic::items::imp::ImplLongId;
 use cairo_lang_semantic::items::structure::StructSemantic;
@@ -65,7 +67,16 @@ impl<'db> FunctionWithBodyId<'db> {
         self.long(db).base_semantic_function(db)
     }
     pub fn signature(&self, db: &'db dyn Database) -> Maybe<Signature<'db>> {
-        Ok(db.function_with_body_lowering(*self)?.signature.clone())
+        match (db.function_with_body_lowering(*self), self.long(db)) {
+            (Ok(lowered), _) => Ok(lowered.signature.clone()),
+            // The lowering of a function with errors is replaced by a stub in error tolerant mode,
+            // so its signature is taken from the semantic model, same as in its lowering.
+            (Err(_), FunctionWithBodyLongId::Semantic(id)) if db.flag_error_tolerant() => {
+                let signature = db.function_with_body_signature(*id)?;
+                Ok(EnrichedSemanticSignature::from_semantic(db, signature).into())
+            }
+            (Err(diag_added), _) => Err(diag_added),
+        }
     }
     pub fn to_concrete(&self, db: &'db dyn Database) -> Maybe<ConcreteFunctionWithBodyId<'db>> {
         Ok(self.long(db).to_concrete(db)?.intern(db))
@@ -9,6 +9,7 @@ pub mod concretize;
 pub mod db;
 pub mod destructs;
 pub mod diagnostic;
+pub mod error_tolerance;
 pub mod fmt;
 pub mod graph_algorithms;
 pub mod ids;
@@ -18,6 +19,7 @@ pub mod lower;
 pub mod objects;
 pub mod optimizations;
 pub mod panic;
+pub mod plugin;
 pub mod reorganize_blocks;
 pub mod scc;
 pub mod specialization;
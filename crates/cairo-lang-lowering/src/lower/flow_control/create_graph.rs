@@ -1,9 +1,12 @@
 use cache::Cache;
+use cairo_lang_semantic::expr::usefulness;
+use cairo_lang_semantic::types::TypesSemantic;
+use cairo_lang_semantic::usage::Usages;
 use cairo_lang_semantic::{self as semantic, Condition, ExprId, PatternId};
 use cairo_lang_syntax::node::TypedStablePtr;
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use filtered_patterns::IndexAndBindings;
-use itertools::Itertools;
+use itertools::{Itertools, chain};
 use patterns::{CreateNodeParams, create_node_for_patterns, get_pattern};
 
 use super::graph::{
@@ -119,20 +122,27 @@ pub fn create_graph_expr_match<'db>(
     let matched_expr_location = ctx.get_location(matched_expr.stable_ptr().untyped());
     let matched_var = graph.new_var(matched_expr.ty(), matched_expr_location);
 
-    // Create a list of patterns and nodes.
-    let pattern_and_nodes: Vec<(PatternId, NodeId)> = expr
+    // Create a list of patterns, nodes and guards.
+    let pattern_and_nodes: Vec<(PatternId, NodeId, Option<ExprId>)> = expr
         .arms
         .iter()
         .flat_map(|match_arm| {
-            // For each arm, create a node for the arm expression.
-            let arm_node =
-                graph.add_node(FlowControlNode::ArmExpr(ArmExpr { expr: match_arm.expression }));
-            // Then map the patterns to that node.
-            match_arm.patterns.iter().map(move |pattern| (*pattern, arm_node))
+            // For each arm, create a node for the arm expression, or a missing node if the guard
+            // of the arm cannot be lowered.
+            let arm_node = match_arm
+                .guard
+                .and_then(|guard| guard_error_node(ctx, &mut graph, match_arm, guard))
+                .unwrap_or_else(|| {
+                    graph.add_node(FlowControlNode::ArmExpr(ArmExpr { expr: match_arm.expression }))
+                });
+            // Then map the patterns to that node, along with the guard of the arm.
+            match_arm.patterns.iter().map(move |pattern| (*pattern, arm_node, match_arm.guard))
         })
         .collect();
 
     let mut cache = Cache::default();
+    // The node of the non-exhaustive match error, shared by all the values not covered by the arms.
+    let mut non_exhaustive_node = None;
 
     let match_node_id = create_node_for_patterns(
         CreateNodeParams {
@@ -140,28 +150,72 @@ pub fn create_graph_expr_match<'db>(
             graph: &mut graph,
             patterns: &pattern_and_nodes
                 .iter()
-                .map(|(pattern, _)| Some(get_pattern(ctx, *pattern)))
+                .map(|(pattern, _, _)| Some(get_pattern(ctx, *pattern)))
                 .collect_vec(),
             build_node_callback: &mut |graph, pattern_indices, path| {
-                // Get the first arm that matches.
-                let Some(index_and_bindings) = pattern_indices.first() else {
-                    // If no arm is available, report a non-exhaustive match error.
-                    let kind = LoweringDiagnosticKind::MatchError(MatchError {
-                        kind: MatchKind::Match,
-                        error: MatchDiagnostic::NonExhaustiveMatch(path),
-                    });
-                    return graph.report_with_missing_node(expr.stable_ptr.untyped(), kind);
+                // The arms that may be chosen, in order: the arms with a guard, up to the first arm
+                // without one.
+                let is_guarded = |index_and_bindings: &IndexAndBindings| {
+                    pattern_and_nodes[index_and_bindings.index()].2.is_some()
                 };
+                let candidates =
+                    pattern_indices.iter().take_while_inclusive(|c| is_guarded(c)).collect_vec();
+                let mut current_node = None;
+                if candidates.last().is_none_or(|c| is_guarded(c)) {
+                    // If no arm is available, report a non-exhaustive match error, with all the
+                    // patterns not covered by the arms. The guarded arms are not considered, as
+                    // their guards may not hold.
+                    current_node = Some(*non_exhaustive_node.get_or_insert_with(|| {
+                        let missing_patterns = usefulness::missing_patterns(
+                            ctx.db,
+                            &ctx.function_body.arenas.patterns,
+                            matched_expr.ty(),
+                            pattern_and_nodes
+                                .iter()
+                                .filter(|(_, _, guard)| guard.is_none())
+                                .map(|(pattern, _, _)| *pattern),
+                        )
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|pattern| pattern.format(ctx.db))
+                        .collect_vec();
+                        let kind = LoweringDiagnosticKind::MatchError(MatchError {
+                            kind: MatchKind::Match,
+                            error: MatchDiagnostic::NonExhaustiveMatch(
+                                if missing_patterns.is_empty() {
+                                    vec![path.clone()]
+                                } else {
+                                    missing_patterns
+                                },
+                            ),
+                        });
+                        graph.report_with_missing_node(expr.stable_ptr.untyped(), kind)
+                    }));
+                }
 
-                cache.get_or_compute(
-                    &mut |graph, index_and_bindings: IndexAndBindings, _path| {
-                        let index = index_and_bindings.index();
-                        index_and_bindings.wrap_node(graph, pattern_and_nodes[index].1)
-                    },
-                    graph,
-                    index_and_bindings,
-                    path,
-                )
+                // Chain the candidates in reverse order, where an arm with a guard continues to
+                // the next candidate if its guard does not hold.
+                for index_and_bindings in candidates.into_iter().rev() {
+                    current_node = Some(cache.get_or_compute(
+                        &mut |graph,
+                              (index_and_bindings, next): (IndexAndBindings, Option<NodeId>),
+                              _path| {
+                            let (_, arm_node, guard) =
+                                pattern_and_nodes[index_and_bindings.index()];
+                            let node = match (guard, next) {
+                                (Some(guard), Some(next)) => {
+                                    create_guard_node(ctx, graph, guard, arm_node, next)
+                                }
+                                _ => arm_node,
+                            };
+                            index_and_bindings.wrap_node(graph, node)
+                        },
+                        graph,
+                        (index_and_bindings.clone(), current_node),
+                        path.clone(),
+                    ));
+                }
+                current_node.unwrap()
             },
             location: matched_expr_location,
         },
@@ -177,6 +231,64 @@ pub fn create_graph_expr_match<'db>(
     graph.finalize(root, ctx)
 }
 
+/// Reports an error if the guard of a match arm uses a non-copyable variable bound by the patterns
+/// of the arm, and returns a missing node for the arm.
+///
+/// Such a variable may be moved or destructured by the guard, while the matched value is still
+/// needed by the following arms if the guard does not hold.
+fn guard_error_node<'db>(
+    ctx: &LoweringContext<'db, '_>,
+    graph: &mut FlowControlGraphBuilder<'db>,
+    match_arm: &semantic::MatchArm,
+    guard: ExprId,
+) -> Option<NodeId> {
+    let arenas = &ctx.function_body.arenas;
+    let usage = Usages::expr_usage(arenas, guard);
+    let mut used_paths =
+        chain!(usage.usage.values(), usage.snap_usage.values(), usage.changes.values());
+    let (variable, path) = used_paths.find_map(|path| {
+        let variable = match_arm
+            .patterns
+            .iter()
+            .flat_map(|pattern| arenas.patterns[*pattern].variables(&arenas.patterns))
+            .find(|variable| semantic::VarId::Local(variable.var.id) == path.base_var())?;
+        ctx.db.copyable(variable.var.ty).is_err().then_some((variable, path))
+    })?;
+    Some(graph.report_with_missing_node(
+        path.stable_ptr(),
+        LoweringDiagnosticKind::MatchError(MatchError {
+            kind: MatchKind::Match,
+            error: MatchDiagnostic::NonCopyableVariableInGuard(
+                variable.name.long(ctx.db).to_string(),
+            ),
+        }),
+    ))
+}
+
+/// Creates the nodes for evaluating the guard of a match arm, continuing to `arm_node` if the
+/// guard holds, and to `next` otherwise.
+fn create_guard_node<'db>(
+    ctx: &LoweringContext<'db, '_>,
+    graph: &mut FlowControlGraphBuilder<'db>,
+    guard: ExprId,
+    arm_node: NodeId,
+    next: NodeId,
+) -> NodeId {
+    let guard_expr = &ctx.function_body.arenas.exprs[guard];
+    let guard_var =
+        graph.new_var(guard_expr.ty(), ctx.get_location(guard_expr.stable_ptr().untyped()));
+    let if_node = graph.add_node(FlowControlNode::BooleanIf(BooleanIf {
+        condition_var: guard_var,
+        true_branch: arm_node,
+        false_branch: next,
+    }));
+    graph.add_node(FlowControlNode::EvaluateExpr(EvaluateExpr {
+        expr: guard,
+        var_id: guard_var,
+        next: if_node,
+    }))
+}
+
 /// Creates a graph node for a let-else statement.
 ///
 /// See [crate::lower::lower_let_else::lower_let_else] for more details.
@@ -88,6 +88,11 @@ impl FilteredPatterns {
     pub fn indices<'a>(&'a self) -> impl Iterator<Item = usize> + 'a {
         self.filter.iter().map(|index_and_bindings| index_and_bindings.index)
     }
+
+    /// Returns an iterator over the patterns accepted by the filter, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &IndexAndBindings> {
+        self.filter.iter()
+    }
 }
 
 /// Represents an item in the filter.
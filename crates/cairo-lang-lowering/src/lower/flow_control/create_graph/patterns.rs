@@ -1,5 +1,7 @@
+use std::collections::BTreeSet;
+
 use cairo_lang_debug::DebugWithDb;
-use cairo_lang_defs::ids::NamedLanguageElementId;
+use cairo_lang_defs::ids::{LanguageElementId, NamedLanguageElementId};
 use cairo_lang_diagnostics::{DiagnosticNote, Maybe};
 use cairo_lang_filesystem::flag::FlagsGroup;
 use cairo_lang_semantic::corelib::{CorelibSemantic, validate_literal};
@@ -14,6 +16,7 @@ use cairo_lang_semantic::{
 use cairo_lang_syntax::node::TypedStablePtr;
 use cairo_lang_syntax::node::ast::ExprPtr;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::try_extract_matches;
 use itertools::{Itertools, zip_eq};
 use num_bigint::BigInt;
 use salsa::Database;
@@ -224,6 +227,7 @@ fn create_node_for_enum<'db>(
             Some(
                 pattern @ (semantic::Pattern::StringLiteral(..)
                 | semantic::Pattern::Literal(..)
+                | semantic::Pattern::Range(..)
                 | semantic::Pattern::Struct(..)
                 | semantic::Pattern::Tuple(..)
                 | semantic::Pattern::FixedSizeArray(..)
@@ -429,10 +433,16 @@ fn create_node_for_tuple_inner<'db>(
                     patterns_on_current_item.push(Some(inner_pattern))
                 }
             }
+            Some(semantic::Pattern::Range(pattern_range))
+                if pattern_range.start.ty == ctx.db.core_info().u256 =>
+            {
+                return report_unsupported_range_pattern(ctx, graph, pattern_range);
+            }
             Some(
                 pattern @ (semantic::Pattern::StringLiteral(..)
                 | semantic::Pattern::EnumVariant(..)
                 | semantic::Pattern::Literal(..)
+                | semantic::Pattern::Range(..)
                 | semantic::Pattern::Tuple(..)
                 | semantic::Pattern::Struct(..)
                 | semantic::Pattern::FixedSizeArray(..)
@@ -552,6 +562,9 @@ fn create_node_for_value<'db>(
     params: CreateNodeParams<'db, '_, '_>,
     input_var: FlowControlVar,
 ) -> NodeId {
+    if params.patterns.iter().any(|pattern| matches!(pattern, Some(semantic::Pattern::Range(..)))) {
+        return create_node_for_ranges(params, input_var);
+    }
     let CreateNodeParams { ctx, graph, patterns, build_node_callback, location: _ } = params;
     let var_ty = graph.var_ty(input_var);
 
@@ -582,7 +595,7 @@ fn create_node_for_value<'db>(
                     filter.add(pattern_index);
                 }
             }
-            Some(semantic::Pattern::Variable(..)) => unreachable!(),
+            Some(semantic::Pattern::Variable(..) | semantic::Pattern::Range(..)) => unreachable!(),
             Some(
                 pattern @ (semantic::Pattern::StringLiteral(..)
                 | semantic::Pattern::EnumVariant(..)
@@ -671,6 +684,142 @@ fn create_node_for_value<'db>(
     current_node
 }
 
+/// Creates a node for matching over numeric values, where some of the patterns are ranges.
+///
+/// The values of the type are split into segments, such that all the values of a segment are
+/// accepted by the same patterns, and a chain of [Downcast] nodes dispatches each segment to its
+/// node.
+fn create_node_for_ranges<'db>(
+    params: CreateNodeParams<'db, '_, '_>,
+    input_var: FlowControlVar,
+) -> NodeId {
+    let CreateNodeParams { ctx, graph, patterns, build_node_callback, location: _ } = params;
+    let var_ty = graph.var_ty(input_var);
+
+    let Some((min, max)) = corelib::bounded_integer_type_range(ctx.db, var_ty) else {
+        let pattern_range = patterns
+            .iter()
+            .find_map(|pattern| try_extract_matches!((*pattern)?, semantic::Pattern::Range))
+            .unwrap();
+        return report_unsupported_range_pattern(ctx, graph, pattern_range);
+    };
+
+    // The inclusive range of values accepted by each valid pattern, where `None` accepts any value.
+    let mut pattern_ranges = Vec::<(usize, Option<(BigInt, BigInt)>)>::default();
+    for (pattern_index, pattern) in patterns.iter().enumerate() {
+        let (literals, range) = match pattern {
+            Some(semantic::Pattern::Literal(semantic::PatternLiteral { literal, .. })) => {
+                (vec![literal], (literal.value.clone(), literal.value.clone()))
+            }
+            Some(semantic::Pattern::Range(pattern_range)) => (
+                vec![&pattern_range.start, &pattern_range.end],
+                (pattern_range.start.value.clone(), pattern_range.last()),
+            ),
+            Some(semantic::Pattern::Otherwise(_)) | None => {
+                pattern_ranges.push((pattern_index, None));
+                continue;
+            }
+            Some(semantic::Pattern::Variable(..)) => unreachable!(),
+            Some(
+                pattern @ (semantic::Pattern::StringLiteral(..)
+                | semantic::Pattern::EnumVariant(..)
+                | semantic::Pattern::Struct(..)
+                | semantic::Pattern::Tuple(..)
+                | semantic::Pattern::FixedSizeArray(..)
+                | semantic::Pattern::Missing(..)),
+            ) => {
+                // This should not be reachable without getting a semantic error.
+                return graph.report_with_missing_node(
+                    pattern.stable_ptr().untyped(),
+                    LoweringDiagnosticKind::UnexpectedError,
+                );
+            }
+        };
+        let mut is_valid = true;
+        for literal in literals {
+            if let Err(err) = validate_literal(ctx.db, var_ty, &literal.value) {
+                graph.report(literal.stable_ptr, LoweringDiagnosticKind::LiteralError(err));
+                is_valid = false;
+            }
+        }
+        if is_valid {
+            pattern_ranges.push((pattern_index, Some(range)));
+        }
+    }
+
+    // The first value of each segment, followed by the value after the last segment.
+    let mut boundaries = BTreeSet::from([min, max + 1]);
+    for (_, range) in &pattern_ranges {
+        if let Some((start, last)) = range {
+            boundaries.insert(start.clone());
+            boundaries.insert(last + 1);
+        }
+    }
+
+    // Build a node for each segment, merging adjacent segments that lead to the same node.
+    let mut segments = Vec::<(BigInt, BigInt, NodeId)>::default();
+    for (start, next) in boundaries.into_iter().tuple_windows() {
+        let last = next - 1;
+        let mut filter = FilteredPatterns::default();
+        for (pattern_index, range) in &pattern_ranges {
+            // A pattern either accepts all the values of the segment or none of them.
+            if range.as_ref().is_none_or(|(range_start, _)| *range_start <= start)
+                && range.as_ref().is_none_or(|(_, range_last)| last <= *range_last)
+            {
+                filter.add(*pattern_index);
+            }
+        }
+        let path = if start == last { start.to_string() } else { format!("{start}..={last}") };
+        let node = build_node_callback(graph, filter, path);
+        match segments.last_mut() {
+            Some((_, segment_last, segment_node)) if *segment_node == node => *segment_last = last,
+            _ => segments.push((start, last, node)),
+        }
+    }
+
+    // The most common node is reached if the value is in none of the other segments.
+    let base_node = segments
+        .iter()
+        .map(|(_, _, node)| *node)
+        .counts()
+        .into_iter()
+        .max_by_key(|(node, count)| (*count, node.0))
+        .unwrap()
+        .0;
+    let mut current_node = base_node;
+    for (start, last, node) in segments.into_iter().rev() {
+        if node == base_node {
+            continue;
+        }
+        let segment_ty = corelib::bounded_int_ty(ctx.db, start, last);
+        let segment_var = graph.new_var(segment_ty, graph.var_location(input_var));
+        current_node = graph.add_node(FlowControlNode::Downcast(Downcast {
+            input: input_var,
+            output: segment_var,
+            in_range: node,
+            out_of_range: current_node,
+        }));
+    }
+    current_node
+}
+
+/// Reports that range patterns are not supported for the type of the given range pattern.
+fn report_unsupported_range_pattern<'db>(
+    ctx: &LoweringContext<'db, '_>,
+    graph: &mut FlowControlGraphBuilder<'db>,
+    pattern_range: &semantic::PatternRange<'db>,
+) -> NodeId {
+    graph.report_with_missing_node(
+        pattern_range.stable_ptr.untyped(),
+        LoweringDiagnosticKind::MatchError(MatchError {
+            kind: graph.kind(),
+            error: MatchDiagnostic::UnsupportedRangePatternType(
+                pattern_range.start.ty.format(ctx.db),
+            ),
+        }),
+    )
+}
+
 /// Checks if the optimization should be applied and [FlowControlNode::ValueMatch] should be used.
 /// If so, returns the number of consecutive literals 0, 1, 2, ... that are present
 /// (this is equal to the first missing value).
@@ -702,7 +851,8 @@ pub fn numeric_match_optimization_threshold<'db>(
     // jump table for small_types the number of steps with if-else is 2 * min(n, number_of_arms) + 4
     // and 9~12 for jump table.
     let default_threshold = if is_small_type { 8 } else { 10 };
-    ctx.db.flag_numeric_match_optimization_min_arms_threshold().unwrap_or(default_threshold)
+    let crate_id = ctx.semantic_function_id.parent_module(ctx.db).owning_crate(ctx.db);
+    ctx.db.flag_numeric_match_optimization_min_arms_threshold(crate_id).unwrap_or(default_threshold)
 }
 
 /// Returns `true` if the pattern accepts any value (`_` or a variable name).
@@ -710,6 +860,7 @@ fn pattern_is_any<'a, 'db>(pattern: &'a semantic::Pattern<'db>) -> bool {
     match pattern {
         semantic::Pattern::Otherwise(..) | semantic::Pattern::Variable(..) => true,
         semantic::Pattern::Literal(..)
+        | semantic::Pattern::Range(..)
         | semantic::Pattern::StringLiteral(..)
         | semantic::Pattern::Struct(..)
         | semantic::Pattern::Tuple(..)
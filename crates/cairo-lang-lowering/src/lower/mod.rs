@@ -36,7 +36,8 @@ use num_traits::ToPrimitive;
 use refs::ClosureInfo;
 use salsa::Database;
 use semantic::corelib::{
-    core_submodule, get_core_function_id, get_core_ty_by_name, get_function_id, never_ty, unit_ty,
+    core_box_ty, core_submodule, get_core_function_id, get_core_ty_by_name, get_function_id,
+    get_usize_ty, never_ty, unit_ty,
 };
 use semantic::items::constant::ConstValue;
 use semantic::types::wrap_in_snapshots;
@@ -54,6 +55,7 @@ use self::context::{
 use self::external::{extern_facade_expr, extern_facade_return_tys};
 use self::logical_op::lower_logical_op;
 use crate::blocks::Blocks;
+use crate::db::LoweringGroup;
 use crate::diagnostic::LoweringDiagnosticKind::{self, *};
 use crate::diagnostic::LoweringDiagnosticsBuilder;
 use crate::ids::{
@@ -64,7 +66,8 @@ use crate::ids::{
 use crate::lower::context::{LoopContext, LoopEarlyReturnInfo, LoweringResult, RefArg, VarRequest};
 use crate::lower::generators::StructDestructure;
 use crate::{
-    BlockId, Lowered, MatchArm, MatchEnumInfo, MatchExternInfo, MatchInfo, VarUsage, VariableId,
+    BlockId, Lowered, MatchArm, MatchEnumInfo, MatchExternInfo, MatchInfo, Statement,
+    StatementConst, VarUsage, VariableId,
 };
 
 mod block_builder;
@@ -722,6 +725,7 @@ fn lower_single_pattern<'db>(
     let pattern = &ctx.function_body.arenas.patterns[pattern_id];
     match pattern {
         semantic::Pattern::Literal(_)
+        | semantic::Pattern::Range(_)
         | semantic::Pattern::StringLiteral(_)
         | semantic::Pattern::EnumVariant(_) => {
             return Err(LoweringFlowError::Failed(
@@ -743,6 +747,33 @@ fn lower_single_pattern<'db>(
             // TODO(spapini): Build semantic_defs in semantic model.
             ctx.semantic_defs.insert(sem_var.id(), sem_var);
         }
+        semantic::Pattern::Struct(structure)
+            if structure.wrapping_info.n_outer_snapshots == 0
+                && structure.wrapping_info.n_boxed_inner_snapshots.is_none()
+                && matches!(lowered_expr, LoweredExpr::MemberPath(..))
+                && is_partial_struct_pattern(ctx, structure) =>
+        {
+            // Partially destructuring a member path moves only the bound members out of it, so the
+            // rest of it may still be used.
+            // Snapshot patterns are destructured as a whole below, as taking their members never
+            // moves out of the original value, and boxed patterns consume the entire box anyway.
+            let LoweredExpr::MemberPath(path, location) = lowered_expr else { unreachable!() };
+            for (member_pattern, member) in structure.field_patterns.clone() {
+                let member_path = ExprVarMemberPath::Member {
+                    parent: Box::new(path.clone()),
+                    member_id: member.id,
+                    stable_ptr: path.stable_ptr(),
+                    concrete_struct_id: structure.concrete_struct_id,
+                    ty: member.ty,
+                };
+                lower_single_pattern(
+                    ctx,
+                    builder,
+                    member_pattern,
+                    LoweredExpr::MemberPath(member_path, location),
+                )?;
+            }
+        }
         semantic::Pattern::Struct(structure) => {
             let members = ctx
                 .db
@@ -810,6 +841,29 @@ fn lower_single_pattern<'db>(
     Ok(())
 }
 
+/// Returns whether a struct pattern binds some of the struct members, but leaves others unbound
+/// using `..`, either directly or in a nested struct pattern.
+/// Patterns binding no members at all (e.g. `let S { .. } = s;`) are not partial, as these are used
+/// to consume the entire value.
+fn is_partial_struct_pattern<'db>(
+    ctx: &LoweringContext<'db, '_>,
+    structure: &semantic::PatternStruct<'db>,
+) -> bool {
+    if structure.field_patterns.is_empty() {
+        return false;
+    }
+    let Ok(members) = ctx.db.concrete_struct_members(structure.concrete_struct_id) else {
+        return false;
+    };
+    structure.field_patterns.len() < members.len()
+        || structure.field_patterns.iter().any(|(pattern, _)| {
+            matches!(
+                &ctx.function_body.arenas.patterns[*pattern],
+                semantic::Pattern::Struct(inner) if is_partial_struct_pattern(ctx, inner)
+            )
+        })
+}
+
 /// A helper function to handle patterns of tuples or fixed size arrays.
 fn lower_tuple_like_pattern_helper<'db>(
     ctx: &mut LoweringContext<'db, '_>,
@@ -992,17 +1046,23 @@ fn lower_expr_string_literal<'db>(
     .intern(db);
 
     // Emit lowering statements to build the ByteArray struct components.
-    let mut data_array_usage =
-        build_empty_data_array(ctx, builder, expr, data_array_new_function, data_array_ty);
-    let remainder = add_chunks_to_data_array(
-        ctx,
-        builder,
-        expr,
-        bytes31_ty,
-        &mut data_array_usage,
-        data_array_append_function,
-        data_array_ty,
-    );
+    let (data_array_usage, remainder) =
+        if db.optimizations().pool_string_literals() && expr.value.len() >= 31 {
+            build_pooled_data_array(ctx, builder, expr, bytes31_ty, data_array_ty)
+        } else {
+            let mut data_array_usage =
+                build_empty_data_array(ctx, builder, expr, data_array_new_function, data_array_ty);
+            let remainder = add_chunks_to_data_array(
+                ctx,
+                builder,
+                expr,
+                bytes31_ty,
+                &mut data_array_usage,
+                data_array_append_function,
+                data_array_ty,
+            );
+            (data_array_usage, remainder)
+        };
     let (pending_word_usage, pending_word_len_usage) =
         add_pending_word(ctx, builder, expr, remainder);
 
@@ -1074,6 +1134,55 @@ fn add_chunks_to_data_array<'db, 'r>(
     remainder
 }
 
+/// Emits lowering statements to build the data array from a boxed constant of the full 31-byte
+/// words of the string literal.
+/// Identical boxed constants share their storage, so repeated literals do not duplicate their data.
+/// Returns the data array and the remaining bytes, for the pending word.
+fn build_pooled_data_array<'db, 'r>(
+    ctx: &mut LoweringContext<'db, '_>,
+    builder: &mut BlockBuilder<'db>,
+    expr: &'r semantic::ExprStringLiteral<'db>,
+    bytes31_ty: semantic::TypeId<'db>,
+    data_array_ty: semantic::TypeId<'db>,
+) -> (VarUsage<'db>, &'r [u8]) {
+    let db = ctx.db;
+    let location = ctx.get_location(expr.stable_ptr.untyped());
+
+    let chunks = expr.value.as_bytes().chunks_exact(31);
+    let remainder = chunks.remainder();
+    let words = chunks
+        .map(|chunk| {
+            ConstValue::Int(BigInt::from_bytes_be(Sign::Plus, chunk), bytes31_ty).intern(db)
+        })
+        .collect_vec();
+    let size = ConstValue::Int(words.len().into(), get_usize_ty(db)).intern(db);
+    let words_ty = TypeLongId::FixedSizeArray { type_id: bytes31_ty, size }.intern(db);
+    let boxed_words = ctx.new_var(VarRequest { ty: core_box_ty(db, words_ty), location });
+    builder.statements.push_statement(Statement::Const(StatementConst::new_boxed(
+        ConstValue::Struct(words, words_ty).intern(db),
+        boxed_words,
+    )));
+
+    let string_literal_data_function = FunctionLongId::Semantic(get_function_id(
+        db,
+        core_submodule(db, SmolStrId::from(db, "byte_array")),
+        SmolStrId::from(db, "string_literal_data"),
+        vec![GenericArgumentId::Constant(size)],
+    ))
+    .intern(db);
+    let data_array_usage = generators::Call {
+        function: string_literal_data_function,
+        inputs: vec![VarUsage { var_id: boxed_words, location }],
+        coupon_input: None,
+        extra_ret_tys: vec![],
+        ret_tys: vec![data_array_ty],
+        location,
+    }
+    .add(ctx, &mut builder.statements)
+    .returns[0];
+    (data_array_usage, remainder)
+}
+
 /// Emits lowering statements to set variables for the pending word of the
 /// ByteArray.
 fn add_pending_word<'db>(
@@ -1853,40 +1962,24 @@ fn lower_expr_struct_ctor<'db>(
         }));
     if members.len() != member_expr_usages.len() {
         // Semantic model should have made sure base struct exist if some members are missing.
-        let base_struct = lower_expr(ctx, builder, expr.base_struct.unwrap())?;
-        if let LoweredExpr::MemberPath(path, location) = base_struct {
-            for (_, member) in members.iter() {
-                let Entry::Vacant(entry) = member_expr_usages.entry(member.id) else {
-                    continue;
-                };
-                let member_path = ExprVarMemberPath::Member {
-                    parent: Box::new(path.clone()),
-                    member_id: member.id,
-                    stable_ptr: path.stable_ptr(),
-                    concrete_struct_id: expr.concrete_struct_id,
-                    ty: member.ty,
-                };
-                entry
-                    .insert(Ok(LoweredExpr::MemberPath(member_path, location)
-                        .as_var_usage(ctx, builder)?));
+        // Member path base structs are already taken member-wise by the semantic model, so the
+        // base struct is destructured here.
+        let base_struct = lower_expr_to_var_usage(ctx, builder, expr.base_struct.unwrap())?;
+        for (base_member, (_, member)) in izip!(
+            StructDestructure {
+                input: base_struct,
+                var_reqs: members
+                    .iter()
+                    .map(|(_, member)| VarRequest { ty: member.ty, location })
+                    .collect(),
             }
-        } else {
-            for (base_member, (_, member)) in izip!(
-                StructDestructure {
-                    input: base_struct.as_var_usage(ctx, builder)?,
-                    var_reqs: members
-                        .iter()
-                        .map(|(_, member)| VarRequest { ty: member.ty, location })
-                        .collect(),
-                }
-                .add(ctx, &mut builder.statements),
-                members.iter()
-            ) {
-                match member_expr_usages.entry(member.id) {
-                    Entry::Occupied(_) => {}
-                    Entry::Vacant(entry) => {
-                        entry.insert(Ok(VarUsage { var_id: base_member, location }));
-                    }
+            .add(ctx, &mut builder.statements),
+            members.iter()
+        ) {
+            match member_expr_usages.entry(member.id) {
+                Entry::Occupied(_) => {}
+                Entry::Vacant(entry) => {
+                    entry.insert(Ok(VarUsage { var_id: base_member, location }));
                 }
             }
         }
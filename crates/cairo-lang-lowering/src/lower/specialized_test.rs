@@ -34,6 +34,7 @@ fn test_specialized_function(
             moveable_functions: vec![],
             inlining_strategy: InliningStrategy::InlineSmallFunctions(0),
             skip_const_folding: false,
+            pool_string_literals: false,
         },
     )));
     let (test_function, semantic_diagnostics) = setup_test_function(db, inputs).split();
@@ -1,6 +1,7 @@
 use std::ops::{Index, IndexMut};
 
 use cairo_lang_diagnostics::{DiagnosticAdded, Maybe};
+use cairo_lang_utils::dense_map::DenseKey;
 use cairo_lang_utils::require;
 
 use crate::analysis::StatementLocation;
@@ -21,6 +22,11 @@ impl BlockId {
         BlockId(self.0 + 1)
     }
 }
+impl DenseKey for BlockId {
+    fn index(self) -> usize {
+        self.0
+    }
+}
 impl core::fmt::Debug for BlockId {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "blk{}", self.0)
@@ -23,6 +23,9 @@ pub struct OptimizationConfig {
     pub(crate) inlining_strategy: InliningStrategy,
     /// Should const folding be skipped.
     pub(crate) skip_const_folding: bool,
+    /// Should the full words of string literals be lowered from boxed constants, so that
+    /// identical literals share the storage of their words.
+    pub(crate) pool_string_literals: bool,
 }
 
 impl OptimizationConfig {
@@ -40,6 +43,7 @@ impl Optimizations {
             moveable_functions: default_moveable_functions(),
             inlining_strategy,
             skip_const_folding: false,
+            pool_string_literals: false,
         })
     }
 
@@ -50,9 +54,19 @@ impl Optimizations {
             moveable_functions: vec!["felt252_sub".to_string()],
             inlining_strategy: Default::default(),
             skip_const_folding: false,
+            pool_string_literals: false,
         })
     }
 
+    /// Returns the optimizations with the pooling of string literals set to the given value.
+    /// Has no effect if `self` is [`Optimizations::Disabled`].
+    pub fn with_pool_string_literals(mut self, pool_string_literals: bool) -> Self {
+        if let Self::Enabled(config) = &mut self {
+            config.pool_string_literals = pool_string_literals;
+        }
+        self
+    }
+
     /// A slice of function names that can be moved during the reorder_statements optimization.
     /// If `self` is [`Optimizations::Disabled`] returns an empty slice.
     pub fn moveable_functions(&self) -> &[String] {
@@ -73,6 +87,11 @@ impl Optimizations {
     pub fn skip_const_folding(&self) -> bool {
         if let Self::Enabled(config) = self { config.skip_const_folding } else { true }
     }
+
+    /// Whether to pool string literals. If `self` is [`Optimizations::Disabled`] returns `false`.
+    pub fn pool_string_literals(&self) -> bool {
+        if let Self::Enabled(config) = self { config.pool_string_literals } else { false }
+    }
 }
 
 #[salsa::tracked(returns(ref))]
@@ -9,6 +9,7 @@ use cairo_lang_semantic::{ConcreteVariant, TypeId};
 use cairo_lang_utils::unordered_hash_map::{Entry, UnorderedHashMap};
 use itertools::Itertools;
 
+use crate::analysis::BlockOrder;
 use crate::ids::FunctionId;
 use crate::optimizations::var_renamer::VarRenamer;
 use crate::utils::RebuilderEx;
@@ -128,7 +129,7 @@ impl<'db> CseContext<'db> {
 /// This optimization identifies identical expressions and replaces redundant computations
 /// with references to the first computed result.
 ///
-/// Blocks must be ordered topologically for the execution to be valid.
+/// Blocks unreachable from the root are left unchanged.
 pub fn cse<'db>(lowered: &mut Lowered<'db>) {
     if lowered.blocks.is_empty() {
         return;
@@ -136,7 +137,7 @@ pub fn cse<'db>(lowered: &mut Lowered<'db>) {
     let mut ctx = CseContext::new(&lowered.variables);
     let mut block_expression_map = UnorderedHashMap::<BlockId, _>::default();
     block_expression_map.insert(BlockId::root(), Default::default());
-    for block_id in (0..lowered.blocks.len()).map(BlockId) {
+    for block_id in BlockOrder::new(lowered).reverse_postorder() {
         let block = &mut lowered.blocks[block_id];
         ctx.expression_map = block_expression_map
             .remove(&block_id)
@@ -4,14 +4,16 @@ mod test;
 
 use std::collections::HashSet;
 
-use cairo_lang_defs::ids::ExternFunctionId;
+use cairo_lang_defs::ids::{ExternFunctionId, LanguageElementId};
 use cairo_lang_filesystem::flag::FlagsGroup;
 use cairo_lang_semantic::helper::ModuleHelper;
+use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
+use cairo_lang_syntax::attribute::consts::NO_EARLY_UNSAFE_PANIC_ATTR;
 use salsa::Database;
 
 use crate::analysis::core::StatementLocation;
 use crate::analysis::{DataflowAnalyzer, DataflowBackAnalysis, Direction, Edge};
-use crate::ids::{LocationId, SemanticFunctionIdEx};
+use crate::ids::{ConcreteFunctionWithBodyId, LocationId, SemanticFunctionIdEx};
 use crate::{
     Block, BlockEnd, BlockId, Lowered, MatchExternInfo, MatchInfo, Statement, StatementCall,
 };
@@ -21,8 +23,21 @@ use crate::{
 ///
 /// This step might replace a match on an empty enum with a call to unsafe_panic and we rely on the
 /// 'trim_unreachable' optimization to clean that up.
-pub fn early_unsafe_panic<'db>(db: &'db dyn Database, lowered: &mut Lowered<'db>) {
-    if !db.flag_unsafe_panic() || lowered.blocks.is_empty() {
+///
+/// Applied only if the `unsafe_panic` flag is set, and neither the crate of the function nor the
+/// function itself opted out (by the crate settings or the `#[no_early_unsafe_panic]` attribute).
+pub fn early_unsafe_panic<'db>(
+    db: &'db dyn Database,
+    function: ConcreteFunctionWithBodyId<'db>,
+    lowered: &mut Lowered<'db>,
+) {
+    if lowered.blocks.is_empty() {
+        return;
+    }
+    let semantic_function = function.base_semantic_function(db).function_with_body_id(db);
+    if !db.flag_early_unsafe_panic(semantic_function.parent_module(db).owning_crate(db))
+        || semantic_function.has_attr(db, NO_EARLY_UNSAFE_PANIC_ATTR).unwrap_or_default()
+    {
         return;
     }
 
@@ -1,6 +1,5 @@
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_filesystem::flag::{Flag, FlagsGroup};
-use cairo_lang_filesystem::ids::FlagLongId;
 use cairo_lang_semantic::test_utils::setup_test_function;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
@@ -26,8 +25,7 @@ fn test_early_unsafe_panic(
     _args: &OrderedHashMap<String, String>,
 ) -> TestRunnerResult {
     let db = &mut LoweringDatabaseForTesting::new();
-    let unsafe_panic_flag_id = FlagLongId(Flag::UNSAFE_PANIC.into());
-    db.set_flag(unsafe_panic_flag_id, Some(Flag::UnsafePanic(true)));
+    db.set_flag(Flag::UnsafePanic(true));
     let (test_function, semantic_diagnostics) = setup_test_function(db, inputs).split();
 
     let function_id =
@@ -63,6 +63,32 @@ pub enum OptimizationPhase<'db> {
 }
 
 impl<'db> OptimizationPhase<'db> {
+    /// Returns the name of the phase, e.g. `ApplyInlining` for
+    /// `ApplyInlining { enable_const_folding: true }`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OptimizationPhase::ApplyInlining { .. } => "ApplyInlining",
+            OptimizationPhase::BranchInversion => "BranchInversion",
+            OptimizationPhase::CancelOps => "CancelOps",
+            OptimizationPhase::ConstFolding => "ConstFolding",
+            OptimizationPhase::Cse => "Cse",
+            OptimizationPhase::DedupBlocks => "DedupBlocks",
+            OptimizationPhase::EarlyUnsafePanic => "EarlyUnsafePanic",
+            OptimizationPhase::OptimizeMatches => "OptimizeMatches",
+            OptimizationPhase::OptimizeRemappings => "OptimizeRemappings",
+            OptimizationPhase::Reboxing => "Reboxing",
+            OptimizationPhase::ReorderStatements => "ReorderStatements",
+            OptimizationPhase::ReorganizeBlocks => "ReorganizeBlocks",
+            OptimizationPhase::ReturnOptimization => "ReturnOptimization",
+            OptimizationPhase::SplitStructs => "SplitStructs",
+            OptimizationPhase::TrimUnreachable => "TrimUnreachable",
+            OptimizationPhase::GasRedeposit => "GasRedeposit",
+            OptimizationPhase::LowerImplicits => "LowerImplicits",
+            OptimizationPhase::Validate => "Validate",
+            OptimizationPhase::SubStrategy { .. } => "SubStrategy",
+        }
+    }
+
     /// Applies the optimization phase to the lowering.
     ///
     /// Assumes `lowered` is a lowering of `function`.
@@ -82,7 +108,7 @@ impl<'db> OptimizationPhase<'db> {
             OptimizationPhase::CancelOps => cancel_ops(lowered),
             OptimizationPhase::ConstFolding => const_folding(db, function, lowered),
             OptimizationPhase::Cse => cse(lowered),
-            OptimizationPhase::EarlyUnsafePanic => early_unsafe_panic(db, lowered),
+            OptimizationPhase::EarlyUnsafePanic => early_unsafe_panic(db, function, lowered),
             OptimizationPhase::DedupBlocks => dedup_blocks(lowered),
             OptimizationPhase::OptimizeMatches => optimize_matches(lowered),
             OptimizationPhase::OptimizeRemappings => optimize_remappings(lowered),
@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 
 use assert_matches::assert_matches;
+use cairo_lang_defs::ids::LanguageElementId;
 use cairo_lang_diagnostics::Maybe;
 use cairo_lang_filesystem::flag::FlagsGroup;
 use cairo_lang_filesystem::ids::SmolStrId;
@@ -44,25 +45,27 @@ pub fn lower_panics<'db>(
         return Ok(());
     }
 
-    let opt_trace_fn = if db.flag_panic_backtrace() {
-        Some(
-            ModuleHelper::core(db)
-                .submodule("internal")
-                .function_id(
-                    "trace",
-                    vec![GenericArgumentId::Constant(
-                        ConstValue::Int(
-                            0x70616e6963u64.into(), // 'panic' as numeric.
-                            db.core_info().felt252,
-                        )
-                        .intern(db),
-                    )],
-                )
-                .lowered(db),
-        )
-    } else {
-        None
-    };
+    let semantic_function = function_id.base_semantic_function(db).function_with_body_id(db);
+    let opt_trace_fn =
+        if db.flag_panic_backtrace(semantic_function.parent_module(db).owning_crate(db)) {
+            Some(
+                ModuleHelper::core(db)
+                    .submodule("internal")
+                    .function_id(
+                        "trace",
+                        vec![GenericArgumentId::Constant(
+                            ConstValue::Int(
+                                0x70616e6963u64.into(), // 'panic' as numeric.
+                                db.core_info().felt252,
+                            )
+                            .intern(db),
+                        )],
+                    )
+                    .lowered(db),
+            )
+        } else {
+            None
+        };
 
     if db.flag_unsafe_panic() {
         lower_unsafe_panic(db, lowered, opt_trace_fn);
@@ -72,12 +75,9 @@ pub fn lower_panics<'db>(
     let signature = function_id.signature(db)?;
     // TODO(orizi): Validate all signature types are fully concrete at this point.
     let panic_info = PanicSignatureInfo::new(db, &signature);
-    let variables = VariableAllocator::new(
-        db,
-        function_id.base_semantic_function(db).function_with_body_id(db),
-        std::mem::take(&mut lowered.variables),
-    )
-    .unwrap();
+    let variables =
+        VariableAllocator::new(db, semantic_function, std::mem::take(&mut lowered.variables))
+            .unwrap();
     let mut ctx = PanicLoweringContext {
         variables,
         block_queue: VecDeque::from_iter(lowered.blocks.get().iter().cloned()),
@@ -0,0 +1,94 @@
+//! Lowering plugins - external transformations of the lowered representation of functions.
+
+use std::sync::Arc;
+
+use cairo_lang_diagnostics::Maybe;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use salsa::Database;
+use thiserror::Error;
+
+use crate::db::LoweringGroup;
+use crate::ids::ConcreteFunctionWithBodyId;
+use crate::optimizations::validate::validate;
+use crate::{Lowered, LoweringStage};
+
+#[cfg(test)]
+#[path = "plugin_test.rs"]
+mod test;
+
+/// A trait for a lowering plugin: an external transformation applied to the lowering of every
+/// function with a body, right after the lowering reaches the stage of the plugin.
+///
+/// Allows adding domain specific optimizations without changing the optimization pipeline.
+pub trait LoweringPlugin: std::fmt::Debug + Sync + Send {
+    /// The name of the plugin. Must be unique among the registered lowering plugins.
+    fn name(&self) -> &str;
+
+    /// The stage after which the plugin is applied. The result of the plugin is the lowering of
+    /// the function at that stage, so it is also the input of the following stages.
+    fn stage(&self) -> LoweringStage;
+
+    /// Applies the transformation to the lowering of `function`.
+    ///
+    /// The transformation must keep the lowering valid, and must not change the signature or the
+    /// parameters of the function, as its callers are lowered according to them.
+    fn apply<'db>(
+        &self,
+        db: &'db dyn Database,
+        function: ConcreteFunctionWithBodyId<'db>,
+        lowered: &mut Lowered<'db>,
+    ) -> Maybe<()>;
+}
+
+/// An error in the registration of lowering plugins.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum LoweringPluginError {
+    #[error("Lowering plugin `{0}` is registered more than once.")]
+    DuplicateName(String),
+}
+
+/// Validates that the given lowering plugins can be registered together.
+pub fn validate_lowering_plugins(
+    plugins: &[Arc<dyn LoweringPlugin>],
+) -> Result<(), LoweringPluginError> {
+    let mut names = OrderedHashSet::<&str>::default();
+    for plugin in plugins {
+        if !names.insert(plugin.name()) {
+            return Err(LoweringPluginError::DuplicateName(plugin.name().to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Applies the lowering plugins of `stage` to the lowering of `function`, in the order of their
+/// registration.
+///
+/// Panics if a plugin breaks the lowering, as the following stages rely on its validity.
+pub fn apply_lowering_plugins<'db>(
+    db: &'db dyn Database,
+    function: ConcreteFunctionWithBodyId<'db>,
+    stage: LoweringStage,
+    lowered: &mut Lowered<'db>,
+) -> Maybe<()> {
+    for plugin in db.lowering_plugins().iter().filter(|plugin| plugin.stage() == stage) {
+        let signature = lowered.signature.clone();
+        let parameters = lowered.parameters.clone();
+        plugin.apply(db, function, lowered)?;
+        if lowered.signature != signature || lowered.parameters != parameters {
+            panic!(
+                "Lowering plugin `{}` changed the signature of function `{}`.",
+                plugin.name(),
+                function.full_path(db)
+            );
+        }
+        if let Err(err) = validate(lowered) {
+            panic!(
+                "Lowering plugin `{}` produced an invalid lowering for function `{}`: {}",
+                plugin.name(),
+                function.full_path(db),
+                err.to_message()
+            );
+        }
+    }
+    Ok(())
+}
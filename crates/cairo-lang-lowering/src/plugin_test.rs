@@ -0,0 +1,152 @@
+use std::sync::{Arc, Mutex};
+
+use cairo_lang_diagnostics::Maybe;
+use cairo_lang_semantic::test_utils::setup_test_function_ex;
+use salsa::Database;
+
+use super::{LoweringPlugin, LoweringPluginError, validate_lowering_plugins};
+use crate::db::{LoweringGroup, set_lowering_plugins};
+use crate::ids::ConcreteFunctionWithBodyId;
+use crate::test_utils::LoweringDatabaseForTesting;
+use crate::{BlockEnd, BlockId, Lowered, LoweringStage, VarUsage};
+
+/// A lowering plugin for testing, logging its applications on `test::foo`.
+#[derive(Debug)]
+struct TestPlugin {
+    name: &'static str,
+    stage: LoweringStage,
+    transform: fn(&mut Lowered<'_>),
+    log: Arc<Mutex<Vec<&'static str>>>,
+}
+
+/// Returns a [TestPlugin] sharing the given log.
+fn test_plugin(
+    name: &'static str,
+    stage: LoweringStage,
+    transform: fn(&mut Lowered<'_>),
+    log: &Arc<Mutex<Vec<&'static str>>>,
+) -> Arc<dyn LoweringPlugin> {
+    Arc::new(TestPlugin { name, stage, transform, log: log.clone() })
+}
+
+impl LoweringPlugin for TestPlugin {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn stage(&self) -> LoweringStage {
+        self.stage
+    }
+
+    fn apply<'db>(
+        &self,
+        db: &'db dyn Database,
+        function: ConcreteFunctionWithBodyId<'db>,
+        lowered: &mut Lowered<'db>,
+    ) -> Maybe<()> {
+        if function.full_path(db) == "test::foo" {
+            self.log.lock().unwrap().push(self.name);
+            (self.transform)(lowered);
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the body of the function with returning its first parameter.
+fn return_first_param(lowered: &mut Lowered<'_>) {
+    let root = &mut lowered.blocks[BlockId::root()];
+    let BlockEnd::Return(_, location) = root.end else { panic!("Expected a return.") };
+    root.statements.clear();
+    let var_id = lowered.parameters[0];
+    root.end = BlockEnd::Return(vec![VarUsage { var_id, location }], location);
+}
+
+/// Removes the statements of the function, keeping the usages of their outputs.
+fn remove_statements(lowered: &mut Lowered<'_>) {
+    lowered.blocks[BlockId::root()].statements.clear();
+}
+
+/// Removes the last parameter of the function.
+fn remove_param(lowered: &mut Lowered<'_>) {
+    lowered.parameters.pop();
+}
+
+/// Lowers `test::foo` to the final stage with the given plugins registered, and runs `check` on
+/// the result.
+fn lower_foo_with_plugins(plugins: Vec<Arc<dyn LoweringPlugin>>, check: impl FnOnce(&Lowered<'_>)) {
+    let mut db = LoweringDatabaseForTesting::new();
+    set_lowering_plugins(&mut db, plugins).unwrap();
+    let test_function = setup_test_function_ex(
+        &db,
+        "fn foo(a: felt252, b: felt252) -> felt252 { a + b }",
+        "foo",
+        "",
+        None,
+        None,
+    )
+    .unwrap();
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(&db, test_function.concrete_function_id);
+    check(db.lowered_body(function_id, LoweringStage::Final).unwrap());
+}
+
+#[test]
+fn test_plugins_order_and_transform() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let plugins = vec![
+        test_plugin("final_first", LoweringStage::Final, |_| {}, &log),
+        test_plugin("first_param", LoweringStage::PreOptimizations, return_first_param, &log),
+        test_plugin("final_second", LoweringStage::Final, |_| {}, &log),
+    ];
+    lower_foo_with_plugins(plugins, |lowered| {
+        let root = &lowered.blocks[BlockId::root()];
+        assert!(root.statements.is_empty());
+        let BlockEnd::Return(returned, _) = &root.end else { panic!("Expected a return.") };
+        assert_eq!(
+            returned.iter().map(|var| var.var_id).collect::<Vec<_>>(),
+            [lowered.parameters[0]]
+        );
+    });
+    assert_eq!(*log.lock().unwrap(), ["first_param", "final_first", "final_second"]);
+}
+
+#[test]
+#[should_panic(expected = "Lowering plugin `remove_statements` produced an invalid lowering for \
+                           function `test::foo`")]
+fn test_plugin_invalid_lowering() {
+    let log = Arc::new(Mutex::new(vec![]));
+    lower_foo_with_plugins(
+        vec![test_plugin(
+            "remove_statements",
+            LoweringStage::PostBaseline,
+            remove_statements,
+            &log,
+        )],
+        |_| {},
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "Lowering plugin `remove_param` changed the signature of function `test::foo`."
+)]
+fn test_plugin_changed_signature() {
+    let log = Arc::new(Mutex::new(vec![]));
+    lower_foo_with_plugins(
+        vec![test_plugin("remove_param", LoweringStage::Monomorphized, remove_param, &log)],
+        |_| {},
+    );
+}
+
+#[test]
+fn test_duplicate_plugin_names() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let plugins = vec![
+        test_plugin("plugin", LoweringStage::Final, |_| {}, &log),
+        test_plugin("plugin", LoweringStage::PostBaseline, |_| {}, &log),
+    ];
+    assert_eq!(
+        validate_lowering_plugins(&plugins),
+        Err(LoweringPluginError::DuplicateName("plugin".into()))
+    );
+}
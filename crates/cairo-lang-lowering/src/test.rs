@@ -20,7 +20,7 @@ use crate::LoweringStage;
 use crate::db::LoweringGroup;
 use crate::diagnostic::{LoweringDiagnostic, LoweringDiagnosticKind};
 use crate::ids::{ConcreteFunctionWithBodyId, LocationId};
-use crate::test_utils::{LoweringDatabaseForTesting, formatted_lowered};
+use crate::test_utils::{LoweringDatabaseForTesting, formatted_lowered, lowered_after_phase};
 
 cairo_lang_test_utils::test_file_test!(
     lowering,
@@ -40,6 +40,7 @@ cairo_lang_test_utils::test_file_test!(
         extern_: "extern",
         fixed_size_array: "fixed_size_array",
         arm_pattern_destructure: "arm_pattern_destructure",
+        after_pass: "after_pass",
         if_: "if",
         inline_macros: "inline_macros",
         implicits: "implicits",
@@ -83,13 +84,38 @@ fn test_function_lowering(
     let formatted_lowering_diagnostics = diagnostics.format(db);
     let combined_diagnostics = format!("{semantic_diagnostics}\n{formatted_lowering_diagnostics}");
     let error = verify_diagnostics_expectation(args, &combined_diagnostics);
-    TestRunnerResult {
-        outputs: OrderedHashMap::from([
-            ("semantic_diagnostics".into(), semantic_diagnostics),
-            ("lowering_diagnostics".into(), formatted_lowering_diagnostics),
-            ("lowering_flat".into(), formatted_lowered(db, lowered.ok())),
-        ]),
-        error,
+    let mut outputs = OrderedHashMap::from([
+        ("semantic_diagnostics".into(), semantic_diagnostics),
+        ("lowering_diagnostics".into(), formatted_lowering_diagnostics),
+        ("lowering_flat".into(), formatted_lowered(db, lowered.ok())),
+    ]);
+    if let Some(after_pass) = inputs.get("after_pass") {
+        outputs.insert(
+            "lowering_after_pass".into(),
+            format_lowered_after_pass(db, function_id, after_pass),
+        );
+    }
+    TestRunnerResult { outputs, error }
+}
+
+/// Formats the lowering of `function_id` after the optimization phase given by `after_pass`, which
+/// is the name of the phase, optionally followed by `:<n>` to select its `n`-th application rather
+/// than its first, e.g. `ConstFolding:2`.
+fn format_lowered_after_pass<'db>(
+    db: &'db LoweringDatabaseForTesting,
+    function_id: ConcreteFunctionWithBodyId<'db>,
+    after_pass: &str,
+) -> String {
+    let (phase_name, occurrence) = match after_pass.trim().split_once(':') {
+        Some((phase_name, occurrence)) => {
+            (phase_name, occurrence.parse().expect("Invalid phase occurrence."))
+        }
+        None => (after_pass.trim(), 1),
+    };
+    match lowered_after_phase(db, function_id, phase_name, occurrence) {
+        Ok(Some(lowered)) => formatted_lowered(db, Some(&lowered)),
+        Ok(None) => panic!("Phase `{phase_name}` is not applied {occurrence} time(s)."),
+        Err(_) => formatted_lowered(db, None),
     }
 }
 
@@ -1,20 +1,24 @@
+pub mod lowered_generator;
+
 use std::sync::{LazyLock, Mutex};
 
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs::db::{init_defs_group, init_external_files};
+use cairo_lang_diagnostics::Maybe;
 use cairo_lang_filesystem::db::{init_dev_corelib, init_files_group};
 use cairo_lang_filesystem::detect::detect_corelib;
 use cairo_lang_filesystem::flag::{Flag, FlagsGroup};
-use cairo_lang_filesystem::ids::FlagLongId;
 use cairo_lang_semantic::db::{PluginSuiteInput, init_semantic_group};
 use cairo_lang_semantic::inline_macros::get_default_plugin_suite;
+use itertools::chain;
 use salsa::Database;
 
-use crate::Lowered;
-use crate::db::init_lowering_group;
+use crate::db::{LoweringGroup, init_lowering_group};
 use crate::fmt::LoweredFormatter;
+use crate::ids::ConcreteFunctionWithBodyId;
 use crate::optimizations::config::Optimizations;
 use crate::utils::InliningStrategy;
+use crate::{Lowered, LoweringStage};
 
 #[salsa::db]
 #[derive(Clone)]
@@ -55,7 +59,7 @@ pub static SHARED_DB: LazyLock<Mutex<LoweringDatabaseForTesting>> =
 pub static SHARED_DB_FUTURE_SIERRA: LazyLock<Mutex<LoweringDatabaseForTesting>> =
     LazyLock::new(|| {
         let mut db = LoweringDatabaseForTesting::new();
-        db.set_flag(FlagLongId(Flag::FUTURE_SIERRA.into()), Some(Flag::FutureSierra(true)));
+        db.set_flag(Flag::FutureSierra(true));
         Mutex::new(db)
     });
 impl LoweringDatabaseForTesting {
@@ -80,3 +84,36 @@ pub fn formatted_lowered(db: &dyn Database, lowered: Option<&Lowered<'_>>) -> St
             .to_string(),
     }
 }
+
+/// Returns the lowering of `function` right after the `occurrence`-th (1-based) application of the
+/// optimization phase named `phase_name` (see
+/// [crate::optimizations::strategy::OptimizationPhase::name]), when applying the baseline and the
+/// final optimization strategies to its [LoweringStage::PreOptimizations] lowering. Returns `None`
+/// if the phase is applied less than `occurrence` times.
+///
+/// Note that unlike [LoweringGroup::lowered_body], lowering plugins are not applied between the
+/// strategies.
+pub fn lowered_after_phase<'db>(
+    db: &'db dyn Database,
+    function: ConcreteFunctionWithBodyId<'db>,
+    phase_name: &str,
+    occurrence: usize,
+) -> Maybe<Option<Lowered<'db>>> {
+    assert!(occurrence > 0, "Phase occurrences are 1-based.");
+    let mut lowered = db.lowered_body(function, LoweringStage::PreOptimizations)?.clone();
+    let phases = chain!(
+        &db.baseline_optimization_strategy().long(db).0,
+        &db.final_optimization_strategy().long(db).0,
+    );
+    let mut remaining = occurrence;
+    for phase in phases {
+        phase.apply(db, function, &mut lowered)?;
+        if phase.name() == phase_name {
+            remaining -= 1;
+            if remaining == 0 {
+                return Ok(Some(lowered));
+            }
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,307 @@
+//! Generator of random, well-formed lowered bodies, for property testing of the analyses.
+//!
+//! The generated bodies are built of `felt252` values, and their snapshots and boxes (e.g.
+//! `Box<@felt252>`), which are all copyable, so every variable may be used any number of times.
+//! The blocks form nested value matches, where the arms of each match jump to a shared merge
+//! block with a remapping, so every variable is defined before it is used on all paths.
+
+#[path = "lowered_generator_test.rs"]
+mod test;
+
+use cairo_lang_semantic::corelib::{core_box_ty, unit_ty};
+use cairo_lang_semantic::items::enm::{MatchArmSelector, ValueSelectorArm};
+use cairo_lang_semantic::test_utils::setup_test_function_ex;
+use cairo_lang_semantic::{TypeId, TypeLongId};
+use cairo_lang_utils::Intern;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use salsa::Database;
+
+use super::LoweringDatabaseForTesting;
+use crate::db::LoweringGroup;
+use crate::ids::{ConcreteFunctionWithBodyId, LocationId};
+use crate::objects::blocks::BlocksBuilder;
+use crate::{
+    Block, BlockEnd, BlockId, Lowered, LoweringStage, MatchArm, MatchEnumValue, MatchInfo,
+    Statement, StatementDesnap, StatementIntoBox, StatementSnapshot, StatementUnbox, VarRemapping,
+    VarUsage, Variable, VariableArena, VariableId,
+};
+
+/// The code of the function whose signature is used for the generated bodies.
+const TEMPLATE_FUNCTION_CODE: &str = "fn foo(a: felt252, b: felt252) -> felt252 { a }";
+
+/// Configuration of the generated lowered bodies.
+#[derive(Clone, Debug)]
+pub struct LoweredGeneratorConfig {
+    /// The maximal nesting depth of matches.
+    pub max_match_depth: usize,
+    /// The maximal number of arms of a match.
+    pub max_arms: usize,
+    /// The maximal number of statements in a block.
+    pub max_statements: usize,
+    /// The maximal number of variables remapped into a merge block.
+    pub max_remapped_vars: usize,
+    /// The maximal number of snapshot and box wrappers around `felt252` in a variable type.
+    pub max_type_depth: usize,
+}
+
+impl Default for LoweredGeneratorConfig {
+    fn default() -> Self {
+        Self {
+            max_match_depth: 3,
+            max_arms: 3,
+            max_statements: 4,
+            max_remapped_vars: 3,
+            max_type_depth: 3,
+        }
+    }
+}
+
+/// Generates a random lowered body with the signature `fn foo(a: felt252, b: felt252) ->
+/// felt252`.
+pub fn generate_lowered<'db>(
+    db: &'db LoweringDatabaseForTesting,
+    rng: &mut impl Rng,
+    config: &LoweredGeneratorConfig,
+) -> Lowered<'db> {
+    let test_function =
+        setup_test_function_ex(db, TEMPLATE_FUNCTION_CODE, "foo", "", None, None).unwrap();
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(db, test_function.concrete_function_id);
+    let template = db.lowered_body(function_id, LoweringStage::PostBaseline).unwrap();
+
+    let mut generator = LoweredGenerator {
+        db,
+        rng,
+        config,
+        felt252_ty: template.variables[template.parameters[0]].ty,
+        location: template.signature.location,
+        variables: VariableArena::default(),
+        blocks: BlocksBuilder::new(),
+        var_types: OrderedHashMap::default(),
+    };
+    let parameters = template
+        .parameters
+        .iter()
+        .map(|param| {
+            let var_id = generator.variables.alloc(template.variables[*param].clone());
+            generator.var_types.insert(var_id, vec![]);
+            var_id
+        })
+        .collect::<Vec<_>>();
+    let root = generator.blocks.alloc_empty();
+    generator.generate_block(root, parameters.clone(), config.max_match_depth, &BlockExit::Return);
+
+    Lowered {
+        diagnostics: template.diagnostics.clone(),
+        signature: template.signature.clone(),
+        variables: generator.variables,
+        blocks: generator.blocks.build().unwrap(),
+        parameters,
+    }
+}
+
+/// A wrapper of a type of a generated variable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TypeWrapper {
+    Snapshot,
+    Box,
+}
+
+/// The kinds of the generated statements.
+#[derive(Clone, Copy, Debug)]
+enum StatementKind {
+    Snapshot,
+    Desnap,
+    IntoBox,
+    Unbox,
+}
+
+/// How a generated block ends, once it has no more nested matches.
+#[derive(Clone)]
+enum BlockExit {
+    /// Returns from the function.
+    Return,
+    /// Jumps to a merge block, assigning its introduced variables.
+    Goto { target: BlockId, introduced_vars: Vec<VariableId> },
+}
+
+/// The state of the generation of a single lowered body.
+struct LoweredGenerator<'db, 'r, R: Rng> {
+    db: &'db dyn Database,
+    rng: &'r mut R,
+    config: &'r LoweredGeneratorConfig,
+    felt252_ty: TypeId<'db>,
+    location: LocationId<'db>,
+    variables: VariableArena<'db>,
+    blocks: BlocksBuilder<'db>,
+    /// The wrappers around `felt252` of the type of each variable, from the innermost.
+    var_types: OrderedHashMap<VariableId, Vec<TypeWrapper>>,
+}
+
+impl<'db, 'r, R: Rng> LoweredGenerator<'db, 'r, R> {
+    /// Generates the content of the block `block_id`, where the variables in `available` are
+    /// defined.
+    fn generate_block(
+        &mut self,
+        block_id: BlockId,
+        mut available: Vec<VariableId>,
+        depth: usize,
+        exit: &BlockExit,
+    ) {
+        let mut statements = vec![];
+        for _ in 0..self.rng.random_range(0..=self.config.max_statements) {
+            if let Some(statement) = self.generate_statement(&available) {
+                available.extend(statement.outputs());
+                statements.push(statement);
+            }
+        }
+        let end = if depth > 0 && self.rng.random_bool(0.5) {
+            self.generate_match(&available, depth, exit)
+        } else {
+            self.generate_exit(&available, exit)
+        };
+        self.blocks.set_block(block_id, Block { statements, end });
+    }
+
+    /// Generates a random statement using the variables in `available`, if any applies.
+    fn generate_statement(&mut self, available: &[VariableId]) -> Option<Statement<'db>> {
+        let input = *available.choose(self.rng).unwrap();
+        let wrappers = self.var_types[&input].clone();
+        let can_wrap = wrappers.len() < self.config.max_type_depth;
+        let kinds = [
+            (StatementKind::Snapshot, can_wrap),
+            (StatementKind::Desnap, wrappers.last() == Some(&TypeWrapper::Snapshot)),
+            (StatementKind::IntoBox, can_wrap),
+            (StatementKind::Unbox, wrappers.last() == Some(&TypeWrapper::Box)),
+        ]
+        .into_iter()
+        .filter_map(|(kind, applies)| applies.then_some(kind))
+        .collect::<Vec<_>>();
+        let input_usage = self.usage(input);
+        let unwrapped = wrappers[..wrappers.len().saturating_sub(1)].to_vec();
+        Some(match kinds.choose(self.rng)? {
+            StatementKind::Snapshot => {
+                let original = self.new_var(wrappers.clone());
+                let snapshot = self.new_var([wrappers, vec![TypeWrapper::Snapshot]].concat());
+                Statement::Snapshot(StatementSnapshot::new(input_usage, original, snapshot))
+            }
+            StatementKind::Desnap => Statement::Desnap(StatementDesnap {
+                input: input_usage,
+                output: self.new_var(unwrapped),
+            }),
+            StatementKind::IntoBox => Statement::IntoBox(StatementIntoBox {
+                input: input_usage,
+                output: self.new_var([wrappers, vec![TypeWrapper::Box]].concat()),
+            }),
+            StatementKind::Unbox => Statement::Unbox(StatementUnbox {
+                input: input_usage,
+                output: self.new_var(unwrapped),
+            }),
+        })
+    }
+
+    /// Generates a match on a `felt252` variable, whose arms merge into a new block, continuing to
+    /// `exit`.
+    fn generate_match(
+        &mut self,
+        available: &[VariableId],
+        depth: usize,
+        exit: &BlockExit,
+    ) -> BlockEnd<'db> {
+        let input = self.choose_var(available, &[]);
+        let input = self.usage(input);
+
+        let num_of_arms = self.rng.random_range(2..=self.config.max_arms.max(2));
+        let arm_blocks = (0..num_of_arms).map(|_| self.blocks.alloc_empty()).collect::<Vec<_>>();
+        let merge_block = self.blocks.alloc_empty();
+        let introduced_vars = (0..self.rng.random_range(0..=self.config.max_remapped_vars))
+            .map(|_| {
+                let wrappers = self.var_types[available.choose(self.rng).unwrap()].clone();
+                self.new_var(wrappers)
+            })
+            .collect::<Vec<_>>();
+
+        let arm_exit =
+            BlockExit::Goto { target: merge_block, introduced_vars: introduced_vars.clone() };
+        let unit_ty = unit_ty(self.db);
+        let arms = arm_blocks
+            .into_iter()
+            .enumerate()
+            .map(|(value, block_id)| {
+                self.generate_block(block_id, available.to_vec(), depth - 1, &arm_exit);
+                let unit_var = self.variables.alloc(Variable::with_default_context(
+                    self.db,
+                    unit_ty,
+                    self.location,
+                ));
+                MatchArm {
+                    arm_selector: MatchArmSelector::Value(ValueSelectorArm { value }),
+                    block_id,
+                    var_ids: vec![unit_var],
+                }
+            })
+            .collect();
+
+        let merge_available = [available, &introduced_vars].concat();
+        self.generate_block(merge_block, merge_available, depth - 1, exit);
+
+        BlockEnd::Match {
+            info: MatchInfo::Value(MatchEnumValue {
+                num_of_arms,
+                input,
+                arms,
+                location: self.location,
+            }),
+        }
+    }
+
+    /// Generates the end of a block without a match, using the variables in `available`.
+    fn generate_exit(&mut self, available: &[VariableId], exit: &BlockExit) -> BlockEnd<'db> {
+        match exit {
+            BlockExit::Return => {
+                let returned = self.choose_var(available, &[]);
+                BlockEnd::Return(vec![self.usage(returned)], self.location)
+            }
+            BlockExit::Goto { target, introduced_vars } => {
+                let remapping = introduced_vars
+                    .iter()
+                    .map(|dst| {
+                        let src = self.choose_var(available, &self.var_types[dst].clone());
+                        (*dst, self.usage(src))
+                    })
+                    .collect();
+                BlockEnd::Goto(*target, VarRemapping { remapping })
+            }
+        }
+    }
+
+    /// Chooses a random variable of `available`, of `felt252` wrapped by `wrappers`.
+    /// Panics if there is no such variable.
+    fn choose_var(&mut self, available: &[VariableId], wrappers: &[TypeWrapper]) -> VariableId {
+        let candidates = available
+            .iter()
+            .filter(|var| self.var_types[*var] == wrappers)
+            .copied()
+            .collect::<Vec<_>>();
+        *candidates.choose(self.rng).unwrap()
+    }
+
+    /// Allocates a new variable of `felt252` wrapped by `wrappers`.
+    fn new_var(&mut self, wrappers: Vec<TypeWrapper>) -> VariableId {
+        let ty = wrappers.iter().fold(self.felt252_ty, |ty, wrapper| match wrapper {
+            TypeWrapper::Snapshot => TypeLongId::Snapshot(ty).intern(self.db),
+            TypeWrapper::Box => core_box_ty(self.db, ty),
+        });
+        let var_id =
+            self.variables.alloc(Variable::with_default_context(self.db, ty, self.location));
+        self.var_types.insert(var_id, wrappers);
+        var_id
+    }
+
+    /// Returns a usage of `var_id`.
+    fn usage(&self, var_id: VariableId) -> VarUsage<'db> {
+        VarUsage { var_id, location: self.location }
+    }
+}
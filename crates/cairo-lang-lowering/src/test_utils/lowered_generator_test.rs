@@ -0,0 +1,52 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::{LoweredGeneratorConfig, generate_lowered};
+use crate::analysis::equality_analysis::EqualityAnalysis;
+use crate::optimizations::validate::validate;
+use crate::test_utils::{LoweringDatabaseForTesting, formatted_lowered};
+use crate::{BlockEnd, BlockId};
+
+#[test]
+fn test_generated_lowered_is_valid() {
+    let db = LoweringDatabaseForTesting::default();
+    let config = LoweredGeneratorConfig::default();
+    for seed in 0..100 {
+        let lowered = generate_lowered(&db, &mut StdRng::seed_from_u64(seed), &config);
+        if let Err(err) = validate(&lowered) {
+            panic!("Invalid lowering for seed {seed}: {}", err.to_message());
+        }
+        for (block_id, block) in lowered.blocks.iter() {
+            assert!(
+                !matches!(block.end, BlockEnd::NotSet),
+                "Block {block_id:?} is not set for seed {seed}."
+            );
+        }
+        // All the blocks are reachable, so the analysis computes a state for each of them.
+        let states = EqualityAnalysis::analyze(&lowered);
+        assert!(states.iter().all(Option::is_some), "Missing analysis state for seed {seed}.");
+    }
+}
+
+#[test]
+fn test_generated_lowered_is_deterministic() {
+    let db = LoweringDatabaseForTesting::default();
+    let config = LoweredGeneratorConfig { max_match_depth: 4, ..Default::default() };
+    for seed in 0..10 {
+        let lowered1 = generate_lowered(&db, &mut StdRng::seed_from_u64(seed), &config);
+        let lowered2 = generate_lowered(&db, &mut StdRng::seed_from_u64(seed), &config);
+        assert_eq!(
+            formatted_lowered(&db, Some(&lowered1)),
+            formatted_lowered(&db, Some(&lowered2))
+        );
+    }
+}
+
+#[test]
+fn test_generated_lowered_without_matches() {
+    let db = LoweringDatabaseForTesting::default();
+    let config = LoweredGeneratorConfig { max_match_depth: 0, ..Default::default() };
+    let lowered = generate_lowered(&db, &mut StdRng::seed_from_u64(0), &config);
+    assert_eq!(lowered.blocks.len(), 1);
+    assert!(matches!(lowered.blocks[BlockId::root()].end, BlockEnd::Return(..)));
+}
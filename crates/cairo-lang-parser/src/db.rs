@@ -1,12 +1,14 @@
 use cairo_lang_diagnostics::{Diagnostics, DiagnosticsBuilder, Maybe, ToMaybe};
-use cairo_lang_filesystem::db::FilesGroup;
-use cairo_lang_filesystem::ids::{FileId, FileKind};
+use cairo_lang_filesystem::db::{FileEdits, FilesGroup};
+use cairo_lang_filesystem::ids::{ArcStr, FileId, FileKind};
+use cairo_lang_filesystem::span::TextEdit;
 use cairo_lang_syntax::node::ast::{Expr, StatementList, SyntaxFile};
 use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode};
 use salsa::Database;
 
 use crate::diagnostic::ParserDiagnostic;
 use crate::parser::Parser;
+use crate::reparse::reparse_file;
 
 #[cfg(test)]
 #[path = "db_test.rs"]
@@ -56,6 +58,31 @@ struct SyntaxData<'db> {
 /// Parses a file and returns the result and the generated [ParserDiagnostic].
 #[salsa::tracked(returns(ref))]
 fn file_syntax_data<'db>(db: &'db dyn Database, file_id: FileId<'db>) -> SyntaxData<'db> {
+    if file_id.kind(db) == FileKind::Module
+        && let Some(content) = db.file_content(file_id)
+    {
+        // Module files are parsed as versions, so the syntax of the current version may be reused
+        // when the file is edited.
+        let version = match db.file_edits(file_id) {
+            Some(file_edits) => file_version(db, file_id, file_edits),
+            None => FileVersion::new(
+                db,
+                file_id,
+                FileVersionOrigin::Content(ArcStr::new(content.into())),
+            ),
+        };
+        // The recorded edits are expected to lead to the content of the file, unless the file was
+        // overridden without the edit macros.
+        if let Some(version_syntax) = file_version_syntax(db, version)
+            && content == &**version_syntax.content
+        {
+            return SyntaxData::new(
+                db,
+                version_syntax.diagnostics.clone(),
+                Ok(version_syntax.syntax.as_syntax_node()),
+            );
+        }
+    }
     let mut diagnostics = DiagnosticsBuilder::default();
     let syntax = db.file_content(file_id).to_maybe().map(|s| match file_id.kind(db) {
         FileKind::Module => Parser::parse_file(db, &mut diagnostics, file_id, s).as_syntax_node(),
@@ -69,6 +96,92 @@ fn file_syntax_data<'db>(db: &'db dyn Database, file_id: FileId<'db>) -> SyntaxD
     SyntaxData::new(db, diagnostics.build(), syntax)
 }
 
+/// A version of the content of a module file, by the edits recorded for it.
+///
+/// A version is either the content of the file as set in full, or a previous version with an edit
+/// applied. As versions are immutable, the syntax of a version stays valid when the file is edited
+/// again, and may be reused by the syntax of the next version.
+#[salsa::interned(debug)]
+struct FileVersion<'db> {
+    file_id: FileId<'db>,
+    origin: FileVersionOrigin<'db>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, salsa::Update)]
+enum FileVersionOrigin<'db> {
+    /// The content of the file, as set in full.
+    Content(ArcStr),
+    /// A previous version, with an edit applied.
+    Edit(FileVersion<'db>, TextEdit),
+}
+
+/// Returns the latest version of a module file by its recorded edits.
+fn file_version<'db>(
+    db: &'db dyn Database,
+    file_id: FileId<'db>,
+    file_edits: &FileEdits,
+) -> FileVersion<'db> {
+    let base = FileVersion::new(
+        db,
+        file_id,
+        FileVersionOrigin::Content(ArcStr::new(file_edits.base_content.clone())),
+    );
+    file_edits.edits.iter().fold(base, |previous, edit| {
+        FileVersion::new(db, file_id, FileVersionOrigin::Edit(previous, edit.clone()))
+    })
+}
+
+/// The syntax of a version of a module file.
+#[derive(Clone, Debug, PartialEq, Eq, salsa::Update)]
+struct FileVersionSyntax<'db> {
+    content: ArcStr,
+    syntax: SyntaxFile<'db>,
+    diagnostics: Diagnostics<'db, ParserDiagnostic<'db>>,
+}
+
+/// Parses a version of a module file. The syntax of a version created by an edit is reparsed from
+/// the syntax of the previous version where possible.
+///
+/// Returns `None` if an edit of the version is not valid for the content it is applied to, in
+/// which case the content of the file is parsed in full.
+///
+/// Only the syntax of the latest versions is kept - the syntax of a previous version is only needed
+/// for the next edit of the file, and is otherwise evicted.
+#[salsa::tracked(returns(ref), lru = 32, heap_size = file_version_syntax_heap_size)]
+fn file_version_syntax<'db>(
+    db: &'db dyn Database,
+    version: FileVersion<'db>,
+) -> Option<FileVersionSyntax<'db>> {
+    let file_id = version.file_id(db);
+    let content = match version.origin(db) {
+        FileVersionOrigin::Content(content) => content,
+        FileVersionOrigin::Edit(previous, edit) => {
+            let previous = file_version_syntax(db, previous).as_ref()?;
+            let content = ArcStr::new(edit.apply(&previous.content).ok()?.into());
+            if let Some((syntax, diagnostics)) = reparse_file(
+                db,
+                file_id,
+                previous.syntax.clone(),
+                &previous.diagnostics,
+                &edit,
+                &content,
+            ) {
+                return Some(FileVersionSyntax { content, syntax, diagnostics });
+            }
+            content
+        }
+    };
+    let mut diagnostics = DiagnosticsBuilder::default();
+    let syntax = Parser::parse_file(db, &mut diagnostics, file_id, &content);
+    Some(FileVersionSyntax { content, syntax, diagnostics: diagnostics.build() })
+}
+
+/// Returns the size of the content held by the syntax of a version, for the memory usage reports.
+/// The syntax itself is interned, so it is not counted.
+fn file_version_syntax_heap_size(version_syntax: &Option<FileVersionSyntax<'_>>) -> usize {
+    version_syntax.as_ref().map_or(0, |version_syntax| version_syntax.content.len())
+}
+
 /// Parses a file and returns its SyntaxNode.
 #[salsa::tracked]
 fn file_syntax<'db>(db: &'db dyn Database, file_id: FileId<'db>) -> Maybe<SyntaxNode<'db>> {
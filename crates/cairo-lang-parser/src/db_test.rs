@@ -1,17 +1,23 @@
 use std::path::PathBuf;
 
+use cairo_lang_filesystem::db::{FilesGroup, MAX_RECORDED_FILE_EDITS};
 use cairo_lang_filesystem::ids::{FileId, SmolStrId};
-use cairo_lang_filesystem::span::TextSpan;
+use cairo_lang_filesystem::span::{TextEdit, TextOffset, TextSpan, TextWidth};
+use cairo_lang_filesystem::{apply_file_edit, override_file_content};
 use cairo_lang_syntax::node::ast::{
     ModuleItemList, SyntaxFile, TerminalEndOfFile, TokenEndOfFile, Trivia,
 };
+use cairo_lang_syntax::node::ids::GreenId;
 use cairo_lang_syntax::node::{SyntaxNode, Terminal, Token as SyntaxToken, TypedSyntaxNode};
+use cairo_lang_utils::Intern;
 use indoc::indoc;
 use pretty_assertions::assert_eq;
 use salsa::Database;
 
 use crate::db::ParserGroup;
+use crate::diagnostic::ParserDiagnosticKind;
 use crate::printer::print_tree;
+use crate::reparse::reparse_file;
 use crate::test_utils::{MockToken, MockTokenStream, create_virtual_file};
 use crate::utils::{SimpleParserDatabase, get_syntax_root_and_diagnostics_from_file};
 
@@ -102,3 +108,163 @@ fn test_token_stream_expr_parser() {
 
     assert_eq!(node_text, expr_code);
 }
+
+const EDITED_FILE_CONTENT: &str = indoc! {r#"
+    use core::array::ArrayTrait;
+
+    /// A struct.
+    struct A {
+        a: felt252,
+        b: Array<felt252>,
+    }
+
+    fn foo(x: felt252) -> felt252 {
+        let y = x + 1;
+        if y == 2 { y } else { bar(y) }
+    }
+
+    fn bar(x: felt252) -> felt252 {
+        x * 2
+    }
+
+    const C: felt252 = 5;
+"#};
+
+/// Returns the syntax and the diagnostics (without their file) of a file, parsed in full.
+fn parse_in_full<'db>(
+    db: &'db SimpleParserDatabase,
+    content: &str,
+) -> (GreenId<'db>, Vec<(TextSpan, ParserDiagnosticKind)>) {
+    let file_id = create_virtual_file(db, "full.cairo", content);
+    file_green_and_diagnostics(db, file_id)
+}
+
+/// Returns the syntax and the diagnostics (without their file) of a file in the DB.
+fn file_green_and_diagnostics<'db>(
+    db: &'db SimpleParserDatabase,
+    file_id: FileId<'db>,
+) -> (GreenId<'db>, Vec<(TextSpan, ParserDiagnosticKind)>) {
+    let green = db.file_module_syntax(file_id).unwrap().as_syntax_node().green_node(db).clone();
+    let diagnostics = db
+        .file_syntax_diagnostics(file_id)
+        .get_all()
+        .into_iter()
+        .map(|diagnostic| (diagnostic.span, diagnostic.kind))
+        .collect();
+    (green.intern(db), diagnostics)
+}
+
+fn text_span(start: usize, end: usize) -> TextSpan {
+    TextSpan::new(
+        TextOffset::START.add_width(TextWidth::new_for_testing(start as u32)),
+        TextOffset::START.add_width(TextWidth::new_for_testing(end as u32)),
+    )
+}
+
+#[test]
+fn test_edited_file_reparse() {
+    let mut db = SimpleParserDatabase::default();
+    let path = PathBuf::from("src/lib.cairo");
+    let db_ref = &mut db;
+    let file_id = FileId::new_on_disk(db_ref, path.clone());
+    override_file_content!(db_ref, file_id, Some(EDITED_FILE_CONTENT.into()));
+
+    let mut content = EDITED_FILE_CONTENT.to_string();
+    let mut apply_edit = |db: &mut SimpleParserDatabase, edit: TextEdit| {
+        content = edit.apply(&content).unwrap();
+        let file_id = FileId::new_on_disk(db, path.clone());
+        apply_file_edit!(db, file_id, &edit).unwrap();
+
+        let file_id = FileId::new_on_disk(db, path.clone());
+        assert_eq!(db.file_content(file_id), Some(content.as_str()));
+        assert_eq!(file_green_and_diagnostics(db, file_id), parse_in_full(db, &content));
+        content.len()
+    };
+
+    // Typing a statement character by character inside a function, and then deleting it.
+    let statement = "let z = if y == 3 { bar(\"a\") } else { (0, 1) };\n    ";
+    let start = EDITED_FILE_CONTENT.find("x * 2").unwrap();
+    for (i, c) in statement.char_indices() {
+        apply_edit(&mut db, TextEdit::new(text_span(start + i, start + i), c.to_string()));
+    }
+    for i in (0..statement.len()).rev() {
+        apply_edit(&mut db, TextEdit::new(text_span(start + i, start + i + 1), ""));
+    }
+
+    // Pseudo-random edits, inserting text that often changes the structure of the file.
+    let insertions = ["{", "}", ";", "\"", " ", "x", "\n", "//", "(", ")", "fn g() {}", ","];
+    let mut seed = 17_usize;
+    let mut content_len = EDITED_FILE_CONTENT.len();
+    for _ in 0..300 {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345) % (1 << 31);
+        let start = seed % (content_len + 1);
+        let edit = if seed.is_multiple_of(3) && start < content_len {
+            TextEdit::new(text_span(start, start + 1), "")
+        } else {
+            TextEdit::new(text_span(start, start), insertions[seed % insertions.len()])
+        };
+        content_len = apply_edit(&mut db, edit);
+    }
+
+    // Overriding the content in full discards the recorded edits.
+    let db_ref = &mut db;
+    let file_id = FileId::new_on_disk(db_ref, path.clone());
+    override_file_content!(db_ref, file_id, Some(EDITED_FILE_CONTENT.into()));
+    let file_id = FileId::new_on_disk(&db, path);
+    assert!(db.file_edits(file_id).is_none());
+    assert_eq!(file_green_and_diagnostics(&db, file_id), parse_in_full(&db, EDITED_FILE_CONTENT));
+}
+
+#[test]
+fn test_reparse_reuses_unedited_items() {
+    let db = SimpleParserDatabase::default();
+    let file_id = create_virtual_file(&db, "file.cairo", EDITED_FILE_CONTENT);
+    let previous = db.file_module_syntax(file_id).unwrap();
+    let previous_diagnostics = db.file_syntax_diagnostics(file_id);
+    let reparse = |edit: &TextEdit| {
+        let content = edit.apply(EDITED_FILE_CONTENT).unwrap();
+        reparse_file(&db, file_id, previous.clone(), previous_diagnostics, edit, &content)
+            .map(|(syntax, _)| syntax.as_syntax_node().green_node(&db).clone().intern(&db))
+    };
+
+    // An edit inside the body of a function is reparsed.
+    let offset = EDITED_FILE_CONTENT.find("x + 1").unwrap();
+    let edit = TextEdit::new(text_span(offset, offset + 1), "y");
+    let (full_parse_green, _) = parse_in_full(&db, &edit.apply(EDITED_FILE_CONTENT).unwrap());
+    assert_eq!(reparse(&edit), Some(full_parse_green));
+
+    // An edit between items, or crossing the bounds of an item, requires a full parse.
+    let offset = EDITED_FILE_CONTENT.find("\n\nfn bar").unwrap();
+    assert_eq!(reparse(&TextEdit::new(text_span(offset, offset), "\n")), None);
+    assert_eq!(reparse(&TextEdit::new(text_span(offset - 3, offset + 5), "")), None);
+
+    // An edit leaving the item unterminated requires a full parse.
+    let offset = EDITED_FILE_CONTENT.find("{ y }").unwrap();
+    assert_eq!(reparse(&TextEdit::new(text_span(offset, offset), "{")), None);
+}
+
+#[test]
+fn test_syntax_of_old_versions_is_evicted() {
+    let mut db = SimpleParserDatabase::default();
+    let path = PathBuf::from("src/lib.cairo");
+    let db_ref = &mut db;
+    let file_id = FileId::new_on_disk(db_ref, path.clone());
+    override_file_content!(db_ref, file_id, Some(EDITED_FILE_CONTENT.into()));
+
+    let offset = EDITED_FILE_CONTENT.find("x * 2").unwrap();
+    let n_edits = MAX_RECORDED_FILE_EDITS;
+    for _ in 0..n_edits {
+        let db_ref = &mut db;
+        let file_id = FileId::new_on_disk(db_ref, path.clone());
+        apply_file_edit!(db_ref, file_id, &TextEdit::new(text_span(offset, offset), " ")).unwrap();
+        let file_id = FileId::new_on_disk(db_ref, path.clone());
+        db_ref.file_module_syntax(file_id).unwrap();
+    }
+
+    // A value is memoized for every version, but only the content of the latest ones is kept.
+    let db_ref: &dyn Database = &db;
+    let info = &db_ref.memory_usage().queries["file_version_syntax"];
+    assert_eq!(info.count(), n_edits + 1);
+    let max_content_len = EDITED_FILE_CONTENT.len() + n_edits;
+    assert!(info.heap_size_of_fields().unwrap() <= 33 * max_content_len);
+}
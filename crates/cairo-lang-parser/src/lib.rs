@@ -12,6 +12,7 @@ pub mod operators;
 pub mod parser;
 pub mod printer;
 pub mod recovery;
+mod reparse;
 pub mod utils;
 mod validation;
 
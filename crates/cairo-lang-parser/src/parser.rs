@@ -144,7 +144,7 @@ impl<'a, 'mt> Parser<'a, 'mt> {
     pub fn new(
         db: &'a dyn Database,
         file_id: FileId<'a>,
-        text: &'a str,
+        text: &str,
         diagnostics: &'mt mut DiagnosticsBuilder<'a, ParserDiagnostic<'a>>,
     ) -> Self {
         let tokens: Deque<LexerTerminal<'a>> = tokenize_all(db, (), Arc::from(text));
@@ -172,13 +172,27 @@ impl<'a, 'mt> Parser<'a, 'mt> {
         db: &'a dyn Database,
         diagnostics: &'mt mut DiagnosticsBuilder<'a, ParserDiagnostic<'a>>,
         file_id: FileId<'a>,
-        text: &'a str,
+        text: &str,
     ) -> SyntaxFile<'a> {
         let parser = Parser::new(db, file_id, text, diagnostics);
         let green = parser.parse_syntax_file();
         SyntaxFile::from_syntax_node(db, SyntaxNode::new_root(db, file_id, green.0))
     }
 
+    /// Parses a fragment of a file, starting at `offset`, as a file.
+    /// The spans of the reported diagnostics are relative to the start of the file.
+    pub(crate) fn parse_file_fragment(
+        db: &'a dyn Database,
+        diagnostics: &'mt mut DiagnosticsBuilder<'a, ParserDiagnostic<'a>>,
+        file_id: FileId<'a>,
+        text: &str,
+        offset: TextOffset,
+    ) -> SyntaxFileGreen<'a> {
+        let mut parser = Parser::new(db, file_id, text, diagnostics);
+        parser.offset = offset;
+        parser.parse_syntax_file()
+    }
+
     /// Parses a file expr.
     pub fn parse_file_expr(
         db: &'a dyn Database,
@@ -1026,6 +1040,65 @@ impl<'a, 'mt> Parser<'a, 'mt> {
             )
     }
 
+    /// Returns whether the next tokens start an item that can only appear in a module and not as
+    /// a statement, possibly preceded by attributes and a visibility, without consuming them.
+    fn is_peek_module_only_item(&self) -> bool {
+        let mut kinds = self.terminals.iter().map(|terminal| terminal.kind);
+        let mut kind = kinds.next();
+        while kind == Some(SyntaxKind::TerminalHash) {
+            if kinds.next() != Some(SyntaxKind::TerminalLBrack) {
+                return false;
+            }
+            let mut depth = 1;
+            while depth > 0 {
+                match kinds.next() {
+                    Some(SyntaxKind::TerminalLBrack) => depth += 1,
+                    Some(SyntaxKind::TerminalRBrack) => depth -= 1,
+                    None | Some(SyntaxKind::TerminalEndOfFile) => return false,
+                    Some(_) => {}
+                }
+            }
+            kind = kinds.next();
+        }
+        if kind == Some(SyntaxKind::TerminalPub) {
+            kind = kinds.next();
+            if kind == Some(SyntaxKind::TerminalLParen) {
+                // Skipping the visibility argument, e.g. `(crate)`.
+                kind = kinds.nth(2);
+            }
+            if matches!(
+                kind,
+                Some(
+                    SyntaxKind::TerminalConst | SyntaxKind::TerminalType | SyntaxKind::TerminalUse
+                )
+            ) {
+                return true;
+            }
+        }
+        matches!(
+            kind,
+            Some(
+                SyntaxKind::TerminalEnum
+                    | SyntaxKind::TerminalExtern
+                    | SyntaxKind::TerminalFunction
+                    | SyntaxKind::TerminalImpl
+                    | SyntaxKind::TerminalModule
+                    | SyntaxKind::TerminalStruct
+                    | SyntaxKind::TerminalTrait
+            )
+        )
+    }
+
+    /// Returns whether the next tokens start a module item, i.e. a module item keyword followed by
+    /// a name or another keyword (as in `extern fn`), without consuming them.
+    fn is_peek_module_item_start(&mut self) -> bool {
+        let kind = self.peek().kind;
+        kind != SyntaxKind::TerminalEndOfFile
+            && is_of_kind!(module_item_kw)(kind)
+            && (self.peek_next_next_kind() == SyntaxKind::TerminalIdentifier
+                || self.peek_next_next_kind().is_keyword_terminal())
+    }
+
     /// Returns a GreenId of a node with an identifier kind.
     fn parse_identifier(&mut self) -> TerminalIdentifierGreen<'a> {
         match self.try_parse_identifier() {
@@ -1232,7 +1305,7 @@ impl<'a, 'mt> Parser<'a, 'mt> {
     }
 
     /// Assumes the current token is Type.
-    /// Expected pattern: `type <name>;`
+    /// Expected pattern: `type <name>[= <type>];`
     fn expect_trait_item_type(
         &mut self,
         attributes: AttributeListGreen<'a>,
@@ -1240,12 +1313,21 @@ impl<'a, 'mt> Parser<'a, 'mt> {
         let type_kw = self.take::<TerminalType<'_>>();
         let name = self.parse_identifier();
         let generic_params = self.parse_optional_generic_params();
+        let default = self.parse_option_trait_item_default(Self::parse_type_expr);
         let semicolon = self.parse_token::<TerminalSemicolon<'_>>();
-        TraitItemType::new_green(self.db, attributes, type_kw, name, generic_params, semicolon)
+        TraitItemType::new_green(
+            self.db,
+            attributes,
+            type_kw,
+            name,
+            generic_params,
+            default,
+            semicolon,
+        )
     }
 
     /// Assumes the current token is Const.
-    /// Expected pattern: `const <name>: <type>;`
+    /// Expected pattern: `const <name>: <type>[= <expr>];`
     fn expect_trait_item_const(
         &mut self,
         attributes: AttributeListGreen<'a>,
@@ -1255,9 +1337,34 @@ impl<'a, 'mt> Parser<'a, 'mt> {
         let type_clause = self.parse_type_clause(ErrorRecovery {
             should_stop: is_of_kind!(eq, semicolon, module_item_kw),
         });
+        let default = self.parse_option_trait_item_default(Self::parse_expr);
         let semicolon = self.parse_token::<TerminalSemicolon<'_>>();
 
-        TraitItemConstant::new_green(self.db, attributes, const_kw, name, type_clause, semicolon)
+        TraitItemConstant::new_green(
+            self.db,
+            attributes,
+            const_kw,
+            name,
+            type_clause,
+            default,
+            semicolon,
+        )
+    }
+
+    /// Parses the optional default of a trait type or constant, whose value is parsed by
+    /// `parse_value`.
+    /// Expected pattern: `[= <value>]`.
+    fn parse_option_trait_item_default(
+        &mut self,
+        parse_value: fn(&mut Self) -> ExprGreen<'a>,
+    ) -> OptionTraitItemDefaultGreen<'a> {
+        if self.peek().kind == SyntaxKind::TerminalEq {
+            let eq = self.take::<TerminalEq<'_>>();
+            let value = parse_value(self);
+            TraitItemDefault::new_green(self.db, eq, value).into()
+        } else {
+            OptionTraitItemDefaultEmpty::new_green(self.db).into()
+        }
     }
 
     /// Assumes the current token is Impl.
@@ -1746,7 +1853,7 @@ impl<'a, 'mt> Parser<'a, 'mt> {
             self.db,
             &self.parse_separated_list::<StructArg<'_>, TerminalComma<'_>, StructArgListElementOrSeparatorGreen<'_>>(
                 Self::try_parse_struct_ctor_argument,
-                is_of_kind!(rparen, block, rbrace, module_item_kw),
+                is_of_kind!(rparen, block, rbrace, semicolon, module_item_kw),
                 "struct constructor argument",
             ),
         );
@@ -2005,7 +2112,7 @@ impl<'a, 'mt> Parser<'a, 'mt> {
         let exprs: Vec<ArgListElementOrSeparatorGreen<'_>> = self
             .parse_separated_list::<Arg<'_>, TerminalComma<'_>, ArgListElementOrSeparatorGreen<'_>>(
                 Self::try_parse_function_argument,
-                is_of_kind!(rparen, rbrace, rbrack, block, module_item_kw),
+                is_of_kind!(rparen, rbrace, rbrack, block, semicolon, module_item_kw),
                 "argument",
             );
         let r_term: <RTerminal as TypedSyntaxNode<'_>>::Green = self.parse_token::<RTerminal>();
@@ -2153,7 +2260,7 @@ impl<'a, 'mt> Parser<'a, 'mt> {
         let exprs: Vec<ExprListElementOrSeparatorGreen<'_>> = self
             .parse_separated_list::<Expr<'_>, TerminalComma<'_>, ExprListElementOrSeparatorGreen<'_>>(
                 Self::try_parse_expr,
-                is_of_kind!(rparen, block, rbrace, module_item_kw),
+                is_of_kind!(rparen, block, rbrace, semicolon, module_item_kw),
                 "expression",
             );
         let rparen = self.parse_token::<TerminalRParen<'_>>();
@@ -2300,8 +2407,8 @@ impl<'a, 'mt> Parser<'a, 'mt> {
         let statements = StatementList::new_green(
             self.db,
             &self.parse_list(
-                Self::try_parse_statement,
-                is_of_kind!(rbrace, module_item_kw),
+                Self::try_parse_block_statement,
+                is_of_kind!(rbrace, module_item_kw, module_only_item_start),
                 "statement",
             ),
         );
@@ -2309,6 +2416,17 @@ impl<'a, 'mt> Parser<'a, 'mt> {
         ExprBlock::new_green(self.db, lbrace, statements, rbrace)
     }
 
+    /// Returns a GreenId of a node with kind Statement or TryParseFailure if a statement can't be
+    /// parsed.
+    /// Fails without consuming any token if the next tokens start an item that can only appear in
+    /// a module, as this most likely means the block is missing its closing brace.
+    fn try_parse_block_statement(&mut self) -> TryParseResult<StatementGreen<'a>> {
+        if self.is_peek_module_only_item() {
+            return Err(TryParseFailure::DoNothing);
+        }
+        self.try_parse_statement()
+    }
+
     /// Assumes the current token is `Match`.
     /// Expected pattern: `match <expr> \{<MatchArm>*\}`
     fn expect_match_expr(&mut self) -> ExprMatchGreen<'a> {
@@ -2549,7 +2667,7 @@ impl<'a, 'mt> Parser<'a, 'mt> {
         let pattern_list = self
             .parse_separated_list_inner::<Pattern<'_>, TerminalOr<'_>, PatternListOrElementOrSeparatorGreen<'_>>(
                 Self::try_parse_pattern,
-                is_of_kind!(match_arrow, rparen, block, rbrace, module_item_kw),
+                is_of_kind!(match_arrow, if_kw, rparen, block, rbrace, module_item_kw),
                 "pattern",
                 Some(ParserDiagnosticKind::DisallowedTrailingSeparatorOr),
             );
@@ -2559,9 +2677,17 @@ impl<'a, 'mt> Parser<'a, 'mt> {
 
         let pattern_list_green = PatternListOr::new_green(self.db, &pattern_list);
 
+        let guard = if self.peek().kind == SyntaxKind::TerminalIf {
+            let if_kw = self.take::<TerminalIf<'_>>();
+            let condition = self.parse_expr();
+            MatchArmGuard::new_green(self.db, if_kw, condition).into()
+        } else {
+            OptionMatchArmGuardEmpty::new_green(self.db).into()
+        };
+
         let arrow = self.parse_token::<TerminalMatchArrow<'_>>();
         let expr = self.parse_expr();
-        Ok(MatchArm::new_green(self.db, pattern_list_green, arrow, expr))
+        Ok(MatchArm::new_green(self.db, pattern_list_green, guard, arrow, expr))
     }
 
     /// Returns a GreenId of a node with some Pattern kind (see
@@ -2576,7 +2702,20 @@ impl<'a, 'mt> Parser<'a, 'mt> {
 
         // TODO(yuval): Support "Or" patterns.
         Ok(match self.peek().kind {
-            SyntaxKind::TerminalLiteralNumber => self.take_terminal_literal_number().into(),
+            SyntaxKind::TerminalLiteralNumber => {
+                let start = self.take_terminal_literal_number();
+                let op: PatternRangeOperatorGreen<'_> = match self.peek().kind {
+                    SyntaxKind::TerminalDotDot => self.take::<TerminalDotDot<'_>>().into(),
+                    SyntaxKind::TerminalDotDotEq => self.take::<TerminalDotDotEq<'_>>().into(),
+                    _ => return Ok(start.into()),
+                };
+                let end = if self.peek().kind == SyntaxKind::TerminalLiteralNumber {
+                    self.take_terminal_literal_number()
+                } else {
+                    self.parse_token::<TerminalLiteralNumber<'_>>()
+                };
+                PatternRange::new_green(self.db, start, op, end).into()
+            }
             SyntaxKind::TerminalShortString => self.take_terminal_short_string().into(),
             SyntaxKind::TerminalTrue => self.take::<TerminalTrue<'_>>().into(),
             SyntaxKind::TerminalFalse => self.take::<TerminalFalse<'_>>().into(),
@@ -2999,6 +3138,10 @@ impl<'a, 'mt> Parser<'a, 'mt> {
     /// Returns a GreenId of a node with kind Member or TryParseFailure if a struct member can't be
     /// parsed.
     fn try_parse_member(&mut self) -> TryParseResult<MemberGreen<'a>> {
+        // A module item most likely means the struct is missing its closing brace.
+        if self.is_peek_module_item_start() {
+            return Err(TryParseFailure::SkipToken);
+        }
         let attributes = self.try_parse_attribute_list("Struct member");
         let visibility = self.parse_visibility();
         let (name, attributes) = match attributes {
@@ -3027,6 +3170,10 @@ impl<'a, 'mt> Parser<'a, 'mt> {
     /// Returns a GreenId of a node with kind Variant or TryParseFailure if an enum variant can't be
     /// parsed.
     fn try_parse_variant(&mut self) -> TryParseResult<VariantGreen<'a>> {
+        // A module item most likely means the enum is missing its closing brace.
+        if self.is_peek_module_item_start() {
+            return Err(TryParseFailure::SkipToken);
+        }
         let attributes = self.try_parse_attribute_list("Enum variant");
         let (name, attributes) = match attributes {
             Ok(attributes) => (self.parse_identifier(), attributes),
@@ -3246,7 +3393,7 @@ impl<'a, 'mt> Parser<'a, 'mt> {
             self.db,
             &self.parse_separated_list::<GenericArg<'_>, TerminalComma<'_>, GenericArgListElementOrSeparatorGreen<'_>>(
                 Self::try_parse_generic_arg,
-                is_of_kind!(rangle, rparen, block, lbrace, rbrace, module_item_kw),
+                is_of_kind!(rangle, rparen, block, lbrace, rbrace, semicolon, module_item_kw),
                 "generic arg",
             ),
         );
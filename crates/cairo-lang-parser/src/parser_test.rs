@@ -159,6 +159,7 @@ cairo_lang_test_utils::test_file_test!(
         path: "path",
         pattern: "pattern",
         question_mark: "question_mark",
+        recovery: "recovery",
         reserved_identifier: "reserved_identifier",
         semicolon: "semicolon",
         skipped_tokens: "skipped_tokens",
@@ -201,6 +202,7 @@ cairo_lang_test_utils::test_file_test!(
         while_: "while",
         for_: "for",
         range: "range",
+        recovery: "recovery",
         repr_ptr: "repr_ptr",
         use_: "use",
         type_alias: "type_alias",
@@ -20,6 +20,13 @@ macro_rules! match_arrow {
 }
 pub(crate) use match_arrow;
 
+macro_rules! if_kw {
+    () => {
+        SyntaxKind::TerminalIf
+    };
+}
+pub(crate) use if_kw;
+
 macro_rules! lbrace {
     () => {
         SyntaxKind::TerminalLBrace
@@ -99,6 +106,13 @@ macro_rules! module_item_kw {
 }
 pub(crate) use module_item_kw;
 
+macro_rules! module_only_item_start {
+    () => {
+        SyntaxKind::TerminalPub | SyntaxKind::TerminalHash
+    };
+}
+pub(crate) use module_only_item_start;
+
 macro_rules! block {
     () => {
         SyntaxKind::TerminalLet | SyntaxKind::TerminalMatch | SyntaxKind::TerminalReturn
@@ -0,0 +1,116 @@
+use cairo_lang_diagnostics::{Diagnostics, DiagnosticsBuilder};
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::span::{TextEdit, TextSpan, TextWidth};
+use cairo_lang_syntax::node::ast::{
+    ModuleItemGreen, ModuleItemList, SyntaxFile, TerminalEndOfFileGreen,
+};
+use cairo_lang_syntax::node::ids::GreenId;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode};
+use salsa::Database;
+
+use crate::ParserDiagnostic;
+use crate::parser::Parser;
+
+/// Reparses a module file after an edit, reusing the syntax of the module items the edit does not
+/// touch.
+///
+/// Only the module item containing the edit is reparsed, and the diagnostics of the other items
+/// are kept, shifted to their new location. Returns `None` if the edit is not strictly inside a
+/// single module item, or if the reparsed item may be parsed differently in the context of the
+/// whole file. In this case the file should be parsed in full.
+pub(crate) fn reparse_file<'db>(
+    db: &'db dyn Database,
+    file_id: FileId<'db>,
+    previous: SyntaxFile<'db>,
+    previous_diagnostics: &Diagnostics<'db, ParserDiagnostic<'db>>,
+    edit: &TextEdit,
+    content: &str,
+) -> Option<(SyntaxFile<'db>, Diagnostics<'db, ParserDiagnostic<'db>>)> {
+    let items = previous.items(db);
+    let (index, item) = items.elements(db).enumerate().find(|(_, item)| {
+        let span = item.as_syntax_node().span_without_trivia(db);
+        span.start < edit.span.start && edit.span.end < span.end
+    })?;
+    let previous_span = item.as_syntax_node().span(db);
+    let new_span = TextSpan::new(
+        previous_span.start,
+        previous_span
+            .end
+            .sub_width(edit.span.width())
+            .add_width(TextWidth::from_str(&edit.new_text)),
+    );
+
+    let mut item_diagnostics = DiagnosticsBuilder::default();
+    let reparsed = Parser::parse_file_fragment(
+        db,
+        &mut item_diagnostics,
+        file_id,
+        new_span.take(content),
+        new_span.start,
+    );
+    let [reparsed_items, reparsed_eof] = reparsed.0.long(db).children() else {
+        unreachable!("A syntax file has exactly two children.");
+    };
+    let [new_item] = reparsed_items.long(db).children() else {
+        return None;
+    };
+    if reparsed_eof.long(db).width(db) != TextWidth::ZERO
+        || !ends_with_closing_terminal(db, *new_item)
+    {
+        return None;
+    }
+
+    // Split the previous diagnostics between the items before and after the reparsed item.
+    let mut diagnostics_before = DiagnosticsBuilder::default();
+    let mut diagnostics_after = DiagnosticsBuilder::default();
+    for diagnostic in previous_diagnostics.get_all() {
+        let span = diagnostic.span;
+        if span.end <= previous_span.start {
+            diagnostics_before.add(diagnostic);
+        } else if span.start >= previous_span.end {
+            let span = TextSpan::new(
+                new_span.end.add_width(span.start - previous_span.end),
+                new_span.end.add_width(span.end - previous_span.end),
+            );
+            diagnostics_after.add(ParserDiagnostic { span, ..diagnostic });
+        } else if !previous_span.contains(span) {
+            // A diagnostic crossing the bounds of the item may not be reported the same way.
+            return None;
+        }
+    }
+    let mut diagnostics = diagnostics_before;
+    diagnostics.extend(item_diagnostics.build());
+    diagnostics.extend(diagnostics_after.build());
+
+    let [previous_items, previous_eof] = previous.as_syntax_node().green_node(db).children() else {
+        unreachable!("A syntax file has exactly two children.");
+    };
+    let mut item_greens: Vec<ModuleItemGreen<'db>> =
+        previous_items.long(db).children().iter().map(|green| ModuleItemGreen(*green)).collect();
+    item_greens[index] = ModuleItemGreen(*new_item);
+    let green = SyntaxFile::new_green(
+        db,
+        ModuleItemList::new_green(db, &item_greens),
+        TerminalEndOfFileGreen(*previous_eof),
+    );
+    let syntax = SyntaxFile::from_syntax_node(db, SyntaxNode::new_root(db, file_id, green.0));
+    Some((syntax, diagnostics.build()))
+}
+
+/// Returns whether the last terminal of the node is a present `}` or `;`. The parsing of such a
+/// node does not depend on the text following it.
+fn ends_with_closing_terminal<'db>(db: &'db dyn Database, mut green: GreenId<'db>) -> bool {
+    loop {
+        let node = green.long(db);
+        if node.kind.is_terminal() {
+            let token = node.children()[1].long(db);
+            return matches!(token.kind, SyntaxKind::TokenRBrace | SyntaxKind::TokenSemicolon)
+                && token.width(db) != TextWidth::ZERO;
+        }
+        let Some(last_child) = node.children().last() else {
+            return false;
+        };
+        green = *last_child;
+    }
+}
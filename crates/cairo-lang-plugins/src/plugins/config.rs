@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use cairo_lang_defs::patcher::PatchBuilder;
 use cairo_lang_defs::plugin::{
     MacroPlugin, MacroPluginMetadata, PluginDiagnostic, PluginGeneratedFile, PluginResult,
@@ -38,6 +40,24 @@ impl PredicateTree {
     }
 }
 
+/// The `#[cfg(...)]` names a crate is expected to check, for reporting unexpected ones.
+#[derive(Clone, Copy)]
+struct ExpectedCfgNames<'a> {
+    /// The configuration of the crate - names of its options are always expected.
+    cfg_set: &'a CfgSet,
+    /// The names declared as expected in the crate settings.
+    declared: &'a BTreeSet<String>,
+}
+
+impl ExpectedCfgNames<'_> {
+    /// Returns whether checking `name` is expected.
+    fn contains(&self, name: &str) -> bool {
+        WELL_KNOWN_CFG_NAMES.contains(&name)
+            || self.declared.contains(name)
+            || self.cfg_set.iter().any(|cfg| cfg.key == name)
+    }
+}
+
 /// Represents a part of a configuration predicate.
 pub enum ConfigPredicatePart<'db> {
     /// A configuration item, either a key-value pair or a simple name.
@@ -56,6 +76,9 @@ pub struct ConfigPlugin;
 
 const CFG_ATTR: &str = "cfg";
 
+/// Names of `#[cfg(...)]` options set by the compiler tools, which are always expected.
+const WELL_KNOWN_CFG_NAMES: [&str; 3] = ["test", "target", "gas"];
+
 impl MacroPlugin for ConfigPlugin {
     fn generate_code<'db>(
         &self,
@@ -64,11 +87,14 @@ impl MacroPlugin for ConfigPlugin {
         metadata: &MacroPluginMetadata<'_>,
     ) -> PluginResult<'db> {
         let mut diagnostics = vec![];
+        let expected_names = metadata
+            .expected_cfg_names
+            .map(|declared| ExpectedCfgNames { cfg_set: metadata.cfg_set, declared });
 
-        if should_drop(db, metadata.cfg_set, &item_ast, &mut diagnostics) {
+        if should_drop(db, metadata.cfg_set, expected_names, &item_ast, &mut diagnostics) {
             PluginResult { code: None, diagnostics, remove_original_item: true }
         } else if let Some(builder) =
-            handle_undropped_item(db, metadata.cfg_set, item_ast, &mut diagnostics)
+            handle_undropped_item(db, metadata.cfg_set, expected_names, item_ast, &mut diagnostics)
         {
             let (content, code_mappings) = builder.build();
             PluginResult {
@@ -110,7 +136,7 @@ impl<'a, Item: QueryAttrs<'a>, Body: BodyItems<'a, Item = Item>> HasItemsInCfgEx
         db: &'a dyn Database,
         cfg_set: &CfgSet,
     ) -> impl Iterator<Item = Item> {
-        self.iter_items(db).filter(move |item| !should_drop(db, cfg_set, item, &mut vec![]))
+        self.iter_items(db).filter(move |item| !should_drop(db, cfg_set, None, item, &mut vec![]))
     }
 }
 
@@ -120,13 +146,20 @@ impl<'a, Item: QueryAttrs<'a>, Body: BodyItems<'a, Item = Item>> HasItemsInCfgEx
 fn handle_undropped_item<'a>(
     db: &'a dyn Database,
     cfg_set: &CfgSet,
+    expected_names: Option<ExpectedCfgNames<'_>>,
     item_ast: ast::ModuleItem<'a>,
     diagnostics: &mut Vec<PluginDiagnostic<'a>>,
 ) -> Option<PatchBuilder<'a>> {
     match item_ast {
         ast::ModuleItem::Trait(trait_item) => {
             let body = try_extract_matches!(trait_item.body(db), ast::MaybeTraitBody::Some)?;
-            let items = get_kept_items_nodes(db, cfg_set, body.iter_items(db), diagnostics)?;
+            let items = get_kept_items_nodes(
+                db,
+                cfg_set,
+                expected_names,
+                body.iter_items(db),
+                diagnostics,
+            )?;
             let mut builder = PatchBuilder::new(db, &trait_item);
             builder.add_node(trait_item.attributes(db).as_syntax_node());
             builder.add_node(trait_item.visibility(db).as_syntax_node());
@@ -142,7 +175,13 @@ fn handle_undropped_item<'a>(
         }
         ast::ModuleItem::Impl(impl_item) => {
             let body = try_extract_matches!(impl_item.body(db), ast::MaybeImplBody::Some)?;
-            let items = get_kept_items_nodes(db, cfg_set, body.iter_items(db), diagnostics)?;
+            let items = get_kept_items_nodes(
+                db,
+                cfg_set,
+                expected_names,
+                body.iter_items(db),
+                diagnostics,
+            )?;
             let mut builder = PatchBuilder::new(db, &impl_item);
             builder.add_node(impl_item.attributes(db).as_syntax_node());
             builder.add_node(impl_item.visibility(db).as_syntax_node());
@@ -167,13 +206,14 @@ fn handle_undropped_item<'a>(
 fn get_kept_items_nodes<'a, Item: QueryAttrs<'a> + TypedSyntaxNode<'a>>(
     db: &'a dyn Database,
     cfg_set: &CfgSet,
+    expected_names: Option<ExpectedCfgNames<'_>>,
     all_items: impl Iterator<Item = Item>,
     diagnostics: &mut Vec<PluginDiagnostic<'a>>,
 ) -> Option<Vec<cairo_lang_syntax::node::SyntaxNode<'a>>> {
     let mut any_dropped = false;
     let mut kept_items_nodes = vec![];
     for item in all_items {
-        if should_drop(db, cfg_set, &item, diagnostics) {
+        if should_drop(db, cfg_set, expected_names, &item, diagnostics) {
             any_dropped = true;
         } else {
             kept_items_nodes.push(item.as_syntax_node());
@@ -183,14 +223,16 @@ fn get_kept_items_nodes<'a, Item: QueryAttrs<'a> + TypedSyntaxNode<'a>>(
 }
 
 /// Checks if the given item should be dropped from the AST.
+/// If `expected_names` is given, checking any other name is reported.
 fn should_drop<'a, Item: QueryAttrs<'a>>(
     db: &'a dyn Database,
     cfg_set: &CfgSet,
+    expected_names: Option<ExpectedCfgNames<'_>>,
     item: &Item,
     diagnostics: &mut Vec<PluginDiagnostic<'a>>,
 ) -> bool {
     item.query_attr(db, CFG_ATTR).any(|attr| {
-        match parse_predicate(db, attr.structurize(db), diagnostics) {
+        match parse_predicate(db, attr.structurize(db), expected_names, diagnostics) {
             Some(predicate_tree) => !predicate_tree.evaluate(cfg_set),
             None => false,
         }
@@ -201,12 +243,13 @@ fn should_drop<'a, Item: QueryAttrs<'a>>(
 fn parse_predicate<'a>(
     db: &'a dyn Database,
     attr: Attribute<'a>,
+    expected_names: Option<ExpectedCfgNames<'_>>,
     diagnostics: &mut Vec<PluginDiagnostic<'a>>,
 ) -> Option<PredicateTree> {
     Some(PredicateTree::And(
         attr.args
             .into_iter()
-            .filter_map(|arg| parse_predicate_item(db, arg, diagnostics))
+            .filter_map(|arg| parse_predicate_item(db, arg, expected_names, diagnostics))
             .collect(),
     ))
 }
@@ -215,10 +258,19 @@ fn parse_predicate<'a>(
 fn parse_predicate_item<'a>(
     db: &'a dyn Database,
     item: AttributeArg<'a>,
+    expected_names: Option<ExpectedCfgNames<'_>>,
     diagnostics: &mut Vec<PluginDiagnostic<'a>>,
 ) -> Option<PredicateTree> {
     match extract_config_predicate_part(db, &item) {
-        Some(ConfigPredicatePart::Cfg(cfg)) => Some(PredicateTree::Cfg(cfg)),
+        Some(ConfigPredicatePart::Cfg(cfg)) => {
+            if expected_names.is_some_and(|expected_names| !expected_names.contains(&cfg.key)) {
+                diagnostics.push(PluginDiagnostic::warning(
+                    item.arg.stable_ptr(db).untyped(),
+                    format!("Unexpected `cfg` name: `{}`.", cfg.key),
+                ));
+            }
+            Some(PredicateTree::Cfg(cfg))
+        }
         Some(ConfigPredicatePart::Call(call)) => {
             let operator = call.path(db).as_syntax_node().get_text(db);
             let args = call
@@ -240,6 +292,7 @@ fn parse_predicate_item<'a>(
                         Some(PredicateTree::Not(Box::new(parse_predicate_item(
                             db,
                             args[0].clone(),
+                            expected_names,
                             diagnostics,
                         )?)))
                     }
@@ -254,7 +307,9 @@ fn parse_predicate_item<'a>(
                     } else {
                         Some(PredicateTree::And(
                             args.into_iter()
-                                .filter_map(|arg| parse_predicate_item(db, arg, diagnostics))
+                                .filter_map(|arg| {
+                                    parse_predicate_item(db, arg, expected_names, diagnostics)
+                                })
                                 .collect(),
                         ))
                     }
@@ -269,7 +324,9 @@ fn parse_predicate_item<'a>(
                     } else {
                         Some(PredicateTree::Or(
                             args.into_iter()
-                                .filter_map(|arg| parse_predicate_item(db, arg, diagnostics))
+                                .filter_map(|arg| {
+                                    parse_predicate_item(db, arg, expected_names, diagnostics)
+                                })
                                 .collect(),
                         ))
                     }
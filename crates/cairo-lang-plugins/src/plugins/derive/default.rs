@@ -1,4 +1,7 @@
 use cairo_lang_defs::plugin::PluginDiagnostic;
+use cairo_lang_syntax::attribute::structured::{
+    AttributeArg, AttributeArgVariant, AttributeStructurize,
+};
 use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::{TypedSyntaxNode, ast};
 use indoc::formatdoc;
@@ -6,7 +9,7 @@ use itertools::{Itertools, chain};
 use salsa::Database;
 
 use super::PluginTypeInfo;
-use crate::plugins::utils::TypeVariant;
+use crate::plugins::utils::{MemberInfo, TypeVariant};
 
 pub const DEFAULT_ATTR: &str = "default";
 
@@ -63,7 +66,26 @@ pub fn handle_default<'db>(
             })
         }
         TypeVariant::Struct => {
-            let header = info.impl_header(DEFAULT_TRAIT, &[DEFAULT_TRAIT, DESTRUCT_TRAIT]);
+            let default_values: Vec<Option<String>> = info
+                .members_info
+                .iter()
+                .map(|member| member_default_value(db, member, diagnostics))
+                .collect();
+            let generics = chain!(
+                info.generics.full_params.iter().map(ToString::to_string),
+                info.members_info
+                    .iter()
+                    .zip(&default_values)
+                    .filter(|(m, _)| m.is_generics_dependent)
+                    .flat_map(|(m, default_value)| {
+                        // Members with an explicit default value do not require `Default`.
+                        chain!(default_value.is_none().then_some(DEFAULT_TRAIT), [DESTRUCT_TRAIT])
+                            .map(|trt| format!("impl {}: {trt}<{}>", m.impl_name(trt), m.ty))
+                    })
+            )
+            .join(", ");
+            let header =
+                format!("impl {ty}Default<{generics}> of {DEFAULT_TRAIT}::<{full_typename}>");
             Some(formatdoc! {"
                 {header} {{
                     fn default() -> {full_typename} {{
@@ -74,12 +96,15 @@ pub fn handle_default<'db>(
                     }}
                 }}
                 ",
-                info.members_info.iter().map(|member| {
+                info.members_info.iter().zip(&default_values).map(|(member, default_value)| {
+                    let value = match default_value {
+                        Some(value) => value.clone(),
+                        None => format!("{}::default()", member.impl_name(DEFAULT_TRAIT)),
+                    };
                     format!(
-                        "let {member} = {destruct_with} {{ value: {imp}::default() }};",
+                        "let {member} = {destruct_with} {{ value: {value} }};",
                         member=member.name,
                         destruct_with=member.destruct_with(),
-                        imp=member.impl_name(DEFAULT_TRAIT),
                     )
                 }).join("\n        "),
                 info.members_info.iter().map(|member| {
@@ -89,3 +114,34 @@ pub fn handle_default<'db>(
         }
     }
 }
+
+/// Returns the explicit default value of a struct member, given by `#[default(<value>)]`,
+/// reporting invalid `default` attributes.
+fn member_default_value<'db>(
+    db: &'db dyn Database,
+    member: &MemberInfo<'db>,
+    diagnostics: &mut Vec<PluginDiagnostic<'db>>,
+) -> Option<String> {
+    let mut default_value = None;
+    for attr in member.attributes.query_attr(db, DEFAULT_ATTR) {
+        let attr = attr.structurize(db);
+        match &attr.args[..] {
+            [AttributeArg { variant: AttributeArgVariant::Unnamed(value), .. }] => {
+                if default_value.is_some() {
+                    diagnostics.push(PluginDiagnostic::error(
+                        attr.stable_ptr,
+                        "Multiple default values for a struct member.".into(),
+                    ));
+                    continue;
+                }
+                default_value =
+                    Some(value.as_syntax_node().get_text_without_trivia(db).long(db).to_string());
+            }
+            _ => diagnostics.push(PluginDiagnostic::error(
+                attr.args_stable_ptr,
+                "Expected a single default value.".into(),
+            )),
+        }
+    }
+    default_value
+}
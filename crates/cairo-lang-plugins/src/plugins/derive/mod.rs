@@ -20,6 +20,7 @@ mod destruct;
 mod hash;
 mod panic_destruct;
 mod partial_eq;
+mod partial_ord;
 mod serde;
 
 #[derive(Debug, Default)]
@@ -58,7 +59,11 @@ impl MacroPlugin for DerivePlugin {
     }
 
     fn declared_attributes<'db>(&self, db: &'db dyn Database) -> Vec<SmolStrId<'db>> {
-        vec![SmolStrId::from(db, DERIVE_ATTR), SmolStrId::from(db, default::DEFAULT_ATTR)]
+        vec![
+            SmolStrId::from(db, DERIVE_ATTR),
+            SmolStrId::from(db, default::DEFAULT_ATTR),
+            SmolStrId::from(db, serde::SERDE_ATTR),
+        ]
     }
 
     fn declared_derives<'db>(&self, db: &'db dyn Database) -> Vec<SmolStrId<'db>> {
@@ -72,6 +77,7 @@ impl MacroPlugin for DerivePlugin {
             SmolStrId::from(db, "Hash"),
             SmolStrId::from(db, "PanicDestruct"),
             SmolStrId::from(db, "PartialEq"),
+            SmolStrId::from(db, "PartialOrd"),
             SmolStrId::from(db, "Serde"),
         ]
     }
@@ -117,7 +123,10 @@ fn generate_derive_code_for_type<'db>(
                 "Hash" => Some(hash::handle_hash(&info)),
                 "PanicDestruct" => Some(panic_destruct::handle_panic_destruct(&info)),
                 "PartialEq" => Some(partial_eq::handle_partial_eq(&info, member_access_desnaps)),
-                "Serde" => Some(serde::handle_serde(&info, member_access_desnaps)),
+                "PartialOrd" => Some(partial_ord::handle_partial_ord(&info)),
+                "Serde" => {
+                    Some(serde::handle_serde(db, &info, member_access_desnaps, &mut diagnostics))
+                }
                 _ => {
                     if !metadata.declared_derives.contains(&derived) {
                         diagnostics.push(PluginDiagnostic::error(
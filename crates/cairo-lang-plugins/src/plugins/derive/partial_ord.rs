@@ -0,0 +1,106 @@
+use indent::indent_by;
+use indoc::formatdoc;
+use itertools::Itertools;
+
+use super::PluginTypeInfo;
+use crate::plugins::utils::TypeVariant;
+
+/// Adds derive result for the `PartialOrd` trait.
+///
+/// Structs are compared lexicographically by their members, in declaration order. Enums are
+/// compared by the order of their variants, and then by the values of the variants.
+pub fn handle_partial_ord(info: &PluginTypeInfo<'_>) -> String {
+    const PARTIAL_ORD_TRAIT: &str = "core::traits::PartialOrd";
+    const PARTIAL_EQ_TRAIT: &str = "core::traits::PartialEq";
+    const DESTRUCT_TRAIT: &str = "core::traits::Destruct";
+    let full_typename = info.full_typename();
+    let ty = &info.name;
+    let (header, body) = match &info.type_variant {
+        TypeVariant::Enum => {
+            let header = info.impl_header(PARTIAL_ORD_TRAIT, &[PARTIAL_ORD_TRAIT, DESTRUCT_TRAIT]);
+            let body = formatdoc! {"
+                match lhs {{
+                    {}
+                }}",
+                info.members_info.iter().enumerate().map(|(lhs_idx, lhs_variant)| {
+                    // There is no `PartialOrd` for the unit type, and unit values are always equal.
+                    let is_unit = lhs_variant.ty == "()";
+                    let x = if is_unit { "_x" } else { "x" };
+                    indent_by(4, formatdoc! {"
+                        {ty}::{lhs_variant}({x}) => match rhs {{
+                            {}
+                        }},",
+                        info.members_info.iter().enumerate().map(|(rhs_idx, rhs_variant)| {
+                            let rhs_name = &rhs_variant.name;
+                            if lhs_idx != rhs_idx {
+                                format!("{ty}::{rhs_name}(_y) => {},", lhs_idx < rhs_idx)
+                            } else if is_unit {
+                                format!("{ty}::{rhs_name}(_y) => false,")
+                            } else {
+                                format!(
+                                    "{ty}::{rhs_name}(y) => {}::lt(x, y),",
+                                    rhs_variant.impl_name(PARTIAL_ORD_TRAIT)
+                                )
+                            }
+                        }).join("\n    "),
+                        lhs_variant=lhs_variant.name,
+                    })
+                }).join("\n    ")
+            };
+            (header, body)
+        }
+        TypeVariant::Struct => {
+            let header = info.impl_header(
+                PARTIAL_ORD_TRAIT,
+                &[PARTIAL_ORD_TRAIT, PARTIAL_EQ_TRAIT, DESTRUCT_TRAIT],
+            );
+            let Some((last, members)) = info.members_info.split_last() else {
+                return formatdoc! {"
+                    {header} {{
+                        fn lt(lhs: {full_typename}, rhs: {full_typename}) -> bool {{
+                            false
+                        }}
+                    }}
+                "};
+            };
+            let destructure = |side: &str| {
+                format!(
+                    "let {ty} {{ {} }} = {side};",
+                    info.members_info
+                        .iter()
+                        .map(|member| format!("{member}: {side}_{member}", member = member.name))
+                        .join(", ")
+                )
+            };
+            let body = formatdoc! {"
+                {}
+                {}
+                {}{}::lt(lhs_{last}, rhs_{last})",
+                destructure("lhs"),
+                destructure("rhs"),
+                members.iter().map(|member| {
+                    formatdoc! {"
+                        if !{eq_imp}::eq(@lhs_{member}, @rhs_{member}) {{
+                            return {ord_imp}::lt(lhs_{member}, rhs_{member});
+                        }}
+                        ",
+                        eq_imp=member.impl_name(PARTIAL_EQ_TRAIT),
+                        ord_imp=member.impl_name(PARTIAL_ORD_TRAIT),
+                        member=member.name,
+                    }
+                }).join(""),
+                last.impl_name(PARTIAL_ORD_TRAIT),
+                last=last.name,
+            };
+            (header, body)
+        }
+    };
+    let body = indent_by(8, body);
+    formatdoc! {"
+        {header} {{
+            fn lt(lhs: {full_typename}, rhs: {full_typename}) -> bool {{
+                {body}
+            }}
+        }}
+    "}
+}
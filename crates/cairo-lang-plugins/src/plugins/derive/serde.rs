@@ -1,16 +1,38 @@
+use cairo_lang_defs::plugin::PluginDiagnostic;
+use cairo_lang_syntax::attribute::structured::{AttributeArgVariant, AttributeStructurize};
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::{TypedSyntaxNode, ast};
 use indent::indent_by;
 use indoc::formatdoc;
-use itertools::Itertools;
+use itertools::{Itertools, chain};
+use salsa::Database;
 
 use super::PluginTypeInfo;
-use crate::plugins::utils::TypeVariant;
+use crate::plugins::utils::{MemberInfo, TypeVariant};
+
+pub const SERDE_ATTR: &str = "serde";
+const SKIP_ARG: &str = "skip";
 
 /// Adds derive result for the `Serde` trait.
-pub fn handle_serde(info: &PluginTypeInfo<'_>, member_access_desnaps: bool) -> String {
+///
+/// Struct members marked with `#[serde(skip)]` are not serialized, and are deserialized using
+/// their `Default` implementation.
+pub fn handle_serde<'db>(
+    db: &'db dyn Database,
+    info: &PluginTypeInfo<'db>,
+    member_access_desnaps: bool,
+    diagnostics: &mut Vec<PluginDiagnostic<'db>>,
+) -> String {
     const SERDE_TRAIT: &str = "core::serde::Serde";
+    const DEFAULT_TRAIT: &str = "core::traits::Default";
     const DESTRUCT_TRAIT: &str = "core::traits::Destruct";
     let ty = &info.name;
     let full_typename = info.full_typename();
+    let skipped: Vec<bool> = info
+        .members_info
+        .iter()
+        .map(|member| is_skipped(db, member, &info.type_variant, diagnostics))
+        .collect();
     let header = match &info.type_variant {
         TypeVariant::Enum => {
             let impl_additional_generics =
@@ -21,7 +43,22 @@ pub fn handle_serde(info: &PluginTypeInfo<'_>, member_access_desnaps: bool) -> S
                 > of {SERDE_TRAIT}<{full_typename}>
             "}
         }
-        TypeVariant::Struct => info.impl_header(SERDE_TRAIT, &[SERDE_TRAIT, DESTRUCT_TRAIT]),
+        TypeVariant::Struct => {
+            let generics = chain!(
+                info.generics.full_params.iter().map(ToString::to_string),
+                info.members_info
+                    .iter()
+                    .zip(&skipped)
+                    .filter(|(m, _)| m.is_generics_dependent)
+                    .flat_map(|(m, is_skipped)| {
+                        let main_trait = if *is_skipped { DEFAULT_TRAIT } else { SERDE_TRAIT };
+                        [main_trait, DESTRUCT_TRAIT]
+                            .map(|trt| format!("impl {}: {trt}<{}>", m.impl_name(trt), m.ty))
+                    })
+            )
+            .join(", ");
+            format!("impl {ty}Serde<{generics}> of {SERDE_TRAIT}::<{full_typename}>")
+        }
     };
     let serialize_body = indent_by(
         8,
@@ -43,7 +80,9 @@ pub fn handle_serde(info: &PluginTypeInfo<'_>, member_access_desnaps: bool) -> S
             TypeVariant::Struct => info
                 .members_info
                 .iter()
-                .map(|member| {
+                .zip(&skipped)
+                .filter(|(_, is_skipped)| !**is_skipped)
+                .map(|(member, _)| {
                     let imp = member.impl_name(SERDE_TRAIT);
                     let member = &member.name;
                     if member_access_desnaps {
@@ -83,12 +122,18 @@ pub fn handle_serde(info: &PluginTypeInfo<'_>, member_access_desnaps: bool) -> S
                     core::option::Option::Some({ty} {{
                         {}
                     }})",
-                    info.members_info.iter().map(|member|format!(
-                        "let {member} = {destruct_with} {{ value: {imp}::deserialize(ref serialized)? }};",
-                        member=member.name,
-                        destruct_with=member.destruct_with(),
-                        imp=member.impl_name(SERDE_TRAIT),
-                    )).join("\n"),
+                    info.members_info.iter().zip(&skipped).map(|(member, is_skipped)| {
+                        let value = if *is_skipped {
+                            format!("{}::default()", member.impl_name(DEFAULT_TRAIT))
+                        } else {
+                            format!("{}::deserialize(ref serialized)?", member.impl_name(SERDE_TRAIT))
+                        };
+                        format!(
+                            "let {member} = {destruct_with} {{ value: {value} }};",
+                            member=member.name,
+                            destruct_with=member.destruct_with(),
+                        )
+                    }).join("\n"),
                     info.members_info.iter().map(|member|format!(
                         "{member}: {member}.value,", member=member.name
                     )).join("\n    "),
@@ -107,3 +152,41 @@ pub fn handle_serde(info: &PluginTypeInfo<'_>, member_access_desnaps: bool) -> S
         }}
     "}
 }
+
+/// Returns whether a member is marked with `#[serde(skip)]`, reporting invalid `serde` attributes.
+fn is_skipped<'db>(
+    db: &'db dyn Database,
+    member: &MemberInfo<'db>,
+    type_variant: &TypeVariant,
+    diagnostics: &mut Vec<PluginDiagnostic<'db>>,
+) -> bool {
+    let mut skipped = false;
+    for attr in member.attributes.query_attr(db, SERDE_ATTR) {
+        if matches!(type_variant, TypeVariant::Enum) {
+            diagnostics.push(PluginDiagnostic::error(
+                attr.stable_ptr(db),
+                "`serde` attributes are only supported on struct members.".into(),
+            ));
+            continue;
+        }
+        let attr = attr.structurize(db);
+        if attr.args.is_empty() {
+            diagnostics
+                .push(PluginDiagnostic::error(attr.args_stable_ptr, "Expected args.".into()));
+        }
+        for arg in attr.args {
+            match &arg.variant {
+                AttributeArgVariant::Unnamed(ast::Expr::Path(path))
+                    if path.as_syntax_node().get_text_without_trivia(db).long(db) == SKIP_ARG =>
+                {
+                    skipped = true;
+                }
+                _ => diagnostics.push(PluginDiagnostic::error(
+                    arg.arg.stable_ptr(db),
+                    format!("Unsupported `serde` argument, expected `{SKIP_ARG}`."),
+                )),
+            }
+        }
+    }
+    skipped
+}
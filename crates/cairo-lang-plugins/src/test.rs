@@ -114,11 +114,10 @@ pub fn test_expand_plugin_inner(
     let db_ref: &mut dyn salsa::Database = &mut db;
     let crate_id = CrateId::plain(db_ref, SmolStrId::from(db_ref, "test"));
     let root = Directory::Real("test_src".into());
-    cairo_lang_filesystem::set_crate_config!(
-        db_ref,
-        crate_id,
-        Some(CrateConfiguration::default_for_root(root))
-    );
+    let mut config = CrateConfiguration::default_for_root(root);
+    config.settings.expected_cfg_names =
+        inputs.get("expected_cfg_names").map(|s| serde_json::from_str(s.as_str()).unwrap());
+    cairo_lang_filesystem::set_crate_config!(db_ref, crate_id, Some(config));
 
     // Main module file.
     let file_id = FileLongId::OnDisk("test_src/lib.cairo".into()).intern(db_ref);
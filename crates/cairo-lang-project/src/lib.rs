@@ -5,6 +5,7 @@ mod test;
 use std::path::{Path, PathBuf};
 
 use cairo_lang_filesystem::db::{CrateIdentifier, CrateSettings};
+use cairo_lang_filesystem::flag::CompilerFlags;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use serde::{Deserialize, Serialize};
 
@@ -31,7 +32,15 @@ pub struct ProjectConfig {
 /// Contents of a Cairo project config file.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProjectConfigContent {
+    /// The crates to compile. Dependencies should not be listed here, so that unused code is
+    /// eliminated. If not set, all the crates in `crate_roots` are compiled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub main_crates: Option<Vec<CrateIdentifier>>,
     pub crate_roots: OrderedHashMap<CrateIdentifier, PathBuf>,
+    /// The compilation flags of the project. Flags that may differ between crates can be
+    /// overridden in the crates' configurations.
+    #[serde(default, skip_serializing_if = "CompilerFlags::is_empty")]
+    pub flags: CompilerFlags,
     /// Additional configurations for the crates.
     #[serde(default)]
     #[serde(rename = "config")]
@@ -58,6 +67,58 @@ impl AllCratesConfig {
 }
 
 impl ProjectConfig {
+    /// Creates a project config without crates, with paths relative to `base_path`.
+    pub fn new(base_path: impl Into<PathBuf>) -> Self {
+        ProjectConfig {
+            base_path: base_path.into(),
+            content: ProjectConfigContent {
+                main_crates: None,
+                crate_roots: Default::default(),
+                flags: Default::default(),
+                crates_config: Default::default(),
+            },
+        }
+    }
+
+    /// Adds a crate rooted at `root` to the project.
+    /// If `settings` are given, they override the global settings for this crate.
+    pub fn with_crate(
+        mut self,
+        crate_identifier: impl Into<CrateIdentifier>,
+        root: impl Into<PathBuf>,
+        settings: Option<CrateSettings>,
+    ) -> Self {
+        let crate_identifier = crate_identifier.into();
+        if let Some(settings) = settings {
+            self.content.crates_config.override_map.insert(crate_identifier.clone(), settings);
+        }
+        self.content.crate_roots.insert(crate_identifier, root.into());
+        self
+    }
+
+    /// Sets the settings of the crates without an override.
+    pub fn with_global_settings(mut self, settings: CrateSettings) -> Self {
+        self.content.crates_config.global = settings;
+        self
+    }
+
+    /// Sets the crates to compile. The rest of the crates are only compiled as dependencies.
+    pub fn with_main_crates(
+        mut self,
+        main_crates: impl IntoIterator<Item = impl Into<CrateIdentifier>>,
+    ) -> Self {
+        self.content.main_crates = Some(main_crates.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns the identifiers of the crates to compile.
+    pub fn main_crates(&self) -> Vec<&CrateIdentifier> {
+        match &self.content.main_crates {
+            Some(main_crates) => main_crates.iter().collect(),
+            None => self.content.crate_roots.keys().collect(),
+        }
+    }
+
     pub fn from_directory(directory: &Path) -> Result<Self, DeserializationError> {
         Self::from_file(&directory.join(PROJECT_FILE_NAME))
     }
@@ -1,12 +1,16 @@
+use std::path::{Path, PathBuf};
+
 use cairo_lang_filesystem::db::{CrateSettings, Edition, ExperimentalFeaturesConfig};
+use cairo_lang_filesystem::flag::CompilerFlags;
 use indoc::indoc;
 use pretty_assertions::assert_eq;
 
-use crate::{AllCratesConfig, ProjectConfigContent};
+use crate::{AllCratesConfig, ProjectConfig, ProjectConfigContent};
 
 #[test]
 fn test_serde() {
     let config = ProjectConfigContent {
+        main_crates: None,
         crate_roots: [
             ("crate1".into(), "dir1".into()),
             ("crate2".into(), "dir2".into()),
@@ -14,6 +18,7 @@ fn test_serde() {
         ]
         .into_iter()
         .collect(),
+        flags: CompilerFlags { unsafe_panic: Some(true), ..Default::default() },
         crates_config: AllCratesConfig {
             global: CrateSettings {
                 name: None,
@@ -22,6 +27,10 @@ fn test_serde() {
                 dependencies: Default::default(),
                 experimental_features: ExperimentalFeaturesConfig::default(),
                 cfg_set: Default::default(),
+                expected_cfg_names: Default::default(),
+                lints: Default::default(),
+                no_early_unsafe_panic: false,
+                flags: Default::default(),
             },
             override_map: [
                 (
@@ -33,6 +42,10 @@ fn test_serde() {
                         dependencies: Default::default(),
                         experimental_features: ExperimentalFeaturesConfig::default(),
                         cfg_set: Default::default(),
+                        expected_cfg_names: Default::default(),
+                        lints: Default::default(),
+                        no_early_unsafe_panic: true,
+                        flags: CompilerFlags { panic_backtrace: Some(true), ..Default::default() },
                     },
                 ),
                 (
@@ -48,8 +61,13 @@ fn test_serde() {
                             coupons: false,
                             user_defined_inline_macros: false,
                             repr_ptrs: false,
+                            fallback_impls: false,
                         },
                         cfg_set: Default::default(),
+                        expected_cfg_names: Default::default(),
+                        lints: Default::default(),
+                        no_early_unsafe_panic: false,
+                        flags: Default::default(),
                     },
                 ),
             ]
@@ -66,6 +84,9 @@ fn test_serde() {
             crate2 = "dir2"
             crate3 = "dir3"
 
+            [flags]
+            unsafe_panic = true
+
             [config.global]
             edition = "2023_01"
 
@@ -77,9 +98,14 @@ fn test_serde() {
             coupons = false
             user_defined_inline_macros = false
             repr_ptrs = false
+            fallback_impls = false
 
             [config.override.crate1]
             edition = "2023_10"
+            no_early_unsafe_panic = true
+
+            [config.override.crate1.flags]
+            panic_backtrace = true
 
             [config.override.crate1.dependencies]
 
@@ -89,6 +115,7 @@ fn test_serde() {
             coupons = false
             user_defined_inline_macros = false
             repr_ptrs = false
+            fallback_impls = false
 
             [config.override.crate3]
             edition = "2023_01"
@@ -101,6 +128,7 @@ fn test_serde() {
             coupons = false
             user_defined_inline_macros = false
             repr_ptrs = false
+            fallback_impls = false
         "# }
     );
     assert_eq!(config, toml::from_str(&serialized).unwrap());
@@ -134,6 +162,7 @@ fn test_serde_defaults() {
         coupons = false
         user_defined_inline_macros = false
         repr_ptrs = false
+        fallback_impls = false
 
         [config.override]
     "# };
@@ -141,3 +170,31 @@ fn test_serde_defaults() {
     let config: ProjectConfigContent = toml::from_str(config_str).unwrap();
     assert_eq!(result, toml::to_string(&config).unwrap());
 }
+
+#[test]
+fn test_serde_main_crates() {
+    let config_str = indoc! { r#"
+        main_crates = ["crate1"]
+
+        [crate_roots]
+        crate1 = "dir1"
+        crate2 = "dir2"
+    "# };
+    let config: ProjectConfigContent = toml::from_str(config_str).unwrap();
+    assert_eq!(config.main_crates, Some(vec!["crate1".into()]));
+    assert_eq!(config, toml::from_str(&toml::to_string(&config).unwrap()).unwrap());
+}
+
+#[test]
+fn test_builder() {
+    let crate2_settings = CrateSettings { edition: Edition::V2024_07, ..Default::default() };
+    let config = ProjectConfig::new("base")
+        .with_crate("crate1", "dir1", None)
+        .with_crate("crate2", "dir2", Some(crate2_settings.clone()))
+        .with_main_crates(["crate1"]);
+    assert_eq!(config.base_path, PathBuf::from("base"));
+    assert_eq!(config.main_crates(), vec![&"crate1".into()]);
+    assert_eq!(config.content.crates_config.get(&"crate1".into()), &CrateSettings::default());
+    assert_eq!(config.content.crates_config.get(&"crate2".into()), &crate2_settings);
+    assert_eq!(config.absolute_crate_root(Path::new("dir2")), PathBuf::from("base/dir2"));
+}
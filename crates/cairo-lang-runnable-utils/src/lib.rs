@@ -1 +1,3 @@
 pub mod builder;
+#[cfg(feature = "cairo-vm")]
+mod entry_code;
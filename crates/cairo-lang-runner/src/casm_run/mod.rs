@@ -6,12 +6,16 @@ use std::sync::Arc;
 use std::vec::IntoIter;
 
 use ark_ff::{BigInteger, PrimeField};
-use cairo_lang_casm::hints::{CoreHint, DeprecatedHint, ExternalHint, Hint, StarknetHint};
+use cairo_lang_casm::hints::{
+    CoreHint, CoreHintBase, DeprecatedHint, ExternalHint, Hint, StarknetHint,
+};
+use cairo_lang_casm::instructions::Instruction;
 use cairo_lang_casm::operand::{
     BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand,
 };
 use cairo_lang_sierra::extensions::ec::EcPointType;
 use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra_to_casm::compiler::{CairoProgram, CairoProgramDebugInfo};
 use cairo_lang_utils::bigint::BigIntAsHex;
 use cairo_lang_utils::byte_array::{BYTE_ARRAY_MAGIC, BYTES_IN_WORD};
 use cairo_lang_utils::extract_matches;
@@ -47,7 +51,9 @@ use {ark_secp256k1 as secp256k1, ark_secp256r1 as secp256r1};
 use self::contract_address::calculate_contract_address;
 use self::dict_manager::DictSquashExecScope;
 use crate::short_string::{as_cairo_short_string, as_cairo_short_string_ex};
-use crate::{Arg, RunResultValue, SierraCasmRunner, StarknetExecutionResources, args_size};
+use crate::{
+    Arg, RunResultValue, SierraCasmRunner, StarknetExecutionResources, args_size, build_hints_dict,
+};
 
 #[cfg(test)]
 mod test;
@@ -108,6 +114,47 @@ pub struct CairoHintProcessor<'a> {
     pub markers: Vec<Vec<Felt252>>,
     /// The traceback set by a panic trace hint call.
     pub panic_traceback: Vec<(Relocatable, Relocatable)>,
+    /// An interceptor of the syscalls and cheatcodes of the run, if any. Passed on to the runs of
+    /// called contracts.
+    pub syscall_interceptor: Option<Box<dyn SyscallInterceptor>>,
+    /// If set, the output of the debug prints of the run is appended to it instead of being
+    /// printed, e.g. for running multiple runs concurrently. Passed on to the runs of called
+    /// contracts.
+    pub captured_output: Option<String>,
+}
+
+/// An interceptor of the syscalls and cheatcodes executed by [CairoHintProcessor], allowing
+/// embedders such as testing frameworks to mock their results, inject state or force failures
+/// without replacing the hint processor.
+pub trait SyscallInterceptor {
+    /// Called before executing a syscall. `request` is positioned at the request of the syscall,
+    /// right after the gas counter.
+    ///
+    /// Returns `None` to execute the syscall as usual, in which case the reads from `request` and
+    /// the changes to `gas_counter` are discarded. Otherwise, `request` must have been read up to
+    /// the end of the request, and the result is written as the response of the syscall, e.g.
+    /// [SyscallResult::Failure] makes the syscall fail with the given revert reason.
+    fn syscall(
+        &mut self,
+        _selector: &str,
+        _gas_counter: &mut usize,
+        _request: &mut MemBuffer<'_>,
+        _starknet_state: &mut StarknetState,
+    ) -> Result<Option<SyscallResult>, HintError> {
+        Ok(None)
+    }
+
+    /// Called before executing a cheatcode, including unknown ones.
+    ///
+    /// Returns `None` to execute the cheatcode as usual, or the output of the cheatcode otherwise.
+    fn cheatcode(
+        &mut self,
+        _selector: &str,
+        _inputs: &[Felt252],
+        _starknet_state: &mut StarknetState,
+    ) -> Result<Option<Vec<Felt252>>, HintError> {
+        Ok(None)
+    }
 }
 
 pub fn cell_ref_to_relocatable(cell_ref: &CellRef, vm: &VirtualMachine) -> Relocatable {
@@ -313,13 +360,33 @@ pub fn get_val(
 }
 
 /// Resulting options from a syscall.
-enum SyscallResult {
+pub enum SyscallResult {
     /// The syscall was successful.
     Success(Vec<MaybeRelocatable>),
     /// The syscall failed, with the revert reason.
     Failure(Vec<Felt252>),
 }
 
+/// Writes the response of a syscall into its buffer, right after its request.
+fn write_syscall_response(
+    system_buffer: &mut MemBuffer<'_>,
+    gas_counter: usize,
+    result: SyscallResult,
+) -> Result<(), HintError> {
+    system_buffer.write(gas_counter)?;
+    match result {
+        SyscallResult::Success(values) => {
+            system_buffer.write(Felt252::from(0))?;
+            system_buffer.write_data(values.into_iter())?;
+        }
+        SyscallResult::Failure(revert_reason) => {
+            system_buffer.write(Felt252::from(1))?;
+            system_buffer.write_arr(revert_reason.into_iter())?;
+        }
+    }
+    Ok(())
+}
+
 macro_rules! fail_syscall {
     ([$reason1:expr, $reason2:expr]) => {
         return Ok(SyscallResult::Failure(vec![
@@ -429,6 +496,12 @@ impl HintProcessorLogic for CairoHintProcessor<'_> {
         let hint = match hint {
             Hint::Starknet(hint) => hint,
             Hint::Core(core_hint_base) => {
+                if let (CoreHintBase::Core(CoreHint::DebugPrint { start, end }), Some(output)) =
+                    (core_hint_base, &mut self.captured_output)
+                {
+                    output.push_str(&format_for_debug(read_felts(vm, start, end)?.into_iter()));
+                    return Ok(());
+                }
                 return execute_core_hint_base(
                     vm,
                     exec_scopes,
@@ -500,6 +573,10 @@ pub trait StarknetHintProcessor: HintProcessor {
     fn take_starknet_state(&mut self) -> StarknetState;
     /// Take [`StarknetExecutionResources`] out of this hint processor, resetting own state.
     fn take_syscalls_used_resources(&mut self) -> StarknetExecutionResources;
+    /// Take the output captured by this hint processor so far, if it captures its output.
+    fn take_captured_output(&mut self) -> Option<String> {
+        None
+    }
 }
 
 impl StarknetHintProcessor for CairoHintProcessor<'_> {
@@ -510,6 +587,10 @@ impl StarknetHintProcessor for CairoHintProcessor<'_> {
     fn take_syscalls_used_resources(&mut self) -> StarknetExecutionResources {
         std::mem::take(&mut self.syscalls_used_resources)
     }
+
+    fn take_captured_output(&mut self) -> Option<String> {
+        self.captured_output.as_mut().map(std::mem::take)
+    }
 }
 
 /// Wrapper trait for a VM owner.
@@ -678,6 +759,28 @@ impl CairoHintProcessor<'_> {
         let mut system_buffer = MemBuffer::new(vm, system_ptr);
         let selector = system_buffer.next_felt252()?.to_bytes_be();
         let mut gas_counter = system_buffer.next_usize()?;
+        let selector = std::str::from_utf8(&selector).unwrap().trim_start_matches('\0');
+        *self.syscalls_used_resources.syscalls.entry(selector.into()).or_default() += 1;
+        if let Some(interceptor) = &mut self.syscall_interceptor {
+            let request_ptr = system_buffer.ptr;
+            let mut intercepted_gas_counter = gas_counter;
+            match interceptor.syscall(
+                selector,
+                &mut intercepted_gas_counter,
+                &mut system_buffer,
+                &mut self.starknet_state,
+            )? {
+                Some(result) => {
+                    return write_syscall_response(
+                        &mut system_buffer,
+                        intercepted_gas_counter,
+                        result,
+                    );
+                }
+                // Discarding the reads of the interceptor.
+                None => system_buffer.ptr = request_ptr,
+            }
+        }
         let mut execute_handle_helper =
             |handler: &mut dyn FnMut(
                 // The syscall buffer.
@@ -685,22 +788,9 @@ impl CairoHintProcessor<'_> {
                 // The gas counter.
                 &mut usize,
             ) -> Result<SyscallResult, HintError>| {
-                match handler(&mut system_buffer, &mut gas_counter)? {
-                    SyscallResult::Success(values) => {
-                        system_buffer.write(gas_counter)?;
-                        system_buffer.write(Felt252::from(0))?;
-                        system_buffer.write_data(values.into_iter())?;
-                    }
-                    SyscallResult::Failure(revert_reason) => {
-                        system_buffer.write(gas_counter)?;
-                        system_buffer.write(Felt252::from(1))?;
-                        system_buffer.write_arr(revert_reason.into_iter())?;
-                    }
-                }
-                Ok(())
+                let result = handler(&mut system_buffer, &mut gas_counter)?;
+                write_syscall_response(&mut system_buffer, gas_counter, result)
             };
-        let selector = std::str::from_utf8(&selector).unwrap().trim_start_matches('\0');
-        *self.syscalls_used_resources.syscalls.entry(selector.into()).or_default() += 1;
         match selector {
             "StorageWrite" => execute_handle_helper(&mut |system_buffer, gas_counter| {
                 self.storage_write(
@@ -1203,8 +1293,8 @@ impl CairoHintProcessor<'_> {
             .registry()
             .get_function(entry_point)
             .expect("Entrypoint exists, but not found.");
-        let res = runner
-            .run_function_with_starknet_context(
+        let (mut hint_processor, ctx) = runner
+            .prepare_starknet_context(
                 function,
                 vec![Arg::Array(calldata.into_iter().map(Arg::Value).collect())],
                 // The costs of the relevant syscall include `ENTRY_POINT_INITIAL_BUDGET` so we
@@ -1213,6 +1303,13 @@ impl CairoHintProcessor<'_> {
                 self.starknet_state.clone(),
             )
             .expect("Internal runner error.");
+        hint_processor.syscall_interceptor = self.syscall_interceptor.take();
+        hint_processor.captured_output = self.captured_output.take();
+        let res =
+            runner.run_function_with_prepared_starknet_context(function, &mut hint_processor, ctx);
+        self.syscall_interceptor = hint_processor.syscall_interceptor.take();
+        self.captured_output = hint_processor.captured_output.take();
+        let res = res.expect("Internal runner error.");
         self.syscalls_used_resources += res.used_resources;
         *gas_counter = res.gas_counter.unwrap().to_usize().unwrap();
         match res.value {
@@ -1256,8 +1353,21 @@ impl CairoHintProcessor<'_> {
             .map(|[value]| value)
         };
 
+        let intercepted = match &mut self.syscall_interceptor {
+            Some(interceptor) => {
+                interceptor.cheatcode(selector, &inputs, &mut self.starknet_state)?
+            }
+            None => None,
+        };
         let mut res_segment = MemBuffer::new_segment(vm);
         let res_segment_start = res_segment.ptr;
+        if let Some(outputs) = intercepted {
+            res_segment.write_data(outputs.into_iter())?;
+            let res_segment_end = res_segment.ptr;
+            insert_value_to_cellref!(vm, output_start, res_segment_start)?;
+            insert_value_to_cellref!(vm, output_end, res_segment_end)?;
+            return Ok(());
+        }
         match selector {
             "set_sequencer_address" => {
                 self.starknet_state.exec_info.block_info.sequencer_address =
@@ -2418,6 +2528,75 @@ pub fn run_function<'a, 'b: 'a>(
     })
 }
 
+/// A value to place in memory before running instructions with [run_instructions].
+#[derive(Clone, Debug)]
+pub enum InitialValue {
+    /// A plain value.
+    Felt(Felt252),
+    /// A pointer to a new segment holding the given values.
+    Segment(Vec<Felt252>),
+}
+
+/// Runs hand-written instructions, e.g. built with the `casm!` macro or the `CasmBuilder`, and
+/// returns the memory and registers after the run.
+///
+/// Before the run, the values of `initial_memory` are placed at `[fp + i]`, and `ap` is set to
+/// `fp + initial_memory.len()`. The instructions are run until their `ret`, so they must not change
+/// `fp` by then.
+pub fn run_instructions(
+    instructions: Vec<Instruction>,
+    initial_memory: Vec<InitialValue>,
+) -> Result<RunFunctionResult, Box<CairoRunError>> {
+    let program = CairoProgram {
+        instructions,
+        consts_info: Default::default(),
+        debug_info: CairoProgramDebugInfo {
+            sierra_statement_info: vec![],
+            frame_sizes: Default::default(),
+        },
+    }
+    .assemble();
+    let (hints_dict, string_to_hint) = build_hints_dict(&program.hints);
+    let mut hint_processor = CairoHintProcessor {
+        runner: None,
+        user_args: vec![],
+        string_to_hint,
+        starknet_state: StarknetState::default(),
+        run_resources: RunResources::default(),
+        syscalls_used_resources: Default::default(),
+        no_temporary_segments: true,
+        markers: Default::default(),
+        panic_traceback: Default::default(),
+        syscall_interceptor: None,
+        captured_output: None,
+    };
+    let n_values = initial_memory.len();
+    run_function(
+        program.bytecode.iter(),
+        vec![],
+        |vm| {
+            let fp = vm.get_fp();
+            for (offset, value) in initial_memory.into_iter().enumerate() {
+                let value = match value {
+                    InitialValue::Felt(value) => MaybeRelocatable::from(value),
+                    InitialValue::Segment(values) => {
+                        let segment = vm.add_memory_segment();
+                        let values = values.into_iter().map(MaybeRelocatable::from).collect_vec();
+                        vm.load_data(segment, &values).map_err(CairoRunError::from)?;
+                        MaybeRelocatable::from(segment)
+                    }
+                };
+                vm.insert_value(Relocatable::from((fp.segment_index, fp.offset + offset)), value)
+                    .map_err(CairoRunError::from)?;
+            }
+            vm.set_ap(fp.offset + n_values);
+            Ok(())
+        },
+        &mut hint_processor,
+        hints_dict,
+    )
+}
+
 /// Formats the given felts as a debug string.
 pub fn format_for_debug(mut felts: IntoIter<Felt252>) -> String {
     let mut items = Vec::new();
@@ -2476,7 +2655,15 @@ where
 }
 
 /// Formats the given felts as a panic string.
-pub fn format_for_panic<T>(mut felts: T) -> String
+pub fn format_for_panic<T>(felts: T) -> String
+where
+    T: Iterator<Item = Felt252> + Clone,
+{
+    format!("Panicked with {}.", format_panic_data(felts))
+}
+
+/// Formats the given felts as panic data, e.g. `"Out of bounds"` or `(0x1, 0x2)`.
+pub fn format_panic_data<T>(mut felts: T) -> String
 where
     T: Iterator<Item = Felt252> + Clone,
 {
@@ -2484,9 +2671,7 @@ where
     while let Some(item) = format_next_item(&mut felts) {
         items.push(item.quote_if_string());
     }
-    let panic_values_string =
-        if let [item] = &items[..] { item.clone() } else { format!("({})", items.join(", ")) };
-    format!("Panicked with {panic_values_string}.")
+    if let [item] = &items[..] { item.clone() } else { format!("({})", items.join(", ")) }
 }
 
 /// Formats a `Felt252`, as a short string if possible.
@@ -1,27 +1,42 @@
 use cairo_lang_casm::assembler::AssembledCairoProgram;
+use cairo_lang_casm::hints::CoreHint;
 use cairo_lang_casm::inline::CasmContext;
+use cairo_lang_casm::instructions::{Blake2sCompressInstruction, Instruction, InstructionBody};
+use cairo_lang_casm::operand::ResOperand;
 use cairo_lang_casm::{casm, cell_ref};
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
 use cairo_lang_sierra_to_casm::compiler::{CairoProgram, CairoProgramDebugInfo};
 use cairo_lang_utils::byte_array::BYTE_ARRAY_MAGIC;
+use cairo_vm::vm::errors::hint_errors::HintError;
 use cairo_vm::vm::runners::cairo_runner::RunResources;
 use indoc::indoc;
-use itertools::Itertools;
+use itertools::{Itertools, chain};
 use num_traits::ToPrimitive;
 use starknet_types_core::felt::Felt as Felt252;
 use test_case::test_case;
 
 use super::format_for_debug;
 use crate::casm_run::contract_address::calculate_contract_address;
-use crate::casm_run::{RunFunctionResult, run_function};
+use crate::casm_run::{InitialValue, MemBuffer, RunFunctionResult, run_function, run_instructions};
 use crate::short_string::{as_cairo_short_string, as_cairo_short_string_ex};
-use crate::{CairoHintProcessor, StarknetState, build_hints_dict};
+use crate::{
+    CairoHintProcessor, RunResultValue, SierraCasmRunner, StarknetState, SyscallInterceptor,
+    SyscallResult, build_hints_dict,
+};
 
 /// Creates a new `AssembledCairoProgram` from the given `CasmContext`.
 fn assembled(casm: CasmContext) -> AssembledCairoProgram {
     CairoProgram {
         instructions: casm.instructions,
         consts_info: Default::default(),
-        debug_info: CairoProgramDebugInfo { sierra_statement_info: vec![] },
+        debug_info: CairoProgramDebugInfo {
+            sierra_statement_info: vec![],
+            frame_sizes: Default::default(),
+        },
     }
     .assemble()
 }
@@ -132,6 +147,8 @@ fn test_runner(function: CasmContext, n_returns: usize, expected: &[i128]) {
         no_temporary_segments: true,
         markers: Default::default(),
         panic_traceback: Default::default(),
+        syscall_interceptor: None,
+        captured_output: None,
     };
 
     let RunFunctionResult { ap, memory, .. } =
@@ -144,6 +161,79 @@ fn test_runner(function: CasmContext, n_returns: usize, expected: &[i128]) {
     );
 }
 
+#[test]
+fn test_run_instructions() {
+    let RunFunctionResult { ap, memory, .. } = run_instructions(
+        casm! {
+            [ap] = [fp] + [fp + 1], ap++;
+            [ap] = [[fp + 2] + 1], ap++;
+            ret;
+        }
+        .instructions,
+        vec![
+            InitialValue::Felt(Felt252::from(12)),
+            InitialValue::Felt(Felt252::from(30)),
+            InitialValue::Segment(vec![Felt252::from(5), Felt252::from(6)]),
+        ],
+    )
+    .expect("Running code failed.");
+    assert_eq!(memory[ap - 2..ap], [Some(Felt252::from(42)), Some(Felt252::from(6))]);
+}
+
+#[test]
+fn test_run_instructions_blake2s_compress() {
+    // The instruction generated for `blake2s_finalize`, see the blake invocation builder.
+    let instruction = Instruction {
+        hints: vec![
+            CoreHint::AllocConstantSize {
+                dst: cell_ref!([ap]),
+                size: ResOperand::Immediate(8.into()),
+            }
+            .into(),
+        ],
+        body: InstructionBody::Blake2sCompress(Blake2sCompressInstruction {
+            state: cell_ref!([fp]),
+            byte_count: cell_ref!([fp + 1]),
+            message: cell_ref!([fp + 2]),
+            finalize: true,
+        }),
+        inc_ap: true,
+    };
+    // The blake2s IV, with the parameters of an unkeyed 32 bytes digest.
+    let state = [
+        0x6B08E647_u32,
+        0xBB67AE85,
+        0x3C6EF372,
+        0xA54FF53A,
+        0x510E527F,
+        0x9B05688C,
+        0x1F83D9AB,
+        0x5BE0CD19,
+    ];
+    let RunFunctionResult { ap, memory, .. } = run_instructions(
+        chain!([instruction], casm! { ret; }.instructions).collect(),
+        vec![
+            InitialValue::Segment(state.into_iter().map(Felt252::from).collect()),
+            InitialValue::Felt(Felt252::from(0)),
+            InitialValue::Segment(vec![Felt252::from(0); 16]),
+        ],
+    )
+    .expect("Running code failed.");
+    let output = memory[ap - 1].unwrap().to_usize().unwrap();
+    // The blake2s digest of the empty message, as little endian words.
+    let expected = [
+        0x307A2169_u32,
+        0x94809079,
+        0xD02111E1,
+        0x7C4A3542,
+        0x48B6551F,
+        0x1EA5A12C,
+        0xFD0D251B,
+        0xF9EED01E,
+    ];
+    assert_eq!(memory[output..output + 8], expected.map(|word| Some(Felt252::from(word))));
+}
+
 #[test]
 fn test_allocate_segment() {
     let program = assembled(casm! {
@@ -163,6 +253,8 @@ fn test_allocate_segment() {
         no_temporary_segments: true,
         markers: Default::default(),
         panic_traceback: Default::default(),
+        syscall_interceptor: None,
+        captured_output: None,
     };
 
     let RunFunctionResult { ap, memory, .. } =
@@ -485,3 +577,84 @@ fn test_calculate_contract_address() {
         deployed_contract_address
     );
 }
+
+/// Mocks the storage syscalls and adds a `double` cheatcode.
+struct MockingInterceptor {
+    /// The value returned by every storage read.
+    storage_value: Felt252,
+}
+
+impl SyscallInterceptor for MockingInterceptor {
+    fn syscall(
+        &mut self,
+        selector: &str,
+        _gas_counter: &mut usize,
+        request: &mut MemBuffer<'_>,
+        _starknet_state: &mut StarknetState,
+    ) -> Result<Option<SyscallResult>, HintError> {
+        Ok(match selector {
+            "StorageRead" => {
+                let _address_domain = request.next_felt252()?;
+                let _address = request.next_felt252()?;
+                Some(SyscallResult::Success(vec![self.storage_value.into()]))
+            }
+            "StorageWrite" => {
+                request.next_felt252()?;
+                request.next_felt252()?;
+                request.next_felt252()?;
+                Some(SyscallResult::Failure(vec![Felt252::from_bytes_be_slice(b"forced")]))
+            }
+            _ => None,
+        })
+    }
+
+    fn cheatcode(
+        &mut self,
+        selector: &str,
+        inputs: &[Felt252],
+        _starknet_state: &mut StarknetState,
+    ) -> Result<Option<Vec<Felt252>>, HintError> {
+        Ok((selector == "double").then(|| inputs.iter().map(|input| input + input).collect()))
+    }
+}
+
+#[test]
+fn test_syscall_interceptor() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            use starknet::SyscallResultTrait;
+            use starknet::syscalls::{storage_read_syscall, storage_write_syscall};
+
+            fn main() -> (felt252, felt252, bool) {
+                let address = 7_felt252.try_into().unwrap();
+                let value = storage_read_syscall(0, address).unwrap_syscall();
+                let doubled = *starknet::testing::cheatcode::<'double'>(array![value].span())[0];
+                let failed = storage_write_syscall(0, address, doubled).is_err();
+                (value, doubled, failed)
+            }
+        "},
+    )
+    .unwrap();
+    let crate_input = test_module.crate_id.long(&db).clone().into_crate_input(&db);
+    DiagnosticsReporter::stderr().with_crates(&[crate_input]).ensure(&db).unwrap();
+    let sierra_program = db.get_sierra_program(vec![test_module.crate_id]).unwrap().program.clone();
+    let sierra_program = replace_sierra_ids_in_program(&db, &sierra_program);
+    let runner =
+        SierraCasmRunner::new(sierra_program, Some(Default::default()), Default::default(), None)
+            .unwrap();
+    let func = runner.find_function("::main").unwrap();
+    let (mut hint_processor, ctx) = runner
+        .prepare_starknet_context(func, vec![], Some(u32::MAX as usize), Default::default())
+        .unwrap();
+    hint_processor.syscall_interceptor =
+        Some(Box::new(MockingInterceptor { storage_value: Felt252::from(21) }));
+    let result =
+        runner.run_function_with_prepared_starknet_context(func, &mut hint_processor, ctx).unwrap();
+    assert_eq!(
+        result.value,
+        RunResultValue::Success(vec![Felt252::from(21), Felt252::from(42), Felt252::from(1)])
+    );
+    assert_eq!(result.used_resources.syscalls["StorageRead"], 1);
+}
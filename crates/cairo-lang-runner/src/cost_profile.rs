@@ -0,0 +1,309 @@
+//! Attribution of the costs of runs - steps, builtin uses and gas - to the functions they were
+//! spent in, for finding the hot spots of programs.
+
+use std::fmt::Write;
+
+use cairo_lang_runnable_utils::builder::RunnableBuilder;
+use cairo_lang_sierra::extensions::core::CoreConcreteLibfunc;
+use cairo_lang_sierra::extensions::gas::{CostTokenType, GasConcreteLibfunc};
+use cairo_lang_sierra::program::{Program, Statement, StatementIdx};
+use cairo_lang_sierra_to_casm::compiler::StatementKindDebugInfo;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_vm::vm::trace::trace_entry::RelocatedTraceEntry;
+use itertools::Itertools;
+
+use crate::profiling::user_function_idx_by_sierra_statement_idx;
+use crate::token_gas_cost;
+
+#[cfg(test)]
+#[path = "cost_profile_test.rs"]
+mod test;
+
+/// The gas cost of a single step.
+const STEP_GAS_COST: usize = 100;
+
+/// The costs spent in some part of a run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionCost {
+    /// The number of executed steps.
+    pub steps: usize,
+    /// The number of uses of each builtin with a gas cost.
+    pub builtins: OrderedHashMap<CostTokenType, usize>,
+    /// The gas consumed - the costs of the steps, holes and range checks of the executed
+    /// libfuncs, and of their builtin uses.
+    pub gas: usize,
+}
+
+impl ExecutionCost {
+    /// Adds the costs of `other` to this one.
+    pub fn add(&mut self, other: &Self) {
+        self.steps += other.steps;
+        for (token_type, uses) in other.builtins.iter() {
+            *self.builtins.entry(*token_type).or_default() += uses;
+        }
+        self.gas += other.gas;
+    }
+
+    /// Returns the value of the given metric of the cost.
+    pub fn get(&self, metric: CostMetric) -> usize {
+        match metric {
+            CostMetric::Steps => self.steps,
+            CostMetric::Gas => self.gas,
+            CostMetric::Builtin(token_type) => {
+                self.builtins.get(&token_type).copied().unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// A metric of [ExecutionCost], for exporting a single weight per stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CostMetric {
+    Steps,
+    Gas,
+    /// The uses of a builtin.
+    Builtin(CostTokenType),
+}
+
+/// The costs of a single function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionCost {
+    /// The name of the Sierra function.
+    pub name: String,
+    /// The costs spent in the function and in the functions it called.
+    pub inclusive: ExecutionCost,
+    /// The costs spent in the function itself.
+    pub exclusive: ExecutionCost,
+}
+
+/// The costs of one or more runs, by the function stacks they were spent in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CostProfile {
+    /// The costs spent directly in the last function of each stack.
+    /// The key is the function stack, as the indices of the functions in the Sierra program, from
+    /// the entry point to the executing function. Recursive calls, including loops, are collapsed
+    /// to a single frame. The entries are sorted in the order they occur.
+    pub stack_costs: OrderedHashMap<Vec<usize>, ExecutionCost>,
+}
+
+impl CostProfile {
+    /// Collects the cost profile of a run from its trace.
+    ///
+    /// The steps are counted from the trace. The gas and the builtin uses of an executed libfunc
+    /// are the costs of its taken branch, as computed when compiling it, except for libfuncs only
+    /// moving gas around (e.g. `withdraw_gas` and function calls), whose gas is the cost of their
+    /// executed steps.
+    pub fn from_trace(
+        builder: &RunnableBuilder,
+        // The offset in memory where builder.casm_program() was loaded.
+        load_offset: usize,
+        trace: &[RelocatedTraceEntry],
+    ) -> Self {
+        let casm_program = builder.casm_program();
+        let sierra_program = builder.sierra_program();
+        let sierra_statement_info = &casm_program.debug_info.sierra_statement_info;
+        let bytecode_len = sierra_statement_info.last().map_or(0, |info| info.end_offset);
+
+        let mut profile = Self::default();
+        // The current function stack, including the executing function.
+        let mut stack = FunctionStack::default();
+        // Whether the previous step was a function call, so the current one starts the callee.
+        let mut in_call = false;
+        // A started branching statement whose taken branch is not yet known, with its stack.
+        let mut pending_branch: Option<(StatementIdx, Vec<usize>)> = None;
+        for step in trace {
+            // Skip the header, the footer and the constant segments.
+            let Some(pc) = step.pc.checked_sub(load_offset).filter(|pc| *pc < bytecode_len) else {
+                continue;
+            };
+            let statement_idx = casm_program.sierra_statement_index_by_pc(pc);
+            let function_idx =
+                user_function_idx_by_sierra_statement_idx(sierra_program, statement_idx);
+            if in_call || stack.functions.is_empty() {
+                stack.push(function_idx);
+                in_call = false;
+            }
+            let statement_start = sierra_statement_info[statement_idx.0].start_offset == pc;
+            // A branch was taken if the execution continued at the start of its target.
+            if statement_start && let Some((branch_idx, branch_stack)) = pending_branch.take() {
+                let Statement::Invocation(invocation) = &sierra_program.statements[branch_idx.0]
+                else {
+                    unreachable!("Only invocations have branches.");
+                };
+                if let Some(taken) = invocation.branches.iter().position(|branch| {
+                    let target = branch_idx.next(branch.target);
+                    sierra_statement_info[target.0].start_offset == pc
+                }) {
+                    let cost = profile.stack_costs.entry(branch_stack).or_default();
+                    add_libfunc_cost(builder, branch_idx, taken, cost);
+                }
+            }
+            if !profile.stack_costs.contains_key(&stack.functions) {
+                profile.stack_costs.insert(stack.functions.clone(), Default::default());
+            }
+            let cost = &mut profile.stack_costs[&stack.functions];
+            cost.steps += 1;
+            match &sierra_program.statements[statement_idx.0] {
+                Statement::Invocation(invocation) => {
+                    let libfunc = builder.registry().get_libfunc(&invocation.libfunc_id).ok();
+                    if libfunc.is_none_or(is_gas_accounting_libfunc) {
+                        cost.gas += STEP_GAS_COST;
+                    } else if statement_start {
+                        if invocation.branches.len() == 1 {
+                            add_libfunc_cost(builder, statement_idx, 0, cost);
+                        } else {
+                            pending_branch = Some((statement_idx, stack.functions.clone()));
+                        }
+                    }
+                    // A call is a single step, so the callee starts at the next one.
+                    in_call = matches!(
+                        libfunc,
+                        Some(
+                            CoreConcreteLibfunc::FunctionCall(_)
+                                | CoreConcreteLibfunc::CouponCall(_)
+                        )
+                    );
+                }
+                Statement::Return(_) => {
+                    cost.gas += STEP_GAS_COST;
+                    stack.pop();
+                }
+            }
+        }
+        profile
+    }
+
+    /// Adds the cost profile of another run to this one.
+    pub fn merge(&mut self, other: &Self) {
+        for (stack, cost) in other.stack_costs.iter() {
+            self.stack_costs.entry(stack.clone()).or_default().add(cost);
+        }
+    }
+
+    /// Returns the total costs of the profiled runs.
+    pub fn total(&self) -> ExecutionCost {
+        let mut total = ExecutionCost::default();
+        for cost in self.stack_costs.values() {
+            total.add(cost);
+        }
+        total
+    }
+
+    /// Returns the inclusive and exclusive costs of each executed function, sorted by decreasing
+    /// inclusive gas. The costs of a recursive function are counted once in its inclusive costs.
+    pub fn function_costs(&self, sierra_program: &Program) -> Vec<FunctionCost> {
+        let mut function_costs = OrderedHashMap::<usize, FunctionCost>::default();
+        for (stack, cost) in self.stack_costs.iter() {
+            for function_idx in stack.iter().unique() {
+                function_costs
+                    .entry(*function_idx)
+                    .or_insert_with(|| FunctionCost {
+                        name: sierra_program.funcs[*function_idx].id.to_string(),
+                        inclusive: Default::default(),
+                        exclusive: Default::default(),
+                    })
+                    .inclusive
+                    .add(cost);
+            }
+            if let Some(function_idx) = stack.last() {
+                function_costs[function_idx].exclusive.add(cost);
+            }
+        }
+        function_costs
+            .into_iter()
+            .map(|(_, function_cost)| function_cost)
+            .sorted_by(|a, b| {
+                b.inclusive.gas.cmp(&a.inclusive.gas).then_with(|| a.name.cmp(&b.name))
+            })
+            .collect()
+    }
+
+    /// Returns the profile in the collapsed stacks format of flamegraph tools - a line per function
+    /// stack, with its frames separated by `;`, followed by the exclusive value of the metric.
+    /// Stacks with a zero value are omitted.
+    pub fn to_collapsed_stacks(&self, sierra_program: &Program, metric: CostMetric) -> String {
+        let mut collapsed = String::new();
+        for (stack, cost) in self.stack_costs.iter() {
+            let value = cost.get(metric);
+            if value == 0 {
+                continue;
+            }
+            let frames = stack.iter().map(|idx| sierra_program.funcs[*idx].id.to_string());
+            writeln!(collapsed, "{} {value}", frames.format(";")).unwrap();
+        }
+        collapsed
+    }
+}
+
+/// A function stack, with recursive calls collapsed to a single frame.
+#[derive(Default)]
+struct FunctionStack {
+    /// The indices of the functions of the frames, in the Sierra program.
+    functions: Vec<usize>,
+    /// The number of calls collapsed into each frame.
+    calls: Vec<usize>,
+}
+
+impl FunctionStack {
+    /// Enters a call to the given function.
+    fn push(&mut self, function_idx: usize) {
+        if self.functions.last() == Some(&function_idx) {
+            *self.calls.last_mut().unwrap() += 1;
+        } else {
+            self.functions.push(function_idx);
+            self.calls.push(1);
+        }
+    }
+
+    /// Returns from the current function.
+    fn pop(&mut self) {
+        let Some(calls) = self.calls.last_mut() else {
+            return;
+        };
+        *calls -= 1;
+        if *calls == 0 {
+            self.calls.pop();
+            self.functions.pop();
+        }
+    }
+}
+
+/// Returns true if the libfunc only moves gas between the wallet and other parts of the code, so
+/// its compiled cost does not reflect the resources it consumes.
+fn is_gas_accounting_libfunc(libfunc: &CoreConcreteLibfunc) -> bool {
+    matches!(
+        libfunc,
+        CoreConcreteLibfunc::FunctionCall(_)
+            | CoreConcreteLibfunc::CouponCall(_)
+            | CoreConcreteLibfunc::BranchAlign(_)
+            | CoreConcreteLibfunc::GasReserve(_)
+            | CoreConcreteLibfunc::Gas(
+                GasConcreteLibfunc::WithdrawGas(_)
+                    | GasConcreteLibfunc::BuiltinWithdrawGas(_)
+                    | GasConcreteLibfunc::RedepositGas(_)
+                    | GasConcreteLibfunc::GetUnspentGas(_)
+            )
+    )
+}
+
+/// Adds the compiled cost of the given branch of an invocation statement to `cost`.
+fn add_libfunc_cost(
+    builder: &RunnableBuilder,
+    statement_idx: StatementIdx,
+    branch_idx: usize,
+    cost: &mut ExecutionCost,
+) {
+    let info = &builder.casm_program().debug_info.sierra_statement_info[statement_idx.0];
+    let StatementKindDebugInfo::Invoke(invoke_info) = &info.additional_kind_info else {
+        return;
+    };
+    for (token_type, value) in invoke_info.result_branch_changes[branch_idx].gas_cost.iter() {
+        let Ok(value) = usize::try_from(*value) else {
+            continue;
+        };
+        if *token_type != CostTokenType::Const {
+            *cost.builtins.entry(*token_type).or_default() += value;
+        }
+        cost.gas += value * token_gas_cost(*token_type);
+    }
+}
@@ -0,0 +1,94 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+
+use super::{CostMetric, CostProfile};
+use crate::SierraCasmRunner;
+
+#[test]
+fn cost_profile_of_calls() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            use core::pedersen::pedersen;
+
+            #[inline(never)]
+            fn hash_chain(n: felt252, acc: felt252) -> felt252 {
+                if n == 0 {
+                    acc
+                } else {
+                    hash_chain(n - 1, pedersen(acc, n))
+                }
+            }
+
+            fn main() -> felt252 {
+                hash_chain(3, 0) + hash_chain(2, 1)
+            }
+        "},
+    )
+    .unwrap();
+    let crate_input = test_module.crate_id.long(&db).clone().into_crate_input(&db);
+    DiagnosticsReporter::stderr().with_crates(&[crate_input]).ensure(&db).unwrap();
+    let SierraProgramWithDebug { program: sierra_program, .. } =
+        db.get_sierra_program(vec![test_module.crate_id]).unwrap();
+    let sierra_program = replace_sierra_ids_in_program(&db, sierra_program);
+    let runner = SierraCasmRunner::new(
+        sierra_program.clone(),
+        Some(Default::default()),
+        OrderedHashMap::default(),
+        None,
+    )
+    .unwrap()
+    .with_cost_profile();
+    let func = runner.find_function("::main").unwrap();
+    let result = runner
+        .run_function_with_starknet_context(
+            func,
+            vec![],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    let mut profile = CostProfile::default();
+    profile.merge(&result.cost_profile.unwrap());
+
+    let total = profile.total();
+    assert_eq!(total.builtins.get(&CostTokenType::Pedersen), Some(&5));
+    assert!(total.gas >= total.steps * 100);
+
+    let function_costs = profile.function_costs(&sierra_program);
+    let [main, hash_chain] = &function_costs[..] else {
+        panic!("Expected exactly two functions, got: {function_costs:?}");
+    };
+    assert!(main.name.ends_with("::main"));
+    assert!(hash_chain.name.ends_with("::hash_chain"));
+    assert_eq!(main.inclusive, total);
+    assert_eq!(hash_chain.inclusive, hash_chain.exclusive);
+    assert_eq!(hash_chain.exclusive.builtins.get(&CostTokenType::Pedersen), Some(&5));
+    assert_eq!(main.exclusive.builtins.get(&CostTokenType::Pedersen), None);
+    let mut exclusive = main.exclusive.clone();
+    exclusive.add(&hash_chain.exclusive);
+    assert_eq!(exclusive, total);
+
+    // The recursion of `hash_chain` is collapsed into a single frame.
+    let collapsed = profile.to_collapsed_stacks(&sierra_program, CostMetric::Gas);
+    let lines: Vec<_> = collapsed.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            format!("{} {}", main.name, main.exclusive.gas),
+            format!("{};{} {}", main.name, hash_chain.name, hash_chain.exclusive.gas),
+        ]
+    );
+    assert_eq!(
+        profile.to_collapsed_stacks(&sierra_program, CostMetric::Builtin(CostTokenType::Pedersen)),
+        format!("{};{} 5\n", main.name, hash_chain.name)
+    );
+}
@@ -0,0 +1,194 @@
+//! Line and branch coverage of Cairo code, collected from the execution traces of runs.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use cairo_lang_runnable_utils::builder::RunnableBuilder;
+use cairo_lang_sierra::program::{Program, Statement, StatementIdx};
+use cairo_lang_sierra_generator::debug_info::StatementsSourceCodeLocations;
+use cairo_lang_sierra_to_casm::compiler::CairoProgram;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_vm::vm::trace::trace_entry::RelocatedTraceEntry;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+#[path = "coverage_test.rs"]
+mod test;
+
+/// The raw coverage info of one or more runs - the number of times each Sierra statement was
+/// executed, and each branch of a branching Sierra statement was taken.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoverageInfo {
+    /// The number of times the execution of each Sierra statement started.
+    /// Only statements compiled into at least one CASM instruction are counted.
+    pub statement_hits: UnorderedHashMap<StatementIdx, usize>,
+    /// The number of times each branch of a branching Sierra statement was taken, keyed by the
+    /// statement and the index of the branch.
+    pub branch_hits: UnorderedHashMap<(StatementIdx, usize), usize>,
+}
+
+impl CoverageInfo {
+    /// Collects the coverage info of a run from its trace.
+    pub fn from_trace(
+        builder: &RunnableBuilder,
+        // The offset in memory where builder.casm_program() was loaded.
+        load_offset: usize,
+        trace: &[RelocatedTraceEntry],
+    ) -> Self {
+        let casm_program = builder.casm_program();
+        let statements = &builder.sierra_program().statements;
+        let sierra_statement_info = &casm_program.debug_info.sierra_statement_info;
+        let bytecode_len = sierra_statement_info.last().map_or(0, |info| info.end_offset);
+
+        let mut coverage = Self::default();
+        // The statement of the previous step, if it was a part of the Sierra based code.
+        let mut prev_statement_idx: Option<StatementIdx> = None;
+        for step in trace {
+            // Skip the header, the footer and the constant segments.
+            let Some(pc) = step.pc.checked_sub(load_offset).filter(|pc| *pc < bytecode_len) else {
+                prev_statement_idx = None;
+                continue;
+            };
+            let statement_idx = casm_program.sierra_statement_index_by_pc(pc);
+            if sierra_statement_info[statement_idx.0].start_offset == pc {
+                *coverage.statement_hits.entry(statement_idx).or_default() += 1;
+                // A branch was taken if the execution continued at the start of its target.
+                if let Some(prev_statement_idx) = prev_statement_idx
+                    && let Statement::Invocation(invocation) = &statements[prev_statement_idx.0]
+                    && invocation.branches.len() > 1
+                    && let Some(branch_idx) = invocation.branches.iter().position(|branch| {
+                        let target = prev_statement_idx.next(branch.target);
+                        sierra_statement_info[target.0].start_offset == pc
+                    })
+                {
+                    *coverage.branch_hits.entry((prev_statement_idx, branch_idx)).or_default() += 1;
+                }
+            }
+            prev_statement_idx = Some(statement_idx);
+        }
+        coverage
+    }
+
+    /// Adds the coverage info of another run to this one.
+    pub fn merge(&mut self, other: &Self) {
+        self.statement_hits
+            .merge(&other.statement_hits, |mut entry, hits| *entry.get_mut() += hits);
+        self.branch_hits.merge(&other.branch_hits, |mut entry, hits| *entry.get_mut() += hits);
+    }
+}
+
+/// A coverage report of Cairo files, built from [CoverageInfo].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoverageReport {
+    /// The coverage of each file, by its full path.
+    pub files: BTreeMap<String, FileCoverage>,
+}
+
+/// The coverage of a single Cairo file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileCoverage {
+    /// The number of executions of each line (1-based) containing code.
+    pub lines: BTreeMap<usize, usize>,
+    /// The branches of the branching statements in the file, sorted by line.
+    pub branches: Vec<BranchCoverage>,
+}
+
+/// The coverage of a single branch of a branching statement.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchCoverage {
+    /// The line (1-based) of the branching statement.
+    pub line: usize,
+    /// The index of the branching Sierra statement.
+    pub statement_idx: usize,
+    /// The index of the branch in the statement.
+    pub branch_idx: usize,
+    /// The number of times the branch was taken.
+    pub hits: usize,
+}
+
+impl CoverageReport {
+    /// Builds the report of the given coverage info.
+    ///
+    /// A statement counts as an execution of the lines of all its locations, from the inlined code
+    /// to the outermost call site, and its branches are reported at each of these lines. The hits
+    /// of a line are the maximal hits of its statements.
+    pub fn new(
+        program: &Program,
+        casm_program: &CairoProgram,
+        code_locations: &StatementsSourceCodeLocations,
+        coverage: &CoverageInfo,
+    ) -> Self {
+        let mut files = BTreeMap::<String, FileCoverage>::new();
+        for (idx, info) in casm_program.debug_info.sierra_statement_info.iter().enumerate() {
+            // Statements without instructions are never executed.
+            if info.start_offset == info.end_offset {
+                continue;
+            }
+            let statement_idx = StatementIdx(idx);
+            let Some(locations) =
+                code_locations.statements_to_code_location_map.get(&statement_idx)
+            else {
+                continue;
+            };
+            let hits = coverage.statement_hits.get(&statement_idx).copied().unwrap_or_default();
+            for (path, span, _) in locations {
+                let line_hits = files
+                    .entry(path.0.clone())
+                    .or_default()
+                    .lines
+                    .entry(span.start.line + 1)
+                    .or_default();
+                *line_hits = (*line_hits).max(hits);
+            }
+            let Statement::Invocation(invocation) = &program.statements[idx] else {
+                continue;
+            };
+            if invocation.branches.len() < 2 {
+                continue;
+            }
+            for (path, span, _) in locations {
+                let file = files.entry(path.0.clone()).or_default();
+                for branch_idx in 0..invocation.branches.len() {
+                    let branch_hits = coverage.branch_hits.get(&(statement_idx, branch_idx));
+                    file.branches.push(BranchCoverage {
+                        line: span.start.line + 1,
+                        statement_idx: idx,
+                        branch_idx,
+                        hits: branch_hits.copied().unwrap_or_default(),
+                    });
+                }
+            }
+        }
+        for file in files.values_mut() {
+            file.branches
+                .sort_by_key(|branch| (branch.line, branch.statement_idx, branch.branch_idx));
+            // A statement may have multiple locations on the same line.
+            file.branches.dedup();
+        }
+        Self { files }
+    }
+
+    /// Returns the report in the LCOV tracefile format.
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+        for (path, file) in &self.files {
+            writeln!(lcov, "TN:").unwrap();
+            writeln!(lcov, "SF:{path}").unwrap();
+            for branch in &file.branches {
+                let BranchCoverage { line, statement_idx, branch_idx, hits } = branch;
+                writeln!(lcov, "BRDA:{line},{statement_idx},{branch_idx},{hits}").unwrap();
+            }
+            let branches_hit = file.branches.iter().filter(|branch| branch.hits > 0).count();
+            writeln!(lcov, "BRF:{}", file.branches.len()).unwrap();
+            writeln!(lcov, "BRH:{branches_hit}").unwrap();
+            for (line, hits) in &file.lines {
+                writeln!(lcov, "DA:{line},{hits}").unwrap();
+            }
+            let lines_hit = file.lines.values().filter(|hits| **hits > 0).count();
+            writeln!(lcov, "LF:{}", file.lines.len()).unwrap();
+            writeln!(lcov, "LH:{lines_hit}").unwrap();
+            writeln!(lcov, "end_of_record").unwrap();
+        }
+        lcov
+    }
+}
@@ -0,0 +1,82 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+
+use super::{CoverageInfo, CoverageReport};
+use crate::SierraCasmRunner;
+
+#[test]
+fn coverage_of_branches() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            #[inline(never)]
+            fn abs_diff(a: u32, b: u32) -> u32 {
+                if a > b {
+                    a - b
+                } else {
+                    b - a
+                }
+            }
+
+            fn main() -> u32 {
+                abs_diff(5, 3) + abs_diff(7, 2)
+            }
+        "},
+    )
+    .unwrap();
+    let crate_input = test_module.crate_id.long(&db).clone().into_crate_input(&db);
+    DiagnosticsReporter::stderr().with_crates(&[crate_input]).ensure(&db).unwrap();
+    let SierraProgramWithDebug { program: sierra_program, debug_info } =
+        db.get_sierra_program(vec![test_module.crate_id]).unwrap();
+    let sierra_program = replace_sierra_ids_in_program(&db, sierra_program);
+    let runner = SierraCasmRunner::new(
+        sierra_program.clone(),
+        Some(Default::default()),
+        OrderedHashMap::default(),
+        None,
+    )
+    .unwrap()
+    .with_coverage();
+    let func = runner.find_function("::main").unwrap();
+    let result = runner
+        .run_function_with_starknet_context(
+            func,
+            vec![],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    let mut coverage = CoverageInfo::default();
+    coverage.merge(&result.coverage_info.unwrap());
+    let report = CoverageReport::new(
+        &sierra_program,
+        runner.casm_program(),
+        &debug_info.statements_locations.extract_statements_source_code_locations(&db),
+        &coverage,
+    );
+
+    let file = &report.files["lib.cairo"];
+    // `abs_diff` is called twice, always taking the `a > b` branch.
+    assert_eq!(file.lines[&4], 2);
+    assert_eq!(file.lines[&6], 0);
+    assert_eq!(file.lines[&11], 1);
+    let comparison_branches: Vec<_> = file
+        .branches
+        .iter()
+        .filter(|branch| branch.line == 3)
+        .map(|branch| (branch.branch_idx, branch.hits))
+        .collect();
+    assert_eq!(comparison_branches, vec![(0, 0), (1, 2)]);
+
+    let lcov = report.to_lcov();
+    assert!(lcov.contains("SF:lib.cairo\n"));
+    assert!(lcov.contains("DA:4,2\n"));
+    assert!(lcov.contains("DA:6,0\n"));
+}
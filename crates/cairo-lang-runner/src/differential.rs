@@ -0,0 +1,272 @@
+//! Differential testing of the Sierra simulator against the CASM runner, for validating that a
+//! program has the same semantics before and after its compilation to CASM.
+
+use std::collections::HashMap;
+
+use cairo_lang_runnable_utils::builder::RunnableBuilder;
+use cairo_lang_sierra::extensions::NamedType;
+use cairo_lang_sierra::extensions::bitwise::BitwiseType;
+use cairo_lang_sierra::extensions::enm::EnumType;
+use cairo_lang_sierra::extensions::felt252::Felt252Type;
+use cairo_lang_sierra::extensions::gas::{CostTokenType, GasBuiltinType};
+use cairo_lang_sierra::extensions::int::unsigned::{Uint8Type, Uint16Type, Uint32Type, Uint64Type};
+use cairo_lang_sierra::extensions::int::unsigned128::Uint128Type;
+use cairo_lang_sierra::extensions::non_zero::NonZeroType;
+use cairo_lang_sierra::extensions::range_check::RangeCheckType;
+use cairo_lang_sierra::extensions::structure::StructType;
+use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_sierra::program::{GenericArg, StatementIdx};
+use cairo_lang_sierra::simulation::value::CoreValue;
+use cairo_lang_sierra::simulation::{self, SimulationError};
+use cairo_lang_sierra_to_casm::invocations::enm::get_variant_selector;
+use itertools::Itertools;
+use num_traits::ToPrimitive;
+use starknet_types_core::felt::Felt as Felt252;
+use thiserror::Error;
+
+use crate::{Arg, RunResultValue, RunnerError, SierraCasmRunner, token_gas_cost};
+
+#[cfg(test)]
+#[path = "differential_test.rs"]
+mod test;
+
+#[derive(Debug, Error)]
+pub enum DifferentialError {
+    #[error(transparent)]
+    RunnerError(#[from] RunnerError),
+    #[error("Simulation failed: {0}")]
+    SimulationError(#[from] SimulationError),
+    #[error("Type `{0}` is not supported in differential runs.")]
+    UnsupportedType(ConcreteTypeId),
+    #[error("The value {0:?} does not match its type.")]
+    UnexpectedValue(CoreValue),
+    #[error("Expected more argument values.")]
+    MissingArguments,
+    #[error("The simulator does not support an initial gas counter of {0}.")]
+    UnsupportedAvailableGas(usize),
+    #[error("Simulation and CASM run results differ.\nSimulation: {simulation:?}\nCASM: {casm:?}")]
+    Mismatch { simulation: Box<DifferentialOutcome>, casm: Box<DifferentialOutcome> },
+}
+
+/// The outcome of a run, comparable between the Sierra simulation and the CASM run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DifferentialOutcome {
+    /// The non-implicit return values on success, or the panic data on panic.
+    pub value: Result<Vec<CoreValue>, Vec<Felt252>>,
+    /// The remaining gas at the end of the run, if the function uses gas.
+    pub gas_counter: Option<Felt252>,
+}
+
+/// Runs the function ending with `name_suffix` both with the Sierra simulator and on the VM,
+/// with the same arguments and available gas, and returns the outcome if both runs agree.
+///
+/// `args` are the memory representation of the non-implicit parameters of the function. Only
+/// felt252, unsigned integers, structs, enums and non-zero wrappers of those are supported as
+/// parameter and return types. Note that the simulator does not support all libfuncs, and panics
+/// when simulating an unsupported one.
+pub fn run_differential(
+    runner: &SierraCasmRunner,
+    name_suffix: &str,
+    args: &[Felt252],
+    available_gas: Option<usize>,
+) -> Result<DifferentialOutcome, DifferentialError> {
+    let builder = runner.builder();
+    let func = runner.find_function(name_suffix)?;
+    let initial_gas = runner.get_initial_available_gas(func, available_gas)?;
+    let initial_gas = i64::try_from(initial_gas)
+        .map_err(|_| DifferentialError::UnsupportedAvailableGas(initial_gas))?;
+
+    // The simulation.
+    let mut arg_values = args.iter().cloned();
+    let inputs = func
+        .signature
+        .param_types
+        .iter()
+        .map(|ty| {
+            let generic_id = &builder.type_long_id(ty).generic_id;
+            Ok(if *generic_id == GasBuiltinType::ID {
+                CoreValue::GasBuiltin(initial_gas)
+            } else if *generic_id == RangeCheckType::ID {
+                CoreValue::RangeCheck
+            } else if *generic_id == BitwiseType::ID {
+                CoreValue::Bitwise
+            } else {
+                decode_value(builder, ty, &mut arg_values)?
+            })
+        })
+        .collect::<Result<Vec<_>, DifferentialError>>()?;
+    let statement_gas_info = statement_gas_info(builder);
+    let outputs = simulation::run(builder.sierra_program(), &statement_gas_info, &func.id, inputs)?;
+    let mut simulation = DifferentialOutcome { value: Ok(vec![]), gas_counter: None };
+    for (ty, output) in func.signature.ret_types.iter().zip(outputs) {
+        let generic_id = &builder.type_long_id(ty).generic_id;
+        if *generic_id == GasBuiltinType::ID {
+            let CoreValue::GasBuiltin(gas) = output else {
+                return Err(DifferentialError::UnexpectedValue(output));
+            };
+            simulation.gas_counter = Some(gas.into());
+        } else if builder.is_user_arg_type(generic_id) {
+            simulation.value = match panic_result_inner_type(builder, ty) {
+                Some(_) => simulation_panic_result(output)?,
+                None => Ok(vec![output]),
+            };
+        }
+    }
+
+    // The CASM run.
+    let result = runner.run_function_with_starknet_context(
+        func,
+        args.iter().cloned().map(Arg::Value).collect(),
+        available_gas,
+        Default::default(),
+    )?;
+    let user_ret_type = func
+        .signature
+        .ret_types
+        .iter()
+        .find(|ty| builder.is_user_arg_type(&builder.type_long_id(ty).generic_id));
+    let value = match (result.value, user_ret_type) {
+        (RunResultValue::Success(values), Some(ty)) => {
+            let ty = panic_result_inner_type(builder, ty).unwrap_or(ty);
+            Ok(vec![decode_value(builder, ty, &mut values.into_iter())?])
+        }
+        (RunResultValue::Success(_), None) => Ok(vec![]),
+        (RunResultValue::Panic(panic_data), _) => Err(panic_data),
+    };
+    let casm = DifferentialOutcome { value, gas_counter: result.gas_counter };
+
+    if simulation != casm {
+        return Err(DifferentialError::Mismatch {
+            simulation: Box::new(simulation),
+            casm: Box::new(casm),
+        });
+    }
+    Ok(casm)
+}
+
+/// Returns the gas withdrawn at each statement, as the VM charges it.
+fn statement_gas_info(builder: &RunnableBuilder) -> HashMap<StatementIdx, i64> {
+    let mut info = HashMap::<StatementIdx, i64>::new();
+    for ((idx, token_type), value) in builder.metadata().gas_info.variable_values.iter() {
+        if CostTokenType::iter_casm_tokens().contains(token_type) {
+            *info.entry(*idx).or_default() += value * token_gas_cost(*token_type) as i64;
+        }
+    }
+    info
+}
+
+/// Returns the inner type of `ty` if it is a `PanicResult`.
+fn panic_result_inner_type<'a>(
+    builder: &'a RunnableBuilder,
+    ty: &ConcreteTypeId,
+) -> Option<&'a ConcreteTypeId> {
+    let long_id = builder.type_long_id(ty);
+    if long_id.generic_id != EnumType::ID {
+        return None;
+    }
+    let [GenericArg::UserType(user_type), GenericArg::Type(inner), _] =
+        long_id.generic_args.as_slice()
+    else {
+        return None;
+    };
+    user_type
+        .debug_name
+        .as_ref()
+        .is_some_and(|name| name.starts_with("core::panics::PanicResult::"))
+        .then_some(inner)
+}
+
+/// Converts the simulated value of a `PanicResult` to the success values or the panic data.
+fn simulation_panic_result(
+    value: CoreValue,
+) -> Result<Result<Vec<CoreValue>, Vec<Felt252>>, DifferentialError> {
+    let CoreValue::Enum { value: inner, index } = value else {
+        return Err(DifferentialError::UnexpectedValue(value));
+    };
+    if index == 0 {
+        return Ok(Ok(vec![*inner]));
+    }
+    // The error variant is a tuple of a `Panic` and the panic data array.
+    let CoreValue::Struct(members) = *inner else {
+        return Err(DifferentialError::UnexpectedValue(*inner));
+    };
+    let Some(CoreValue::Array(panic_data)) = members.into_iter().nth(1) else {
+        return Err(DifferentialError::UnexpectedValue(CoreValue::Uninitialized));
+    };
+    let panic_data = panic_data
+        .into_iter()
+        .map(|value| match value {
+            CoreValue::Felt252(value) => Ok(value),
+            other => Err(DifferentialError::UnexpectedValue(other)),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Err(panic_data))
+}
+
+/// Decodes a value of type `ty` from its memory representation.
+fn decode_value(
+    builder: &RunnableBuilder,
+    ty: &ConcreteTypeId,
+    values: &mut impl Iterator<Item = Felt252>,
+) -> Result<CoreValue, DifferentialError> {
+    let long_id = builder.type_long_id(ty);
+    let generic_id = &long_id.generic_id;
+    let unsupported = || DifferentialError::UnsupportedType(ty.clone());
+    if *generic_id == StructType::ID {
+        return Ok(CoreValue::Struct(
+            long_id.generic_args[1..]
+                .iter()
+                .map(|arg| match arg {
+                    GenericArg::Type(member) => decode_value(builder, member, values),
+                    _ => Err(unsupported()),
+                })
+                .collect::<Result<_, _>>()?,
+        ));
+    }
+    if *generic_id == EnumType::ID {
+        let variants = long_id.generic_args[1..]
+            .iter()
+            .map(|arg| match arg {
+                GenericArg::Type(variant) => Ok(variant),
+                _ => Err(unsupported()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let selector = values.next().ok_or(DifferentialError::MissingArguments)?;
+        let index = (0..variants.len())
+            .find(|index| {
+                get_variant_selector(variants.len(), *index)
+                    .is_ok_and(|variant_selector| Felt252::from(variant_selector) == selector)
+            })
+            .ok_or(DifferentialError::UnexpectedValue(CoreValue::Felt252(selector)))?;
+        // The variant value is at the end of the enum, after the padding.
+        let padding = builder.type_size(ty) - 1 - builder.type_size(variants[index]);
+        for _ in 0..padding {
+            values.next().ok_or(DifferentialError::MissingArguments)?;
+        }
+        let value = decode_value(builder, variants[index], values)?;
+        return Ok(CoreValue::Enum { value: Box::new(value), index });
+    }
+    if *generic_id == NonZeroType::ID {
+        let [GenericArg::Type(inner)] = long_id.generic_args.as_slice() else {
+            return Err(unsupported());
+        };
+        return decode_value(builder, inner, values);
+    }
+    let decode_felt: fn(Felt252) -> Option<CoreValue> = if *generic_id == Felt252Type::ID {
+        |value| Some(CoreValue::Felt252(value))
+    } else if *generic_id == Uint8Type::ID {
+        |value| value.to_u8().map(CoreValue::Uint8)
+    } else if *generic_id == Uint16Type::ID {
+        |value| value.to_u16().map(CoreValue::Uint16)
+    } else if *generic_id == Uint32Type::ID {
+        |value| value.to_u32().map(CoreValue::Uint32)
+    } else if *generic_id == Uint64Type::ID {
+        |value| value.to_u64().map(CoreValue::Uint64)
+    } else if *generic_id == Uint128Type::ID {
+        |value| value.to_u128().map(CoreValue::Uint128)
+    } else {
+        return Err(unsupported());
+    };
+    let value = values.next().ok_or(DifferentialError::MissingArguments)?;
+    decode_felt(value).ok_or(DifferentialError::UnexpectedValue(CoreValue::Felt252(value)))
+}
@@ -0,0 +1,120 @@
+use std::sync::LazyLock;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra::simulation::value::CoreValue;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+use starknet_types_core::felt::Felt as Felt252;
+use test_case::test_case;
+
+use super::{DifferentialError, DifferentialOutcome, run_differential};
+use crate::SierraCasmRunner;
+
+/// The runner of the program compiled from [CODE].
+static RUNNER: LazyLock<SierraCasmRunner> = LazyLock::new(|| runner_for_code(CODE));
+
+/// Creates a runner for a program compiled from `code`.
+fn runner_for_code(code: &str) -> SierraCasmRunner {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(&db, code).unwrap();
+    let crate_input = test_module.crate_id.long(&db).clone().into_crate_input(&db);
+    DiagnosticsReporter::stderr().with_crates(&[crate_input]).ensure(&db).unwrap();
+    let SierraProgramWithDebug { program: sierra_program, .. } =
+        db.get_sierra_program(vec![test_module.crate_id]).unwrap();
+    let sierra_program = replace_sierra_ids_in_program(&db, sierra_program);
+    SierraCasmRunner::new(sierra_program, Some(Default::default()), OrderedHashMap::default(), None)
+        .unwrap()
+}
+
+const CODE: &str = indoc! {"
+    fn fib(a: felt252, b: felt252, n: felt252) -> felt252 {
+        if n == 0 {
+            a
+        } else {
+            fib(b, a + b, n - 1)
+        }
+    }
+
+    fn checked_div(a: u128, b: u128) -> u128 {
+        if b == 0 {
+            panic_with_felt252('division by zero');
+        }
+        a / b
+    }
+
+    fn sign(x: felt252) -> Option<bool> {
+        if x == 0 {
+            Option::None
+        } else {
+            Option::Some(x == 1)
+        }
+    }
+
+    fn sum(values: Array<felt252>) -> felt252 {
+        0
+    }
+
+    fn narrow(x: u64) -> Option<u8> {
+        x.try_into()
+    }
+
+    fn widen_divmod(a: u16, b: u32) -> (u32, u32) {
+        let a: u32 = a.into();
+        (a / b, a % b)
+    }
+"};
+
+#[test_case("::fib", &[1, 1, 10]; "fib")]
+#[test_case("::checked_div", &[7, 2]; "checked_div")]
+#[test_case("::checked_div", &[7, 0]; "checked_div by zero")]
+#[test_case("::sign", &[0]; "sign none")]
+#[test_case("::sign", &[1]; "sign true")]
+#[test_case("::sign", &[5]; "sign false")]
+#[test_case("::narrow", &[200]; "narrow fits")]
+#[test_case("::narrow", &[300]; "narrow overflows")]
+#[test_case("::widen_divmod", &[65535, 10]; "widen_divmod")]
+fn test_differential(name: &str, args: &[u128]) {
+    let args = args.iter().map(|arg| Felt252::from(*arg)).collect::<Vec<_>>();
+    run_differential(&RUNNER, name, &args, Some(u32::MAX as usize)).unwrap();
+}
+
+#[test]
+fn test_differential_outcome() {
+    let args = [Felt252::from(1), Felt252::from(1), Felt252::from(10)];
+    let DifferentialOutcome { value, gas_counter } =
+        run_differential(&RUNNER, "::fib", &args, Some(u32::MAX as usize)).unwrap();
+    // The success value of a panicable function is wrapped in a tuple.
+    assert_eq!(value, Ok(vec![CoreValue::Struct(vec![CoreValue::Felt252(Felt252::from(89))])]));
+    assert!(gas_counter.is_some());
+
+    let args = [Felt252::from(7), Felt252::from(0)];
+    let DifferentialOutcome { value, .. } =
+        run_differential(&RUNNER, "::checked_div", &args, Some(u32::MAX as usize)).unwrap();
+    assert_eq!(value, Err(vec![Felt252::from_bytes_be_slice(b"division by zero")]));
+
+    let DifferentialOutcome { value, .. } =
+        run_differential(&RUNNER, "::sign", &[Felt252::from(1)], Some(u32::MAX as usize)).unwrap();
+    assert_eq!(
+        value,
+        Ok(vec![CoreValue::Enum {
+            value: Box::new(CoreValue::Enum {
+                value: Box::new(CoreValue::Struct(vec![])),
+                index: 1
+            }),
+            index: 0,
+        }])
+    );
+}
+
+#[test]
+fn test_differential_unsupported_type() {
+    assert!(matches!(
+        run_differential(&RUNNER, "::sum", &[], Some(u32::MAX as usize)),
+        Err(DifferentialError::UnsupportedType(_))
+    ));
+}
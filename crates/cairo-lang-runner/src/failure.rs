@@ -0,0 +1,177 @@
+//! Structured reports of failed runs - the decoded panic data and the Cairo call stack of the
+//! panic.
+
+use std::fmt::Display;
+
+use cairo_lang_runnable_utils::builder::RunnableBuilder;
+use cairo_lang_sierra::extensions::NamedType;
+use cairo_lang_sierra::extensions::core::CoreConcreteLibfunc;
+use cairo_lang_sierra::extensions::enm::EnumType;
+use cairo_lang_sierra::program::{GenericArg, Statement, StatementIdx};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_vm::vm::trace::trace_entry::RelocatedTraceEntry;
+use starknet_types_core::felt::Felt as Felt252;
+
+use crate::casm_run::format_next_item;
+use crate::profiling::user_function_idx_by_sierra_statement_idx;
+
+#[cfg(test)]
+#[path = "failure_test.rs"]
+mod test;
+
+/// The report of a run that panicked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunFailure {
+    /// The raw panic data.
+    pub panic_data: Vec<Felt252>,
+    /// The Cairo call stack at the panic, from the entry point to the panicking function.
+    /// Empty if it could not be reconstructed, e.g. if the Sierra program has no debug names.
+    pub call_stack: Vec<FailureFrame>,
+}
+
+/// A frame of the call stack of a panic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailureFrame {
+    /// The name of the Sierra function of the frame.
+    pub function: String,
+    /// The last Sierra statement executed in the frame - the call of the next frame, or the return
+    /// of the panic for the panicking function.
+    pub statement_idx: StatementIdx,
+}
+
+impl RunFailure {
+    /// Builds the report of a panicking run from its trace and memory.
+    ///
+    /// The call stack is the stack of the first return of a panic, found by checking the returned
+    /// `PanicResult` of every function returning one.
+    pub fn from_trace(
+        builder: &RunnableBuilder,
+        // The offset in memory where builder.casm_program() was loaded.
+        load_offset: usize,
+        trace: &[RelocatedTraceEntry],
+        memory: &[Option<Felt252>],
+        panic_data: Vec<Felt252>,
+    ) -> Self {
+        let casm_program = builder.casm_program();
+        let sierra_program = builder.sierra_program();
+        let sierra_statement_info = &casm_program.debug_info.sierra_statement_info;
+        let bytecode_len = sierra_statement_info.last().map_or(0, |info| info.end_offset);
+
+        // The current function stack, with the last statement executed in each frame.
+        let mut stack: Vec<(usize, StatementIdx)> = vec![];
+        let mut in_call = false;
+        for step in trace {
+            // Skip the header, the footer and the constant segments.
+            let Some(pc) = step.pc.checked_sub(load_offset).filter(|pc| *pc < bytecode_len) else {
+                continue;
+            };
+            let statement_idx = casm_program.sierra_statement_index_by_pc(pc);
+            let function_idx =
+                user_function_idx_by_sierra_statement_idx(sierra_program, statement_idx);
+            if in_call || stack.is_empty() {
+                stack.push((function_idx, statement_idx));
+                in_call = false;
+            }
+            stack.last_mut().unwrap().1 = statement_idx;
+            match &sierra_program.statements[statement_idx.0] {
+                // A call is a single step, so the callee starts at the next one.
+                Statement::Invocation(_) => in_call = is_call(builder, statement_idx),
+                Statement::Return(_) => {
+                    if returns_panic(builder, function_idx, step.ap, memory) {
+                        let call_stack = stack
+                            .iter()
+                            .map(|(function_idx, statement_idx)| FailureFrame {
+                                function: sierra_program.funcs[*function_idx].id.to_string(),
+                                statement_idx: *statement_idx,
+                            })
+                            .collect();
+                        return Self { panic_data, call_stack };
+                    }
+                    stack.pop();
+                }
+            }
+        }
+        Self { panic_data, call_stack: vec![] }
+    }
+
+    /// Returns the decoded panic data, e.g. `Panicked with "Out of bounds".`.
+    pub fn message(&self) -> String {
+        self.message_with_error_names(&Default::default())
+    }
+
+    /// Returns the decoded panic data, with felts found in `error_names` replaced by their names,
+    /// e.g. names of errors taken from an ABI.
+    pub fn message_with_error_names(
+        &self,
+        error_names: &OrderedHashMap<Felt252, String>,
+    ) -> String {
+        let mut felts = self.panic_data.clone().into_iter();
+        let mut items = Vec::new();
+        loop {
+            if let Some(name) = felts.as_slice().first().and_then(|felt| error_names.get(felt)) {
+                items.push(name.clone());
+                felts.next();
+                continue;
+            }
+            let Some(item) = format_next_item(&mut felts) else {
+                break;
+            };
+            items.push(item.quote_if_string());
+        }
+        let items =
+            if let [item] = &items[..] { item.clone() } else { format!("({})", items.join(", ")) };
+        format!("Panicked with {items}.")
+    }
+}
+
+impl Display for RunFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.message())?;
+        if !self.call_stack.is_empty() {
+            writeln!(f, "Call stack:")?;
+            for frame in &self.call_stack {
+                writeln!(f, "    {} (statement #{})", frame.function, frame.statement_idx.0)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if the given statement is a function call.
+fn is_call(builder: &RunnableBuilder, statement_idx: StatementIdx) -> bool {
+    let Statement::Invocation(invocation) = &builder.sierra_program().statements[statement_idx.0]
+    else {
+        return false;
+    };
+    matches!(
+        builder.registry().get_libfunc(&invocation.libfunc_id),
+        Ok(CoreConcreteLibfunc::FunctionCall(_) | CoreConcreteLibfunc::CouponCall(_))
+    )
+}
+
+/// Returns true if the function returns a `PanicResult`, and the one returned at a `ret` step
+/// with the given `ap` is a panic.
+fn returns_panic(
+    builder: &RunnableBuilder,
+    function_idx: usize,
+    ap: usize,
+    memory: &[Option<Felt252>],
+) -> bool {
+    let function = &builder.sierra_program().funcs[function_idx];
+    let Some(ty) = function.signature.ret_types.last() else {
+        return false;
+    };
+    let long_id = builder.type_long_id(ty);
+    let is_panic_result = long_id.generic_id == EnumType::ID
+        && matches!(long_id.generic_args.first(), Some(GenericArg::UserType(ut))
+            if ut.debug_name.as_ref().is_some_and(|name| name.starts_with("core::panics::PanicResult::")));
+    if !is_panic_result {
+        return false;
+    }
+    // The `PanicResult` is the last returned value, starting with its variant selector, which is
+    // the index of the variant for enums of two variants.
+    let size = builder.type_size(ty) as usize;
+    ap.checked_sub(size)
+        .and_then(|selector_address| memory.get(selector_address)?.as_ref())
+        .is_some_and(|selector| *selector == Felt252::from(1))
+}
@@ -0,0 +1,63 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+use itertools::Itertools;
+use starknet_types_core::felt::Felt as Felt252;
+
+use crate::{RunResultValue, SierraCasmRunner};
+
+#[test]
+fn failure_with_call_stack() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            #[inline(never)]
+            fn check(a: felt252) {
+                if a == 3 {
+                    core::panic_with_felt252('boom');
+                }
+            }
+
+            #[inline(never)]
+            fn check_all(a: felt252) {
+                check(a - 1);
+                check(a);
+            }
+
+            fn main() {
+                check_all(2);
+                check_all(3);
+            }
+        "},
+    )
+    .unwrap();
+    let crate_input = test_module.crate_id.long(&db).clone().into_crate_input(&db);
+    DiagnosticsReporter::stderr().with_crates(&[crate_input]).ensure(&db).unwrap();
+    let sierra_program = db.get_sierra_program(vec![test_module.crate_id]).unwrap().program.clone();
+    let sierra_program = replace_sierra_ids_in_program(&db, &sierra_program);
+    let runner =
+        SierraCasmRunner::new(sierra_program, None, OrderedHashMap::default(), None).unwrap();
+    let func = runner.find_function("::main").unwrap();
+    let result =
+        runner.run_function_with_starknet_context(func, vec![], None, Default::default()).unwrap();
+    let boom = Felt252::from_bytes_be_slice(b"boom");
+    assert_eq!(result.value, RunResultValue::Panic(vec![boom]));
+
+    let failure = result.failure.unwrap();
+    assert_eq!(failure.panic_data, vec![boom]);
+    assert_eq!(failure.message(), "Panicked with 0x626f6f6d ('boom').");
+    let error_names = OrderedHashMap::from_iter([(boom, "Error::Boom".to_string())]);
+    assert_eq!(failure.message_with_error_names(&error_names), "Panicked with Error::Boom.");
+    let functions = failure
+        .call_stack
+        .iter()
+        .map(|frame| frame.function.rsplit("::").next().unwrap())
+        .collect_vec();
+    assert_eq!(functions, vec!["main", "check_all", "check"]);
+    assert!(failure.to_string().starts_with("Panicked with 0x626f6f6d ('boom').\nCall stack:\n"));
+}
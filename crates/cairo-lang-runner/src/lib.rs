@@ -7,7 +7,10 @@ use cairo_lang_sierra::extensions::NamedType;
 use cairo_lang_sierra::extensions::enm::EnumType;
 use cairo_lang_sierra::extensions::gas::{CostTokenType, GasBuiltinType};
 use cairo_lang_sierra::ids::{ConcreteTypeId, GenericTypeId};
-use cairo_lang_sierra::program::{Function, GenericArg};
+use cairo_lang_sierra::instrumentation::Probe;
+use cairo_lang_sierra::program::{Function, GenericArg, StatementIdx};
+use cairo_lang_sierra_generator::debug_info::SourceMap;
+use cairo_lang_sierra_to_casm::compiler::CairoProgram;
 use cairo_lang_sierra_to_casm::metadata::MetadataComputationConfig;
 use cairo_lang_starknet::contract::ContractInfo;
 use cairo_lang_utils::casts::IntoOrPanic;
@@ -20,20 +23,31 @@ use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
 use cairo_vm::vm::runners::cairo_runner::{ExecutionResources, RunResources};
 use cairo_vm::vm::vm_core::VirtualMachine;
 use casm_run::hint_to_hint_params;
-pub use casm_run::{CairoHintProcessor, StarknetState};
+pub use casm_run::{CairoHintProcessor, StarknetState, SyscallInterceptor, SyscallResult};
+use cost_profile::CostProfile;
+use coverage::CoverageInfo;
+use failure::RunFailure;
 use num_bigint::BigInt;
 use num_traits::ToPrimitive;
+use probe_profile::probe_hits_from_trace;
 use profiling::ProfilingInfo;
 use starknet_types_core::felt::Felt as Felt252;
 use thiserror::Error;
 
 use crate::casm_run::{RunFunctionResult, StarknetHintProcessor};
 use crate::profiling::ProfilerConfig;
+use crate::trace::{ExecutionTracer, TraceSink};
 
 pub mod casm_run;
 pub mod clap;
+pub mod cost_profile;
+pub mod coverage;
+pub mod differential;
+pub mod failure;
+pub mod probe_profile;
 pub mod profiling;
 pub mod short_string;
+pub mod trace;
 
 const MAX_STACK_TRACE_DEPTH_DEFAULT: usize = 100;
 
@@ -49,6 +63,8 @@ pub enum RunnerError {
     ArgumentsSizeMismatch { expected: usize, actual: usize },
     #[error(transparent)]
     CairoRunError(#[from] Box<CairoRunError>),
+    #[error("Failed writing the execution trace: {0}")]
+    TraceSinkError(#[from] std::io::Error),
 }
 
 /// The full result of a run with Starknet state.
@@ -60,6 +76,14 @@ pub struct RunResultStarknet {
     pub used_resources: StarknetExecutionResources,
     /// The profiling info of the run, if requested.
     pub profiling_info: Option<ProfilingInfo>,
+    /// The coverage info of the run, if requested.
+    pub coverage_info: Option<CoverageInfo>,
+    /// The cost profile of the run, if requested.
+    pub cost_profile: Option<CostProfile>,
+    /// The number of executions of each probe, if probes were given.
+    pub probe_hits: Option<Vec<usize>>,
+    /// The report of the failure of the run, if it panicked.
+    pub failure: Option<RunFailure>,
 }
 
 /// The full result of a run.
@@ -71,6 +95,14 @@ pub struct RunResult {
     pub used_resources: ExecutionResources,
     /// The profiling info of the run, if requested.
     pub profiling_info: Option<ProfilingInfo>,
+    /// The coverage info of the run, if requested.
+    pub coverage_info: Option<CoverageInfo>,
+    /// The cost profile of the run, if requested.
+    pub cost_profile: Option<CostProfile>,
+    /// The number of executions of each probe, if probes were given.
+    pub probe_hits: Option<Vec<usize>>,
+    /// The report of the failure of the run, if it panicked.
+    pub failure: Option<RunFailure>,
 }
 
 /// The execution resources in a run.
@@ -182,6 +214,14 @@ pub struct SierraCasmRunner {
     starknet_contracts_info: OrderedHashMap<Felt252, ContractInfo>,
     /// Whether to run the profiler when running using this runner.
     run_profiler: Option<ProfilingInfoCollectionConfig>,
+    /// Whether to collect coverage info when running using this runner.
+    collect_coverage: bool,
+    /// Whether to collect the cost profile when running using this runner.
+    collect_cost_profile: bool,
+    /// The statements of the probes to count the executions of, if the program is instrumented.
+    probes: Option<Vec<StatementIdx>>,
+    /// The tracer of the runs of this runner, if tracing is enabled.
+    tracer: Option<ExecutionTracer>,
 }
 impl SierraCasmRunner {
     pub fn new(
@@ -190,11 +230,71 @@ impl SierraCasmRunner {
         starknet_contracts_info: OrderedHashMap<Felt252, ContractInfo>,
         run_profiler: Option<ProfilingInfoCollectionConfig>,
     ) -> Result<Self, RunnerError> {
-        Ok(Self {
-            builder: RunnableBuilder::new(sierra_program, metadata_config)?,
+        Ok(Self::from_builder(
+            RunnableBuilder::new(sierra_program, metadata_config)?,
             starknet_contracts_info,
             run_profiler,
-        })
+        ))
+    }
+
+    /// Creates a runner of the program of an existing builder, e.g. one compiled with a
+    /// non-default CASM configuration (see [RunnableBuilder::new_ex]).
+    pub fn from_builder(
+        builder: RunnableBuilder,
+        starknet_contracts_info: OrderedHashMap<Felt252, ContractInfo>,
+        run_profiler: Option<ProfilingInfoCollectionConfig>,
+    ) -> Self {
+        Self {
+            builder,
+            starknet_contracts_info,
+            run_profiler,
+            collect_coverage: false,
+            collect_cost_profile: false,
+            probes: None,
+            tracer: None,
+        }
+    }
+
+    /// Makes the runner collect coverage info in the results of its runs.
+    pub fn with_coverage(mut self) -> Self {
+        self.collect_coverage = true;
+        self
+    }
+
+    /// Makes the runner collect the cost profile in the results of its runs.
+    pub fn with_cost_profile(mut self) -> Self {
+        self.collect_cost_profile = true;
+        self
+    }
+
+    /// Makes the runner count the executions of the given probes of its instrumented program in
+    /// the results of its runs.
+    pub fn with_probes(mut self, probes: &[Probe]) -> Self {
+        self.probes = Some(probes.iter().map(|probe| probe.statement_idx).collect());
+        self
+    }
+
+    /// Makes the runner stream the execution traces of its runs into `sink`, step by step.
+    /// If a source map is given, its pcs must be relative to the start of the CASM program (i.e.
+    /// built with a pc offset of 0), and the steps are attributed to the Cairo code they were
+    /// compiled from.
+    pub fn with_trace(
+        mut self,
+        sink: impl TraceSink + 'static,
+        source_map: Option<SourceMap>,
+    ) -> Self {
+        self.tracer = Some(ExecutionTracer::new(sink, source_map));
+        self
+    }
+
+    /// Returns the CASM program run by this runner.
+    pub fn casm_program(&self) -> &CairoProgram {
+        self.builder.casm_program()
+    }
+
+    /// Returns the builder of the runnable program, e.g. for inspecting its types.
+    pub fn builder(&self) -> &RunnableBuilder {
+        &self.builder
     }
 
     /// Runs the VM starting from a function in the context of a given Starknet state.
@@ -218,8 +318,17 @@ impl SierraCasmRunner {
         hint_processor: &mut dyn StarknetHintProcessor,
         PreparedStarknetContext { hints_dict, bytecode, builtins }: PreparedStarknetContext,
     ) -> Result<RunResultStarknet, RunnerError> {
-        let RunResult { gas_counter, memory, value, used_resources, profiling_info } =
-            self.run_function(func, hint_processor, hints_dict, bytecode.iter(), builtins)?;
+        let RunResult {
+            gas_counter,
+            memory,
+            value,
+            used_resources,
+            profiling_info,
+            coverage_info,
+            cost_profile,
+            probe_hits,
+            failure,
+        } = self.run_function(func, hint_processor, hints_dict, bytecode.iter(), builtins)?;
         let mut all_used_resources = hint_processor.take_syscalls_used_resources();
         all_used_resources.basic_resources += &used_resources;
         Ok(RunResultStarknet {
@@ -229,6 +338,10 @@ impl SierraCasmRunner {
             starknet_state: hint_processor.take_starknet_state(),
             used_resources: all_used_resources,
             profiling_info,
+            coverage_info,
+            cost_profile,
+            probe_hits,
+            failure,
         })
     }
 
@@ -306,16 +419,55 @@ impl SierraCasmRunner {
             }
         };
 
-        let Self { builder, starknet_contracts_info: _, run_profiler } = self;
+        let Self {
+            builder,
+            starknet_contracts_info: _,
+            run_profiler,
+            collect_coverage,
+            collect_cost_profile,
+            probes,
+            tracer,
+        } = self;
 
         // The real program starts right after the header.
         let load_offset = header_end + 1;
 
+        if let Some(tracer) = tracer {
+            tracer.record(builder, load_offset, &relocated_trace)?;
+        }
+
         let profiling_info = run_profiler.as_ref().map(|config| {
             ProfilingInfo::from_trace(builder, load_offset, config, &relocated_trace)
         });
+        let coverage_info = collect_coverage
+            .then(|| CoverageInfo::from_trace(builder, load_offset, &relocated_trace));
+        let cost_profile = collect_cost_profile
+            .then(|| CostProfile::from_trace(builder, load_offset, &relocated_trace));
+        let probe_hits = probes
+            .as_ref()
+            .map(|probes| probe_hits_from_trace(builder, load_offset, &relocated_trace, probes));
+        let failure = match &value {
+            RunResultValue::Success(_) => None,
+            RunResultValue::Panic(panic_data) => Some(RunFailure::from_trace(
+                builder,
+                load_offset,
+                &relocated_trace,
+                &memory,
+                panic_data.clone(),
+            )),
+        };
 
-        Ok(RunResult { gas_counter, memory, value, used_resources, profiling_info })
+        Ok(RunResult {
+            gas_counter,
+            memory,
+            value,
+            used_resources,
+            profiling_info,
+            coverage_info,
+            cost_profile,
+            probe_hits,
+            failure,
+        })
     }
 
     /// Prepares context for running a function in the context of a given Starknet state.
@@ -344,6 +496,8 @@ impl SierraCasmRunner {
             no_temporary_segments: true,
             markers: Default::default(),
             panic_traceback: Default::default(),
+            syscall_interceptor: None,
+            captured_output: None,
         };
         Ok((
             hint_processor,
@@ -0,0 +1,38 @@
+//! Counting the executions of the probes of programs instrumented by
+//! [cairo_lang_sierra::instrumentation].
+
+use cairo_lang_runnable_utils::builder::RunnableBuilder;
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_vm::vm::trace::trace_entry::RelocatedTraceEntry;
+
+#[cfg(test)]
+#[path = "probe_profile_test.rs"]
+mod test;
+
+/// Returns the number of executions of each of the given probe statements in a run.
+///
+/// A probe compiles to a single instruction, so its executions are the visits of its pc.
+pub fn probe_hits_from_trace(
+    builder: &RunnableBuilder,
+    // The offset in memory where builder.casm_program() was loaded.
+    load_offset: usize,
+    trace: &[RelocatedTraceEntry],
+    probes: &[StatementIdx],
+) -> Vec<usize> {
+    let sierra_statement_info = &builder.casm_program().debug_info.sierra_statement_info;
+    let probe_by_pc: UnorderedHashMap<usize, usize> = probes
+        .iter()
+        .enumerate()
+        .map(|(probe_idx, statement_idx)| {
+            (load_offset + sierra_statement_info[statement_idx.0].start_offset, probe_idx)
+        })
+        .collect();
+    let mut hits = vec![0; probes.len()];
+    for step in trace {
+        if let Some(probe_idx) = probe_by_pc.get(&step.pc) {
+            hits[*probe_idx] += 1;
+        }
+    }
+    hits
+}
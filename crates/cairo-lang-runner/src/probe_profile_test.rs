@@ -0,0 +1,69 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra::instrumentation::instrument_program;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+use itertools::Itertools;
+
+use crate::{RunResultValue, SierraCasmRunner};
+
+#[test]
+fn probe_hits_of_calls() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            #[inline(never)]
+            fn sum(n: felt252, acc: felt252) -> felt252 {
+                if n == 0 {
+                    acc
+                } else {
+                    sum(n - 1, acc + n)
+                }
+            }
+
+            fn main() -> felt252 {
+                sum(3, 0) + sum(2, 1)
+            }
+        "},
+    )
+    .unwrap();
+    let crate_input = test_module.crate_id.long(&db).clone().into_crate_input(&db);
+    DiagnosticsReporter::stderr().with_crates(&[crate_input]).ensure(&db).unwrap();
+    let SierraProgramWithDebug { program: sierra_program, .. } =
+        db.get_sierra_program(vec![test_module.crate_id]).unwrap();
+    let sierra_program = replace_sierra_ids_in_program(&db, sierra_program);
+    let instrumented = instrument_program(&sierra_program);
+    let runner = SierraCasmRunner::new(
+        instrumented.program.clone(),
+        Some(Default::default()),
+        OrderedHashMap::default(),
+        None,
+    )
+    .unwrap()
+    .with_probes(&instrumented.probes);
+    let func = runner.find_function("::main").unwrap();
+    let result = runner
+        .run_function_with_starknet_context(
+            func,
+            vec![],
+            Some(u32::MAX as usize),
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(result.value, RunResultValue::Success(vec![10.into()]));
+
+    let profile = instrumented.profile(&result.probe_hits.unwrap());
+    let calls = profile
+        .functions
+        .iter()
+        .filter(|(_, function)| function.calls > 0)
+        .map(|(name, function)| (name.rsplit("::").next().unwrap(), function.calls))
+        .sorted()
+        .collect_vec();
+    assert_eq!(calls, [("main", 1), ("sum", 7)]);
+}
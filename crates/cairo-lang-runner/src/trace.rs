@@ -0,0 +1,139 @@
+//! Structured execution traces of runs, attributed to the Cairo code they were compiled from.
+
+use std::io::Write;
+use std::sync::Mutex;
+use std::sync::mpsc::Sender;
+
+use cairo_lang_casm::instructions::instruction_offsets;
+use cairo_lang_runnable_utils::builder::RunnableBuilder;
+use cairo_lang_sierra_generator::debug_info::SourceMap;
+use cairo_vm::vm::trace::trace_entry::RelocatedTraceEntry;
+use serde::{Deserialize, Serialize};
+
+use crate::profiling::user_function_idx_by_sierra_statement_idx;
+
+#[cfg(test)]
+#[path = "trace_test.rs"]
+mod test;
+
+/// A single step of an execution trace.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceStep {
+    /// The pc of the step, as in the relocated trace of the VM.
+    pub pc: usize,
+    /// The ap of the step.
+    pub ap: usize,
+    /// The fp of the step.
+    pub fp: usize,
+    /// The executed instruction. `None` for instructions of the entry code of the run, which is
+    /// not a part of the compiled program.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction: Option<String>,
+    /// The index of the Sierra statement the instruction was compiled from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statement_idx: Option<usize>,
+    /// The name of the Sierra function the instruction is a part of.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// The innermost Cairo location the instruction was compiled from, if a source map was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<TraceLocation>,
+}
+
+/// A position in a Cairo file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceLocation {
+    /// The full path of the file.
+    pub file: String,
+    /// The line, 1 based.
+    pub line: usize,
+    /// The column, 1 based.
+    pub col: usize,
+}
+
+/// A destination of the steps of execution traces.
+pub trait TraceSink: Send {
+    /// Receives the next step of the trace.
+    fn step(&mut self, step: &TraceStep) -> std::io::Result<()>;
+}
+
+/// Sends the steps over a channel, e.g. to be consumed while the runner is still in use.
+impl TraceSink for Sender<TraceStep> {
+    fn step(&mut self, step: &TraceStep) -> std::io::Result<()> {
+        self.send(step.clone()).map_err(|_| std::io::ErrorKind::BrokenPipe.into())
+    }
+}
+
+/// A sink writing each step as a line of JSON.
+pub struct JsonLinesTraceSink<W: Write + Send>(pub W);
+
+impl<W: Write + Send> TraceSink for JsonLinesTraceSink<W> {
+    fn step(&mut self, step: &TraceStep) -> std::io::Result<()> {
+        serde_json::to_writer(&mut self.0, step)?;
+        writeln!(self.0)
+    }
+}
+
+/// Records the execution traces of the runs of a runner into a sink.
+pub struct ExecutionTracer {
+    /// The sink receiving the steps.
+    sink: Mutex<Box<dyn TraceSink>>,
+    /// The source map of the CASM program, for the Cairo locations of the steps. Its pcs are
+    /// relative to the start of the CASM program.
+    source_map: Option<SourceMap>,
+}
+
+impl ExecutionTracer {
+    /// Creates a tracer streaming into `sink`.
+    pub fn new(sink: impl TraceSink + 'static, source_map: Option<SourceMap>) -> Self {
+        Self { sink: Mutex::new(Box::new(sink)), source_map }
+    }
+
+    /// Streams the steps of a run into the sink.
+    pub fn record(
+        &self,
+        builder: &RunnableBuilder,
+        // The offset in memory where builder.casm_program() was loaded.
+        load_offset: usize,
+        trace: &[RelocatedTraceEntry],
+    ) -> std::io::Result<()> {
+        let casm_program = builder.casm_program();
+        let sierra_program = builder.sierra_program();
+        let sierra_statement_info = &casm_program.debug_info.sierra_statement_info;
+        let bytecode_len = sierra_statement_info.last().map_or(0, |info| info.end_offset);
+
+        let mut sink = self.sink.lock().unwrap();
+        for entry in trace {
+            let mut step =
+                TraceStep { pc: entry.pc, ap: entry.ap, fp: entry.fp, ..Default::default() };
+            if let Some(pc) = entry.pc.checked_sub(load_offset).filter(|pc| *pc < bytecode_len) {
+                let statement_idx = casm_program.sierra_statement_index_by_pc(pc);
+                let info = &sierra_statement_info[statement_idx.0];
+                // Finding the instruction starting at the pc, from the first one of the statement.
+                let end_idx = sierra_statement_info
+                    .get(statement_idx.0 + 1)
+                    .map_or(casm_program.instructions.len(), |next| next.instruction_idx);
+                let instructions = &casm_program.instructions[info.instruction_idx..end_idx];
+                step.instruction = instruction_offsets(instructions)
+                    .into_iter()
+                    .zip(instructions)
+                    .find(|(offset, _)| info.start_offset + offset == pc)
+                    .map(|(_, instruction)| instruction.to_string());
+                step.statement_idx = Some(statement_idx.0);
+                let function_idx =
+                    user_function_idx_by_sierra_statement_idx(sierra_program, statement_idx);
+                step.function = Some(sierra_program.funcs[function_idx].id.to_string());
+                step.location = self.source_map.as_ref().and_then(|source_map| {
+                    let (file, span, _) = source_map.locations_at(pc).into_iter().next()?;
+                    Some(TraceLocation {
+                        file: file.to_string(),
+                        line: span.start.line + 1,
+                        col: span.start.col + 1,
+                    })
+                });
+            }
+            sink.step(&step)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::mpsc;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::debug_info::SourceMap;
+use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use indoc::indoc;
+
+use super::{JsonLinesTraceSink, TraceLocation, TraceSink, TraceStep};
+use crate::{RunResultValue, SierraCasmRunner};
+
+#[test]
+fn trace_with_source_attribution() {
+    let db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(
+        &db,
+        indoc! {"
+            #[inline(never)]
+            fn fail(a: felt252) {
+                if a == 3 {
+                    panic!(\"oops\");
+                }
+            }
+
+            fn main() {
+                fail(2);
+                fail(3);
+            }
+        "},
+    )
+    .unwrap();
+    let crate_input = test_module.crate_id.long(&db).clone().into_crate_input(&db);
+    DiagnosticsReporter::stderr().with_crates(&[crate_input]).ensure(&db).unwrap();
+    let SierraProgramWithDebug { program: sierra_program, debug_info } =
+        db.get_sierra_program(vec![test_module.crate_id]).unwrap();
+    let sierra_program = replace_sierra_ids_in_program(&db, sierra_program);
+    let runner =
+        SierraCasmRunner::new(sierra_program, None, OrderedHashMap::default(), None).unwrap();
+    let source_map = SourceMap::new(
+        runner
+            .casm_program()
+            .debug_info
+            .sierra_statement_info
+            .iter()
+            .map(|info| (info.start_offset, info.end_offset)),
+        &debug_info.statements_locations.extract_statements_source_code_locations(&db),
+        0,
+    );
+    let (sender, receiver) = mpsc::channel();
+    let runner = runner.with_trace(sender, Some(source_map));
+    let func = runner.find_function("::main").unwrap();
+    let result =
+        runner.run_function_with_starknet_context(func, vec![], None, Default::default()).unwrap();
+    assert!(matches!(result.value, RunResultValue::Panic(_)));
+
+    let steps: Vec<TraceStep> = receiver.try_iter().collect();
+    // The run starts and ends in the entry code.
+    for step in [steps.first().unwrap(), steps.last().unwrap()] {
+        assert_eq!((&step.instruction, &step.function, &step.location), (&None, &None, &None));
+    }
+    let first_program_step = steps.iter().find(|step| step.instruction.is_some()).unwrap();
+    assert_eq!(first_program_step.function.as_deref(), Some("test::main"));
+    assert_eq!(first_program_step.instruction.as_deref(), Some("[ap + 0] = 2, ap++"));
+    assert_eq!(
+        first_program_step.location,
+        Some(TraceLocation { file: "lib.cairo".into(), line: 9, col: 5 })
+    );
+    // `fail` is called twice.
+    let calls = steps.iter().filter(|step| step.instruction.as_deref() == Some("ret"));
+    assert_eq!(calls.filter(|step| step.function.as_deref() == Some("test::fail")).count(), 2);
+    // The steps of the panic are attributed to the `panic!` in `fail`.
+    let panic_step = steps
+        .iter()
+        .rfind(|step| {
+            step.location.as_ref().is_some_and(|location| location.line == 4)
+                && step.function.as_deref() == Some("test::fail")
+        })
+        .unwrap();
+    assert_eq!(
+        panic_step.location,
+        Some(TraceLocation { file: "lib.cairo".into(), line: 4, col: 9 })
+    );
+
+    // Steps are written as JSON lines.
+    let mut sink = JsonLinesTraceSink(vec![]);
+    sink.step(panic_step).unwrap();
+    let line = String::from_utf8(sink.0).unwrap();
+    assert_eq!(serde_json::from_str::<TraceStep>(line.trim_end()).unwrap(), *panic_step);
+}
@@ -336,12 +336,14 @@ enum VisibilityCached {
     Public,
     PublicInCrate,
     Private,
+    PublicInSuper,
 }
 impl VisibilityCached {
     fn new(visibility: Visibility) -> Self {
         match visibility {
             Visibility::Public => VisibilityCached::Public,
             Visibility::PublicInCrate => VisibilityCached::PublicInCrate,
+            Visibility::PublicInSuper => VisibilityCached::PublicInSuper,
             Visibility::Private => VisibilityCached::Private,
         }
     }
@@ -349,6 +351,7 @@ impl VisibilityCached {
         match self {
             VisibilityCached::Public => Visibility::Public,
             VisibilityCached::PublicInCrate => Visibility::PublicInCrate,
+            VisibilityCached::PublicInSuper => Visibility::PublicInSuper,
             VisibilityCached::Private => Visibility::Private,
         }
     }
@@ -436,7 +439,7 @@ impl ModuleItemInfoCached {
 pub enum FeatureKindCached {
     Stable,
     Unstable { feature: SmolStr, note: Option<SmolStr> },
-    Deprecated { feature: SmolStr, note: Option<SmolStr> },
+    Deprecated { feature: Option<SmolStr>, note: Option<SmolStr> },
     Internal { feature: SmolStr, note: Option<SmolStr> },
 }
 
@@ -449,7 +452,7 @@ impl FeatureKindCached {
                 note: note.map(|note| note.long(ctx.db).clone()),
             },
             FeatureKind::Deprecated { feature, note } => FeatureKindCached::Deprecated {
-                feature: feature.long(ctx.db).clone(),
+                feature: feature.map(|feature| feature.long(ctx.db).clone()),
                 note: note.map(|note| note.long(ctx.db).clone()),
             },
             FeatureKind::Internal { feature, note } => FeatureKindCached::Internal {
@@ -466,7 +469,7 @@ impl FeatureKindCached {
                 note: note.map(|note| note.intern(ctx.db)),
             },
             FeatureKindCached::Deprecated { feature, note } => FeatureKind::Deprecated {
-                feature: feature.intern(ctx.db),
+                feature: feature.map(|feature| feature.intern(ctx.db)),
                 note: note.map(|note| note.intern(ctx.db)),
             },
             FeatureKindCached::Internal { feature, note } => FeatureKind::Internal {
@@ -898,6 +898,32 @@ pub fn try_extract_box_inner_type<'db>(
     (extern_type_id.name(db).long(db) == "Box").then_some(inner)
 }
 
+/// Returns the range of values of a bounded integer type - a fixed-size integer type or a
+/// `BoundedInt` - or `None` for any other type.
+pub fn bounded_integer_type_range<'db>(
+    db: &'db dyn Database,
+    ty: TypeId<'db>,
+) -> Option<(BigInt, BigInt)> {
+    let info = db.core_info();
+    let ranges: [(TypeId<'db>, BigInt, BigInt); 10] = [
+        (info.u8, u8::MIN.into(), u8::MAX.into()),
+        (info.u16, u16::MIN.into(), u16::MAX.into()),
+        (info.u32, u32::MIN.into(), u32::MAX.into()),
+        (info.u64, u64::MIN.into(), u64::MAX.into()),
+        (info.u128, u128::MIN.into(), u128::MAX.into()),
+        (info.i8, i8::MIN.into(), i8::MAX.into()),
+        (info.i16, i16::MIN.into(), i16::MAX.into()),
+        (info.i32, i32::MIN.into(), i32::MAX.into()),
+        (info.i64, i64::MIN.into(), i64::MAX.into()),
+        (info.i128, i128::MIN.into(), i128::MAX.into()),
+    ];
+    ranges
+        .into_iter()
+        .find(|(range_ty, _, _)| *range_ty == ty)
+        .map(|(_, min, max)| (min, max))
+        .or_else(|| try_extract_bounded_int_type_ranges(db, ty))
+}
+
 /// Returns the ranges of a BoundedInt if it is a BoundedInt type.
 pub fn try_extract_bounded_int_type_ranges<'db>(
     db: &'db dyn Database,
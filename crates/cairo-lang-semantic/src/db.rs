@@ -3,13 +3,17 @@ use std::sync::Arc;
 use cairo_lang_defs::db::{DefsGroup, DefsGroupEx, defs_group_input};
 use cairo_lang_defs::ids::{
     ImplAliasId, InlineMacroExprPluginId, InlineMacroExprPluginLongId, LanguageElementId,
-    LookupItemId, MacroPluginId, MacroPluginLongId, ModuleId, ModuleItemId, UseId,
+    LookupItemId, MacroPluginId, MacroPluginLongId, ModuleId, ModuleItemId, NamedLanguageElementId,
+    UseId,
 };
 use cairo_lang_diagnostics::{Diagnostics, DiagnosticsBuilder, Maybe};
 use cairo_lang_filesystem::db::FilesGroup;
 use cairo_lang_filesystem::ids::{CrateId, CrateInput, FileId, FileLongId, SmolStrId, Tracked};
-use cairo_lang_syntax::attribute::consts::{DEPRECATED_ATTR, UNUSED_IMPORTS, UNUSED_VARIABLES};
-use cairo_lang_syntax::node::{TypedStablePtr, ast};
+use cairo_lang_filesystem::span::TextWidth;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_syntax::attribute::consts::{FALLBACK_ATTR, UNREACHABLE_PUB, UNUSED_IMPORTS};
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode, ast};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
@@ -25,6 +29,7 @@ use crate::items::constant::ConstantSemantic;
 use crate::items::enm::EnumSemantic;
 use crate::items::extern_function::ExternFunctionSemantic;
 use crate::items::extern_type::ExternTypeSemantic;
+use crate::items::feature_kind::feature_config_from_item_and_parent_modules;
 use crate::items::free_function::FreeFunctionSemantic;
 use crate::items::imp::{ImplId, ImplSemantic};
 use crate::items::impl_alias::ImplAliasSemantic;
@@ -36,6 +41,7 @@ use crate::items::structure::StructSemantic;
 use crate::items::trt::TraitSemantic;
 use crate::items::us::{SemanticUseEx, UseSemantic};
 use crate::items::visibility::Visibility;
+use crate::lint::BUILTIN_LINTS;
 use crate::plugin::{AnalyzerPlugin, InternedPluginSuite, PluginSuite};
 use crate::resolve::{ResolvedConcreteItem, ResolvedGenericItem, ResolverData};
 
@@ -200,6 +206,16 @@ fn module_semantic_diagnostics<'db>(
     module_id: ModuleId<'db>,
 ) -> Maybe<Diagnostics<'db, SemanticDiagnostic<'db>>> {
     let mut diagnostics = SemanticDiagnostics::new(module_id);
+    if let ModuleId::CrateRoot(crate_id) = module_id
+        && let Some(cycle) = db.crate_dependency_cycle(crate_id)
+    {
+        let file_syntax = db.file_module_syntax(db.module_main_file(module_id)?)?;
+        diagnostics.report_with_inner_span(
+            file_syntax.stable_ptr(db),
+            (TextWidth::default(), TextWidth::default()),
+            SemanticDiagnosticKind::CrateDependencyCycle(cycle.to_vec()),
+        );
+    }
     for (_module_id, plugin_diag) in
         module_id.module_data(db)?.plugin_diagnostics(db).iter().cloned()
     {
@@ -298,6 +314,8 @@ fn module_semantic_diagnostics<'db>(
         }
     }
     add_unused_item_diagnostics(db, module_id, data, &mut diagnostics);
+    add_unreachable_pub_item_diagnostics(db, module_id, data, &mut diagnostics);
+    add_misplaced_fallback_attr_diagnostics(db, data, &mut diagnostics);
     add_duplicated_names_from_macro_expansions_diagnostics(db, module_id, &mut diagnostics);
     for analyzer_plugin_id in db.crate_analyzer_plugins(module_id.owning_crate(db)).iter() {
         let analyzer_plugin = analyzer_plugin_id.long(db);
@@ -349,7 +367,7 @@ fn crate_analyzer_plugins<'db>(
 
 #[salsa::tracked]
 fn declared_allows(db: &dyn Database, crate_id: CrateId<'_>) -> Arc<OrderedHashSet<String>> {
-    let base_lints = [DEPRECATED_ATTR, UNUSED_IMPORTS, UNUSED_VARIABLES];
+    let base_lints = BUILTIN_LINTS.map(|lint| lint.name);
 
     let crate_analyzer_plugins = db.crate_analyzer_plugins(crate_id);
 
@@ -381,6 +399,97 @@ fn add_unused_item_diagnostics<'db>(
     }
 }
 
+/// Adds diagnostics for `#[fallback]` attributes on items of a module which are not impls,
+/// including the items of its impls and traits.
+fn add_misplaced_fallback_attr_diagnostics<'db>(
+    db: &'db dyn Database,
+    data: &ModuleSemanticData<'db>,
+    diagnostics: &mut SemanticDiagnostics<'db>,
+) {
+    for info in data.items.values() {
+        let inner_items = match info.item_id {
+            ModuleItemId::Impl(impl_def_id) => match db.module_impl_by_id(impl_def_id) {
+                Ok(impl_ast) => match impl_ast.body(db) {
+                    ast::MaybeImplBody::Some(body) => {
+                        body.items(db).elements(db).map(|item| item.as_syntax_node()).collect()
+                    }
+                    ast::MaybeImplBody::None(_) => vec![],
+                },
+                Err(_) => vec![],
+            },
+            ModuleItemId::Trait(trait_id) => match db.module_trait_by_id(trait_id) {
+                Ok(trait_ast) => match trait_ast.body(db) {
+                    ast::MaybeTraitBody::Some(body) => {
+                        body.items(db).elements(db).map(|item| item.as_syntax_node()).collect()
+                    }
+                    ast::MaybeTraitBody::None(_) => vec![],
+                },
+                Err(_) => vec![],
+            },
+            _ => vec![],
+        };
+        let item_syntax = info.item_id.stable_location(db).syntax_node(db);
+        let misplaced_items = chain!(
+            (!matches!(info.item_id, ModuleItemId::Impl(_))).then_some(item_syntax),
+            inner_items
+        );
+        for syntax in misplaced_items {
+            if let Some(attr) = syntax.find_attr(db, FALLBACK_ATTR) {
+                diagnostics
+                    .report(attr.stable_ptr(db), SemanticDiagnosticKind::FallbackAttrOnNonImpl);
+            }
+        }
+    }
+}
+
+/// Adds diagnostics for `pub` items of a module which are not reachable from outside the crate.
+fn add_unreachable_pub_item_diagnostics<'db>(
+    db: &'db dyn Database,
+    module_id: ModuleId<'db>,
+    data: &'db ModuleSemanticData<'db>,
+    diagnostics: &mut SemanticDiagnostics<'db>,
+) {
+    let ignore_visibility = db
+        .crate_config(module_id.owning_crate(db))
+        .is_some_and(|config| config.settings.edition.ignore_visibility());
+    if ignore_visibility || is_module_publicly_reachable(db, module_id) {
+        return;
+    }
+    for info in data.items.values() {
+        if info.visibility != Visibility::Public {
+            continue;
+        }
+        let item_id = &info.item_id;
+        let syntax = item_id.stable_location(db).syntax_node(db);
+        // The diagnostics of the attributes are reported with the item.
+        let ignored = &mut SemanticDiagnostics::new(module_id);
+        let feature_config =
+            feature_config_from_item_and_parent_modules(db, item_id, &syntax, ignored);
+        diagnostics.report_lint(
+            item_id.name_identifier(db).stable_ptr(db),
+            feature_config.lint_level(db, UNREACHABLE_PUB),
+            SemanticDiagnosticKind::UnreachablePubItem,
+        );
+    }
+}
+
+/// Returns whether the `pub` items of a module are reachable from outside the crate - i.e. whether
+/// the module and all its parent modules are `pub`. Re-exports by `pub use` are not considered.
+fn is_module_publicly_reachable<'db>(db: &'db dyn Database, module_id: ModuleId<'db>) -> bool {
+    match module_id {
+        ModuleId::CrateRoot(_) => true,
+        ModuleId::Submodule(submodule_id) => {
+            let parent = submodule_id.parent_module(db);
+            db.module_item_info_by_name(parent, submodule_id.name(db))
+                .ok()
+                .flatten()
+                .is_some_and(|info| info.visibility == Visibility::Public)
+                && is_module_publicly_reachable(db, parent)
+        }
+        ModuleId::MacroCall { id, .. } => is_module_publicly_reachable(db, id.parent_module(db)),
+    }
+}
+
 /// Adds diagnostics for unused imports.
 fn add_unused_import_diagnostics<'db>(
     db: &'db dyn Database,
@@ -388,29 +497,22 @@ fn add_unused_import_diagnostics<'db>(
     use_id: UseId<'db>,
     diagnostics: &mut SemanticDiagnostics<'db>,
 ) {
-    let _iife =
-        (|| {
-            let item = db.use_resolved_item(use_id).ok()?;
-            // TODO(orizi): Properly handle usages of impls, and then add warnings on their usages
-            // as well.
-            require(!matches!(
-                item,
-                ResolvedGenericItem::Impl(_) | ResolvedGenericItem::GenericImplAlias(_)
-            ))?;
-            require(!all_used_uses.contains(&use_id))?;
-            let resolver_data = db.use_resolver_data(use_id).ok()?;
-
-            require(
-                !resolver_data
-                    .feature_config
-                    .allowed_lints
-                    .contains(&SmolStrId::from(db, UNUSED_IMPORTS)),
-            )?;
-            Some(diagnostics.report(
-                use_id.untyped_stable_ptr(db),
-                SemanticDiagnosticKind::UnusedImport(use_id),
-            ))
-        })();
+    let _iife = (|| {
+        let item = db.use_resolved_item(use_id).ok()?;
+        // TODO(orizi): Properly handle usages of impls, and then add warnings on their usages
+        // as well.
+        require(!matches!(
+            item,
+            ResolvedGenericItem::Impl(_) | ResolvedGenericItem::GenericImplAlias(_)
+        ))?;
+        require(!all_used_uses.contains(&use_id))?;
+        let resolver_data = db.use_resolver_data(use_id).ok()?;
+        diagnostics.report_lint(
+            use_id.untyped_stable_ptr(db),
+            resolver_data.feature_config.lint_level(db, UNUSED_IMPORTS),
+            SemanticDiagnosticKind::UnusedImport(use_id),
+        )
+    })();
 }
 
 #[salsa::tracked]
@@ -604,6 +706,47 @@ pub trait PluginSuiteInput: Database {
         self.set_override_crate_inline_macro_plugins(crate_id, inline_macro_plugins);
         self.set_override_crate_analyzer_plugins(crate_id, analyzer_plugins);
     }
+
+    /// Sets macro, inline macro and analyzer plugins present in the [`PluginSuite`] for the crate
+    /// given by the [`CrateInput`], overriding the defaults for that crate.
+    /// Unlike [`Self::set_override_crate_plugins_from_suite`], does not require the crate and the
+    /// plugins to be interned first.
+    ///
+    /// *Note*: Sets the same Salsa inputs as [`Self::set_override_crate_plugins_from_suite`].
+    fn set_override_crate_input_plugins_from_suite(
+        &mut self,
+        crate_input: CrateInput,
+        suite: PluginSuite,
+    ) {
+        let PluginSuite { plugins, inline_macro_plugins, analyzer_plugins } = suite;
+
+        let mut macro_overrides = self.macro_plugin_overrides_input().clone();
+        macro_overrides
+            .insert(crate_input.clone(), plugins.into_iter().map(MacroPluginLongId).collect());
+        let mut inline_macro_overrides = self.inline_macro_plugin_overrides_input().clone();
+        inline_macro_overrides.insert(
+            crate_input.clone(),
+            Arc::new(
+                inline_macro_plugins
+                    .into_iter()
+                    .map(|(name, plugin)| (name, InlineMacroExprPluginLongId(plugin)))
+                    .collect(),
+            ),
+        );
+        let mut analyzer_overrides = self.analyzer_plugin_overrides_input().clone();
+        analyzer_overrides
+            .insert(crate_input, analyzer_plugins.into_iter().map(AnalyzerPluginLongId).collect());
+
+        defs_group_input(self.as_dyn_database())
+            .set_macro_plugin_overrides(self)
+            .to(Some(macro_overrides));
+        defs_group_input(self.as_dyn_database())
+            .set_inline_macro_plugin_overrides(self)
+            .to(Some(inline_macro_overrides));
+        semantic_group_input(self.as_dyn_database())
+            .set_analyzer_plugin_overrides(self)
+            .to(Some(analyzer_overrides));
+    }
 }
 
 impl<T: Database + ?Sized> PluginSuiteInput for T {}
@@ -10,28 +10,31 @@ use cairo_lang_defs::ids::{
 };
 use cairo_lang_defs::plugin::PluginDiagnostic;
 use cairo_lang_diagnostics::{
-    DiagnosticAdded, DiagnosticEntry, DiagnosticNote, Diagnostics, DiagnosticsBuilder, ErrorCode,
-    Severity, error_code,
+    Applicability, DiagnosticAdded, DiagnosticEntry, DiagnosticNote, DiagnosticSuggestion,
+    Diagnostics, DiagnosticsBuilder, ErrorCode, Severity, error_code, find_similar_name,
 };
-use cairo_lang_filesystem::db::Edition;
-use cairo_lang_filesystem::ids::{SmolStrId, SpanInFile};
-use cairo_lang_filesystem::span::TextWidth;
+use cairo_lang_filesystem::db::{Edition, FilesGroup, LintLevel};
+use cairo_lang_filesystem::ids::{CrateId, SmolStrId, SpanInFile};
+use cairo_lang_filesystem::span::{TextOffset, TextSpan, TextWidth};
 use cairo_lang_parser::ParserDiagnostic;
 use cairo_lang_syntax as syntax;
-use cairo_lang_syntax::node::ast;
 use cairo_lang_syntax::node::helpers::GetIdentifier;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{Terminal, TypedSyntaxNode, ast};
 use itertools::Itertools;
 use salsa::Database;
 use syntax::node::ids::SyntaxStablePtrId;
 
 use crate::corelib::LiteralError;
 use crate::expr::inference::InferenceError;
+use crate::items::cycle::{CycleItemId, cycle_notes};
 use crate::items::feature_kind::FeatureMarkerDiagnostic;
 use crate::items::trt::ConcreteTraitTypeId;
+use crate::lint::lint_level_attr;
 use crate::path::ContextualizePath;
 use crate::resolve::{ResolvedConcreteItem, ResolvedGenericItem};
 use crate::types::peel_snapshots;
-use crate::{ConcreteTraitId, semantic};
+use crate::{ConcreteTraitId, TypeLongId, semantic};
 
 #[cfg(test)]
 #[path = "diagnostic_test.rs"]
@@ -86,6 +89,22 @@ pub trait SemanticDiagnosticsBuilder<'db> {
         stable_ptr: impl Into<SyntaxStablePtrId<'db>>,
         kind: SemanticDiagnosticKind<'db>,
     ) -> DiagnosticAdded;
+    /// Report a diagnostic of a lint in the location of the given ptr, according to the level of
+    /// the lint - skipped if allowed, and reported as an error if denied.
+    fn report_lint(
+        &mut self,
+        stable_ptr: impl Into<SyntaxStablePtrId<'db>>,
+        level: LintLevel,
+        kind: SemanticDiagnosticKind<'db>,
+    ) -> Option<DiagnosticAdded> {
+        match level {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(self.report(stable_ptr, kind)),
+            LintLevel::Deny => {
+                Some(self.report(stable_ptr, SemanticDiagnosticKind::DeniedLint(Box::new(kind))))
+            }
+        }
+    }
     /// Report a diagnostic in the location after the given ptr (with width 0).
     fn report_after(
         &mut self,
@@ -223,16 +242,16 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             SemanticDiagnosticKind::MemberSpecifiedMoreThanOnce => {
                 "Member specified more than once.".into()
             }
-            SemanticDiagnosticKind::ConstCycle => {
+            SemanticDiagnosticKind::ConstCycle(_) => {
                 "Cycle detected while resolving 'const' items.".into()
             }
-            SemanticDiagnosticKind::UseCycle => {
+            SemanticDiagnosticKind::UseCycle(_) => {
                 "Cycle detected while resolving 'use' items.".into()
             }
-            SemanticDiagnosticKind::TypeAliasCycle => {
+            SemanticDiagnosticKind::TypeAliasCycle(_) => {
                 "Cycle detected while resolving type-alias/impl-type items.".into()
             }
-            SemanticDiagnosticKind::ImplAliasCycle => {
+            SemanticDiagnosticKind::ImplAliasCycle(_) => {
                 "Cycle detected while resolving 'impls alias' items.".into()
             }
             SemanticDiagnosticKind::ImplRequirementCycle => {
@@ -579,13 +598,18 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
                 )
             }
             SemanticDiagnosticKind::DeprecatedFeature { feature_name, note } => {
-                format!(
-                    "Usage of deprecated feature `{0}` with no `#[feature({0})]` attribute.{1}",
-                    feature_name.long(db),
-                    note.as_ref()
-                        .map(|note| format!(" Note: {}", note.long(db)))
-                        .unwrap_or_default()
-                )
+                let note = note
+                    .as_ref()
+                    .map(|note| format!(" Note: {}", note.long(db)))
+                    .unwrap_or_default();
+                match feature_name {
+                    Some(feature_name) => format!(
+                        "Usage of deprecated feature `{0}` with no `#[feature({0})]` attribute.{1}",
+                        feature_name.long(db),
+                        note
+                    ),
+                    None => format!("Usage of deprecated item.{note}"),
+                }
             }
             SemanticDiagnosticKind::InternalFeature { feature_name, note } => {
                 format!(
@@ -726,6 +750,13 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             SemanticDiagnosticKind::UnusedImport(use_id) => {
                 format!("Unused import: `{}`", use_id.full_path(db))
             }
+            SemanticDiagnosticKind::UnreachablePubItem => "Unreachable `pub` item: a module \
+                                                           containing it is not `pub`. Consider \
+                                                           using `pub(crate)`."
+                .into(),
+            SemanticDiagnosticKind::DeniedLint(kind) => {
+                SemanticDiagnostic { kind: (**kind).clone(), ..self.clone() }.format(db)
+            }
             SemanticDiagnosticKind::UnexpectedEnumPattern(ty) => {
                 format!(r#"Unexpected type for enum pattern. "{}" is not an enum."#, ty.format(db),)
             }
@@ -863,6 +894,12 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             }
             SemanticDiagnosticKind::InnerFailedConstantCalculation(inner, _) => inner.format(db),
             SemanticDiagnosticKind::DivisionByZero => "Division by zero.".into(),
+            SemanticDiagnosticKind::ConstantCalculationFuelExhausted => {
+                "Constant calculation exceeded the evaluation step limit.".into()
+            }
+            SemanticDiagnosticKind::ConstantIndexOutOfBounds { index, size } => {
+                format!("Index out of bounds: the size is {size} but the index is {index}.")
+            }
             SemanticDiagnosticKind::ExternTypeWithImplGenericsNotSupported => {
                 "Extern types with impl generics are not supported.".into()
             }
@@ -874,7 +911,8 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
                     actual_trt.debug(db),
                 )
             }
-            SemanticDiagnosticKind::InternalInferenceError(err) => err.format(db),
+            SemanticDiagnosticKind::InternalInferenceError(err)
+            | SemanticDiagnosticKind::TracedInferenceError(err, _) => err.format(db),
             SemanticDiagnosticKind::DerefNonRef { ty } => {
                 format!("Type `{}` cannot be dereferenced", ty.format(db))
             }
@@ -984,9 +1022,9 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             SemanticDiagnosticKind::UnsupportedFeatureAttrArguments => {
                 "`feature` attribute argument should be a single string.".into()
             }
-            SemanticDiagnosticKind::UnsupportedAllowAttrArguments => {
+            SemanticDiagnosticKind::UnsupportedLintAttrArguments(level) => {
                 // TODO(orizi): Add information about the allowed arguments.
-                "`allow` attribute argument not supported.".into()
+                format!("`{}` attribute argument not supported.", lint_level_attr(*level))
             }
             SemanticDiagnosticKind::UnsupportedPubArgument => "Unsupported `pub` argument.".into(),
             SemanticDiagnosticKind::UnknownStatementAttribute => {
@@ -1038,6 +1076,14 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
                  by enabling the `repr_ptrs` experimental feature in the crate config."
                     .into()
             }
+            SemanticDiagnosticKind::FallbackImplsDisabled => {
+                "Fallback impls are disabled in the current crate.\nYou can enable them by \
+                 enabling the `fallback_impls` experimental feature in the crate config."
+                    .into()
+            }
+            SemanticDiagnosticKind::FallbackAttrOnNonImpl => {
+                "`fallback` attribute is only allowed on impls.".into()
+            }
             SemanticDiagnosticKind::AssignmentToReprPtrVariable { .. } => {
                 "Cannot assign to a variable with a taken pointer".into()
             }
@@ -1176,6 +1222,9 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             SemanticDiagnosticKind::TypeConstraintsSyntaxNotEnabled => {
                 "Type constraints syntax is not enabled in the current crate.".into()
             }
+            SemanticDiagnosticKind::EmptyRangePattern => {
+                "Range pattern is empty: its start must be lower than its end.".into()
+            }
             SemanticDiagnosticKind::PatternMissingArgs(path) => {
                 format!(
                     "Pattern missing subpattern for the payload of variant. Consider using `{}(_)`",
@@ -1199,6 +1248,12 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             SemanticDiagnosticKind::OnlyTypeOrConstParamsInNegImpl => {
                 "Negative impls may only use type or const generic parameters.".into()
             }
+            SemanticDiagnosticKind::CrateDependencyCycle(cycle) => {
+                format!(
+                    "Cyclic dependency between crates: {}.",
+                    cycle.iter().map(|crate_id| crate_id.long(db).name().long(db)).join(" -> ")
+                )
+            }
         }
     }
     fn location(&self, db: &'db dyn Database) -> SpanInFile<'db> {
@@ -1228,20 +1283,26 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             | SemanticDiagnosticKind::UnstableFeature { .. }
             | SemanticDiagnosticKind::DeprecatedFeature { .. }
             | SemanticDiagnosticKind::UnusedImport { .. }
+            | SemanticDiagnosticKind::UnreachablePubItem
             | SemanticDiagnosticKind::CallingShadowedFunction { .. }
             | SemanticDiagnosticKind::UnusedConstant
             | SemanticDiagnosticKind::UnusedUse
             | SemanticDiagnosticKind::PatternMissingArgs(_)
-            | SemanticDiagnosticKind::UnsupportedAllowAttrArguments => Severity::Warning,
+            | SemanticDiagnosticKind::UnsupportedLintAttrArguments(_) => Severity::Warning,
             SemanticDiagnosticKind::PluginDiagnostic(diag) => diag.severity,
             _ => Severity::Error,
         }
     }
 
-    fn notes(&self, _db: &dyn Database) -> &[DiagnosticNote<'_>] {
+    fn notes(&self, db: &'db dyn Database) -> &[DiagnosticNote<'_>] {
         match &self.kind {
             SemanticDiagnosticKind::InnerFailedConstantCalculation(_, notes) => notes,
             SemanticDiagnosticKind::AssignmentToReprPtrVariable(notes) => notes,
+            SemanticDiagnosticKind::TracedInferenceError(_, notes) => notes,
+            SemanticDiagnosticKind::ConstCycle(Some(item))
+            | SemanticDiagnosticKind::UseCycle(Some(item))
+            | SemanticDiagnosticKind::TypeAliasCycle(Some(item))
+            | SemanticDiagnosticKind::ImplAliasCycle(Some(item)) => cycle_notes(db, *item),
             _ => &[],
         }
     }
@@ -1279,10 +1340,10 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             SemanticDiagnosticKind::MemberSpecifiedMoreThanOnce => error_code!(E2021),
             SemanticDiagnosticKind::StructBaseStructExpressionNotLast => error_code!(E2022),
             SemanticDiagnosticKind::StructBaseStructExpressionNoEffect => error_code!(E2023),
-            SemanticDiagnosticKind::ConstCycle => error_code!(E2024),
-            SemanticDiagnosticKind::UseCycle => error_code!(E2025),
-            SemanticDiagnosticKind::TypeAliasCycle => error_code!(E2026),
-            SemanticDiagnosticKind::ImplAliasCycle => error_code!(E2027),
+            SemanticDiagnosticKind::ConstCycle(_) => error_code!(E2024),
+            SemanticDiagnosticKind::UseCycle(_) => error_code!(E2025),
+            SemanticDiagnosticKind::TypeAliasCycle(_) => error_code!(E2026),
+            SemanticDiagnosticKind::ImplAliasCycle(_) => error_code!(E2027),
             SemanticDiagnosticKind::ImplRequirementCycle => error_code!(E2028),
             SemanticDiagnosticKind::WrongNumberOfParameters { .. } => error_code!(E2029),
             SemanticDiagnosticKind::WrongNumberOfArguments { .. } => error_code!(E2030),
@@ -1362,6 +1423,10 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             SemanticDiagnosticKind::SuperUsedInMacroCallTopLevel => error_code!(E2098),
             SemanticDiagnosticKind::ItemNotVisible(..) => error_code!(E2099),
             SemanticDiagnosticKind::UnusedImport(_) => error_code!(E2100),
+            SemanticDiagnosticKind::UnreachablePubItem => error_code!(E2315),
+            SemanticDiagnosticKind::DeniedLint(kind) => {
+                return SemanticDiagnostic { kind: (**kind).clone(), ..self.clone() }.error_code();
+            }
             SemanticDiagnosticKind::RedundantModifier { .. } => error_code!(E2101),
             SemanticDiagnosticKind::ReferenceLocalVariable => error_code!(E2102),
             SemanticDiagnosticKind::UnexpectedEnumPattern(_) => error_code!(E2103),
@@ -1395,6 +1460,8 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             SemanticDiagnosticKind::ConstantCalculationDepthExceeded => error_code!(E2129),
             SemanticDiagnosticKind::InnerFailedConstantCalculation(..) => error_code!(E2130),
             SemanticDiagnosticKind::DivisionByZero => error_code!(E2131),
+            SemanticDiagnosticKind::ConstantCalculationFuelExhausted => error_code!(E2316),
+            SemanticDiagnosticKind::ConstantIndexOutOfBounds { .. } => error_code!(E2317),
             SemanticDiagnosticKind::ExternTypeWithImplGenericsNotSupported => error_code!(E2132),
             SemanticDiagnosticKind::MissingSemicolon => error_code!(E2133),
             SemanticDiagnosticKind::TraitMismatch { .. } => error_code!(E2134),
@@ -1418,7 +1485,7 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             SemanticDiagnosticKind::RedundantImplicitPrecedenceAttribute => error_code!(E2150),
             SemanticDiagnosticKind::UnsupportedImplicitPrecedenceArguments => error_code!(E2151),
             SemanticDiagnosticKind::UnsupportedFeatureAttrArguments => error_code!(E2152),
-            SemanticDiagnosticKind::UnsupportedAllowAttrArguments => error_code!(E2153),
+            SemanticDiagnosticKind::UnsupportedLintAttrArguments(_) => error_code!(E2153),
             SemanticDiagnosticKind::UnsupportedPubArgument => error_code!(E2154),
             SemanticDiagnosticKind::UnknownStatementAttribute => error_code!(E2155),
             SemanticDiagnosticKind::InlineMacroNotFound(_) => error_code!(E2156),
@@ -1458,14 +1525,19 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
             SemanticDiagnosticKind::DuplicateTypeConstraint { .. } => error_code!(E2190),
             SemanticDiagnosticKind::TypeConstraintsSyntaxNotEnabled => error_code!(E2191),
             SemanticDiagnosticKind::PatternMissingArgs(_) => error_code!(E2192),
+            SemanticDiagnosticKind::EmptyRangePattern => error_code!(E2318),
+            SemanticDiagnosticKind::FallbackImplsDisabled => error_code!(E2319),
+            SemanticDiagnosticKind::FallbackAttrOnNonImpl => error_code!(E2320),
             SemanticDiagnosticKind::UndefinedMacroPlaceholder(_) => error_code!(E2193),
             SemanticDiagnosticKind::UserDefinedInlineMacrosDisabled => error_code!(E2194),
             SemanticDiagnosticKind::NonNeverLetElseType => error_code!(E2195),
             SemanticDiagnosticKind::OnlyTypeOrConstParamsInNegImpl => error_code!(E2196),
+            SemanticDiagnosticKind::CrateDependencyCycle(_) => error_code!(E2197),
             SemanticDiagnosticKind::PluginDiagnostic(diag) => {
                 diag.error_code.unwrap_or(error_code!(E2200))
             }
-            SemanticDiagnosticKind::InternalInferenceError(inference_error) => {
+            SemanticDiagnosticKind::InternalInferenceError(inference_error)
+            | SemanticDiagnosticKind::TracedInferenceError(inference_error, _) => {
                 match inference_error {
                     InferenceError::Reported(_) => error_code!(E2300),
                     InferenceError::Cycle(_) => error_code!(E2301),
@@ -1487,10 +1559,128 @@ impl<'db> DiagnosticEntry<'db> for SemanticDiagnostic<'db> {
         })
     }
 
+    fn suggestions(&self, db: &'db dyn Database) -> Vec<DiagnosticSuggestion<'db>> {
+        match &self.kind {
+            SemanticDiagnosticKind::WrongArgumentType { expected_ty, actual_ty } if matches!(expected_ty.long(db), TypeLongId::Snapshot(inner) if inner == actual_ty) =>
+            {
+                let location = self.location(db);
+                vec![DiagnosticSuggestion::new(
+                    "consider passing a snapshot".into(),
+                    SpanInFile { file_id: location.file_id, span: location.span.start_only() },
+                    "@".into(),
+                    Applicability::MachineApplicable,
+                )]
+            }
+            SemanticDiagnosticKind::CannotCallMethod { relevant_traits, .. } => {
+                let Some((location, indent)) = self.enclosing_module_item_start(db) else {
+                    return vec![];
+                };
+                relevant_traits
+                    .iter()
+                    .map(|trait_path| {
+                        DiagnosticSuggestion::new(
+                            format!("consider importing `{trait_path}`"),
+                            location,
+                            format!("{indent}use {trait_path};\n"),
+                            Applicability::MaybeIncorrect,
+                        )
+                    })
+                    .collect()
+            }
+            SemanticDiagnosticKind::PathNotFound(_) => self.similar_name_suggestion(db),
+            _ => vec![],
+        }
+    }
+
     fn is_same_kind(&self, other: &Self) -> bool {
         other.kind == self.kind
     }
 }
+impl<'db> SemanticDiagnostic<'db> {
+    /// Returns the location of the start of the line of the module item containing the
+    /// diagnostic, along with the indentation of the item.
+    fn enclosing_module_item_start(
+        &self,
+        db: &'db dyn Database,
+    ) -> Option<(SpanInFile<'db>, String)> {
+        let node = self.stable_location.syntax_node(db);
+        let item = node.ancestors_with_self(db).find(|node| {
+            node.parent(db).is_some_and(|parent| parent.kind(db) == SyntaxKind::ModuleItemList)
+        })?;
+        let file_id = self.stable_location.file_id(db);
+        let content = db.file_content(file_id)?;
+        let item_start = item.span_start_without_trivia(db).as_u32() as usize;
+        let line_start = content[..item_start].rfind('\n').map_or(0, |newline| newline + 1);
+        let indent = &content[line_start..item_start];
+        if !indent.chars().all(char::is_whitespace) {
+            return None;
+        }
+        let line_start = TextOffset::START.add_width(TextWidth::at(content, line_start));
+        Some((SpanInFile { file_id, span: TextSpan::cursor(line_start) }, indent.to_string()))
+    }
+
+    /// Returns a suggestion to replace a path segment that was not found with a similarly named
+    /// item of the context module, or a similarly named variable of the enclosing function.
+    fn similar_name_suggestion(&self, db: &'db dyn Database) -> Vec<DiagnosticSuggestion<'db>> {
+        let node = self.stable_location.syntax_node(db);
+        if node.kind(db) != SyntaxKind::TerminalIdentifier {
+            return vec![];
+        }
+        // Only the first segment of a path is resolved in the context module.
+        let segment = node.parent(db);
+        let segments = segment.and_then(|segment| segment.parent(db));
+        if let (Some(segment), Some(segments)) = (segment, segments)
+            && segments.kind(db) == SyntaxKind::ExprPathInner
+            && segments.get_children(db).first() != Some(&segment)
+        {
+            return vec![];
+        }
+        let name = node.get_text_without_trivia(db).long(db).as_str();
+
+        let mut candidates: Vec<SmolStrId<'db>> = vec![];
+        if let Ok(module_data) = self.context_module.module_data(db) {
+            candidates.extend(module_data.items(db).iter().map(|item| item.name(db)));
+        }
+        if let Some(function) = node.ancestors(db).find(|node| {
+            matches!(node.kind(db), SyntaxKind::FunctionWithBody | SyntaxKind::TraitItemFunction)
+        }) {
+            for node in function.descendants(db) {
+                match node.kind(db) {
+                    SyntaxKind::Param => {
+                        candidates.push(ast::Param::from_syntax_node(db, node).name(db).text(db));
+                    }
+                    SyntaxKind::PatternIdentifier => candidates
+                        .push(ast::PatternIdentifier::from_syntax_node(db, node).name(db).text(db)),
+                    // A simple binding in a `let` pattern is parsed as a path.
+                    SyntaxKind::StatementLet => {
+                        let pattern = ast::StatementLet::from_syntax_node(db, node).pattern(db);
+                        candidates.extend(
+                            pattern
+                                .as_syntax_node()
+                                .descendants(db)
+                                .filter(|node| node.kind(db) == SyntaxKind::TerminalIdentifier)
+                                .map(|node| {
+                                    ast::TerminalIdentifier::from_syntax_node(db, node).text(db)
+                                }),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let Some(similar_name) =
+            find_similar_name(name, candidates.iter().map(|candidate| candidate.long(db).as_str()))
+        else {
+            return vec![];
+        };
+        vec![DiagnosticSuggestion::new(
+            format!("a similar name exists: `{similar_name}`"),
+            self.location(db),
+            similar_name.to_string(),
+            Applicability::MaybeIncorrect,
+        )]
+    }
+}
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
 pub enum SemanticDiagnosticKind<'db> {
@@ -1533,10 +1723,10 @@ pub enum SemanticDiagnosticKind<'db> {
     MemberSpecifiedMoreThanOnce,
     StructBaseStructExpressionNotLast,
     StructBaseStructExpressionNoEffect,
-    ConstCycle,
-    UseCycle,
-    TypeAliasCycle,
-    ImplAliasCycle,
+    ConstCycle(Option<CycleItemId<'db>>),
+    UseCycle(Option<CycleItemId<'db>>),
+    TypeAliasCycle(Option<CycleItemId<'db>>),
+    ImplAliasCycle(Option<CycleItemId<'db>>),
     ImplRequirementCycle,
     MissingMember(SmolStrId<'db>),
     WrongNumberOfParameters {
@@ -1682,7 +1872,7 @@ pub enum SemanticDiagnosticKind<'db> {
         note: Option<SmolStrId<'db>>,
     },
     DeprecatedFeature {
-        feature_name: SmolStrId<'db>,
+        feature_name: Option<SmolStrId<'db>>,
         note: Option<SmolStrId<'db>>,
     },
     InternalFeature {
@@ -1724,6 +1914,9 @@ pub enum SemanticDiagnosticKind<'db> {
     SuperUsedInMacroCallTopLevel,
     ItemNotVisible(ModuleItemId<'db>, Vec<ModuleId<'db>>),
     UnusedImport(UseId<'db>),
+    UnreachablePubItem,
+    /// A diagnostic of a lint set to the `deny` level, reported as an error.
+    DeniedLint(Box<SemanticDiagnosticKind<'db>>),
     RedundantModifier {
         current_modifier: SmolStrId<'db>,
         previous_modifier: SmolStrId<'db>,
@@ -1778,6 +1971,11 @@ pub enum SemanticDiagnosticKind<'db> {
     ConstantCalculationDepthExceeded,
     InnerFailedConstantCalculation(Box<SemanticDiagnostic<'db>>, Vec<DiagnosticNote<'db>>),
     DivisionByZero,
+    ConstantCalculationFuelExhausted,
+    ConstantIndexOutOfBounds {
+        index: usize,
+        size: usize,
+    },
     ExternTypeWithImplGenericsNotSupported,
     MissingSemicolon,
     TraitMismatch {
@@ -1788,6 +1986,9 @@ pub enum SemanticDiagnosticKind<'db> {
         ty: semantic::TypeId<'db>,
     },
     InternalInferenceError(InferenceError<'db>),
+    /// An inference error with the trace of the trait solver, when enabled by the
+    /// `trait_solver_trace` flag.
+    TracedInferenceError(InferenceError<'db>, Vec<DiagnosticNote<'db>>),
     NoImplementationOfIndexOperator {
         ty: semantic::TypeId<'db>,
         inference_errors: TraitInferenceErrors<'db>,
@@ -1816,7 +2017,7 @@ pub enum SemanticDiagnosticKind<'db> {
     RedundantImplicitPrecedenceAttribute,
     UnsupportedImplicitPrecedenceArguments,
     UnsupportedFeatureAttrArguments,
-    UnsupportedAllowAttrArguments,
+    UnsupportedLintAttrArguments(LintLevel),
     UnsupportedPubArgument,
     UnknownStatementAttribute,
     InlineMacroNotFound(SmolStrId<'db>),
@@ -1837,6 +2038,10 @@ pub enum SemanticDiagnosticKind<'db> {
     CouponsDisabled,
     /// Representation pointers are disabled in the current crate.
     ReprPtrsDisabled,
+    /// Fallback impls are disabled in the current crate.
+    FallbackImplsDisabled,
+    /// A `#[fallback]` attribute on an item which is not an impl.
+    FallbackAttrOnNonImpl,
     /// Cannot assign to a variable with a taken pointer.
     AssignmentToReprPtrVariable(Vec<DiagnosticNote<'db>>),
     FixedSizeArrayTypeNonSingleType,
@@ -1877,10 +2082,13 @@ pub enum SemanticDiagnosticKind<'db> {
     },
     TypeConstraintsSyntaxNotEnabled,
     PatternMissingArgs(ast::ExprPath<'db>),
+    EmptyRangePattern,
     UndefinedMacroPlaceholder(SmolStrId<'db>),
     UserDefinedInlineMacrosDisabled,
     NonNeverLetElseType,
     OnlyTypeOrConstParamsInNegImpl,
+    /// The crate is a part of the given cycle of crate dependencies, starting and ending with it.
+    CrateDependencyCycle(Vec<CrateId<'db>>),
 }
 
 /// The kind of an expression with multiple possible return types.
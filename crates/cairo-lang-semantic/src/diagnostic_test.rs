@@ -6,6 +6,7 @@ use cairo_lang_defs::patcher::{PatchBuilder, RewriteNode};
 use cairo_lang_defs::plugin::{
     MacroPlugin, MacroPluginMetadata, PluginDiagnostic, PluginGeneratedFile, PluginResult,
 };
+use cairo_lang_filesystem::flag::{Flag, FlagsGroup};
 use cairo_lang_filesystem::ids::SmolStrId;
 use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::{TypedStablePtr, ast};
@@ -29,6 +30,7 @@ cairo_lang_test_utils::test_file_test!(
     {
         allow: "allow",
         allow_attr: "allow_attr",
+        lints: "lints",
         deref: "deref",
         tests: "tests",
         not_found: "not_found",
@@ -314,3 +316,121 @@ fn test_analyzer_diagnostics() {
     "#},
     );
 }
+
+#[cairo_lang_test_utils::test]
+fn test_trait_solver_trace() {
+    let mut db_val = SemanticDatabaseForTesting::new_empty();
+    let db = &mut db_val;
+    db.set_flag(Flag::TraitSolverTrace(true));
+    let crate_id = setup_test_crate(
+        db,
+        indoc! {"
+            trait MyTrait<T> {
+                fn value(self: T) -> felt252;
+            }
+            impl U8MyTrait of MyTrait<u8> {
+                fn value(self: u8) -> felt252 {
+                    1
+                }
+            }
+            impl CopyMyTrait<T, +Copy<T>, +Into<T, felt252>> of MyTrait<T> {
+                fn value(self: T) -> felt252 {
+                    self.into()
+                }
+            }
+            impl DropMyTrait<T, +Drop<T>, +Into<T, felt252>> of MyTrait<T> {
+                fn value(self: T) -> felt252 {
+                    self.into()
+                }
+            }
+            fn ambiguous() -> felt252 {
+                MyTrait::value(1_u16)
+            }
+            fn missing() -> felt252 {
+                MyTrait::value(array![1_u16])
+            }
+       "},
+    );
+
+    assert_eq!(
+        get_crate_semantic_diagnostics(db, crate_id).format(db),
+        indoc! {"
+            error[E2313]: Trait `test::MyTrait::<core::integer::u16>` has multiple implementations, in: `test::CopyMyTrait::<core::integer::u16, core::integer::u16Copy, core::integer::U16IntoFelt252>`, `test::DropMyTrait::<core::integer::u16, core::integer::u16Drop, core::integer::U16IntoFelt252>`
+             --> lib.cairo:20:14
+                MyTrait::value(1_u16)
+                         ^^^^^
+            note: Candidate `test::CopyMyTrait` matches as `test::CopyMyTrait::<core::integer::u16, core::integer::u16Copy, core::integer::U16IntoFelt252>`.
+            note: Candidate `test::DropMyTrait` matches as `test::DropMyTrait::<core::integer::u16, core::integer::u16Drop, core::integer::U16IntoFelt252>`.
+
+            error[E2311]: Trait has no implementation in context: test::MyTrait::<core::array::Array::<core::integer::u16>>.
+             --> lib.cairo:23:14
+                MyTrait::value(array![1_u16])
+                         ^^^^^
+            note: Candidate `test::CopyMyTrait` was rejected: Its requirement `core::traits::Copy::<core::array::Array::<core::integer::u16>>` is not satisfied.
+            note: Candidate `test::DropMyTrait` was rejected: Its requirement `core::traits::Into::<core::array::Array::<core::integer::u16>, core::felt252>` is not satisfied.
+
+        "},
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_suggestions() {
+    let mut db_val = SemanticDatabaseForTesting::new_empty();
+    let db = &mut db_val;
+    let crate_id = setup_test_crate(
+        db,
+        indoc! {"
+            mod helpers {
+                pub trait Double {
+                    fn double(self: u32) -> u32;
+                }
+                pub impl DoubleImpl of Double {
+                    fn double(self: u32) -> u32 {
+                        self * 2
+                    }
+                }
+            }
+            fn by_snapshot(value: @u32) -> u32 {
+                *value
+            }
+            mod inner {
+                fn missing_import(value: u32) -> u32 {
+                    value.double()
+                }
+            }
+            fn misspelled(count: u32) -> u32 {
+                let total = 1;
+                by_snapshot(count) + cont + ttoal
+            }
+       "},
+    );
+
+    let suggestions = get_crate_semantic_diagnostics(db, crate_id)
+        .format_with_severity(db, &Default::default())
+        .iter()
+        .flat_map(|entry| entry.suggestions().to_vec())
+        .map(|suggestion| {
+            format!(
+                "{} ({:?}): {}:{}:{}-{}:{} `{}`",
+                suggestion.message,
+                suggestion.applicability,
+                suggestion.file,
+                suggestion.span.start_line,
+                suggestion.span.start_col,
+                suggestion.span.end_line,
+                suggestion.span.end_col,
+                suggestion.replacement.escape_debug(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        suggestions,
+        [
+            "consider passing a snapshot (MachineApplicable): lib.cairo:20:16-20:16 `@`",
+            "a similar name exists: `count` (MaybeIncorrect): lib.cairo:20:25-20:29 `count`",
+            "a similar name exists: `total` (MaybeIncorrect): lib.cairo:20:32-20:37 `total`",
+            "consider importing `crate::helpers::Double` (MaybeIncorrect): lib.cairo:14:0-14:0 \
+             `    use crate::helpers::Double;\\n`",
+        ]
+    );
+}
@@ -0,0 +1,661 @@
+//! Export of the resolved semantic model of a crate, for consumption by external tools such as
+//! documentation generators and static analyzers.
+//!
+//! The model is built by [export_crate_model] and is serialized (e.g. to JSON) using serde. The
+//! schema is defined by the types in this module, with struct fields serialized by their names:
+//! * A [CrateModel] holds the `format_version` of the schema - see [CRATE_MODEL_FORMAT_VERSION] -
+//!   and all the modules of the crate, including the inline and plugin generated ones, as a flat
+//!   list of [ModuleModel]s.
+//! * Each module holds its [ItemModel]s, tagged by a `kind` field, its [UseModel]s - the edges of
+//!   the use graph - and an [ItemErrorModel] for each item or use with semantic errors.
+//! * Impls hold the resolutions of the items of their trait - the implemented functions, and the
+//!   resolved types, constants and impls.
+//! * Types, traits and impls are given by their fully resolved names, e.g. `core::array::Array::<
+//!   core::felt252>`.
+//! * Enums without data, such as [VisibilityModel], are serialized as snake case strings, e.g.
+//!   `"public_in_crate"`. Optional fields are omitted when missing.
+//!
+//! For example, `pub fn foo(ref a: u8) -> u8` in the crate `test` is exported to JSON as:
+//! ```json
+//! {
+//!   "kind": "free_function",
+//!   "name": "foo",
+//!   "path": "test::foo",
+//!   "visibility": "public",
+//!   "generic_params": [],
+//!   "signature": {
+//!     "params": [{ "name": "a", "ty": "core::integer::u8", "mutability": "reference" }],
+//!     "return_type": "core::integer::u8",
+//!     "implicits": [],
+//!     "panicable": true,
+//!     "is_const": false
+//!   }
+//! }
+//! ```
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{
+    GenericParamId, LanguageElementId, ModuleId, ModuleItemId, NamedLanguageElementId,
+    TopLevelLanguageElementId,
+};
+use cairo_lang_diagnostics::{DiagnosticEntry, Maybe, Severity};
+use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use salsa::Database;
+use serde::{Deserialize, Serialize};
+
+use crate::db::SemanticGroup;
+use crate::items::constant::ConstantSemantic;
+use crate::items::enm::EnumSemantic;
+use crate::items::extern_function::ExternFunctionSemantic;
+use crate::items::extern_type::ExternTypeSemantic;
+use crate::items::free_function::FreeFunctionSemantic;
+use crate::items::generics::GenericParamImpl;
+use crate::items::imp::ImplSemantic;
+use crate::items::impl_alias::ImplAliasSemantic;
+use crate::items::module::ModuleSemantic;
+use crate::items::module_type_alias::ModuleTypeAliasSemantic;
+use crate::items::structure::StructSemantic;
+use crate::items::trt::TraitSemantic;
+use crate::items::us::SemanticUseEx;
+use crate::items::visibility::Visibility;
+use crate::{GenericParam, Mutability, SemanticDiagnostic, Signature};
+
+#[cfg(test)]
+#[path = "export_test.rs"]
+mod test;
+
+/// The version of the schema of [CrateModel].
+/// Bumped on any change that is not backward compatible, e.g. removing or renaming a field. Adding
+/// fields or item kinds does not bump the version.
+pub const CRATE_MODEL_FORMAT_VERSION: u32 = 1;
+
+/// The semantic model of a crate.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrateModel {
+    /// The version of the schema the model was exported with - see [CRATE_MODEL_FORMAT_VERSION].
+    pub format_version: u32,
+    /// The name of the crate.
+    pub name: String,
+    /// All the modules of the crate, starting with the crate root.
+    pub modules: Vec<ModuleModel>,
+}
+
+/// The semantic model of a module.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleModel {
+    /// The full path of the module, e.g. `my_crate::my_module`.
+    pub path: String,
+    /// The items defined in the module, excluding submodules and uses.
+    pub items: Vec<ItemModel>,
+    /// The submodules of the module, by their full paths.
+    pub submodules: Vec<String>,
+    /// The uses of the module.
+    pub uses: Vec<UseModel>,
+    /// The items and uses of the module with semantic errors. Items that could still be exported
+    /// also appear in `items`, with the types that failed to resolve given as `<missing>`.
+    pub errors: Vec<ItemErrorModel>,
+}
+
+/// The visibility of an item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VisibilityModel {
+    /// `pub`.
+    Public,
+    /// `pub(crate)`.
+    PublicInCrate,
+    /// `pub(super)`.
+    PublicInSuper,
+    /// No visibility modifier.
+    Private,
+}
+impl From<Visibility> for VisibilityModel {
+    fn from(visibility: Visibility) -> Self {
+        match visibility {
+            Visibility::Public => Self::Public,
+            Visibility::PublicInCrate => Self::PublicInCrate,
+            Visibility::PublicInSuper => Self::PublicInSuper,
+            Visibility::Private => Self::Private,
+        }
+    }
+}
+
+/// The information common to all module items.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemInfoModel {
+    /// The name of the item.
+    pub name: String,
+    /// The full path of the item, e.g. `my_crate::my_module::MyStruct`.
+    pub path: String,
+    /// The visibility of the item.
+    pub visibility: VisibilityModel,
+}
+
+/// A module item with semantic errors.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemErrorModel {
+    #[serde(flatten)]
+    pub info: ItemInfoModel,
+    /// The messages of the errors reported within the item.
+    pub diagnostics: Vec<String>,
+}
+
+/// A module item, tagged by its `kind`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ItemModel {
+    /// `const NAME: ty = ...;`.
+    Constant {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+        /// The type of the constant.
+        ty: String,
+    },
+    /// `fn`.
+    FreeFunction {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+        generic_params: Vec<GenericParamModel>,
+        signature: SignatureModel,
+    },
+    /// `extern fn`.
+    ExternFunction {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+        generic_params: Vec<GenericParamModel>,
+        signature: SignatureModel,
+    },
+    /// `struct`.
+    Struct {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+        generic_params: Vec<GenericParamModel>,
+        members: Vec<MemberModel>,
+    },
+    /// `enum`.
+    Enum {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+        generic_params: Vec<GenericParamModel>,
+        variants: Vec<VariantModel>,
+    },
+    /// `extern type`.
+    ExternType {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+        generic_params: Vec<GenericParamModel>,
+    },
+    /// `type Alias = ...;`.
+    TypeAlias {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+        generic_params: Vec<GenericParamModel>,
+        /// The aliased type.
+        ty: String,
+    },
+    /// `trait`.
+    Trait {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+        generic_params: Vec<GenericParamModel>,
+        functions: Vec<FunctionModel>,
+        /// The names of the associated types of the trait.
+        types: Vec<String>,
+        constants: Vec<TraitConstantModel>,
+    },
+    /// `impl ... of Trait`.
+    Impl {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+        generic_params: Vec<GenericParamModel>,
+        /// The concrete trait implemented by the impl, e.g. `core::traits::Into::<u8, u16>`.
+        concrete_trait: String,
+        functions: Vec<ImplFunctionModel>,
+        types: Vec<ImplTypeModel>,
+        constants: Vec<ImplConstantModel>,
+        impls: Vec<ImplImplModel>,
+    },
+    /// `impl Alias = ...;`.
+    ImplAlias {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+        generic_params: Vec<GenericParamModel>,
+        /// The aliased impl.
+        impl_path: String,
+    },
+    /// `macro`.
+    MacroDeclaration {
+        #[serde(flatten)]
+        info: ItemInfoModel,
+    },
+}
+
+/// The kind of a generic parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenericParamKind {
+    Type,
+    Const,
+    Impl,
+    NegativeImpl,
+}
+
+/// A generic parameter.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenericParamModel {
+    /// The name of the parameter - `_` for anonymous impl parameters.
+    pub name: String,
+    pub kind: GenericParamKind,
+    /// The type of a const parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ty: Option<String>,
+    /// The concrete trait of an impl parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub concrete_trait: Option<String>,
+}
+
+/// The signature of a function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureModel {
+    pub params: Vec<ParamModel>,
+    pub return_type: String,
+    /// The implicit parameters of the function, e.g. `core::RangeCheck`.
+    pub implicits: Vec<String>,
+    /// Whether the function may panic - i.e. is not `nopanic`.
+    pub panicable: bool,
+    /// Whether the function is a `const fn`.
+    pub is_const: bool,
+}
+
+/// The mutability of a function parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MutabilityModel {
+    Immutable,
+    Mutable,
+    /// A `ref` parameter.
+    Reference,
+}
+
+/// A function parameter.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamModel {
+    pub name: String,
+    pub ty: String,
+    pub mutability: MutabilityModel,
+}
+
+/// A struct member.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberModel {
+    pub name: String,
+    pub ty: String,
+    pub visibility: VisibilityModel,
+}
+
+/// An enum variant.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariantModel {
+    pub name: String,
+    pub ty: String,
+}
+
+/// A trait function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionModel {
+    pub name: String,
+    pub generic_params: Vec<GenericParamModel>,
+    pub signature: SignatureModel,
+}
+
+/// A trait constant.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraitConstantModel {
+    pub name: String,
+    pub ty: String,
+}
+
+/// An impl function, with the trait function it implements.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImplFunctionModel {
+    pub name: String,
+    /// The full path of the implemented trait function, e.g. `core::traits::Into::into`.
+    pub trait_function: String,
+    pub generic_params: Vec<GenericParamModel>,
+    pub signature: SignatureModel,
+}
+
+/// An impl type, resolving the trait type of the same name.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImplTypeModel {
+    pub name: String,
+    /// The resolved type.
+    pub ty: String,
+}
+
+/// An impl constant, resolving the trait constant of the same name.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImplConstantModel {
+    pub name: String,
+    /// The resolved value of the constant.
+    pub value: String,
+}
+
+/// An impl impl, resolving the trait impl of the same name.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImplImplModel {
+    pub name: String,
+    /// The resolved impl.
+    pub impl_path: String,
+}
+
+/// A `use` of an item - an edge of the use graph.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UseModel {
+    /// The name the item is used as.
+    pub name: String,
+    pub visibility: VisibilityModel,
+    /// The full path of the used item.
+    pub target: String,
+}
+
+/// Exports the semantic model of the given crate.
+///
+/// Items with semantic errors are reported in the [ModuleModel::errors] of their module, and the
+/// rest of the crate is still exported. Fails only if a module of the crate could not be resolved,
+/// e.g. if its file is missing.
+pub fn export_crate_model<'db>(db: &'db dyn Database, crate_id: CrateId<'db>) -> Maybe<CrateModel> {
+    Ok(CrateModel {
+        format_version: CRATE_MODEL_FORMAT_VERSION,
+        name: crate_id.long(db).name().to_string(db),
+        modules: db
+            .crate_modules(crate_id)
+            .iter()
+            .map(|module_id| export_module(db, *module_id))
+            .collect::<Maybe<_>>()?,
+    })
+}
+
+/// Exports the semantic model of a single module.
+fn export_module<'db>(db: &'db dyn Database, module_id: ModuleId<'db>) -> Maybe<ModuleModel> {
+    let mut items = vec![];
+    let mut submodules = vec![];
+    let mut uses = vec![];
+    let mut errors = vec![];
+    let module_diagnostics = db
+        .module_semantic_diagnostics(module_id)
+        .map(|diagnostics| diagnostics.get_all())
+        .unwrap_or_default();
+    for (name, item_info) in db.priv_module_semantic_data(module_id)?.items.iter() {
+        let info = ItemInfoModel {
+            name: name.to_string(db),
+            path: item_info.item_id.full_path(db),
+            visibility: item_info.visibility.into(),
+        };
+        let exported = match item_info.item_id {
+            ModuleItemId::Submodule(id) => {
+                submodules.push(ModuleId::Submodule(id).full_path(db));
+                continue;
+            }
+            ModuleItemId::Use(id) => db.use_resolved_item(id).map(|item| {
+                uses.push(UseModel {
+                    name: info.name.clone(),
+                    visibility: info.visibility,
+                    target: item.full_path(db),
+                })
+            }),
+            item_id => export_item(db, info.clone(), item_id).map(|item| items.push(item)),
+        };
+        let diagnostics = item_error_messages(db, &module_diagnostics, item_info.item_id);
+        if exported.is_err() || !diagnostics.is_empty() {
+            errors.push(ItemErrorModel { info, diagnostics });
+        }
+    }
+    Ok(ModuleModel { path: module_id.full_path(db), items, submodules, uses, errors })
+}
+
+/// Exports a module item which is neither a submodule nor a use.
+fn export_item<'db>(
+    db: &'db dyn Database,
+    info: ItemInfoModel,
+    item_id: ModuleItemId<'db>,
+) -> Maybe<ItemModel> {
+    Ok(match item_id {
+        ModuleItemId::Submodule(_) | ModuleItemId::Use(_) => {
+            unreachable!("Submodules and uses are not exported as items.")
+        }
+        ModuleItemId::Constant(id) => {
+            ItemModel::Constant { info, ty: db.constant_const_value(id)?.ty(db)?.format(db) }
+        }
+        ModuleItemId::FreeFunction(id) => ItemModel::FreeFunction {
+            info,
+            generic_params: export_generic_params(db, db.free_function_generic_params(id)?),
+            signature: export_signature(db, db.free_function_signature(id)?),
+        },
+        ModuleItemId::ExternFunction(id) => ItemModel::ExternFunction {
+            info,
+            generic_params: export_generic_params(
+                db,
+                db.extern_function_declaration_generic_params(id)?,
+            ),
+            signature: export_signature(db, db.extern_function_signature(id)?),
+        },
+        ModuleItemId::Struct(id) => ItemModel::Struct {
+            info,
+            generic_params: export_generic_params(db, db.struct_generic_params(id)?),
+            members: db
+                .struct_members(id)?
+                .iter()
+                .map(|(name, member)| MemberModel {
+                    name: name.to_string(db),
+                    ty: member.ty.format(db),
+                    visibility: member.visibility.into(),
+                })
+                .collect(),
+        },
+        ModuleItemId::Enum(id) => ItemModel::Enum {
+            info,
+            generic_params: export_generic_params(db, db.enum_generic_params(id)?),
+            variants: db
+                .enum_variants(id)?
+                .iter()
+                .map(|(name, variant_id)| {
+                    Ok(VariantModel {
+                        name: name.to_string(db),
+                        ty: db.variant_semantic(id, *variant_id)?.ty.format(db),
+                    })
+                })
+                .collect::<Maybe<_>>()?,
+        },
+        ModuleItemId::ExternType(id) => ItemModel::ExternType {
+            info,
+            generic_params: export_generic_params(
+                db,
+                db.extern_type_declaration_generic_params(id)?,
+            ),
+        },
+        ModuleItemId::TypeAlias(id) => ItemModel::TypeAlias {
+            info,
+            generic_params: export_generic_params(db, &db.module_type_alias_generic_params(id)?),
+            ty: db.module_type_alias_resolved_type(id)?.format(db),
+        },
+        ModuleItemId::Trait(id) => ItemModel::Trait {
+            info,
+            generic_params: export_generic_params(db, db.trait_generic_params(id)?),
+            functions: db
+                .trait_functions(id)?
+                .iter()
+                .map(|(name, function_id)| {
+                    Ok(FunctionModel {
+                        name: name.to_string(db),
+                        generic_params: export_generic_params(
+                            db,
+                            db.trait_function_generic_params(*function_id)?,
+                        ),
+                        signature: export_signature(db, db.trait_function_signature(*function_id)?),
+                    })
+                })
+                .collect::<Maybe<_>>()?,
+            types: db.trait_types(id)?.keys().map(|name| name.to_string(db)).collect(),
+            constants: db
+                .trait_constants(id)?
+                .iter()
+                .map(|(name, constant_id)| {
+                    Ok(TraitConstantModel {
+                        name: name.to_string(db),
+                        ty: db.trait_constant_type(*constant_id)?.format(db),
+                    })
+                })
+                .collect::<Maybe<_>>()?,
+        },
+        ModuleItemId::Impl(id) => ItemModel::Impl {
+            info,
+            generic_params: export_generic_params(db, db.impl_def_generic_params(id)?),
+            concrete_trait: db.impl_def_concrete_trait(id)?.full_path(db),
+            functions: db
+                .impl_functions(id)?
+                .iter()
+                .map(|(name, function_id)| {
+                    Ok(ImplFunctionModel {
+                        name: name.to_string(db),
+                        trait_function: db
+                            .impl_function_trait_function(*function_id)?
+                            .full_path(db),
+                        generic_params: export_generic_params(
+                            db,
+                            db.impl_function_generic_params(*function_id)?,
+                        ),
+                        signature: export_signature(db, db.impl_function_signature(*function_id)?),
+                    })
+                })
+                .collect::<Maybe<_>>()?,
+            types: db
+                .impl_types(id)?
+                .keys()
+                .map(|type_id| {
+                    Ok(ImplTypeModel {
+                        name: type_id.name(db).to_string(db),
+                        ty: db.impl_type_def_resolved_type(*type_id)?.format(db),
+                    })
+                })
+                .collect::<Maybe<_>>()?,
+            constants: db
+                .impl_constants(id)?
+                .keys()
+                .map(|constant_id| {
+                    Ok(ImplConstantModel {
+                        name: constant_id.name(db).to_string(db),
+                        value: db.impl_constant_def_value(*constant_id)?.format(db),
+                    })
+                })
+                .collect::<Maybe<_>>()?,
+            impls: db
+                .impl_impls(id)?
+                .keys()
+                .map(|impl_impl_id| {
+                    Ok(ImplImplModel {
+                        name: impl_impl_id.name(db).to_string(db),
+                        impl_path: db.impl_impl_def_impl(*impl_impl_id, false)?.format(db),
+                    })
+                })
+                .collect::<Maybe<_>>()?,
+        },
+        ModuleItemId::ImplAlias(id) => ItemModel::ImplAlias {
+            info,
+            generic_params: export_generic_params(db, &db.impl_alias_generic_params(id)?),
+            impl_path: db.impl_alias_resolved_impl(id)?.format(db),
+        },
+        ModuleItemId::MacroDeclaration(_) => ItemModel::MacroDeclaration { info },
+    })
+}
+
+/// Returns the messages of the error diagnostics reported within the given item, including its
+/// body.
+fn item_error_messages<'db>(
+    db: &'db dyn Database,
+    module_diagnostics: &[SemanticDiagnostic<'db>],
+    item_id: ModuleItemId<'db>,
+) -> Vec<String> {
+    let mut item_node = item_id.stable_location(db).syntax_node(db);
+    // Uses are located at their leaves, while their errors may be on any segment of their paths.
+    if let ModuleItemId::Use(_) = item_id
+        && let Some(item_use) = item_node.ancestor_of_kind(db, SyntaxKind::ItemUse)
+    {
+        item_node = item_use;
+    }
+    let item_file_id = item_node.stable_ptr(db).file_id(db);
+    let item_span = item_node.span_without_trivia(db);
+    module_diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            let location = diagnostic.location(db);
+            diagnostic.severity() == Severity::Error
+                && location.file_id == item_file_id
+                && item_span.contains(location.span)
+        })
+        .map(|diagnostic| diagnostic.format(db))
+        .collect()
+}
+
+/// Exports a function signature.
+fn export_signature<'db>(db: &'db dyn Database, signature: &Signature<'db>) -> SignatureModel {
+    SignatureModel {
+        params: signature
+            .params
+            .iter()
+            .map(|param| ParamModel {
+                name: param.name.to_string(db),
+                ty: param.ty.format(db),
+                mutability: match param.mutability {
+                    Mutability::Immutable => MutabilityModel::Immutable,
+                    Mutability::Mutable => MutabilityModel::Mutable,
+                    Mutability::Reference => MutabilityModel::Reference,
+                },
+            })
+            .collect(),
+        return_type: signature.return_type.format(db),
+        implicits: signature.implicits.iter().map(|ty| ty.format(db)).collect(),
+        panicable: signature.panicable,
+        is_const: signature.is_const,
+    }
+}
+
+/// Exports generic parameters.
+fn export_generic_params<'db>(
+    db: &'db dyn Database,
+    generic_params: &[GenericParam<'db>],
+) -> Vec<GenericParamModel> {
+    let name = |id: GenericParamId<'db>| {
+        id.name(db).map(|name| name.to_string(db)).unwrap_or_else(|| "_".into())
+    };
+    let impl_param = |param: &GenericParamImpl<'db>, kind| GenericParamModel {
+        name: name(param.id),
+        kind,
+        ty: None,
+        concrete_trait: param
+            .concrete_trait
+            .as_ref()
+            .ok()
+            .map(|concrete_trait| concrete_trait.full_path(db)),
+    };
+    generic_params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Type(param) => GenericParamModel {
+                name: name(param.id),
+                kind: GenericParamKind::Type,
+                ty: None,
+                concrete_trait: None,
+            },
+            GenericParam::Const(param) => GenericParamModel {
+                name: name(param.id),
+                kind: GenericParamKind::Const,
+                ty: Some(param.ty.format(db)),
+                concrete_trait: None,
+            },
+            GenericParam::Impl(param) => impl_param(param, GenericParamKind::Impl),
+            GenericParam::NegImpl(param) => impl_param(param, GenericParamKind::NegativeImpl),
+        })
+        .collect()
+}
@@ -0,0 +1,27 @@
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use super::export_crate_model;
+use crate::test_utils::{SemanticDatabaseForTesting, setup_test_crate};
+
+cairo_lang_test_utils::test_file_test!(
+    export,
+    "src/export_test_data",
+    {
+        export: "export",
+    },
+    test_export_crate_model
+);
+
+fn test_export_crate_model(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = SemanticDatabaseForTesting::default();
+    let crate_id = setup_test_crate(&db, &inputs["crate_code"]);
+    let crate_model = match export_crate_model(&db, crate_id) {
+        Ok(model) => serde_json::to_string_pretty(&model).unwrap(),
+        Err(_) => "<Failed exporting the crate model>".into(),
+    };
+    TestRunnerResult::success(OrderedHashMap::from([("crate_model".into(), crate_model)]))
+}
@@ -18,14 +18,14 @@ use cairo_lang_defs::ids::{
 use cairo_lang_defs::plugin::{InlineMacroExprPlugin, MacroPluginMetadata};
 use cairo_lang_diagnostics::{DiagnosticNote, Maybe, skip_diagnostic};
 use cairo_lang_filesystem::cfg::CfgSet;
-use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::db::{FilesGroup, LintLevel};
 use cairo_lang_filesystem::ids::{
     CodeMapping, CodeOrigin, FileKind, FileLongId, SmolStrId, VirtualFile,
 };
 use cairo_lang_filesystem::span::TextOffset;
 use cairo_lang_parser::db::ParserGroup;
 use cairo_lang_proc_macros::DebugWithDb;
-use cairo_lang_syntax::attribute::consts::UNUSED_VARIABLES;
+use cairo_lang_syntax::attribute::consts::{UNUSED_MUST_USE, UNUSED_VARIABLES};
 use cairo_lang_syntax::node::ast::{
     BinaryOperator, BlockOrIf, ConditionListAnd, ExprPtr, OptionReturnTypeClause, PatternListOr,
     PatternStructParam, TerminalIdentifier, UnaryOperator,
@@ -52,7 +52,7 @@ use super::inference::{Inference, InferenceData, InferenceError};
 use super::objects::*;
 use super::pattern::{
     Pattern, PatternEnumVariant, PatternFixedSizeArray, PatternLiteral, PatternMissing,
-    PatternOtherwise, PatternTuple, PatternVariable, PatternWrappingInfo,
+    PatternOtherwise, PatternRange, PatternTuple, PatternVariable, PatternWrappingInfo,
 };
 use crate::corelib::{
     self, CorelibSemantic, core_binary_operator, core_bool_ty, core_unary_operator,
@@ -223,8 +223,8 @@ pub enum ContextFunction<'db> {
 /// Context inside loops or closures.
 #[derive(Debug, Clone)]
 struct InnerContext<'db> {
-    /// The return type in the current context.
-    return_type: TypeId<'db>,
+    /// The return type in the current context, if inside a function or a closure.
+    return_type: Option<TypeId<'db>>,
     /// The kind of inner context.
     kind: InnerContextKind<'db>,
 }
@@ -427,7 +427,7 @@ impl<'ctx, 'mt> ComputationContext<'ctx, 'mt> {
     /// Returns the return type in the current context if available.
     fn get_return_type(&mut self) -> Option<TypeId<'ctx>> {
         if let Some(inner_ctx) = &self.inner_ctx {
-            return Some(inner_ctx.return_type);
+            return inner_ctx.return_type;
         }
 
         if let Some(signature) = self.signature {
@@ -455,7 +455,7 @@ impl<'ctx, 'mt> ComputationContext<'ctx, 'mt> {
     }
 
     /// Applies inference rewriter to all the rewritable things in the computation context.
-    fn apply_inference_rewriter(&mut self) {
+    pub fn apply_inference_rewriter(&mut self) {
         self.apply_inference_rewriter_to_exprs();
         for (_id, pattern) in &mut self.arenas.patterns {
             self.resolver.inference().internal_rewrite(pattern).no_err();
@@ -590,21 +590,14 @@ fn add_unused_binding_warning<'db>(
                 }
             },
             Binding::LocalVar(local_var) => {
-                if !local_var.allow_unused
-                    && !ctx_feature_config
-                        .allowed_lints
-                        .contains(&SmolStrId::from(db, UNUSED_VARIABLES))
-                {
-                    diagnostics.report(binding.stable_ptr(db), UnusedVariable);
+                if !local_var.allow_unused {
+                    let level = ctx_feature_config.lint_level(db, UNUSED_VARIABLES);
+                    diagnostics.report_lint(binding.stable_ptr(db), level, UnusedVariable);
                 }
             }
             Binding::Param(_) => {
-                if !ctx_feature_config
-                    .allowed_lints
-                    .contains(&SmolStrId::from(db, UNUSED_VARIABLES))
-                {
-                    diagnostics.report(binding.stable_ptr(db), UnusedVariable);
-                }
+                let level = ctx_feature_config.lint_level(db, UNUSED_VARIABLES);
+                diagnostics.report_lint(binding.stable_ptr(db), level, UnusedVariable);
             }
         }
     }
@@ -829,6 +822,7 @@ fn expand_inline_macro<'db>(
             syntax,
             &MacroPluginMetadata {
                 cfg_set: &ctx.cfg_set,
+                expected_cfg_names: ctx.resolver.settings.expected_cfg_names.as_ref(),
                 declared_derives: ctx.db.declared_derives(crate_id),
                 allowed_features: &ctx.resolver.data.feature_config.allowed_features,
                 edition: ctx.resolver.settings.edition,
@@ -1883,11 +1877,19 @@ fn compute_arm_semantic<'db>(
     expr: &Expr<'db>,
     arm_expr_syntax: ast::Expr<'db>,
     patterns_syntax: &PatternListOr<'db>,
-) -> (Vec<PatternAndId<'db>>, ExprAndId<'db>) {
+    guard_syntax: ast::OptionMatchArmGuard<'db>,
+) -> (Vec<PatternAndId<'db>>, Option<ExprAndId<'db>>, ExprAndId<'db>) {
     ctx.run_in_subscope(|new_ctx| {
         let patterns = compute_pattern_list_or_semantic(new_ctx, expr, patterns_syntax);
+        // The guard is computed after the patterns, so it may use the variables they bind.
+        let guard = match guard_syntax {
+            ast::OptionMatchArmGuard::Empty(_) => None,
+            ast::OptionMatchArmGuard::MatchArmGuard(guard) => {
+                Some(compute_bool_condition_semantic(new_ctx, &guard.condition(new_ctx.db)))
+            }
+        };
         let arm_expr = compute_expr_semantic(new_ctx, &arm_expr_syntax);
-        (patterns, arm_expr)
+        (patterns, guard, arm_expr)
     })
 }
 
@@ -1983,12 +1985,18 @@ fn compute_expr_match_semantic<'db>(
     // diagnostics as possible.
     let patterns_and_exprs: Vec<_> = syntax_arms
         .map(|syntax_arm| {
-            compute_arm_semantic(ctx, &expr, syntax_arm.expression(db), &syntax_arm.patterns(db))
+            compute_arm_semantic(
+                ctx,
+                &expr,
+                syntax_arm.expression(db),
+                &syntax_arm.patterns(db),
+                syntax_arm.guard(db),
+            )
         })
         .collect();
     // Unify arm types.
     let mut helper = FlowMergeTypeHelper::new(ctx.db, MultiArmExprKind::Match);
-    for (_, expr) in &patterns_and_exprs {
+    for (_, _, expr) in &patterns_and_exprs {
         let expr_ty = ctx.reduce_ty(expr.ty());
         if !helper.try_merge_types(
             ctx.db,
@@ -2003,8 +2011,9 @@ fn compute_expr_match_semantic<'db>(
     // Compute semantic representation of the match arms.
     let semantic_arms = patterns_and_exprs
         .into_iter()
-        .map(|(patterns, arm_expr)| MatchArm {
+        .map(|(patterns, guard, arm_expr)| MatchArm {
             patterns: patterns.iter().map(|pattern| pattern.id).collect(),
+            guard: guard.map(|guard| guard.id),
             expression: arm_expr.id,
         })
         .collect();
@@ -2329,7 +2338,7 @@ fn compute_loop_body_semantic<'db>(
 ) -> (ExprId, InnerContext<'db>) {
     let db: &dyn Database = ctx.db;
     ctx.run_in_subscope(|new_ctx| {
-        let return_type = new_ctx.get_return_type().unwrap();
+        let return_type = new_ctx.get_return_type();
         let old_inner_ctx = new_ctx.inner_ctx.replace(InnerContext { return_type, kind });
         let (statements, tail) = statements_and_tail(ctx.db, syntax.statements(db));
         let mut statements_semantic = vec![];
@@ -2402,9 +2411,10 @@ fn compute_expr_closure_semantic<'db>(
             }
         };
 
-        let old_inner_ctx = new_ctx
-            .inner_ctx
-            .replace(InnerContext { return_type, kind: InnerContextKind::Closure });
+        let old_inner_ctx = new_ctx.inner_ctx.replace(InnerContext {
+            return_type: Some(return_type),
+            kind: InnerContextKind::Closure,
+        });
         let body = match syntax.expr(db) {
             ast::Expr::Block(syntax) => compute_closure_body_semantic(new_ctx, syntax),
             _ => compute_expr_semantic(new_ctx, &syntax.expr(db)).id,
@@ -2795,6 +2805,37 @@ fn maybe_compute_pattern_semantic<'db>(
                 stable_ptr: literal_pattern.stable_ptr(db).into(),
             })
         }
+        ast::Pattern::Range(range_pattern) => {
+            let end_syntax = range_pattern.end(db);
+            if end_syntax.numeric_value_and_suffix(db).is_none() {
+                // A missing end was already reported by the parser.
+                return Err(skip_diagnostic());
+            }
+            let start = literal_to_semantic(ctx, &range_pattern.start(db))?;
+            let end = literal_to_semantic(ctx, &end_syntax)?;
+            let inference = &mut ctx.resolver.inference();
+            inference.conform_ty(end.ty, start.ty).map_err(|err_set| {
+                inference.report_on_pending_error(
+                    err_set,
+                    ctx.diagnostics,
+                    end_syntax.stable_ptr(db).untyped(),
+                )
+            })?;
+            let inclusive = matches!(range_pattern.op(db), ast::PatternRangeOperator::DotDotEq(_));
+            let is_empty =
+                if inclusive { start.value > end.value } else { start.value >= end.value };
+            if is_empty {
+                return Err(ctx
+                    .diagnostics
+                    .report(range_pattern.stable_ptr(db), EmptyRangePattern));
+            }
+            Pattern::Range(PatternRange {
+                start,
+                end,
+                inclusive,
+                stable_ptr: range_pattern.stable_ptr(db).into(),
+            })
+        }
         ast::Pattern::ShortString(short_string_pattern) => {
             let literal = short_string_to_semantic(ctx, short_string_pattern)?;
             Pattern::Literal(PatternLiteral {
@@ -3379,12 +3420,8 @@ fn create_variable_pattern<'db>(
             false
         }
     };
-    let allow_unused = ctx
-        .resolver
-        .data
-        .feature_config
-        .allowed_lints
-        .contains(&SmolStrId::from(db, UNUSED_VARIABLES));
+    let allow_unused =
+        ctx.resolver.data.feature_config.lint_level(db, UNUSED_VARIABLES) == LintLevel::Allow;
     Pattern::Variable(PatternVariable {
         name: identifier.text(db),
         var: LocalVariable { id: var_id, ty, is_mut, allow_unused },
@@ -3541,6 +3578,35 @@ fn struct_ctor_expr<'db>(
             .diagnostics
             .report(base_struct_syntax.stable_ptr(db), StructBaseStructExpressionNoEffect));
     }
+    // Missing members of a variable base struct are taken by member access, so only these members
+    // are moved out of it, and the rest of it may still be used.
+    if let Some((base_struct_id, _)) = &base_struct
+        && let Some(base_path) = ctx.arenas.exprs[*base_struct_id].as_member_path()
+    {
+        let stable_ptr = base_path.stable_ptr();
+        for (_, member) in members.iter() {
+            let Entry::Vacant(entry) = member_exprs.entry(member.id) else {
+                continue;
+            };
+            let member_path = ExprVarMemberPath::Member {
+                parent: Box::new(base_path.clone()),
+                member_id: member.id,
+                stable_ptr,
+                concrete_struct_id,
+                ty: member.ty,
+            };
+            let member_expr = Expr::MemberAccess(ExprMemberAccess {
+                expr: *base_struct_id,
+                concrete_struct_id,
+                member: member.id,
+                ty: member.ty,
+                member_path: Some(member_path),
+                n_snapshots: 0,
+                stable_ptr,
+            });
+            entry.insert(Some(ctx.arenas.exprs.alloc(member_expr)));
+        }
+    }
     Ok(Expr::StructCtor(ExprStructCtor {
         concrete_struct_id,
         members: member_exprs.into_iter().filter_map(|(x, y)| Some((y?, x))).collect(),
@@ -4551,17 +4617,26 @@ pub fn compute_and_append_statement_semantic<'db>(
                     ctx.diagnostics.report_after(expr_syntax.stable_ptr(db), MissingSemicolon);
                 }
                 let ty: TypeId<'_> = expr.ty();
+                let must_use_level =
+                    ctx.resolver.data.feature_config.lint_level(db, UNUSED_MUST_USE);
                 if let TypeLongId::Concrete(concrete) = ty.long(db)
                     && concrete.is_must_use(db)?
                 {
-                    ctx.diagnostics.report(expr_syntax.stable_ptr(db), UnhandledMustUseType(ty));
+                    ctx.diagnostics.report_lint(
+                        expr_syntax.stable_ptr(db),
+                        must_use_level,
+                        UnhandledMustUseType(ty),
+                    );
                 }
                 if let Expr::FunctionCall(expr_function_call) = &expr.expr {
                     let generic_function_id =
                         expr_function_call.function.long(db).function.generic_function;
                     if generic_function_id.is_must_use(db)? {
-                        ctx.diagnostics
-                            .report(expr_syntax.stable_ptr(db), UnhandledMustUseFunction);
+                        ctx.diagnostics.report_lint(
+                            expr_syntax.stable_ptr(db),
+                            must_use_level,
+                            UnhandledMustUseFunction,
+                        );
                     }
                 }
                 statements.push(ctx.arenas.statements.alloc(semantic::Statement::Expr(
@@ -4592,17 +4667,14 @@ pub fn compute_and_append_statement_semantic<'db>(
                     (Some(expr.id), expr.ty(), expr_syntax.stable_ptr(db).untyped())
                 }
             };
-            let expected_ty = match &ctx.inner_ctx {
-                None => ctx.get_return_type().ok_or_else(|| {
-                    ctx.diagnostics.report(
-                        return_syntax.stable_ptr(db),
-                        UnsupportedOutsideOfFunction(
-                            UnsupportedOutsideOfFunctionFeatureName::ReturnStatement,
-                        ),
-                    )
-                })?,
-                Some(ctx) => ctx.return_type,
-            };
+            let expected_ty = ctx.get_return_type().ok_or_else(|| {
+                ctx.diagnostics.report(
+                    return_syntax.stable_ptr(db),
+                    UnsupportedOutsideOfFunction(
+                        UnsupportedOutsideOfFunctionFeatureName::ReturnStatement,
+                    ),
+                )
+            })?;
 
             let expected_ty = ctx.reduce_ty(expected_ty);
             let expr_ty = ctx.reduce_ty(expr_ty);
@@ -12,7 +12,8 @@ use cairo_lang_defs::ids::{
     LookupItemId, MacroCallId, MemberId, NamedLanguageElementId, ParamId, StructId,
     TraitConstantId, TraitFunctionId, TraitId, TraitImplId, TraitTypeId, VarId, VariantId,
 };
-use cairo_lang_diagnostics::{DiagnosticAdded, skip_diagnostic};
+use cairo_lang_diagnostics::{DiagnosticAdded, DiagnosticNote, skip_diagnostic};
+use cairo_lang_filesystem::flag::FlagsGroup;
 use cairo_lang_proc_macros::{DebugWithDb, HeapSize, SemanticObject};
 use cairo_lang_syntax::node::TypedStablePtr;
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
@@ -20,10 +21,13 @@ use cairo_lang_utils::deque::Deque;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use cairo_lang_utils::{Intern, define_short_id, extract_matches};
+use itertools::Itertools;
 use salsa::Database;
 
 use self::canonic::{CanonicalImpl, CanonicalMapping, CanonicalTrait, NoError};
-use self::solver::{Ambiguity, SolutionSet, enrich_lookup_context};
+use self::solver::{
+    Ambiguity, CandidateOutcome, SolutionSet, enrich_lookup_context, trace_canonic_trait_solutions,
+};
 use crate::corelib::CorelibSemantic;
 use crate::diagnostic::{SemanticDiagnosticKind, SemanticDiagnostics, SemanticDiagnosticsBuilder};
 use crate::expr::inference::canonic::ResultNoErrEx;
@@ -183,6 +187,16 @@ pub enum InferenceVar {
     Impl(LocalImplVarId),
     NegativeImpl(LocalNegativeImplVarId),
 }
+impl InferenceVar {
+    pub fn format(&self) -> String {
+        match self {
+            InferenceVar::Type(id) => format!("type `?{}`", id.0),
+            InferenceVar::Const(id) => format!("const `?{}`", id.0),
+            InferenceVar::Impl(id) => format!("impl `?{}`", id.0),
+            InferenceVar::NegativeImpl(id) => format!("negative impl `?{}`", id.0),
+        }
+    }
+}
 
 // TODO(spapini): Add to diagnostics.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, DebugWithDb, salsa::Update)]
@@ -355,6 +369,8 @@ struct PendingInferenceError<'db> {
     err: InferenceError<'db>,
     /// The optional location of the error.
     stable_ptr: Option<SyntaxStablePtrId<'db>>,
+    /// The variable that failed to be inferred, if the error is on a specific variable.
+    var: Option<InferenceVar>,
 }
 
 /// A mapping of an impl var's trait items to concrete items.
@@ -1009,6 +1025,7 @@ impl<'db, 'id> Inference<'db, 'id> {
                     self.error_status = Err(InferenceErrorStatus::Pending(PendingInferenceError {
                         err,
                         stable_ptr: self.stable_ptrs.get(&InferenceVar::Impl(var)).cloned(),
+                        var: Some(InferenceVar::Impl(var)),
                     }));
                     return Err(err_set);
                 }
@@ -1346,24 +1363,30 @@ impl<'db, 'id> Inference<'db, 'id> {
         err: InferenceError<'db>,
         stable_ptr: Option<SyntaxStablePtrId<'db>>,
     ) -> ErrorSet {
+        self.set_pending_error(PendingInferenceError { err, stable_ptr, var: None })
+    }
+
+    /// Sets an error in the inference state, with a var to fetch location for the diagnostics
+    /// reporting. Does nothing if an error is already set.
+    /// Returns an `ErrorSet` that can be used in reporting the error.
+    pub fn set_error_on_var(&mut self, err: InferenceError<'db>, var: InferenceVar) -> ErrorSet {
+        let stable_ptr = self.stable_ptrs.get(&var).cloned();
+        self.set_pending_error(PendingInferenceError { err, stable_ptr, var: Some(var) })
+    }
+
+    /// Sets a pending error in the inference state. Does nothing if an error is already set.
+    fn set_pending_error(&mut self, error: PendingInferenceError<'db>) -> ErrorSet {
         if self.error_status.is_err() {
             return ErrorSet;
         }
-        self.error_status = Err(if let InferenceError::Reported(diag_added) = err {
+        self.error_status = Err(if let InferenceError::Reported(diag_added) = error.err {
             InferenceErrorStatus::Consumed(diag_added)
         } else {
-            InferenceErrorStatus::Pending(PendingInferenceError { err, stable_ptr })
+            InferenceErrorStatus::Pending(error)
         });
         ErrorSet
     }
 
-    /// Sets an error in the inference state, with a var to fetch location for the diagnostics
-    /// reporting. Does nothing if an error is already set.
-    /// Returns an `ErrorSet` that can be used in reporting the error.
-    pub fn set_error_on_var(&mut self, err: InferenceError<'db>, var: InferenceVar) -> ErrorSet {
-        self.set_error_ex(err, self.stable_ptrs.get(&var).cloned())
-    }
-
     /// Returns whether an error is set (either pending or consumed).
     pub fn is_error_set(&self) -> InferenceResult<()> {
         self.error_status.as_ref().copied().map_err(|_| ErrorSet)
@@ -1372,8 +1395,11 @@ impl<'db, 'id> Inference<'db, 'id> {
     /// If there is no stable ptr for the pending error, add it by the given var.
     fn add_error_stable_ptr(&mut self, var: InferenceVar) {
         let var_stable_ptr = self.stable_ptrs.get(&var).copied();
-        if let Err(InferenceErrorStatus::Pending(PendingInferenceError { err: _, stable_ptr })) =
-            &mut self.error_status
+        if let Err(InferenceErrorStatus::Pending(PendingInferenceError {
+            err: _,
+            stable_ptr,
+            var: _,
+        })) = &mut self.error_status
             && stable_ptr.is_none()
         {
             *stable_ptr = var_stable_ptr;
@@ -1420,6 +1446,7 @@ impl<'db, 'id> Inference<'db, 'id> {
                     PendingInferenceError {
                         err: InferenceError::Reported(diag_added),
                         stable_ptr: None,
+                        var: None,
                     },
                 );
                 self.error_status = Err(InferenceErrorStatus::Consumed(diag_added));
@@ -1447,6 +1474,8 @@ impl<'db, 'id> Inference<'db, 'id> {
         match state_error {
             InferenceErrorStatus::Consumed(diag_added) => *diag_added,
             InferenceErrorStatus::Pending(pending) => {
+                let pending = pending.clone();
+                let stable_ptr = pending.stable_ptr.unwrap_or(stable_ptr);
                 let diag_added = match &pending.err {
                     InferenceError::TypeNotInferred(_) if diagnostics.error_count > 0 => {
                         // If we have other diagnostics, there is no need to TypeNotInferred.
@@ -1455,7 +1484,13 @@ impl<'db, 'id> Inference<'db, 'id> {
                         // 'DiagnosticAdded' here.
                         skip_diagnostic()
                     }
-                    diag => diag.report(diagnostics, pending.stable_ptr.unwrap_or(stable_ptr)),
+                    err => match self.solver_trace(err, pending.var) {
+                        Some(trace) => diagnostics.report(
+                            stable_ptr,
+                            SemanticDiagnosticKind::TracedInferenceError(err.clone(), trace),
+                        ),
+                        None => err.report(diagnostics, stable_ptr),
+                    },
                 };
                 self.error_status = Err(InferenceErrorStatus::Consumed(diag_added));
                 diag_added
@@ -1463,6 +1498,73 @@ impl<'db, 'id> Inference<'db, 'id> {
         }
     }
 
+    /// Returns the trace of the trait solver for an impl resolution error on `var`, explaining the
+    /// outcome of each candidate impl, if the `trait_solver_trace` flag is set.
+    fn solver_trace(
+        &mut self,
+        err: &InferenceError<'db>,
+        var: Option<InferenceVar>,
+    ) -> Option<Vec<DiagnosticNote<'db>>> {
+        if !self.db.flag_trait_solver_trace() {
+            return None;
+        }
+        let Some(InferenceVar::Impl(var)) = var else {
+            return None;
+        };
+        if !matches!(
+            err,
+            InferenceError::NoImplsFound(_)
+                | InferenceError::Ambiguity(
+                    Ambiguity::MultipleImplsFound { .. } | Ambiguity::FreeVariable { .. }
+                )
+        ) {
+            return None;
+        }
+        let impl_var = self.impl_var(var).clone();
+        let concrete_trait_id = self.rewrite(impl_var.concrete_trait_id).no_err();
+        let mappings = self.impl_vars_trait_item_mappings.get(&var).cloned().unwrap_or_default();
+        let mappings = self.rewrite(mappings).no_err();
+        let mut lookup_context = impl_var.lookup_context.long(self.db).clone();
+        enrich_lookup_context(self.db, concrete_trait_id, &mut lookup_context);
+        let (canonical_trait, canonicalizer) =
+            CanonicalTrait::canonicalize(self.db, self.inference_id, concrete_trait_id, mappings);
+
+        let mut notes = vec![];
+        let vars = canonicalizer.source_vars();
+        if !vars.is_empty() {
+            notes.push(DiagnosticNote::text_only(format!(
+                "Uninferred variables in `{:?}`: {}.",
+                concrete_trait_id.debug(self.db),
+                vars.iter().map(|var| var.format()).join(", ")
+            )));
+        }
+        let trace = trace_canonic_trait_solutions(
+            self.db,
+            canonical_trait,
+            lookup_context.intern(self.db),
+            (*self.data.impl_type_bounds).clone(),
+        );
+        if trace.is_empty() {
+            notes.push(DiagnosticNote::text_only("No candidate impls found in context.".into()));
+        }
+        for (candidate, outcome) in trace {
+            let candidate = candidate.format(self.db);
+            notes.push(DiagnosticNote::text_only(match outcome {
+                CandidateOutcome::Solved(solution) => format!(
+                    "Candidate `{candidate}` matches as `{}`.",
+                    solution.embed(self, &canonicalizer).format(self.db)
+                ),
+                CandidateOutcome::Ambiguous(ambiguity) => {
+                    format!("Candidate `{candidate}` is ambiguous: {}", ambiguity.format(self.db))
+                }
+                CandidateOutcome::Rejected(rejection) => {
+                    format!("Candidate `{candidate}` was rejected: {}", rejection.format(self.db))
+                }
+            }));
+        }
+        Some(notes)
+    }
+
     /// If the current status is of a pending error, reports an alternative diagnostic, by calling
     /// `report`, and consumes the error. Otherwise, does nothing.
     pub fn report_modified_if_pending(
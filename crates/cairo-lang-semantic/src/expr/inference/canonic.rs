@@ -6,6 +6,7 @@ use cairo_lang_defs::ids::{
 use cairo_lang_proc_macros::SemanticObject;
 use cairo_lang_utils::Intern;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::chain;
 use salsa::Database;
 
 use super::{
@@ -105,6 +106,17 @@ pub struct CanonicalMapping<'db> {
     from_canonic: VarMapping<'db>,
 }
 impl<'db> CanonicalMapping<'db> {
+    /// Returns the variables of the inference that are mapped to canonical variables.
+    pub fn source_vars(&self) -> Vec<InferenceVar> {
+        let mapping = &self.to_canonic;
+        chain!(
+            mapping.type_var_mapping.keys().map(|id| InferenceVar::Type(*id)),
+            mapping.const_var_mapping.keys().map(|id| InferenceVar::Const(*id)),
+            mapping.impl_var_mapping.keys().map(|id| InferenceVar::Impl(*id)),
+            mapping.negative_impl_var_mapping.keys().map(|id| InferenceVar::NegativeImpl(*id)),
+        )
+        .collect()
+    }
     fn from_to_canonic(to_canonic: VarMapping<'db>) -> CanonicalMapping<'db> {
         let from_canonic = VarMapping {
             type_var_mapping: to_canonic.type_var_mapping.iter().map(|(k, v)| (*v, *k)).collect(),
@@ -4,8 +4,10 @@ use std::sync::Arc;
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs::ids::{GenericParamId, LanguageElementId};
 use cairo_lang_proc_macros::SemanticObject;
+use cairo_lang_syntax::attribute::consts::FALLBACK_ATTR;
 use cairo_lang_utils::Intern;
 use cairo_lang_utils::ordered_hash_map::Entry;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use itertools::{Itertools, chain, zip_eq};
 use salsa::Database;
 
@@ -13,13 +15,14 @@ use super::canonic::{CanonicalImpl, CanonicalTrait, MapperError, ResultNoErrEx};
 use super::conform::InferenceConform;
 use super::infers::InferenceEmbeddings;
 use super::{
-    ImplVarTraitItemMappings, InferenceData, InferenceError, InferenceId, InferenceResult,
-    InferenceVar, LocalImplVarId,
+    ImplVarTraitItemMappings, Inference, InferenceData, InferenceError, InferenceId,
+    InferenceResult, InferenceVar, LocalImplVarId,
 };
+use crate::items::attribute::SemanticQueryAttrs;
 use crate::items::constant::{ConstValue, ConstValueId, ImplConstantId};
 use crate::items::imp::{
     ImplId, ImplImplId, ImplLongId, ImplLookupContext, ImplLookupContextId, ImplSemantic,
-    UninferredImpl, UninferredImplById, find_candidates_at_context,
+    UninferredImpl, UninferredImplById, are_fallback_impls_enabled, find_candidates_at_context,
     find_closure_generated_candidate,
 };
 use crate::items::trt::TraitSemantic;
@@ -72,6 +75,10 @@ pub enum Ambiguity<'db> {
         var: InferenceVar,
     },
     WillNotInfer(ConcreteTraitId<'db>),
+    FallbackImplForNonConcreteTrait {
+        concrete_trait_id: ConcreteTraitId<'db>,
+        impl_id: ImplId<'db>,
+    },
     NegativeImplWithUnsupportedExtractedArgs(GenericArgumentId<'db>),
     NegativeImplWithUnsupportedGenericParam(GenericParamId<'db>),
 }
@@ -95,6 +102,14 @@ impl<'db> Ambiguity<'db> {
                     concrete_trait_id.debug(db)
                 )
             }
+            Ambiguity::FallbackImplForNonConcreteTrait { concrete_trait_id, impl_id } => {
+                format!(
+                    "Trait `{:?}` is only implemented by the fallback impl `{}`, which is not \
+                     selected for generic arguments that are not fully concrete.",
+                    concrete_trait_id.debug(db),
+                    impl_id.format(db)
+                )
+            }
             Ambiguity::NegativeImplWithUnsupportedExtractedArgs(garg) => {
                 format!("Negative impl has an unsupported generic argument {:?}.", garg.debug(db),)
             }
@@ -196,6 +211,19 @@ pub fn enrich_lookup_context_with_ty<'db>(
     }
 }
 
+/// Returns the candidates for solving `concrete_trait_id` in the given `lookup_context`.
+fn trait_candidates<'db>(
+    db: &'db dyn Database,
+    concrete_trait_id: ConcreteTraitId<'db>,
+    lookup_context: ImplLookupContextId<'db>,
+) -> OrderedHashSet<UninferredImplById<'db>> {
+    let filter = concrete_trait_id.filter(db);
+    let mut candidates = find_candidates_at_context(db, lookup_context, filter).unwrap_or_default();
+    find_closure_generated_candidate(db, concrete_trait_id)
+        .map(|candidate| candidates.insert(UninferredImplById(candidate)));
+    candidates
+}
+
 /// Attempts to solve a `canonical_trait`. Will try to find candidates in the given
 /// `lookup_context`.
 fn solve_canonical_trait<'db>(
@@ -204,12 +232,11 @@ fn solve_canonical_trait<'db>(
     lookup_context: ImplLookupContextId<'db>,
     impl_type_bounds: Arc<BTreeMap<ImplTypeById<'db>, TypeId<'db>>>,
 ) -> SolutionSet<'db, CanonicalImpl<'db>> {
-    let filter = canonical_trait.id.filter(db);
-    let mut candidates = find_candidates_at_context(db, lookup_context, filter).unwrap_or_default();
-    find_closure_generated_candidate(db, canonical_trait.id)
-        .map(|candidate| candidates.insert(UninferredImplById(candidate)));
+    let candidates = trait_candidates(db, canonical_trait.id, lookup_context);
 
     let mut unique_solution: Option<CanonicalImpl<'_>> = None;
+    // Solutions from `#[fallback]` impls, used only if no other candidate solves the trait.
+    let mut fallback_solution: Option<SolutionSet<'_, CanonicalImpl<'_>>> = None;
     for candidate in candidates.into_iter() {
         let Ok(candidate_solution_set) = solve_candidate(
             db,
@@ -220,6 +247,24 @@ fn solve_canonical_trait<'db>(
         ) else {
             continue;
         };
+        if is_fallback_candidate(db, &candidate.0) {
+            fallback_solution = match (fallback_solution, candidate_solution_set) {
+                (prev, SolutionSet::None) => prev,
+                (Some(SolutionSet::Unique(prev)), SolutionSet::Unique(solution))
+                    if prev.0 != solution.0 =>
+                {
+                    Some(SolutionSet::Ambiguous(Ambiguity::MultipleImplsFound {
+                        concrete_trait_id: canonical_trait.id,
+                        impls: vec![prev.0, solution.0],
+                    }))
+                }
+                (Some(SolutionSet::Ambiguous(ambiguity)), _) => {
+                    Some(SolutionSet::Ambiguous(ambiguity))
+                }
+                (_, solution_set) => Some(solution_set),
+            };
+            continue;
+        }
 
         let candidate_solution = match candidate_solution_set {
             SolutionSet::None => continue,
@@ -239,7 +284,123 @@ fn solve_canonical_trait<'db>(
         }
         unique_solution = Some(candidate_solution);
     }
-    unique_solution.map(SolutionSet::Unique).unwrap_or(SolutionSet::None)
+    if let Some(unique_solution) = unique_solution {
+        return SolutionSet::Unique(unique_solution);
+    }
+    match fallback_solution {
+        // A more specific impl may exist for some of the substitutions of the generic arguments,
+        // so a fallback impl is only selected once the trait is fully concrete. This keeps the
+        // selection stable when generic functions are concretized.
+        Some(SolutionSet::Unique(solution)) if !canonical_trait.id.is_fully_concrete(db) => {
+            SolutionSet::Ambiguous(Ambiguity::FallbackImplForNonConcreteTrait {
+                concrete_trait_id: canonical_trait.id,
+                impl_id: solution.0,
+            })
+        }
+        fallback_solution => fallback_solution.unwrap_or(SolutionSet::None),
+    }
+}
+
+/// Returns whether `candidate` is an impl marked with `#[fallback]`, which is selected only if no
+/// other impl solves the trait.
+///
+/// The attribute is ignored if fallback impls are not enabled in the crate of the impl.
+fn is_fallback_candidate<'db>(db: &'db dyn Database, candidate: &UninferredImpl<'db>) -> bool {
+    match candidate {
+        UninferredImpl::Def(impl_def_id) => {
+            impl_def_id.has_attr(db, FALLBACK_ATTR).unwrap_or_default()
+                && are_fallback_impls_enabled(db, impl_def_id.parent_module(db))
+        }
+        UninferredImpl::ImplAlias(_)
+        | UninferredImpl::GenericParam(_)
+        | UninferredImpl::ImplImpl(_)
+        | UninferredImpl::GeneratedImpl(_) => false,
+    }
+}
+
+/// The reason a candidate impl was rejected when solving a trait.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CandidateRejection<'db> {
+    /// The candidate could not be computed, e.g. due to errors in its definition.
+    InvalidCandidate,
+    /// The candidate implements the trait with non-matching generic arguments.
+    TraitMismatch(ConcreteTraitId<'db>),
+    /// A requirement of the candidate, e.g. an impl generic parameter, is not satisfied.
+    UnsatisfiedRequirement(Option<ConcreteTraitId<'db>>),
+    /// Inferring the candidate as the trait failed.
+    InferenceFailed(InferenceError<'db>),
+}
+impl<'db> CandidateRejection<'db> {
+    pub fn format(&self, db: &dyn Database) -> String {
+        match self {
+            CandidateRejection::InvalidCandidate => "The candidate has errors.".into(),
+            CandidateRejection::TraitMismatch(concrete_trait_id) => {
+                format!("It implements `{:?}`.", concrete_trait_id.debug(db))
+            }
+            CandidateRejection::UnsatisfiedRequirement(Some(concrete_trait_id)) => {
+                format!("Its requirement `{:?}` is not satisfied.", concrete_trait_id.debug(db))
+            }
+            CandidateRejection::UnsatisfiedRequirement(None) => {
+                "Its requirements are not satisfied.".into()
+            }
+            CandidateRejection::InferenceFailed(err) => err.format(db),
+        }
+    }
+}
+
+/// The outcome of attempting to solve a trait with a single candidate impl.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CandidateOutcome<'db> {
+    /// The candidate solves the trait as the given impl.
+    Solved(CanonicalImpl<'db>),
+    /// The candidate may solve the trait, but not uniquely.
+    Ambiguous(Ambiguity<'db>),
+    /// The candidate does not solve the trait.
+    Rejected(CandidateRejection<'db>),
+}
+
+/// Attempts to solve a `canonical_trait` with each of the candidates in the given
+/// `lookup_context`, returning the outcome for every candidate - unlike
+/// [SemanticSolver::canonic_trait_solutions], which stops on the first ambiguity.
+/// Used for explaining failed and ambiguous resolutions.
+/// Assumes the lookup context is already enriched by [enrich_lookup_context].
+pub fn trace_canonic_trait_solutions<'db>(
+    db: &'db dyn Database,
+    canonical_trait: CanonicalTrait<'db>,
+    lookup_context: ImplLookupContextId<'db>,
+    impl_type_bounds: BTreeMap<ImplTypeById<'db>, TypeId<'db>>,
+) -> Vec<(UninferredImpl<'db>, CandidateOutcome<'db>)> {
+    let impl_type_bounds = Arc::new(impl_type_bounds);
+    trait_candidates(db, canonical_trait.id, lookup_context)
+        .into_iter()
+        .map(|candidate| {
+            let outcome = match solve_candidate(
+                db,
+                &canonical_trait,
+                candidate.0,
+                lookup_context,
+                impl_type_bounds.clone(),
+            ) {
+                Ok(SolutionSet::Unique(solution)) => CandidateOutcome::Solved(solution),
+                Ok(SolutionSet::Ambiguous(ambiguity)) => CandidateOutcome::Ambiguous(ambiguity),
+                // The lite inference does not explain refutations, so re-solving with a full
+                // inference to find the refuted requirement.
+                Ok(SolutionSet::None) => CandidateOutcome::Rejected(
+                    solve_candidate_with_full_inference(
+                        db,
+                        &canonical_trait,
+                        candidate.0,
+                        candidate_lookup_context(db, lookup_context, &candidate.0),
+                        impl_type_bounds.clone(),
+                    )
+                    .err()
+                    .unwrap_or(CandidateRejection::UnsatisfiedRequirement(None)),
+                ),
+                Err(rejection) => CandidateOutcome::Rejected(rejection),
+            };
+            (candidate.0, outcome)
+        })
+        .collect()
 }
 
 /// Attempts to solve `candidate` as the requested `canonical_trait`.
@@ -249,9 +410,9 @@ fn solve_candidate<'db>(
     candidate: UninferredImpl<'db>,
     lookup_context: ImplLookupContextId<'db>,
     impl_type_bounds: Arc<BTreeMap<ImplTypeById<'db>, TypeId<'db>>>,
-) -> InferenceResult<SolutionSet<'db, CanonicalImpl<'db>>> {
+) -> Result<SolutionSet<'db, CanonicalImpl<'db>>, CandidateRejection<'db>> {
     let Ok(candidate_concrete_trait) = candidate.concrete_trait(db) else {
-        return Err(super::ErrorSet);
+        return Err(CandidateRejection::InvalidCandidate);
     };
     // If the candidate is fully concrete, or it's a generic which is var free, there is nothing
     // to substitute. A generic param may not be var free, if it contains impl types.
@@ -261,7 +422,7 @@ fn solve_candidate<'db>(
     let target_final = canonical_trait.id.is_var_free(db);
     let mut lite_inference = LiteInference::new(db);
     if candidate_final && target_final && candidate_concrete_trait != canonical_trait.id {
-        return Err(super::ErrorSet);
+        return Err(CandidateRejection::TraitMismatch(candidate_concrete_trait));
     }
 
     let mut res = lite_inference.can_conform_generic_args(
@@ -273,30 +434,28 @@ fn solve_candidate<'db>(
     if matches!(candidate, UninferredImpl::GenericParam(_))
         && !lite_inference.substitution.is_empty()
     {
-        return Err(super::ErrorSet);
+        return Err(CandidateRejection::TraitMismatch(candidate_concrete_trait));
     }
 
     // If the trait has trait types, we default to using inference.
     if res == CanConformResult::Accepted {
         let Ok(trait_types) = db.trait_types(canonical_trait.id.trait_id(db)) else {
-            return Err(super::ErrorSet);
+            return Err(CandidateRejection::InvalidCandidate);
         };
         if !trait_types.is_empty() && !canonical_trait.mappings.types.is_empty() {
             res = CanConformResult::InferenceRequired;
         }
     }
 
-    // Add the defining module of the candidate to the lookup.
-    let mut lookup_context = lookup_context.long(db).clone();
-    lookup_context.insert_lookup_scope(db, &candidate);
-    let lookup_context = lookup_context.intern(db);
+    let lookup_context = candidate_lookup_context(db, lookup_context, &candidate);
     if res == CanConformResult::Rejected {
-        return Err(super::ErrorSet);
+        return Err(CandidateRejection::TraitMismatch(candidate_concrete_trait));
     } else if CanConformResult::Accepted == res {
         match candidate {
             UninferredImpl::Def(impl_def_id) => {
-                let imp_generic_params =
-                    db.impl_def_generic_params(impl_def_id).map_err(|_| super::ErrorSet)?;
+                let imp_generic_params = db
+                    .impl_def_generic_params(impl_def_id)
+                    .map_err(|_| CandidateRejection::InvalidCandidate)?;
 
                 match lite_inference.infer_generic_assignment(
                     imp_generic_params,
@@ -328,10 +487,59 @@ fn solve_candidate<'db>(
         }
     }
 
+    solve_candidate_with_full_inference(
+        db,
+        canonical_trait,
+        candidate,
+        lookup_context,
+        impl_type_bounds,
+    )
+}
+
+/// Returns the lookup context for solving `candidate` - with the defining module of the candidate
+/// added to the lookup.
+fn candidate_lookup_context<'db>(
+    db: &'db dyn Database,
+    lookup_context: ImplLookupContextId<'db>,
+    candidate: &UninferredImpl<'db>,
+) -> ImplLookupContextId<'db> {
+    let mut lookup_context = lookup_context.long(db).clone();
+    lookup_context.insert_lookup_scope(db, candidate);
+    lookup_context.intern(db)
+}
+
+/// Attempts to solve `candidate` as the requested `canonical_trait`, using a full inference.
+fn solve_candidate_with_full_inference<'db>(
+    db: &'db dyn Database,
+    canonical_trait: &CanonicalTrait<'db>,
+    candidate: UninferredImpl<'db>,
+    lookup_context: ImplLookupContextId<'db>,
+    impl_type_bounds: Arc<BTreeMap<ImplTypeById<'db>, TypeId<'db>>>,
+) -> Result<SolutionSet<'db, CanonicalImpl<'db>>, CandidateRejection<'db>> {
     let mut inference_data: InferenceData<'_> = InferenceData::new(InferenceId::Canonical);
     let mut inference = inference_data.inference(db);
     inference.data.impl_type_bounds = impl_type_bounds;
-    let (canonical_trait, canonical_embedding) = canonical_trait.embed(&mut inference);
+    solve_candidate_with_inference(&mut inference, canonical_trait, candidate, lookup_context)
+        .map_err(|err_set| match inference.consume_error_without_reporting(err_set) {
+            Some(InferenceError::NoImplsFound(concrete_trait_id)) => {
+                CandidateRejection::UnsatisfiedRequirement(Some(concrete_trait_id))
+            }
+            Some(err) => CandidateRejection::InferenceFailed(err),
+            None => CandidateRejection::InvalidCandidate,
+        })
+}
+
+/// Attempts to solve `candidate` as the requested `canonical_trait`, in the given inference.
+/// Refuted requirements of the candidate are returned as errors, for the caller to explain the
+/// rejection.
+fn solve_candidate_with_inference<'db>(
+    inference: &mut Inference<'db, '_>,
+    canonical_trait: &CanonicalTrait<'db>,
+    candidate: UninferredImpl<'db>,
+    lookup_context: ImplLookupContextId<'db>,
+) -> InferenceResult<SolutionSet<'db, CanonicalImpl<'db>>> {
+    let db = inference.db;
+    let (canonical_trait, canonical_embedding) = canonical_trait.embed(inference);
 
     // If the closure params are not var free, we cannot infer the negative impl.
     // We use the canonical trait to concretize the closure params.
@@ -366,10 +574,14 @@ fn solve_candidate<'db>(
         inference.conform_impl(mapped_impl_id, *impl_id)?;
     }
 
-    let mut inference = inference_data.inference(db);
     let solution_set = inference.solution_set()?;
     Ok(match solution_set {
-        SolutionSet::None => SolutionSet::None,
+        SolutionSet::None => {
+            let (var, err) = inference
+                .first_undetermined_variable()
+                .expect("A refuted inference must have an undetermined variable.");
+            return Err(inference.set_error_on_var(err, var));
+        }
         SolutionSet::Ambiguous(ambiguity) => SolutionSet::Ambiguous(ambiguity),
         SolutionSet::Unique(_) => {
             let candidate_impl = inference.rewrite(candidate_impl).no_err();
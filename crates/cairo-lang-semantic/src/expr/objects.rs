@@ -450,6 +450,8 @@ pub enum Condition {
 #[debug_db(ExprFormatter<'db>)]
 pub struct MatchArm {
     pub patterns: Vec<PatternId>,
+    /// An optional `if` condition that must hold for the arm to be taken.
+    pub guard: Option<ExprId>,
     pub expression: ExprId,
 }
 
@@ -546,6 +548,8 @@ pub struct ExprStructCtor<'db> {
     pub members: Vec<(ExprId, MemberId<'db>)>,
     /// The base struct to copy missing members from if provided.
     /// For example `let x = MyStruct { a: 1, ..base }`.
+    /// If the base struct is a member path (e.g. a variable), the members taken from it are
+    /// already included in `members` as member accesses on it.
     pub base_struct: Option<ExprId>,
     pub ty: semantic::TypeId<'db>,
     #[hide_field_debug_with_db]
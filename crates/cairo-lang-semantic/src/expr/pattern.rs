@@ -28,6 +28,7 @@ use crate::{
 #[debug_db(ExprFormatter<'db>)]
 pub enum Pattern<'db> {
     Literal(PatternLiteral<'db>),
+    Range(PatternRange<'db>),
     StringLiteral(PatternStringLiteral<'db>),
     Variable(PatternVariable<'db>),
     Struct(PatternStruct<'db>),
@@ -42,6 +43,7 @@ impl<'db> Pattern<'db> {
     pub fn ty(&self) -> semantic::TypeId<'db> {
         match self {
             Pattern::Literal(literal) => literal.literal.ty,
+            Pattern::Range(range) => range.start.ty,
             Pattern::StringLiteral(string_literal) => string_literal.string_literal.ty,
             Pattern::Variable(variable) => variable.var.ty,
             Pattern::Struct(pattern_struct) => pattern_struct.ty,
@@ -81,6 +83,7 @@ impl<'db> Pattern<'db> {
                 }
             }
             Pattern::Literal(_)
+            | Pattern::Range(_)
             | Pattern::StringLiteral(_)
             | Pattern::Otherwise(_)
             | Pattern::Missing(_) => vec![],
@@ -90,6 +93,7 @@ impl<'db> Pattern<'db> {
     pub fn stable_ptr(&self) -> ast::PatternPtr<'db> {
         match self {
             Pattern::Literal(pattern) => pattern.stable_ptr,
+            Pattern::Range(pattern) => pattern.stable_ptr,
             Pattern::StringLiteral(pattern) => pattern.stable_ptr,
             Pattern::Variable(pattern) => pattern.stable_ptr,
             Pattern::Struct(pattern) => pattern.stable_ptr.into(),
@@ -171,6 +175,27 @@ pub struct PatternLiteral<'db> {
     pub stable_ptr: ast::PatternPtr<'db>,
 }
 
+/// A range of numeric literals, e.g. `1..5` or `1..=5`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, DebugWithDb, SemanticObject)]
+#[debug_db(ExprFormatter<'db>)]
+pub struct PatternRange<'db> {
+    pub start: ExprNumericLiteral<'db>,
+    pub end: ExprNumericLiteral<'db>,
+    /// Whether the end of the range is included (`..=`).
+    #[dont_rewrite]
+    pub inclusive: bool,
+    #[hide_field_debug_with_db]
+    #[dont_rewrite]
+    pub stable_ptr: ast::PatternPtr<'db>,
+}
+
+impl<'db> PatternRange<'db> {
+    /// Returns the inclusive upper bound of the range.
+    pub fn last(&self) -> num_bigint::BigInt {
+        if self.inclusive { self.end.value.clone() } else { &self.end.value - 1 }
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, DebugWithDb, SemanticObject)]
 #[debug_db(ExprFormatter<'db>)]
 pub struct PatternStringLiteral<'db> {
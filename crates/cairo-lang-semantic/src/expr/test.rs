@@ -43,6 +43,7 @@ cairo_lang_test_utils::test_file_test!(
         enum_: "enum",
         error_propagate: "error_propagate",
         for_: "for",
+        fallback_impl: "fallback_impl",
         fixed_size_array: "fixed_size_array",
         function_call: "function_call",
         generics: "generics",
@@ -0,0 +1,314 @@
+//! Usefulness analysis of patterns - computing the values not covered by a list of patterns, as
+//! concrete patterns, for reporting non-exhaustive matches.
+//!
+//! Based on the algorithm of "Warnings for pattern matching" (Maranget, 2007): a value is not
+//! covered by a list of patterns if the wildcard pattern is useful with respect to the list, and
+//! the witnesses of that usefulness are the missing patterns.
+
+use std::collections::BTreeSet;
+
+use cairo_lang_defs::ids::NamedLanguageElementId;
+use cairo_lang_diagnostics::Maybe;
+use itertools::{Itertools, chain};
+use num_bigint::BigInt;
+use salsa::Database;
+
+use super::compute::unwrap_pattern_type;
+use super::objects::PatternArena;
+use crate::items::enm::SemanticEnumEx;
+use crate::items::structure::StructSemantic;
+use crate::{
+    ConcreteStructId, ConcreteTypeId, ConcreteVariant, Pattern, PatternId, TypeId, TypeLongId,
+    corelib,
+};
+
+#[cfg(test)]
+#[path = "usefulness_test.rs"]
+mod test;
+
+/// A pattern of values not covered by a list of patterns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MissingPattern<'db> {
+    /// Any value - `_`.
+    Wildcard,
+    /// A variant of an enum, with the missing pattern of its inner value.
+    Variant(ConcreteVariant<'db>, Box<MissingPattern<'db>>),
+    /// A tuple, with the missing patterns of its elements.
+    Tuple(Vec<MissingPattern<'db>>),
+    /// A struct, with the missing patterns of its members by order.
+    Struct(ConcreteStructId<'db>, Vec<MissingPattern<'db>>),
+    /// An inclusive range of integers.
+    Range(BigInt, BigInt),
+}
+
+impl<'db> MissingPattern<'db> {
+    /// Formats the pattern as Cairo code, e.g. `Option::Some((Inner::B(_), _))`.
+    pub fn format(&self, db: &'db dyn Database) -> String {
+        match self {
+            MissingPattern::Wildcard => "_".into(),
+            MissingPattern::Variant(variant, inner) => {
+                if variant.concrete_enum_id == corelib::core_bool_enum(db) {
+                    // The `bool` variants are matched by the `true` and `false` literals.
+                    return variant.id.name(db).long(db).to_lowercase();
+                }
+                let enum_name = variant.concrete_enum_id.enum_id(db).name(db).long(db);
+                let variant_name = variant.id.name(db).long(db);
+                if **inner == MissingPattern::Wildcard && variant.ty == corelib::unit_ty(db) {
+                    format!("{enum_name}::{variant_name}")
+                } else {
+                    format!("{enum_name}::{variant_name}({})", inner.format(db))
+                }
+            }
+            MissingPattern::Tuple(elements) => match &elements[..] {
+                [element] => format!("({},)", element.format(db)),
+                _ => format!("({})", elements.iter().map(|element| element.format(db)).join(", ")),
+            },
+            MissingPattern::Struct(concrete_struct_id, members) => {
+                let struct_name = concrete_struct_id.struct_id(db).name(db).long(db);
+                let Ok(member_names) = db.concrete_struct_members(*concrete_struct_id) else {
+                    return format!("{struct_name} {{ .. }}");
+                };
+                let covered_members = member_names
+                    .keys()
+                    .zip(members)
+                    .filter(|(_, pattern)| **pattern != MissingPattern::Wildcard)
+                    .map(|(name, pattern)| format!("{}: {}", name.long(db), pattern.format(db)))
+                    .collect_vec();
+                let rest = (covered_members.len() < members.len()).then(|| "..".to_string());
+                format!("{struct_name} {{ {} }}", chain!(covered_members, rest).join(", "))
+            }
+            MissingPattern::Range(start, last) if start == last => start.to_string(),
+            MissingPattern::Range(start, last) => format!("{start}..={last}"),
+        }
+    }
+}
+
+/// Returns the patterns of the values of type `ty` not covered by any of `patterns`, or an empty
+/// vector if the patterns are exhaustive.
+///
+/// Literal patterns are never considered exhaustive, so a match on a numeric or string value is
+/// only covered by a catch-all pattern - unless it is a match on a bounded integer with range
+/// patterns, in which case the values are covered by the ranges and literals.
+pub fn missing_patterns<'db>(
+    db: &'db dyn Database,
+    arena: &PatternArena<'db>,
+    ty: TypeId<'db>,
+    patterns: impl IntoIterator<Item = PatternId>,
+) -> Maybe<Vec<MissingPattern<'db>>> {
+    let rows = patterns.into_iter().map(|pattern| vec![Some(&arena[pattern])]).collect_vec();
+    let witnesses = UsefulnessContext { db, arena }.missing(rows, &[ty])?;
+    Ok(witnesses.into_iter().map(|mut witness| witness.remove(0)).collect())
+}
+
+/// A pattern in a row of the pattern matrix, where `None` represents the `_` pattern.
+type PatternOption<'a, 'db> = Option<&'a Pattern<'db>>;
+
+/// A constructor of values of a type.
+#[derive(Clone)]
+enum Constructor<'db> {
+    Variant(ConcreteVariant<'db>),
+    Tuple,
+    Struct(ConcreteStructId<'db>),
+    /// An inclusive range of integers, all accepted by the same patterns.
+    Range(BigInt, BigInt),
+}
+
+/// The constructors of the values of a type, along with the types of their fields.
+/// `None` if the values of the type cannot be enumerated by constructors (e.g. numeric values).
+type TypeConstructors<'db> = Option<Vec<(Constructor<'db>, Vec<TypeId<'db>>)>>;
+
+struct UsefulnessContext<'db, 'a> {
+    db: &'db dyn Database,
+    arena: &'a PatternArena<'db>,
+}
+
+impl<'db, 'a> UsefulnessContext<'db, 'a> {
+    /// Returns the vectors of patterns of the values of types `tys` not covered by any of the
+    /// `rows`, where each row is a vector of patterns for the values of types `tys`.
+    fn missing(
+        &self,
+        rows: Vec<Vec<PatternOption<'a, 'db>>>,
+        tys: &[TypeId<'db>],
+    ) -> Maybe<Vec<Vec<MissingPattern<'db>>>> {
+        let Some((head_ty, tail_tys)) = tys.split_first() else {
+            return Ok(if rows.is_empty() { vec![vec![]] } else { vec![] });
+        };
+        let constructors = self.type_constructors(*head_ty, &rows)?;
+        if constructors.as_ref().is_some_and(|constructors| constructors.is_empty()) {
+            // A type without values is always covered.
+            return Ok(vec![]);
+        }
+        let head_is_wildcard = rows.iter().all(|row| is_wildcard(row[0]));
+        let Some(constructors) = constructors.filter(|_| !head_is_wildcard) else {
+            // The head column does not distinguish between the values - only the rows matching any
+            // head value are relevant, and the missing head values are reported as `_`.
+            let default_rows =
+                rows.into_iter().filter(|row| is_wildcard(row[0])).map(|row| row[1..].to_vec());
+            return Ok(self
+                .missing(default_rows.collect(), tail_tys)?
+                .into_iter()
+                .map(|witness| chain!([MissingPattern::Wildcard], witness).collect())
+                .collect());
+        };
+        let mut witnesses = vec![];
+        for (constructor, field_tys) in constructors {
+            let specialized_rows = rows
+                .iter()
+                .filter_map(|row| {
+                    let fields = self.specialize(row[0], &constructor, field_tys.len())?;
+                    Some(chain!(fields, row[1..].iter().copied()).collect())
+                })
+                .collect();
+            let tys = chain!(field_tys.iter().copied(), tail_tys.iter().copied()).collect_vec();
+            for mut witness in self.missing(specialized_rows, &tys)? {
+                let tail = witness.split_off(field_tys.len());
+                let pattern = match &constructor {
+                    Constructor::Variant(variant) => {
+                        MissingPattern::Variant(*variant, Box::new(witness.remove(0)))
+                    }
+                    Constructor::Tuple => MissingPattern::Tuple(witness),
+                    Constructor::Struct(concrete_struct_id) => {
+                        MissingPattern::Struct(*concrete_struct_id, witness)
+                    }
+                    Constructor::Range(start, last) => {
+                        // Merge with the missing range right before this one, if it has the same
+                        // missing values in the rest of the columns.
+                        if let Some([MissingPattern::Range(_, prev_last), prev_tail @ ..]) =
+                            witnesses.last_mut().map(|prev: &mut Vec<_>| prev.as_mut_slice())
+                            && &*prev_last + 1 == *start
+                            && *prev_tail == tail[..]
+                        {
+                            *prev_last = last.clone();
+                            continue;
+                        }
+                        MissingPattern::Range(start.clone(), last.clone())
+                    }
+                };
+                witnesses.push(chain!([pattern], tail).collect());
+            }
+        }
+        Ok(witnesses)
+    }
+
+    /// Returns the constructors of the values of type `ty`, matched by the head column of `rows`.
+    fn type_constructors(
+        &self,
+        ty: TypeId<'db>,
+        rows: &[Vec<PatternOption<'a, 'db>>],
+    ) -> Maybe<TypeConstructors<'db>> {
+        let db = self.db;
+        if corelib::numeric_upcastable_to_felt252(db, ty) {
+            return Ok(self.range_constructors(ty, rows));
+        }
+        Ok(Some(match unwrap_pattern_type(db, ty).0 {
+            TypeLongId::Concrete(ConcreteTypeId::Enum(concrete_enum_id)) => db
+                .concrete_enum_variants(concrete_enum_id)?
+                .into_iter()
+                .map(|variant| (Constructor::Variant(variant), vec![variant.ty]))
+                .collect(),
+            TypeLongId::Concrete(ConcreteTypeId::Struct(concrete_struct_id)) => {
+                let members = db.concrete_struct_members(concrete_struct_id)?;
+                vec![(
+                    Constructor::Struct(concrete_struct_id),
+                    members.values().map(|member| member.ty).collect(),
+                )]
+            }
+            TypeLongId::Tuple(tys) => vec![(Constructor::Tuple, tys)],
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Returns the constructors of the values of the integer type `ty`, if the head column of
+    /// `rows` has range patterns and the values of `ty` are bounded.
+    ///
+    /// The values are split into ranges, such that each literal or range pattern in the column
+    /// either matches all the values of a range, or none of them.
+    fn range_constructors(
+        &self,
+        ty: TypeId<'db>,
+        rows: &[Vec<PatternOption<'a, 'db>>],
+    ) -> TypeConstructors<'db> {
+        let patterns = rows.iter().filter_map(|row| row[0]);
+        if !patterns.clone().any(|pattern| matches!(pattern, Pattern::Range(_))) {
+            return None;
+        }
+        let (min, max) = corelib::bounded_integer_type_range(self.db, ty)?;
+        // The first value of each range, followed by the value after the last range.
+        let mut boundaries = BTreeSet::from([min.clone(), &max + 1]);
+        for pattern in patterns {
+            let (start, last) = match pattern {
+                Pattern::Literal(pattern) => {
+                    (pattern.literal.value.clone(), pattern.literal.value.clone())
+                }
+                Pattern::Range(pattern) => (pattern.start.value.clone(), pattern.last()),
+                _ => continue,
+            };
+            for boundary in [start, last + 1] {
+                if min < boundary && boundary <= max {
+                    boundaries.insert(boundary);
+                }
+            }
+        }
+        Some(
+            boundaries
+                .into_iter()
+                .tuple_windows()
+                .map(|(start, next)| (Constructor::Range(start, next - 1), vec![]))
+                .collect(),
+        )
+    }
+
+    /// Returns the patterns of the fields of `pattern` if it matches values of `constructor`, or
+    /// `None` if it does not.
+    fn specialize(
+        &self,
+        pattern: PatternOption<'a, 'db>,
+        constructor: &Constructor<'db>,
+        n_fields: usize,
+    ) -> Option<Vec<PatternOption<'a, 'db>>> {
+        let field = |pattern_id: &PatternId| Some(&self.arena[*pattern_id]);
+        if is_wildcard(pattern) {
+            return Some(vec![None; n_fields]);
+        }
+        match (pattern?, constructor) {
+            (Pattern::Literal(pattern), Constructor::Range(start, last)) => {
+                (*start == pattern.literal.value && *last == pattern.literal.value).then(Vec::new)
+            }
+            (Pattern::Range(pattern), Constructor::Range(start, last)) => {
+                (pattern.start.value <= *start && *last <= pattern.last()).then(Vec::new)
+            }
+            (Pattern::EnumVariant(pattern), Constructor::Variant(variant)) => (pattern.variant.idx
+                == variant.idx)
+                .then(|| vec![pattern.inner_pattern.as_ref().and_then(field)]),
+            (Pattern::Tuple(pattern), Constructor::Tuple) => {
+                Some(pattern.field_patterns.iter().map(field).collect())
+            }
+            (Pattern::Struct(pattern), Constructor::Struct(concrete_struct_id)) => {
+                let members = self.db.concrete_struct_members(*concrete_struct_id).ok()?;
+                Some(
+                    members
+                        .values()
+                        .map(|member| {
+                            pattern
+                                .field_patterns
+                                .iter()
+                                .find(|(_, field_member)| field_member.id == member.id)
+                                .and_then(|(pattern_id, _)| field(pattern_id))
+                        })
+                        .collect(),
+                )
+            }
+            // A pattern not matching the type of the value - an error is reported elsewhere, so
+            // the pattern is considered as matching anything, to avoid cascading diagnostics.
+            _ => Some(vec![None; n_fields]),
+        }
+    }
+}
+
+/// Returns whether `pattern` matches any value.
+fn is_wildcard(pattern: PatternOption<'_, '_>) -> bool {
+    matches!(
+        pattern,
+        None | Some(Pattern::Variable(_) | Pattern::Otherwise(_) | Pattern::Missing(_))
+    )
+}
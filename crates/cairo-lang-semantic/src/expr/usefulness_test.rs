@@ -0,0 +1,170 @@
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+use super::missing_patterns;
+use crate::Expr;
+use crate::items::function_with_body::FunctionWithBodySemantic;
+use crate::test_utils::{SemanticDatabaseForTesting, setup_test_function_ex};
+
+const MODULE_CODE: &str = "
+    enum Inner { A, B: felt252, C: (felt252, bool) }
+    struct Pair { first: Inner, second: Option<felt252> }
+";
+
+/// Returns the formatted missing patterns of the first match in the function `foo`.
+fn format_missing_patterns(function_code: &str) -> Vec<String> {
+    let db = &SemanticDatabaseForTesting::default();
+    let test_function =
+        setup_test_function_ex(db, function_code, "foo", MODULE_CODE, None, None).unwrap();
+    let body = db.function_body(test_function.function_id).unwrap();
+    let expr_match = body
+        .arenas
+        .exprs
+        .iter()
+        .find_map(|(_, expr)| match expr {
+            Expr::Match(expr_match) => Some(expr_match),
+            _ => None,
+        })
+        .unwrap();
+    let matched_ty = body.arenas.exprs[expr_match.matched_expr].ty();
+    let patterns = expr_match.arms.iter().flat_map(|arm| arm.patterns.iter().copied());
+    let missing = missing_patterns(db, &body.arenas.patterns, matched_ty, patterns).unwrap();
+    missing.iter().map(|pattern| pattern.format(db)).collect_vec()
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_nested_enum() {
+    assert_eq!(
+        format_missing_patterns(
+            "fn foo(x: Option<Inner>) { match x { Some(Inner::A) => {}, Some(Inner::C(_)) => {}, \
+             None => {} } }"
+        ),
+        ["Option::Some(Inner::B(_))"],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_unit_variant() {
+    assert_eq!(
+        format_missing_patterns("fn foo(x: Option<Inner>) { match x { Some(_) => {} } }"),
+        ["Option::None"],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_tuple() {
+    assert_eq!(
+        format_missing_patterns(
+            "fn foo(x: (Inner, bool)) { match x { (Inner::A, _) => {}, (_, true) => {} } }"
+        ),
+        ["(Inner::B(_), false)", "(Inner::C(_), false)"],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_variant_of_tuple() {
+    assert_eq!(
+        format_missing_patterns(
+            "fn foo(x: Inner) { match x { Inner::A => {}, Inner::B(_) => {}, Inner::C((_, false)) \
+             => {} } }"
+        ),
+        ["Inner::C((_, true))"],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_struct() {
+    assert_eq!(
+        format_missing_patterns(
+            "fn foo(x: Pair) { match x { Pair { first: Inner::A, .. } => {}, Pair { first: _, \
+             second: None } => {} } }"
+        ),
+        [
+            "Pair { first: Inner::B(_), second: Option::Some(_) }",
+            "Pair { first: Inner::C(_), second: Option::Some(_) }"
+        ],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_snapshot() {
+    assert_eq!(
+        format_missing_patterns(
+            "fn foo(x: @Option<Inner>) { match x { Some(Inner::A) => {}, Some(_) => {} } }"
+        ),
+        ["Option::None"],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_literal() {
+    assert_eq!(
+        format_missing_patterns(
+            "fn foo(x: Option<felt252>) { match x { Some(0) => {}, None => {} } }"
+        ),
+        ["Option::Some(_)"],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_or_patterns() {
+    assert_eq!(
+        format_missing_patterns(
+            "fn foo(x: Option<Inner>) { match x { Some(Inner::A) | Some(Inner::B(_)) => {}, \
+             Some(Inner::C(_)) | None => {} } }"
+        ),
+        Vec::<String>::new(),
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_untouched_column() {
+    assert_eq!(
+        format_missing_patterns("fn foo(x: Option<(Inner, Inner)>) { match x { None => {} } }"),
+        ["Option::Some(_)"],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_ranges() {
+    assert_eq!(
+        format_missing_patterns(
+            "fn foo(x: u8) { match x { 0 => {}, 1..=9 | 20..30 => {}, 100..=255 => {} } }"
+        ),
+        ["10..=19", "30..=99"],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_exhaustive_ranges() {
+    assert_eq!(
+        format_missing_patterns(
+            "fn foo(x: u8) { match x { 0..128 => {}, 128 => {}, 129..=255 => {} } }"
+        ),
+        Vec::<String>::new(),
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_signed_ranges() {
+    assert_eq!(
+        format_missing_patterns("fn foo(x: i8) { match x { 0..=127 => {} } }"),
+        ["-128..=-1"],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_ranges_in_tuple() {
+    assert_eq!(
+        format_missing_patterns(
+            "fn foo(x: (u8, bool)) { match x { (0..10, _) => {}, (10, true) => {}, (11..=255, \
+             true) => {} } }"
+        ),
+        ["(10..=255, false)"],
+    );
+}
+
+#[cairo_lang_test_utils::test]
+fn test_missing_patterns_ranges_felt252() {
+    assert_eq!(format_missing_patterns("fn foo(x: felt252) { match x { 0..10 => {} } }"), ["_"],);
+}
@@ -0,0 +1,248 @@
+//! Lookup of the semantic information of the expression at a given offset in a file, for the use of
+//! IDE layers (hover, inlay hints, go to definition, etc.).
+
+use cairo_lang_defs::ids::{
+    FreeFunctionLongId, FunctionWithBodyId, ImplFunctionLongId, ImplItemId, LookupItemId, MemberId,
+    ModuleItemId, TraitFunctionLongId, TraitItemId,
+};
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::span::TextOffset;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_proc_macros::DebugWithDb;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, Terminal, TypedSyntaxNode, ast};
+use cairo_lang_utils::Intern;
+use salsa::Database;
+
+use crate::db::SemanticGroup;
+use crate::items::enm::EnumSemantic;
+use crate::items::function_with_body::{FunctionWithBodySemantic, SemanticExprLookup};
+use crate::items::functions::FunctionsSemantic;
+use crate::items::structure::StructSemantic;
+use crate::lsp_helpers::LspHelpers;
+use crate::resolve::ResolvedGenericItem;
+use crate::types::ClosureTypeLongId;
+use crate::{Condition, Expr, ExprFunctionCallArg, ExprId, Statement, TypeId, TypeLongId, corelib};
+
+#[cfg(test)]
+#[path = "expr_at_offset_test.rs"]
+mod test;
+
+/// The semantic information of an expression at a given offset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExprAtOffset<'db> {
+    /// The function whose body contains the expression.
+    pub function_id: FunctionWithBodyId<'db>,
+    /// The innermost expression containing the offset.
+    pub expr_id: ExprId,
+    /// The inferred type of the expression.
+    pub ty: TypeId<'db>,
+    /// The type expected by the context of the expression, if the context constrains it - e.g. the
+    /// type of the matching parameter for a function call argument.
+    pub expected_ty: Option<TypeId<'db>>,
+    /// The definition the expression (or the identifier at the offset) resolves to, if any.
+    pub definition: Option<ExprDefinition<'db>>,
+}
+
+/// A definition an expression resolves to.
+#[derive(Clone, Debug, PartialEq, Eq, DebugWithDb)]
+#[debug_db(dyn Database)]
+pub enum ExprDefinition<'db> {
+    /// A resolved item, including local variables and parameters.
+    Item(ResolvedGenericItem<'db>),
+    /// A member of a struct.
+    Member(MemberId<'db>),
+}
+
+/// Implementation of [LspHelpers::expr_at_offset].
+pub fn expr_at_offset<'db>(
+    db: &'db dyn Database,
+    file_id: FileId<'db>,
+    offset: TextOffset,
+) -> Option<ExprAtOffset<'db>> {
+    let node = db.file_syntax(file_id).ok()?.lookup_offset(db, offset);
+    let function_id = function_with_body_containing_node(db, node)?;
+    let (expr_node, expr_id) = find_semantic_expr(db, function_id, node)?;
+    let expr = db.expr_semantic(function_id, expr_id);
+    Some(ExprAtOffset {
+        function_id,
+        expr_id,
+        ty: expr.ty(),
+        expected_ty: expected_type(db, function_id, expr_node, expr_id),
+        definition: identifier_definition(db, function_id, node)
+            .or_else(|| expr_definition(db, &expr)),
+    })
+}
+
+/// Returns the function with a body whose definition contains the given node.
+fn function_with_body_containing_node<'db>(
+    db: &'db dyn Database,
+    node: SyntaxNode<'db>,
+) -> Option<FunctionWithBodyId<'db>> {
+    let function_node = node.ancestors_with_self(db).find(|node| {
+        matches!(node.kind(db), SyntaxKind::FunctionWithBody | SyntaxKind::TraitItemFunction)
+    })?;
+    let module_id = db.find_module_containing_node(function_node)?;
+    Some(match function_node.kind(db) {
+        SyntaxKind::TraitItemFunction => FunctionWithBodyId::Trait(
+            TraitFunctionLongId(
+                module_id,
+                ast::TraitItemFunction::from_syntax_node(db, function_node).stable_ptr(db),
+            )
+            .intern(db),
+        ),
+        _ => {
+            let ptr = ast::FunctionWithBody::from_syntax_node(db, function_node).stable_ptr(db);
+            if function_node.ancestor_of_kind(db, SyntaxKind::ItemImpl).is_some() {
+                FunctionWithBodyId::Impl(ImplFunctionLongId(module_id, ptr).intern(db))
+            } else {
+                FunctionWithBodyId::Free(FreeFunctionLongId(module_id, ptr).intern(db))
+            }
+        }
+    })
+}
+
+/// Returns the innermost expression of the function containing the given node (inclusive), along
+/// with its syntax node.
+fn find_semantic_expr<'db>(
+    db: &'db dyn Database,
+    function_id: FunctionWithBodyId<'db>,
+    node: SyntaxNode<'db>,
+) -> Option<(SyntaxNode<'db>, ExprId)> {
+    node.ancestors_with_self(db).find_map(|node| {
+        let expr = ast::Expr::cast(db, node)?;
+        Some((node, db.lookup_expr_by_ptr(function_id, expr.stable_ptr(db)).ok()?))
+    })
+}
+
+/// Returns the type expected by the context of the expression `expr_id`, found at `expr_node`.
+fn expected_type<'db>(
+    db: &'db dyn Database,
+    function_id: FunctionWithBodyId<'db>,
+    expr_node: SyntaxNode<'db>,
+    expr_id: ExprId,
+) -> Option<TypeId<'db>> {
+    let Some((parent_node, parent_id)) =
+        expr_node.parent(db).and_then(|parent| find_semantic_expr(db, function_id, parent))
+    else {
+        // The expression is the body of the function.
+        return (db.function_body_expr(function_id).ok()? == expr_id)
+            .then(|| Some(db.function_with_body_signature(function_id).ok()?.return_type))
+            .flatten();
+    };
+    match db.expr_semantic(function_id, parent_id) {
+        Expr::FunctionCall(call) => {
+            let index = call.args.iter().position(|arg| match arg {
+                ExprFunctionCallArg::Value(id) | ExprFunctionCallArg::TempReference(id) => {
+                    *id == expr_id
+                }
+                ExprFunctionCallArg::Reference(_) => false,
+            })?;
+            Some(db.concrete_function_signature(call.function).ok()?.params.get(index)?.ty)
+        }
+        Expr::Assignment(assignment) => {
+            (assignment.rhs == expr_id).then(|| assignment.ref_arg.ty())
+        }
+        Expr::StructCtor(ctor) => {
+            if ctor.base_struct == Some(expr_id) {
+                return Some(ctor.ty);
+            }
+            let (_, member_id) = ctor.members.iter().find(|(id, _)| *id == expr_id)?;
+            let members = db.concrete_struct_members(ctor.concrete_struct_id).ok()?;
+            members.values().find(|member| member.id == *member_id).map(|member| member.ty)
+        }
+        Expr::EnumVariantCtor(ctor) => (ctor.value_expr == expr_id).then_some(ctor.variant.ty),
+        Expr::Block(block) => {
+            if block.tail == Some(expr_id) {
+                return expected_type(db, function_id, parent_node, parent_id);
+            }
+            block.statements.iter().find_map(|statement_id| {
+                match db.statement_semantic(function_id, *statement_id) {
+                    Statement::Let(statement) if statement.expr == expr_id => {
+                        Some(db.pattern_semantic(function_id, statement.pattern).ty())
+                    }
+                    Statement::Return(statement) if statement.expr_option == Some(expr_id) => {
+                        return_type(db, function_id, expr_node)
+                    }
+                    _ => None,
+                }
+            })
+        }
+        Expr::If(expr_if) => {
+            if expr_if.if_block == expr_id || expr_if.else_block == Some(expr_id) {
+                return expected_type(db, function_id, parent_node, parent_id);
+            }
+            expr_if
+                .conditions
+                .iter()
+                .any(|condition| matches!(condition, Condition::BoolExpr(id) if *id == expr_id))
+                .then(|| corelib::core_bool_ty(db))
+        }
+        Expr::Match(expr_match) => {
+            if expr_match.arms.iter().any(|arm| arm.expression == expr_id) {
+                return expected_type(db, function_id, parent_node, parent_id);
+            }
+            expr_match
+                .arms
+                .iter()
+                .any(|arm| arm.guard == Some(expr_id))
+                .then(|| corelib::core_bool_ty(db))
+        }
+        Expr::ExprClosure(closure) if closure.body == expr_id => {
+            closure_return_type(db, closure.ty)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the return type of the closure or function a `return` statement at `node` returns from.
+fn return_type<'db>(
+    db: &'db dyn Database,
+    function_id: FunctionWithBodyId<'db>,
+    node: SyntaxNode<'db>,
+) -> Option<TypeId<'db>> {
+    let Some(closure) = node.ancestor_of_type::<ast::ExprClosure<'db>>(db) else {
+        return Some(db.function_with_body_signature(function_id).ok()?.return_type);
+    };
+    let closure_id = db.lookup_expr_by_ptr(function_id, closure.stable_ptr(db).into()).ok()?;
+    closure_return_type(db, db.expr_semantic(function_id, closure_id).ty())
+}
+
+/// Returns the return type of a closure type.
+fn closure_return_type<'db>(db: &'db dyn Database, closure_ty: TypeId<'db>) -> Option<TypeId<'db>> {
+    match closure_ty.long(db) {
+        TypeLongId::Closure(ClosureTypeLongId { ret_ty, .. }) => Some(*ret_ty),
+        _ => None,
+    }
+}
+
+/// Returns the definition of the identifier at `node`, if `node` is a resolved identifier token.
+fn identifier_definition<'db>(
+    db: &'db dyn Database,
+    function_id: FunctionWithBodyId<'db>,
+    node: SyntaxNode<'db>,
+) -> Option<ExprDefinition<'db>> {
+    let identifier = ast::TerminalIdentifier::cast_token(db, node)?;
+    let lookup_item_id = match function_id {
+        FunctionWithBodyId::Free(id) => LookupItemId::ModuleItem(ModuleItemId::FreeFunction(id)),
+        FunctionWithBodyId::Impl(id) => LookupItemId::ImplItem(ImplItemId::Function(id)),
+        FunctionWithBodyId::Trait(id) => LookupItemId::TraitItem(TraitItemId::Function(id)),
+    };
+    db.lookup_resolved_generic_item_by_ptr(lookup_item_id, identifier.stable_ptr(db))
+        .map(ExprDefinition::Item)
+}
+
+/// Returns the definition an expression directly refers to.
+fn expr_definition<'db>(db: &'db dyn Database, expr: &Expr<'db>) -> Option<ExprDefinition<'db>> {
+    Some(match expr {
+        Expr::Var(var) => ExprDefinition::Item(ResolvedGenericItem::Variable(var.var)),
+        Expr::FunctionCall(call) => ExprDefinition::Item(ResolvedGenericItem::GenericFunction(
+            call.function.get_concrete(db).generic_function,
+        )),
+        Expr::MemberAccess(member_access) => ExprDefinition::Member(member_access.member),
+        Expr::EnumVariantCtor(ctor) => ExprDefinition::Item(ResolvedGenericItem::Variant(
+            db.variant_semantic(ctor.variant.concrete_enum_id.enum_id(db), ctor.variant.id).ok()?,
+        )),
+        _ => return None,
+    })
+}
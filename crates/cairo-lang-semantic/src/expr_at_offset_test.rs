@@ -0,0 +1,66 @@
+use cairo_lang_debug::DebugWithDb;
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::set_crate_config;
+use cairo_lang_filesystem::span::TextOffset;
+use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode};
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use crate::TypeId;
+use crate::items::function_with_body::FunctionWithBodySemantic;
+use crate::lsp_helpers::LspHelpers;
+use crate::test_utils::{SemanticDatabaseForTesting, setup_test_crate};
+
+cairo_lang_test_utils::test_file_test!(
+    expr_at_offset,
+    "src/expr_at_offset_test_data",
+    {
+        expr_at_offset: "expr_at_offset",
+    },
+    test_expr_at_offset
+);
+
+/// The marker of the offset to look up in the crate code.
+const CARET: &str = "<caret>";
+
+fn test_expr_at_offset(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    // A fresh database is used, as the shared one cannot be mutated.
+    let mut db = SemanticDatabaseForTesting::new_empty();
+    let (prefix, suffix) = inputs["crate_code"].split_once(CARET).expect("Missing caret.");
+    let content = format!("{prefix}{suffix}");
+    // Register the crate, so its files are mapped to its modules.
+    let db_ref = &mut db;
+    let crate_id = setup_test_crate(db_ref, &content);
+    let config = db_ref.crate_config(crate_id).cloned();
+    set_crate_config!(db_ref, crate_id, config);
+    let db = &db;
+    let crate_id = setup_test_crate(db, &content);
+    let file_id = db.module_main_file(ModuleId::CrateRoot(crate_id)).unwrap();
+
+    let Some(info) = db.expr_at_offset(file_id, TextOffset::from_str(prefix)) else {
+        return TestRunnerResult::success(OrderedHashMap::from([(
+            "expr".into(),
+            "<No expression at offset>".into(),
+        )]));
+    };
+    let expr = db.expr_semantic(info.function_id, info.expr_id);
+    let format_ty = |ty: Option<TypeId<'_>>| ty.map_or_else(|| "None".into(), |ty| ty.format(db));
+    TestRunnerResult::success(OrderedHashMap::from([
+        (
+            "expr".into(),
+            expr.stable_ptr().lookup(db).as_syntax_node().get_text_without_trivia(db).to_string(db),
+        ),
+        ("type".into(), info.ty.format(db)),
+        ("expected_type".into(), format_ty(info.expected_ty)),
+        (
+            "definition".into(),
+            info.definition
+                .map_or_else(|| "None".into(), |definition| format!("{:?}", definition.debug(db))),
+        ),
+    ]))
+}
@@ -1,4 +1,6 @@
-use cairo_lang_defs::ids::{ExternFunctionId, FreeFunctionId, ModuleId, ModuleItemId, TraitId};
+use cairo_lang_defs::ids::{
+    ExternFunctionId, FreeFunctionId, ImplDefId, ModuleId, ModuleItemId, TraitId,
+};
 use cairo_lang_filesystem::ids::SmolStrId;
 use salsa::Database;
 
@@ -43,6 +45,15 @@ impl<'a> ModuleHelper<'a> {
         };
         id
     }
+    /// Returns the id of an impl definition named `name` in the current module.
+    pub fn impl_def_id(&self, name: &'a str) -> ImplDefId<'a> {
+        let Ok(Some(ModuleItemId::Impl(id))) =
+            self.db.module_item_by_name(self.id, SmolStrId::from(self.db, name))
+        else {
+            panic!("`{name}` not found in `{}`.", self.id.full_path(self.db));
+        };
+        id
+    }
     /// Returns the id of a free function named `name` in the current module.
     pub fn free_function_id(&self, name: &'a str) -> FreeFunctionId<'a> {
         let Ok(Some(ModuleItemId::FreeFunction(id))) =
@@ -8,10 +8,13 @@ use cairo_lang_defs::plugin::{
 use cairo_lang_defs::plugin_utils::{
     not_legacy_macro_diagnostic, try_extract_unnamed_arg, unsupported_bracket_diagnostic,
 };
+use cairo_lang_diagnostics::DiagnosticsBuilder;
+use cairo_lang_filesystem::ids::{FileKind, FileLongId, SmolStrId, VirtualFile};
 use cairo_lang_filesystem::span::{TextSpan, TextWidth};
 use cairo_lang_parser::macro_helpers::AsLegacyInlineMacro;
+use cairo_lang_parser::parser::Parser;
 use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode, ast};
-use cairo_lang_utils::{OptionHelper, try_extract_matches};
+use cairo_lang_utils::{Intern, OptionHelper, try_extract_matches};
 use indoc::indoc;
 use num_bigint::{BigInt, Sign};
 use salsa::Database;
@@ -56,6 +59,7 @@ impl InlineMacroExprPlugin for WriteMacro {
             write!(f, "hello {world_ba}"); // `f` contains "hellohello worldhello world".
             let (x, y) = (1, 2);
             write!(f, "{x} + {y} = 3");  // `f` contains "hellohello worldhello world1 + 2 = 3".
+            write!(f, "{x + y:>3}"); // `f` contains "hellohello worldhello world1 + 2 = 3  3".
             ```
         "#}
             .to_string(),
@@ -100,7 +104,7 @@ impl InlineMacroExprPlugin for WritelnMacro {
             writeln!(f, "hello {}", world_ba); // `f` contains "hello\nhello world\n".
             writeln!(f, "hello {world_ba}"); // `f` contains "hello\nhello world\nhello world\n".
             let (x, y) = (1, 2);
-            writeln!(f, "{x}+{y}=3"); // `f` contains "hello\nhello world\nhello world\n1+2=3\n".
+            writeln!(f, "{x}+{y}={x + y:*<3}"); // `f` ends with "1+2=3**\n".
             ```
         "#}
             .to_string(),
@@ -278,7 +282,8 @@ impl<'db> FormattingInfo<'db> {
                     format_iter.next();
                     continue;
                 }
-                let argument_info = match extract_placeholder_argument(&mut format_iter) {
+                let argument_info = match extract_placeholder_argument(builder.db, &mut format_iter)
+                {
                     Ok(argument_info) => argument_info,
                     Err(error_message) => {
                         diagnostics.push(PluginDiagnostic::error_with_inner_span(
@@ -315,7 +320,7 @@ impl<'db> FormattingInfo<'db> {
                                 builder.db,
                                 arg,
                             ),
-                            argument_info.formatting_trait,
+                            &argument_info,
                         );
                     }
                     PlaceholderArgumentSource::Next => {
@@ -330,29 +335,35 @@ impl<'db> FormattingInfo<'db> {
                                     builder.db,
                                     &self.args[i],
                                 ),
-                                argument_info.formatting_trait,
+                                &argument_info,
                             );
                         } else {
                             missing_args += 1;
                         }
                     }
-                    PlaceholderArgumentSource::Named(argument) => {
+                    PlaceholderArgumentSource::Named(ref argument)
+                    | PlaceholderArgumentSource::Expression(ref argument) => {
                         let start = format_string_base
                             .add_width(TextWidth::from_str(&self.format_string[..(idx + 1)]));
-                        let origin =
-                            TextSpan::new_with_width(start, TextWidth::from_str(&argument));
+                        let origin = TextSpan::new_with_width(start, TextWidth::from_str(argument));
+                        let mapped = RewriteNode::Mapped {
+                            origin,
+                            node: RewriteNode::text(argument).into(),
+                        };
+                        // Expressions are wrapped in parentheses, so the snapshot applies to all of
+                        // them.
+                        let arg = match argument_info.source {
+                            PlaceholderArgumentSource::Expression(_) => {
+                                vec![RewriteNode::text("@("), mapped, RewriteNode::text(")")]
+                            }
+                            _ => vec![RewriteNode::text("@"), mapped],
+                        };
                         self.append_formatted_arg(
                             builder,
                             &mut ident_count,
                             &mut pending_chars,
-                            RewriteNode::new_modified(vec![
-                                RewriteNode::text("@"),
-                                RewriteNode::Mapped {
-                                    origin,
-                                    node: RewriteNode::text(&argument).into(),
-                                },
-                            ]),
-                            argument_info.formatting_trait,
+                            RewriteNode::new_modified(arg),
+                            &argument_info,
                         );
                     }
                 }
@@ -423,24 +434,59 @@ impl<'db> FormattingInfo<'db> {
 
     /// Appends a formatted argument to the formatter, flushing the pending bytes if necessary.
     /// This includes opening a new match, which is only closed at the end of the macro handling.
+    /// Padded arguments are first formatted into a temporary formatter, and then appended with
+    /// their padding. Without an explicit alignment, the default alignment of the formatting trait
+    /// for the argument is used, e.g. numbers are aligned to the right.
     fn append_formatted_arg(
         &self,
         builder: &mut PatchBuilder<'db>,
         ident_count: &mut usize,
         pending_chars: &mut String,
         arg: RewriteNode<'db>,
-        fmt_type: FormattingTrait,
+        argument_info: &PlaceholderArgumentInfo,
     ) {
+        let fmt_type = &argument_info.formatting_trait;
         self.flush_pending_chars(builder, pending_chars, *ident_count);
         self.add_indentation(builder, *ident_count);
+        let (arg, target) = if argument_info.padding.is_some() {
+            // The argument is bound to a variable, as it is also used for the default alignment.
+            builder.add_modified(RewriteNode::interpolate_patched(
+                "let __write_macro_arg__ = $arg$;\n",
+                &[("arg".to_string(), arg)].into(),
+            ));
+            self.add_indentation(builder, *ident_count);
+            builder.add_str(
+                "let mut __write_macro_padded__: core::fmt::Formatter = \
+                 core::traits::Default::default();\n",
+            );
+            self.add_indentation(builder, *ident_count);
+            (RewriteNode::text("__write_macro_arg__"), RewriteNode::text("__write_macro_padded__"))
+        } else {
+            (arg, self.formatter_arg_node.clone())
+        };
         builder.add_modified(RewriteNode::interpolate_patched(
             &format!("match core::fmt::{fmt_type}::fmt($arg$, ref $f$) {{\n"),
-            &[("arg".to_string(), arg), ("f".to_string(), self.formatter_arg_node.clone())].into(),
+            &[("arg".to_string(), arg), ("f".to_string(), target)].into(),
         ));
         *ident_count += 1;
         self.add_indentation(builder, *ident_count);
         builder.add_str("core::result::Result::Ok(_) => {\n");
         *ident_count += 1;
+        if let Some(Padding { fill, alignment, width }) = &argument_info.padding {
+            let alignment = match alignment {
+                Some(alignment) => format!("core::fmt::Alignment::{alignment}"),
+                None => format!("core::fmt::{fmt_type}::default_alignment(__write_macro_arg__)"),
+            };
+            self.add_indentation(builder, *ident_count);
+            builder.add_modified(RewriteNode::interpolate_patched(
+                &format!(
+                    "core::fmt::write_padded(ref $f$, @__write_macro_padded__.buffer, {:#x}, \
+                     {alignment}, {width});\n",
+                    *fill as u32,
+                ),
+                &[("f".to_string(), self.formatter_arg_node.clone())].into(),
+            ));
+        }
     }
 
     /// Flushes the pending bytes to the formatter.
@@ -471,6 +517,39 @@ struct PlaceholderArgumentInfo {
     source: PlaceholderArgumentSource,
     /// The formatting trait to use (e.g. `Display`, `Debug`).
     formatting_trait: FormattingTrait,
+    /// The padding to apply to the formatted argument, if a width was given.
+    padding: Option<Padding>,
+}
+
+/// The padding of a placeholder argument, e.g. `{:*^10}`.
+struct Padding {
+    /// The ASCII character to pad with.
+    fill: char,
+    /// Where to place the formatted argument within the padding, or `None` for the default
+    /// alignment of the formatting trait for the argument.
+    alignment: Option<Alignment>,
+    /// The minimal width of the formatted argument, in bytes. Bounded by the range of `usize` in
+    /// Cairo.
+    width: u32,
+}
+
+/// The alignment of a padded placeholder argument, matching `core::fmt::Alignment`.
+enum Alignment {
+    /// Got `<`.
+    Left,
+    /// Got `^`.
+    Center,
+    /// Got `>`.
+    Right,
+}
+impl fmt::Display for Alignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Alignment::Left => write!(f, "Left"),
+            Alignment::Center => write!(f, "Center"),
+            Alignment::Right => write!(f, "Right"),
+        }
+    }
 }
 
 /// The source of a placeholder argument.
@@ -481,6 +560,8 @@ enum PlaceholderArgumentSource {
     Next,
     /// The placeholder argument is given by a name.
     Named(String),
+    /// The placeholder argument is given by an inline expression, e.g. `{x + y}`.
+    Expression(String),
 }
 
 /// A formatting trait is a specific method for how to format placeholder arguments within a format
@@ -506,19 +587,28 @@ impl fmt::Display for FormattingTrait {
 /// Extracts a placeholder argument from a format string. On error, returns Err with a relevant
 /// error string.
 fn extract_placeholder_argument(
+    db: &dyn Database,
     format_iter: &mut std::iter::Peekable<std::iter::Enumerate<std::str::Chars<'_>>>,
 ) -> Result<PlaceholderArgumentInfo, &'static str> {
-    // The part before the ':' (if any), indicating the name of the parameter.
+    // The part before the ':' (if any), indicating the parameter name or expression.
     let mut parameter_name = String::new();
     // The part after the ':' (if any), indicating the formatting specification.
     let mut formatting_spec = String::new();
     let mut placeholder_terminated = false;
-    for (_, c) in format_iter.by_ref() {
-        if c == '}' {
+    // The nesting depth of brackets within an expression parameter.
+    let mut depth = 0_usize;
+    while let Some((_, c)) = format_iter.next() {
+        if depth == 0 && c == '}' {
             placeholder_terminated = true;
             break;
         }
-        if c == ':' {
+        if c == ':' && !parameter_name.is_empty() && matches!(format_iter.peek(), Some(&(_, ':'))) {
+            // A path separator within an expression, e.g. `{a::b}`.
+            parameter_name.push_str("::");
+            format_iter.next();
+            continue;
+        }
+        if depth == 0 && c == ':' {
             for (_, c) in format_iter.by_ref() {
                 if c == '}' {
                     placeholder_terminated = true;
@@ -528,7 +618,8 @@ fn extract_placeholder_argument(
                     return Err("Unexpected character in placeholder: the formatting \
                                 specification part (after the ':') cannot contain a ':'");
                 }
-                if c.is_ascii_graphic() {
+                // A space is only allowed as the fill character, which comes first.
+                if c.is_ascii_graphic() || (c == ' ' && formatting_spec.is_empty()) {
                     formatting_spec.push(c);
                 } else {
                     return Err("Unexpected character in placeholder: the formatting \
@@ -538,18 +629,90 @@ fn extract_placeholder_argument(
             }
             break;
         }
-        if c.is_ascii_alphanumeric() || c == '_' {
-            parameter_name.push(c);
-        } else {
-            return Err("Unexpected character in placeholder: parameter name can only contain \
-                        alphanumeric characters and '_'. You may be missing a ':'");
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ => {}
         }
+        parameter_name.push(c);
     }
     if !placeholder_terminated {
         return Err("Unterminated placeholder: no matching '}' for '{'");
     }
 
-    let fmt_type = match formatting_spec.as_str() {
+    let (formatting_trait, padding) = parse_formatting_spec(&formatting_spec)?;
+
+    let is_name = parameter_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    // Besides paths, only placeholders with whitespace or brackets are taken as expressions, so
+    // that a typo in a parameter name is still reported as such.
+    let is_expression = parameter_name.contains("::")
+        || parameter_name.contains(|c: char| c.is_whitespace() || "()[]{}".contains(c));
+    let source = if parameter_name.is_empty() {
+        PlaceholderArgumentSource::Next
+    } else if let Ok(position) = parameter_name.parse::<usize>() {
+        PlaceholderArgumentSource::Positional(position)
+    } else if is_name && parameter_name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err("Invalid parameter name");
+    } else if is_name {
+        PlaceholderArgumentSource::Named(parameter_name)
+    } else if parameter_name.trim().is_empty() {
+        return Err("Empty expression in placeholder");
+    } else if is_expression && is_valid_expression(db, &parameter_name) {
+        PlaceholderArgumentSource::Expression(parameter_name)
+    } else {
+        return Err("Unexpected character in placeholder: parameter name can only contain \
+                    alphanumeric characters and '_'. You may be missing a ':'");
+    };
+    Ok(PlaceholderArgumentInfo { source, formatting_trait, padding })
+}
+
+/// Returns whether the given text parses as a single expression, without errors.
+fn is_valid_expression(db: &dyn Database, text: &str) -> bool {
+    let file_id = FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: SmolStrId::from(db, "format_string_expression"),
+        content: SmolStrId::from(db, text),
+        code_mappings: [].into(),
+        kind: FileKind::Expr,
+        original_item_removed: false,
+    })
+    .intern(db);
+    let mut diagnostics = DiagnosticsBuilder::default();
+    Parser::parse_token_stream_expr(db, &mut diagnostics, file_id, None);
+    diagnostics.build().check_error_free().is_ok()
+}
+
+/// Parses the formatting specification of a placeholder (the part after the ':'), of the form
+/// `[[fill]align][width][type]`. On error, returns Err with a relevant error string.
+fn parse_formatting_spec(spec: &str) -> Result<(FormattingTrait, Option<Padding>), &'static str> {
+    let parse_alignment = |c: char| match c {
+        '<' => Some(Alignment::Left),
+        '^' => Some(Alignment::Center),
+        '>' => Some(Alignment::Right),
+        _ => None,
+    };
+    let mut chars = spec.chars();
+    let (fill, alignment, rest) = match (chars.next(), chars.next()) {
+        (Some(fill), Some(align)) if parse_alignment(align).is_some() => {
+            (fill, parse_alignment(align), chars.as_str())
+        }
+        (Some(align), _) if parse_alignment(align).is_some() => {
+            (' ', parse_alignment(align), &spec[1..])
+        }
+        _ => (' ', None, spec),
+    };
+    if fill == ' ' && spec.starts_with(' ') && alignment.is_none() {
+        return Err("Unexpected character in placeholder: the formatting specification part \
+                    (after the ':') can only contain graphic characters");
+    }
+    let width_len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let (width, type_spec) = rest.split_at(width_len);
+    if width.starts_with('0') {
+        return Err("Unsupported width: zero padding is not supported, use `0>` as the fill and \
+                    alignment instead");
+    }
+
+    let formatting_trait = match type_spec {
         "" => FormattingTrait::Display,
         "?" => FormattingTrait::Debug,
         "x" => FormattingTrait::LowerHex,
@@ -558,15 +721,16 @@ fn extract_placeholder_argument(
                         are supported");
         }
     };
-
-    let source = if parameter_name.is_empty() {
-        PlaceholderArgumentSource::Next
-    } else if let Ok(position) = parameter_name.parse::<usize>() {
-        PlaceholderArgumentSource::Positional(position)
-    } else if parameter_name.starts_with(|c: char| c.is_ascii_digit()) {
-        return Err("Invalid parameter name");
+    let padding = if width.is_empty() {
+        if alignment.is_some() {
+            return Err("Missing width: fill and alignment require a width");
+        }
+        None
     } else {
-        PlaceholderArgumentSource::Named(parameter_name)
+        let Ok(width) = width.parse() else {
+            return Err("Unsupported width: the width must fit in a `usize`");
+        };
+        Some(Padding { fill, alignment, width })
     };
-    Ok(PlaceholderArgumentInfo { source, formatting_trait: fmt_type })
+    Ok((formatting_trait, padding))
 }
@@ -4,15 +4,16 @@ use std::sync::Arc;
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_defs::ids::{
-    ConstantId, ExternFunctionId, GenericParamId, LanguageElementId, LookupItemId, ModuleItemId,
-    NamedLanguageElementId, TopLevelLanguageElementId, TraitConstantId, TraitId, VarId,
+    ConstantId, ExternFunctionId, GenericParamId, ImplDefId, LanguageElementId, LookupItemId,
+    MemberId, ModuleItemId, NamedLanguageElementId, TopLevelLanguageElementId, TraitConstantId,
+    TraitId, VarId,
 };
 use cairo_lang_diagnostics::{
     DiagnosticAdded, DiagnosticEntry, DiagnosticNote, Diagnostics, Maybe, MaybeAsRef,
     skip_diagnostic,
 };
 use cairo_lang_proc_macros::{DebugWithDb, HeapSize, SemanticObject};
-use cairo_lang_syntax::node::ast::ItemConstant;
+use cairo_lang_syntax::node::ast::{self, ItemConstant};
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
@@ -26,6 +27,7 @@ use num_traits::{ToPrimitive, Zero};
 use salsa::Database;
 use starknet_types_core::felt::{CAIRO_PRIME_BIGINT, Felt as Felt252};
 
+use super::cycle::CycleItemId;
 use super::functions::{GenericFunctionId, GenericFunctionWithBodyId};
 use super::imp::{ImplId, ImplLongId};
 use crate::corelib::{
@@ -49,10 +51,11 @@ use crate::resolve::{Resolver, ResolverData};
 use crate::substitution::{GenericSubstitution, SemanticRewriter};
 use crate::types::resolve_type;
 use crate::{
-    Arenas, ConcreteFunction, ConcreteTypeId, ConcreteVariant, Condition, Expr, ExprBlock,
-    ExprConstant, ExprFunctionCall, ExprFunctionCallArg, ExprId, ExprMemberAccess, ExprStructCtor,
-    FunctionId, GenericParam, LogicalOperator, Pattern, PatternId, SemanticDiagnostic, Statement,
-    TypeId, TypeLongId, semantic_object_for_id,
+    Arenas, ConcreteFunction, ConcreteStructId, ConcreteTypeId, ConcreteVariant, Condition, Expr,
+    ExprBlock, ExprConstant, ExprFunctionCall, ExprFunctionCallArg, ExprId, ExprMemberAccess,
+    ExprStructCtor, ExprVarMemberPath, FunctionId, GenericParam, LogicalOperator, Pattern,
+    PatternId, SemanticDiagnostic, Statement, StatementBreak, StatementReturn, TypeId, TypeLongId,
+    semantic_object_for_id,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, DebugWithDb)]
@@ -319,6 +322,7 @@ fn constant_semantic_data<'db>(
             lookup_item_id,
             None,
             &const_id,
+            CycleItemId::Constant(const_id),
         )
     } else {
         constant_semantic_data_helper(
@@ -345,6 +349,7 @@ fn constant_semantic_data_cycle<'db>(
         lookup_item_id,
         None,
         &const_id,
+        CycleItemId::Constant(const_id),
     )
 }
 
@@ -410,6 +415,7 @@ pub fn constant_semantic_data_cycle_helper<'db>(
     lookup_item_id: LookupItemId<'db>,
     parent_resolver_data: Option<Arc<ResolverData<'db>>>,
     element_id: &impl LanguageElementId<'db>,
+    cycle_item: CycleItemId<'db>,
 ) -> Maybe<ConstantData<'db>> {
     let module_id = element_id.parent_module(db);
     let mut diagnostics: SemanticDiagnostics<'_> = SemanticDiagnostics::new(module_id);
@@ -425,8 +431,8 @@ pub fn constant_semantic_data_cycle_helper<'db>(
 
     let resolver_data = Arc::new(resolver.data);
 
-    let diagnostic_added =
-        diagnostics.report(constant_ast.stable_ptr(db), SemanticDiagnosticKind::ConstCycle);
+    let diagnostic_added = diagnostics
+        .report(constant_ast.stable_ptr(db), SemanticDiagnosticKind::ConstCycle(Some(cycle_item)));
     Ok(ConstantData {
         constant: Err(diagnostic_added),
         const_value: ConstValue::Missing(diagnostic_added).intern(db),
@@ -445,37 +451,24 @@ pub fn validate_const_expr<'db>(ctx: &mut ComputationContext<'db, '_>, expr_id:
         vars: Default::default(),
         generic_substitution: Default::default(),
         depth: 0,
+        fuel: MAX_CONST_EVAL_FUEL,
+        pending_flow: None,
         diagnostics: ctx.diagnostics,
     };
     eval_ctx.validate(expr_id);
 }
 
 /// Resolves the given const expression and evaluates its value.
-pub fn resolve_const_expr_and_evaluate<'db, 'mt>(
+pub fn resolve_const_expr_and_evaluate<'db>(
     db: &'db dyn Database,
-    ctx: &'mt mut ComputationContext<'db, '_>,
+    ctx: &mut ComputationContext<'db, '_>,
     value: &ExprAndId<'db>,
     const_stable_ptr: SyntaxStablePtrId<'db>,
     target_type: TypeId<'db>,
     finalize: bool,
 ) -> ConstValueId<'db> {
     let prev_err_count = ctx.diagnostics.error_count;
-    let mut_ref = &mut ctx.resolver;
-    let mut inference: crate::expr::inference::Inference<'db, '_> = mut_ref.inference();
-    if let Err(err_set) = inference.conform_ty(value.ty(), target_type) {
-        inference.report_on_pending_error(err_set, ctx.diagnostics, const_stable_ptr);
-    }
-
-    if finalize {
-        // Check fully resolved.
-        inference.finalize(ctx.diagnostics, const_stable_ptr);
-    } else if let Err(err_set) = inference.solve() {
-        inference.report_on_pending_error(err_set, ctx.diagnostics, const_stable_ptr);
-    }
-
-    // TODO(orizi): Consider moving this to be called only upon creating const values, other callees
-    // don't necessarily need it.
-    ctx.apply_inference_rewriter_to_exprs();
+    resolve_const_expr(ctx, value, const_stable_ptr, target_type, finalize);
 
     match &value.expr {
         Expr::Constant(ExprConstant { const_value_id, .. }) => *const_value_id,
@@ -492,6 +485,8 @@ pub fn resolve_const_expr_and_evaluate<'db, 'mt>(
                 vars: Default::default(),
                 generic_substitution: Default::default(),
                 depth: 0,
+                fuel: MAX_CONST_EVAL_FUEL,
+                pending_flow: None,
                 diagnostics: ctx.diagnostics,
             };
             eval_ctx.validate(value.id);
@@ -504,6 +499,88 @@ pub fn resolve_const_expr_and_evaluate<'db, 'mt>(
     }
 }
 
+/// Resolves the expression of a constant whose evaluation is deferred to the contexts substituting
+/// its generic parameters and `Self` impl - e.g. the default value of a trait constant, which is
+/// evaluated for each impl not overriding it - and validates that it can be used as a constant.
+/// Returns the expression if it is valid, to be evaluated by
+/// [evaluate_constant_with_substitution].
+pub fn resolve_const_expr_and_validate<'db>(
+    ctx: &mut ComputationContext<'db, '_>,
+    value: &ExprAndId<'db>,
+    const_stable_ptr: SyntaxStablePtrId<'db>,
+    target_type: TypeId<'db>,
+) -> Maybe<ExprId> {
+    let prev_err_count = ctx.diagnostics.error_count;
+    resolve_const_expr(ctx, value, const_stable_ptr, target_type, true);
+    validate_const_expr(ctx, value.id);
+    if ctx.diagnostics.error_count > prev_err_count { Err(skip_diagnostic()) } else { Ok(value.id) }
+}
+
+/// Evaluates a constant validated by [resolve_const_expr_and_validate], in the context of the
+/// given substitution of its generic parameters and `Self` impl.
+pub fn evaluate_constant_with_substitution<'db>(
+    db: &'db dyn Database,
+    constant: &Constant<'db>,
+    substitution: GenericSubstitution<'db>,
+    diagnostics: &mut SemanticDiagnostics<'db>,
+) -> ConstValueId<'db> {
+    let info = db.const_calc_info();
+    let value = ConstantEvaluateContext {
+        db,
+        info: info.as_ref(),
+        arenas: &constant.arenas,
+        vars: Default::default(),
+        generic_substitution: substitution.clone(),
+        depth: 0,
+        fuel: MAX_CONST_EVAL_FUEL,
+        pending_flow: None,
+        diagnostics,
+    }
+    .evaluate(constant.value);
+    // The types of the evaluated values are not substituted by the evaluation.
+    substitution
+        .substitute(db, value)
+        .unwrap_or_else(|diag_added| ConstValue::Missing(diag_added).intern(db))
+}
+
+/// Conforms the type of the expression of a constant to its target type and solves its inference.
+fn resolve_const_expr<'db>(
+    ctx: &mut ComputationContext<'db, '_>,
+    value: &ExprAndId<'db>,
+    const_stable_ptr: SyntaxStablePtrId<'db>,
+    target_type: TypeId<'db>,
+    finalize: bool,
+) {
+    let mut_ref = &mut ctx.resolver;
+    let mut inference: crate::expr::inference::Inference<'db, '_> = mut_ref.inference();
+    if let Err(err_set) = inference.conform_ty(value.ty(), target_type) {
+        inference.report_on_pending_error(err_set, ctx.diagnostics, const_stable_ptr);
+    }
+
+    if finalize {
+        // Check fully resolved.
+        inference.finalize(ctx.diagnostics, const_stable_ptr);
+    } else if let Err(err_set) = inference.solve() {
+        inference.report_on_pending_error(err_set, ctx.diagnostics, const_stable_ptr);
+    }
+
+    // TODO(orizi): Consider moving this to be called only upon creating const values, other callees
+    // don't necessarily need it.
+    ctx.apply_inference_rewriter();
+}
+
+/// The maximal number of evaluation steps - loop iterations and function calls - of the calculation
+/// of a single constant.
+const MAX_CONST_EVAL_FUEL: usize = 100_000;
+
+/// A change of the control flow of a constant calculation, due to a `break`, `continue` or `return`
+/// statement, skipping the evaluation of the rest of the enclosing loop body or function body.
+enum PendingFlow<'a> {
+    Break(ConstValueId<'a>),
+    Continue,
+    Return(ConstValueId<'a>),
+}
+
 /// A context for evaluating constant expressions.
 struct ConstantEvaluateContext<'a, 'r, 'mt> {
     db: &'a dyn Database,
@@ -512,6 +589,10 @@ struct ConstantEvaluateContext<'a, 'r, 'mt> {
     vars: OrderedHashMap<VarId<'a>, ConstValueId<'a>>,
     generic_substitution: GenericSubstitution<'a>,
     depth: usize,
+    /// The number of evaluation steps left for the calculation, shared with the contexts of the
+    /// called functions.
+    fuel: usize,
+    pending_flow: Option<PendingFlow<'a>>,
     diagnostics: &'mt mut SemanticDiagnostics<'a>,
 }
 impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
@@ -519,7 +600,7 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
     fn validate(&mut self, expr_id: ExprId) {
         match &self.arenas.exprs[expr_id] {
             Expr::Var(_) | Expr::Constant(_) | Expr::Missing(_) => {}
-            Expr::Block(ExprBlock { statements, tail: Some(inner), .. }) => {
+            Expr::Block(ExprBlock { statements, tail, .. }) => {
                 for statement_id in statements {
                     match &self.arenas.statements[*statement_id] {
                         Statement::Let(statement) => {
@@ -528,6 +609,13 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
                         Statement::Expr(expr) => {
                             self.validate(expr.expr);
                         }
+                        Statement::Break(StatementBreak { expr_option, .. })
+                        | Statement::Return(StatementReturn { expr_option, .. }) => {
+                            if let Some(expr) = expr_option {
+                                self.validate(*expr);
+                            }
+                        }
+                        Statement::Continue(_) => {}
                         other => {
                             self.diagnostics.report(
                                 other.stable_ptr(),
@@ -536,18 +624,23 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
                         }
                     }
                 }
-                self.validate(*inner);
+                if let Some(inner) = tail {
+                    self.validate(*inner);
+                }
             }
+            Expr::Loop(expr) => self.validate(expr.body),
+            Expr::While(expr) => {
+                self.validate(match &expr.condition {
+                    Condition::BoolExpr(id) | Condition::Let(id, _) => *id,
+                });
+                self.validate(expr.body);
+            }
+            Expr::Assignment(expr) => self.validate(expr.rhs),
             Expr::FunctionCall(expr) => {
                 for arg in &expr.args {
                     match arg {
                         ExprFunctionCallArg::Value(arg) => self.validate(*arg),
-                        ExprFunctionCallArg::Reference(var) => {
-                            self.diagnostics.report(
-                                var.stable_ptr(),
-                                SemanticDiagnosticKind::UnsupportedConstant,
-                            );
-                        }
+                        ExprFunctionCallArg::Reference(_) => {}
                         ExprFunctionCallArg::TempReference(expr_id) => {
                             self.diagnostics.report(
                                 self.arenas.exprs[*expr_id].stable_ptr(),
@@ -602,6 +695,9 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
             Expr::Match(expr) => {
                 self.validate(expr.matched_expr);
                 for arm in &expr.arms {
+                    if let Some(guard) = arm.guard {
+                        self.validate(guard);
+                    }
                     self.validate(arm.expression);
                 }
             }
@@ -652,6 +748,9 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
             return false;
         };
         let impl_def = imp.concrete_impl_id.impl_def_id(db);
+        if impl_def == self.fixed_size_array_index_view {
+            return true;
+        }
         if impl_def.parent_module(db).owning_crate(db) != db.core_crate() {
             return false;
         }
@@ -677,7 +776,7 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
                 .generic_substitution
                 .substitute(self.db, expr.const_value_id)
                 .unwrap_or_else(to_missing),
-            Expr::Block(ExprBlock { statements, tail: Some(inner), .. }) => {
+            Expr::Block(ExprBlock { statements, tail, .. }) => {
                 for statement_id in statements {
                     match &self.arenas.statements[*statement_id] {
                         Statement::Let(statement) => {
@@ -687,6 +786,17 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
                         Statement::Expr(expr) => {
                             self.evaluate(expr.expr);
                         }
+                        Statement::Break(statement) => {
+                            let value = self.evaluate_optional(statement.expr_option);
+                            self.pending_flow = Some(PendingFlow::Break(value));
+                        }
+                        Statement::Continue(_) => {
+                            self.pending_flow = Some(PendingFlow::Continue);
+                        }
+                        Statement::Return(statement) => {
+                            let value = self.evaluate_optional(statement.expr_option);
+                            self.pending_flow = Some(PendingFlow::Return(value));
+                        }
                         other => {
                             self.diagnostics.report(
                                 other.stable_ptr(),
@@ -694,8 +804,33 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
                             );
                         }
                     }
+                    if self.pending_flow.is_some() {
+                        return to_missing(skip_diagnostic());
+                    }
+                }
+                self.evaluate_optional(*tail)
+            }
+            Expr::Loop(expr) => loop {
+                if let Some(value) = self.evaluate_loop_iteration(expr.stable_ptr, expr.body) {
+                    return value;
+                }
+            },
+            Expr::While(expr) => loop {
+                match self.evaluate_condition(&expr.condition) {
+                    Some(true) => {}
+                    Some(false) => return self.unit_const,
+                    None => return to_missing(skip_diagnostic()),
+                }
+                if let Some(value) = self.evaluate_loop_iteration(expr.stable_ptr, expr.body) {
+                    return value;
+                }
+            },
+            Expr::Assignment(expr) => {
+                let value = self.evaluate(expr.rhs);
+                match self.assign(&expr.ref_arg, value) {
+                    Ok(()) => self.unit_const,
+                    Err(diag_added) => to_missing(diag_added),
                 }
-                self.evaluate(*inner)
             }
             Expr::FunctionCall(expr) => self.evaluate_function_call(expr),
             Expr::Literal(expr) => ConstValueId::from_int(db, expr.ty, &expr.value),
@@ -776,20 +911,26 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
                 let ConstValue::Enum(variant, value) = value.long(db) else {
                     return to_missing(skip_diagnostic());
                 };
-                for arm in &expr.arms {
+                'arms: for arm in &expr.arms {
                     for pattern_id in &arm.patterns {
                         let pattern = &self.arenas.patterns[*pattern_id];
-                        if matches!(pattern, Pattern::Otherwise(_)) {
-                            return self.evaluate(arm.expression);
-                        }
-                        let Pattern::EnumVariant(pattern) = pattern else {
-                            continue;
-                        };
-                        if pattern.variant.idx != variant.idx {
-                            continue;
+                        if !matches!(pattern, Pattern::Otherwise(_)) {
+                            let Pattern::EnumVariant(pattern) = pattern else {
+                                continue;
+                            };
+                            if pattern.variant.idx != variant.idx {
+                                continue;
+                            }
+                            if let Some(inner_pattern) = pattern.inner_pattern {
+                                self.destructure_pattern(inner_pattern, *value);
+                            }
                         }
-                        if let Some(inner_pattern) = pattern.inner_pattern {
-                            self.destructure_pattern(inner_pattern, *value);
+                        if let Some(guard) = arm.guard {
+                            match self.evaluate_condition(&Condition::BoolExpr(guard)) {
+                                Some(true) => {}
+                                Some(false) => continue 'arms,
+                                None => return to_missing(skip_diagnostic()),
+                            }
                         }
                         return self.evaluate(arm.expression);
                     }
@@ -804,44 +945,13 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
             Expr::If(expr) => {
                 let mut if_condition: bool = true;
                 for condition in &expr.conditions {
-                    match condition {
-                        crate::Condition::BoolExpr(id) => {
-                            let condition = self.evaluate(*id);
-                            let ConstValue::Enum(variant, _) = condition.long(db) else {
-                                return to_missing(skip_diagnostic());
-                            };
-                            if *variant != true_variant(self.db) {
-                                if_condition = false;
-                                break;
-                            }
-                        }
-                        crate::Condition::Let(id, patterns) => {
-                            let value = self.evaluate(*id);
-                            let ConstValue::Enum(variant, value) = value.long(db) else {
-                                return to_missing(skip_diagnostic());
-                            };
-                            let mut found_pattern = false;
-                            for pattern_id in patterns {
-                                let Pattern::EnumVariant(pattern) =
-                                    &self.arenas.patterns[*pattern_id]
-                                else {
-                                    continue;
-                                };
-                                if pattern.variant != *variant {
-                                    // Continue to the next option in the `|` list.
-                                    continue;
-                                }
-                                if let Some(inner_pattern) = pattern.inner_pattern {
-                                    self.destructure_pattern(inner_pattern, *value);
-                                }
-                                found_pattern = true;
-                                break;
-                            }
-                            if !found_pattern {
-                                if_condition = false;
-                                break;
-                            }
+                    match self.evaluate_condition(condition) {
+                        Some(true) => {}
+                        Some(false) => {
+                            if_condition = false;
+                            break;
                         }
+                        None => return to_missing(skip_diagnostic()),
                     }
                 }
 
@@ -857,6 +967,93 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
         }
     }
 
+    /// Evaluates the given optional expression, defaulting to the unit value.
+    fn evaluate_optional(&mut self, expr_id: Option<ExprId>) -> ConstValueId<'a> {
+        match expr_id {
+            Some(expr_id) => self.evaluate(expr_id),
+            None => self.unit_const,
+        }
+    }
+
+    /// Evaluates a condition of an `if` or a `while`, binding the variables of its pattern if
+    /// matched. Returns `None` if the condition could not be evaluated.
+    fn evaluate_condition(&mut self, condition: &Condition) -> Option<bool> {
+        let db = self.db;
+        match condition {
+            Condition::BoolExpr(id) => {
+                let ConstValue::Enum(variant, _) = self.evaluate(*id).long(db) else {
+                    return None;
+                };
+                Some(*variant == true_variant(db))
+            }
+            Condition::Let(id, patterns) => {
+                let ConstValue::Enum(variant, value) = self.evaluate(*id).long(db) else {
+                    return None;
+                };
+                for pattern_id in patterns {
+                    let Pattern::EnumVariant(pattern) = &self.arenas.patterns[*pattern_id] else {
+                        continue;
+                    };
+                    if pattern.variant != *variant {
+                        // Continue to the next option in the `|` list.
+                        continue;
+                    }
+                    if let Some(inner_pattern) = pattern.inner_pattern {
+                        self.destructure_pattern(inner_pattern, *value);
+                    }
+                    return Some(true);
+                }
+                Some(false)
+            }
+        }
+    }
+
+    /// Evaluates a single iteration of a loop. Returns the value of the loop if it is done - due to
+    /// a `break`, a `return`, or a failure of the calculation.
+    fn evaluate_loop_iteration(
+        &mut self,
+        stable_ptr: ast::ExprPtr<'a>,
+        body: ExprId,
+    ) -> Option<ConstValueId<'a>> {
+        let db = self.db;
+        if let Err(diag_added) = self.consume_fuel(stable_ptr.untyped()) {
+            return Some(ConstValue::Missing(diag_added).intern(db));
+        }
+        let prev_err_count = self.diagnostics.error_count;
+        self.evaluate(body);
+        match self.pending_flow.take() {
+            Some(PendingFlow::Break(value)) => Some(value),
+            Some(flow @ PendingFlow::Return(_)) => {
+                self.pending_flow = Some(flow);
+                Some(ConstValue::Missing(skip_diagnostic()).intern(db))
+            }
+            // Stopping on failures, as the exit condition of the loop may never be reached.
+            _ if self.diagnostics.error_count > prev_err_count => {
+                Some(ConstValue::Missing(skip_diagnostic()).intern(db))
+            }
+            None | Some(PendingFlow::Continue) => None,
+        }
+    }
+
+    /// Consumes a single evaluation step. Reports a diagnostic at the given location if the
+    /// calculation runs out of steps.
+    fn consume_fuel(&mut self, stable_ptr: SyntaxStablePtrId<'a>) -> Maybe<()> {
+        match self.fuel {
+            // Already reported when the fuel ran out.
+            0 => Err(skip_diagnostic()),
+            1 => {
+                self.fuel = 0;
+                Err(self
+                    .diagnostics
+                    .report(stable_ptr, SemanticDiagnosticKind::ConstantCalculationFuelExhausted))
+            }
+            _ => {
+                self.fuel -= 1;
+                Ok(())
+            }
+        }
+    }
+
     /// Attempts to evaluate constants from a const function call.
     fn evaluate_function_call(&mut self, expr: &ExprFunctionCall<'a>) -> ConstValueId<'a> {
         let db = self.db;
@@ -864,9 +1061,17 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
         let args = expr
             .args
             .iter()
-            .filter_map(|arg| try_extract_matches!(arg, ExprFunctionCallArg::Value))
-            .map(|arg| self.evaluate(*arg))
+            .filter_map(|arg| match arg {
+                ExprFunctionCallArg::Value(arg) => Some(self.evaluate(*arg)),
+                ExprFunctionCallArg::Reference(ref_arg) => {
+                    Some(self.evaluate_member_path(ref_arg).unwrap_or_else(to_missing))
+                }
+                ExprFunctionCallArg::TempReference(_) => None,
+            })
             .collect_vec();
+        if self.pending_flow.is_some() {
+            return to_missing(skip_diagnostic());
+        }
         if expr.function == self.panic_with_felt252 {
             return to_missing(self.diagnostics.report(
                 expr.stable_ptr.untyped(),
@@ -896,6 +1101,22 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
         } else if imp.function == self.not_fn {
             return bool_value(args[0] == self.false_const);
         }
+        if let ImplLongId::Concrete(concrete_impl) = imp.impl_id.long(db)
+            && concrete_impl.impl_def_id(db) == self.fixed_size_array_index_view
+        {
+            return self.evaluate_fixed_size_array_index(&args, expr);
+        }
+        // Compound assignments (e.g. `x += y`) are evaluated using their operator, with the result
+        // assigned into their `ref` argument.
+        let assign_op = [
+            (self.add_assign_fn, self.add_fn),
+            (self.sub_assign_fn, self.sub_fn),
+            (self.mul_assign_fn, self.mul_fn),
+            (self.div_assign_fn, self.div_fn),
+            (self.rem_assign_fn, self.rem_fn),
+        ]
+        .into_iter()
+        .find_map(|(assign_fn, op_fn)| (assign_fn == imp.function).then_some(op_fn));
 
         let args = match args
             .into_iter()
@@ -907,7 +1128,11 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
             // the function, or the arg itself couldn't have been calculated.
             None => return to_missing(skip_diagnostic()),
         };
-        let value = match imp.function {
+        let (function, ty) = match assign_op {
+            Some(op_fn) => (op_fn, args[0].ty),
+            None => (imp.function, expr.ty),
+        };
+        let value = match function {
             id if id == self.neg_fn => -&args[0].v,
             id if id == self.add_fn => &args[0].v + &args[1].v,
             id if id == self.sub_fn => &args[0].v - &args[1].v,
@@ -943,15 +1168,51 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
                 unreachable!("Unexpected function call in constant lowering: {:?}", expr)
             }
         };
-        if expr.ty == self.felt252 {
-            ConstValue::Int(canonical_felt252(&value), expr.ty).intern(db)
-        } else if let Err(err) = validate_literal(db, expr.ty, &value) {
+        let value = if ty == self.felt252 {
+            ConstValue::Int(canonical_felt252(&value), ty).intern(db)
+        } else if let Err(err) = validate_literal(db, ty, &value) {
             to_missing(
                 self.diagnostics
                     .report(expr.stable_ptr.untyped(), SemanticDiagnosticKind::LiteralError(err)),
             )
         } else {
-            ConstValueId::from_int(db, expr.ty, &value)
+            ConstValueId::from_int(db, ty, &value)
+        };
+        if assign_op.is_none() {
+            return value;
+        }
+        let Some(ExprFunctionCallArg::Reference(ref_arg)) = expr.args.first() else {
+            return to_missing(skip_diagnostic());
+        };
+        match self.assign(ref_arg, value) {
+            Ok(()) => self.unit_const,
+            Err(diag_added) => to_missing(diag_added),
+        }
+    }
+
+    /// Evaluates indexing into a fixed-size array.
+    fn evaluate_fixed_size_array_index(
+        &mut self,
+        args: &[ConstValueId<'a>],
+        expr: &ExprFunctionCall<'a>,
+    ) -> ConstValueId<'a> {
+        let db = self.db;
+        let [array, index] = args else {
+            return ConstValue::Missing(skip_diagnostic()).intern(db);
+        };
+        let (ConstValue::Struct(items, _), Some(index)) =
+            (array.long(db), index.long(db).to_int().and_then(|index| index.to_usize()))
+        else {
+            // A diagnostic should have been reported for the arguments.
+            return ConstValue::Missing(skip_diagnostic()).intern(db);
+        };
+        match items.get(index) {
+            Some(item) => *item,
+            None => ConstValue::Missing(self.diagnostics.report(
+                expr.stable_ptr,
+                SemanticDiagnosticKind::ConstantIndexOutOfBounds { index, size: items.len() },
+            ))
+            .intern(db),
         }
     }
 
@@ -1077,7 +1338,10 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
         let signature = db.function_with_body_signature(concrete_body_id).ok()?;
         require(signature.is_const)?;
         let generic_substitution = body_id.substitution(db).ok()?;
-        let body = db.function_body(concrete_body_id).ok()?;
+        let body = match db.function_body(concrete_body_id) {
+            Ok(body) => body,
+            Err(diag_added) => return Some(ConstValue::Missing(diag_added).intern(db)),
+        };
         const MAX_CONST_EVAL_DEPTH: usize = 100;
         if self.depth > MAX_CONST_EVAL_DEPTH {
             return Some(
@@ -1088,6 +1352,9 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
                 .intern(db),
             );
         }
+        if let Err(diag_added) = self.consume_fuel(expr.stable_ptr.untyped()) {
+            return Some(ConstValue::Missing(diag_added).intern(db));
+        }
         let mut diagnostics = SemanticDiagnostics::new(concrete_body_id.parent_module(db));
         let mut inner = ConstantEvaluateContext {
             db,
@@ -1101,9 +1368,22 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
                 .collect(),
             generic_substitution,
             depth: self.depth + 1,
+            fuel: self.fuel,
+            pending_flow: None,
             diagnostics: &mut diagnostics,
         };
-        let value = inner.evaluate(body.body_expr);
+        let mut value = inner.evaluate(body.body_expr);
+        if let Some(PendingFlow::Return(return_value)) = inner.pending_flow.take() {
+            value = return_value;
+        }
+        // The final values of the `ref` parameters, to be assigned back into their arguments.
+        let ref_values = zip(&signature.params, &expr.args)
+            .filter_map(|(param, arg)| {
+                let ExprFunctionCallArg::Reference(ref_arg) = arg else { return None };
+                Some((ref_arg, *inner.vars.get(&VarId::Param(param.id))?))
+            })
+            .collect_vec();
+        self.fuel = inner.fuel;
         for diagnostic in diagnostics.build().get_all() {
             let location = diagnostic.location(db);
             let (inner_diag, mut notes) = match diagnostic.kind {
@@ -1128,6 +1408,11 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
                 SemanticDiagnosticKind::InnerFailedConstantCalculation(inner_diag, notes),
             );
         }
+        for (ref_arg, ref_value) in ref_values {
+            if let Err(diag_added) = self.assign(ref_arg, ref_value) {
+                return Some(ConstValue::Missing(diag_added).intern(db));
+            }
+        }
         Some(value)
     }
 
@@ -1138,13 +1423,70 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
             // A semantic diagnostic should have been reported.
             return Err(skip_diagnostic());
         };
-        let members = self.db.concrete_struct_members(expr.concrete_struct_id)?;
-        let Some(member_idx) = members.iter().position(|(_, member)| member.id == expr.member)
-        else {
-            // A semantic diagnostic should have been reported.
-            return Err(skip_diagnostic());
-        };
-        Ok(values[member_idx])
+        Ok(values[self.member_index(expr.concrete_struct_id, expr.member)?])
+    }
+
+    /// Returns the index of the given member in the members of the given struct.
+    fn member_index(
+        &self,
+        concrete_struct_id: ConcreteStructId<'a>,
+        member_id: MemberId<'a>,
+    ) -> Maybe<usize> {
+        let members = self.db.concrete_struct_members(concrete_struct_id)?;
+        // A semantic diagnostic should have been reported if the member is missing.
+        members.values().position(|member| member.id == member_id).ok_or_else(skip_diagnostic)
+    }
+
+    /// Returns the current value of a variable, or of a member of it.
+    fn evaluate_member_path(
+        &mut self,
+        member_path: &ExprVarMemberPath<'a>,
+    ) -> Maybe<ConstValueId<'a>> {
+        match member_path {
+            ExprVarMemberPath::Var(expr) => self.vars.get(&expr.var).copied().ok_or_else(|| {
+                self.diagnostics
+                    .report(expr.stable_ptr, SemanticDiagnosticKind::UnsupportedConstant)
+            }),
+            ExprVarMemberPath::Member { parent, member_id, concrete_struct_id, .. } => {
+                let ConstValue::Struct(values, _) =
+                    self.evaluate_member_path(parent)?.long(self.db)
+                else {
+                    // A semantic diagnostic should have been reported.
+                    return Err(skip_diagnostic());
+                };
+                Ok(values[self.member_index(*concrete_struct_id, *member_id)?])
+            }
+        }
+    }
+
+    /// Assigns a value into a variable, or into a member of it.
+    fn assign(
+        &mut self,
+        member_path: &ExprVarMemberPath<'a>,
+        value: ConstValueId<'a>,
+    ) -> Maybe<()> {
+        match member_path {
+            ExprVarMemberPath::Var(expr) => {
+                let Some(var_value) = self.vars.get_mut(&expr.var) else {
+                    return Err(self
+                        .diagnostics
+                        .report(expr.stable_ptr, SemanticDiagnosticKind::UnsupportedConstant));
+                };
+                *var_value = value;
+                Ok(())
+            }
+            ExprVarMemberPath::Member { parent, member_id, concrete_struct_id, .. } => {
+                let ConstValue::Struct(values, ty) =
+                    self.evaluate_member_path(parent)?.long(self.db)
+                else {
+                    // A semantic diagnostic should have been reported.
+                    return Err(skip_diagnostic());
+                };
+                let mut values = values.clone();
+                values[self.member_index(*concrete_struct_id, *member_id)?] = value;
+                self.assign(parent, ConstValue::Struct(values, *ty).intern(self.db))
+            }
+        }
     }
 
     /// Destructures the pattern into the const value of the variables in scope.
@@ -1153,6 +1495,7 @@ impl<'a, 'r, 'mt> ConstantEvaluateContext<'a, 'r, 'mt> {
         let db = self.db;
         match pattern {
             Pattern::Literal(_)
+            | Pattern::Range(_)
             | Pattern::StringLiteral(_)
             | Pattern::Otherwise(_)
             | Pattern::Missing(_) => {}
@@ -1277,6 +1620,8 @@ pub struct ConstCalcInfo<'db> {
     pub nz_fns: UnorderedHashSet<ExternFunctionId<'db>>,
     /// The range of values of a numeric type.
     pub type_value_ranges: UnorderedHashMap<TypeId<'db>, TypeRange>,
+    /// The impl of indexing into fixed-size arrays.
+    fixed_size_array_index_view: ImplDefId<'db>,
 
     core_info: Arc<CoreInfo<'db>>,
 }
@@ -1317,6 +1662,11 @@ impl<'db> ConstCalcInfo<'db> {
                 core_info.partialeq_trt,
                 core_info.partialord_trt,
                 core_info.not_trt,
+                core_info.add_assign_trt,
+                core_info.sub_assign_trt,
+                core_info.mul_assign_trt,
+                core_info.div_assign_trt,
+                core_info.rem_assign_trt,
             ]),
             true_const: ConstValue::Enum(true_variant(db), unit_const).intern(db),
             false_const: ConstValue::Enum(false_variant(db), unit_const).intern(db),
@@ -1383,6 +1733,9 @@ impl<'db> ConstCalcInfo<'db> {
                 (class_hash_ty, TypeRange::new(BigInt::ZERO, BigInt::from(1) << 251)),
                 (contract_address_ty, TypeRange::new(BigInt::ZERO, BigInt::from(1) << 251)),
             ]),
+            fixed_size_array_index_view: core
+                .submodule("fixed_size_array")
+                .impl_def_id("FixedSizeArrayIndexView"),
             core_info,
         }
     }
@@ -0,0 +1,360 @@
+//! Description of the items of query cycles, for reporting them in cycle diagnostics.
+//!
+//! Salsa does not pass the participants of a cycle to the cycle handler of a query, and the values
+//! of all the participants are replaced by their cycle fallbacks. The cycle is therefore described
+//! lazily, when its diagnostic is formatted and the cycle is done: the cycle is found by walking
+//! the dependencies of the items, from the item of the diagnostic until reaching it again, where
+//! the dependencies of an item are the items recorded in the resolved items of its resolver.
+//! The walk only follows the items that may be part of a query cycle. The cycle is unknown if it
+//! goes through other items.
+
+use std::collections::VecDeque;
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::diagnostic_utils::StableLocation;
+use cairo_lang_defs::ids::{
+    ConstantId, FreeFunctionId, ImplAliasId, ImplConstantDefId, ImplDefId, ImplFunctionId,
+    ImplImplDefId, ImplItemId, ImplTypeDefId, LanguageElementId, LookupItemId, ModuleItemId,
+    ModuleTypeAliasId, NamedLanguageElementId, TopLevelLanguageElementId, TraitItemId, UseId,
+};
+use cairo_lang_diagnostics::{DiagnosticNote, Maybe};
+use cairo_lang_filesystem::ids::Tracked;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, Terminal, TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::try_extract_matches;
+use itertools::chain;
+use salsa::Database;
+
+use super::functions::GenericFunctionId;
+use super::imp::{
+    ImplId, ImplLongId, ImplSemantic, impl_constant_by_trait_constant,
+    impl_function_by_trait_function, impl_impl_by_trait_impl,
+};
+use crate::diagnostic::{NotFoundItemType, SemanticDiagnostics};
+use crate::expr::inference::InferenceId;
+use crate::resolve::{
+    ResolutionContext, ResolvedConcreteItem, ResolvedGenericItem, ResolvedItems, Resolver,
+    ResolverData,
+};
+
+/// An item which may be part of a query cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, salsa::Update)]
+pub enum CycleItemId<'db> {
+    Constant(ConstantId<'db>),
+    TypeAlias(ModuleTypeAliasId<'db>),
+    ImplAlias(ImplAliasId<'db>),
+    Use(UseId<'db>),
+    FreeFunction(FreeFunctionId<'db>),
+    Impl(ImplDefId<'db>),
+    ImplType(ImplTypeDefId<'db>),
+    ImplConstant(ImplConstantDefId<'db>),
+    ImplImpl(ImplImplDefId<'db>),
+    ImplFunction(ImplFunctionId<'db>),
+}
+impl<'db> CycleItemId<'db> {
+    /// Returns the full path of the item.
+    fn full_path(self, db: &'db dyn Database) -> String {
+        match self {
+            CycleItemId::Constant(id) => id.full_path(db),
+            CycleItemId::TypeAlias(id) => id.full_path(db),
+            CycleItemId::ImplAlias(id) => id.full_path(db),
+            CycleItemId::Use(id) => id.full_path(db),
+            CycleItemId::FreeFunction(id) => id.full_path(db),
+            CycleItemId::Impl(id) => id.full_path(db),
+            CycleItemId::ImplType(id) => id.full_path(db),
+            CycleItemId::ImplConstant(id) => id.full_path(db),
+            CycleItemId::ImplImpl(id) => id.full_path(db),
+            CycleItemId::ImplFunction(id) => id.full_path(db),
+        }
+    }
+
+    /// Returns the location of the name of the item.
+    fn name_location(self, db: &'db dyn Database) -> StableLocation<'db> {
+        let module_item = match self {
+            CycleItemId::Constant(id) => ModuleItemId::Constant(id),
+            CycleItemId::TypeAlias(id) => ModuleItemId::TypeAlias(id),
+            CycleItemId::ImplAlias(id) => ModuleItemId::ImplAlias(id),
+            CycleItemId::Use(id) => ModuleItemId::Use(id),
+            CycleItemId::FreeFunction(id) => ModuleItemId::FreeFunction(id),
+            CycleItemId::Impl(id) => ModuleItemId::Impl(id),
+            CycleItemId::ImplType(id) => {
+                return name_stable_location(db, id.stable_ptr(db).lookup(db).name(db));
+            }
+            CycleItemId::ImplConstant(id) => {
+                return name_stable_location(db, id.stable_ptr(db).lookup(db).name(db));
+            }
+            CycleItemId::ImplImpl(id) => {
+                return name_stable_location(db, id.stable_ptr(db).lookup(db).name(db));
+            }
+            CycleItemId::ImplFunction(id) => {
+                return name_stable_location(
+                    db,
+                    id.stable_ptr(db).lookup(db).declaration(db).name(db),
+                );
+            }
+        };
+        match db.module_item_name_stable_ptr(module_item.parent_module(db), module_item) {
+            Ok(ptr) => StableLocation::new(ptr),
+            Err(_) => module_item.stable_location(db),
+        }
+    }
+
+    /// Returns the id of the item as a lookup item.
+    fn lookup_item_id(self) -> LookupItemId<'db> {
+        match self {
+            CycleItemId::Constant(id) => LookupItemId::ModuleItem(ModuleItemId::Constant(id)),
+            CycleItemId::TypeAlias(id) => LookupItemId::ModuleItem(ModuleItemId::TypeAlias(id)),
+            CycleItemId::ImplAlias(id) => LookupItemId::ModuleItem(ModuleItemId::ImplAlias(id)),
+            CycleItemId::Use(id) => LookupItemId::ModuleItem(ModuleItemId::Use(id)),
+            CycleItemId::FreeFunction(id) => {
+                LookupItemId::ModuleItem(ModuleItemId::FreeFunction(id))
+            }
+            CycleItemId::Impl(id) => LookupItemId::ModuleItem(ModuleItemId::Impl(id)),
+            CycleItemId::ImplType(id) => LookupItemId::ImplItem(ImplItemId::Type(id)),
+            CycleItemId::ImplConstant(id) => LookupItemId::ImplItem(ImplItemId::Constant(id)),
+            CycleItemId::ImplImpl(id) => LookupItemId::ImplItem(ImplItemId::Impl(id)),
+            CycleItemId::ImplFunction(id) => LookupItemId::ImplItem(ImplItemId::Function(id)),
+        }
+    }
+
+    /// Returns the items the item depends on, by the items resolved for its paths.
+    ///
+    /// The resolver data stored for the items of a cycle is the one of their cycle fallback, which
+    /// does not resolve their paths, so the paths are resolved again, each by its own resolution.
+    fn dependencies(self, db: &'db dyn Database) -> Maybe<Vec<CycleItemId<'db>>> {
+        let inference_id = InferenceId::LookupItemDeclaration(self.lookup_item_id());
+        let (module_id, impl_def_id) = match self {
+            CycleItemId::Constant(id) => (id.parent_module(db), None),
+            CycleItemId::TypeAlias(id) => (id.parent_module(db), None),
+            CycleItemId::ImplAlias(id) => (id.parent_module(db), None),
+            CycleItemId::Use(id) => (id.parent_module(db), None),
+            CycleItemId::FreeFunction(id) => (id.parent_module(db), None),
+            CycleItemId::Impl(id) => (id.parent_module(db), None),
+            CycleItemId::ImplType(id) => (id.parent_module(db), Some(id.impl_def_id(db))),
+            CycleItemId::ImplConstant(id) => (id.parent_module(db), Some(id.impl_def_id(db))),
+            CycleItemId::ImplImpl(id) => (id.parent_module(db), Some(id.impl_def_id(db))),
+            CycleItemId::ImplFunction(id) => (id.parent_module(db), Some(id.impl_def_id(db))),
+        };
+        // Items of impls are resolved in the context of the impl, for resolving `Self`.
+        let mut resolver = match impl_def_id {
+            Some(impl_def_id) => Resolver::with_data(
+                db,
+                db.impl_def_resolver_data(impl_def_id)?.clone_with_inference_id(db, inference_id),
+            ),
+            None => Resolver::new(db, module_id, inference_id),
+        };
+        let mut diagnostics = SemanticDiagnostics::new(module_id);
+        let syntax_nodes = match self {
+            CycleItemId::Use(id) => {
+                let use_ast = ast::UsePath::Leaf(db.module_use_by_id(id)?);
+                resolver
+                    .resolve_use_path(
+                        &mut diagnostics,
+                        use_ast,
+                        ResolutionContext::ModuleItem(ModuleItemId::Use(id)),
+                    )
+                    .ok();
+                return Ok(resolved_dependencies(db, &resolver.data));
+            }
+            CycleItemId::Constant(id) => vec![db.module_constant_by_id(id)?.as_syntax_node()],
+            CycleItemId::TypeAlias(id) => vec![db.module_type_alias_by_id(id)?.as_syntax_node()],
+            CycleItemId::ImplAlias(id) => vec![db.module_impl_alias_by_id(id)?.as_syntax_node()],
+            CycleItemId::FreeFunction(id) => {
+                vec![db.module_free_function_by_id(id)?.as_syntax_node()]
+            }
+            // The items of an impl depend on the impl, and not the other way around.
+            CycleItemId::Impl(id) => {
+                let impl_ast = db.module_impl_by_id(id)?;
+                vec![
+                    impl_ast.generic_params(db).as_syntax_node(),
+                    impl_ast.trait_path(db).as_syntax_node(),
+                ]
+            }
+            CycleItemId::ImplType(id) => vec![id.stable_ptr(db).lookup(db).as_syntax_node()],
+            CycleItemId::ImplConstant(id) => vec![id.stable_ptr(db).lookup(db).as_syntax_node()],
+            CycleItemId::ImplImpl(id) => vec![id.stable_ptr(db).lookup(db).as_syntax_node()],
+            CycleItemId::ImplFunction(id) => vec![id.stable_ptr(db).lookup(db).as_syntax_node()],
+        };
+        let mut dependencies = vec![];
+        for path in syntax_nodes.into_iter().flat_map(|node| syntax_paths(db, node)) {
+            resolver
+                .resolve_concrete_path(&mut diagnostics, &path, NotFoundItemType::Identifier)
+                .ok();
+            dependencies.extend(resolved_dependencies(db, &resolver.data));
+            resolver.data.resolved_items = Default::default();
+            resolver.data.used_uses = Default::default();
+        }
+        Ok(dependencies)
+    }
+}
+
+/// Returns the stable location of the name of an item.
+fn name_stable_location<'db>(
+    db: &'db dyn Database,
+    name: ast::TerminalIdentifier<'db>,
+) -> StableLocation<'db> {
+    StableLocation::new(name.stable_ptr(db).untyped())
+}
+
+/// Returns the paths in a syntax node, including the paths nested in other paths.
+fn syntax_paths<'db>(db: &'db dyn Database, node: SyntaxNode<'db>) -> Vec<ast::ExprPath<'db>> {
+    node.descendants(db)
+        .filter(|node| node.kind(db) == SyntaxKind::ExprPath)
+        .map(|node| ast::ExprPath::from_syntax_node(db, node))
+        .collect()
+}
+
+/// Returns the items which may be part of a query cycle out of the items recorded by the resolution
+/// of a single path, in the order of its segments.
+///
+/// A segment found through a `use` depends on the `use` rather than on the item it was resolved
+/// to, and an item of an impl is found by the trait item of its segment, following the segment of
+/// the impl. The uses which have no segment of their own come last.
+fn resolved_dependencies<'db>(
+    db: &'db dyn Database,
+    resolver_data: &ResolverData<'db>,
+) -> Vec<CycleItemId<'db>> {
+    let ResolvedItems { concrete, generic } = &resolver_data.resolved_items;
+    let segment_offset =
+        |ptr: &ast::TerminalIdentifierPtr<'db>| ptr.untyped().lookup(db).offset(db);
+    let concrete_segments = concrete.iter_sorted_by_key(|(ptr, _)| segment_offset(ptr));
+    let generic_segments = generic.iter_sorted_by_key(|(ptr, _)| segment_offset(ptr));
+    let mut segments: Vec<_> =
+        chain!(concrete_segments.map(|(ptr, _)| *ptr), generic_segments.map(|(ptr, _)| *ptr))
+            .collect();
+    segments.sort_by_key(segment_offset);
+    segments.dedup();
+
+    let mut uses: Vec<_> = resolver_data.used_uses.iter().copied().collect();
+    let mut dependencies = vec![];
+    let mut prev_impl_def_id = None;
+    for ptr in segments {
+        let impl_def_id = prev_impl_def_id.take();
+        let name = ptr.lookup(db).text(db);
+        if let Some(position) = uses.iter().position(|use_id| use_id.name(db) == name) {
+            dependencies.push(CycleItemId::Use(uses.remove(position)));
+            continue;
+        }
+        if let Some(ResolvedConcreteItem::Impl(impl_id)) = concrete.get(&ptr) {
+            prev_impl_def_id = concrete_impl_def_id(db, *impl_id);
+        }
+        let dependency = match generic.get(&ptr) {
+            Some(ResolvedGenericItem::TraitItem(trait_item_id)) => {
+                impl_def_id.and_then(|impl_def_id| impl_item(db, impl_def_id, *trait_item_id))
+            }
+            Some(item) => generic_dependency(db, item),
+            None => None,
+        };
+        dependencies.extend(dependency);
+    }
+    // Uses which failed to resolve have no segment of their own.
+    dependencies.extend(uses.into_iter().map(CycleItemId::Use));
+    dependencies
+}
+
+/// Returns the item a path segment depends on, given the generic item it was resolved to.
+fn generic_dependency<'db>(
+    db: &'db dyn Database,
+    item: &ResolvedGenericItem<'db>,
+) -> Option<CycleItemId<'db>> {
+    Some(match item {
+        ResolvedGenericItem::GenericConstant(id) => CycleItemId::Constant(*id),
+        ResolvedGenericItem::GenericTypeAlias(id) => CycleItemId::TypeAlias(*id),
+        ResolvedGenericItem::GenericImplAlias(id) => CycleItemId::ImplAlias(*id),
+        ResolvedGenericItem::Impl(id) => CycleItemId::Impl(*id),
+        ResolvedGenericItem::GenericFunction(GenericFunctionId::Free(id)) => {
+            CycleItemId::FreeFunction(*id)
+        }
+        ResolvedGenericItem::GenericFunction(GenericFunctionId::Impl(function)) => {
+            let impl_def_id = concrete_impl_def_id(db, function.impl_id)?;
+            impl_item(db, impl_def_id, TraitItemId::Function(function.function))?
+        }
+        _ => return None,
+    })
+}
+
+/// Returns the item of an impl definition implementing the given trait item.
+fn impl_item<'db>(
+    db: &'db dyn Database,
+    impl_def_id: ImplDefId<'db>,
+    trait_item_id: TraitItemId<'db>,
+) -> Option<CycleItemId<'db>> {
+    Some(match trait_item_id {
+        TraitItemId::Function(id) => {
+            CycleItemId::ImplFunction(impl_function_by_trait_function(db, impl_def_id, id).ok()??)
+        }
+        TraitItemId::Type(id) => {
+            CycleItemId::ImplType(db.impl_type_by_trait_type(impl_def_id, id).ok()?)
+        }
+        TraitItemId::Constant(id) => {
+            CycleItemId::ImplConstant(impl_constant_by_trait_constant(db, impl_def_id, id).ok()?)
+        }
+        TraitItemId::Impl(id) => {
+            CycleItemId::ImplImpl(impl_impl_by_trait_impl(db, impl_def_id, id).ok()?)
+        }
+    })
+}
+
+/// Returns the impl definition of an impl, if it is a concrete impl.
+fn concrete_impl_def_id<'db>(
+    db: &'db dyn Database,
+    impl_id: ImplId<'db>,
+) -> Option<ImplDefId<'db>> {
+    try_extract_matches!(impl_id.long(db), ImplLongId::Concrete)
+        .map(|concrete_impl_id| concrete_impl_id.impl_def_id(db))
+}
+
+/// Returns the items of a shortest cycle through the item, starting with the item itself.
+/// Returns an empty vector if no cycle is found.
+fn cycle_items<'db>(db: &'db dyn Database, item: CycleItemId<'db>) -> Vec<CycleItemId<'db>> {
+    // The item each visited item was first reached from.
+    let mut reached_from = OrderedHashMap::<CycleItemId<'db>, CycleItemId<'db>>::default();
+    let mut queue = VecDeque::from([item]);
+    while let Some(current) = queue.pop_front() {
+        for dependency in current.dependencies(db).unwrap_or_default() {
+            if dependency == item {
+                let mut cycle = vec![current];
+                while let Some(previous) = reached_from.get(cycle.last().unwrap()) {
+                    cycle.push(*previous);
+                }
+                cycle.reverse();
+                return cycle;
+            }
+            if !reached_from.contains_key(&dependency) {
+                reached_from.insert(dependency, current);
+                queue.push_back(dependency);
+            }
+        }
+    }
+    vec![]
+}
+
+/// Returns notes describing the cycle the item is part of, one per item of the cycle, at the
+/// location of the item's name. Returns no notes if the cycle is unknown.
+///
+/// Must only be called once the cycle is done, e.g. when formatting its diagnostics.
+pub fn cycle_notes<'db>(
+    db: &'db dyn Database,
+    item: CycleItemId<'db>,
+) -> &'db [DiagnosticNote<'db>] {
+    cycle_notes_helper(db, (), item)
+}
+
+#[salsa::tracked(returns(ref))]
+fn cycle_notes_helper<'db>(
+    db: &'db dyn Database,
+    _tracked: Tracked,
+    item: CycleItemId<'db>,
+) -> Vec<DiagnosticNote<'db>> {
+    let items = cycle_items(db, item);
+    let next_items = items.iter().cycle().skip(1);
+    items
+        .iter()
+        .zip(next_items)
+        .map(|(item, next)| {
+            let text = format!("`{}` depends on `{}`", item.full_path(db), next.full_path(db));
+            DiagnosticNote::with_location(text, item.name_location(db).span_in_file(db))
+        })
+        .collect()
+}
@@ -1,15 +1,15 @@
 use cairo_lang_defs::ids::{LanguageElementId, ModuleId};
-use cairo_lang_filesystem::db::{FilesGroup, default_crate_settings};
+use cairo_lang_filesystem::db::{FilesGroup, LintLevel, default_crate_settings};
 use cairo_lang_filesystem::ids::{CrateId, SmolStrId};
 use cairo_lang_syntax::attribute::consts::{
-    ALLOW_ATTR, DEPRECATED_ATTR, FEATURE_ATTR, INTERNAL_ATTR, UNSTABLE_ATTR, UNUSED,
-    UNUSED_IMPORTS, UNUSED_VARIABLES,
+    DEPRECATED_ATTR, FEATURE_ATTR, INTERNAL_ATTR, UNSTABLE_ATTR, UNUSED_IMPORTS,
 };
 use cairo_lang_syntax::attribute::structured::{
     self, AttributeArg, AttributeArgVariant, AttributeStructurize,
 };
 use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::{Terminal, TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use cairo_lang_utils::try_extract_matches;
 use itertools::Itertools;
@@ -17,6 +17,7 @@ use salsa::Database;
 
 use crate::db::SemanticGroup;
 use crate::diagnostic::{SemanticDiagnosticKind, SemanticDiagnostics, SemanticDiagnosticsBuilder};
+use crate::lint::{default_lint_level, expand_lint_group, is_warning_level_name, lint_level_attr};
 
 /// The kind of a feature for an item.
 #[derive(Clone, Debug, PartialEq, Eq, salsa::Update)]
@@ -25,9 +26,10 @@ pub enum FeatureKind<'db> {
     Stable,
     /// The feature of the item is unstable, with the given name to allow.
     Unstable { feature: SmolStrId<'db>, note: Option<SmolStrId<'db>> },
-    /// The feature of the item is deprecated, with the given name to allow, and an optional note
+    /// The feature of the item is deprecated, with an optional name to allow, and an optional note
     /// to appear in diagnostics.
-    Deprecated { feature: SmolStrId<'db>, note: Option<SmolStrId<'db>> },
+    /// Without a feature name, usages may only be allowed using the `deprecated` lint.
+    Deprecated { feature: Option<SmolStrId<'db>>, note: Option<SmolStrId<'db>> },
     /// This feature is for internal corelib use only. Using it in user code is not advised.
     Internal { feature: SmolStrId<'db>, note: Option<SmolStrId<'db>> },
 }
@@ -57,7 +59,7 @@ impl<'db> FeatureKind<'db> {
             let attr = deprecated_attrs.into_iter().next().unwrap().structurize(db);
             let [feature, note, _] =
                 parse_feature_attr(db, diagnostics, &attr, ["feature", "note", "since"]);
-            feature.map(|feature| Self::Deprecated { feature, note }).ok_or(attr)
+            Ok(Self::Deprecated { feature, note })
         } else {
             let attr = internal_attrs.into_iter().next().unwrap().structurize(db);
             let [feature, note, _] =
@@ -82,7 +84,7 @@ pub trait HasFeatureKind<'db> {
 pub enum FeatureMarkerDiagnostic {
     /// Multiple markers on the same item.
     MultipleMarkers,
-    /// Every marker must have a feature argument, to allow ignoring the warning.
+    /// Unstable and internal markers must have a feature argument, to allow ignoring the warning.
     MissingAllowFeature,
     /// Unsupported argument in the feature marker attribute.
     UnsupportedArgument,
@@ -132,8 +134,8 @@ fn add_diag<'db>(
 pub struct FeatureConfig<'db> {
     /// The current set of allowed features.
     pub allowed_features: OrderedHashSet<SmolStrId<'db>>,
-    /// Which lints are allowed.
-    pub allowed_lints: OrderedHashSet<SmolStrId<'db>>,
+    /// The levels of lints, overriding their default levels.
+    pub lint_levels: OrderedHashMap<SmolStrId<'db>, LintLevel>,
 }
 
 impl<'db> FeatureConfig<'db> {
@@ -147,10 +149,9 @@ impl<'db> FeatureConfig<'db> {
                 restore.features_to_remove.push(feature_name);
             }
         }
-        for allow_lint in other.allowed_lints {
-            if self.allowed_lints.insert(allow_lint) {
-                restore.allowed_lints_to_remove.push(allow_lint);
-            }
+        for (lint, level) in other.lint_levels {
+            let prev_level = self.lint_levels.insert(lint, level);
+            restore.lint_levels_to_restore.push((lint, prev_level));
         }
         restore
     }
@@ -160,10 +161,25 @@ impl<'db> FeatureConfig<'db> {
         for feature_name in restore.features_to_remove {
             self.allowed_features.swap_remove(&feature_name);
         }
-        for allow_lint in restore.allowed_lints_to_remove {
-            self.allowed_lints.swap_remove(&allow_lint);
+        for (lint, prev_level) in restore.lint_levels_to_restore.into_iter().rev() {
+            match prev_level {
+                Some(level) => {
+                    self.lint_levels.insert(lint, level);
+                }
+                None => {
+                    self.lint_levels.swap_remove(&lint);
+                }
+            }
         }
     }
+
+    /// Returns the level of the lint with the given name.
+    pub fn lint_level(&self, db: &'db dyn Database, lint: &str) -> LintLevel {
+        self.lint_levels
+            .get(&SmolStrId::from(db, lint))
+            .copied()
+            .unwrap_or_else(|| default_lint_level(lint))
+    }
 }
 
 /// The data required to restore the feature configuration after an override.
@@ -171,8 +187,8 @@ impl<'db> FeatureConfig<'db> {
 pub struct FeatureConfigRestore<'db> {
     /// The features to remove from the configuration after the override.
     features_to_remove: Vec<SmolStrId<'db>>,
-    /// The previous state of the allowed lints.
-    allowed_lints_to_remove: Vec<SmolStrId<'db>>,
+    /// The previous levels of the overridden lints.
+    lint_levels_to_restore: Vec<(SmolStrId<'db>, Option<LintLevel>)>,
 }
 
 impl FeatureConfigRestore<'_> {
@@ -204,27 +220,22 @@ pub fn feature_config_from_ast_item<'db>(
             }
         },
     );
-    process_feature_attr_kind(
-        db,
-        syntax,
-        ALLOW_ATTR,
-        || SemanticDiagnosticKind::UnsupportedAllowAttrArguments,
-        diagnostics,
-        |value| {
-            let allowed = value.as_syntax_node().get_text_without_trivia(db);
-            // Expand lint group UNUSED to include all `unused` lints.
-            if allowed.long(db) == UNUSED {
-                let all_unused_lints = [UNUSED_VARIABLES, UNUSED_IMPORTS];
-                return all_unused_lints.iter().all(|&lint| {
-                    let _already_allowed = config.allowed_lints.insert(SmolStrId::from(db, lint));
-                    db.declared_allows(crate_id).contains(lint)
-                });
-            }
-
-            let _already_allowed = config.allowed_lints.insert(allowed);
-            db.declared_allows(crate_id).contains(allowed.long(db).as_str())
-        },
-    );
+    for level in [LintLevel::Allow, LintLevel::Warn, LintLevel::Deny] {
+        process_feature_attr_kind(
+            db,
+            syntax,
+            lint_level_attr(level),
+            || SemanticDiagnosticKind::UnsupportedLintAttrArguments(level),
+            diagnostics,
+            |value| {
+                let name = value.as_syntax_node().get_text_without_trivia(db);
+                expand_lint_group(name.long(db)).into_iter().all(|lint| {
+                    config.lint_levels.insert(SmolStrId::from(db, lint), level);
+                    db.declared_allows(crate_id).contains(lint) || is_warning_level_name(lint)
+                })
+            },
+        );
+    }
     config
 }
 
@@ -267,16 +278,21 @@ pub fn feature_config_from_item_and_parent_modules<'db>(
     let mut config = loop {
         match current_module_id {
             ModuleId::CrateRoot(crate_id) => {
-                let all_declarations_pub = db
+                let settings = db
                     .crate_config(crate_id)
-                    .map(|config| config.settings.edition.ignore_visibility())
-                    .unwrap_or_else(|| default_crate_settings(db).edition.ignore_visibility());
-                let mut allowed_lints = OrderedHashSet::default();
-                if all_declarations_pub {
-                    let _already_allowed =
-                        allowed_lints.insert(SmolStrId::from(db, UNUSED_IMPORTS));
+                    .map(|config| &config.settings)
+                    .unwrap_or_else(|| default_crate_settings(db));
+                let mut lint_levels = OrderedHashMap::default();
+                for (name, level) in &settings.lints {
+                    for lint in expand_lint_group(name) {
+                        lint_levels.insert(SmolStrId::from(db, lint), *level);
+                    }
+                }
+                if settings.edition.ignore_visibility() {
+                    // All the declarations are public, so imports may be used by other crates.
+                    lint_levels.insert(SmolStrId::from(db, UNUSED_IMPORTS), LintLevel::Allow);
                 }
-                break FeatureConfig { allowed_features: OrderedHashSet::default(), allowed_lints };
+                break FeatureConfig { allowed_features: OrderedHashSet::default(), lint_levels };
             }
             ModuleId::Submodule(id) => {
                 current_module_id = id.parent_module(db);
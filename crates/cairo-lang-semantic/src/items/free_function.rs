@@ -4,7 +4,7 @@ use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_defs::ids::{
     FreeFunctionId, FunctionTitleId, LanguageElementId, LookupItemId, ModuleItemId,
 };
-use cairo_lang_diagnostics::{Diagnostics, Maybe, MaybeAsRef};
+use cairo_lang_diagnostics::{Diagnostics, Maybe, MaybeAsRef, skip_diagnostic};
 use cairo_lang_syntax::attribute::structured::{Attribute, AttributeListStructurize};
 use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode};
 use cairo_lang_utils::Intern;
@@ -123,8 +123,20 @@ fn free_function_declaration_data<'db>(
     })
 }
 
+/// Cycle handling for [priv_free_function_body_data].
+///
+/// A body only depends on itself through the evaluation of a constant calling the function, and the
+/// cycle is reported as a cycle of that constant.
+fn priv_free_function_body_data_cycle<'db>(
+    _db: &'db dyn Database,
+    _id: salsa::Id,
+    _free_function_id: FreeFunctionId<'db>,
+) -> Maybe<FunctionBodyData<'db>> {
+    Err(skip_diagnostic())
+}
+
 /// Query implementation of [FreeFunctionSemantic::priv_free_function_body_data].
-#[salsa::tracked(returns(ref))]
+#[salsa::tracked(cycle_result=priv_free_function_body_data_cycle, returns(ref))]
 fn priv_free_function_body_data<'db>(
     db: &'db dyn Database,
     free_function_id: FreeFunctionId<'db>,
@@ -20,6 +20,7 @@ use cairo_lang_filesystem::db::FilesGroup;
 use cairo_lang_filesystem::ids::{CrateId, CrateLongId, SmolStrId, Tracked, UnstableSalsaId};
 use cairo_lang_proc_macros::{DebugWithDb, HeapSize, SemanticObject};
 use cairo_lang_syntax as syntax;
+use cairo_lang_syntax::attribute::consts::FALLBACK_ATTR;
 use cairo_lang_syntax::node::ast::{
     OptionTypeClause, OptionWrappedGenericParamList, UnaryOperator,
 };
@@ -39,8 +40,9 @@ use syntax::node::{Terminal, TypedStablePtr, TypedSyntaxNode};
 
 use super::constant::{
     ConstValue, ConstValueId, ConstantData, ImplConstantId, constant_semantic_data_cycle_helper,
-    constant_semantic_data_helper,
+    constant_semantic_data_helper, evaluate_constant_with_substitution,
 };
+use super::cycle::CycleItemId;
 use super::enm::SemanticEnumEx;
 use super::feature_kind::{FeatureKind, HasFeatureKind};
 use super::function_with_body::{FunctionBody, FunctionBodyData, get_inline_config};
@@ -56,6 +58,7 @@ use super::impl_alias::{
     ImplAliasData, impl_alias_generic_params_data_helper, impl_alias_semantic_data_cycle_helper,
     impl_alias_semantic_data_helper,
 };
+use super::implization::impl_def_trait_defaults_substitution;
 use super::trt::{
     ConcreteTraitConstantId, ConcreteTraitGenericFunctionId, ConcreteTraitGenericFunctionLongId,
     ConcreteTraitImplId,
@@ -869,6 +872,11 @@ fn impl_declaration_data_inner<'db>(
         inference.rewrite(concrete_trait).no_err();
 
     let attributes = impl_ast.attributes(db).structurize(db);
+    if let Some(attr) = attributes.iter().find(|attr| attr.id.long(db) == FALLBACK_ATTR)
+        && !are_fallback_impls_enabled(db, impl_def_id.parent_module(db))
+    {
+        diagnostics.report(attr.stable_ptr, FallbackImplsDisabled);
+    }
     let mut resolver_data = resolver.data;
     resolver_data.trait_or_impl_ctx = TraitOrImplContext::Impl(impl_def_id);
     Ok(ImplDeclarationData {
@@ -879,6 +887,12 @@ fn impl_declaration_data_inner<'db>(
     })
 }
 
+/// Returns true if fallback impls are enabled in the module.
+pub(crate) fn are_fallback_impls_enabled(db: &dyn Database, module_id: ModuleId<'_>) -> bool {
+    db.crate_config(module_id.owning_crate(db))
+        .is_some_and(|config| config.settings.experimental_features.fallback_impls)
+}
+
 // === Impl Definition ===
 
 #[derive(Clone, Debug, PartialEq, Eq, DebugWithDb, salsa::Update)]
@@ -955,6 +969,37 @@ fn impl_semantic_definition_diagnostics<'db>(
     for impl_item_constant_id in data.item_constant_asts.keys() {
         diagnostics.extend(db.impl_constant_def_semantic_diagnostics(*impl_item_constant_id));
     }
+    if let Ok(trait_id) = db.impl_def_trait(impl_def_id)
+        && let Ok(trait_types) = db.trait_types(trait_id)
+    {
+        for (name, trait_type_id) in trait_types.iter() {
+            if data.item_id_by_name.contains_key(name)
+                || !matches!(db.trait_type_default(*trait_type_id), Ok(Some(_)))
+            {
+                continue;
+            }
+            if let Ok(default_data) = impl_default_type_data(db, impl_def_id, *trait_type_id, false)
+            {
+                diagnostics.extend(default_data.diagnostics.clone());
+            }
+        }
+    }
+    if let Ok(trait_id) = db.impl_def_trait(impl_def_id)
+        && let Ok(trait_constants) = db.trait_constants(trait_id)
+    {
+        for (name, trait_constant_id) in trait_constants.iter() {
+            if data.item_id_by_name.contains_key(name)
+                || !matches!(db.trait_constant_default(*trait_constant_id), Ok(Some(_)))
+            {
+                continue;
+            }
+            if let Ok(default_data) =
+                impl_default_constant_data(db, impl_def_id, *trait_constant_id, false)
+            {
+                diagnostics.extend(default_data.diagnostics.clone());
+            }
+        }
+    }
     for impl_item_impl_id in data.item_impl_asts.keys() {
         diagnostics.extend(db.impl_impl_def_semantic_diagnostics(*impl_item_impl_id));
     }
@@ -1218,7 +1263,7 @@ fn impl_functions<'db>(
 
 /// Query implementation of [PrivImplSemantic::impl_function_by_trait_function].
 #[salsa::tracked]
-fn impl_function_by_trait_function<'db>(
+pub(crate) fn impl_function_by_trait_function<'db>(
     db: &'db dyn Database,
     impl_def_id: ImplDefId<'db>,
     trait_function_id: TraitFunctionId<'db>,
@@ -1313,7 +1358,7 @@ fn impl_type_by_trait_type_tracked<'db>(
 
 /// Query implementation of [PrivImplSemantic::impl_constant_by_trait_constant].
 #[salsa::tracked]
-fn impl_constant_by_trait_constant<'db>(
+pub(crate) fn impl_constant_by_trait_constant<'db>(
     db: &'db dyn Database,
     impl_def_id: ImplDefId<'db>,
     trait_constant_id: TraitConstantId<'db>,
@@ -1345,7 +1390,7 @@ fn impl_impl_by_id<'db>(
 
 /// Query implementation of [PrivImplSemantic::impl_impl_by_trait_impl].
 #[salsa::tracked]
-fn impl_impl_by_trait_impl<'db>(
+pub(crate) fn impl_impl_by_trait_impl<'db>(
     db: &'db dyn Database,
     impl_def_id: ImplDefId<'db>,
     trait_impl_id: TraitImplId<'db>,
@@ -1939,6 +1984,18 @@ pub enum UninferredImpl<'db> {
     GeneratedImpl(UninferredGeneratedImplId<'db>),
 }
 impl<'db> UninferredImpl<'db> {
+    pub fn format(&self, db: &dyn Database) -> String {
+        match self {
+            UninferredImpl::Def(impl_def_id) => impl_def_id.full_path(db),
+            UninferredImpl::ImplAlias(impl_alias_id) => impl_alias_id.full_path(db),
+            UninferredImpl::GenericParam(param) => param.format(db).to_string(db),
+            UninferredImpl::ImplImpl(impl_impl_id) => impl_impl_id.full_path(db),
+            UninferredImpl::GeneratedImpl(generated_impl) => {
+                format!("{:?}", generated_impl.concrete_trait(db).debug(db))
+            }
+        }
+    }
+
     pub fn concrete_trait(&self, db: &'db dyn Database) -> Maybe<ConcreteTraitId<'db>> {
         match self {
             UninferredImpl::Def(impl_def_id) => db.impl_def_concrete_trait(*impl_def_id),
@@ -2445,6 +2502,7 @@ fn impl_type_semantic_data<'db>(
                 &impl_type_def_ast,
                 lookup_item_id,
                 generic_params_data,
+                CycleItemId::ImplType(impl_type_def_id),
             )?,
             trait_type_id,
             diagnostics: diagnostics.build(),
@@ -2475,6 +2533,52 @@ fn impl_type_semantic_data_cycle<'db>(
     impl_type_semantic_data(db, impl_type_def_id, true).clone()
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, DebugWithDb, salsa::Update)]
+#[debug_db(dyn Database)]
+struct ImplDefaultTypeData<'db> {
+    /// The diagnostics of the implization of the default type for the impl.
+    diagnostics: Diagnostics<'db, SemanticDiagnostic<'db>>,
+    ty: Maybe<TypeId<'db>>,
+}
+
+/// Returns the default type of a trait type, implized for an impl not defining the type.
+#[salsa::tracked(cycle_result=impl_default_type_data_cycle, returns(ref))]
+pub(crate) fn impl_default_type_data<'db>(
+    db: &'db dyn Database,
+    impl_def_id: ImplDefId<'db>,
+    trait_type_id: TraitTypeId<'db>,
+    in_cycle: bool,
+) -> Maybe<ImplDefaultTypeData<'db>> {
+    let mut diagnostics = SemanticDiagnostics::new(impl_def_id.parent_module(db));
+    let ty = if in_cycle {
+        let default_ptr = trait_type_id.stable_ptr(db).lookup(db).default(db).stable_ptr(db);
+        Err(diagnostics.report(default_ptr, TypeAliasCycle(None)))
+    } else {
+        let default = db.trait_type_default(trait_type_id)?.ok_or_else(skip_diagnostic)?;
+        impl_def_trait_defaults_substitution(db, impl_def_id)?.substitute(db, default)
+    };
+    Ok(ImplDefaultTypeData { diagnostics: diagnostics.build(), ty })
+}
+
+fn impl_default_type_data_cycle<'db>(
+    db: &'db dyn Database,
+    _id: salsa::Id,
+    impl_def_id: ImplDefId<'db>,
+    trait_type_id: TraitTypeId<'db>,
+    _in_cycle: bool,
+) -> Maybe<ImplDefaultTypeData<'db>> {
+    impl_default_type_data(db, impl_def_id, trait_type_id, true).clone()
+}
+
+/// Returns the default type of a trait type, implized for an impl not defining the type.
+pub(crate) fn impl_default_type<'db>(
+    db: &'db dyn Database,
+    impl_def_id: ImplDefId<'db>,
+    trait_type_id: TraitTypeId<'db>,
+) -> Maybe<TypeId<'db>> {
+    impl_default_type_data(db, impl_def_id, trait_type_id, false).maybe_as_ref()?.ty
+}
+
 /// Returns the generic parameters data of an impl type definition.
 #[salsa::tracked(returns(ref))]
 fn impl_type_def_generic_params_data<'db>(
@@ -2634,6 +2738,7 @@ fn impl_constant_semantic_data<'db>(
             lookup_item_id,
             Some(Arc::new(resolver.data)),
             &impl_def_id,
+            CycleItemId::ImplConstant(impl_constant_def_id),
         )?
     } else {
         constant_semantic_data_helper(
@@ -2714,12 +2819,59 @@ fn impl_constant_implized_by_context<'db>(
     impl_constant_id: ImplConstantId<'db>,
     impl_def_id: ImplDefId<'db>,
 ) -> Maybe<ConstValueId<'db>> {
+    let trait_constant_id = impl_constant_id.trait_constant_id();
+    if db.impl_item_by_name(impl_def_id, trait_constant_id.name(db))?.is_none()
+        && db.trait_constant_default(trait_constant_id)?.is_some()
+    {
+        return Ok(impl_default_constant_data(db, impl_def_id, trait_constant_id, false)
+            .maybe_as_ref()?
+            .value);
+    }
     let impl_constant_def_id: ImplConstantDefId<'_> =
-        db.impl_constant_by_trait_constant(impl_def_id, impl_constant_id.trait_constant_id())?;
+        db.impl_constant_by_trait_constant(impl_def_id, trait_constant_id)?;
 
     db.impl_constant_def_value(impl_constant_def_id)
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, DebugWithDb, salsa::Update)]
+#[debug_db(dyn Database)]
+struct ImplDefaultConstantData<'db> {
+    /// The diagnostics of the evaluation of the default value for the impl.
+    diagnostics: Diagnostics<'db, SemanticDiagnostic<'db>>,
+    value: ConstValueId<'db>,
+}
+
+/// Returns the value of the default of a trait constant, evaluated for an impl not defining the
+/// constant.
+#[salsa::tracked(cycle_result=impl_default_constant_data_cycle, returns(ref))]
+fn impl_default_constant_data<'db>(
+    db: &'db dyn Database,
+    impl_def_id: ImplDefId<'db>,
+    trait_constant_id: TraitConstantId<'db>,
+    in_cycle: bool,
+) -> Maybe<ImplDefaultConstantData<'db>> {
+    let mut diagnostics = SemanticDiagnostics::new(impl_def_id.parent_module(db));
+    let value = if in_cycle {
+        let default_ptr = trait_constant_id.stable_ptr(db).lookup(db).default(db).stable_ptr(db);
+        ConstValue::Missing(diagnostics.report(default_ptr, ConstCycle(None))).intern(db)
+    } else {
+        let constant = db.trait_constant_default(trait_constant_id)?.ok_or_else(skip_diagnostic)?;
+        let substitution = impl_def_trait_defaults_substitution(db, impl_def_id)?;
+        evaluate_constant_with_substitution(db, constant, substitution, &mut diagnostics)
+    };
+    Ok(ImplDefaultConstantData { diagnostics: diagnostics.build(), value })
+}
+
+fn impl_default_constant_data_cycle<'db>(
+    db: &'db dyn Database,
+    _id: salsa::Id,
+    impl_def_id: ImplDefId<'db>,
+    trait_constant_id: TraitConstantId<'db>,
+    _in_cycle: bool,
+) -> Maybe<ImplDefaultConstantData<'db>> {
+    impl_default_constant_data(db, impl_def_id, trait_constant_id, true).clone()
+}
+
 /// Query implementation of [ImplSemantic::impl_constant_implized_by_context].
 #[salsa::tracked(cycle_result=impl_constant_implized_by_context_cycle)]
 fn impl_constant_implized_by_context_tracked<'db>(
@@ -2884,6 +3036,7 @@ fn impl_impl_semantic_data<'db>(
             impl_impl_def_ast,
             lookup_item_id,
             generic_params_data,
+            CycleItemId::ImplImpl(impl_impl_def_id),
         )?
     } else {
         impl_alias_semantic_data_helper(db, impl_impl_def_ast, lookup_item_id, generic_params_data)?
@@ -3010,7 +3163,8 @@ fn implicit_impl_impl_semantic_data<'db>(
 ) -> Maybe<ImplicitImplImplData<'db>> {
     let mut diagnostics = SemanticDiagnostics::new(impl_def_id.parent_module(db));
     if in_cycle {
-        let err = Err(diagnostics.report(impl_def_id.stable_ptr(db).untyped(), ImplAliasCycle));
+        let err =
+            Err(diagnostics.report(impl_def_id.stable_ptr(db).untyped(), ImplAliasCycle(None)));
         return Ok(ImplicitImplImplData {
             resolved_impl: err,
             trait_impl_id,
@@ -3636,8 +3790,20 @@ fn priv_impl_function_body_data<'db>(
     })
 }
 
+/// Cycle handling for [priv_impl_function_body_data_tracked].
+///
+/// A body only depends on itself through the evaluation of a constant calling the function, and the
+/// cycle is reported as a cycle of that constant.
+fn priv_impl_function_body_data_cycle<'db>(
+    _db: &'db dyn Database,
+    _id: salsa::Id,
+    _impl_function_id: ImplFunctionId<'db>,
+) -> Maybe<FunctionBodyData<'db>> {
+    Err(skip_diagnostic())
+}
+
 /// Computes impl function body data.
-#[salsa::tracked(returns(ref))]
+#[salsa::tracked(cycle_result=priv_impl_function_body_data_cycle, returns(ref))]
 fn priv_impl_function_body_data_tracked<'db>(
     db: &'db dyn Database,
     impl_function_id: ImplFunctionId<'db>,
@@ -4133,6 +4299,15 @@ pub trait ImplSemantic<'db>: Database {
             .maybe_as_ref()?
             .item_constant_asts)
     }
+    /// Returns the impls items in the impl.
+    fn impl_impls(
+        &'db self,
+        impl_def_id: ImplDefId<'db>,
+    ) -> Maybe<&'db OrderedHashMap<ImplImplDefId<'db>, ast::ItemImplAlias<'db>>> {
+        Ok(&impl_definition_data(self.as_dyn_database(), impl_def_id)
+            .maybe_as_ref()?
+            .item_impl_asts)
+    }
     /// Returns the functions in the impl.
     fn impl_functions(
         &'db self,
@@ -4253,6 +4428,17 @@ pub trait ImplSemantic<'db>: Database {
             .resolver_data
             .clone())
     }
+    /// Returns the resolved impl of an impl item impl.
+    fn impl_impl_def_impl(
+        &'db self,
+        impl_impl_def_id: ImplImplDefId<'db>,
+        in_cycle: bool,
+    ) -> Maybe<ImplId<'db>> {
+        impl_impl_semantic_data(self.as_dyn_database(), impl_impl_def_id, in_cycle)
+            .maybe_as_ref()?
+            .impl_data
+            .resolved_impl
+    }
 
     // Impl impl.
     // ================
@@ -4429,15 +4615,6 @@ trait PrivImplSemantic<'db>: Database {
     ) -> Maybe<ast::ItemTypeAlias<'db>> {
         impl_type_by_id(self.as_dyn_database(), impl_type_id)
     }
-    /// Returns the impls items in the impl.
-    fn impl_impls(
-        &'db self,
-        impl_def_id: ImplDefId<'db>,
-    ) -> Maybe<&'db OrderedHashMap<ImplImplDefId<'db>, ast::ItemImplAlias<'db>>> {
-        Ok(&impl_definition_data(self.as_dyn_database(), impl_def_id)
-            .maybe_as_ref()?
-            .item_impl_asts)
-    }
     /// Returns the impl AST of the impl impl that matches the given id, if exists.
     fn impl_impl_by_id(
         &'db self,
@@ -4522,17 +4699,6 @@ trait PrivImplSemantic<'db>: Database {
             .map(|data| data.diagnostics.clone())
             .unwrap_or_default()
     }
-    /// Returns the resolved impl of an impl item impl.
-    fn impl_impl_def_impl(
-        &'db self,
-        impl_impl_def_id: ImplImplDefId<'db>,
-        in_cycle: bool,
-    ) -> Maybe<ImplId<'db>> {
-        impl_impl_semantic_data(self.as_dyn_database(), impl_impl_def_id, in_cycle)
-            .maybe_as_ref()?
-            .impl_data
-            .resolved_impl
-    }
     /// Returns the semantic diagnostics of an implicit impl.
     fn implicit_impl_impl_semantic_diagnostics(
         &'db self,
@@ -12,6 +12,7 @@ use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode, ast};
 use cairo_lang_utils::try_extract_matches;
 use salsa::Database;
 
+use super::cycle::CycleItemId;
 use super::generics::{GenericParamsData, semantic_generic_params};
 use super::imp::ImplId;
 use crate::db::SemanticGroup;
@@ -53,6 +54,7 @@ fn impl_alias_semantic_data<'db>(
             &impl_alias_ast,
             lookup_item_id,
             generic_params_data,
+            CycleItemId::ImplAlias(impl_alias_id),
         )
     } else {
         impl_alias_semantic_data_helper(db, &impl_alias_ast, lookup_item_id, generic_params_data)
@@ -115,13 +117,15 @@ pub fn impl_alias_semantic_data_cycle_helper<'db>(
     impl_alias_ast: &ast::ItemImplAlias<'db>,
     lookup_item_id: LookupItemId<'db>,
     generic_params_data: GenericParamsData<'db>,
+    cycle_item: CycleItemId<'db>,
 ) -> Maybe<ImplAliasData<'db>> {
     let mut diagnostics = SemanticDiagnostics::new(lookup_item_id.parent_module(db));
     // TODO(spapini): when code changes in a file, all the AST items change (as they contain a path
     // to the green root that changes. Once ASTs are rooted on items, use a selector that picks only
     // the item instead of all the module data.
     // TODO(spapini): Add generic args when they are supported on structs.
-    let err = Err(diagnostics.report(impl_alias_ast.name(db).stable_ptr(db), ImplAliasCycle));
+    let err = Err(diagnostics
+        .report(impl_alias_ast.name(db).stable_ptr(db), ImplAliasCycle(Some(cycle_item))));
     diagnostics.extend(generic_params_data.diagnostics);
     let inference_id = InferenceId::LookupItemDeclaration(lookup_item_id);
     let attributes = impl_alias_ast.attributes(db).structurize(db);
@@ -1,9 +1,13 @@
-use cairo_lang_defs::ids::{ImplDefId, TraitTypeId};
+use cairo_lang_defs::ids::{ImplDefId, NamedLanguageElementId, TraitTypeId};
 use cairo_lang_diagnostics::Maybe;
+use cairo_lang_utils::Intern;
 use salsa::Database;
 
 use crate::TypeId;
-use crate::items::imp::ImplSemantic;
+use crate::items::generics::generic_params_to_args;
+use crate::items::imp::{ConcreteImplLongId, ImplLongId, ImplSemantic, impl_default_type};
+use crate::items::trt::TraitSemantic;
+use crate::substitution::GenericSubstitution;
 
 /// Implementation of [ImplizationSemantic::trait_type_implized_by_context].
 fn trait_type_implized_by_context<'db>(
@@ -11,11 +15,34 @@ fn trait_type_implized_by_context<'db>(
     trait_type_id: TraitTypeId<'db>,
     impl_def_id: ImplDefId<'db>,
 ) -> Maybe<TypeId<'db>> {
+    if db.impl_item_info_by_name(impl_def_id, trait_type_id.name(db))?.is_none()
+        && db.trait_type_default(trait_type_id)?.is_some()
+    {
+        return impl_default_type(db, impl_def_id, trait_type_id);
+    }
     let impl_type_def_id = db.impl_type_by_trait_type(impl_def_id, trait_type_id)?;
 
     db.impl_type_def_resolved_type(impl_type_def_id)
 }
 
+/// Returns the substitution implizing the defaults of the trait items of an impl definition - i.e.
+/// replacing the generic parameters of the trait by the generic arguments of the impl's trait, and
+/// the `Self` impl by the impl.
+pub(crate) fn impl_def_trait_defaults_substitution<'db>(
+    db: &'db dyn Database,
+    impl_def_id: ImplDefId<'db>,
+) -> Maybe<GenericSubstitution<'db>> {
+    let generic_args = generic_params_to_args(db.impl_def_generic_params(impl_def_id)?, db);
+    let self_impl =
+        ImplLongId::Concrete(ConcreteImplLongId { impl_def_id, generic_args }.intern(db))
+            .intern(db);
+    let concrete_trait_id = db.impl_def_concrete_trait(impl_def_id)?;
+    Ok(GenericSubstitution::from_impl(self_impl).concat(GenericSubstitution::new(
+        db.trait_generic_params(concrete_trait_id.trait_id(db))?,
+        concrete_trait_id.generic_args(db),
+    )))
+}
+
 /// Query implementation of [ImplizationSemantic::trait_type_implized_by_context].
 #[salsa::tracked(cycle_result=trait_type_implized_by_context_cycle)]
 fn trait_type_implized_by_context_tracked<'db>(
@@ -12,6 +12,7 @@ use crate::resolve::{ResolutionContext, ResolvedGenericItem, Resolver};
 
 pub mod attribute;
 pub mod constant;
+pub mod cycle;
 pub mod enm;
 pub mod extern_function;
 pub mod extern_type;
@@ -6,6 +6,7 @@ use cairo_lang_diagnostics::{Diagnostics, Maybe, MaybeAsRef, ToMaybe};
 use cairo_lang_proc_macros::DebugWithDb;
 use salsa::Database;
 
+use super::cycle::CycleItemId;
 use super::generics::GenericParamsData;
 use super::type_aliases::{
     TypeAliasData, type_alias_generic_params_data_helper, type_alias_semantic_data_cycle_helper,
@@ -49,6 +50,7 @@ fn module_type_alias_semantic_data<'db>(
             module_type_alias_ast,
             lookup_item_id,
             generic_params_data,
+            CycleItemId::TypeAlias(module_type_alias_id),
         )?
     } else {
         type_alias_semantic_data_helper(
@@ -20,6 +20,7 @@ cairo_lang_test_utils::test_file_test!(
         module: "module",
         early_conform: "early_conform",
         type_mismatch_diagnostics: "type_mismatch_diagnostics",
+        crates: "crates",
     },
     test_function_diagnostics,
     ["expect_diagnostics"]
@@ -8,7 +8,7 @@ use cairo_lang_defs::ids::{
     TraitConstantId, TraitConstantLongId, TraitFunctionId, TraitFunctionLongId, TraitId,
     TraitImplId, TraitImplLongId, TraitItemId, TraitTypeId, TraitTypeLongId, UseId,
 };
-use cairo_lang_diagnostics::{Diagnostics, DiagnosticsBuilder, Maybe, MaybeAsRef};
+use cairo_lang_diagnostics::{Diagnostics, DiagnosticsBuilder, Maybe, MaybeAsRef, skip_diagnostic};
 use cairo_lang_filesystem::ids::SmolStrId;
 use cairo_lang_proc_macros::{DebugWithDb, HeapSize, SemanticObject};
 use cairo_lang_syntax as syntax;
@@ -22,6 +22,7 @@ use cairo_lang_utils::{Intern, define_short_id};
 use salsa::Database;
 
 use super::TraitOrImplContext;
+use super::constant::{Constant, resolve_const_expr_and_validate};
 use super::feature_kind::FeatureKind;
 use super::function_with_body::{FunctionBodyData, get_implicit_precedence, get_inline_config};
 use super::functions::{
@@ -36,7 +37,9 @@ use super::imp::{GenericsHeadFilter, ImplLongId, TraitFilter};
 use crate::db::get_resolver_data_options;
 use crate::diagnostic::SemanticDiagnosticKind::{self, *};
 use crate::diagnostic::{NotFoundItemType, SemanticDiagnostics, SemanticDiagnosticsBuilder};
-use crate::expr::compute::{ComputationContext, ContextFunction, Environment, compute_root_expr};
+use crate::expr::compute::{
+    ComputationContext, ContextFunction, Environment, compute_expr_semantic, compute_root_expr,
+};
 use crate::expr::inference::InferenceId;
 use crate::expr::inference::canonic::ResultNoErrEx;
 use crate::items::feature_kind::HasFeatureKind;
@@ -499,7 +502,14 @@ fn trait_required_item_names<'db>(
                 let body = id.stable_ptr(db).lookup(db).body(db);
                 matches!(body, ast::MaybeTraitFunctionBody::None(_))
             }
-            TraitItemId::Type(_) | TraitItemId::Constant(_) => true,
+            TraitItemId::Type(id) => {
+                let default = id.stable_ptr(db).lookup(db).default(db);
+                matches!(default, ast::OptionTraitItemDefault::Empty(_))
+            }
+            TraitItemId::Constant(id) => {
+                let default = id.stable_ptr(db).lookup(db).default(db);
+                matches!(default, ast::OptionTraitItemDefault::Empty(_))
+            }
             TraitItemId::Impl(_) => false,
         } {
             required_items.insert(*item_name);
@@ -730,6 +740,8 @@ fn priv_trait_definition_data<'db>(
 struct TraitItemTypeData<'db> {
     pub diagnostics: Diagnostics<'db, SemanticDiagnostic<'db>>,
     pub attributes: Vec<Attribute<'db>>,
+    /// The default type, used by the impls not defining the type.
+    pub default: Option<TypeId<'db>>,
     pub resolver_data: Arc<ResolverData<'db>>,
 }
 
@@ -794,16 +806,22 @@ fn priv_trait_type_data<'db>(
     let inference_id = InferenceId::LookupItemDeclaration(LookupItemId::TraitItem(
         TraitItemId::Type(trait_type_id),
     ));
-    let resolver = Resolver::with_data(
+    let mut resolver = Resolver::with_data(
         db,
         (*type_generic_params_data.resolver_data).clone_with_inference_id(db, inference_id),
     );
     diagnostics.extend(type_generic_params_data.diagnostics.clone());
 
     let attributes = type_syntax.attributes(db).structurize(db);
+    let default = match type_syntax.default(db) {
+        ast::OptionTraitItemDefault::Empty(_) => None,
+        ast::OptionTraitItemDefault::TraitItemDefault(default) => {
+            Some(resolve_type(db, &mut diagnostics, &mut resolver, &default.value(db)))
+        }
+    };
     let resolver_data = Arc::new(resolver.data);
 
-    Ok(TraitItemTypeData { diagnostics: diagnostics.build(), attributes, resolver_data })
+    Ok(TraitItemTypeData { diagnostics: diagnostics.build(), attributes, default, resolver_data })
 }
 
 // === Trait item constant ===
@@ -844,6 +862,58 @@ fn priv_trait_constant_data<'db>(
     Ok(TraitItemConstantData { diagnostics: diagnostics.build(), ty, attributes, resolver_data })
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, DebugWithDb, salsa::Update)]
+#[debug_db(dyn Database)]
+struct TraitItemConstantDefaultData<'db> {
+    pub diagnostics: Diagnostics<'db, SemanticDiagnostic<'db>>,
+    /// The default value expression, evaluated for each impl not defining the constant.
+    pub constant: Maybe<Constant<'db>>,
+    pub resolver_data: Arc<ResolverData<'db>>,
+}
+
+/// Query implementation of [PrivTraitSemantic::priv_trait_constant_default_data].
+///
+/// Computed separately from [PrivTraitSemantic::priv_trait_constant_data], as the default value may
+/// refer to the other constants of the trait, whose types are required for its resolution.
+#[salsa::tracked(returns(ref))]
+fn priv_trait_constant_default_data<'db>(
+    db: &'db dyn Database,
+    trait_constant: TraitConstantId<'db>,
+) -> Maybe<Option<TraitItemConstantDefaultData<'db>>> {
+    let trait_id = trait_constant.trait_id(db);
+    let data = db.priv_trait_definition_data(trait_id)?;
+    let constant_syntax = &data.item_constant_asts[&trait_constant];
+    let ast::OptionTraitItemDefault::TraitItemDefault(default) = constant_syntax.default(db) else {
+        return Ok(None);
+    };
+    let mut diagnostics = SemanticDiagnostics::new(trait_constant.parent_module(db));
+    let constant_data = db.priv_trait_constant_data(trait_constant)?;
+    let inference_id = InferenceId::LookupItemDefinition(LookupItemId::TraitItem(
+        TraitItemId::Constant(trait_constant),
+    ));
+    let mut resolver = Resolver::with_data(
+        db,
+        constant_data.resolver_data.clone_with_inference_id(db, inference_id),
+    );
+
+    let mut ctx = ComputationContext::new_global(db, &mut diagnostics, &mut resolver);
+    let value = compute_expr_semantic(&mut ctx, &default.value(db));
+    let value = resolve_const_expr_and_validate(
+        &mut ctx,
+        &value,
+        default.stable_ptr(db).untyped(),
+        constant_data.ty,
+    );
+    let constant = value.map(|value| Constant { value, arenas: Arc::new(ctx.arenas) });
+    let resolver_data = Arc::new(resolver.data);
+
+    Ok(Some(TraitItemConstantDefaultData {
+        diagnostics: diagnostics.build(),
+        constant,
+        resolver_data,
+    }))
+}
+
 /// Implementation of [TraitSemantic::concrete_trait_constant_type].
 fn concrete_trait_constant_type<'db>(
     db: &'db dyn Database,
@@ -1104,8 +1174,20 @@ fn concrete_trait_function_signature<'db>(
 
 // === Body ===
 
+/// Cycle handling for [priv_trait_function_body_data].
+///
+/// A body only depends on itself through the evaluation of a constant calling the function, and the
+/// cycle is reported as a cycle of that constant.
+fn priv_trait_function_body_data_cycle<'db>(
+    _db: &'db dyn Database,
+    _id: salsa::Id,
+    _trait_function_id: TraitFunctionId<'db>,
+) -> Maybe<Option<FunctionBodyData<'db>>> {
+    Err(skip_diagnostic())
+}
+
 /// Query implementation of [TraitSemantic::priv_trait_function_body_data].
-#[salsa::tracked(returns(ref))]
+#[salsa::tracked(cycle_result=priv_trait_function_body_data_cycle, returns(ref))]
 fn priv_trait_function_body_data<'db>(
     db: &'db dyn Database,
     trait_function_id: TraitFunctionId<'db>,
@@ -1327,6 +1409,13 @@ pub trait TraitSemantic<'db>: Database {
     ) -> Maybe<Arc<ResolverData<'db>>> {
         Ok(self.priv_trait_type_data(trait_type_id)?.resolver_data.clone())
     }
+    /// Returns the default type of a trait type, if it has one.
+    fn trait_type_default(
+        &'db self,
+        trait_type_id: TraitTypeId<'db>,
+    ) -> Maybe<Option<TypeId<'db>>> {
+        Ok(self.priv_trait_type_data(trait_type_id)?.default)
+    }
 
     // Trait constants.
     // ================
@@ -1346,8 +1435,22 @@ pub trait TraitSemantic<'db>: Database {
         &'db self,
         id: TraitConstantId<'db>,
     ) -> Maybe<Arc<ResolverData<'db>>> {
+        if let Some(default_data) = self.priv_trait_constant_default_data(id)? {
+            return Ok(default_data.resolver_data.clone());
+        }
         Ok(self.priv_trait_constant_data(id)?.resolver_data.clone())
     }
+    /// Returns the default value of a trait constant, if it has one, as an expression to be
+    /// evaluated for each impl not defining the constant.
+    fn trait_constant_default(
+        &'db self,
+        id: TraitConstantId<'db>,
+    ) -> Maybe<Option<&'db Constant<'db>>> {
+        match self.priv_trait_constant_default_data(id)? {
+            Some(default_data) => Ok(Some(default_data.constant.maybe_as_ref()?)),
+            None => Ok(None),
+        }
+    }
     /// Returns the type of a trait constant.
     fn concrete_trait_constant_type(
         &'db self,
@@ -1544,7 +1647,14 @@ trait PrivTraitSemantic<'db>: Database {
         &'db self,
         id: TraitConstantId<'db>,
     ) -> Diagnostics<'db, SemanticDiagnostic<'db>> {
-        self.priv_trait_constant_data(id).map(|data| data.diagnostics.clone()).unwrap_or_default()
+        let mut diagnostics = DiagnosticsBuilder::default();
+        if let Ok(data) = self.priv_trait_constant_data(id) {
+            diagnostics.extend(data.diagnostics.clone());
+        }
+        if let Ok(Some(default_data)) = self.priv_trait_constant_default_data(id) {
+            diagnostics.extend(default_data.diagnostics.clone());
+        }
+        diagnostics.build()
     }
     /// Private query to compute data about a trait constant.
     fn priv_trait_constant_data(
@@ -1553,6 +1663,15 @@ trait PrivTraitSemantic<'db>: Database {
     ) -> Maybe<&'db TraitItemConstantData<'db>> {
         priv_trait_constant_data(self.as_dyn_database(), trait_constant).maybe_as_ref()
     }
+    /// Private query to compute data about the default value of a trait constant.
+    fn priv_trait_constant_default_data(
+        &'db self,
+        trait_constant: TraitConstantId<'db>,
+    ) -> Maybe<Option<&'db TraitItemConstantDefaultData<'db>>> {
+        Ok(priv_trait_constant_default_data(self.as_dyn_database(), trait_constant)
+            .maybe_as_ref()?
+            .as_ref())
+    }
     /// Private query to compute data about a trait impl.
     fn priv_trait_impl_data(
         &'db self,
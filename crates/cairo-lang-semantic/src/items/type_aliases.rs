@@ -7,6 +7,7 @@ use cairo_lang_syntax::attribute::structured::{Attribute, AttributeListStructuri
 use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode, ast};
 use salsa::Database;
 
+use super::cycle::CycleItemId;
 use super::generics::{GenericParamsData, semantic_generic_params};
 use crate::TypeId;
 use crate::diagnostic::SemanticDiagnosticKind::TypeAliasCycle;
@@ -93,9 +94,11 @@ pub fn type_alias_semantic_data_cycle_helper<'db>(
     type_alias_ast: &ast::ItemTypeAlias<'db>,
     lookup_item_id: LookupItemId<'db>,
     generic_params_data: GenericParamsData<'db>,
+    cycle_item: CycleItemId<'db>,
 ) -> Maybe<TypeAliasData<'db>> {
     let inference_id = InferenceId::LookupItemDeclaration(lookup_item_id);
-    let err = Err(diagnostics.report(type_alias_ast.name(db).stable_ptr(db), TypeAliasCycle));
+    let err = Err(diagnostics
+        .report(type_alias_ast.name(db).stable_ptr(db), TypeAliasCycle(Some(cycle_item))));
 
     let resolver = Resolver::with_data(
         db,
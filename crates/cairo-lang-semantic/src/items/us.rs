@@ -15,6 +15,7 @@ use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use itertools::{Itertools, chain};
 use salsa::Database;
 
+use super::cycle::CycleItemId;
 use super::module::get_module_global_uses;
 use super::visibility::peek_visible_in;
 use crate::SemanticDiagnostic;
@@ -184,7 +185,8 @@ fn priv_use_semantic_data_cycle<'db>(
     let module_id = use_id.parent_module(db);
     let mut diagnostics = SemanticDiagnostics::new(module_id);
     let use_ast = db.module_use_by_id(use_id)?;
-    let err = Err(diagnostics.report(use_ast.stable_ptr(db), UseCycle));
+    let err =
+        Err(diagnostics.report(use_ast.stable_ptr(db), UseCycle(Some(CycleItemId::Use(use_id)))));
     let inference_id =
         InferenceId::LookupItemDeclaration(LookupItemId::ModuleItem(ModuleItemId::Use(use_id)));
     Ok(Arc::new(UseData {
@@ -339,7 +341,7 @@ fn priv_global_use_semantic_data_tracked_initial<'db>(
             PathNotFound(NotFoundItemType::Identifier),
         )
     } else {
-        diagnostics.report(global_use_ast.stable_ptr(db), UseCycle)
+        diagnostics.report(global_use_ast.stable_ptr(db), UseCycle(None))
     };
     Ok(UseGlobalData { diagnostics: diagnostics.build(), imported_module: Err(err) })
 }
@@ -1,5 +1,5 @@
 use cairo_lang_defs::db::DefsGroup;
-use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_defs::ids::{LanguageElementId, ModuleId};
 use cairo_lang_syntax::node::{Terminal, TypedSyntaxNode, ast};
 use salsa::Database;
 
@@ -10,6 +10,8 @@ use crate::diagnostic::{SemanticDiagnosticKind, SemanticDiagnostics, SemanticDia
 pub enum Visibility {
     Public,
     PublicInCrate,
+    /// `pub(super)` - visible in the parent module of the containing module.
+    PublicInSuper,
     Private,
 }
 impl Visibility {
@@ -22,14 +24,16 @@ impl Visibility {
             ast::Visibility::Pub(visibility_pub) => match visibility_pub.argument_clause(db) {
                 ast::OptionVisibilityPubArgumentClause::Empty(_) => Self::Public,
                 ast::OptionVisibilityPubArgumentClause::VisibilityPubArgumentClause(argument) => {
-                    if argument.argument(db).text(db).long(db) == "crate" {
-                        Self::PublicInCrate
-                    } else {
-                        diagnostics.report(
-                            argument.stable_ptr(db),
-                            SemanticDiagnosticKind::UnsupportedPubArgument,
-                        );
-                        Self::Public
+                    match argument.argument(db).text(db).long(db).as_str() {
+                        "crate" => Self::PublicInCrate,
+                        "super" => Self::PublicInSuper,
+                        _ => {
+                            diagnostics.report(
+                                argument.stable_ptr(db),
+                                SemanticDiagnosticKind::UnsupportedPubArgument,
+                            );
+                            Self::Public
+                        }
                     }
                 }
             },
@@ -54,6 +58,17 @@ pub fn peek_visible_in<'db>(
         Visibility::PublicInCrate => {
             user_module_id.owning_crate(db) == containing_module_id.owning_crate(db)
         }
+        Visibility::PublicInSuper => {
+            let containing_module_id = db.module_perceived_module(containing_module_id);
+            let parent_module_id = match containing_module_id {
+                ModuleId::Submodule(submodule_id) => {
+                    db.module_perceived_module(submodule_id.parent_module(db))
+                }
+                // `pub(super)` in the crate root is visible in the entire crate.
+                _ => containing_module_id,
+            };
+            db.module_ancestors(user_module_id).contains(&parent_module_id)
+        }
         Visibility::Private => db
             .module_ancestors(user_module_id)
             .contains(&db.module_perceived_module(containing_module_id)),
@@ -6,12 +6,15 @@ pub mod cache;
 pub mod corelib;
 pub mod db;
 pub mod diagnostic;
+pub mod export;
 pub mod expr;
+pub mod expr_at_offset;
 pub mod helper;
 pub mod ids;
 pub mod inline_macros;
 pub mod items;
 pub mod keyword;
+pub mod lint;
 pub mod lookup_item;
 pub mod lsp_helpers;
 pub mod path;
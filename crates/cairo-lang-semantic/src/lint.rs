@@ -0,0 +1,125 @@
+//! The lints of the compiler - diagnostics of valid but suspicious code, whose level may be
+//! configured by the `lints` crate setting, and overridden by the `#[allow(...)]`, `#[warn(...)]`
+//! and `#[deny(...)]` attributes.
+//!
+//! The level of any warning may be configured the same way by its diagnostic code (e.g.
+//! `#[allow(E0001)]`), or by the [WARNINGS] lint group for all warnings (e.g. `#[deny(warnings)]`).
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_diagnostics::ErrorCode;
+use cairo_lang_filesystem::db::{FilesGroup, LintLevel, default_crate_settings};
+use cairo_lang_filesystem::ids::{CrateId, SpanInFile};
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_syntax::attribute::consts::{
+    ALLOW_ATTR, DENY_ATTR, DEPRECATED_ATTR, UNREACHABLE_PUB, UNUSED, UNUSED_IMPORTS,
+    UNUSED_MUST_USE, UNUSED_VARIABLES, WARN_ATTR, WARNINGS,
+};
+use cairo_lang_syntax::attribute::structured::AttributeStructurize;
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode};
+use salsa::Database;
+
+/// A lint of the compiler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lint {
+    /// The name of the lint, as used in attributes and crate settings.
+    pub name: &'static str,
+    /// The level of the lint, unless configured otherwise.
+    pub default_level: LintLevel,
+}
+
+/// The lints of the compiler.
+pub const BUILTIN_LINTS: [Lint; 5] = [
+    Lint { name: UNUSED_VARIABLES, default_level: LintLevel::Warn },
+    Lint { name: UNUSED_IMPORTS, default_level: LintLevel::Warn },
+    Lint { name: UNUSED_MUST_USE, default_level: LintLevel::Warn },
+    Lint { name: UNREACHABLE_PUB, default_level: LintLevel::Allow },
+    Lint { name: DEPRECATED_ATTR, default_level: LintLevel::Warn },
+];
+
+/// The lints of the `unused` lint group.
+const UNUSED_LINTS: [&str; 3] = [UNUSED_VARIABLES, UNUSED_IMPORTS, UNUSED_MUST_USE];
+
+/// Returns the default level of the lint with the given name.
+/// Lints which are not builtin (e.g. lints of plugins) are warnings by default.
+pub fn default_lint_level(name: &str) -> LintLevel {
+    BUILTIN_LINTS
+        .iter()
+        .find(|lint| lint.name == name)
+        .map_or(LintLevel::Warn, |lint| lint.default_level)
+}
+
+/// Returns the names of the lints configured by the given name - the lints of the group if it is a
+/// lint group, or the name itself otherwise.
+pub fn expand_lint_group(name: &str) -> Vec<&str> {
+    if name == UNUSED { UNUSED_LINTS.to_vec() } else { vec![name] }
+}
+
+/// Returns the name of the attribute setting lints to the given level.
+pub fn lint_level_attr(level: LintLevel) -> &'static str {
+    match level {
+        LintLevel::Allow => ALLOW_ATTR,
+        LintLevel::Warn => WARN_ATTR,
+        LintLevel::Deny => DENY_ATTR,
+    }
+}
+
+/// Returns whether the name configures the level of warnings by their diagnostic code or as a
+/// group, rather than the level of a lint.
+pub fn is_warning_level_name(name: &str) -> bool {
+    name == WARNINGS
+        || matches!(name.as_bytes(), [b'E', rest @ ..] if rest.len() == 4 && rest.iter().all(u8::is_ascii_digit))
+}
+
+/// Returns the level configured for a warning with the given code at the given location in the
+/// given crate - by the innermost `#[allow(...)]`, `#[warn(...)]` or `#[deny(...)]` attribute on
+/// the code or its modules, or else by the `lints` setting of the crate. The code takes precedence
+/// over the [WARNINGS] group in the crate settings, while in attributes the last attribute of the
+/// innermost item wins.
+///
+/// Returns `None` if the level of the warning is not configured.
+pub fn configured_warning_level<'db>(
+    db: &'db dyn Database,
+    crate_id: CrateId<'db>,
+    location: SpanInFile<'db>,
+    code: Option<ErrorCode>,
+) -> Option<LintLevel> {
+    let names: Vec<&str> = code.iter().map(ErrorCode::as_str).chain([WARNINGS]).collect();
+    let location = location.user_location(db);
+    let mut node = db
+        .file_syntax(location.file_id)
+        .ok()
+        .map(|root| root.lookup_offset(db, location.span.start));
+    while let Some(current) = node {
+        for ancestor in current.ancestors_with_self(db) {
+            let mut level = None;
+            for attr in ancestor.attributes_elements(db) {
+                let attr = attr.structurize(db);
+                let Some(attr_level) = [LintLevel::Allow, LintLevel::Warn, LintLevel::Deny]
+                    .into_iter()
+                    .find(|level| attr.id.long(db) == lint_level_attr(*level))
+                else {
+                    continue;
+                };
+                if names.iter().any(|name| attr.is_single_unnamed_arg(db, name)) {
+                    level = Some(attr_level);
+                }
+            }
+            if level.is_some() {
+                return level;
+            }
+        }
+        // Continue from the declaration of the file's module, if it is a submodule.
+        let file_id = current.stable_ptr(db).file_id(db);
+        node = match db.file_modules(file_id).ok().and_then(|modules| modules.first()) {
+            Some(ModuleId::Submodule(id)) => Some(id.stable_ptr(db).lookup(db).as_syntax_node()),
+            _ => None,
+        };
+    }
+    let settings = db
+        .crate_config(crate_id)
+        .map(|config| &config.settings)
+        .unwrap_or_else(|| default_crate_settings(db));
+    names.iter().find_map(|name| settings.lints.get(*name).copied())
+}
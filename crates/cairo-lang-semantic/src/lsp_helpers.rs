@@ -9,6 +9,7 @@ use cairo_lang_filesystem::db::{
     CORELIB_CRATE_NAME, FilesGroup, default_crate_settings, ext_as_virtual,
 };
 use cairo_lang_filesystem::ids::{CrateId, CrateLongId, FileId, FileLongId, SmolStrId, Tracked};
+use cairo_lang_filesystem::span::TextOffset;
 use cairo_lang_syntax::node::ast::ItemModule;
 use cairo_lang_syntax::node::helpers::GetIdentifier;
 use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode};
@@ -19,6 +20,7 @@ use itertools::chain;
 use salsa::Database;
 
 use crate::expr::inference::InferenceId;
+use crate::expr_at_offset::{ExprAtOffset, expr_at_offset};
 use crate::items::constant::ConstantSemantic;
 use crate::items::enm::EnumSemantic;
 use crate::items::free_function::FreeFunctionSemantic;
@@ -671,5 +673,16 @@ pub trait LspHelpers<'db>: Database {
     fn find_module_containing_node(&'db self, node: SyntaxNode<'db>) -> Option<ModuleId<'db>> {
         find_module_containing_node(self.as_dyn_database(), (), node)
     }
+
+    /// Returns the semantic information of the innermost expression containing the given offset in
+    /// a file - its inferred type, the type expected by its context and the definition it resolves
+    /// to. Returns `None` if the offset is not within an expression in a function body.
+    fn expr_at_offset(
+        &'db self,
+        file_id: FileId<'db>,
+        offset: TextOffset,
+    ) -> Option<ExprAtOffset<'db>> {
+        expr_at_offset(self.as_dyn_database(), file_id, offset)
+    }
 }
 impl<'db, T: Database + ?Sized> LspHelpers<'db> for T {}
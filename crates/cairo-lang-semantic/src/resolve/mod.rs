@@ -1303,15 +1303,13 @@ impl<'db> Resolver<'db> {
                 );
             }
             FeatureKind::Deprecated { feature, note }
-                if !self
-                    .data
-                    .feature_config
-                    .allowed_lints
-                    .contains(&SmolStrId::from(self.db, DEPRECATED_ATTR))
-                    && !self.data.feature_config.allowed_features.contains(feature) =>
+                if !feature.is_some_and(|feature| {
+                    self.data.feature_config.allowed_features.contains(&feature)
+                }) =>
             {
-                diagnostics.report(
+                diagnostics.report_lint(
                     identifier.stable_ptr(db),
+                    self.data.feature_config.lint_level(db, DEPRECATED_ATTR),
                     DeprecatedFeature { feature_name: *feature, note: *note },
                 );
             }
@@ -1845,17 +1843,17 @@ impl<'db, 'a> Resolution<'db, 'a> {
         self.resolver.insert_used_use(inner_item_info.item_id);
         let inner_generic_item =
             ResolvedGenericItem::from_module_item(db, inner_item_info.item_id)?;
+        self.resolver
+            .data
+            .resolved_items
+            .generic
+            .insert(identifier.stable_ptr(db), inner_generic_item.clone());
         let mut specialized_item = self.resolver.specialize_generic_module_item(
             self.diagnostics,
             identifier,
-            inner_generic_item.clone(),
+            inner_generic_item,
             generic_args_syntax.clone(),
         )?;
-        self.resolver
-            .data
-            .resolved_items
-            .generic
-            .insert(identifier.stable_ptr(db), inner_generic_item);
         self.resolver.handle_same_impl_trait(
             self.diagnostics,
             &mut specialized_item,
@@ -2411,6 +2409,11 @@ impl<'db, 'a> Resolution<'db, 'a> {
                     }
                 }
 
+                // Mark the trait item, as the impl item is reduced below, and may lose its origin.
+                self.resolver.resolved_items.generic.insert(
+                    identifier.stable_ptr(db),
+                    ResolvedGenericItem::TraitItem(trait_item_id),
+                );
                 match trait_item_id {
                     TraitItemId::Function(trait_function_id) => {
                         let generic_function_id = GenericFunctionId::Impl(ImplGenericFunctionId {
@@ -4,17 +4,21 @@ use cairo_lang_defs::db::{DefsGroup, init_defs_group, init_external_files};
 use cairo_lang_defs::ids::{FunctionWithBodyId, ModuleId};
 use cairo_lang_diagnostics::{Diagnostics, DiagnosticsBuilder};
 use cairo_lang_filesystem::db::{
-    CrateSettings, Edition, ExperimentalFeaturesConfig, init_dev_corelib, init_files_group,
+    CrateConfigurationInput, CrateSettings, DependencySettings, Edition,
+    ExperimentalFeaturesConfig, files_group_input, init_dev_corelib, init_files_group,
+    set_crate_configs_input,
 };
 use cairo_lang_filesystem::detect::detect_corelib;
 use cairo_lang_filesystem::ids::{
-    BlobId, CrateId, CrateLongId, FileKind, FileLongId, SmolStrId, VirtualFile,
+    BlobId, CrateId, CrateInput, CrateLongId, DirectoryInput, FileKind, FileLongId, SmolStrId,
+    VirtualFile,
 };
 use cairo_lang_parser::db::ParserGroup;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_test_utils::verify_diagnostics_expectation;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::{Intern, OptionFrom, extract_matches};
+use itertools::Itertools;
 use salsa::Database;
 
 use crate::db::{PluginSuiteInput, SemanticGroup, init_semantic_group};
@@ -90,6 +94,29 @@ pub struct TestModule<'a> {
     pub module_id: ModuleId<'a>,
 }
 
+/// Returns the settings of the test crates, when not given explicitly.
+fn default_test_crate_settings() -> CrateSettings {
+    CrateSettings {
+        name: None,
+        edition: Edition::default(),
+        version: None,
+        dependencies: Default::default(),
+        experimental_features: ExperimentalFeaturesConfig {
+            negative_impls: true,
+            associated_item_constraints: true,
+            coupons: true,
+            user_defined_inline_macros: true,
+            repr_ptrs: true,
+            fallback_impls: true,
+        },
+        cfg_set: Default::default(),
+        expected_cfg_names: Default::default(),
+        lints: Default::default(),
+        no_early_unsafe_panic: false,
+        flags: Default::default(),
+    }
+}
+
 /// Sets up a crate with given content, and returns its crate id.
 pub fn setup_test_crate_ex<'a>(
     db: &'a dyn Database,
@@ -109,20 +136,7 @@ pub fn setup_test_crate_ex<'a>(
     let settings: CrateSettings = if let Some(crate_settings) = crate_settings {
         toml::from_str(crate_settings).expect("Invalid config.")
     } else {
-        CrateSettings {
-            name: None,
-            edition: Edition::default(),
-            version: None,
-            dependencies: Default::default(),
-            experimental_features: ExperimentalFeaturesConfig {
-                negative_impls: true,
-                associated_item_constraints: true,
-                coupons: true,
-                user_defined_inline_macros: true,
-                repr_ptrs: true,
-            },
-            cfg_set: Default::default(),
-        }
+        default_test_crate_settings()
     };
 
     CrateLongId::Virtual {
@@ -239,6 +253,162 @@ pub fn setup_test_function_ex<'a>(
     }
 }
 
+/// A crate of a multi-crate test setup, see [setup_test_crates].
+pub struct TestCrateSpec {
+    /// The name of the crate, by which it is used from the crates depending on it.
+    pub name: String,
+    /// The files of the crate, by their `/` separated paths relative to the crate root, and their
+    /// content. Must include `lib.cairo`.
+    pub files: Vec<(String, String)>,
+    /// The names of the crates of the setup this crate depends on.
+    pub dependencies: Vec<String>,
+    /// The edition of the crate, if not the default one.
+    pub edition: Option<Edition>,
+    /// The plugins of the crate, overriding the default plugins of the database, if set.
+    pub plugin_suite: Option<PluginSuite>,
+}
+
+/// Parses the crates of a multi-crate test setup, given in the `crates` tag of test files.
+///
+/// Each crate starts with a `// crate: <name>` line, and is optionally followed by a
+/// `// dependencies: <name>, <name>` line declaring the crates it depends on, a
+/// `// edition: <edition>` line setting its edition, and a `// plugins: none` line disabling its
+/// plugins. The content of each file of the crate follows a
+/// `// file: <path>` line, and the content before the first such line is of `lib.cairo`. E.g.:
+/// ```text
+/// // crate: dep
+/// // file: lib.cairo
+/// pub mod utils;
+/// // file: utils.cairo
+/// pub fn foo() -> felt252 { 1 }
+/// ```
+pub fn parse_test_crates(crates: &str) -> Vec<TestCrateSpec> {
+    let mut specs: Vec<TestCrateSpec> = vec![];
+    // The index of the file the content lines are added to, in the files of the last crate.
+    let mut file_index = 0;
+    for line in crates.lines() {
+        if let Some(name) = line.strip_prefix("// crate: ") {
+            specs.push(TestCrateSpec {
+                name: name.trim().to_string(),
+                files: vec![("lib.cairo".to_string(), String::new())],
+                dependencies: vec![],
+                edition: None,
+                plugin_suite: None,
+            });
+            file_index = 0;
+            continue;
+        }
+        let spec = specs.last_mut().expect("Expected a `// crate: <name>` line first.");
+        if let Some(dependencies) = line.strip_prefix("// dependencies: ") {
+            spec.dependencies = dependencies.split(',').map(|dep| dep.trim().to_string()).collect();
+        } else if let Some(edition) = line.strip_prefix("// edition: ") {
+            spec.edition = Some(
+                toml::Value::String(edition.trim().to_string())
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("Invalid edition: `{edition}`.")),
+            );
+        } else if let Some(plugins) = line.strip_prefix("// plugins: ") {
+            assert_eq!(plugins.trim(), "none", "Unsupported plugins: `{plugins}`.");
+            spec.plugin_suite = Some(PluginSuite::default());
+        } else if let Some(path) = line.strip_prefix("// file: ") {
+            let path = path.trim();
+            file_index = match spec.files.iter().position(|(file_path, _)| file_path == path) {
+                Some(0) => 0,
+                Some(_) => panic!("Duplicate file `{path}` in crate `{}`.", spec.name),
+                None => {
+                    spec.files.push((path.to_string(), String::new()));
+                    spec.files.len() - 1
+                }
+            };
+        } else {
+            let content = &mut spec.files[file_index].1;
+            content.push_str(line);
+            content.push('\n');
+        }
+    }
+    specs
+}
+
+/// Sets up the crates of a multi-crate test setup in the database, as crates with the virtual
+/// files of the specs. Use [test_crate_id] for their crate ids.
+/// `db` must not share its storage with other live database handles.
+pub fn setup_test_crates(db: &mut dyn Database, specs: Vec<TestCrateSpec>) {
+    let db_ref: &dyn Database = db;
+    let mut crate_configs = files_group_input(db_ref).crate_configs(db_ref).clone().unwrap();
+    let mut plugin_suites = vec![];
+    for TestCrateSpec { name, files, dependencies, edition, plugin_suite } in specs {
+        let mut settings = default_test_crate_settings();
+        if let Some(edition) = edition {
+            settings.edition = edition;
+        }
+        settings.dependencies = dependencies
+            .into_iter()
+            .map(|dependency| (dependency, DependencySettings { discriminator: None }))
+            .collect();
+        let crate_input = CrateInput::Real { name, discriminator: None };
+        crate_configs.insert(
+            crate_input.clone(),
+            CrateConfigurationInput {
+                root: DirectoryInput::from_virtual_files(files),
+                settings,
+                cache_file: None,
+            },
+        );
+        if let Some(plugin_suite) = plugin_suite {
+            plugin_suites.push((crate_input, plugin_suite));
+        }
+    }
+    set_crate_configs_input(db, Some(crate_configs));
+    for (crate_input, plugin_suite) in plugin_suites {
+        db.set_override_crate_input_plugins_from_suite(crate_input, plugin_suite);
+    }
+}
+
+/// Returns the crate id of the crate named `name`, set up by [setup_test_crates].
+pub fn test_crate_id<'a>(db: &'a dyn Database, name: &str) -> CrateId<'a> {
+    CrateLongId::Real { name: SmolStrId::from(db, name), discriminator: None }.intern(db)
+}
+
+/// Returns the semantic model of a given function, as [setup_test_function], where the test crate
+/// depends on the crates given in `inputs["crates"]` (see [parse_test_crates]), which are set up
+/// in the database.
+/// The diagnostics also include the diagnostics of these crates.
+/// Note that setting up the crates sets database inputs, so `db` must not share its storage with
+/// other live database handles (e.g. a clone of a shared database).
+pub fn setup_test_function_with_crates<'a>(
+    db: &'a mut dyn Database,
+    inputs: &'a OrderedHashMap<String, String>,
+) -> WithStringDiagnostics<TestFunction<'a>> {
+    let Some(crates) = inputs.get("crates") else {
+        return setup_test_function(db, inputs);
+    };
+    let specs = parse_test_crates(crates);
+    let crate_names = specs.iter().map(|spec| spec.name.clone()).collect_vec();
+    setup_test_crates(db, specs);
+    let db: &'a dyn Database = db;
+
+    let mut settings = inputs
+        .get("crate_settings")
+        .map_or_else(default_test_crate_settings, |s| toml::from_str(s).expect("Invalid config."));
+    for name in &crate_names {
+        settings.dependencies.insert(name.clone(), DependencySettings { discriminator: None });
+    }
+    let (test_function, diagnostics) = setup_test_function_ex(
+        db,
+        &inputs["function_code"],
+        &inputs["function_name"],
+        inputs.get("module_code").map_or("", String::as_str),
+        Some(&toml::to_string_pretty(&settings).unwrap()),
+        None,
+    )
+    .split();
+    let crates_diagnostics = crate_names
+        .iter()
+        .map(|name| get_crate_semantic_diagnostics(db, test_crate_id(db, name)).format(db))
+        .join("");
+    WithStringDiagnostics { value: test_function, diagnostics: crates_diagnostics + &diagnostics }
+}
+
 /// Helper struct for the return value of [setup_test_expr] and [setup_test_block].
 pub struct TestExpr<'a> {
     pub module_id: ModuleId<'a>,
@@ -328,9 +498,14 @@ pub fn test_function_diagnostics(
     inputs: &OrderedHashMap<String, String>,
     args: &OrderedHashMap<String, String>,
 ) -> TestRunnerResult {
-    let db = &SemanticDatabaseForTesting::default();
+    // Setting up the crates sets database inputs, which is blocked while the storage is shared.
+    let db = &mut if inputs.contains_key("crates") {
+        SemanticDatabaseForTesting::new_empty()
+    } else {
+        SemanticDatabaseForTesting::default()
+    };
 
-    let diagnostics = setup_test_function(db, inputs).get_diagnostics();
+    let diagnostics = setup_test_function_with_crates(db, inputs).get_diagnostics();
     let error = verify_diagnostics_expectation(args, &diagnostics);
 
     TestRunnerResult {
@@ -179,6 +179,13 @@ impl<'db> Usages<'db> {
         usages
     }
 
+    /// Computes the [Usage] of a single expression, e.g. of a match arm guard.
+    pub fn expr_usage(arenas: &Arenas<'db>, expr: ExprId) -> Usage<'db> {
+        let mut usage = Usage::default();
+        Self { usages: Default::default() }.handle_expr(arenas, expr, &mut usage);
+        usage
+    }
+
     pub fn handle_closure(
         &mut self,
         arenas: &Arenas<'db>,
@@ -366,6 +373,9 @@ impl<'db> Usages<'db> {
                     for pattern in &arm.patterns {
                         Self::handle_pattern(&arenas.patterns, *pattern, current);
                     }
+                    if let Some(guard) = arm.guard {
+                        self.handle_expr(arenas, guard, current);
+                    }
                     self.handle_expr(arenas, arm.expression, current);
                 }
             }
@@ -406,7 +416,10 @@ impl<'db> Usages<'db> {
                 for (expr_id, _) in &expr.members {
                     self.handle_expr(arenas, *expr_id, current);
                 }
-                if let Some(base) = &expr.base_struct {
+                // A member path base struct is only used through the member accesses in `members`.
+                if let Some(base) = &expr.base_struct
+                    && arenas.exprs[*base].as_member_path().is_none()
+                {
                     self.handle_expr(arenas, *base, current);
                 }
             }
@@ -423,7 +436,7 @@ impl<'db> Usages<'db> {
     fn handle_pattern(arena: &PatternArena<'db>, pattern: PatternId, current: &mut Usage<'db>) {
         let pattern = &arena[pattern];
         match pattern {
-            Pattern::Literal(_) | Pattern::StringLiteral(_) => {}
+            Pattern::Literal(_) | Pattern::Range(_) | Pattern::StringLiteral(_) => {}
             Pattern::Variable(pattern) => {
                 current.introductions.insert(VarId::Local(pattern.var.id));
             }
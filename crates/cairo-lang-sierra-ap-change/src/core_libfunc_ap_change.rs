@@ -42,6 +42,7 @@ use cairo_lang_sierra::extensions::range::IntRangeConcreteLibfunc;
 use cairo_lang_sierra::extensions::starknet::StarknetConcreteLibfunc;
 use cairo_lang_sierra::extensions::starknet::testing::TestingConcreteLibfunc;
 use cairo_lang_sierra::extensions::structure::StructConcreteLibfunc;
+use cairo_lang_sierra::extensions::utils::Range;
 use cairo_lang_sierra::ids::ConcreteTypeId;
 use num_bigint::BigInt;
 use num_traits::{One, Zero};
@@ -157,6 +158,9 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
             EcConcreteLibfunc::StateFinalize(_) => vec![ApChange::Known(11), ApChange::Known(3)],
             EcConcreteLibfunc::StateInit(_) => vec![ApChange::Known(7)],
             EcConcreteLibfunc::StateAddMul(_) => vec![ApChange::Known(0)],
+            EcConcreteLibfunc::StateAddMulMany(libfunc) => {
+                vec![ApChange::Known(2 * (libfunc.count - 1))]
+            }
             EcConcreteLibfunc::PointFromX(_) => vec![ApChange::Known(11), ApChange::Known(7)],
             EcConcreteLibfunc::UnwrapPoint(_) => vec![ApChange::Known(0)],
             EcConcreteLibfunc::Zero(_) => vec![ApChange::Known(0)],
@@ -319,7 +323,9 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
         },
         Felt252Dict(libfunc) => match libfunc {
             Felt252DictConcreteLibfunc::New(_) => vec![ApChange::Known(6)],
-            Felt252DictConcreteLibfunc::Squash(_) => vec![ApChange::Unknown],
+            Felt252DictConcreteLibfunc::Squash(_) | Felt252DictConcreteLibfunc::SquashMany(_) => {
+                vec![ApChange::Unknown]
+            }
         },
         Pedersen(libfunc) => match libfunc {
             PedersenConcreteLibfunc::PedersenHash(_) => vec![ApChange::Known(0)],
@@ -405,21 +411,22 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
             | BoundedIntConcreteLibfunc::Sub(_)
             | BoundedIntConcreteLibfunc::Mul(_) => vec![ApChange::Known(0)],
             BoundedIntConcreteLibfunc::DivRem(libfunc) => {
-                vec![ApChange::Known(
-                    match BoundedIntDivRemAlgorithm::try_new(&libfunc.lhs, &libfunc.rhs).unwrap() {
-                        BoundedIntDivRemAlgorithm::KnownSmallRhs => 5,
-                        BoundedIntDivRemAlgorithm::KnownSmallQuotient { .. } => 6,
-                        BoundedIntDivRemAlgorithm::KnownSmallLhs { .. } => 7,
-                    },
-                )]
+                vec![ApChange::Known(bounded_int_div_rem_ap_change(&libfunc.lhs, &libfunc.rhs))]
             }
             BoundedIntConcreteLibfunc::Constrain(libfunc) => {
-                vec![
-                    ApChange::Known(
-                        1 + if libfunc.boundary == BigInt::one().shl(128) { 0 } else { 1 },
-                    ),
-                    ApChange::Known(1 + if libfunc.boundary.is_zero() { 0 } else { 1 }),
-                ]
+                bounded_int_constrain_ap_changes(&libfunc.boundary).map(ApChange::Known).to_vec()
+            }
+            BoundedIntConcreteLibfunc::AddConstrain(libfunc)
+            | BoundedIntConcreteLibfunc::MulConstrain(libfunc) => {
+                bounded_int_constrain_ap_changes(&libfunc.boundary)
+                    .map(|ap_change| ApChange::Known(ap_change + 1))
+                    .to_vec()
+            }
+            BoundedIntConcreteLibfunc::DivRemConstrain(libfunc) => {
+                let div_rem_ap_change = bounded_int_div_rem_ap_change(&libfunc.lhs, &libfunc.rhs);
+                bounded_int_constrain_ap_changes(&libfunc.boundary)
+                    .map(|ap_change| ApChange::Known(ap_change + div_rem_ap_change))
+                    .to_vec()
             }
             BoundedIntConcreteLibfunc::TrimMin(libfunc)
             | BoundedIntConcreteLibfunc::TrimMax(libfunc) => {
@@ -524,3 +531,20 @@ fn sint_ap_change<TSintTraits: SintTraits + IntMulTraits>(
         SintConcrete::Diff(_) => vec![ApChange::Known(2), ApChange::Known(3)],
     }
 }
+
+/// Returns the ap change of the bounded int div rem libfunc for the given ranges.
+fn bounded_int_div_rem_ap_change(lhs: &Range, rhs: &Range) -> usize {
+    match BoundedIntDivRemAlgorithm::try_new(lhs, rhs).unwrap() {
+        BoundedIntDivRemAlgorithm::KnownSmallRhs => 5,
+        BoundedIntDivRemAlgorithm::KnownSmallQuotient { .. } => 6,
+        BoundedIntDivRemAlgorithm::KnownSmallLhs { .. } => 7,
+    }
+}
+
+/// Returns the ap changes of the branches of constraining a bounded int by the given boundary.
+fn bounded_int_constrain_ap_changes(boundary: &BigInt) -> [usize; 2] {
+    [
+        1 + if *boundary == BigInt::one().shl(128) { 0 } else { 1 },
+        1 + if boundary.is_zero() { 0 } else { 1 },
+    ]
+}
@@ -4,8 +4,9 @@ use cairo_lang_sierra::program::StatementIdx;
 
 use crate::core_libfunc_cost_base::{self, CostOperations, FunctionCostInfo};
 pub use crate::core_libfunc_cost_base::{
-    DICT_SQUASH_FIXED_COST, DICT_SQUASH_REPEATED_ACCESS_COST, DICT_SQUASH_UNIQUE_KEY_COST,
-    InvocationCostInfoProvider, SEGMENT_ARENA_ALLOCATION_COST,
+    DICT_SQUASH_FIXED_COST, DICT_SQUASH_MANY_BASE_COST, DICT_SQUASH_MANY_PER_DICT_COST,
+    DICT_SQUASH_REPEATED_ACCESS_COST, DICT_SQUASH_UNIQUE_KEY_COST, InvocationCostInfoProvider,
+    SEGMENT_ARENA_ALLOCATION_COST, dict_squash_many_fixed_cost,
 };
 use crate::gas_info::GasInfo;
 pub use crate::starknet_libfunc_cost_base::SYSTEM_CALL_COST;
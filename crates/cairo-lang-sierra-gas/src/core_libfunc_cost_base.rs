@@ -47,6 +47,7 @@ use cairo_lang_sierra::extensions::poseidon::PoseidonConcreteLibfunc;
 use cairo_lang_sierra::extensions::qm31::QM31Concrete;
 use cairo_lang_sierra::extensions::range::IntRangeConcreteLibfunc;
 use cairo_lang_sierra::extensions::structure::StructConcreteLibfunc;
+use cairo_lang_sierra::extensions::utils::Range;
 use cairo_lang_sierra::ids::{ConcreteTypeId, FunctionId};
 use cairo_lang_sierra::program::{Function, StatementIdx};
 use cairo_lang_utils::casts::IntoOrPanic;
@@ -74,6 +75,14 @@ pub const DICT_SQUASH_REPEATED_ACCESS_COST: ConstCost =
 pub const DICT_SQUASH_FIXED_COST: ConstCost =
     ConstCost { steps: 57, holes: 0, range_checks: 3, range_checks96: 0 };
 
+/// The cost of `felt252_dict_squash_many` not dependent on the number of dicts, keys and accesses.
+pub const DICT_SQUASH_MANY_BASE_COST: ConstCost =
+    ConstCost { steps: 19, holes: 0, range_checks: 0, range_checks96: 0 };
+/// The cost of `felt252_dict_squash_many` per squashed dict, not dependent on the number of keys
+/// and accesses.
+pub const DICT_SQUASH_MANY_PER_DICT_COST: ConstCost =
+    ConstCost { steps: 42, holes: 0, range_checks: 3, range_checks96: 0 };
+
 /// The cost of allocating a segment in the segment arena. This is charged to pay for the
 /// finalization step of the segment arena.
 pub const SEGMENT_ARENA_ALLOCATION_COST: ConstCost =
@@ -235,6 +244,15 @@ pub fn core_libfunc_cost(
                     pre_cost: PreCost::builtin(CostTokenType::EcOp),
                 }]
             }
+            EcConcreteLibfunc::StateAddMulMany(libfunc) => {
+                let count: i32 = libfunc.count.into_or_panic();
+                // 5 steps per builtin instance, and 2 more for copying the accumulated state into
+                // each instance but the first.
+                vec![BranchCost::Regular {
+                    const_cost: ConstCost::steps(7 * count - 2),
+                    pre_cost: PreCost::n_builtins(CostTokenType::EcOp, count),
+                }]
+            }
             EcConcreteLibfunc::PointFromX(_) => vec![
                 (ConstCost::steps(14) + ConstCost::range_checks(3)).into(), // Success.
                 ConstCost::steps(9).into(),                                 // Failure.
@@ -446,6 +464,10 @@ pub fn core_libfunc_cost(
                 // for each subsequent access to an existing key.
                 vec![DICT_SQUASH_FIXED_COST.into()]
             }
+            Felt252DictConcreteLibfunc::SquashMany(libfunc) => {
+                // Same as `dict_squash`, but with the fixed cost shared between the dicts.
+                vec![dict_squash_many_fixed_cost(libfunc.count).into()]
+            }
         },
         Felt252SquashedDict(_) => vec![ConstCost::default().into()],
         Pedersen(libfunc) => match libfunc {
@@ -515,38 +537,22 @@ pub fn core_libfunc_cost(
             | BoundedIntConcreteLibfunc::Sub(_)
             | BoundedIntConcreteLibfunc::Mul(_) => vec![ConstCost::steps(0).into()],
             BoundedIntConcreteLibfunc::DivRem(libfunc) => {
-                vec![
-                    match BoundedIntDivRemAlgorithm::try_new(&libfunc.lhs, &libfunc.rhs).unwrap() {
-                        BoundedIntDivRemAlgorithm::KnownSmallRhs => {
-                            ConstCost { steps: 7, holes: 0, range_checks: 3, range_checks96: 0 }
-                        }
-                        BoundedIntDivRemAlgorithm::KnownSmallQuotient { .. } => {
-                            ConstCost { steps: 9, holes: 0, range_checks: 4, range_checks96: 0 }
-                        }
-                        BoundedIntDivRemAlgorithm::KnownSmallLhs { .. } => {
-                            ConstCost { steps: 11, holes: 0, range_checks: 4, range_checks96: 0 }
-                        }
-                    }
-                    .into(),
-                ]
+                vec![bounded_int_div_rem_cost(&libfunc.lhs, &libfunc.rhs).into()]
             }
             BoundedIntConcreteLibfunc::Constrain(libfunc) => {
-                vec![
-                    (ConstCost {
-                        steps: 2 + if libfunc.boundary == BigInt::one().shl(128) { 0 } else { 1 },
-                        holes: 0,
-                        range_checks: 1,
-                        range_checks96: 0,
-                    })
-                    .into(),
-                    (ConstCost {
-                        steps: 3 + if libfunc.boundary.is_zero() { 0 } else { 1 },
-                        holes: 0,
-                        range_checks: 1,
-                        range_checks96: 0,
-                    })
-                    .into(),
-                ]
+                bounded_int_constrain_costs(&libfunc.boundary).map(Into::into).to_vec()
+            }
+            BoundedIntConcreteLibfunc::AddConstrain(libfunc)
+            | BoundedIntConcreteLibfunc::MulConstrain(libfunc) => {
+                bounded_int_constrain_costs(&libfunc.boundary)
+                    .map(|cost| (cost + ConstCost::steps(1)).into())
+                    .to_vec()
+            }
+            BoundedIntConcreteLibfunc::DivRemConstrain(libfunc) => {
+                let div_rem_cost = bounded_int_div_rem_cost(&libfunc.lhs, &libfunc.rhs);
+                bounded_int_constrain_costs(&libfunc.boundary)
+                    .map(|cost| (cost + div_rem_cost).into())
+                    .to_vec()
             }
             BoundedIntConcreteLibfunc::TrimMin(libfunc)
             | BoundedIntConcreteLibfunc::TrimMax(libfunc) => {
@@ -1010,3 +1016,49 @@ fn felt252_libfunc_cost(libfunc: &Felt252Concrete) -> Vec<ConstCost> {
         }
     }
 }
+
+/// Returns the cost of the bounded int div rem libfunc for the given ranges.
+fn bounded_int_div_rem_cost(lhs: &Range, rhs: &Range) -> ConstCost {
+    match BoundedIntDivRemAlgorithm::try_new(lhs, rhs).unwrap() {
+        BoundedIntDivRemAlgorithm::KnownSmallRhs => {
+            ConstCost { steps: 7, holes: 0, range_checks: 3, range_checks96: 0 }
+        }
+        BoundedIntDivRemAlgorithm::KnownSmallQuotient { .. } => {
+            ConstCost { steps: 9, holes: 0, range_checks: 4, range_checks96: 0 }
+        }
+        BoundedIntDivRemAlgorithm::KnownSmallLhs { .. } => {
+            ConstCost { steps: 11, holes: 0, range_checks: 4, range_checks96: 0 }
+        }
+    }
+}
+
+/// Returns the costs of the branches of constraining a bounded int by the given boundary.
+fn bounded_int_constrain_costs(boundary: &BigInt) -> [ConstCost; 2] {
+    [
+        ConstCost {
+            steps: 2 + if *boundary == BigInt::one().shl(128) { 0 } else { 1 },
+            holes: 0,
+            range_checks: 1,
+            range_checks96: 0,
+        },
+        ConstCost {
+            steps: 3 + if boundary.is_zero() { 0 } else { 1 },
+            holes: 0,
+            range_checks: 1,
+            range_checks96: 0,
+        },
+    ]
+}
+
+/// Returns the cost of squashing `count` dicts using `felt252_dict_squash_many`, not dependent on
+/// the number of keys and accesses.
+pub fn dict_squash_many_fixed_cost(count: usize) -> ConstCost {
+    let count: i32 = count.into_or_panic();
+    ConstCost {
+        steps: DICT_SQUASH_MANY_BASE_COST.steps + count * DICT_SQUASH_MANY_PER_DICT_COST.steps,
+        holes: 0,
+        range_checks: DICT_SQUASH_MANY_BASE_COST.range_checks
+            + count * DICT_SQUASH_MANY_PER_DICT_COST.range_checks,
+        range_checks96: 0,
+    }
+}
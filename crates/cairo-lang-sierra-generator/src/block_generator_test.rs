@@ -1,13 +1,11 @@
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_filesystem::flag::{Flag, FlagsGroup};
-use cairo_lang_filesystem::ids::FlagLongId;
 use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_lowering::{self as lowering, LoweringStage, ids};
 use cairo_lang_semantic::test_utils::setup_test_function;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::Intern;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
-use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use lowering::fmt::LoweredFormatter;
 use lowering::ids::ConcreteFunctionWithBodyId;
 
@@ -40,8 +38,7 @@ fn block_generator_test(
 
     // Tests have recursions for revoking AP. Automatic addition of 'withdraw_gas` calls would add
     // unnecessary complication to them.
-    let add_withdraw_gas_flag_id = FlagLongId(Flag::ADD_WITHDRAW_GAS.into());
-    db.set_flag(add_withdraw_gas_flag_id, Some(Flag::AddWithdrawGas(false)));
+    db.set_flag(Flag::AddWithdrawGas(false));
 
     // Parse code and create semantic model.
     let (test_function, semantic_diagnostics) = setup_test_function(db, inputs).split();
@@ -68,7 +65,7 @@ fn block_generator_test(
     };
 
     // Generate (pre-)Sierra statements.
-    let lifetime = find_variable_lifetime(lowered, &OrderedHashSet::default())
+    let lifetime = find_variable_lifetime(lowered, &Default::default())
         .expect("Failed to retrieve lifetime information.");
     let expr_generator_context = ExprGeneratorContext::new(
         db,
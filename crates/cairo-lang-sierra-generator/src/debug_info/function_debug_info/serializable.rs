@@ -8,7 +8,7 @@ use crate::debug_info::{SourceCodeSpan, SourceFileFullPath};
 
 /// The serializable debug info of all sierra functions in the program.
 pub struct SerializableAllFunctionsDebugInfo(
-    pub(super) HashMap<SierraFunctionId, SerializableFunctionDebugInfo>,
+    pub HashMap<SierraFunctionId, SerializableFunctionDebugInfo>,
 );
 
 impl From<SerializableAllFunctionsDebugInfo> for Annotations {
@@ -4,15 +4,23 @@ use salsa::Database;
 use serde::{Deserialize, Serialize};
 
 mod function_debug_info;
+mod source_map;
 mod statements_locations;
+mod variable_locations;
 
 pub use function_debug_info::serializable::{
-    SerializableAllFunctionsDebugInfo, SerializableFunctionDebugInfo,
+    CairoVariableName, SerializableAllFunctionsDebugInfo, SerializableFunctionDebugInfo,
+    SierraFunctionId, SierraVarId,
 };
 pub use function_debug_info::{AllFunctionsDebugInfo, FunctionDebugInfo};
+pub use source_map::{SOURCE_MAP_VERSION, SourceMap, SourceMapEntry, SourceMapLocation};
 pub use statements_locations::StatementsLocations;
 pub use statements_locations::statements_code_locations::StatementsSourceCodeLocations;
 pub use statements_locations::statements_functions::StatementsFunctions;
+pub use variable_locations::{
+    VARIABLE_LOCATIONS_NAMESPACE, VARIABLE_LOCATIONS_VERSION, VariableLocation, VariableLocations,
+    VariableLocationsEntry,
+};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SierraProgramDebugInfo<'db> {
@@ -0,0 +1,145 @@
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::debug_info::{SourceCodeSpan, StatementsSourceCodeLocations};
+
+#[cfg(test)]
+#[path = "source_map_test.rs"]
+mod test;
+
+/// The version of the [SourceMap] serialization format.
+pub const SOURCE_MAP_VERSION: u32 = 1;
+
+/// A mapping from CASM pc ranges to the Sierra statements they were compiled from, and to the
+/// locations in the Cairo code that caused these statements to be generated.
+///
+/// Serialized (e.g. to JSON) as:
+/// ```json
+/// {
+///   "version": 1,
+///   "files": ["/path/to/lib.cairo"],
+///   "entries": [
+///     {
+///       "pc_start": 0,
+///       "pc_end": 2,
+///       "statement_idx": 0,
+///       "locations": [
+///         {
+///           "file": 0,
+///           "span": { "start": { "line": 1, "col": 4 }, "end": { "line": 1, "col": 9 } },
+///           "is_macro": false
+///         }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+/// Entries are sorted by pc, do not overlap, and only exist for statements that were compiled into
+/// at least one instruction. `pc_end` is not included in the range. `file` is an index into
+/// `files`. Locations are ordered from the innermost (inlined) code to the outermost call site.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SourceMap {
+    /// The version of the format, see [SOURCE_MAP_VERSION].
+    pub version: u32,
+    /// The full paths of the Cairo files referenced by the entries.
+    pub files: Vec<String>,
+    /// The mapping entries, sorted by pc.
+    pub entries: Vec<SourceMapEntry>,
+}
+
+/// The source information of a range of CASM instructions, generated from a single Sierra
+/// statement.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SourceMapEntry {
+    /// The first pc of the range.
+    pub pc_start: usize,
+    /// The end of the range, not included.
+    pub pc_end: usize,
+    /// The index of the Sierra statement the range was compiled from.
+    pub statement_idx: usize,
+    /// The Cairo locations that caused the statement to be generated.
+    pub locations: Vec<SourceMapLocation>,
+}
+
+/// A location in a Cairo file, referenced by a [SourceMapEntry].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SourceMapLocation {
+    /// The index of the file in [SourceMap::files].
+    pub file: usize,
+    /// The span in the file.
+    pub span: SourceCodeSpan,
+    /// Whether the location is a part of a macro expansion.
+    pub is_macro: bool,
+}
+
+impl SourceMap {
+    /// Builds a source map.
+    ///
+    /// # Arguments
+    /// * `statement_offsets` - The `(start, end)` CASM offsets of each Sierra statement, by
+    ///   statement index (e.g. from the `sierra_statement_info` of the CASM debug info).
+    /// * `code_locations` - The Cairo locations of the Sierra statements.
+    /// * `pc_offset` - The pc of the first instruction of the Sierra based code, in case the code
+    ///   is preceded by additional instructions (e.g. an entry code header).
+    pub fn new(
+        statement_offsets: impl IntoIterator<Item = (usize, usize)>,
+        code_locations: &StatementsSourceCodeLocations,
+        pc_offset: usize,
+    ) -> Self {
+        let mut files = OrderedHashSet::<String>::default();
+        let entries = statement_offsets
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (start, end))| start < end)
+            .map(|(statement_idx, (start, end))| {
+                let locations = code_locations
+                    .statements_to_code_location_map
+                    .get(&StatementIdx(statement_idx))
+                    .into_iter()
+                    .flatten()
+                    .map(|(path, span, is_macro)| {
+                        let (file, _) = files.insert_full(path.0.clone());
+                        SourceMapLocation { file, span: span.clone(), is_macro: *is_macro }
+                    })
+                    .collect();
+                SourceMapEntry {
+                    pc_start: pc_offset + start,
+                    pc_end: pc_offset + end,
+                    statement_idx,
+                    locations,
+                }
+            })
+            .collect();
+        Self { version: SOURCE_MAP_VERSION, files: files.into_iter().collect(), entries }
+    }
+
+    /// Shifts all the pcs of the map by `offset`, e.g. when the code is prefixed by a header.
+    pub fn offset_pcs(&mut self, offset: usize) {
+        for entry in &mut self.entries {
+            entry.pc_start += offset;
+            entry.pc_end += offset;
+        }
+    }
+
+    /// Returns the entry of the range containing `pc`, if any.
+    pub fn entry_at(&self, pc: usize) -> Option<&SourceMapEntry> {
+        let idx = self.entries.partition_point(|entry| entry.pc_end <= pc);
+        self.entries.get(idx).filter(|entry| entry.pc_start <= pc)
+    }
+
+    /// Returns the Sierra statement index the instruction at `pc` was compiled from, if any.
+    pub fn statement_at(&self, pc: usize) -> Option<StatementIdx> {
+        self.entry_at(pc).map(|entry| StatementIdx(entry.statement_idx))
+    }
+
+    /// Returns the Cairo locations of the instruction at `pc`, as `(file path, span, is_macro)`,
+    /// from the innermost to the outermost.
+    pub fn locations_at(&self, pc: usize) -> Vec<(&str, &SourceCodeSpan, bool)> {
+        self.entry_at(pc)
+            .into_iter()
+            .flat_map(|entry| &entry.locations)
+            .map(|location| (self.files[location.file].as_str(), &location.span, location.is_macro))
+            .collect()
+    }
+}
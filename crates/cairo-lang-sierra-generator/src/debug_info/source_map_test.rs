@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use cairo_lang_sierra::program::StatementIdx;
+use pretty_assertions::assert_eq;
+
+use super::{SOURCE_MAP_VERSION, SourceMap, SourceMapEntry, SourceMapLocation};
+use crate::debug_info::{
+    SourceCodeLocation, SourceCodeSpan, SourceFileFullPath, StatementsSourceCodeLocations,
+};
+
+fn span(line: usize, start_col: usize, end_col: usize) -> SourceCodeSpan {
+    SourceCodeSpan {
+        start: SourceCodeLocation { line, col: start_col },
+        end: SourceCodeLocation { line, col: end_col },
+    }
+}
+
+fn test_source_map() -> SourceMap {
+    let lib = SourceFileFullPath("lib.cairo".into());
+    let other = SourceFileFullPath("other.cairo".into());
+    let code_locations = StatementsSourceCodeLocations {
+        statements_to_code_location_map: HashMap::from([
+            (StatementIdx(0), vec![(lib.clone(), span(1, 4, 9), false)]),
+            (StatementIdx(1), vec![(lib.clone(), span(2, 0, 3), false)]),
+            (
+                StatementIdx(3),
+                vec![(other.clone(), span(7, 2, 5), true), (lib.clone(), span(3, 4, 8), false)],
+            ),
+        ]),
+    };
+    // Statement 1 is compiled into no instructions, statement 2 has no location.
+    SourceMap::new([(0, 2), (2, 2), (2, 3), (3, 6)], &code_locations, 10)
+}
+
+#[test]
+fn source_map_entries() {
+    assert_eq!(
+        test_source_map(),
+        SourceMap {
+            version: SOURCE_MAP_VERSION,
+            files: vec!["lib.cairo".into(), "other.cairo".into()],
+            entries: vec![
+                SourceMapEntry {
+                    pc_start: 10,
+                    pc_end: 12,
+                    statement_idx: 0,
+                    locations: vec![SourceMapLocation {
+                        file: 0,
+                        span: span(1, 4, 9),
+                        is_macro: false
+                    }],
+                },
+                SourceMapEntry { pc_start: 12, pc_end: 13, statement_idx: 2, locations: vec![] },
+                SourceMapEntry {
+                    pc_start: 13,
+                    pc_end: 16,
+                    statement_idx: 3,
+                    locations: vec![
+                        SourceMapLocation { file: 1, span: span(7, 2, 5), is_macro: true },
+                        SourceMapLocation { file: 0, span: span(3, 4, 8), is_macro: false },
+                    ],
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn source_map_queries() {
+    let source_map = test_source_map();
+    assert_eq!(source_map.statement_at(9), None);
+    assert_eq!(source_map.statement_at(10), Some(StatementIdx(0)));
+    assert_eq!(source_map.statement_at(11), Some(StatementIdx(0)));
+    assert_eq!(source_map.statement_at(12), Some(StatementIdx(2)));
+    assert_eq!(source_map.statement_at(15), Some(StatementIdx(3)));
+    assert_eq!(source_map.statement_at(16), None);
+    assert_eq!(source_map.locations_at(12), vec![]);
+    assert_eq!(
+        source_map.locations_at(14),
+        vec![("other.cairo", &span(7, 2, 5), true), ("lib.cairo", &span(3, 4, 8), false)]
+    );
+}
+
+#[test]
+fn source_map_serialization() {
+    let source_map = test_source_map();
+    let serialized = serde_json::to_string(&source_map).unwrap();
+    assert_eq!(serde_json::from_str::<SourceMap>(&serialized).unwrap(), source_map);
+}
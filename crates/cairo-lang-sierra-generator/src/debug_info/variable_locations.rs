@@ -0,0 +1,131 @@
+use cairo_lang_sierra::debug_info::Annotations;
+use cairo_lang_sierra::ids::VarId;
+use cairo_lang_sierra::program::Program;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::debug_info::{SerializableAllFunctionsDebugInfo, SierraFunctionId, SierraVarId};
+
+#[cfg(test)]
+#[path = "variable_locations_test.rs"]
+mod test;
+
+/// The version of the [VariableLocations] serialization format.
+pub const VARIABLE_LOCATIONS_VERSION: u32 = 1;
+
+/// The namespace of the [VariableLocations] in the [Annotations] of a program.
+pub const VARIABLE_LOCATIONS_NAMESPACE: &str = "github.com/starkware-libs/cairo/variable-locations";
+
+/// A mapping from CASM pc ranges to the memory cells holding the values of the Cairo variables
+/// alive at the start of each range.
+///
+/// Serialized (e.g. to JSON) as:
+/// ```json
+/// {
+///   "version": 1,
+///   "entries": [
+///     {
+///       "pc_start": 0,
+///       "pc_end": 2,
+///       "variables": [{ "name": "x", "sierra_var": 0, "cells": ["[fp + -3]"] }]
+///     }
+///   ]
+/// }
+/// ```
+/// Entries are sorted by pc and do not overlap. Each entry covers the instructions of a single
+/// Sierra statement, and the cells are relative to the `ap` and `fp` values at `pc_start`.
+/// A cell is either a memory reference (`[ap + 1]`, `[[fp + -3] + 2]`), an immediate value, or an
+/// operation on those (`[ap + -1] + 5`), for values that were not computed yet.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VariableLocations {
+    /// The version of the format, see [VARIABLE_LOCATIONS_VERSION].
+    pub version: u32,
+    /// The mapping entries, sorted by pc.
+    pub entries: Vec<VariableLocationsEntry>,
+}
+
+/// The Cairo variables alive at the start of a range of CASM instructions.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VariableLocationsEntry {
+    /// The first pc of the range.
+    pub pc_start: usize,
+    /// The end of the range, not included.
+    pub pc_end: usize,
+    /// The variables alive at `pc_start`.
+    pub variables: Vec<VariableLocation>,
+}
+
+/// The location of the value of a Cairo variable.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VariableLocation {
+    /// The name of the variable in the Cairo code.
+    pub name: String,
+    /// The id of the Sierra variable holding the value.
+    pub sierra_var: u64,
+    /// The memory cells of the value, one per felt252 of its representation.
+    pub cells: Vec<String>,
+}
+
+impl VariableLocations {
+    /// Builds the variable locations of a program.
+    ///
+    /// # Arguments
+    /// * `program` - The Sierra program, used to find the function of each statement.
+    /// * `statement_variables` - The `(start, end)` CASM offsets of each Sierra statement, by
+    ///   statement index, with the cells of the Sierra variables alive at its start.
+    /// * `functions_debug_info` - The debug info mapping Sierra variables to Cairo variables.
+    ///   Sierra variables without a corresponding Cairo variable are omitted.
+    pub fn new(
+        program: &Program,
+        statement_variables: impl IntoIterator<Item = ((usize, usize), Vec<(VarId, Vec<String>)>)>,
+        functions_debug_info: &SerializableAllFunctionsDebugInfo,
+    ) -> Self {
+        let entry_points = program
+            .funcs
+            .iter()
+            .map(|function| (function.entry_point.0, SierraFunctionId(function.id.id)))
+            .sorted_by_key(|(entry_point, _)| *entry_point)
+            .collect_vec();
+        let entries = statement_variables
+            .into_iter()
+            .enumerate()
+            .filter(|(_, ((start, end), _))| start < end)
+            .filter_map(|(statement_idx, ((pc_start, pc_end), variables))| {
+                let function_idx =
+                    entry_points.partition_point(|(entry_point, _)| *entry_point <= statement_idx);
+                let (_, function_id) = entry_points.get(function_idx.checked_sub(1)?)?;
+                let function_info = functions_debug_info.0.get(function_id)?;
+                let variables = variables
+                    .into_iter()
+                    .filter_map(|(var_id, cells)| {
+                        let (name, _) =
+                            function_info.sierra_to_cairo_variable.get(&SierraVarId(var_id.id))?;
+                        Some(VariableLocation { name: name.clone(), sierra_var: var_id.id, cells })
+                    })
+                    .collect_vec();
+                (!variables.is_empty()).then_some(VariableLocationsEntry {
+                    pc_start,
+                    pc_end,
+                    variables,
+                })
+            })
+            .collect();
+        Self { version: VARIABLE_LOCATIONS_VERSION, entries }
+    }
+
+    /// Returns the entry of the range containing `pc`, if any.
+    pub fn entry_at(&self, pc: usize) -> Option<&VariableLocationsEntry> {
+        let idx = self.entries.partition_point(|entry| entry.pc_end <= pc);
+        self.entries.get(idx).filter(|entry| entry.pc_start <= pc)
+    }
+}
+
+impl From<VariableLocations> for Annotations {
+    fn from(value: VariableLocations) -> Self {
+        OrderedHashMap::from([(
+            VARIABLE_LOCATIONS_NAMESPACE.to_string(),
+            serde_json::to_value(value).unwrap(),
+        )])
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use cairo_lang_sierra::ProgramParser;
+use cairo_lang_sierra::ids::VarId;
+use pretty_assertions::assert_eq;
+
+use super::{
+    VARIABLE_LOCATIONS_VERSION, VariableLocation, VariableLocations, VariableLocationsEntry,
+};
+use crate::debug_info::{
+    SerializableAllFunctionsDebugInfo, SerializableFunctionDebugInfo, SierraFunctionId,
+    SierraVarId, SourceCodeSpan, SourceFileFullPath,
+};
+
+fn function_info(variables: &[(u64, &str)]) -> SerializableFunctionDebugInfo {
+    SerializableFunctionDebugInfo {
+        function_file_path: SourceFileFullPath("lib.cairo".into()),
+        function_code_span: SourceCodeSpan::default(),
+        sierra_to_cairo_variable: variables
+            .iter()
+            .map(|(id, name)| (SierraVarId(*id), (name.to_string(), SourceCodeSpan::default())))
+            .collect(),
+    }
+}
+
+fn test_variable_locations() -> VariableLocations {
+    let program = ProgramParser::new()
+        .parse(
+            "type [0] = felt252;
+            libfunc [0] = store_temp<[0]>;
+            [0]([0]) -> ([1]);
+            return([1]);
+            [0]([0]) -> ([0]);
+            return([0]);
+            [1]@2([0]: [0]) -> ([0]);
+            [0]@0([0]: [0]) -> ([0]);",
+        )
+        .unwrap();
+    let functions_debug_info = SerializableAllFunctionsDebugInfo(HashMap::from([
+        (SierraFunctionId(0), function_info(&[(0, "a")])),
+        (SierraFunctionId(1), function_info(&[(0, "b"), (1, "c")])),
+    ]));
+    let cells = |cells: &[&str]| cells.iter().map(|cell| cell.to_string()).collect::<Vec<_>>();
+    VariableLocations::new(
+        &program,
+        [
+            ((0, 1), vec![(VarId::new(0), cells(&["[fp + -3]"]))]),
+            // Variable `[1]` has no name in the first function.
+            ((1, 2), vec![(VarId::new(1), cells(&["[ap + -1]"]))]),
+            // No instructions.
+            ((2, 2), vec![(VarId::new(0), cells(&["[fp + -3]"]))]),
+            ((2, 4), vec![(VarId::new(0), cells(&["[fp + -4]"])), (VarId::new(2), cells(&["5"]))]),
+        ],
+        &functions_debug_info,
+    )
+}
+
+#[test]
+fn variable_locations_entries() {
+    assert_eq!(
+        test_variable_locations(),
+        VariableLocations {
+            version: VARIABLE_LOCATIONS_VERSION,
+            entries: vec![
+                VariableLocationsEntry {
+                    pc_start: 0,
+                    pc_end: 1,
+                    variables: vec![VariableLocation {
+                        name: "a".into(),
+                        sierra_var: 0,
+                        cells: vec!["[fp + -3]".into()],
+                    }],
+                },
+                VariableLocationsEntry {
+                    pc_start: 2,
+                    pc_end: 4,
+                    variables: vec![VariableLocation {
+                        name: "b".into(),
+                        sierra_var: 0,
+                        cells: vec!["[fp + -4]".into()],
+                    }],
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn variable_locations_lookup() {
+    let variable_locations = test_variable_locations();
+    assert_eq!(variable_locations.entry_at(0).map(|entry| entry.pc_start), Some(0));
+    assert_eq!(variable_locations.entry_at(1), None);
+    assert_eq!(variable_locations.entry_at(3).map(|entry| entry.pc_start), Some(2));
+    assert_eq!(variable_locations.entry_at(4), None);
+}
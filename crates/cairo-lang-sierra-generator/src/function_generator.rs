@@ -9,7 +9,6 @@ use cairo_lang_lowering::{self as lowering, Lowered, LoweringStage};
 use cairo_lang_sierra::extensions::lib_func::SierraApChange;
 use cairo_lang_sierra::ids::ConcreteLibfuncId;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
-use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use itertools::{Itertools, zip_eq};
 use salsa::Database;
 
@@ -17,6 +16,7 @@ use crate::block_generator::generate_function_result;
 use crate::db::SierraGenGroup;
 use crate::expr_generator_context::ExprGeneratorContext;
 use crate::lifetime::{SierraGenVar, find_variable_lifetime};
+use crate::local_slots::{LocalSlots, pack_local_variables};
 use crate::local_variables::{AnalyzeApChangesResult, analyze_ap_changes};
 use crate::pre_sierra;
 use crate::store_variables::{LocalVariables, add_store_statements};
@@ -69,8 +69,8 @@ fn get_function_ap_change_and_code<'db>(
         analyze_ap_change_result;
 
     // Get lifetime information.
-    let local_variables = variables_info.local_variables.clone();
-    let lifetime = find_variable_lifetime(lowered_function, &local_variables)?;
+    let local_slots = pack_local_variables(lowered_function, &variables_info.local_variables);
+    let lifetime = find_variable_lifetime(lowered_function, &local_slots)?;
 
     let mut context = ExprGeneratorContext::new(
         db,
@@ -108,7 +108,7 @@ fn get_function_ap_change_and_code<'db>(
 
     context.push_statement(label);
 
-    let sierra_local_variables = allocate_local_variables(&mut context, &local_variables)?;
+    let sierra_local_variables = allocate_local_variables(&mut context, &local_slots)?;
 
     // Revoking ap tracking as the first non-local command for unknown ap-change function, to allow
     // proper ap-equation solving. TODO(orizi): Fix the solver to not require this constraint.
@@ -202,35 +202,37 @@ pub fn priv_get_dummy_function<'db>(
     })
 }
 
-/// Allocates space for the local variables.
+/// Allocates space for the local variables, once per slot (see [LocalSlots]).
 /// Returns:
 /// * A map from a Sierra variable that should be stored as local variable to its allocated space
 ///   (uninitialized local variable).
 /// * A list of Sierra statements.
 fn allocate_local_variables<'db>(
     context: &mut ExprGeneratorContext<'db, '_>,
-    local_variables: &OrderedHashSet<lowering::VariableId>,
+    local_slots: &LocalSlots,
 ) -> Maybe<LocalVariables> {
     let mut sierra_local_variables =
         OrderedHashMap::<cairo_lang_sierra::ids::VarId, cairo_lang_sierra::ids::VarId>::default();
-    for lowering_var_id in local_variables {
+    for (lowering_var_id, slot) in local_slots.iter() {
         let sierra_var_id = context.get_sierra_variable(*lowering_var_id);
         let uninitialized_local_var_id =
-            context.get_sierra_variable(SierraGenVar::UninitializedLocal(*lowering_var_id));
-        context.push_statement(simple_basic_statement(
-            alloc_local_libfunc_id(
-                context.get_db(),
-                context.get_variable_sierra_type(*lowering_var_id)?,
-            ),
-            &[],
-            std::slice::from_ref(&uninitialized_local_var_id),
-        ));
+            context.get_sierra_variable(SierraGenVar::UninitializedLocal(*slot));
+        if lowering_var_id == slot {
+            context.push_statement(simple_basic_statement(
+                alloc_local_libfunc_id(
+                    context.get_db(),
+                    context.get_variable_sierra_type(*lowering_var_id)?,
+                ),
+                &[],
+                std::slice::from_ref(&uninitialized_local_var_id),
+            ));
+        }
 
         sierra_local_variables.insert(sierra_var_id, uninitialized_local_var_id);
     }
 
     // Add finalize_locals() statement.
-    if !local_variables.is_empty() {
+    if !local_slots.is_empty() {
         context.push_statement(simple_basic_statement(
             finalize_locals_libfunc_id(context.get_db()),
             &[],
@@ -15,6 +15,7 @@ mod function_generator;
 pub mod function_generator_test_utils;
 mod id_allocator;
 mod lifetime;
+mod local_slots;
 mod local_variables;
 mod next_statement_index_fetch;
 pub mod pre_sierra;
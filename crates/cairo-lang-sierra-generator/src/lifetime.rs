@@ -15,6 +15,8 @@ use lowering::borrow_check::Demand;
 use lowering::borrow_check::demand::{AuxCombine, DemandReporter};
 use lowering::{Lowered, VarUsage};
 
+use crate::local_slots::LocalSlots;
+
 /// Represents the location where a drop statement for a variable should be added.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum DropLocation {
@@ -43,12 +45,12 @@ impl Debug for UseLocation {
 /// For example, uninitialized local variables do not have a representation as lowering
 /// [VariableId], since they are created in the sierra-generation phase.
 /// Instead, we refer to it as [SierraGenVar::UninitializedLocal] by the actual local variable
-/// (not the uninitialized version).
+/// (not the uninitialized version) representing its slot (see [LocalSlots]).
 #[derive(Clone, Copy, Eq, Hash, PartialEq)]
 pub enum SierraGenVar {
     /// Represents a regular variable.
     LoweringVar(VariableId),
-    /// Represents an uninitialized local variable, by the corresponding local variable.
+    /// Represents an uninitialized local variable, by the local variable representing its slot.
     UninitializedLocal(VariableId),
 }
 
@@ -94,14 +96,15 @@ impl VariableLifetimeResult {
     }
 }
 
-/// Given the lowering of a function, returns lifetime information for all the variables.
+/// Given the lowering of a function and the slots of its local variables, returns lifetime
+/// information for all the variables.
 /// See [VariableLifetimeResult].
 pub fn find_variable_lifetime<'db>(
     lowered_function: &Lowered<'db>,
-    local_vars: &OrderedHashSet<VariableId>,
+    local_slots: &LocalSlots,
 ) -> Maybe<VariableLifetimeResult> {
     lowered_function.blocks.has_root()?;
-    let context = VariableLifetimeContext { local_vars, res: VariableLifetimeResult::default() };
+    let context = VariableLifetimeContext { local_slots, res: VariableLifetimeResult::default() };
     let mut analysis = BackAnalysis::new(lowered_function, context);
 
     let mut root_demands = analysis.get_root_info();
@@ -126,7 +129,7 @@ pub fn find_variable_lifetime<'db>(
 
 /// Context information for [find_variable_lifetime] and its helper functions.
 struct VariableLifetimeContext<'a> {
-    local_vars: &'a OrderedHashSet<VariableId>,
+    local_slots: &'a LocalSlots,
     res: VariableLifetimeResult,
 }
 
@@ -212,11 +215,12 @@ impl<'db> Analyzer<'db, '_> for VariableLifetimeContext<'_> {
             }),
         );
         for (idx, (dst, _src)) in remapping.iter().enumerate() {
-            if self.local_vars.contains(dst) {
+            if let Some(slot) = self.local_slots.get(dst) {
                 assert!(
                     info.vars
+                        .to_mut()
                         .insert(
-                            SierraGenVar::UninitializedLocal(*dst),
+                            SierraGenVar::UninitializedLocal(*slot),
                             UseLocation { statement_location, idx }
                         )
                         .is_none(),
@@ -282,11 +286,12 @@ impl VariableLifetimeContext<'_> {
     ) {
         info.variables_introduced(self, vars, drop_location);
         for (idx, var_id) in vars.iter().enumerate() {
-            if self.local_vars.contains(var_id) {
+            if let Some(slot) = self.local_slots.get(var_id) {
                 assert!(
                     info.vars
+                        .to_mut()
                         .insert(
-                            SierraGenVar::UninitializedLocal(*var_id),
+                            SierraGenVar::UninitializedLocal(*slot),
                             UseLocation { statement_location, idx }
                         )
                         .is_none(),
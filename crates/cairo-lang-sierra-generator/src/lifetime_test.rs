@@ -8,6 +8,7 @@ use itertools::Itertools;
 use lowering::ids::ConcreteFunctionWithBodyId;
 
 use super::find_variable_lifetime;
+use crate::local_slots::pack_local_variables;
 use crate::local_variables::analyze_ap_changes;
 use crate::test_utils::SierraGenDatabaseForTesting;
 
@@ -51,7 +52,8 @@ fn check_variable_lifetime(
 
     let local_variables =
         analyze_ap_changes(db, lowered_function).unwrap().variables_info.local_variables;
-    let find_variable_lifetime_res = find_variable_lifetime(lowered_function, &local_variables)
+    let local_slots = pack_local_variables(lowered_function, &local_variables);
+    let find_variable_lifetime_res = find_variable_lifetime(lowered_function, &local_slots)
         .expect("find_variable_lifetime failed unexpectedly");
     let last_use_str = find_variable_lifetime_res
         .last_use
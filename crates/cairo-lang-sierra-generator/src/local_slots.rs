@@ -0,0 +1,118 @@
+//! Assignment of the local variables of a function to the local slots of its frame.
+
+use cairo_lang_lowering as lowering;
+use cairo_lang_lowering::{BlockId, VariableId};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use lowering::{BlockEnd, Lowered};
+
+/// A map from each local variable to the local variable whose slot it uses.
+pub type LocalSlots = OrderedHashMap<VariableId, VariableId>;
+
+/// Assigns the local variables to local slots, packing local variables of the same type into a
+/// single slot when no flow of the function introduces more than one of them.
+///
+/// Memory cells are written once per run of the function, so a slot can not be reused within a
+/// flow, but local variables that are introduced on mutually exclusive flows (for example, in
+/// different arms of a match) can share it.
+pub fn pack_local_variables(
+    lowered_function: &Lowered<'_>,
+    local_variables: &OrderedHashSet<VariableId>,
+) -> LocalSlots {
+    let introducing_blocks = find_introducing_blocks(lowered_function, local_variables);
+    let mut reachable_blocks = UnorderedHashMap::<BlockId, UnorderedHashSet<BlockId>>::default();
+    for block_id in local_variables.iter().flat_map(|var| &introducing_blocks[var]) {
+        if !reachable_blocks.contains_key(block_id) {
+            let reachable = find_reachable_blocks(lowered_function, *block_id);
+            reachable_blocks.insert(*block_id, reachable);
+        }
+    }
+    let are_exclusive = |var: VariableId, other: VariableId| {
+        introducing_blocks[&var].iter().all(|block_id| {
+            introducing_blocks[&other].iter().all(|other_block_id| {
+                !reachable_blocks[block_id].contains(other_block_id)
+                    && !reachable_blocks[other_block_id].contains(block_id)
+            })
+        })
+    };
+
+    // The local variables using each slot, by the local variable representing the slot.
+    let mut slots = OrderedHashMap::<VariableId, Vec<VariableId>>::default();
+    let mut local_slots = LocalSlots::default();
+    for var in local_variables.iter().copied() {
+        let ty = lowered_function.variables[var].ty;
+        let slot = slots
+            .iter()
+            .find(|(slot, vars)| {
+                lowered_function.variables[**slot].ty == ty
+                    && vars.iter().all(|other| are_exclusive(var, *other))
+            })
+            .map(|(slot, _)| *slot)
+            .unwrap_or(var);
+        slots.entry(slot).or_default().push(var);
+        local_slots.insert(var, slot);
+    }
+    local_slots
+}
+
+/// Returns the blocks introducing each of the given variables.
+/// A variable introduced by a goto is introduced by the block ending with the goto, and a variable
+/// introduced by a match arm is introduced by the block of the arm.
+fn find_introducing_blocks(
+    lowered_function: &Lowered<'_>,
+    vars: &OrderedHashSet<VariableId>,
+) -> UnorderedHashMap<VariableId, Vec<BlockId>> {
+    let mut introducing_blocks = UnorderedHashMap::<VariableId, Vec<BlockId>>::default();
+    let mut add = |var: &VariableId, block_id: BlockId| {
+        if vars.contains(var) {
+            introducing_blocks.entry(*var).or_default().push(block_id);
+        }
+    };
+    for (block_id, block) in lowered_function.blocks.iter() {
+        for var in block.statements.iter().flat_map(|statement| statement.outputs()) {
+            add(var, block_id);
+        }
+        match &block.end {
+            BlockEnd::Goto(_, remapping) => {
+                for var in remapping.keys() {
+                    add(var, block_id);
+                }
+            }
+            BlockEnd::Match { info } => {
+                for arm in info.arms() {
+                    for var in &arm.var_ids {
+                        add(var, arm.block_id);
+                    }
+                }
+            }
+            BlockEnd::Return(..) | BlockEnd::Panic(_) | BlockEnd::NotSet => {}
+        }
+    }
+    // Parameters are not introduced by any block, and are never packed with other variables.
+    for var in vars.iter() {
+        introducing_blocks.entry(*var).or_insert_with(|| vec![BlockId::root()]);
+    }
+    introducing_blocks
+}
+
+/// Returns the blocks reachable from the given block, including itself.
+fn find_reachable_blocks(
+    lowered_function: &Lowered<'_>,
+    block_id: BlockId,
+) -> UnorderedHashSet<BlockId> {
+    let mut reachable = UnorderedHashSet::<BlockId>::default();
+    let mut stack = vec![block_id];
+    while let Some(block_id) = stack.pop() {
+        if !reachable.insert(block_id) {
+            continue;
+        }
+        match &lowered_function.blocks[block_id].end {
+            BlockEnd::Goto(target, _) => stack.push(*target),
+            BlockEnd::Match { info } => stack.extend(info.arms().iter().map(|arm| arm.block_id)),
+            BlockEnd::Return(..) | BlockEnd::Panic(_) | BlockEnd::NotSet => {}
+        }
+    }
+    reachable
+}
@@ -1,8 +1,9 @@
 use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_defs::ids::ModuleItemId;
 use cairo_lang_filesystem::ids::SmolStrId;
-use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_lowering::db::{LoweringGroup, lowering_group_input};
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_lowering::optimizations::config::Optimizations;
 use cairo_lang_semantic::items::module::ModuleSemantic;
 use cairo_lang_semantic::test_utils::setup_test_function;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
@@ -11,6 +12,7 @@ use cairo_lang_utils::try_extract_matches;
 use indoc::indoc;
 use itertools::Itertools;
 use pretty_assertions::assert_eq;
+use salsa::Setter;
 use test_case::test_case;
 
 use super::get_dummy_program_for_size_estimation;
@@ -27,9 +29,11 @@ cairo_lang_test_utils::test_file_test!(
     {
         coupon: "coupon",
         function_call: "function_call",
+        string_literal_pooling: "string_literal_pooling",
         type_dependency: "type_dependency",
     },
-    test_program_generator
+    test_program_generator,
+    ["pool_string_literals"]
 );
 
 cairo_lang_test_utils::test_file_test!(
@@ -69,9 +73,20 @@ fn test_dummy_program_generator(
 
 fn test_program_generator(
     inputs: &OrderedHashMap<String, String>,
-    _args: &OrderedHashMap<String, String>,
+    args: &OrderedHashMap<String, String>,
 ) -> TestRunnerResult {
-    let program = checked_compile_to_sierra(inputs["cairo_code"].as_str());
+    let program = if args.get("pool_string_literals").is_some_and(|value| value == "true") {
+        let mut db = SierraGenDatabaseForTesting::new_empty();
+        lowering_group_input(&db).set_optimizations(&mut db).to(Some(
+            Optimizations::enabled_with_minimal_movable_functions().with_pool_string_literals(true),
+        ));
+        let crate_id = setup_db_and_get_crate_id(&db, inputs["cairo_code"].as_str());
+        let SierraProgramWithDebug { program, .. } =
+            db.get_sierra_program(vec![crate_id]).expect("`get_sierra_program` failed.");
+        replace_sierra_ids_in_program(&db, program)
+    } else {
+        checked_compile_to_sierra(inputs["cairo_code"].as_str())
+    };
     TestRunnerResult::success(OrderedHashMap::from([("sierra_code".into(), program.to_string())]))
 }
 
@@ -0,0 +1,158 @@
+//! Lookahead analysis of the future uses of variables, used to avoid storing variables as locals
+//! where they are not used after a possible revoke point.
+
+use cairo_lang_sierra as sierra;
+use cairo_lang_sierra::extensions::OutputVarReferenceInfo;
+use cairo_lang_sierra::extensions::boxing::LocalIntoBoxLibfuncWrapped;
+use cairo_lang_sierra::extensions::lib_func::{
+    LibfuncSignature, SierraApChange, SignatureAndTypeGenericLibfunc,
+};
+use cairo_lang_sierra::ids::ConcreteLibfuncId;
+use cairo_lang_sierra::program::{GenBranchInfo, GenBranchTarget, GenInvocation, GenStatement};
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use salsa::Database;
+
+use super::LocalVariables;
+use crate::db::SierraGenGroup;
+use crate::pre_sierra;
+
+/// The arguments that are marked as local variables, but do not need to be stored as locals when
+/// used by the invocation at the given statement index.
+pub type UnneededLocalStores = UnorderedHashSet<(usize, sierra::ids::VarId)>;
+
+/// Finds the arguments marked as local variables that do not need to be stored as locals when
+/// used.
+///
+/// A variable is marked as local if it is used after a revoke point in some flow of the function.
+/// When the variable is consumed by a straight-line invocation, it only needs to be stored as a
+/// local if one of the outputs of the invocation aliasing it may be used after a revoke point or a
+/// merge of flows, or if the invocation requires it to be a local.
+pub fn find_unneeded_local_stores<'db, GetLibfuncSignature>(
+    db: &'db dyn Database,
+    statements: &[pre_sierra::StatementWithLocation<'db>],
+    get_libfunc_signature: &GetLibfuncSignature,
+    local_variables: &LocalVariables,
+) -> UnneededLocalStores
+where
+    GetLibfuncSignature: Fn(ConcreteLibfuncId) -> &'db LibfuncSignature,
+{
+    let mut unneeded = UnneededLocalStores::default();
+    for (idx, statement) in statements.iter().enumerate() {
+        let pre_sierra::Statement::Sierra(GenStatement::Invocation(invocation)) =
+            &statement.statement
+        else {
+            continue;
+        };
+        let signature = get_libfunc_signature(invocation.libfunc_id.clone());
+        let Some(results) = simple_invocation_results(invocation, signature) else {
+            continue;
+        };
+        if requires_local_args(db, invocation) {
+            continue;
+        }
+        for (param_idx, arg) in invocation.args.iter().enumerate() {
+            if !local_variables.contains_key(arg) {
+                continue;
+            }
+            let may_be_needed = aliasing_outputs(results, signature, param_idx).any(|output| {
+                may_be_used_after_revoke(db, statements, get_libfunc_signature, idx + 1, output)
+            });
+            if !may_be_needed {
+                unneeded.insert((idx, arg.clone()));
+            }
+        }
+    }
+    unneeded
+}
+
+/// Returns whether `var` may be used after a revoke point or a merge of flows, when it is alive
+/// at the statement at index `start`.
+///
+/// The analysis is conservative - it only follows straight-line code with known ap changes, and
+/// returns true on any other statement before `var` is consumed.
+fn may_be_used_after_revoke<'db, GetLibfuncSignature>(
+    db: &'db dyn Database,
+    statements: &[pre_sierra::StatementWithLocation<'db>],
+    get_libfunc_signature: &GetLibfuncSignature,
+    start: usize,
+    var: &sierra::ids::VarId,
+) -> bool
+where
+    GetLibfuncSignature: Fn(ConcreteLibfuncId) -> &'db LibfuncSignature,
+{
+    for (idx, statement) in statements.iter().enumerate().skip(start) {
+        let invocation = match &statement.statement {
+            pre_sierra::Statement::Sierra(GenStatement::Invocation(invocation)) => invocation,
+            pre_sierra::Statement::PushValues(push_values) => {
+                if push_values.iter().any(|push_value| push_value.var == *var) {
+                    return true;
+                }
+                continue;
+            }
+            pre_sierra::Statement::Sierra(GenStatement::Return(_))
+            | pre_sierra::Statement::Label(_) => return true,
+        };
+        let signature = get_libfunc_signature(invocation.libfunc_id.clone());
+        let Some(results) = simple_invocation_results(invocation, signature) else {
+            return true;
+        };
+        let Some(param_idx) = invocation.args.iter().position(|arg| arg == var) else {
+            continue;
+        };
+        if requires_local_args(db, invocation) {
+            return true;
+        }
+        // The variable is consumed by the invocation, and lives on only through its aliases.
+        return aliasing_outputs(results, signature, param_idx).any(|output| {
+            may_be_used_after_revoke(db, statements, get_libfunc_signature, idx + 1, output)
+        });
+    }
+    true
+}
+
+/// Returns whether `invocation` requires its arguments to be local variables.
+fn requires_local_args<StatementId>(
+    db: &dyn Database,
+    invocation: &GenInvocation<StatementId>,
+) -> bool {
+    db.lookup_concrete_lib_func(&invocation.libfunc_id).generic_id.0
+        == LocalIntoBoxLibfuncWrapped::STR_ID
+}
+
+/// Returns the results of `invocation` if it is a single branch invocation with a fallthrough and
+/// a known ap change.
+fn simple_invocation_results<'a, StatementId>(
+    invocation: &'a GenInvocation<StatementId>,
+    signature: &LibfuncSignature,
+) -> Option<&'a [sierra::ids::VarId]> {
+    match (&invocation.branches[..], &signature.branch_signatures[..]) {
+        ([GenBranchInfo { target: GenBranchTarget::Fallthrough, results }], [branch_signature])
+            if matches!(
+                branch_signature.ap_change,
+                SierraApChange::Known { .. } | SierraApChange::BranchAlign
+            ) =>
+        {
+            Some(results)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the outputs of a simple invocation that alias its parameter at `param_idx`.
+fn aliasing_outputs<'a>(
+    results: &'a [sierra::ids::VarId],
+    signature: &'a LibfuncSignature,
+    param_idx: usize,
+) -> impl Iterator<Item = &'a sierra::ids::VarId> {
+    results.iter().zip(&signature.branch_signatures[0].vars).filter_map(move |(result, info)| {
+        match info.ref_info {
+            OutputVarReferenceInfo::SameAsParam { param_idx: idx }
+            | OutputVarReferenceInfo::PartialParam { param_idx: idx }
+                if idx == param_idx =>
+            {
+                Some(result)
+            }
+            _ => None,
+        }
+    })
+}
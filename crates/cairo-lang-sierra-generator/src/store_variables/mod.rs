@@ -1,5 +1,6 @@
 //! Handles the automatic addition of store_temp() and store_local() statements.
 
+mod future_use;
 mod known_stack;
 mod state;
 
@@ -24,6 +25,7 @@ use state::{VarState, VariablesState, merge_optional_states};
 
 use crate::db::SierraGenGroup;
 use crate::pre_sierra;
+use crate::store_variables::future_use::{UnneededLocalStores, find_unneeded_local_stores};
 use crate::store_variables::known_stack::KnownStack;
 use crate::store_variables::state::DeferredVariableInfo;
 use crate::utils::{
@@ -42,7 +44,8 @@ pub type LocalVariables = OrderedHashMap<sierra::ids::VarId, sierra::ids::VarId>
 /// deferred reference.
 ///
 /// `local_variables` is a map from variables that should be stored as local to their allocated
-/// space.
+/// space. A variable marked as local is not stored as a local when it is consumed, if none of its
+/// aliases may be used after a revoke point (see [find_unneeded_local_stores]).
 pub fn add_store_statements<'db, GetLibfuncSignature>(
     db: &'db dyn Database,
     statements: Vec<pre_sierra::StatementWithLocation<'db>>,
@@ -76,7 +79,14 @@ where
             pre_sierra::Statement::Label(_) => {}
         }
     }
-    let mut handler = AddStoreVariableStatements::new(db, local_variables, duplicated_vars);
+    let unneeded_local_stores =
+        find_unneeded_local_stores(db, &statements, get_libfunc_signature, &local_variables);
+    let mut handler = AddStoreVariableStatements::new(
+        db,
+        local_variables,
+        duplicated_vars,
+        unneeded_local_stores,
+    );
     let mut state_opt = Some(VariablesState {
         variables: OrderedHashMap::from_iter(params.iter().map(|param| {
             (
@@ -91,7 +101,8 @@ where
         known_stack: Default::default(),
     });
     // Go over the statements, restarting whenever we see a branch or a label.
-    for statement in statements {
+    for (idx, statement) in statements.into_iter().enumerate() {
+        handler.statement_idx = idx;
         let prev_len = handler.result.len();
         let location = statement.location;
         state_opt = handler.handle_statement(state_opt, statement, get_libfunc_signature);
@@ -106,6 +117,11 @@ struct AddStoreVariableStatements<'db> {
     db: &'db dyn Database,
     local_variables: LocalVariables,
     duplicated_vars: OrderedHashSet<sierra::ids::VarId>,
+    /// The arguments marked as local variables that do not need to be stored as locals when used,
+    /// by the index of the using statement.
+    unneeded_local_stores: UnneededLocalStores,
+    /// The index of the currently handled statement, in the original list of statements.
+    statement_idx: usize,
     /// A list of output statements (the original statement, together with the added statements,
     /// such as "store_temp").
     result: Vec<pre_sierra::StatementWithLocation<'db>>,
@@ -122,11 +138,14 @@ impl<'db> AddStoreVariableStatements<'db> {
         db: &'db dyn Database,
         local_variables: LocalVariables,
         duplicated_vars: OrderedHashSet<sierra::ids::VarId>,
+        unneeded_local_stores: UnneededLocalStores,
     ) -> Self {
         AddStoreVariableStatements {
             db,
             local_variables,
             duplicated_vars,
+            unneeded_local_stores,
+            statement_idx: 0,
             result: Vec::new(),
             future_states: OrderedHashMap::default(),
         }
@@ -283,15 +302,16 @@ impl<'db> AddStoreVariableStatements<'db> {
         allow_add_const: bool,
         allow_const: bool,
     ) -> VarState {
+        // A variable marked as local is stored when used, in case an alias of the variable is used
+        // after a revoke point (for example, due to `SameAsParam` output).
+        let store_as_local =
+            !self.unneeded_local_stores.contains(&(self.statement_idx, arg.clone()));
         match state.pop_var_state(arg) {
             VarState::Removed => {
                 unreachable!("`{arg}` was previously moved.");
             }
             VarState::Deferred { info: deferred_info } => {
-                // If a deferred argument was marked as a local variable, then store
-                // it. This is important in case an alias of the variable is used later
-                // (for example, due to `SameAsParam` output).
-                if self.store_var_as_local(arg, &deferred_info.ty) {
+                if store_as_local && self.store_var_as_local(arg, &deferred_info.ty) {
                     VarState::LocalVar
                 } else {
                     match deferred_info.kind {
@@ -327,7 +347,7 @@ impl<'db> AddStoreVariableStatements<'db> {
                 }
             }
             VarState::TempVar { ty } => {
-                if self.store_var_as_local(arg, &ty) {
+                if store_as_local && self.store_var_as_local(arg, &ty) {
                     return VarState::LocalVar;
                 }
                 VarState::TempVar { ty }
@@ -371,6 +371,43 @@ fn store_local_simple() {
     );
 }
 
+/// Tests that variables marked as local are stored as locals when used only if they are used after
+/// a revoke point through an alias.
+#[test]
+fn store_local_only_if_needed_in_future() {
+    let db = SierraGenDatabaseForTesting::default();
+    let statements: Vec<pre_sierra::StatementWithLocation<'_>> = vec![
+        dummy_simple_statement(&db, "store_temp<felt252>", &["0"], &["1"]),
+        // Consumed before any revoke point - no need to store as local.
+        dummy_simple_statement(&db, "felt252_add", &["1", "2"], &["3"]),
+        dummy_simple_statement(&db, "store_temp<felt252>", &["3"], &["4"]),
+        // The aliases of `4` are used after a revoke point - stored as local.
+        dummy_simple_statement(&db, "dup", &["4"], &["5", "6"]),
+        dummy_simple_statement(&db, "revoke_ap", &[], &[]),
+        dummy_simple_statement(&db, "felt252_add", &["5", "6"], &["7"]),
+        dummy_return_statement(&[]),
+    ];
+
+    assert_eq!(
+        test_add_store_statements(
+            &db,
+            statements,
+            OrderedHashMap::from_iter(vec![("1".into(), "101".into()), ("4".into(), "104".into())]),
+            &["0", "2"]
+        ),
+        vec![
+            "store_temp<felt252>(0) -> (1)",
+            "felt252_add(1, 2) -> (3)",
+            "store_temp<felt252>(3) -> (4)",
+            "store_local<felt252>(104, 4) -> (4)",
+            "dup(4) -> (5, 6)",
+            "revoke_ap() -> ()",
+            "felt252_add(5, 6) -> (7)",
+            "return()",
+        ]
+    );
+}
+
 #[test]
 fn same_as_param() {
     let db = SierraGenDatabaseForTesting::default();
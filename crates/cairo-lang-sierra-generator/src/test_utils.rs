@@ -5,7 +5,6 @@ use cairo_lang_defs::ids::ModuleId;
 use cairo_lang_filesystem::db::{init_dev_corelib, init_files_group};
 use cairo_lang_filesystem::detect::detect_corelib;
 use cairo_lang_filesystem::flag::{Flag, FlagsGroup};
-use cairo_lang_filesystem::ids::FlagLongId;
 use cairo_lang_lowering::db::{LoweringGroup, lowering_group_input};
 use cairo_lang_semantic::corelib::CorelibSemantic;
 use cairo_lang_semantic::db::{PluginSuiteInput, SemanticGroup, init_semantic_group};
@@ -44,16 +43,15 @@ pub static SHARED_DB: LazyLock<Mutex<SierraGenDatabaseForTesting>> =
 pub static SHARED_DB_WITHOUT_AD_WITHDRAW_GAS: LazyLock<Mutex<SierraGenDatabaseForTesting>> =
     LazyLock::new(|| {
         let mut db = SierraGenDatabaseForTesting::new_empty();
-        let add_withdraw_gas_flag_id = FlagLongId(Flag::ADD_WITHDRAW_GAS.into());
-        db.set_flag(add_withdraw_gas_flag_id, Some(Flag::AddWithdrawGas(false)));
+        db.set_flag(Flag::AddWithdrawGas(false));
         Mutex::new(db)
     });
 pub static SHARED_DB_WITHOUT_ADD_WITHDRAW_GAS_FUTURE_SIERRA: LazyLock<
     Mutex<SierraGenDatabaseForTesting>,
 > = LazyLock::new(|| {
     let mut db = SierraGenDatabaseForTesting::new_empty();
-    db.set_flag(FlagLongId(Flag::ADD_WITHDRAW_GAS.into()), Some(Flag::AddWithdrawGas(false)));
-    db.set_flag(FlagLongId(Flag::FUTURE_SIERRA.into()), Some(Flag::FutureSierra(true)));
+    db.set_flag(Flag::AddWithdrawGas(false));
+    db.set_flag(Flag::FutureSierra(true));
     Mutex::new(db)
 });
 impl SierraGenDatabaseForTesting {
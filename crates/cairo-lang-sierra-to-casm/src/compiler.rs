@@ -1,7 +1,13 @@
 use std::fmt::Display;
 
-use cairo_lang_casm::assembler::AssembledCairoProgram;
-use cairo_lang_casm::instructions::{Instruction, InstructionBody, RetInstruction};
+use cairo_lang_casm::assembler::{
+    AssembledCairoProgram, CodeRelocation, CodeSymbol, RelocatableCairoProgram, symbolize,
+};
+use cairo_lang_casm::instructions::{
+    Instruction, InstructionBody, RetInstruction, instruction_offsets,
+};
+use cairo_lang_casm::operand::DerefOrImmediate;
+use cairo_lang_casm::schedule::{Constraint, ScheduledInstructions, schedule_ap_updates};
 use cairo_lang_sierra::extensions::ConcreteLibfunc;
 use cairo_lang_sierra::extensions::circuit::{CircuitConcreteLibfunc, CircuitInfo, VALUE_SIZE};
 use cairo_lang_sierra::extensions::const_type::ConstConcreteLibfunc;
@@ -11,7 +17,7 @@ use cairo_lang_sierra::extensions::core::{
 use cairo_lang_sierra::extensions::coupon::CouponConcreteLibfunc;
 use cairo_lang_sierra::extensions::gas::GasConcreteLibfunc;
 use cairo_lang_sierra::extensions::lib_func::SierraApChange;
-use cairo_lang_sierra::ids::{ConcreteLibfuncId, ConcreteTypeId, VarId};
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId, VarId};
 use cairo_lang_sierra::program::{
     BranchTarget, GenericArg, Invocation, Program, Statement, StatementIdx,
 };
@@ -21,20 +27,21 @@ use cairo_lang_utils::casts::IntoOrPanic;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
-use itertools::{chain, zip_eq};
+use itertools::{Itertools, chain, zip_eq};
 use num_bigint::BigInt;
 use num_traits::{ToPrimitive, Zero};
 use thiserror::Error;
 
 use crate::annotations::{AnnotationError, ProgramAnnotations, StatementAnnotations};
 use crate::circuit::CircuitsInfo;
+use crate::environment::frame_state::FrameState;
 use crate::invocations::enm::get_variant_selector;
 use crate::invocations::{
     BranchChanges, InvocationError, ProgramInfo, check_references_on_stack, compile_invocation,
 };
 use crate::metadata::Metadata;
-use crate::references::{ReferenceValue, ReferencesError, check_types_match};
-use crate::relocations::{RelocationEntry, relocate_instructions};
+use crate::references::{ReferenceExpression, ReferenceValue, ReferencesError, check_types_match};
+use crate::relocations::{Relocation, RelocationEntry, relocate_instructions};
 
 #[cfg(test)]
 #[path = "compiler_test.rs"]
@@ -46,8 +53,8 @@ pub enum CompilationError {
     ProgramRegistryError(Box<ProgramRegistryError>),
     #[error(transparent)]
     AnnotationError(#[from] AnnotationError),
-    #[error("#{statement_idx}: {error}")]
-    InvocationError { statement_idx: StatementIdx, error: InvocationError },
+    #[error(transparent)]
+    InvocationError(Box<InvocationCompilationError>),
     #[error("#{statement_idx}: Return arguments are not on the stack.")]
     ReturnArgumentsNotOnStack { statement_idx: StatementIdx },
     #[error("#{statement_idx}: {error}")]
@@ -83,10 +90,43 @@ pub enum CompilationError {
     MetadataNegativeGasVariable,
 }
 
+/// A failure to compile a libfunc invocation, along with the context of the invocation.
+#[derive(Error, Debug, Eq, PartialEq)]
+#[error(
+    "#{statement_idx}: {error}\nLibfunc: `{libfunc_id}`.\nArguments: {}.{}",
+    display_refs(refs),
+    suggestion.as_ref().map(|suggestion| format!("\nSuggestion: {suggestion}")).unwrap_or_default()
+)]
+pub struct InvocationCompilationError {
+    pub statement_idx: StatementIdx,
+    pub libfunc_id: ConcreteLibfuncId,
+    /// The expressions of the arguments of the invocation.
+    pub refs: Vec<ReferenceExpression>,
+    pub error: InvocationError,
+    /// A suggestion for fixing the error, if available.
+    pub suggestion: Option<String>,
+}
+
+impl InvocationCompilationError {
+    /// Creates the error for a failure to compile `invocation` at `statement_idx` with the
+    /// arguments `refs`.
+    fn new(
+        statement_idx: StatementIdx,
+        invocation: &Invocation,
+        refs: &[ReferenceValue],
+        error: InvocationError,
+    ) -> Self {
+        let refs = refs.iter().map(|r| r.expression.clone()).collect::<Vec<_>>();
+        let suggestion = error.suggestion(&refs);
+        Self { statement_idx, libfunc_id: invocation.libfunc_id.clone(), refs, error, suggestion }
+    }
+}
+
 impl CompilationError {
     pub fn stmt_indices(&self) -> Vec<StatementIdx> {
         match self {
             CompilationError::AnnotationError(err) => err.stmt_indices(),
+            CompilationError::InvocationError(err) => vec![err.statement_idx],
             _ => vec![],
         }
     }
@@ -99,6 +139,12 @@ pub struct SierraToCasmConfig {
     pub gas_usage_check: bool,
     /// CASM bytecode size limit.
     pub max_bytecode_size: usize,
+    /// Whether to record the locations of the live Sierra variables at the start of each
+    /// statement (see [SierraStatementDebugInfo::variable_locations]).
+    pub record_variable_locations: bool,
+    /// Whether to coalesce the `ap` updates of the compiled code into the `ap++` flags of the
+    /// instructions around them (see [cairo_lang_casm::schedule]).
+    pub coalesce_ap_updates: bool,
 }
 
 /// The CASM program representation.
@@ -111,11 +157,11 @@ pub struct CairoProgram {
 impl Display for CairoProgram {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if std::env::var("PRINT_CASM_BYTECODE_OFFSETS").is_ok() {
-            let mut bytecode_offset = 0;
-            for instruction in &self.instructions {
-                writeln!(f, "{instruction}; // {bytecode_offset}")?;
-                bytecode_offset += instruction.body.op_size();
+            let offsets = instruction_offsets(&self.instructions);
+            for (instruction, offset) in self.instructions.iter().zip(&offsets) {
+                writeln!(f, "{instruction}; // {offset}")?;
             }
+            let mut bytecode_offset = *offsets.last().unwrap();
             for segment in self.consts_info.segments.values() {
                 writeln!(f, "ret; // {bytecode_offset}")?;
                 bytecode_offset += 1;
@@ -181,6 +227,71 @@ impl CairoProgram {
         AssembledCairoProgram { bytecode, hints }
     }
 
+    /// Returns the entry point symbols of the functions of `program`, the Sierra program this was
+    /// compiled from, sorted by offset.
+    pub fn function_symbols(&self, program: &Program) -> Vec<CodeSymbol> {
+        program
+            .funcs
+            .iter()
+            .map(|func| CodeSymbol {
+                name: func.id.to_string(),
+                offset: self.debug_info.sierra_statement_info[func.entry_point.0].start_offset,
+            })
+            .sorted_by_key(|symbol| symbol.offset)
+            .collect()
+    }
+
+    /// Creates a relocatable assembled representation of the program, with the entry points of the
+    /// functions of `program`, the Sierra program this was compiled from, as its symbols.
+    pub fn assemble_relocatable(&self, program: &Program) -> RelocatableCairoProgram {
+        let mut relocations = vec![];
+        for (instruction, offset) in
+            self.instructions.iter().zip(instruction_offsets(&self.instructions))
+        {
+            if let Some(target) = absolute_code_target(instruction) {
+                // The immediate word directly follows the instruction word.
+                relocations.push(CodeRelocation { word_offset: offset + 1, target });
+            }
+        }
+        RelocatableCairoProgram {
+            program: self.assemble(),
+            relocations,
+            symbols: self.function_symbols(program),
+        }
+    }
+
+    /// Returns a listing of the program, where each symbol out of `symbols` (sorted by offset)
+    /// labels the instruction at its offset, and each jump is annotated by the symbolized target.
+    pub fn symbolized_listing(&self, symbols: &[CodeSymbol]) -> String {
+        let mut listing = String::new();
+        let mut symbols_iter = symbols.iter().peekable();
+        let offsets = instruction_offsets(&self.instructions);
+        for (instruction, &offset) in self.instructions.iter().zip(&offsets) {
+            while let Some(symbol) = symbols_iter.next_if(|symbol| symbol.offset <= offset) {
+                listing.push_str(&format!("{}:\n", symbol.name));
+            }
+            // Hints are printed on their own lines before the instruction.
+            let text = instruction.to_string().replace('\n', "\n    ");
+            listing.push_str(&format!("    {text}; // {offset}"));
+            let target = relative_code_target(instruction, offset)
+                .or_else(|| absolute_code_target(instruction));
+            if let Some((symbol, distance)) = target.and_then(|target| symbolize(symbols, target)) {
+                listing.push_str(&format!(" -> {}+{distance}", symbol.name));
+            }
+            listing.push('\n');
+        }
+        let mut offset = *offsets.last().unwrap();
+        for segment in self.consts_info.segments.values() {
+            listing.push_str(&format!("    ret; // {offset}\n"));
+            offset += 1;
+            for value in &segment.values {
+                listing.push_str(&format!("    dw {value}; // {offset}\n"));
+                offset += 1;
+            }
+        }
+        listing
+    }
+
     /// Returns the Sierra statement corresponding to the given PC.
     pub fn sierra_statement_index_by_pc(&self, pc: usize) -> StatementIdx {
         // the `-1` here can't cause an underflow as the first statement is always at
@@ -203,6 +314,10 @@ pub struct SierraStatementDebugInfo {
     pub instruction_idx: usize,
     /// Statement-kind-dependent information.
     pub additional_kind_info: StatementKindDebugInfo,
+    /// The memory cells of the Sierra variables alive at the start of the statement, relative to
+    /// the `ap` and `fp` values at `start_offset`.
+    /// Empty unless [SierraToCasmConfig::record_variable_locations] is set.
+    pub variable_locations: OrderedHashMap<VarId, ReferenceExpression>,
 }
 
 /// Additional debug information for a Sierra statement, depending on its kind
@@ -234,6 +349,10 @@ pub struct InvokeStatementDebugInfo {
 pub struct CairoProgramDebugInfo {
     /// The debug information per Sierra statement.
     pub sierra_statement_info: Vec<SierraStatementDebugInfo>,
+    /// The number of fp-relative slots allocated for the local variables of each function, in
+    /// the order of the functions in the program. Local variables on mutually exclusive flows of a
+    /// function may share slots, as packed by the Sierra generator.
+    pub frame_sizes: OrderedHashMap<FunctionId, usize>,
 }
 
 /// The information about the constants used in the program.
@@ -254,6 +373,9 @@ impl ConstsInfo {
         const_segments_max_size: usize,
     ) -> Result<Self, CompilationError> {
         let mut segments_data_size = 0;
+        // The offset of the data of each const added to a segment, by the segment id and the
+        // data, so that consts with identical data in the same segment share a single definition.
+        let mut data_offsets = UnorderedHashMap::<(u32, Vec<BigInt>), usize>::default();
 
         // A lambda to add a const.
         // Note that `segments` is passed as an argument to avoid taking borrowing it.
@@ -262,10 +384,18 @@ impl ConstsInfo {
                              ty,
                              const_data: Vec<BigInt>| {
             let segment: &mut ConstSegment = segments.entry(segment_id).or_default();
+            let key = (segment_id, const_data);
+            if let Some(offset) = data_offsets.get(&key) {
+                segment.const_offset.insert(ty, *offset);
+                return Ok(());
+            }
+            let (_, const_data) = key;
 
             segments_data_size += const_data.len();
-            segment.const_offset.insert(ty, segment.values.len());
-            segment.values.extend(const_data);
+            let offset = segment.values.len();
+            segment.const_offset.insert(ty, offset);
+            segment.values.extend(const_data.iter().cloned());
+            data_offsets.insert((segment_id, const_data), offset);
             if segments_data_size + segments.len() > const_segments_max_size {
                 return Err(CompilationError::CodeSizeLimitExceeded);
             }
@@ -479,6 +609,8 @@ pub fn compile(
         program.type_declarations.iter().map(|td| &td.id),
     )?;
 
+    let mut frame_sizes: OrderedHashMap<FunctionId, usize> =
+        program.funcs.iter().map(|func| (func.id.clone(), 0)).collect();
     let mut program_offset: usize = 0;
     for (statement_id, statement) in program.statements.iter().enumerate() {
         let statement_idx = StatementIdx(statement_id);
@@ -491,6 +623,11 @@ pub fn compile(
                 let (annotations, return_refs) = program_annotations
                     .get_annotations_after_take_args(statement_idx, ref_ids.iter())
                     .map_err(|err| Box::new(err.into()))?;
+                let variable_locations = if config.record_variable_locations {
+                    collect_variable_locations(ref_ids, &return_refs, &annotations)
+                } else {
+                    Default::default()
+                };
                 return_refs.iter().for_each(|r| r.validate(&program_info.type_sizes));
 
                 if let Some(var_id) = annotations.refs.keys().next() {
@@ -509,17 +646,19 @@ pub fn compile(
                         &return_refs,
                     )
                     .map_err(|err| Box::new(err.into()))?;
-                check_references_on_stack(&return_refs).map_err(|error| match error {
-                    InvocationError::InvalidReferenceExpressionForArgument => {
-                        CompilationError::ReturnArgumentsNotOnStack { statement_idx }
-                    }
-                    _ => CompilationError::InvocationError { statement_idx, error },
-                })?;
+                check_references_on_stack(&return_refs)
+                    .map_err(|_| CompilationError::ReturnArgumentsNotOnStack { statement_idx })?;
+                if let FrameState::Finalized { allocated } = annotations.environment.frame_state {
+                    let frame_size =
+                        frame_sizes.entry(annotations.function_id.clone()).or_default();
+                    *frame_size = (*frame_size).max(allocated);
+                }
 
                 let start_offset = program_offset;
 
-                let ret_instruction = RetInstruction {};
-                program_offset += ret_instruction.op_size();
+                let ret_instruction =
+                    Instruction::new(InstructionBody::Ret(RetInstruction {}), false);
+                program_offset += ret_instruction.encoded_size();
 
                 sierra_statement_info.push(SierraStatementDebugInfo {
                     start_offset,
@@ -528,14 +667,20 @@ pub fn compile(
                     additional_kind_info: StatementKindDebugInfo::Return(
                         ReturnStatementDebugInfo { ref_values: return_refs },
                     ),
+                    variable_locations,
                 });
 
-                instructions.push(Instruction::new(InstructionBody::Ret(ret_instruction), false));
+                instructions.push(ret_instruction);
             }
             Statement::Invocation(invocation) => {
                 let (annotations, invoke_refs) = program_annotations
                     .get_annotations_after_take_args(statement_idx, invocation.args.iter())
                     .map_err(|err| Box::new(err.into()))?;
+                let variable_locations = if config.record_variable_locations {
+                    collect_variable_locations(&invocation.args, &invoke_refs, &annotations)
+                } else {
+                    Default::default()
+                };
 
                 let libfunc = program_info
                     .registry
@@ -564,13 +709,22 @@ pub fn compile(
                     &invoke_refs,
                     annotations.environment,
                 )
-                .map_err(|error| CompilationError::InvocationError { statement_idx, error })?;
+                .map_err(|error| {
+                    CompilationError::InvocationError(Box::new(InvocationCompilationError::new(
+                        statement_idx,
+                        invocation,
+                        &invoke_refs,
+                        error,
+                    )))
+                })?;
 
                 let start_offset = program_offset;
 
-                for instruction in &compiled_invocation.instructions {
-                    program_offset += instruction.body.op_size();
-                }
+                program_offset += compiled_invocation
+                    .instructions
+                    .iter()
+                    .map(Instruction::encoded_size)
+                    .sum::<usize>();
 
                 sierra_statement_info.push(SierraStatementDebugInfo {
                     start_offset,
@@ -582,6 +736,7 @@ pub fn compile(
                             ref_values: invoke_refs,
                         },
                     ),
+                    variable_locations,
                 });
 
                 for mut entry in compiled_invocation.relocations {
@@ -633,6 +788,16 @@ pub fn compile(
         }
     }
 
+    if config.coalesce_ap_updates {
+        program_offset = schedule_program_ap_updates(
+            program,
+            &mut instructions,
+            &mut relocations,
+            &mut sierra_statement_info,
+            config.record_variable_locations,
+        );
+    }
+
     let const_segments_max_size = config
         .max_bytecode_size
         .checked_sub(program_offset)
@@ -648,10 +813,82 @@ pub fn compile(
     Ok(CairoProgram {
         instructions,
         consts_info,
-        debug_info: CairoProgramDebugInfo { sierra_statement_info },
+        debug_info: CairoProgramDebugInfo { sierra_statement_info, frame_sizes },
     })
 }
 
+/// Coalesces the `ap` updates of the compiled `instructions` before their relocation, and updates
+/// the relocations and the debug information of the statements to the scheduled code. Returns the
+/// size of the scheduled code.
+///
+/// The statements targeted by jumps and calls start basic blocks, and the relocated instructions
+/// are kept in place unchanged, as their immediates are relative to their own offsets. If
+/// `all_statements_start_blocks` is set, `ap` has its original value at the start of every
+/// statement, so the recorded variable locations remain valid.
+fn schedule_program_ap_updates(
+    program: &Program,
+    instructions: &mut Vec<Instruction>,
+    relocations: &mut [RelocationEntry],
+    sierra_statement_info: &mut [SierraStatementDebugInfo],
+    all_statements_start_blocks: bool,
+) -> usize {
+    let mut constraints = vec![Constraint::Free; instructions.len()];
+    for entry in relocations.iter() {
+        constraints[entry.instruction_idx] = Constraint::Fixed;
+    }
+    let block_starts = chain!(
+        program.funcs.iter().map(|func| func.entry_point),
+        relocations.iter().filter_map(|entry| match entry.relocation {
+            Relocation::RelativeStatementId(target) => Some(target),
+            _ => None,
+        }),
+        (0..sierra_statement_info.len()).filter(|_| all_statements_start_blocks).map(StatementIdx),
+    );
+    for statement_idx in block_starts {
+        if let Some(constraint) =
+            constraints.get_mut(sierra_statement_info[statement_idx.0].instruction_idx)
+            && *constraint == Constraint::Free
+        {
+            *constraint = Constraint::BlockStart;
+        }
+    }
+
+    let ScheduledInstructions { instructions: scheduled, new_indices } =
+        schedule_ap_updates(instructions, &constraints);
+    *instructions = scheduled;
+    for entry in relocations.iter_mut() {
+        entry.instruction_idx = new_indices[entry.instruction_idx];
+    }
+    let offsets = instruction_offsets(instructions.iter());
+    for info in sierra_statement_info.iter_mut() {
+        info.instruction_idx = new_indices[info.instruction_idx];
+        info.start_offset = offsets[info.instruction_idx];
+    }
+    let code_size = *offsets.last().unwrap();
+    let end_offsets = sierra_statement_info
+        .iter()
+        .skip(1)
+        .map(|info| info.start_offset)
+        .chain([code_size])
+        .collect_vec();
+    for (info, end_offset) in zip_eq(sierra_statement_info.iter_mut(), end_offsets) {
+        info.end_offset = end_offset;
+    }
+    code_size
+}
+
+/// Returns the locations of all the variables alive at a statement - the arguments taken by the
+/// statement, followed by the variables left in its annotations.
+fn collect_variable_locations(
+    arg_ids: &[VarId],
+    arg_refs: &[ReferenceValue],
+    annotations: &StatementAnnotations,
+) -> OrderedHashMap<VarId, ReferenceExpression> {
+    chain!(zip_eq(arg_ids, arg_refs), annotations.refs.iter())
+        .map(|(var_id, value)| (var_id.clone(), value.expression.clone()))
+        .collect()
+}
+
 /// Runs basic validations on the given metadata.
 pub fn validate_metadata(
     program: &Program,
@@ -732,3 +969,42 @@ fn is_branch_align(
 
     Ok(false)
 }
+
+/// Renders the expressions of the arguments of an invocation for error messages.
+fn display_refs(refs: &[ReferenceExpression]) -> String {
+    if refs.is_empty() {
+        return "none".to_string();
+    }
+    refs.iter().map(|expr| format!("`{expr}`")).join(", ")
+}
+
+/// Returns the code offset targeted by `instruction` if it is an absolute jump or call to an
+/// immediate address.
+fn absolute_code_target(instruction: &Instruction) -> Option<usize> {
+    match &instruction.body {
+        InstructionBody::Jump(jump) if !jump.relative => immediate_target(&jump.target),
+        InstructionBody::Call(call) if !call.relative => immediate_target(&call.target),
+        _ => None,
+    }
+}
+
+/// Returns the code offset targeted by `instruction` at `offset` if it is a relative jump or call
+/// to an immediate offset.
+fn relative_code_target(instruction: &Instruction, offset: usize) -> Option<usize> {
+    let jump_offset = match &instruction.body {
+        InstructionBody::Jump(jump) if jump.relative => &jump.target,
+        InstructionBody::Call(call) if call.relative => &call.target,
+        InstructionBody::Jnz(jnz) => &jnz.jump_offset,
+        _ => return None,
+    };
+    let DerefOrImmediate::Immediate(jump_offset) = jump_offset else { return None };
+    (BigInt::from(offset) + &jump_offset.value).to_usize()
+}
+
+/// Returns the value of `target` as a code offset if it is an immediate.
+fn immediate_target(target: &DerefOrImmediate) -> Option<usize> {
+    match target {
+        DerefOrImmediate::Immediate(value) => value.value.to_usize(),
+        DerefOrImmediate::Deref(_) => None,
+    }
+}
@@ -3,6 +3,7 @@ use cairo_lang_sierra_type_size::ProgramRegistryInfo;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use indoc::indoc;
+use itertools::Itertools;
 use test_case::test_case;
 
 use super::SierraToCasmConfig;
@@ -482,6 +483,50 @@ use crate::test_utils::{read_sierra_example_file, strip_comments_and_linebreaks}
         dw 17;
     "};
     "Simple use of constants.")]
+#[test_case(indoc! {"
+        type felt252 = felt252;
+        type Tuple<felt252> = Struct<ut@Tuple, felt252>;
+        type Const<felt252, 5> = Const<felt252, 5>;
+        type Const<Tuple<felt252>, Const<felt252, 5>> = Const<Tuple<felt252>, Const<felt252, 5>>;
+        type Box<felt252> = Box<felt252>;
+        type Box<Tuple<felt252>> = Box<Tuple<felt252>>;
+
+
+        libfunc const_as_box<Const<felt252, 5>> = const_as_box<Const<felt252, 5>, 0>;
+        libfunc const_as_box<Const<Tuple<felt252>, Const<felt252, 5>>> =
+            const_as_box<Const<Tuple<felt252>, Const<felt252, 5>>, 0>;
+        libfunc unbox<felt252> = unbox<felt252>;
+        libfunc unbox<Tuple<felt252>> = unbox<Tuple<felt252>>;
+        libfunc store_temp<felt252> = store_temp<felt252>;
+        libfunc store_temp<Tuple<felt252>> = store_temp<Tuple<felt252>>;
+        libfunc drop<felt252> = drop<felt252>;
+
+
+        const_as_box<Const<felt252, 5>>() -> ([1]);
+        const_as_box<Const<Tuple<felt252>, Const<felt252, 5>>>() -> ([2]);
+        unbox<felt252>([1]) -> ([1]);
+        unbox<Tuple<felt252>>([2]) -> ([2]);
+        store_temp<felt252>([1]) -> ([1]);
+        drop<felt252>([1]) -> ();
+        store_temp<Tuple<felt252>>([2]) -> ([2]);
+        return([2]);
+
+        test_program@0() -> (Tuple<felt252>);
+
+    "},
+    false,
+    indoc! {"
+        call rel 11;
+        [ap + 0] = [ap + -1] + 10, ap++;
+        call rel 7;
+        [ap + 0] = [ap + -1] + 6, ap++;
+        [ap + 0] = [[ap + -4] + 0], ap++;
+        [ap + 0] = [[ap + -2] + 0], ap++;
+        ret;
+        ret;
+        dw 5;
+    "};
+    "Constants with identical data share a definition.")]
 #[test_case(indoc! {"
     type felt252 = felt252;
     type Tuple<felt252, felt252> = Struct<ut@Tuple, felt252, felt252>;
@@ -769,7 +814,12 @@ fn sierra_to_casm(sierra_code: &str, gas_usage_check: bool, expected_casm: &str)
         calc_metadata_ap_change_only(&program, &program_info).unwrap()
     };
     // `max_bytecode_size` is a small value to ensure we can pass with small values.
-    let config = SierraToCasmConfig { gas_usage_check, max_bytecode_size: 100 };
+    let config = SierraToCasmConfig {
+        gas_usage_check,
+        max_bytecode_size: 100,
+        record_variable_locations: false,
+        coalesce_ap_updates: false,
+    };
     pretty_assertions::assert_eq!(
         compile(&program, &program_info, &metadata, config)
             .expect("Compilation failed.")
@@ -778,6 +828,239 @@ fn sierra_to_casm(sierra_code: &str, gas_usage_check: bool, expected_casm: &str)
     );
 }
 
+#[test]
+fn records_variable_locations() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc felt252_add = felt252_add;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+
+            felt252_add([0], [1]) -> ([2]);
+            store_temp_felt252([2]) -> ([2]);
+            return([2]);
+
+            test_program@0([0]: felt252, [1]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let program_info = ProgramRegistryInfo::new(&program).unwrap();
+    let metadata = calc_metadata_ap_change_only(&program, &program_info).unwrap();
+    let config = SierraToCasmConfig {
+        gas_usage_check: false,
+        max_bytecode_size: usize::MAX,
+        record_variable_locations: true,
+        coalesce_ap_updates: false,
+    };
+    let casm = compile(&program, &program_info, &metadata, config).unwrap();
+    let variable_locations: Vec<Vec<String>> = casm
+        .debug_info
+        .sierra_statement_info
+        .iter()
+        .map(|info| {
+            info.variable_locations
+                .iter()
+                .map(|(var_id, expression)| {
+                    format!("{var_id}: {}", expression.cells.iter().join(", "))
+                })
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        variable_locations,
+        vec![
+            vec!["[0]: [fp + -4]", "[1]: [fp + -3]"],
+            vec!["[2]: [fp + -4] + [fp + -3]"],
+            vec!["[2]: [ap + -1]"],
+        ]
+    );
+}
+
+#[test]
+fn records_frame_sizes() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type UninitializedFelt252 = Uninitialized<felt252>;
+
+            libfunc alloc_local_felt252 = alloc_local<felt252>;
+            libfunc finalize_locals = finalize_locals;
+            libfunc store_local_felt252 = store_local<felt252>;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+            libfunc drop_felt252 = drop<felt252>;
+
+            alloc_local_felt252() -> ([1]);
+            alloc_local_felt252() -> ([2]);
+            finalize_locals() -> ();
+            store_local_felt252([1], [0]) -> ([1]);
+            store_local_felt252([2], [1]) -> ([2]);
+            drop_felt252([2]) -> ();
+            return();
+            store_temp_felt252([0]) -> ([0]);
+            return([0]);
+
+            with_locals@0([0]: felt252) -> ();
+            without_locals@7([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let program_info = ProgramRegistryInfo::new(&program).unwrap();
+    let metadata = calc_metadata_ap_change_only(&program, &program_info).unwrap();
+    let config = SierraToCasmConfig {
+        gas_usage_check: false,
+        max_bytecode_size: usize::MAX,
+        record_variable_locations: false,
+        coalesce_ap_updates: false,
+    };
+    let casm = compile(&program, &program_info, &metadata, config).unwrap();
+    let frame_sizes: Vec<_> = casm
+        .debug_info
+        .frame_sizes
+        .iter()
+        .map(|(function_id, size)| (function_id.to_string(), *size))
+        .collect();
+    assert_eq!(
+        frame_sizes,
+        vec![("with_locals".to_string(), 2), ("without_locals".to_string(), 0)]
+    );
+}
+
+#[test]
+fn symbolized_listing_and_relocations() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc call_inner = function_call<user@inner>;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+
+            store_temp_felt252([0]) -> ([0]);
+            call_inner([0]) -> ([1]);
+            return([1]);
+            store_temp_felt252([0]) -> ([0]);
+            return([0]);
+
+            outer@0([0]: felt252) -> (felt252);
+            inner@3([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let program_info = ProgramRegistryInfo::new(&program).unwrap();
+    let metadata = calc_metadata_ap_change_only(&program, &program_info).unwrap();
+    let config = SierraToCasmConfig {
+        gas_usage_check: false,
+        max_bytecode_size: usize::MAX,
+        record_variable_locations: false,
+        coalesce_ap_updates: false,
+    };
+    let casm = compile(&program, &program_info, &metadata, config).unwrap();
+    let symbols = casm.function_symbols(&program);
+    assert_eq!(
+        casm.symbolized_listing(&symbols),
+        indoc! {"
+            outer:
+                [ap + 0] = [fp + -3], ap++; // 0
+                call rel 3; // 1 -> inner+0
+                ret; // 3
+            inner:
+                [ap + 0] = [fp + -3], ap++; // 4
+                ret; // 5
+        "}
+    );
+    let relocatable = casm.assemble_relocatable(&program);
+    assert_eq!(relocatable.program.bytecode, casm.assemble().bytecode);
+    assert!(relocatable.relocations.is_empty());
+    assert_eq!(relocatable.symbols, symbols);
+}
+
+#[test]
+fn coalesces_ap_updates() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+            type UninitializedFelt252 = Uninitialized<felt252>;
+
+            libfunc alloc_local_felt252 = alloc_local<felt252>;
+            libfunc finalize_locals = finalize_locals;
+            libfunc store_local_felt252 = store_local<felt252>;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc branch_align = branch_align;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+            libfunc call_inner = function_call<user@inner>;
+
+            alloc_local_felt252() -> ([2]);
+            finalize_locals() -> ();
+            store_local_felt252([2], [0]) -> ([2]);
+            felt252_is_zero([1]) { fallthrough() 7([3]) };
+            branch_align() -> ();
+            store_temp_felt252([2]) -> ([2]);
+            return([2]);
+            branch_align() -> ();
+            drop_nz_felt252([3]) -> ();
+            store_temp_felt252([2]) -> ([2]);
+            call_inner([2]) -> ([4]);
+            return([4]);
+            store_temp_felt252([0]) -> ([0]);
+            return([0]);
+
+            outer@0([0]: felt252, [1]: felt252) -> (felt252);
+            inner@12([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let program_info = ProgramRegistryInfo::new(&program).unwrap();
+    let metadata = calc_metadata_ap_change_only(&program, &program_info).unwrap();
+    let config = SierraToCasmConfig {
+        gas_usage_check: false,
+        max_bytecode_size: usize::MAX,
+        record_variable_locations: false,
+        coalesce_ap_updates: true,
+    };
+    let casm = compile(&program, &program_info, &metadata, config).unwrap();
+    // The `ap += 1` of `finalize_locals` is coalesced into the following `store_local`.
+    assert_eq!(
+        casm.symbolized_listing(&casm.function_symbols(&program)),
+        indoc! {"
+            outer:
+                [fp + 0] = [fp + -4], ap++; // 0
+                jmp rel 6 if [fp + -3] != 0; // 1 -> outer+7
+                [ap + 3] = [fp + 0], ap++; // 3
+                ap += 3; // 4
+                ret; // 6
+                [ap + 0] = [fp + 0], ap++; // 7
+                call rel 3; // 8 -> inner+0
+                ret; // 10
+            inner:
+                [ap + 0] = [fp + -3], ap++; // 11
+                ret; // 12
+        "}
+    );
+    let statement_offsets = casm
+        .debug_info
+        .sierra_statement_info
+        .iter()
+        .map(|info| (info.start_offset, info.end_offset))
+        .collect_vec();
+    assert_eq!(
+        statement_offsets,
+        vec![
+            (0, 0),
+            (0, 0),
+            (0, 1),
+            (1, 3),
+            (3, 3),
+            (3, 6),
+            (6, 7),
+            (7, 7),
+            (7, 7),
+            (7, 8),
+            (8, 10),
+            (10, 11),
+            (11, 12),
+            (12, 13)
+        ]
+    );
+}
+
 cairo_lang_test_utils::test_file_test!(
     compiler_errors,
     "src/test_data",
@@ -822,7 +1105,12 @@ fn compiler_errors(
             &program,
             &info,
             &metadata,
-            SierraToCasmConfig { gas_usage_check: false, max_bytecode_size },
+            SierraToCasmConfig {
+                gas_usage_check: false,
+                max_bytecode_size,
+                record_variable_locations: false,
+                coalesce_ap_updates: false,
+            },
         )
         .expect_err("Compilation is expected to fail.")
         .to_string(),
@@ -2,6 +2,8 @@ use cairo_lang_casm::builder::{CasmBuilder, Var};
 use cairo_lang_casm::casm_build_extend;
 use cairo_lang_sierra::extensions::ec::{EcConcreteLibfunc, EcPointType};
 use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_utils::casts::IntoOrPanic;
+use itertools::Itertools;
 use starknet_types_core::felt::{Felt as Felt252, NonZeroFelt};
 
 use super::{CompiledInvocation, CompiledInvocationBuilder, InvocationError};
@@ -23,6 +25,9 @@ pub fn build(
         EcConcreteLibfunc::StateFinalize(_) => build_ec_state_finalize(builder),
         EcConcreteLibfunc::StateInit(_) => build_ec_state_init(builder),
         EcConcreteLibfunc::StateAddMul(_) => build_ec_state_add_mul(builder),
+        EcConcreteLibfunc::StateAddMulMany(libfunc) => {
+            build_ec_state_add_mul_many(builder, libfunc.count)
+        }
         EcConcreteLibfunc::PointFromX(_) => build_ec_point_from_x_nz(builder),
         EcConcreteLibfunc::UnwrapPoint(_) => build_ec_point_unwrap(builder),
         EcConcreteLibfunc::Zero(_) => build_ec_zero(builder),
@@ -461,3 +466,77 @@ fn build_ec_state_add_mul(
         Default::default(),
     ))
 }
+
+/// Handles instruction for computing `S + M_0 * Q_0 + ... + M_{count-1} * Q_{count-1}` where `S` is
+/// an EC state, the `M_i`s are scalars (felt252) and the `Q_i`s are EC points.
+///
+/// Each EC op builtin instance starts from the result of the previous instance.
+fn build_ec_state_add_mul_many(
+    builder: CompiledInvocationBuilder<'_>,
+    count: usize,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [ec_builtin_expr, expr_state, expr_products @ ..] = builder.refs else {
+        return Err(InvocationError::WrongNumberOfArguments {
+            expected: 2 + 2 * count,
+            actual: builder.refs.len(),
+        });
+    };
+    if expr_products.len() != 2 * count {
+        return Err(InvocationError::WrongNumberOfArguments {
+            expected: 2 + 2 * count,
+            actual: builder.refs.len(),
+        });
+    }
+    let ec_builtin = ec_builtin_expr.expression.try_unpack_single()?;
+    let [sx, sy, random_ptr] = expr_state.expression.try_unpack()?;
+
+    let count_i16: i16 = count.into_or_panic();
+    let mut casm_builder = CasmBuilder::with_capacity(7 * count, 2 * (count - 1));
+    add_input_variables! {casm_builder,
+        buffer(7 * count_i16 - 1) ec_builtin;
+        deref sx;
+        deref sy;
+        deref random_ptr;
+    };
+    let mut products = vec![];
+    for (expr_m, expr_point) in expr_products.iter().tuples() {
+        let [m] = expr_m.expression.try_unpack()?;
+        let [px, py] = expr_point.expression.try_unpack()?;
+        add_input_variables! {casm_builder,
+            deref m;
+            deref px;
+            deref py;
+        };
+        products.push((m, px, py));
+    }
+    casm_build_extend! {casm_builder,
+        assert sx = *(ec_builtin++);
+        assert sy = *(ec_builtin++);
+    };
+    let mut result = None;
+    for (m, px, py) in products {
+        if let Some((result_x, result_y)) = result {
+            // Copying the result of the previous instance, as it is the `P` of this instance.
+            casm_build_extend! {casm_builder,
+                tempvar acc_x = result_x;
+                tempvar acc_y = result_y;
+                assert acc_x = *(ec_builtin++);
+                assert acc_y = *(ec_builtin++);
+            };
+        }
+        casm_build_extend! {casm_builder,
+            assert px = *(ec_builtin++);
+            assert py = *(ec_builtin++);
+            assert m = *(ec_builtin++);
+            let result_x = *(ec_builtin++);
+            let result_y = *(ec_builtin++);
+        };
+        result = Some((result_x, result_y));
+    }
+    let (result_x, result_y) = result.unwrap();
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [("Fallthrough", &[&[ec_builtin], &[result_x, result_y, random_ptr]], None)],
+        Default::default(),
+    ))
+}
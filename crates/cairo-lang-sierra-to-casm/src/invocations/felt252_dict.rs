@@ -5,9 +5,11 @@ use cairo_lang_sierra::extensions::felt252_dict::{
 };
 use cairo_lang_sierra_gas::core_libfunc_cost::{
     DICT_SQUASH_FIXED_COST, DICT_SQUASH_REPEATED_ACCESS_COST, DICT_SQUASH_UNIQUE_KEY_COST,
-    SEGMENT_ARENA_ALLOCATION_COST,
+    SEGMENT_ARENA_ALLOCATION_COST, dict_squash_many_fixed_cost,
 };
 use cairo_lang_sierra_gas::objects::ConstCost;
+use cairo_lang_utils::casts::IntoOrPanic;
+use itertools::chain;
 
 use super::{CompiledInvocation, CompiledInvocationBuilder, InvocationError};
 use crate::invocations::{CostValidationInfo, add_input_variables};
@@ -23,6 +25,9 @@ pub fn build_dict(
     match libfunc {
         Felt252DictConcreteLibfunc::New(_) => build_felt252_dict_new(builder),
         Felt252DictConcreteLibfunc::Squash(_) => build_felt252_dict_squash(builder),
+        Felt252DictConcreteLibfunc::SquashMany(libfunc) => {
+            build_felt252_dict_squash_many(builder, libfunc.count)
+        }
     }
 }
 
@@ -261,6 +266,248 @@ fn build_felt252_dict_squash(
     ))
 }
 
+/// Handles the dict_squash_many instruction.
+///
+/// Squashes `count` dicts of the same segment arena within a single function scope, reading and
+/// updating the segment arena only once for all of them.
+fn build_felt252_dict_squash_many(
+    builder: CompiledInvocationBuilder<'_>,
+    count: usize,
+) -> Result<CompiledInvocation, InvocationError> {
+    let cells = builder
+        .refs
+        .iter()
+        .map(|r| r.expression.try_unpack_single())
+        .collect::<Result<Vec<_>, _>>()?;
+    let wrong_arg_count =
+        || InvocationError::WrongNumberOfArguments { expected: 3 + count, actual: cells.len() };
+    let [range_check_ptr, gas_builtin, segment_arena_ptr, dict_end_addresses @ ..] = &cells[..]
+    else {
+        return Err(wrong_arg_count());
+    };
+    if dict_end_addresses.len() != count || count == 0 {
+        return Err(wrong_arg_count());
+    }
+    let (range_check_ptr, gas_builtin, segment_arena_ptr) =
+        (*range_check_ptr, *gas_builtin, *segment_arena_ptr);
+    // Counters for the amount of steps in the generated code.
+    let mut fixed_steps: i32 = 0;
+    // Steps executed for every squashed dict, of code that is generated only once.
+    let mut per_dict_steps: i32 = 0;
+    let mut unique_key_steps: i32 = 0;
+    let mut repeated_access_steps: i32 = 0;
+
+    let mut casm_builder = CasmBuilder::with_capacity(120 + 30 * count, 18);
+    add_input_variables! {casm_builder,
+        buffer(2) segment_arena_ptr;
+        buffer(0) range_check_ptr;
+        deref gas_builtin;
+    };
+    let mut squash_many_arg_dict_end_addresses = Vec::with_capacity(count);
+    for dict_end_address in dict_end_addresses {
+        squash_many_arg_dict_end_addresses.push(
+            casm_builder.add_var(
+                dict_end_address
+                    .to_buffer(0)
+                    .ok_or(InvocationError::InvalidReferenceExpressionForArgument)?,
+            ),
+        );
+    }
+    let count_i16: i16 = count.into_or_panic();
+    casm_build_extend! {casm_builder,
+        #{ validate steps == 0; }
+        const dict_access_size = DICT_ACCESS_SIZE;
+        const dict_info_size = 3;
+        const one = 1;
+        const gas_refund_per_access =
+            DICT_SQUASH_UNIQUE_KEY_COST.cost() - DICT_SQUASH_REPEATED_ACCESS_COST.cost();
+        const dicts_count = count_i16;
+        // SquashMany is a wrapper that provides a clean scope for all the dict squashes, where
+        // local variables can be allocated.
+        // Push SquashMany arguments.
+        tempvar squash_many_arg_range_check_ptr = range_check_ptr;
+        tempvar squash_many_arg_gas_builtin = gas_builtin;
+        tempvar squash_many_arg_segment_arena_ptr = segment_arena_ptr;
+    };
+    for dict_end_address in squash_many_arg_dict_end_addresses.iter_mut() {
+        let value = *dict_end_address;
+        casm_build_extend!(casm_builder, tempvar squash_many_arg_dict_end_address = value;);
+        *dict_end_address = squash_many_arg_dict_end_address;
+    }
+    casm_build_extend! {casm_builder,
+        let () = call SquashMany;
+        // The code following the call does not change `ap`, so the values returned by SquashMany
+        // are the outputs of the libfunc.
+        jump DONE;
+
+        SquashMany:
+        localvar infos = squash_many_arg_segment_arena_ptr[-3];
+        localvar n_dicts = squash_many_arg_segment_arena_ptr[-2];
+        localvar n_destructed = squash_many_arg_segment_arena_ptr[-1];
+        localvar n_dicts_minus_1 = n_dicts - one;
+    };
+    // Allocates function local variables for data needed after the calls to SquashDict: the length
+    // of the accesses of each dict, the accumulated number of refunded accesses (multiplied by
+    // the dict access size), and the squashed dicts except for the last.
+    let dict_locals = (0..count)
+        .map(|_| [casm_builder.alloc_var(true), casm_builder.alloc_var(true)])
+        .collect::<Vec<_>>();
+    let squashed_dict_locals = (1..count)
+        .map(|_| [casm_builder.alloc_var(true), casm_builder.alloc_var(true)])
+        .collect::<Vec<_>>();
+    casm_builder.add_ap(4 * count - 2);
+    casm_build_extend! {casm_builder,
+        // Write a new dict_manager data to the dict_manager segment (same except for the
+        // n_destructed which is incremented by the number of squashed dicts).
+        assert infos = *(squash_many_arg_segment_arena_ptr++);
+        assert n_dicts = *(squash_many_arg_segment_arena_ptr++);
+        tempvar n_destructed_plus_count = n_destructed + dicts_count;
+        assert n_destructed_plus_count = *(squash_many_arg_segment_arena_ptr++);
+    };
+    let mut range_check_ptr = squash_many_arg_range_check_ptr;
+    let mut prev_refunded_len = None;
+    let mut squashed_dicts = Vec::with_capacity(count);
+    for (i, (dict_end_address, [dict_accesses_len, refunded_len])) in
+        squash_many_arg_dict_end_addresses.into_iter().zip(dict_locals).enumerate()
+    {
+        let dict_offset: i16 = i.into_or_panic();
+        casm_build_extend! {casm_builder,
+            // Guess the index of the dictionary.
+            tempvar dict_index;
+            hint GetSegmentArenaIndex {dict_end_ptr: dict_end_address} into {dict_index};
+            // Verify that dict_index < n_dicts.
+            // Range check use
+            assert dict_index = *(range_check_ptr++);
+            tempvar n_dicts_minus_1_minus_index = n_dicts_minus_1 - dict_index;
+            // Range check use
+            assert n_dicts_minus_1_minus_index = *(range_check_ptr++);
+            // Write the missing data in the dict_info (destruction index and the end of the
+            // dict_segment).
+            tempvar info_offset = dict_index * dict_info_size;
+            tempvar info_ptr = infos + info_offset;
+            const dict_offset = dict_offset;
+            tempvar destruction_index = n_destructed + dict_offset;
+            assert destruction_index = info_ptr[2];
+            assert dict_end_address = info_ptr[1];
+        };
+        // Split just to avoid recursion limit when the macro is parsed.
+        casm_build_extend! {casm_builder,
+            // Push SquashDict arguments.
+            tempvar dict_squash_arg_range_check_ptr = range_check_ptr;
+            tempvar dict_squash_arg_dict_accesses_start = info_ptr[0];
+            tempvar dict_squash_arg_dict_accesses_end = dict_end_address;
+            // Compute the length of the access segment, and store it in a local variable.
+            assert dict_accesses_len = dict_end_address - dict_squash_arg_dict_accesses_start;
+            let (returned_range_check_ptr, squashed_dict_end, squashed_dict_start) =
+                call SquashDict;
+            // Find the number of keys.
+            tempvar squashed_dict_len = squashed_dict_end - squashed_dict_start;
+            // The number of refunded accesses is number_of_accesses - number_of_keys, which equals
+            // to dict_accesses_len / dict_access_size - squashed_dict_len / dict_access_size.
+            // The division is applied once, on the sum over all the dicts.
+            tempvar accesses_len_minus_squashed_len = dict_accesses_len - squashed_dict_len;
+        };
+        if let Some(prev_refunded_len) = prev_refunded_len {
+            casm_build_extend! {casm_builder,
+                assert refunded_len = prev_refunded_len + accesses_len_minus_squashed_len;
+            };
+        } else {
+            casm_build_extend!(casm_builder, assert refunded_len = accesses_len_minus_squashed_len;);
+        }
+        if let Some([local_squashed_dict_start, local_squashed_dict_end]) =
+            squashed_dict_locals.get(i).copied()
+        {
+            casm_build_extend! {casm_builder,
+                assert local_squashed_dict_start = squashed_dict_start;
+                assert local_squashed_dict_end = squashed_dict_end;
+            };
+            squashed_dicts.push([local_squashed_dict_start, local_squashed_dict_end]);
+        } else {
+            // The last squashed dict is still available on the stack.
+            squashed_dicts.push([squashed_dict_start, squashed_dict_end]);
+        }
+        range_check_ptr = returned_range_check_ptr;
+        prev_refunded_len = Some(refunded_len);
+        if i != 0 {
+            continue;
+        }
+        // The body of SquashDict is placed right after its first call, as the arguments of each of
+        // its calls are different variables.
+        casm_build_extend!(casm_builder, jump SquashManyRest;);
+        let (squash_dict_inner_args, squash_dict_steps) = build_squash_dict(
+            &mut casm_builder,
+            dict_access_size,
+            one,
+            SquashDictArgs {
+                squash_dict_arg_range_check_ptr: dict_squash_arg_range_check_ptr,
+                squash_dict_arg_dict_accesses_start: dict_squash_arg_dict_accesses_start,
+                squash_dict_arg_dict_accesses_end: dict_squash_arg_dict_accesses_end,
+            },
+        );
+        per_dict_steps += squash_dict_steps;
+        let (squash_dict_inner_steps, unique_key_steps_, repeated_access_steps_) =
+            build_squash_dict_inner(&mut casm_builder, squash_dict_inner_args);
+        per_dict_steps += squash_dict_inner_steps;
+        unique_key_steps += unique_key_steps_;
+        repeated_access_steps += repeated_access_steps_;
+        casm_build_extend!(casm_builder, SquashManyRest:);
+    }
+    let refunded_len = prev_refunded_len.unwrap();
+    casm_build_extend! {casm_builder,
+        tempvar n_refunded_accesses = refunded_len / dict_access_size;
+        tempvar gas_to_refund = n_refunded_accesses * gas_refund_per_access;
+        // Push the returned variables.
+        tempvar returned_range_check_ptr = range_check_ptr;
+        tempvar returned_gas_builtin = squash_many_arg_gas_builtin + gas_to_refund;
+        tempvar returned_segment_arena_ptr = squash_many_arg_segment_arena_ptr;
+    };
+    for [squashed_dict_start, squashed_dict_end] in &mut squashed_dicts {
+        let (start, end) = (*squashed_dict_start, *squashed_dict_end);
+        casm_build_extend! {casm_builder,
+            tempvar returned_squashed_dict_start = start;
+            tempvar returned_squashed_dict_end = end;
+        };
+        *squashed_dict_start = returned_squashed_dict_start;
+        *squashed_dict_end = returned_squashed_dict_end;
+    }
+    let outputs = chain!(
+        [returned_range_check_ptr, returned_gas_builtin, returned_segment_arena_ptr]
+            .map(|var| ReferenceExpression { cells: vec![casm_builder.get_adjusted(var)] }),
+        squashed_dicts.iter().map(|squashed_dict| ReferenceExpression {
+            cells: squashed_dict.map(|var| casm_builder.get_adjusted(var)).to_vec(),
+        })
+    )
+    .collect::<Vec<_>>();
+    casm_build_extend! {casm_builder,
+        ret;
+        #{ fixed_steps += steps; steps = 0; }
+        DONE:
+        #{ fixed_steps += steps; steps = 0; }
+    };
+    fixed_steps += count_i16 as i32 * per_dict_steps;
+    // Manually counted, range check uses are marked in the builder code.
+    let fixed_range_checks = 3 * count_i16 as i32;
+    assert_eq!(
+        ConstCost {
+            steps: fixed_steps,
+            holes: 0,
+            range_checks: fixed_range_checks,
+            range_checks96: 0
+        },
+        dict_squash_many_fixed_cost(count)
+    );
+    assert_eq!(unique_key_steps, DICT_SQUASH_UNIQUE_KEY_COST.steps);
+    assert_eq!(repeated_access_steps, DICT_SQUASH_REPEATED_ACCESS_COST.steps);
+    let CasmBuildResult { instructions, branches: [(state, _)] } =
+        casm_builder.build(["Fallthrough"]);
+    assert_eq!(
+        state.ap_change, 0,
+        "The outputs are relative to the `ap` at the end of SquashMany."
+    );
+
+    Ok(builder.build(instructions, vec![], [outputs.into_iter()].into_iter()))
+}
+
 struct SquashDictArgs {
     pub squash_dict_arg_range_check_ptr: Var,
     pub squash_dict_arg_dict_accesses_start: Var,
@@ -161,19 +161,17 @@ fn build_get_unspent_gas(
     casm_build_extend! {casm_builder,
         tempvar builtin_cost = cost_builtin_ptr;
         const const_count = get_token_count(CostTokenType::Const);
-        tempvar total_unspent = gas_counter + const_count;
-    };
-    let mut total_unspent = total_unspent;
-    for token_type in CostTokenType::iter_precost() {
-        let index = token_type.offset_in_builtin_costs();
-        casm_build_extend! {casm_builder,
-            tempvar single_cost = builtin_cost[index];
+        tempvar const_unspent = gas_counter + const_count;
+        let total_unspent = fold (
+            unspent = const_unspent,
+            token_type in CostTokenType::iter_precost()
+        ) {
+            tempvar single_cost = builtin_cost[token_type.offset_in_builtin_costs()];
             const count = get_token_count(*token_type);
             tempvar multi_cost = single_cost * count;
-            tempvar updated_total_unspent = total_unspent + multi_cost;
-        };
-        total_unspent = updated_total_unspent;
-    }
+            tempvar updated_unspent = unspent + multi_cost;
+        } => updated_unspent;
+    };
     Ok(builder.build_from_casm_builder_ex(
         casm_builder,
         [("Fallthrough", &[&[gas_counter], &[total_unspent]], None)],
@@ -1,6 +1,6 @@
 use std::ops::Shl;
 
-use cairo_lang_casm::builder::CasmBuilder;
+use cairo_lang_casm::builder::{CasmBuilder, Var};
 use cairo_lang_casm::casm_build_extend;
 use cairo_lang_sierra::extensions::bounded_int::{
     BoundedIntConcreteLibfunc, BoundedIntDivRemAlgorithm,
@@ -39,6 +39,15 @@ pub fn build(
         BoundedIntConcreteLibfunc::Constrain(libfunc) => {
             build_constrain(builder, &libfunc.boundary)
         }
+        BoundedIntConcreteLibfunc::AddConstrain(libfunc) => {
+            build_op_constrain(builder, Felt252BinaryOperator::Add, &libfunc.boundary)
+        }
+        BoundedIntConcreteLibfunc::MulConstrain(libfunc) => {
+            build_op_constrain(builder, Felt252BinaryOperator::Mul, &libfunc.boundary)
+        }
+        BoundedIntConcreteLibfunc::DivRemConstrain(libfunc) => {
+            build_div_rem_constrain(builder, &libfunc.lhs, &libfunc.rhs, &libfunc.boundary)
+        }
         BoundedIntConcreteLibfunc::TrimMin(libfunc)
         | BoundedIntConcreteLibfunc::TrimMax(libfunc) => {
             build_trim(builder, &libfunc.trimmed_value)
@@ -60,18 +69,85 @@ pub fn build_div_rem(
     let alg = BoundedIntDivRemAlgorithm::try_new(lhs, rhs).unwrap();
 
     let mut casm_builder = CasmBuilder::with_capacity(12, 2);
-    let rc_slack = match &alg {
-        BoundedIntDivRemAlgorithm::KnownSmallRhs => 2,
-        BoundedIntDivRemAlgorithm::KnownSmallQuotient { .. }
-        | BoundedIntDivRemAlgorithm::KnownSmallLhs { .. } => 3,
+    add_input_variables! {casm_builder,
+        buffer(div_rem_rc_slack(&alg)) range_check;
+        deref a;
+        deref b;
     };
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    let (q, r) = add_div_rem_checks(&mut casm_builder, range_check, a, b, alg);
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [("Fallthrough", &[&[range_check], &[q], &[r]], None)],
+        CostValidationInfo {
+            builtin_infos: vec![BuiltinInfo {
+                cost_token_ty: CostTokenType::RangeCheck,
+                start: orig_range_check,
+                end: range_check,
+            }],
+            extra_costs: None,
+        },
+    ))
+}
+
+/// Build div rem on bounded ints, with a constraint of the quotient to one of the ranges split by
+/// `boundary`.
+fn build_div_rem_constrain(
+    builder: CompiledInvocationBuilder<'_>,
+    lhs: &Range,
+    rhs: &Range,
+    boundary: &BigInt,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [range_check, a, b] = builder.try_get_single_cells()?;
+
+    let alg = BoundedIntDivRemAlgorithm::try_new(lhs, rhs).unwrap();
+
+    let mut casm_builder = CasmBuilder::with_capacity(18, 3);
     add_input_variables! {casm_builder,
-        buffer(rc_slack) range_check;
+        buffer(div_rem_rc_slack(&alg) + 1) range_check;
         deref a;
         deref b;
     };
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    let (q, r) = add_div_rem_checks(&mut casm_builder, range_check, a, b, alg);
+    add_constrain_checks(&mut casm_builder, range_check, q, boundary);
+    let target_statement_id = get_non_fallthrough_statement_id(&builder);
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [
+            ("Fallthrough", &[&[range_check], &[q], &[r]], None),
+            ("Over", &[&[range_check], &[q], &[r]], Some(target_statement_id)),
+        ],
+        CostValidationInfo {
+            builtin_infos: vec![BuiltinInfo {
+                cost_token_ty: CostTokenType::RangeCheck,
+                start: orig_range_check,
+                end: range_check,
+            }],
+            extra_costs: None,
+        },
+    ))
+}
+
+/// Returns the number of range check usages of the given div rem algorithm, minus 1.
+fn div_rem_rc_slack(alg: &BoundedIntDivRemAlgorithm) -> i16 {
+    match alg {
+        BoundedIntDivRemAlgorithm::KnownSmallRhs => 2,
+        BoundedIntDivRemAlgorithm::KnownSmallQuotient { .. }
+        | BoundedIntDivRemAlgorithm::KnownSmallLhs { .. } => 3,
+    }
+}
+
+/// Adds the instructions computing and verifying the quotient and remainder of `a` divided by `b`
+/// using `alg`. Returns the variables of the quotient and remainder.
+fn add_div_rem_checks(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    a: Var,
+    b: Var,
+    alg: BoundedIntDivRemAlgorithm,
+) -> (Var, Var) {
     casm_build_extend! {casm_builder,
-        let orig_range_check = range_check;
         tempvar r_plus_1;
         tempvar b_minus_r_minus_1;
     };
@@ -164,9 +240,30 @@ pub fn build_div_rem(
         assert bq = b * q;
         assert a = bq + r;
     }
+    (q, r)
+}
+
+/// Build constrain on bounded ints.
+fn build_constrain(
+    builder: CompiledInvocationBuilder<'_>,
+    boundary: &BigInt,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [range_check, value] = builder.try_get_single_cells()?;
+
+    let mut casm_builder = CasmBuilder::with_capacity(6, 2);
+    add_input_variables! {casm_builder,
+        buffer(1) range_check;
+        deref value;
+    };
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    add_constrain_checks(&mut casm_builder, range_check, value, boundary);
+    let target_statement_id = get_non_fallthrough_statement_id(&builder);
     Ok(builder.build_from_casm_builder(
         casm_builder,
-        [("Fallthrough", &[&[range_check], &[q], &[r]], None)],
+        [
+            ("Fallthrough", &[&[range_check], &[value]], None),
+            ("Over", &[&[range_check], &[value]], Some(target_statement_id)),
+        ],
         CostValidationInfo {
             builtin_infos: vec![BuiltinInfo {
                 cost_token_ty: CostTokenType::RangeCheck,
@@ -178,36 +275,37 @@ pub fn build_div_rem(
     ))
 }
 
-/// Build constrain on bounded ints.
-fn build_constrain(
+/// Build a binary operation on bounded ints, with a constraint of the result to one of the ranges
+/// split by `boundary`.
+fn build_op_constrain(
     builder: CompiledInvocationBuilder<'_>,
+    op: Felt252BinaryOperator,
     boundary: &BigInt,
 ) -> Result<CompiledInvocation, InvocationError> {
-    let [range_check, value] = builder.try_get_single_cells()?;
+    let [range_check, lhs, rhs] = builder.try_get_single_cells()?;
 
-    let mut casm_builder = CasmBuilder::with_capacity(6, 2);
+    let mut casm_builder = CasmBuilder::with_capacity(7, 2);
     add_input_variables! {casm_builder,
         buffer(1) range_check;
-        deref value;
+        deref lhs;
+        deref rhs;
     };
     casm_build_extend! {casm_builder,
         let orig_range_check = range_check;
-        const under_fixer = (BigInt::one().shl(128) - boundary) as BigInt;
-        const rc_bound_imm = BigInt::one().shl(128) as BigInt;
-        const minus_boundary = -boundary;
-        tempvar is_under;
-        let canonical_value = value + minus_boundary;
-        hint TestLessThanOrEqual {lhs: rc_bound_imm, rhs: canonical_value} into {dst: is_under};
-        jump Under if is_under != 0;
-    // Over:
-        maybe_tempvar shifted_value = canonical_value;
-        assert shifted_value = *(range_check++);
-        jump Over;
-    Under:
-        // value < boundary  <=>  value + (2**128 - boundary) < 2**128.
-        maybe_tempvar shifted_value = value + under_fixer;
-        assert shifted_value = *(range_check++);
+        tempvar value;
     };
+    match op {
+        Felt252BinaryOperator::Add => {
+            casm_build_extend!(casm_builder, assert value = lhs + rhs;);
+        }
+        Felt252BinaryOperator::Mul => {
+            casm_build_extend!(casm_builder, assert value = lhs * rhs;);
+        }
+        Felt252BinaryOperator::Sub | Felt252BinaryOperator::Div => {
+            unreachable!("Only addition and multiplication are constrained.")
+        }
+    }
+    add_constrain_checks(&mut casm_builder, range_check, value, boundary);
     let target_statement_id = get_non_fallthrough_statement_id(&builder);
     Ok(builder.build_from_casm_builder(
         casm_builder,
@@ -226,6 +324,34 @@ fn build_constrain(
     ))
 }
 
+/// Adds the instructions branching on whether `value` is below `boundary`, and range checking
+/// `value` to be within the range of the taken branch.
+/// Falls through if `value < boundary`, and jumps to the `Over` label otherwise.
+fn add_constrain_checks(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    value: Var,
+    boundary: &BigInt,
+) {
+    casm_build_extend! {casm_builder,
+        const under_fixer = (BigInt::one().shl(128) - boundary) as BigInt;
+        const rc_bound_imm = BigInt::one().shl(128) as BigInt;
+        const minus_boundary = -boundary;
+        tempvar is_under;
+        let canonical_value = value + minus_boundary;
+        hint TestLessThanOrEqual {lhs: rc_bound_imm, rhs: canonical_value} into {dst: is_under};
+        jump Under if is_under != 0;
+    // Over:
+        maybe_tempvar shifted_value = canonical_value;
+        assert shifted_value = *(range_check++);
+        jump Over;
+    Under:
+        // value < boundary  <=>  value + (2**128 - boundary) < 2**128.
+        maybe_tempvar shifted_value = value + under_fixer;
+        assert shifted_value = *(range_check++);
+    };
+}
+
 /// Build trim on bounded ints.
 fn build_trim(
     builder: CompiledInvocationBuilder<'_>,
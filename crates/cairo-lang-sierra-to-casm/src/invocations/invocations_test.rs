@@ -0,0 +1,12 @@
+use crate::invocations::test_utils::test_compile_libfunc;
+
+cairo_lang_test_utils::test_file_test!(
+    compile_libfunc,
+    "src/invocations/test_data",
+    {
+        bitwise: "bitwise",
+        blake: "blake",
+        felt252: "felt252",
+    },
+    test_compile_libfunc
+);
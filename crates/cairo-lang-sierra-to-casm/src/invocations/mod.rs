@@ -70,6 +70,10 @@ mod unsafe_panic;
 #[cfg(test)]
 mod test_utils;
 
+#[cfg(test)]
+#[path = "invocations_test.rs"]
+mod test;
+
 #[derive(Error, Debug, Eq, PartialEq)]
 pub enum InvocationError {
     #[error("One of the arguments does not satisfy the requirements of the libfunc.")]
@@ -97,6 +101,54 @@ pub enum InvocationError {
     #[error("{output_ty} is not contained in the circuit {circuit_ty}.")]
     InvalidCircuitOutput { output_ty: ConcreteTypeId, circuit_ty: ConcreteTypeId },
 }
+impl InvocationError {
+    /// Returns a suggestion for fixing the error, given the expressions of the arguments of the
+    /// failing invocation.
+    pub fn suggestion(&self, refs: &[ReferenceExpression]) -> Option<String> {
+        match self {
+            InvocationError::InvalidReferenceExpressionForArgument => Some(
+                match refs.iter().position(|expr| {
+                    expr.cells.iter().any(|cell| {
+                        !matches!(cell, CellExpression::Deref(_) | CellExpression::Immediate(_))
+                    })
+                }) {
+                    Some(idx) => format!(
+                        "Argument #{idx} (`{}`) must be a deref; add a `store_temp` before the \
+                         invocation.",
+                        refs[idx]
+                    ),
+                    None => "The arguments may be required to be derefs or to be consecutive on \
+                             the stack; add a `store_temp` for each of them before the invocation."
+                        .to_string(),
+                },
+            ),
+            InvocationError::WrongNumberOfArguments { expected, actual } => {
+                Some(format!("Pass {expected} arguments instead of {actual}."))
+            }
+            InvocationError::UnknownTypeData | InvocationError::UnknownVariableData => Some(
+                "The metadata does not match the program; recompute it for this program."
+                    .to_string(),
+            ),
+            InvocationError::FrameStateError(FrameStateError::InvalidAllocLocal(
+                FrameState::Finalized { .. },
+            )) => Some("Move the `alloc_local` before the `finalize_locals`.".to_string()),
+            InvocationError::FrameStateError(FrameStateError::InvalidFinalizeLocals(
+                FrameState::Finalized { .. },
+            )) => Some("Remove the repeated `finalize_locals`.".to_string()),
+            InvocationError::FrameStateError(
+                FrameStateError::InvalidAllocLocal(_) | FrameStateError::InvalidFinalizeLocals(_),
+            ) => Some(
+                "Allocate and finalize the locals at the start of the function, before any change \
+                 to ap or to the ap tracking."
+                    .to_string(),
+            ),
+            InvocationError::FrameStateError(FrameStateError::FinalizeLocalsMissing(_)) => {
+                Some("Add a `finalize_locals` after the last `alloc_local`.".to_string())
+            }
+            _ => None,
+        }
+    }
+}
 
 /// Describes a simple change in the ap tracking itself.
 #[derive(Clone, Debug, Eq, PartialEq)]
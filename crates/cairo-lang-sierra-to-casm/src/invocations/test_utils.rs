@@ -1,5 +1,7 @@
 use cairo_lang_casm::ap_change::ApChange;
+use cairo_lang_casm::cell_expression::{CellExpression, CellOperator};
 use cairo_lang_casm::instructions::Instruction;
+use cairo_lang_casm::operand::{CellRef, DerefOrImmediate, Register};
 use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
 use cairo_lang_sierra::extensions::lib_func::{
     SignatureSpecializationContext, SpecializationContext,
@@ -14,10 +16,13 @@ use cairo_lang_sierra::program::{BranchInfo, BranchTarget, Invocation, Statement
 use cairo_lang_sierra_ap_change::ap_change_info::ApChangeInfo;
 use cairo_lang_sierra_gas::gas_info::GasInfo;
 use cairo_lang_sierra_type_size::TypeSizeMap;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use itertools::{Itertools, zip_eq};
+use num_bigint::BigInt;
 
-use super::{CompiledInvocation, ProgramInfo, compile_invocation};
+use super::{CompiledInvocation, InvocationError, ProgramInfo, compile_invocation};
 use crate::environment::Environment;
 use crate::environment::gas_wallet::GasWallet;
 use crate::metadata::Metadata;
@@ -211,6 +216,15 @@ impl std::fmt::Debug for ReducedCompiledInvocation {
 ///
 /// Currently, only works if all the libfunc's types (both inputs and output) are of size 1.
 pub fn compile_libfunc(libfunc: &str, refs: Vec<ReferenceExpression>) -> ReducedCompiledInvocation {
+    try_compile_libfunc(libfunc, refs).expect("Failed to compile invocation.")
+}
+
+/// Same as [compile_libfunc], but returns the error of the invocation compilation instead of
+/// panicking.
+pub fn try_compile_libfunc(
+    libfunc: &str,
+    refs: Vec<ReferenceExpression>,
+) -> Result<ReducedCompiledInvocation, InvocationError> {
     let long_id = cairo_lang_sierra::ConcreteLibfuncLongIdParser::new().parse(libfunc).unwrap();
     let mut context = MockSpecializationContext { type_infos: Default::default() };
     {
@@ -266,31 +280,147 @@ pub fn compile_libfunc(libfunc: &str, refs: Vec<ReferenceExpression>) -> Reduced
         .collect();
 
     let environment: Environment = Environment::new(GasWallet::Disabled);
-    ReducedCompiledInvocation::new(
-        compile_invocation(
-            program_info,
-            &Invocation {
-                libfunc_id: "".into(),
-                args: (0..args.len() as u64).map(VarId::new).collect(),
-                branches: libfunc
-                    .branch_signatures()
-                    .iter()
-                    .enumerate()
-                    .map(|(i, branch)| BranchInfo {
-                        target: if libfunc.fallthrough() == Some(i) {
-                            BranchTarget::Fallthrough
-                        } else {
-                            BranchTarget::Statement(StatementIdx(i))
-                        },
-                        results: (0..branch.vars.len() as u64).map(VarId::new).collect(),
-                    })
-                    .collect(),
-            },
-            &libfunc,
-            StatementIdx(0),
-            &args,
-            environment,
-        )
-        .expect("Failed to compile invocation."),
+    compile_invocation(
+        program_info,
+        &Invocation {
+            libfunc_id: "".into(),
+            args: (0..args.len() as u64).map(VarId::new).collect(),
+            branches: libfunc
+                .branch_signatures()
+                .iter()
+                .enumerate()
+                .map(|(i, branch)| BranchInfo {
+                    target: if libfunc.fallthrough() == Some(i) {
+                        BranchTarget::Fallthrough
+                    } else {
+                        BranchTarget::Statement(StatementIdx(i))
+                    },
+                    results: (0..branch.vars.len() as u64).map(VarId::new).collect(),
+                })
+                .collect(),
+        },
+        &libfunc,
+        StatementIdx(0),
+        &args,
+        environment,
     )
+    .map(ReducedCompiledInvocation::new)
+}
+
+/// Parses a cell reference of the form `[ap + 1]` or `[fp + -3]`.
+fn parse_cell_ref(text: &str) -> CellRef {
+    let inner = text
+        .trim()
+        .strip_prefix('[')
+        .and_then(|text| text.strip_suffix(']'))
+        .unwrap_or_else(|| panic!("Invalid cell reference `{text}`."));
+    let (register, offset) = inner.split_once(" + ").unwrap_or((inner, "0"));
+    let register = match register.trim() {
+        "ap" => Register::AP,
+        "fp" => Register::FP,
+        other => panic!("Invalid register `{other}`."),
+    };
+    let offset = offset.trim().parse().unwrap_or_else(|_| panic!("Invalid offset `{offset}`."));
+    CellRef { register, offset }
+}
+
+/// Parses a cell expression, in the format it is displayed in.
+fn parse_cell_expression(text: &str) -> CellExpression {
+    let text = text.trim();
+    if let Some(inner) = text.strip_prefix('[').and_then(|text| text.strip_suffix(']'))
+        && inner.starts_with('[')
+    {
+        let (cell, offset) = inner.rsplit_once(" + ").unwrap_or((inner, "0"));
+        let offset = offset.parse().unwrap_or_else(|_| panic!("Invalid offset `{offset}`."));
+        return CellExpression::DoubleDeref(parse_cell_ref(cell), offset);
+    }
+    let Some(end) = text.find(']') else {
+        return CellExpression::Immediate(
+            text.parse().unwrap_or_else(|_| panic!("Invalid cell expression `{text}`.")),
+        );
+    };
+    let (a, rest) = text.split_at(end + 1);
+    let a = parse_cell_ref(a);
+    let Some((op, b)) = rest.trim().split_once(' ') else {
+        return CellExpression::Deref(a);
+    };
+    let op = match op {
+        "+" => CellOperator::Add,
+        "-" => CellOperator::Sub,
+        "*" => CellOperator::Mul,
+        "/" => CellOperator::Div,
+        other => panic!("Invalid operator `{other}`."),
+    };
+    let b = if b.trim().starts_with('[') {
+        DerefOrImmediate::Deref(parse_cell_ref(b))
+    } else {
+        DerefOrImmediate::from(
+            b.trim().parse::<BigInt>().unwrap_or_else(|_| panic!("Invalid operand `{b}`.")),
+        )
+    };
+    CellExpression::BinOp { op, a, b }
+}
+
+/// Parses reference expressions, one per line, with their cells separated by commas.
+fn parse_reference_expressions(text: &str) -> Vec<ReferenceExpression> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| ReferenceExpression {
+            cells: line.split(", ").map(parse_cell_expression).collect(),
+        })
+        .collect()
+}
+
+/// Test runner compiling a libfunc with the given references as arguments, and snapshotting the
+/// generated code and the branch changes.
+///
+/// Inputs:
+/// * `libfunc` - The long id of the libfunc, e.g. `blake2s_compress`.
+/// * `refs` - The references to the arguments, one per line, e.g. `[fp + -3]`.
+///
+/// Outputs:
+/// * `casm` - The generated instructions, including their hints.
+/// * `relocations` - The static relocations of the instructions.
+/// * `branches` - The ap-change and output references of each branch.
+/// * `error` - The error of the compilation, if it failed.
+pub fn test_compile_libfunc(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let compiled = match try_compile_libfunc(
+        inputs["libfunc"].trim(),
+        parse_reference_expressions(inputs.get("refs").map(String::as_str).unwrap_or_default()),
+    ) {
+        Ok(compiled) => compiled,
+        Err(error) => {
+            return TestRunnerResult::success(OrderedHashMap::from([
+                ("casm".into(), "".into()),
+                ("relocations".into(), "".into()),
+                ("branches".into(), "".into()),
+                ("error".into(), format!("{error:?}")),
+            ]));
+        }
+    };
+    let casm = compiled.instructions.iter().map(|inst| format!("{inst};\n")).join("");
+    let relocations =
+        compiled.relocations.iter().map(|relocation| format!("{relocation:?}\n")).join("");
+    let branches = compiled
+        .results
+        .iter()
+        .enumerate()
+        .map(|(i, branch)| {
+            format!(
+                "{i}: {}\n{}",
+                branch.ap_change,
+                branch.refs.iter().map(|r| format!("  {r}\n")).join("")
+            )
+        })
+        .join("");
+    TestRunnerResult::success(OrderedHashMap::from([
+        ("casm".into(), casm),
+        ("relocations".into(), relocations),
+        ("branches".into(), branches),
+        ("error".into(), "".into()),
+    ]))
 }
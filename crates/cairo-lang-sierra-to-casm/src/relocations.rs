@@ -141,7 +141,7 @@ pub fn relocate_instructions(
             }
         }
 
-        program_offset += instruction.body.op_size();
+        program_offset += instruction.encoded_size();
     }
     assert_eq!(
         relocation_entry, None,
@@ -0,0 +1,376 @@
+//! A builder for arithmetic circuits evaluated by the `AddMod` and `MulMod` builtins.
+//!
+//! Builds the Sierra type tree of a circuit from a gate graph, validates it and computes the
+//! builtin instances it requires, and declares the libfuncs needed for evaluating it.
+
+use std::ops::Shl;
+
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+use thiserror::Error;
+
+use crate::extensions::bounded_int::BoundedIntType;
+use crate::extensions::circuit::{
+    AddCircuitInputLibFuncWrapped, AddModGate, AddModType, Circuit, CircuitData, CircuitDescriptor,
+    CircuitFailureGuarantee, CircuitInput, CircuitInputAccumulator, CircuitModulus, CircuitOutputs,
+    CircuitPartialOutputs, EvalCircuitLibFuncWrapped, GetCircuitDescriptorLibFuncWrapped,
+    GetOutputLibFunc, InitCircuitDataLibFuncWrapped, InverseGate, MOD_BUILTIN_INSTANCE_SIZE,
+    MulModGate, MulModType, SubModGate, U96Guarantee, U96LimbsLessThanGuarantee, VALUE_SIZE,
+};
+use crate::extensions::lib_func::SignatureAndTypeGenericLibfunc;
+use crate::extensions::range_check::RangeCheck96Type;
+use crate::extensions::structure::StructType;
+use crate::extensions::{NamedLibfunc, NamedType};
+use crate::ids::{ConcreteLibfuncId, ConcreteTypeId, GenericLibfuncId, GenericTypeId, UserTypeId};
+use crate::program::{
+    ConcreteLibfuncLongId, ConcreteTypeLongId, GenericArg, LibfuncDeclaration, TypeDeclaration,
+};
+
+#[cfg(test)]
+#[path = "circuit_builder_test.rs"]
+mod test;
+
+/// Errors returned when building a circuit.
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum CircuitBuilderError {
+    #[error("A circuit must have at least one output.")]
+    NoOutputs,
+    #[error("Element #{0} was not created by this builder.")]
+    UnknownElement(usize),
+    #[error("Input #{0} is not used by any of the circuit outputs.")]
+    UnusedInput(usize),
+}
+
+/// A handle to an element of a circuit - either an input or the output of a gate.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct CircuitElement(usize);
+
+/// A node of the circuit gate graph.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+enum Gate {
+    /// The input with the given index.
+    Input(usize),
+    AddMod(CircuitElement, CircuitElement),
+    SubMod(CircuitElement, CircuitElement),
+    MulMod(CircuitElement, CircuitElement),
+    Inverse(CircuitElement),
+}
+impl Gate {
+    /// Returns the elements the gate reads.
+    fn operands(&self) -> Vec<CircuitElement> {
+        match *self {
+            Gate::Input(_) => vec![],
+            Gate::AddMod(lhs, rhs) | Gate::SubMod(lhs, rhs) | Gate::MulMod(lhs, rhs) => {
+                vec![lhs, rhs]
+            }
+            Gate::Inverse(input) => vec![input],
+        }
+    }
+}
+
+/// Builds an arithmetic circuit gate by gate.
+///
+/// Gates may only read elements created before them, so the gate graph is always acyclic.
+/// Identical gates are merged, as they are represented by the same Sierra type.
+#[derive(Default)]
+pub struct CircuitBuilder {
+    /// The gates of the circuit, in creation order.
+    gates: Vec<Gate>,
+    /// Maps a gate to its index in `gates`.
+    gate_indices: UnorderedHashMap<Gate, usize>,
+    /// The number of inputs created so far.
+    n_inputs: usize,
+}
+impl CircuitBuilder {
+    /// Adds a new input to the circuit. Inputs are indexed by their creation order.
+    pub fn input(&mut self) -> CircuitElement {
+        let idx = self.n_inputs;
+        self.n_inputs += 1;
+        self.add_gate(Gate::Input(idx))
+    }
+
+    /// Adds a gate computing `lhs + rhs` modulo the circuit modulus.
+    pub fn add(&mut self, lhs: CircuitElement, rhs: CircuitElement) -> CircuitElement {
+        self.add_gate(Gate::AddMod(lhs, rhs))
+    }
+
+    /// Adds a gate computing `lhs - rhs` modulo the circuit modulus.
+    pub fn sub(&mut self, lhs: CircuitElement, rhs: CircuitElement) -> CircuitElement {
+        self.add_gate(Gate::SubMod(lhs, rhs))
+    }
+
+    /// Adds a gate computing `lhs * rhs` modulo the circuit modulus.
+    pub fn mul(&mut self, lhs: CircuitElement, rhs: CircuitElement) -> CircuitElement {
+        self.add_gate(Gate::MulMod(lhs, rhs))
+    }
+
+    /// Adds a gate computing the inverse of `input` modulo the circuit modulus.
+    /// The evaluation of the circuit fails if `input` is not invertible.
+    pub fn inverse(&mut self, input: CircuitElement) -> CircuitElement {
+        self.add_gate(Gate::Inverse(input))
+    }
+
+    /// Adds `gate` to the circuit, unless an identical gate already exists.
+    fn add_gate(&mut self, gate: Gate) -> CircuitElement {
+        let next_idx = self.gates.len();
+        let idx = *self.gate_indices.entry(gate).or_insert(next_idx);
+        if idx == next_idx {
+            self.gates.push(gate);
+        }
+        CircuitElement(idx)
+    }
+
+    /// Validates the circuit with the given outputs, and generates its Sierra declarations.
+    pub fn build(
+        &self,
+        outputs: &[CircuitElement],
+    ) -> Result<CircuitDeclarations, CircuitBuilderError> {
+        if outputs.is_empty() {
+            return Err(CircuitBuilderError::NoOutputs);
+        }
+        let reachable = self.reachable_gates(outputs)?;
+        // Each input is reduced modulo the modulus using a `MulMod` instance.
+        let mut sizes = CircuitSizes {
+            n_inputs: self.n_inputs,
+            n_mul_mods: self.n_inputs,
+            ..CircuitSizes::default()
+        };
+        for (idx, gate) in self.gates.iter().enumerate() {
+            if !reachable.contains(&idx) {
+                // Inputs must be indexed `0, 1, ..., n_inputs - 1` in the circuit type.
+                if let Gate::Input(input_idx) = gate {
+                    return Err(CircuitBuilderError::UnusedInput(*input_idx));
+                }
+                continue;
+            }
+            match gate {
+                Gate::Input(_) => {}
+                Gate::AddMod(..) | Gate::SubMod(..) => sizes.n_add_mods += 1,
+                Gate::MulMod(..) | Gate::Inverse(..) => sizes.n_mul_mods += 1,
+            }
+        }
+        // The values are the const `1`, the unreduced inputs, and an output for every instance.
+        sizes.rc96_usage = (1 + self.n_inputs + sizes.n_add_mods + sizes.n_mul_mods) * VALUE_SIZE;
+
+        let mut declarations = DeclarationsBuilder::default();
+        // Types are declared in creation order, so each gate is declared after its operands.
+        let mut element_tys = vec![None; self.gates.len()];
+        for (idx, gate) in self.gates.iter().enumerate() {
+            if !reachable.contains(&idx) {
+                continue;
+            }
+            let operand_ty = |element: &CircuitElement| -> GenericArg {
+                GenericArg::Type(element_tys[element.0].clone().unwrap())
+            };
+            let (generic_id, generic_args) = match gate {
+                Gate::Input(input_idx) => {
+                    (CircuitInput::ID, vec![GenericArg::Value((*input_idx).into())])
+                }
+                Gate::AddMod(lhs, rhs) => (AddModGate::ID, vec![operand_ty(lhs), operand_ty(rhs)]),
+                Gate::SubMod(lhs, rhs) => (SubModGate::ID, vec![operand_ty(lhs), operand_ty(rhs)]),
+                Gate::MulMod(lhs, rhs) => (MulModGate::ID, vec![operand_ty(lhs), operand_ty(rhs)]),
+                Gate::Inverse(input) => (InverseGate::ID, vec![operand_ty(input)]),
+            };
+            element_tys[idx] = Some(declarations.ty(generic_id, generic_args));
+        }
+        let output_tys: Vec<ConcreteTypeId> =
+            outputs.iter().map(|output| element_tys[output.0].clone().unwrap()).collect();
+        let outputs_tuple_ty = declarations.tuple_ty(&output_tys);
+        let circuit_ty = declarations.ty(Circuit::ID, vec![GenericArg::Type(outputs_tuple_ty)]);
+        let libfuncs = declarations.evaluation_libfuncs(&circuit_ty, &output_tys);
+
+        Ok(CircuitDeclarations {
+            circuit_ty,
+            output_tys,
+            libfuncs,
+            sizes,
+            type_declarations: declarations.type_declarations,
+            libfunc_declarations: declarations.libfunc_declarations,
+        })
+    }
+
+    /// Returns the indices of the gates the outputs depend on, including the outputs themselves.
+    fn reachable_gates(
+        &self,
+        outputs: &[CircuitElement],
+    ) -> Result<UnorderedHashSet<usize>, CircuitBuilderError> {
+        let mut reachable = UnorderedHashSet::<usize>::default();
+        let mut stack: Vec<CircuitElement> = outputs.to_vec();
+        while let Some(CircuitElement(idx)) = stack.pop() {
+            let gate = self.gates.get(idx).ok_or(CircuitBuilderError::UnknownElement(idx))?;
+            if reachable.insert(idx) {
+                stack.extend(gate.operands());
+            }
+        }
+        Ok(reachable)
+    }
+}
+
+/// The builtin usage of a single evaluation of a circuit.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CircuitSizes {
+    /// The number of circuit inputs.
+    pub n_inputs: usize,
+    /// The number of `AddMod` builtin instances used - one per add or sub gate.
+    pub n_add_mods: usize,
+    /// The number of `MulMod` builtin instances used - one per mul or inverse gate, and one per
+    /// input for reducing it modulo the modulus.
+    pub n_mul_mods: usize,
+    /// The number of 96-bit range checks used for the values of the circuit.
+    pub rc96_usage: usize,
+}
+impl CircuitSizes {
+    /// Returns the number of memory cells used by the `AddMod` builtin.
+    pub fn add_mod_cells(&self) -> usize {
+        self.n_add_mods * MOD_BUILTIN_INSTANCE_SIZE
+    }
+
+    /// Returns the number of memory cells used by the `MulMod` builtin.
+    pub fn mul_mod_cells(&self) -> usize {
+        self.n_mul_mods * MOD_BUILTIN_INSTANCE_SIZE
+    }
+}
+
+/// The concrete libfuncs used for evaluating a circuit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircuitLibfuncs {
+    /// `init_circuit_data` - starts accumulating the circuit inputs.
+    pub init_circuit_data: ConcreteLibfuncId,
+    /// `add_circuit_input` - adds the next input, branching once all the inputs were added.
+    pub add_circuit_input: ConcreteLibfuncId,
+    /// `get_circuit_descriptor` - returns the descriptor of the circuit.
+    pub get_circuit_descriptor: ConcreteLibfuncId,
+    /// `eval_circuit` - evaluates the circuit.
+    pub eval_circuit: ConcreteLibfuncId,
+    /// `get_circuit_output` for each of the requested outputs, in the same order.
+    pub get_outputs: Vec<ConcreteLibfuncId>,
+}
+
+/// The Sierra declarations of a circuit built by a [CircuitBuilder].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircuitDeclarations {
+    /// The `Circuit` type.
+    pub circuit_ty: ConcreteTypeId,
+    /// The types of the requested outputs, in the same order.
+    pub output_tys: Vec<ConcreteTypeId>,
+    /// The libfuncs used for evaluating the circuit.
+    pub libfuncs: CircuitLibfuncs,
+    /// The builtin usage of a single evaluation of the circuit.
+    pub sizes: CircuitSizes,
+    /// The declarations of all the types used by the circuit and its libfuncs, where each type is
+    /// declared after the types it depends on.
+    pub type_declarations: Vec<TypeDeclaration>,
+    /// The declarations of the libfuncs in `libfuncs`.
+    pub libfunc_declarations: Vec<LibfuncDeclaration>,
+}
+
+/// Helper for declaring types and libfuncs, each at most once.
+#[derive(Default)]
+struct DeclarationsBuilder {
+    type_declarations: Vec<TypeDeclaration>,
+    libfunc_declarations: Vec<LibfuncDeclaration>,
+    type_ids: UnorderedHashMap<ConcreteTypeLongId, ConcreteTypeId>,
+    libfunc_ids: UnorderedHashMap<ConcreteLibfuncLongId, ConcreteLibfuncId>,
+}
+impl DeclarationsBuilder {
+    /// Declares the concrete type, if not already declared, and returns its id.
+    fn ty(&mut self, generic_id: GenericTypeId, generic_args: Vec<GenericArg>) -> ConcreteTypeId {
+        let long_id = ConcreteTypeLongId { generic_id, generic_args };
+        if let Some(id) = self.type_ids.get(&long_id) {
+            return id.clone();
+        }
+        let id = ConcreteTypeId::from_string(long_id.to_string());
+        self.type_ids.insert(long_id.clone(), id.clone());
+        self.type_declarations.push(TypeDeclaration {
+            id: id.clone(),
+            long_id,
+            declared_type_info: None,
+        });
+        id
+    }
+
+    /// Declares the concrete libfunc, if not already declared, and returns its id.
+    fn libfunc(&mut self, generic_id: &str, generic_args: Vec<GenericArg>) -> ConcreteLibfuncId {
+        let long_id =
+            ConcreteLibfuncLongId { generic_id: GenericLibfuncId::from(generic_id), generic_args };
+        if let Some(id) = self.libfunc_ids.get(&long_id) {
+            return id.clone();
+        }
+        let id = ConcreteLibfuncId::from_string(long_id.to_string());
+        self.libfunc_ids.insert(long_id.clone(), id.clone());
+        self.libfunc_declarations.push(LibfuncDeclaration { id: id.clone(), long_id });
+        id
+    }
+
+    /// Declares a tuple of the given member types.
+    fn tuple_ty(&mut self, members: &[ConcreteTypeId]) -> ConcreteTypeId {
+        self.struct_ty("Tuple", members)
+    }
+
+    /// Declares a struct of the given user type and member types.
+    fn struct_ty(&mut self, user_type: &str, members: &[ConcreteTypeId]) -> ConcreteTypeId {
+        let generic_args = [GenericArg::UserType(UserTypeId::from_string(user_type))]
+            .into_iter()
+            .chain(members.iter().cloned().map(GenericArg::Type))
+            .collect();
+        self.ty(StructType::ID, generic_args)
+    }
+
+    /// Declares a bounded int type with the given range.
+    fn bounded_int_ty(&mut self, min: BigInt, max: BigInt) -> ConcreteTypeId {
+        self.ty(BoundedIntType::ID, vec![GenericArg::Value(min), GenericArg::Value(max)])
+    }
+
+    /// Declares the libfuncs used for evaluating the circuit, along with the types they use.
+    fn evaluation_libfuncs(
+        &mut self,
+        circuit_ty: &ConcreteTypeId,
+        output_tys: &[ConcreteTypeId],
+    ) -> CircuitLibfuncs {
+        let circuit_arg = || vec![GenericArg::Type(circuit_ty.clone())];
+        // The types used by the signatures of the evaluation libfuncs.
+        self.ty(RangeCheck96Type::id(), vec![]);
+        self.ty(AddModType::id(), vec![]);
+        self.ty(MulModType::id(), vec![]);
+        self.ty(CircuitModulus::id(), vec![]);
+        self.ty(CircuitFailureGuarantee::id(), vec![]);
+        let u96_guarantee_ty = self.ty(U96Guarantee::id(), vec![]);
+        self.tuple_ty(&vec![u96_guarantee_ty; VALUE_SIZE]);
+        self.bounded_int_ty(BigInt::zero(), BigInt::zero());
+        self.bounded_int_ty(BigInt::one(), BigInt::one());
+        let u96_ty = self.bounded_int_ty(BigInt::zero(), BigInt::one().shl(96) - 1);
+        self.struct_ty("core::circuit::u384", &vec![u96_ty; VALUE_SIZE]);
+        self.ty(U96LimbsLessThanGuarantee::ID, vec![GenericArg::Value(VALUE_SIZE.into())]);
+        for generic_id in [
+            CircuitInputAccumulator::ID,
+            CircuitData::ID,
+            CircuitDescriptor::ID,
+            CircuitOutputs::ID,
+            CircuitPartialOutputs::ID,
+        ] {
+            self.ty(generic_id, circuit_arg());
+        }
+
+        CircuitLibfuncs {
+            init_circuit_data: self.libfunc(InitCircuitDataLibFuncWrapped::STR_ID, circuit_arg()),
+            add_circuit_input: self.libfunc(AddCircuitInputLibFuncWrapped::STR_ID, circuit_arg()),
+            get_circuit_descriptor: self
+                .libfunc(GetCircuitDescriptorLibFuncWrapped::STR_ID, circuit_arg()),
+            eval_circuit: self.libfunc(EvalCircuitLibFuncWrapped::STR_ID, circuit_arg()),
+            get_outputs: output_tys
+                .iter()
+                .map(|output_ty| {
+                    self.libfunc(
+                        GetOutputLibFunc::STR_ID,
+                        vec![
+                            GenericArg::Type(circuit_ty.clone()),
+                            GenericArg::Type(output_ty.clone()),
+                        ],
+                    )
+                })
+                .collect(),
+        }
+    }
+}
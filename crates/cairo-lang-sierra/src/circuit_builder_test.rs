@@ -0,0 +1,69 @@
+use cairo_lang_test_utils::test;
+
+use super::{CircuitBuilder, CircuitBuilderError, CircuitSizes};
+use crate::extensions::circuit::CircuitTypeConcrete;
+use crate::extensions::core::{CoreLibfunc, CoreType, CoreTypeConcrete};
+use crate::program::Program;
+use crate::program_registry::ProgramRegistry;
+
+#[test]
+fn build_circuit() {
+    let mut builder = CircuitBuilder::default();
+    let in0 = builder.input();
+    let in1 = builder.input();
+    let sum = builder.add(in0, in1);
+    let diff = builder.sub(in0, in1);
+    let prod = builder.mul(sum, diff);
+    let inv = builder.inverse(prod);
+    // Identical gates are merged.
+    assert_eq!(builder.add(in0, in1), sum);
+
+    let declarations = builder.build(&[inv, sum]).unwrap();
+    assert_eq!(
+        declarations.sizes,
+        CircuitSizes { n_inputs: 2, n_add_mods: 2, n_mul_mods: 4, rc96_usage: 36 }
+    );
+    assert_eq!(declarations.sizes.add_mod_cells(), 14);
+    assert_eq!(declarations.sizes.mul_mod_cells(), 28);
+    assert_eq!(
+        declarations.circuit_ty.to_string(),
+        "Circuit<Struct<ut@Tuple, InverseGate<MulModGate<AddModGate<CircuitInput<0>, \
+         CircuitInput<1>>, SubModGate<CircuitInput<0>, CircuitInput<1>>>>, \
+         AddModGate<CircuitInput<0>, CircuitInput<1>>>>"
+    );
+    assert_eq!(declarations.libfuncs.get_outputs.len(), 2);
+
+    // The declarations are accepted by Sierra, and describe a circuit of the computed size.
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(&Program {
+        type_declarations: declarations.type_declarations,
+        libfunc_declarations: declarations.libfunc_declarations,
+        statements: vec![],
+        funcs: vec![],
+    })
+    .unwrap();
+    let CoreTypeConcrete::Circuit(CircuitTypeConcrete::Circuit(circuit)) =
+        registry.get_type(&declarations.circuit_ty).unwrap()
+    else {
+        panic!("Expected a circuit type.");
+    };
+    let info = &circuit.circuit_info;
+    assert_eq!(info.n_inputs, declarations.sizes.n_inputs);
+    assert_eq!(info.add_offsets.len(), declarations.sizes.n_add_mods);
+    assert_eq!(info.mul_offsets.len(), declarations.sizes.n_mul_mods);
+    assert_eq!(info.rc96_usage(), declarations.sizes.rc96_usage);
+}
+
+#[test]
+fn build_invalid_circuit() {
+    let mut builder = CircuitBuilder::default();
+    let in0 = builder.input();
+    let in1 = builder.input();
+    let inv = builder.inverse(in1);
+    assert_eq!(builder.build(&[]), Err(CircuitBuilderError::NoOutputs));
+    assert_eq!(builder.build(&[in0]), Err(CircuitBuilderError::UnusedInput(1)));
+    assert_eq!(builder.build(&[inv]), Err(CircuitBuilderError::UnusedInput(0)));
+
+    let mut other_builder = CircuitBuilder::default();
+    other_builder.input();
+    assert_eq!(other_builder.build(&[inv]), Err(CircuitBuilderError::UnknownElement(2)));
+}
@@ -79,6 +79,9 @@ define_libfunc_hierarchy! {
         Mul(BoundedIntMulLibfunc),
         DivRem(BoundedIntDivRemLibfunc),
         Constrain(BoundedIntConstrainLibfunc),
+        AddConstrain(BoundedIntOpConstrainLibfunc<false>),
+        MulConstrain(BoundedIntOpConstrainLibfunc<true>),
+        DivRemConstrain(BoundedIntDivRemConstrainLibfunc),
         TrimMin(BoundedIntTrimLibfunc<false>),
         TrimMax(BoundedIntTrimLibfunc<true>),
         IsZero(BoundedIntIsZeroLibfunc),
@@ -98,9 +101,7 @@ impl SignatureOnlyGenericLibfunc for BoundedIntAddLibfunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibfuncSignature, SpecializationError> {
-        specialize_helper(context, args, |lhs_range, rhs_range| {
-            (lhs_range.lower + rhs_range.lower, (lhs_range.upper - 1) + (rhs_range.upper - 1))
-        })
+        specialize_helper(context, args, add_result_bounds)
     }
 }
 
@@ -154,16 +155,7 @@ impl SignatureOnlyGenericLibfunc for BoundedIntMulLibfunc {
         } else {
             [lhs_info, rhs_info].map(Range::from_type_info)
         };
-        let (lhs_range, rhs_range) = (lhs_range?, rhs_range?);
-        // The result is the minimum and maximum of the four possible extremes.
-        // Done to properly handle multiplication by negative values.
-        let extremes = [
-            &lhs_range.lower * &rhs_range.lower,
-            lhs_range.lower * (&rhs_range.upper - 1),
-            (&lhs_range.upper - 1) * rhs_range.lower,
-            (lhs_range.upper - 1) * (rhs_range.upper - 1),
-        ];
-        let (min_result, max_result) = extremes.into_iter().minmax().into_option().unwrap();
+        let (min_result, max_result) = mul_result_bounds(lhs_range?, rhs_range?);
         let res_ty = bounded_int_ty(context, min_result, max_result)?;
         Ok(LibfuncSignature::new_non_branch_ex(
             vec![
@@ -195,19 +187,7 @@ impl NamedLibfunc for BoundedIntDivRemLibfunc {
         let (lhs, rhs) = args_as_two_types(args)?;
         let lhs_range = Range::from_type(context, lhs)?;
         let rhs_range = Range::from_type(context, rhs)?;
-        // Supporting only division of a non-negative number by a positive number (non zero and non
-        // negative).
-        // TODO(orizi): Consider relaxing the constraint, and defining the
-        // div_rem of negatives.
-        require(!lhs_range.lower.is_negative() && !rhs_range.lower.is_negative())
-            .ok_or(SpecializationError::UnsupportedGenericArg)?;
-        // Making sure the algorithm is runnable.
-        BoundedIntDivRemAlgorithm::try_new(&lhs_range, &rhs_range)
-            .ok_or(SpecializationError::UnsupportedGenericArg)?;
-        require(rhs_range.upper >= BigInt::from(2))
-            .ok_or(SpecializationError::UnsupportedGenericArg)?;
-        let quotient_min = lhs_range.lower / (&rhs_range.upper - 1);
-        let quotient_max = (&lhs_range.upper - 1) / std::cmp::max(rhs_range.lower, BigInt::one());
+        let (quotient_min, quotient_max) = div_rem_quotient_bounds(&lhs_range, &rhs_range)?;
         let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
         Ok(LibfuncSignature::new_non_branch_ex(
             vec![
@@ -332,12 +312,7 @@ impl NamedLibfunc for BoundedIntConstrainLibfunc {
         } else {
             Range::from_type_info(ty_info)?
         };
-        require(&range.lower < boundary && boundary < &range.upper)
-            .ok_or(SpecializationError::UnsupportedGenericArg)?;
-        let low_range = Range::half_open(range.lower, boundary.clone());
-        let high_range = Range::half_open(boundary.clone(), range.upper);
-        require(low_range.is_small_range() && high_range.is_small_range())
-            .ok_or(SpecializationError::UnsupportedGenericArg)?;
+        let [low_range, high_range] = split_at_boundary(range, boundary)?;
         let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
         let branch_signature = |rng: Range| {
             let inner_res_ty = bounded_int_ty(context, rng.lower, rng.upper - 1)?;
@@ -387,6 +362,146 @@ impl SignatureBasedConcreteLibfunc for BoundedIntConstrainConcreteLibfunc {
     }
 }
 
+/// Libfunc for adding (or multiplying, if `IS_MUL`) two BoundedInts, and constraining the result to
+/// one of two non-empty ranges: [Min, Boundary) or [Boundary, Max], where [Min, Max] is the range
+/// of the result of the operation.
+#[derive(Default)]
+pub struct BoundedIntOpConstrainLibfunc<const IS_MUL: bool> {}
+impl<const IS_MUL: bool> NamedLibfunc for BoundedIntOpConstrainLibfunc<IS_MUL> {
+    type Concrete = BoundedIntConstrainConcreteLibfunc;
+
+    const STR_ID: &'static str =
+        if IS_MUL { "bounded_int_mul_constrain" } else { "bounded_int_add_constrain" };
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let (lhs, rhs, boundary) = args_as_two_types_and_value(args)?;
+        let lhs_range = Range::from_type(context, lhs)?;
+        let rhs_range = Range::from_type(context, rhs)?;
+        let (min_result, max_result) = if IS_MUL {
+            mul_result_bounds(lhs_range, rhs_range)
+        } else {
+            add_result_bounds(lhs_range, rhs_range)
+        };
+        let [low_range, high_range] =
+            split_at_boundary(Range::closed(min_result, max_result), boundary)?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let branch_signature = |rng: Range| {
+            Ok(BranchSignature {
+                vars: vec![
+                    OutputVarInfo::new_builtin(range_check_type.clone()),
+                    OutputVarInfo {
+                        ty: bounded_int_ty(context, rng.lower, rng.upper - 1)?,
+                        ref_info: OutputVarReferenceInfo::SimpleDerefs,
+                    },
+                ],
+                ap_change: SierraApChange::Known { new_vars_only: false },
+            })
+        };
+        Ok(LibfuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+                ParamSignature::new(lhs.clone()),
+                ParamSignature::new(rhs.clone()),
+            ],
+            branch_signatures: vec![branch_signature(low_range)?, branch_signature(high_range)?],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (_, _, boundary) = args_as_two_types_and_value(args)?;
+        Ok(Self::Concrete {
+            boundary: boundary.clone(),
+            signature: self.specialize_signature(context, args)?,
+        })
+    }
+}
+
+/// Libfunc for dividing two non negative BoundedInts, and constraining the quotient to one of two
+/// non-empty ranges: [Min, Boundary) or [Boundary, Max], where [Min, Max] is the range of the
+/// quotient.
+#[derive(Default)]
+pub struct BoundedIntDivRemConstrainLibfunc {}
+impl NamedLibfunc for BoundedIntDivRemConstrainLibfunc {
+    type Concrete = BoundedIntDivRemConstrainConcreteLibfunc;
+
+    const STR_ID: &'static str = "bounded_int_div_rem_constrain";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let (lhs, rhs, boundary) = args_as_two_types_and_value(args)?;
+        let lhs_range = Range::from_type(context, lhs)?;
+        let rhs_range = Range::from_type(context, rhs)?;
+        let (quotient_min, quotient_max) = div_rem_quotient_bounds(&lhs_range, &rhs_range)?;
+        let [low_range, high_range] =
+            split_at_boundary(Range::closed(quotient_min, quotient_max), boundary)?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let remainder_ty = bounded_int_ty(context, 0.into(), rhs_range.upper - 2)?;
+        let branch_signature = |rng: Range| {
+            Ok(BranchSignature {
+                vars: vec![
+                    OutputVarInfo::new_builtin(range_check_type.clone()),
+                    OutputVarInfo {
+                        ty: bounded_int_ty(context, rng.lower, rng.upper - 1)?,
+                        ref_info: OutputVarReferenceInfo::SimpleDerefs,
+                    },
+                    OutputVarInfo {
+                        ty: remainder_ty.clone(),
+                        ref_info: OutputVarReferenceInfo::SimpleDerefs,
+                    },
+                ],
+                ap_change: SierraApChange::Known { new_vars_only: false },
+            })
+        };
+        Ok(LibfuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+                ParamSignature::new(lhs.clone()),
+                ParamSignature::new(nonzero_ty(context, rhs)?),
+            ],
+            branch_signatures: vec![branch_signature(low_range)?, branch_signature(high_range)?],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (lhs, rhs, boundary) = args_as_two_types_and_value(args)?;
+        Ok(Self::Concrete {
+            lhs: Range::from_type(context, lhs)?,
+            rhs: Range::from_type(context, rhs)?,
+            boundary: boundary.clone(),
+            signature: self.specialize_signature(context, args)?,
+        })
+    }
+}
+
+pub struct BoundedIntDivRemConstrainConcreteLibfunc {
+    pub lhs: Range,
+    pub rhs: Range,
+    pub boundary: BigInt,
+    signature: LibfuncSignature,
+}
+impl SignatureBasedConcreteLibfunc for BoundedIntDivRemConstrainConcreteLibfunc {
+    fn signature(&self) -> &LibfuncSignature {
+        &self.signature
+    }
+}
+
 /// Libfunc for trimming a BoundedInt<Min, Max> by removing `Min` or `Max` from the range.
 /// The libfunc is also applicable for standard types such as u* and i*.
 #[derive(Default)]
@@ -481,6 +596,70 @@ fn specialize_helper(
     ))
 }
 
+/// Returns the bounds of the result of adding values from the given ranges.
+fn add_result_bounds(lhs: Range, rhs: Range) -> (BigInt, BigInt) {
+    (lhs.lower + rhs.lower, (lhs.upper - 1) + (rhs.upper - 1))
+}
+
+/// Returns the bounds of the result of multiplying values from the given ranges.
+fn mul_result_bounds(lhs: Range, rhs: Range) -> (BigInt, BigInt) {
+    // The result is the minimum and maximum of the four possible extremes.
+    // Done to properly handle multiplication by negative values.
+    let extremes = [
+        &lhs.lower * &rhs.lower,
+        lhs.lower * (&rhs.upper - 1),
+        (&lhs.upper - 1) * rhs.lower,
+        (lhs.upper - 1) * (rhs.upper - 1),
+    ];
+    extremes.into_iter().minmax().into_option().unwrap()
+}
+
+/// Returns the bounds of the quotient of dividing values from the given ranges.
+/// Fails if the div_rem of the ranges is not supported.
+fn div_rem_quotient_bounds(
+    lhs: &Range,
+    rhs: &Range,
+) -> Result<(BigInt, BigInt), SpecializationError> {
+    // Supporting only division of a non-negative number by a positive number (non zero and non
+    // negative).
+    // TODO(orizi): Consider relaxing the constraint, and defining the
+    // div_rem of negatives.
+    require(!lhs.lower.is_negative() && !rhs.lower.is_negative())
+        .ok_or(SpecializationError::UnsupportedGenericArg)?;
+    // Making sure the algorithm is runnable.
+    BoundedIntDivRemAlgorithm::try_new(lhs, rhs)
+        .ok_or(SpecializationError::UnsupportedGenericArg)?;
+    require(rhs.upper >= BigInt::from(2)).ok_or(SpecializationError::UnsupportedGenericArg)?;
+    let quotient_min = &lhs.lower / (&rhs.upper - 1);
+    let quotient_max = (&lhs.upper - 1) / std::cmp::max(&rhs.lower, &BigInt::one());
+    Ok((quotient_min, quotient_max))
+}
+
+/// Splits `range` into the non-empty ranges `[range.lower, boundary)` and
+/// `[boundary, range.upper)`, both of which must be small enough to be range checked.
+fn split_at_boundary(range: Range, boundary: &BigInt) -> Result<[Range; 2], SpecializationError> {
+    require(&range.lower < boundary && boundary < &range.upper)
+        .ok_or(SpecializationError::UnsupportedGenericArg)?;
+    let low_range = Range::half_open(range.lower, boundary.clone());
+    let high_range = Range::half_open(boundary.clone(), range.upper);
+    require(low_range.is_small_range() && high_range.is_small_range())
+        .ok_or(SpecializationError::UnsupportedGenericArg)?;
+    Ok([low_range, high_range])
+}
+
+/// Helper for extracting two types and a value from the generic args of a libfunc.
+fn args_as_two_types_and_value(
+    args: &[GenericArg],
+) -> Result<(&ConcreteTypeId, &ConcreteTypeId, &BigInt), SpecializationError> {
+    match args {
+        [GenericArg::Type(lhs), GenericArg::Type(rhs), GenericArg::Value(value)] => {
+            Ok((lhs, rhs, value))
+        }
+        [_, _, _] => Err(SpecializationError::UnsupportedGenericArg),
+        _ => Err(SpecializationError::WrongNumberOfGenericArgs),
+    }
+}
+
 /// Libfunc for checking whether the given bounded int is zero or not, and returning a non-zero
 /// wrapped value in case of success.
 #[derive(Default)]
@@ -1,3 +1,4 @@
+use num_traits::ToPrimitive;
 use starknet_types_core::felt::Felt as Felt252;
 
 use super::felt252::Felt252Type;
@@ -6,13 +7,15 @@ use super::range_check::RangeCheckType;
 use crate::define_libfunc_hierarchy;
 use crate::extensions::lib_func::{
     BranchSignature, DeferredOutputKind, LibfuncSignature, OutputVarInfo, ParamSignature,
-    SierraApChange, SignatureSpecializationContext,
+    SierraApChange, SignatureSpecializationContext, SpecializationContext,
 };
 use crate::extensions::{
-    NamedType, NoGenericArgsGenericLibfunc, NoGenericArgsGenericType, OutputVarReferenceInfo,
-    SpecializationError,
+    NamedLibfunc, NamedType, NoGenericArgsGenericLibfunc, NoGenericArgsGenericType,
+    OutputVarReferenceInfo, SignatureBasedConcreteLibfunc, SpecializationError,
+    args_as_single_value,
 };
 use crate::ids::GenericTypeId;
+use crate::program::GenericArg;
 
 // Type representing the EcOp builtin.
 #[derive(Default)]
@@ -76,6 +79,7 @@ define_libfunc_hierarchy! {
         StateFinalize(EcStateFinalizeLibfunc),
         StateInit(EcStateInitLibfunc),
         StateAddMul(EcStateAddMulLibfunc),
+        StateAddMulMany(EcStateAddMulManyLibfunc),
         PointFromX(EcPointFromXLibfunc),
         UnwrapPoint(EcUnwrapPointLibfunc),
         Zero(EcZeroLibfunc),
@@ -417,3 +421,82 @@ impl NoGenericArgsGenericLibfunc for EcStateAddMulLibfunc {
         ))
     }
 }
+
+/// Libfunc for applying the EC op builtin multiple times: given an EC state `S`, and `COUNT` pairs
+/// of a scalar `M_i` and an EC point `Q_i`, computes a new EC state `S + M_0 * Q_0 + ... +
+/// M_{COUNT-1} * Q_{COUNT-1}`, where each builtin instance accumulates into the result of the
+/// previous one.
+#[derive(Default)]
+pub struct EcStateAddMulManyLibfunc {}
+impl NamedLibfunc for EcStateAddMulManyLibfunc {
+    type Concrete = EcStateAddMulManyConcreteLibfunc;
+
+    const STR_ID: &'static str = "ec_state_add_mul_many";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let count = args_as_count(args)?;
+        let ec_builtin_ty = context.get_concrete_type(EcOpType::id(), &[])?;
+        let ec_state_ty = context.get_concrete_type(EcStateType::id(), &[])?;
+        let felt252_ty = context.get_concrete_type(Felt252Type::id(), &[])?;
+        let ecpoint_ty = context.get_concrete_type(EcPointType::id(), &[])?;
+        let nonzero_ecpoint_ty = nonzero_ty(context, &ecpoint_ty)?;
+
+        let mut param_signatures = vec![
+            ParamSignature::new(ec_builtin_ty.clone()).with_allow_add_const(),
+            ParamSignature::new(ec_state_ty.clone()),
+        ];
+        for _ in 0..count {
+            param_signatures.push(ParamSignature::new(felt252_ty.clone()));
+            param_signatures.push(ParamSignature::new(nonzero_ecpoint_ty.clone()));
+        }
+        Ok(LibfuncSignature::new_non_branch_ex(
+            param_signatures,
+            vec![
+                OutputVarInfo::new_builtin(ec_builtin_ty),
+                OutputVarInfo {
+                    ty: ec_state_ty,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::Known { new_vars_only: false },
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(EcStateAddMulManyConcreteLibfunc {
+            count: args_as_count(args)?,
+            signature: self.specialize_signature(context, args)?,
+        })
+    }
+}
+
+pub struct EcStateAddMulManyConcreteLibfunc {
+    /// The number of scalar and point pairs added to the state.
+    pub count: usize,
+    signature: LibfuncSignature,
+}
+impl SignatureBasedConcreteLibfunc for EcStateAddMulManyConcreteLibfunc {
+    fn signature(&self) -> &LibfuncSignature {
+        &self.signature
+    }
+}
+
+/// Helper for extracting the number of added products from the generic args of
+/// `ec_state_add_mul_many`.
+fn args_as_count(args: &[GenericArg]) -> Result<usize, SpecializationError> {
+    let count =
+        args_as_single_value(args)?.to_usize().ok_or(SpecializationError::UnsupportedGenericArg)?;
+    // A single product should be added using `ec_state_add_mul`.
+    if count < 2 {
+        return Err(SpecializationError::UnsupportedGenericArg);
+    }
+    Ok(count)
+}
@@ -1,3 +1,9 @@
+use std::iter::repeat_n;
+
+use cairo_lang_utils::require;
+use itertools::chain;
+use num_traits::ToPrimitive;
+
 use super::enm::EnumType;
 use super::felt252::Felt252Type;
 use super::gas::GasBuiltinType;
@@ -11,14 +17,15 @@ use crate::define_libfunc_hierarchy;
 use crate::extensions::lib_func::{
     DeferredOutputKind, LibfuncSignature, OutputVarInfo, ParamSignature, SierraApChange,
     SignatureAndTypeGenericLibfunc, SignatureOnlyGenericLibfunc, SignatureSpecializationContext,
-    WrapSignatureAndTypeGenericLibfunc,
+    SpecializationContext, WrapSignatureAndTypeGenericLibfunc,
 };
 use crate::extensions::type_specialization_context::TypeSpecializationContext;
 use crate::extensions::types::{
     GenericTypeArgGenericType, GenericTypeArgGenericTypeWrapper, TypeInfo,
 };
 use crate::extensions::{
-    NamedType, OutputVarReferenceInfo, SpecializationError, args_as_single_type,
+    NamedLibfunc, NamedType, OutputVarReferenceInfo, SignatureBasedConcreteLibfunc,
+    SpecializationError, args_as_single_type,
 };
 use crate::ids::{ConcreteTypeId, GenericTypeId};
 use crate::program::{ConcreteTypeLongId, GenericArg};
@@ -106,6 +113,7 @@ define_libfunc_hierarchy! {
     pub enum Felt252DictLibfunc {
         New(Felt252DictNewLibfunc),
         Squash(Felt252DictSquashLibfunc),
+        SquashMany(Felt252DictSquashManyLibfunc),
     }, Felt252DictConcreteLibfunc
 }
 
@@ -185,6 +193,86 @@ impl SignatureOnlyGenericLibfunc for Felt252DictSquashLibfunc {
     }
 }
 
+/// The maximal number of dicts squashed by a single `felt252_dict_squash_many` call.
+pub const SQUASH_MANY_MAX_DICTS: usize = 64;
+
+/// Libfunc for performing a `squash` operation on multiple dicts of the same segment arena at
+/// once. Returns a pointer to each of the squashed dicts.
+///
+/// Shares the segment arena bookkeeping between the dicts, making it cheaper than squashing each
+/// dict separately.
+#[derive(Default)]
+pub struct Felt252DictSquashManyLibfunc {}
+impl NamedLibfunc for Felt252DictSquashManyLibfunc {
+    type Concrete = Felt252DictSquashManyConcreteLibfunc;
+
+    const STR_ID: &'static str = "felt252_dict_squash_many";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let (generic_ty, count) = args_as_type_and_count(args)?;
+        let dict_ty =
+            context.get_wrapped_concrete_type(Felt252DictType::id(), generic_ty.clone())?;
+        let squashed_dict_ty =
+            context.get_wrapped_concrete_type(SquashedFelt252DictType::id(), generic_ty.clone())?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let gas_builtin_type = context.get_concrete_type(GasBuiltinType::id(), &[])?;
+        let segment_arena_ty = context.get_concrete_type(SegmentArenaType::id(), &[])?;
+        let builtin_tys = [range_check_type, gas_builtin_type, segment_arena_ty];
+        Ok(LibfuncSignature::new_non_branch(
+            chain!(builtin_tys.iter().cloned(), repeat_n(dict_ty, count)).collect(),
+            chain!(builtin_tys, repeat_n(squashed_dict_ty, count))
+                .enumerate()
+                .map(|(idx, ty)| OutputVarInfo {
+                    ty,
+                    ref_info: OutputVarReferenceInfo::NewTempVar { idx },
+                })
+                .collect(),
+            SierraApChange::Unknown,
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (_, count) = args_as_type_and_count(args)?;
+        Ok(Self::Concrete { count, signature: self.specialize_signature(context, args)? })
+    }
+}
+
+pub struct Felt252DictSquashManyConcreteLibfunc {
+    /// The number of squashed dicts.
+    pub count: usize,
+    signature: LibfuncSignature,
+}
+impl SignatureBasedConcreteLibfunc for Felt252DictSquashManyConcreteLibfunc {
+    fn signature(&self) -> &LibfuncSignature {
+        &self.signature
+    }
+}
+
+/// Helper for extracting the value type and the number of dicts from the generic args of
+/// `felt252_dict_squash_many`.
+fn args_as_type_and_count(
+    args: &[GenericArg],
+) -> Result<(&ConcreteTypeId, usize), SpecializationError> {
+    let (ty, count) = match args {
+        [GenericArg::Type(ty), GenericArg::Value(count)] => (ty, count),
+        [_, _] => return Err(SpecializationError::UnsupportedGenericArg),
+        _ => return Err(SpecializationError::WrongNumberOfGenericArgs),
+    };
+    let count = count.to_usize().ok_or(SpecializationError::UnsupportedGenericArg)?;
+    // Squashing a single dict should be done using `felt252_dict_squash`.
+    require((2..=SQUASH_MANY_MAX_DICTS).contains(&count))
+        .ok_or(SpecializationError::UnsupportedGenericArg)?;
+    Ok((ty, count))
+}
+
 /// Type representing an entry access to a felt252_dict.
 #[derive(Default)]
 pub struct Felt252DictEntryTypeWrapped {}
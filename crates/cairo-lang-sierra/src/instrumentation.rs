@@ -0,0 +1,225 @@
+//! Instrumentation of Sierra programs with execution counters.
+//!
+//! [instrument_program] inserts probes - invocations of the `trace` libfunc with a dedicated flag -
+//! at the entry of every function and before every loop back-edge. Running the instrumented program
+//! and counting the executions of each probe gives an [ExecutionProfile], holding the number of
+//! calls and back-edges taken per function.
+//!
+//! Cairo loops are compiled into recursive functions, so a back-edge is either a jump to an earlier
+//! statement of the function or a direct call of the function from itself. Mutual recursion is not
+//! detected, and only shows up in the calls of the functions.
+
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use itertools::Itertools;
+use num_bigint::BigInt;
+use serde::{Deserialize, Serialize};
+
+use crate::extensions::NamedLibfunc;
+use crate::extensions::function_call::FunctionCallLibfunc;
+use crate::extensions::trace::TraceLibfunc;
+use crate::ids::{ConcreteLibfuncId, FunctionId};
+use crate::program::{
+    BranchInfo, BranchTarget, ConcreteLibfuncLongId, GenericArg, Invocation, Program, Statement,
+    StatementIdx,
+};
+use crate::rewrite::{Rewrite, RewriteContext, RewriteRule, rewrite_program};
+
+#[cfg(test)]
+#[path = "instrumentation_test.rs"]
+mod test;
+
+/// The base of the `trace` flags of probes - the ASCII of "probe" shifted to the high bits, so the
+/// flags do not collide with the flags of user traces. The flag of a probe is the base plus its
+/// index.
+pub const PROBE_FLAG_BASE: u64 = 0x70726f6265 << 24;
+
+/// The kind of a probe.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProbeKind {
+    /// Counts the calls of the function.
+    Entry,
+    /// Counts the times the backward jump or self-recursive call at the given statement of the
+    /// original program is taken.
+    BackEdge(StatementIdx),
+}
+
+/// A counter inserted into an instrumented program.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Probe {
+    /// The index of the function containing the probe, in the functions of the program.
+    pub function_idx: usize,
+    /// What the probe counts.
+    pub kind: ProbeKind,
+    /// The statement of the probe in the instrumented program.
+    pub statement_idx: StatementIdx,
+}
+
+/// A program instrumented with probes.
+#[derive(Clone, Debug)]
+pub struct InstrumentedProgram {
+    /// The instrumented program.
+    pub program: Program,
+    /// The probes of the program, by their index.
+    pub probes: Vec<Probe>,
+}
+impl InstrumentedProgram {
+    /// Builds the execution profile from the number of executions of each probe.
+    pub fn profile(&self, probe_hits: &[usize]) -> ExecutionProfile {
+        assert_eq!(probe_hits.len(), self.probes.len(), "Wrong number of probe hits.");
+        let mut profile = ExecutionProfile::default();
+        for (probe, hits) in self.probes.iter().zip(probe_hits) {
+            let name = self.program.funcs[probe.function_idx].id.to_string();
+            let function = profile.functions.entry(name).or_default();
+            match probe.kind {
+                ProbeKind::Entry => function.calls += hits,
+                ProbeKind::BackEdge(_) => function.back_edges += hits,
+            }
+        }
+        profile
+    }
+}
+
+/// The execution counts of a function.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FunctionProfile {
+    /// The number of calls of the function, including its recursive calls.
+    pub calls: usize,
+    /// The number of loop back-edges taken inside the function, including self-recursive calls.
+    pub back_edges: usize,
+}
+
+/// The execution counts of the functions of a program, by function name.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionProfile {
+    pub functions: OrderedHashMap<String, FunctionProfile>,
+}
+impl ExecutionProfile {
+    /// Adds the counts of another profile to this one.
+    pub fn merge(&mut self, other: &Self) {
+        for (name, function) in other.functions.iter() {
+            let entry = self.functions.entry(name.clone()).or_default();
+            entry.calls += function.calls;
+            entry.back_edges += function.back_edges;
+        }
+    }
+}
+
+/// Inserts a probe at the entry of every function, before every unconditional jump to an earlier
+/// statement and before every call of a function from itself.
+///
+/// The statements of a function are the ones from its entry point up to the next entry point of
+/// the program, so the functions are not required to be declared in the order of their code.
+///
+/// Back-edges of branching statements are not instrumented, as a probe cannot be placed on a
+/// single branch without adding a statement outside of the rewritten sequence.
+pub fn instrument_program(program: &Program) -> InstrumentedProgram {
+    let mut probes = vec![];
+    let mut probe_indices = UnorderedHashMap::<usize, Vec<usize>>::default();
+    let mut add_probe = |statement_idx: usize, function_idx: usize, kind: ProbeKind| {
+        probe_indices.entry(statement_idx).or_default().push(probes.len());
+        probes.push(Probe { function_idx, kind, statement_idx: StatementIdx(statement_idx) });
+    };
+    // The callee of each `function_call` libfunc, for finding the self-recursive calls.
+    let mut callees = UnorderedHashMap::<ConcreteLibfuncId, FunctionId>::default();
+    for declaration in &program.libfunc_declarations {
+        if let ConcreteLibfuncLongId { generic_id, generic_args } = &declaration.long_id
+            && generic_id.0 == FunctionCallLibfunc::STR_ID
+            && let [GenericArg::UserFunc(callee)] = &generic_args[..]
+        {
+            callees.insert(declaration.id.clone(), callee.clone());
+        }
+    }
+    let mut entry_points =
+        program.funcs.iter().map(|function| function.entry_point.0).collect_vec();
+    entry_points.sort();
+    for (function_idx, function) in program.funcs.iter().enumerate() {
+        let start = function.entry_point.0;
+        add_probe(start, function_idx, ProbeKind::Entry);
+        let end = entry_points
+            .iter()
+            .find(|entry_point| **entry_point > start)
+            .copied()
+            .unwrap_or(program.statements.len());
+        for idx in start..end {
+            let Statement::Invocation(invocation) = &program.statements[idx] else {
+                continue;
+            };
+            let is_backward_jump = matches!(
+                &invocation.branches[..],
+                [BranchInfo { target: BranchTarget::Statement(target), .. }] if target.0 <= idx
+            );
+            let is_self_call = callees.get(&invocation.libfunc_id) == Some(&function.id);
+            if is_backward_jump || is_self_call {
+                add_probe(idx, function_idx, ProbeKind::BackEdge(StatementIdx(idx)));
+            }
+        }
+    }
+
+    let inserter = ProbeInserter { probe_indices };
+    let program = rewrite_program(program, &[&inserter]);
+    // Find the statements of the probes in the instrumented program by their flags.
+    let mut probe_libfuncs = UnorderedHashMap::<ConcreteLibfuncId, usize>::default();
+    for declaration in &program.libfunc_declarations {
+        if let Some(probe_idx) = probe_idx_from_long_id(&declaration.long_id, probes.len()) {
+            probe_libfuncs.insert(declaration.id.clone(), probe_idx);
+        }
+    }
+    for (idx, statement) in program.statements.iter().enumerate() {
+        if let Statement::Invocation(invocation) = statement
+            && let Some(probe_idx) = probe_libfuncs.get(&invocation.libfunc_id)
+        {
+            probes[*probe_idx].statement_idx = StatementIdx(idx);
+        }
+    }
+    InstrumentedProgram { program, probes }
+}
+
+/// Returns the long id of the libfunc of the probe with the given index.
+fn probe_long_id(probe_idx: usize) -> ConcreteLibfuncLongId {
+    ConcreteLibfuncLongId {
+        generic_id: TraceLibfunc::STR_ID.into(),
+        generic_args: vec![GenericArg::Value(BigInt::from(PROBE_FLAG_BASE) + probe_idx)],
+    }
+}
+
+/// Returns the index of the probe whose libfunc has the given long id, if it is one.
+fn probe_idx_from_long_id(long_id: &ConcreteLibfuncLongId, n_probes: usize) -> Option<usize> {
+    if long_id.generic_id.0 != TraceLibfunc::STR_ID {
+        return None;
+    }
+    let [GenericArg::Value(flag)] = &long_id.generic_args[..] else { return None };
+    let probe_idx = usize::try_from(flag - BigInt::from(PROBE_FLAG_BASE)).ok()?;
+    (probe_idx < n_probes).then_some(probe_idx)
+}
+
+/// A rewrite rule inserting the probes before their statements.
+struct ProbeInserter {
+    /// The indices of the probes to insert before each statement of the original program.
+    probe_indices: UnorderedHashMap<usize, Vec<usize>>,
+}
+impl RewriteRule for ProbeInserter {
+    fn try_rewrite(
+        &self,
+        context: &mut RewriteContext,
+        start: StatementIdx,
+        statements: &[Statement],
+    ) -> Option<Rewrite> {
+        let probe_indices = self.probe_indices.get(&start.0)?;
+        let mut replacement = probe_indices
+            .iter()
+            .map(|probe_idx| {
+                Statement::Invocation(Invocation {
+                    libfunc_id: context.declare_libfunc(probe_long_id(*probe_idx)),
+                    args: vec![],
+                    branches: vec![BranchInfo {
+                        target: BranchTarget::Fallthrough,
+                        results: vec![],
+                    }],
+                })
+            })
+            .collect::<Vec<_>>();
+        replacement.push(statements[0].clone());
+        Some(Rewrite { n_matched: 1, replacement })
+    }
+}
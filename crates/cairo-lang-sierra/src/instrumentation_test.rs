@@ -0,0 +1,136 @@
+use cairo_lang_test_utils::test;
+use indoc::indoc;
+
+use super::{ExecutionProfile, FunctionProfile, Probe, ProbeKind, instrument_program};
+use crate::ProgramParser;
+use crate::program::StatementIdx;
+
+#[test]
+fn instrument() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc jump = jump;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+            libfunc call_loop = function_call<user@Loop>;
+
+            call_loop([0]) -> ([1]);
+            return([1]);
+            felt252_is_zero([0]) { fallthrough() 4([1]) };
+            return([0]);
+            drop_nz_felt252([1]) -> ();
+            jump() { 2() };
+
+            Main@0([0]: felt252) -> (felt252);
+            Loop@2([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let instrumented = instrument_program(&program);
+    assert_eq!(
+        instrumented.program.to_string(),
+        indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc jump = jump;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+            libfunc call_loop = function_call<user@Loop>;
+            libfunc trace<8102661147984920576> = trace<8102661147984920576>;
+            libfunc trace<8102661147984920577> = trace<8102661147984920577>;
+            libfunc trace<8102661147984920578> = trace<8102661147984920578>;
+
+            F0:
+            trace<8102661147984920576>() -> ();
+            call_loop([0]) -> ([1]);
+            return([1]);
+            F1:
+            trace<8102661147984920577>() -> ();
+            felt252_is_zero([0]) { fallthrough() F1_B0([1]) };
+            return([0]);
+            F1_B0:
+            drop_nz_felt252([1]) -> ();
+            trace<8102661147984920578>() -> ();
+            jump() { F1() };
+
+            Main@F0([0]: felt252) -> (felt252);
+            Loop@F1([0]: felt252) -> (felt252);
+        "}
+    );
+    assert_eq!(
+        instrumented.probes,
+        vec![
+            Probe { function_idx: 0, kind: ProbeKind::Entry, statement_idx: StatementIdx(0) },
+            Probe { function_idx: 1, kind: ProbeKind::Entry, statement_idx: StatementIdx(3) },
+            Probe {
+                function_idx: 1,
+                kind: ProbeKind::BackEdge(StatementIdx(5)),
+                statement_idx: StatementIdx(7),
+            },
+        ]
+    );
+    assert_eq!(
+        instrumented.profile(&[1, 4, 3]),
+        ExecutionProfile {
+            functions: [
+                ("Main".into(), FunctionProfile { calls: 1, back_edges: 0 }),
+                ("Loop".into(), FunctionProfile { calls: 4, back_edges: 3 }),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    );
+}
+
+#[test]
+fn instrument_recursion() {
+    // The functions are declared out of the order of their code, and the loop is a recursion.
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+            libfunc call_loop = function_call<user@Loop>;
+
+            felt252_is_zero([0]) { fallthrough() 2([1]) };
+            return([0]);
+            drop_nz_felt252([1]) -> ();
+            call_loop([0]) -> ([2]);
+            return([2]);
+            call_loop([0]) -> ([1]);
+            return([1]);
+
+            Main@5([0]: felt252) -> (felt252);
+            Loop@0([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let instrumented = instrument_program(&program);
+    assert_eq!(
+        instrumented.probes,
+        vec![
+            Probe { function_idx: 0, kind: ProbeKind::Entry, statement_idx: StatementIdx(7) },
+            Probe { function_idx: 1, kind: ProbeKind::Entry, statement_idx: StatementIdx(0) },
+            Probe {
+                function_idx: 1,
+                kind: ProbeKind::BackEdge(StatementIdx(3)),
+                statement_idx: StatementIdx(4),
+            },
+        ]
+    );
+    assert_eq!(
+        instrumented.profile(&[1, 4, 3]),
+        ExecutionProfile {
+            functions: [
+                ("Main".into(), FunctionProfile { calls: 1, back_edges: 0 }),
+                ("Loop".into(), FunctionProfile { calls: 4, back_edges: 3 }),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    );
+}
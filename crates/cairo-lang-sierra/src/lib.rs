@@ -12,17 +12,21 @@
 use lalrpop_util::lalrpop_mod;
 
 pub mod algorithm;
+pub mod circuit_builder;
 pub mod debug_info;
 pub mod edit_state;
 pub mod extensions;
 pub mod fmt;
 pub mod ids;
+pub mod instrumentation;
 mod labeled_statement;
 pub mod program;
 pub mod program_registry;
+pub mod rewrite;
 pub mod simulation;
 #[cfg(test)]
 mod test_utils;
+pub mod type_layout;
 
 lalrpop_mod!(
     #[allow(clippy::all, unused_extern_crates)]
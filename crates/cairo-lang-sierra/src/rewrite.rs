@@ -0,0 +1,229 @@
+//! A framework for statement-level rewrites of Sierra programs.
+//!
+//! A [RewriteRule] matches a sequence of consecutive statements and replaces it with a new
+//! sequence. [rewrite_program] applies rules over a whole program, and fixes up the statement
+//! indices of branch targets and function entry points accordingly.
+
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+
+use crate::ids::{ConcreteLibfuncId, VarId};
+use crate::program::{
+    BranchTarget, ConcreteLibfuncLongId, Function, Invocation, LibfuncDeclaration, Program,
+    Statement, StatementIdx,
+};
+
+#[cfg(test)]
+#[path = "rewrite_test.rs"]
+mod test;
+
+/// The replacement of a matched sequence of statements.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rewrite {
+    /// The number of matched statements, starting from the current one. Must be positive.
+    pub n_matched: usize,
+    /// The statements replacing the matched statements.
+    ///
+    /// Branch targets of the replacement are statement indices of the original program, and are
+    /// fixed up after the rewrite. The replacement may jump to any statement not removed by a
+    /// rewrite, including the one right after the matched statements.
+    pub replacement: Vec<Statement>,
+}
+
+/// A rule for rewriting sequences of statements.
+pub trait RewriteRule {
+    /// Tries to match the rule on the statements starting at `statements[0]`, which is the
+    /// statement `start` of the original program.
+    ///
+    /// Returns the rewrite to apply if the rule matches. The rewrite is discarded if any of the
+    /// matched statements but the first is the target of a jump or a function entry point.
+    fn try_rewrite(
+        &self,
+        context: &mut RewriteContext,
+        start: StatementIdx,
+        statements: &[Statement],
+    ) -> Option<Rewrite>;
+}
+
+/// The context available to rewrite rules.
+pub struct RewriteContext {
+    /// The libfunc declarations, including the ones added by rewrites.
+    libfunc_declarations: Vec<LibfuncDeclaration>,
+    /// Maps a libfunc id to its index in `libfunc_declarations`.
+    libfunc_indices: UnorderedHashMap<ConcreteLibfuncId, usize>,
+    /// Maps a libfunc long id to its id.
+    libfunc_ids: UnorderedHashMap<ConcreteLibfuncLongId, ConcreteLibfuncId>,
+    /// The next unused variable id.
+    next_var_id: u64,
+}
+impl RewriteContext {
+    fn new(program: &Program) -> Self {
+        let mut context = Self {
+            libfunc_declarations: vec![],
+            libfunc_indices: Default::default(),
+            libfunc_ids: Default::default(),
+            next_var_id: 0,
+        };
+        for declaration in &program.libfunc_declarations {
+            context.add_libfunc_declaration(declaration.clone());
+        }
+        let mut use_var = |var: &VarId| context.next_var_id = context.next_var_id.max(var.id + 1);
+        for function in &program.funcs {
+            function.params.iter().for_each(|param| use_var(&param.id));
+        }
+        for statement in &program.statements {
+            match statement {
+                Statement::Invocation(invocation) => {
+                    invocation.args.iter().for_each(&mut use_var);
+                    for branch in &invocation.branches {
+                        branch.results.iter().for_each(&mut use_var);
+                    }
+                }
+                Statement::Return(vars) => vars.iter().for_each(&mut use_var),
+            }
+        }
+        context
+    }
+
+    /// Adds a libfunc declaration to the context.
+    fn add_libfunc_declaration(&mut self, declaration: LibfuncDeclaration) {
+        self.libfunc_indices.insert(declaration.id.clone(), self.libfunc_declarations.len());
+        self.libfunc_ids.insert(declaration.long_id.clone(), declaration.id.clone());
+        self.libfunc_declarations.push(declaration);
+    }
+
+    /// Returns the long id of a declared libfunc.
+    pub fn libfunc_long_id(&self, id: &ConcreteLibfuncId) -> Option<&ConcreteLibfuncLongId> {
+        self.libfunc_indices.get(id).map(|idx| &self.libfunc_declarations[*idx].long_id)
+    }
+
+    /// Returns the id of a libfunc, declaring it if it is not declared yet.
+    pub fn declare_libfunc(&mut self, long_id: ConcreteLibfuncLongId) -> ConcreteLibfuncId {
+        if let Some(id) = self.libfunc_ids.get(&long_id) {
+            return id.clone();
+        }
+        let mut id = ConcreteLibfuncId::from_string(long_id.to_string());
+        if self.libfunc_indices.contains_key(&id) {
+            // The name is already taken by a libfunc with a different long id.
+            let mut numeric_id = id.id;
+            while self.libfunc_indices.contains_key(&ConcreteLibfuncId::new(numeric_id)) {
+                numeric_id = numeric_id.wrapping_add(1);
+            }
+            id = ConcreteLibfuncId::new(numeric_id);
+        }
+        self.add_libfunc_declaration(LibfuncDeclaration { id: id.clone(), long_id });
+        id
+    }
+
+    /// Returns a variable id not used anywhere in the program.
+    pub fn new_var(&mut self) -> VarId {
+        let var = VarId::new(self.next_var_id);
+        self.next_var_id += 1;
+        var
+    }
+
+    /// Matches the first `N` statements against invocations of libfuncs with the given generic
+    /// ids. All the matched invocations but the last must have a single fallthrough branch.
+    pub fn match_invocations<'a, const N: usize>(
+        &self,
+        statements: &'a [Statement],
+        generic_ids: [&str; N],
+    ) -> Option<[&'a Invocation; N]> {
+        let statements = statements.get(..N)?;
+        let mut invocations = Vec::with_capacity(N);
+        for (i, (statement, generic_id)) in statements.iter().zip(generic_ids).enumerate() {
+            let Statement::Invocation(invocation) = statement else {
+                return None;
+            };
+            if self.libfunc_long_id(&invocation.libfunc_id)?.generic_id.0 != generic_id {
+                return None;
+            }
+            if i + 1 < N
+                && !matches!(
+                    &invocation.branches[..],
+                    [branch] if branch.target == BranchTarget::Fallthrough
+                )
+            {
+                return None;
+            }
+            invocations.push(invocation);
+        }
+        invocations.try_into().ok()
+    }
+}
+
+/// Applies the rewrite rules over the statements of the program.
+///
+/// The statements are scanned in order, and at each statement the first matching rule is applied.
+/// The statements of an applied rewrite are not scanned again.
+pub fn rewrite_program(program: &Program, rules: &[&dyn RewriteRule]) -> Program {
+    let mut context = RewriteContext::new(program);
+    // Statements that are the target of a jump, and cannot be removed by a rewrite.
+    let mut targets = UnorderedHashSet::<usize>::default();
+    for function in &program.funcs {
+        targets.insert(function.entry_point.0);
+    }
+    for statement in &program.statements {
+        if let Statement::Invocation(invocation) = statement {
+            for branch in &invocation.branches {
+                if let BranchTarget::Statement(target) = branch.target {
+                    targets.insert(target.0);
+                }
+            }
+        }
+    }
+
+    let n_statements = program.statements.len();
+    let mut statements = vec![];
+    // Maps the index of an original statement to its new index. `None` for removed statements.
+    let mut new_indices: Vec<Option<usize>> = vec![None; n_statements + 1];
+    let mut idx = 0;
+    while idx < n_statements {
+        let rewrite = rules.iter().find_map(|rule| {
+            let rewrite =
+                rule.try_rewrite(&mut context, StatementIdx(idx), &program.statements[idx..])?;
+            let end = idx + rewrite.n_matched;
+            (rewrite.n_matched > 0
+                && end <= n_statements
+                && (idx + 1..end).all(|inner| !targets.contains(&inner)))
+            .then_some(rewrite)
+        });
+        new_indices[idx] = Some(statements.len());
+        match rewrite {
+            Some(Rewrite { n_matched, replacement }) => {
+                statements.extend(replacement);
+                idx += n_matched;
+            }
+            None => {
+                statements.push(program.statements[idx].clone());
+                idx += 1;
+            }
+        }
+    }
+    new_indices[n_statements] = Some(statements.len());
+
+    let fix_index = |StatementIdx(idx): StatementIdx| {
+        StatementIdx(
+            new_indices
+                .get(idx)
+                .copied()
+                .flatten()
+                .unwrap_or_else(|| panic!("Statement #{idx} was removed by a rewrite.")),
+        )
+    };
+    let statements = statements.into_iter().map(|statement| statement.map(fix_index)).collect();
+    let funcs = program
+        .funcs
+        .iter()
+        .map(|function| Function {
+            entry_point: fix_index(function.entry_point),
+            ..function.clone()
+        })
+        .collect();
+    Program {
+        type_declarations: program.type_declarations.clone(),
+        libfunc_declarations: context.libfunc_declarations,
+        statements,
+        funcs,
+    }
+}
@@ -0,0 +1,148 @@
+use cairo_lang_test_utils::test;
+use indoc::indoc;
+
+use super::{Rewrite, RewriteContext, RewriteRule, rewrite_program};
+use crate::ProgramParser;
+use crate::program::{
+    BranchInfo, BranchTarget, ConcreteLibfuncLongId, GenericArg, Invocation, Statement,
+    StatementIdx,
+};
+
+/// Removes a `dup` whose second copy is immediately dropped.
+struct RemoveDupDrop;
+impl RewriteRule for RemoveDupDrop {
+    fn try_rewrite(
+        &self,
+        context: &mut RewriteContext,
+        _start: StatementIdx,
+        statements: &[Statement],
+    ) -> Option<Rewrite> {
+        let [dup, drop] = context.match_invocations(statements, ["dup", "drop"])?;
+        let [copy0, copy1] = &dup.branches[0].results[..] else { return None };
+        if drop.args != [copy1.clone()] {
+            return None;
+        }
+        // Renames the first copy back to the original variable.
+        let rename = context.declare_libfunc(ConcreteLibfuncLongId {
+            generic_id: "rename".into(),
+            generic_args: context.libfunc_long_id(&dup.libfunc_id)?.generic_args.clone(),
+        });
+        Some(Rewrite {
+            n_matched: 2,
+            replacement: vec![Statement::Invocation(Invocation {
+                libfunc_id: rename,
+                args: dup.args.clone(),
+                branches: vec![BranchInfo {
+                    target: BranchTarget::Fallthrough,
+                    results: vec![copy0.clone()],
+                }],
+            })],
+        })
+    }
+}
+
+/// Adds a `felt252_const<0>` and a `drop` before each `return`.
+struct InstrumentReturns;
+impl RewriteRule for InstrumentReturns {
+    fn try_rewrite(
+        &self,
+        context: &mut RewriteContext,
+        _start: StatementIdx,
+        statements: &[Statement],
+    ) -> Option<Rewrite> {
+        let Statement::Return(_) = statements.first()? else { return None };
+        let felt252 = "felt252".into();
+        let konst = context.declare_libfunc(ConcreteLibfuncLongId {
+            generic_id: "felt252_const".into(),
+            generic_args: vec![GenericArg::Value(0.into())],
+        });
+        let drop = context.declare_libfunc(ConcreteLibfuncLongId {
+            generic_id: "drop".into(),
+            generic_args: vec![GenericArg::Type(felt252)],
+        });
+        let var = context.new_var();
+        Some(Rewrite {
+            n_matched: 1,
+            replacement: vec![
+                Statement::Invocation(Invocation {
+                    libfunc_id: konst,
+                    args: vec![],
+                    branches: vec![BranchInfo {
+                        target: BranchTarget::Fallthrough,
+                        results: vec![var.clone()],
+                    }],
+                }),
+                Statement::Invocation(Invocation {
+                    libfunc_id: drop,
+                    args: vec![var],
+                    branches: vec![BranchInfo {
+                        target: BranchTarget::Fallthrough,
+                        results: vec![],
+                    }],
+                }),
+                statements[0].clone(),
+            ],
+        })
+    }
+}
+
+#[test]
+fn rewrite() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc dup_felt252 = dup<felt252>;
+            libfunc drop_felt252 = drop<felt252>;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc jump = jump;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+
+            dup_felt252([0]) -> ([1], [2]);
+            drop_felt252([2]) -> ();
+            felt252_is_zero([1]) { fallthrough() 5([3]) };
+            dup_felt252([0]) -> ([4], [5]);
+            jump() { 7() };
+            drop_nz_felt252([3]) -> ();
+            dup_felt252([0]) -> ([4], [5]);
+            drop_felt252([5]) -> ();
+            return([4]);
+
+            Func@0([0]: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let rewritten = rewrite_program(&program, &[&RemoveDupDrop, &InstrumentReturns]);
+    // The last `dup` is not rewritten, as its `drop` is a jump target.
+    assert_eq!(
+        rewritten.to_string(),
+        indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc dup_felt252 = dup<felt252>;
+            libfunc drop_felt252 = drop<felt252>;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc jump = jump;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+            libfunc rename<felt252> = rename<felt252>;
+            libfunc felt252_const<0> = felt252_const<0>;
+
+            F0:
+            rename<felt252>([0]) -> ([1]);
+            felt252_is_zero([1]) { fallthrough() F0_B0([3]) };
+            dup_felt252([0]) -> ([4], [5]);
+            jump() { F0_B1() };
+            F0_B0:
+            drop_nz_felt252([3]) -> ();
+            dup_felt252([0]) -> ([4], [5]);
+            F0_B1:
+            drop_felt252([5]) -> ();
+            felt252_const<0>() -> ([6]);
+            drop_felt252([6]) -> ();
+            return([4]);
+
+            Func@F0([0]: felt252) -> (felt252);
+        "}
+    );
+}
@@ -9,7 +9,9 @@ use starknet_types_core::felt::{Felt as Felt252, NonZeroFelt as NonZeroFelt252};
 use super::LibfuncSimulationError;
 use super::value::CoreValue;
 use crate::extensions::array::ArrayConcreteLibfunc;
+use crate::extensions::blake::BlakeConcreteLibfunc;
 use crate::extensions::boolean::BoolConcreteLibfunc;
+use crate::extensions::bounded_int::BoundedIntConcreteLibfunc;
 use crate::extensions::core::CoreConcreteLibfunc;
 use crate::extensions::ec::{EcConcreteLibfunc, EcPointType};
 use crate::extensions::enm::{EnumConcreteLibfunc, EnumInitConcreteLibfunc};
@@ -238,7 +240,13 @@ pub fn simulate<
             take_inputs!(let [CoreValue::Enum { value, index }] = inputs);
             (vec![*value], index)
         }
-        CoreConcreteLibfunc::Enum(EnumConcreteLibfunc::FromBoundedInt(_)) => todo!(),
+        CoreConcreteLibfunc::Enum(EnumConcreteLibfunc::FromBoundedInt(_)) => {
+            let [value] = take_inputs(inputs)?;
+            let index = int_value(&value)
+                .and_then(|value| value.to_usize())
+                .ok_or(LibfuncSimulationError::WrongArgType)?;
+            (vec![CoreValue::Enum { value: Box::new(CoreValue::Struct(vec![])), index }], 0)
+        }
         CoreConcreteLibfunc::Struct(StructConcreteLibfunc::Construct(_)) => {
             (vec![CoreValue::Struct(inputs)], 0)
         }
@@ -257,6 +265,16 @@ pub fn simulate<
             // Returning the same dict since it is exactly the same as the squashed one.
             (vec![CoreValue::RangeCheck, CoreValue::Dict(dict)], 0)
         }
+        CoreConcreteLibfunc::Felt252Dict(Felt252DictConcreteLibfunc::SquashMany(_)) => {
+            let Some((CoreValue::RangeCheck, dicts)) = inputs.split_first() else {
+                return Err(LibfuncSimulationError::WrongArgType);
+            };
+            if !dicts.iter().all(|dict| matches!(dict, CoreValue::Dict(_))) {
+                return Err(LibfuncSimulationError::WrongArgType);
+            }
+            // Returning the same dicts since they are exactly the same as the squashed ones.
+            (inputs, 0)
+        }
         CoreConcreteLibfunc::Felt252SquashedDict(_) => {
             unimplemented!("Simulation of Felt252SquashedDict is not implemented yet.");
         }
@@ -297,11 +315,22 @@ pub fn simulate<
         CoreConcreteLibfunc::Bytes31(_) => unimplemented!(),
         CoreConcreteLibfunc::Const(_) => unimplemented!(),
         CoreConcreteLibfunc::Coupon(_) => unimplemented!(),
-        CoreConcreteLibfunc::BoundedInt(_) => unimplemented!(),
+        CoreConcreteLibfunc::BoundedInt(libfunc) => simulate_bounded_int_libfunc(libfunc, inputs)?,
         CoreConcreteLibfunc::Circuit(_) => unimplemented!(),
         CoreConcreteLibfunc::IntRange(_) => unimplemented!(),
-        CoreConcreteLibfunc::Blake(_) => unimplemented!(),
-        CoreConcreteLibfunc::Trace(_) => unimplemented!(),
+        CoreConcreteLibfunc::Blake(libfunc) => {
+            take_inputs!(let [
+                CoreValue::Struct(state), CoreValue::Uint32(byte_count), CoreValue::Struct(message)
+            ] = inputs);
+            let finalize = matches!(libfunc, BlakeConcreteLibfunc::Blake2sFinalize(_));
+            let state =
+                blake2s_compress(&u32_words(state)?, &u32_words(message)?, byte_count, finalize);
+            (vec![CoreValue::Struct(state.into_iter().map(CoreValue::Uint32).collect())], 0)
+        }
+        CoreConcreteLibfunc::Trace(_) => {
+            let [] = take_inputs(inputs)?;
+            (vec![], 0)
+        }
         CoreConcreteLibfunc::QM31(_) => unimplemented!(),
         CoreConcreteLibfunc::UnsafePanic(_) => unimplemented!(),
         CoreConcreteLibfunc::DummyFunctionCall(_) => unimplemented!(),
@@ -446,7 +475,10 @@ fn simulate_u128_libfunc(
             // "True" branch (branch 1) is the case a == b.
             (vec![], usize::from(lhs == rhs))
         }
-        Uint128Concrete::ByteReverse(_) => todo!("ByteReverse"),
+        Uint128Concrete::ByteReverse(_) => {
+            take_inputs!(let [CoreValue::Bitwise, CoreValue::Uint128(value)] = inputs);
+            (vec![CoreValue::Bitwise, CoreValue::Uint128(value.swap_bytes())], 0)
+        }
         Uint128Concrete::Bitwise(_) => {
             take_inputs!(let [
                 CoreValue::Bitwise, CoreValue::Uint128(lhs), CoreValue::Uint128(rhs)
@@ -506,9 +538,35 @@ fn simulate_u8_libfunc(
                 None => (vec![CoreValue::RangeCheck], 1),
             }
         }
-        Uint8Concrete::IsZero(_) => unimplemented!(),
-        Uint8Concrete::Divmod(_) => unimplemented!(),
-        Uint8Concrete::Bitwise(_) => unimplemented!(),
+        Uint8Concrete::IsZero(_) => {
+            take_inputs!(let [CoreValue::Uint8(value)] = inputs);
+            if value.is_zero() { (vec![], 0) } else { (vec![CoreValue::Uint8(value)], 1) }
+        }
+        Uint8Concrete::Divmod(_) => {
+            take_inputs!(let [
+                CoreValue::RangeCheck, CoreValue::Uint8(lhs), CoreValue::Uint8(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    CoreValue::Uint8(lhs / rhs),
+                    CoreValue::Uint8(lhs % rhs),
+                ],
+                0,
+            )
+        }
+        Uint8Concrete::Bitwise(_) => {
+            take_inputs!(let [CoreValue::Bitwise, CoreValue::Uint8(lhs), CoreValue::Uint8(rhs)] = inputs);
+            (
+                vec![
+                    CoreValue::Bitwise,
+                    CoreValue::Uint8(lhs & rhs),
+                    CoreValue::Uint8(lhs | rhs),
+                    CoreValue::Uint8(lhs ^ rhs),
+                ],
+                0,
+            )
+        }
         Uint8Concrete::WideMul(_) => {
             take_inputs!(let [CoreValue::Uint8(lhs), CoreValue::Uint8(rhs)] = inputs);
             (vec![CoreValue::Uint16(u16::from(lhs) * u16::from(rhs))], 0)
@@ -558,9 +616,35 @@ fn simulate_u16_libfunc(
                 None => (vec![CoreValue::RangeCheck], 1),
             }
         }
-        Uint16Concrete::IsZero(_) => unimplemented!(),
-        Uint16Concrete::Divmod(_) => unimplemented!(),
-        Uint16Concrete::Bitwise(_) => unimplemented!(),
+        Uint16Concrete::IsZero(_) => {
+            take_inputs!(let [CoreValue::Uint16(value)] = inputs);
+            if value.is_zero() { (vec![], 0) } else { (vec![CoreValue::Uint16(value)], 1) }
+        }
+        Uint16Concrete::Divmod(_) => {
+            take_inputs!(let [
+                CoreValue::RangeCheck, CoreValue::Uint16(lhs), CoreValue::Uint16(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    CoreValue::Uint16(lhs / rhs),
+                    CoreValue::Uint16(lhs % rhs),
+                ],
+                0,
+            )
+        }
+        Uint16Concrete::Bitwise(_) => {
+            take_inputs!(let [CoreValue::Bitwise, CoreValue::Uint16(lhs), CoreValue::Uint16(rhs)] = inputs);
+            (
+                vec![
+                    CoreValue::Bitwise,
+                    CoreValue::Uint16(lhs & rhs),
+                    CoreValue::Uint16(lhs | rhs),
+                    CoreValue::Uint16(lhs ^ rhs),
+                ],
+                0,
+            )
+        }
         Uint16Concrete::WideMul(_) => {
             take_inputs!(let [CoreValue::Uint16(lhs), CoreValue::Uint16(rhs)] = inputs);
             (vec![CoreValue::Uint32(u32::from(lhs) * u32::from(rhs))], 0)
@@ -610,9 +694,35 @@ fn simulate_u32_libfunc(
                 None => (vec![CoreValue::RangeCheck], 1),
             }
         }
-        Uint32Concrete::IsZero(_) => unimplemented!(),
-        Uint32Concrete::Divmod(_) => unimplemented!(),
-        Uint32Concrete::Bitwise(_) => unimplemented!(),
+        Uint32Concrete::IsZero(_) => {
+            take_inputs!(let [CoreValue::Uint32(value)] = inputs);
+            if value.is_zero() { (vec![], 0) } else { (vec![CoreValue::Uint32(value)], 1) }
+        }
+        Uint32Concrete::Divmod(_) => {
+            take_inputs!(let [
+                CoreValue::RangeCheck, CoreValue::Uint32(lhs), CoreValue::Uint32(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    CoreValue::Uint32(lhs / rhs),
+                    CoreValue::Uint32(lhs % rhs),
+                ],
+                0,
+            )
+        }
+        Uint32Concrete::Bitwise(_) => {
+            take_inputs!(let [CoreValue::Bitwise, CoreValue::Uint32(lhs), CoreValue::Uint32(rhs)] = inputs);
+            (
+                vec![
+                    CoreValue::Bitwise,
+                    CoreValue::Uint32(lhs & rhs),
+                    CoreValue::Uint32(lhs | rhs),
+                    CoreValue::Uint32(lhs ^ rhs),
+                ],
+                0,
+            )
+        }
         Uint32Concrete::WideMul(_) => {
             take_inputs!(let [CoreValue::Uint32(lhs), CoreValue::Uint32(rhs)] = inputs);
             (vec![CoreValue::Uint64(u64::from(lhs) * u64::from(rhs))], 0)
@@ -660,9 +770,35 @@ fn simulate_u64_libfunc(
                 None => (vec![CoreValue::RangeCheck], 1),
             }
         }
-        Uint64Concrete::IsZero(_) => unimplemented!(),
-        Uint64Concrete::Divmod(_) => unimplemented!(),
-        Uint64Concrete::Bitwise(_) => unimplemented!(),
+        Uint64Concrete::IsZero(_) => {
+            take_inputs!(let [CoreValue::Uint64(value)] = inputs);
+            if value.is_zero() { (vec![], 0) } else { (vec![CoreValue::Uint64(value)], 1) }
+        }
+        Uint64Concrete::Divmod(_) => {
+            take_inputs!(let [
+                CoreValue::RangeCheck, CoreValue::Uint64(lhs), CoreValue::Uint64(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    CoreValue::Uint64(lhs / rhs),
+                    CoreValue::Uint64(lhs % rhs),
+                ],
+                0,
+            )
+        }
+        Uint64Concrete::Bitwise(_) => {
+            take_inputs!(let [CoreValue::Bitwise, CoreValue::Uint64(lhs), CoreValue::Uint64(rhs)] = inputs);
+            (
+                vec![
+                    CoreValue::Bitwise,
+                    CoreValue::Uint64(lhs & rhs),
+                    CoreValue::Uint64(lhs | rhs),
+                    CoreValue::Uint64(lhs ^ rhs),
+                ],
+                0,
+            )
+        }
         Uint64Concrete::WideMul(_) => {
             take_inputs!(let [CoreValue::Uint64(lhs), CoreValue::Uint64(rhs)] = inputs);
             (vec![CoreValue::Uint128(u128::from(lhs) * u128::from(rhs))], 0)
@@ -670,6 +806,169 @@ fn simulate_u64_libfunc(
     })
 }
 
+/// Simulate bounded int library functions.
+fn simulate_bounded_int_libfunc(
+    libfunc: &BoundedIntConcreteLibfunc,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    let as_int = |value: &CoreValue| int_value(value).ok_or(LibfuncSimulationError::WrongArgType);
+    Ok(match libfunc {
+        BoundedIntConcreteLibfunc::Add(_)
+        | BoundedIntConcreteLibfunc::Sub(_)
+        | BoundedIntConcreteLibfunc::Mul(_) => {
+            let [lhs, rhs] = take_inputs(inputs)?;
+            let (lhs, rhs) = (as_int(&lhs)?, as_int(&rhs)?);
+            let result = match libfunc {
+                BoundedIntConcreteLibfunc::Add(_) => lhs + rhs,
+                BoundedIntConcreteLibfunc::Sub(_) => lhs - rhs,
+                _ => lhs * rhs,
+            };
+            (vec![CoreValue::BoundedInt(result)], 0)
+        }
+        BoundedIntConcreteLibfunc::DivRem(_) => {
+            take_inputs!(let [CoreValue::RangeCheck, lhs, rhs] = inputs);
+            let (lhs, rhs) = (as_int(&lhs)?, as_int(&rhs)?);
+            if rhs.is_zero() {
+                return Err(LibfuncSimulationError::WrongArgType);
+            }
+            let (q, r) = lhs.div_rem(&rhs);
+            (vec![CoreValue::RangeCheck, CoreValue::BoundedInt(q), CoreValue::BoundedInt(r)], 0)
+        }
+        BoundedIntConcreteLibfunc::Constrain(libfunc) => {
+            take_inputs!(let [CoreValue::RangeCheck, value] = inputs);
+            let value = as_int(&value)?;
+            let branch = usize::from(value >= libfunc.boundary);
+            (vec![CoreValue::RangeCheck, CoreValue::BoundedInt(value)], branch)
+        }
+        BoundedIntConcreteLibfunc::AddConstrain(constrain)
+        | BoundedIntConcreteLibfunc::MulConstrain(constrain) => {
+            take_inputs!(let [CoreValue::RangeCheck, lhs, rhs] = inputs);
+            let (lhs, rhs) = (as_int(&lhs)?, as_int(&rhs)?);
+            let value = match libfunc {
+                BoundedIntConcreteLibfunc::AddConstrain(_) => lhs + rhs,
+                _ => lhs * rhs,
+            };
+            let branch = usize::from(value >= constrain.boundary);
+            (vec![CoreValue::RangeCheck, CoreValue::BoundedInt(value)], branch)
+        }
+        BoundedIntConcreteLibfunc::DivRemConstrain(libfunc) => {
+            take_inputs!(let [CoreValue::RangeCheck, lhs, rhs] = inputs);
+            let (lhs, rhs) = (as_int(&lhs)?, as_int(&rhs)?);
+            if rhs.is_zero() {
+                return Err(LibfuncSimulationError::WrongArgType);
+            }
+            let (q, r) = lhs.div_rem(&rhs);
+            let branch = usize::from(q >= libfunc.boundary);
+            (
+                vec![CoreValue::RangeCheck, CoreValue::BoundedInt(q), CoreValue::BoundedInt(r)],
+                branch,
+            )
+        }
+        BoundedIntConcreteLibfunc::TrimMin(libfunc)
+        | BoundedIntConcreteLibfunc::TrimMax(libfunc) => {
+            let [value] = take_inputs(inputs)?;
+            let value = as_int(&value)?;
+            if value == libfunc.trimmed_value {
+                (vec![], 0)
+            } else {
+                (vec![CoreValue::BoundedInt(value)], 1)
+            }
+        }
+        BoundedIntConcreteLibfunc::IsZero(_) => {
+            let [value] = take_inputs(inputs)?;
+            if as_int(&value)?.is_zero() { (vec![], 0) } else { (vec![value], 1) }
+        }
+        BoundedIntConcreteLibfunc::WrapNonZero(_) => {
+            let [value] = take_inputs(inputs)?;
+            (vec![value], 0)
+        }
+    })
+}
+
+/// Returns the integer value of an integer typed value.
+pub(super) fn int_value(value: &CoreValue) -> Option<BigInt> {
+    Some(match value {
+        CoreValue::Felt252(value) => value.to_bigint(),
+        CoreValue::Uint8(value) => BigInt::from(*value),
+        CoreValue::Uint16(value) => BigInt::from(*value),
+        CoreValue::Uint32(value) => BigInt::from(*value),
+        CoreValue::Uint64(value) => BigInt::from(*value),
+        CoreValue::Uint128(value) => BigInt::from(*value),
+        CoreValue::BoundedInt(value) => value.clone(),
+        _ => return None,
+    })
+}
+
+/// Returns the words of a simulated fixed size array of `u32`s.
+fn u32_words<const N: usize>(values: Vec<CoreValue>) -> Result<[u32; N], LibfuncSimulationError> {
+    values
+        .into_iter()
+        .map(|value| match value {
+            CoreValue::Uint32(word) => Ok(word),
+            _ => Err(LibfuncSimulationError::WrongArgType),
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .try_into()
+        .map_err(|_| LibfuncSimulationError::WrongArgType)
+}
+
+/// The initialization vector of blake2s.
+pub(super) const BLAKE2S_IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// The message word permutations of the rounds of blake2s.
+const BLAKE2S_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The blake2s compression function, as computed by the `blake2s` instruction.
+pub(super) fn blake2s_compress(
+    state: &[u32; 8],
+    message: &[u32; 16],
+    byte_count: u32,
+    finalize: bool,
+) -> [u32; 8] {
+    let mut v = [0; 16];
+    v[..8].copy_from_slice(state);
+    v[8..].copy_from_slice(&BLAKE2S_IV);
+    v[12] ^= byte_count;
+    if finalize {
+        v[14] ^= u32::MAX;
+    }
+    let mut mix = |a: usize, b: usize, c: usize, d: usize, x: u32, y: u32| {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(12);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(8);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(7);
+    };
+    for sigma in &BLAKE2S_SIGMA {
+        let m = |i: usize| message[sigma[i]];
+        mix(0, 4, 8, 12, m(0), m(1));
+        mix(1, 5, 9, 13, m(2), m(3));
+        mix(2, 6, 10, 14, m(4), m(5));
+        mix(3, 7, 11, 15, m(6), m(7));
+        mix(0, 5, 10, 15, m(8), m(9));
+        mix(1, 6, 11, 12, m(10), m(11));
+        mix(2, 7, 8, 13, m(12), m(13));
+        mix(3, 4, 9, 14, m(14), m(15));
+    }
+    std::array::from_fn(|i| state[i] ^ v[i] ^ v[i + 8])
+}
+
 /// Simulate felt252 library functions.
 fn simulate_felt252_libfunc(
     libfunc: &Felt252Concrete,
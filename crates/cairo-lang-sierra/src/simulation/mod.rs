@@ -2,13 +2,20 @@ use std::collections::HashMap;
 
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use itertools::izip;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use starknet_types_core::felt::Felt as Felt252;
 use thiserror::Error;
 
 use self::value::CoreValue;
 use crate::edit_state::{EditState, EditStateError};
-use crate::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
-use crate::ids::{FunctionId, VarId};
-use crate::program::{Program, Statement, StatementIdx};
+use crate::extensions::casts::CastConcreteLibfunc;
+use crate::extensions::const_type::{
+    ConstAsBoxConcreteLibfunc, ConstAsImmediateConcreteLibfunc, ConstConcreteLibfunc,
+};
+use crate::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType, CoreTypeConcrete};
+use crate::ids::{ConcreteTypeId, FunctionId, VarId};
+use crate::program::{GenericArg, Program, Statement, StatementIdx};
 use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
 
 pub mod core;
@@ -25,6 +32,8 @@ pub enum LibfuncSimulationError {
     WrongArgType,
     #[error("Could not resolve requested symbol value")]
     UnresolvedStatementGasInfo,
+    #[error("Could not resolve the value of a constant")]
+    UnresolvedConstValue,
     #[error("Error occurred during user function call")]
     FunctionSimulationError(FunctionId, Box<SimulationError>),
 }
@@ -129,19 +138,120 @@ impl SimulationContext<'_> {
         libfunc: &CoreConcreteLibfunc,
         inputs: Vec<CoreValue>,
     ) -> Result<(Vec<CoreValue>, usize), SimulationError> {
-        core::simulate(
-            libfunc,
-            inputs,
-            || self.statement_gas_info.get(&statement_id).copied(),
-            |function_id, inputs| {
-                self.simulate_function(function_id, inputs).map_err(|error| {
-                    LibfuncSimulationError::FunctionSimulationError(
-                        function_id.clone(),
-                        Box::new(error),
-                    )
+        // Libfuncs whose outputs depend on types are simulated here, as the types are only
+        // available through the registry.
+        match libfunc {
+            CoreConcreteLibfunc::Const(
+                ConstConcreteLibfunc::AsImmediate(ConstAsImmediateConcreteLibfunc {
+                    const_type,
+                    ..
                 })
-            },
-        )
+                | ConstConcreteLibfunc::AsBox(ConstAsBoxConcreteLibfunc { const_type, .. }),
+            ) => {
+                if inputs.is_empty() {
+                    self.const_value(const_type).map(|value| (vec![value], 0))
+                } else {
+                    Err(LibfuncSimulationError::WrongNumberOfArgs)
+                }
+            }
+            CoreConcreteLibfunc::Cast(libfunc) => self.simulate_cast(libfunc, inputs),
+            _ => core::simulate(
+                libfunc,
+                inputs,
+                || self.statement_gas_info.get(&statement_id).copied(),
+                |function_id, inputs| {
+                    self.simulate_function(function_id, inputs).map_err(|error| {
+                        LibfuncSimulationError::FunctionSimulationError(
+                            function_id.clone(),
+                            Box::new(error),
+                        )
+                    })
+                },
+            ),
+        }
         .map_err(|error| SimulationError::LibfuncSimulationError(error, statement_id))
     }
+
+    /// Returns the value of the constant of type `ty`.
+    fn const_value(&self, ty: &ConcreteTypeId) -> Result<CoreValue, LibfuncSimulationError> {
+        let Ok(CoreTypeConcrete::Const(const_type)) = self.registry.get_type(ty) else {
+            return Err(LibfuncSimulationError::UnresolvedConstValue);
+        };
+        let inner_type = self
+            .registry
+            .get_type(&const_type.inner_ty)
+            .map_err(|_| LibfuncSimulationError::UnresolvedConstValue)?;
+        match (inner_type, const_type.inner_data.as_slice()) {
+            (CoreTypeConcrete::Struct(_), _) => const_type
+                .inner_data
+                .iter()
+                .map(|member| match member {
+                    GenericArg::Type(member) => self.const_value(member),
+                    _ => Err(LibfuncSimulationError::UnresolvedConstValue),
+                })
+                .collect::<Result<_, _>>()
+                .map(CoreValue::Struct)
+                .ok(),
+            (CoreTypeConcrete::Enum(_), _) => match const_type.inner_data.as_slice() {
+                [GenericArg::Value(index), GenericArg::Type(variant)] => Some(CoreValue::Enum {
+                    value: Box::new(self.const_value(variant)?),
+                    index: index.to_usize().ok_or(LibfuncSimulationError::UnresolvedConstValue)?,
+                }),
+                _ => None,
+            },
+            (_, [GenericArg::Value(value)]) => self.int_typed_value(&const_type.inner_ty, value),
+            _ => None,
+        }
+        .ok_or(LibfuncSimulationError::UnresolvedConstValue)
+    }
+
+    /// Simulates a cast between integer types.
+    fn simulate_cast(
+        &self,
+        libfunc: &CastConcreteLibfunc,
+        inputs: Vec<CoreValue>,
+    ) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+        match libfunc {
+            CastConcreteLibfunc::Upcast(libfunc) => {
+                let [value] = <[CoreValue; 1]>::try_from(inputs)
+                    .map_err(|_| LibfuncSimulationError::WrongNumberOfArgs)?;
+                let to_ty = &libfunc.signature.branch_signatures[0].vars[0].ty;
+                let value = core::int_value(&value)
+                    .and_then(|value| self.int_typed_value(to_ty, &value))
+                    .ok_or(LibfuncSimulationError::WrongArgType)?;
+                Ok((vec![value], 0))
+            }
+            CastConcreteLibfunc::Downcast(libfunc) => {
+                let [CoreValue::RangeCheck, value] = <[CoreValue; 2]>::try_from(inputs)
+                    .map_err(|_| LibfuncSimulationError::WrongNumberOfArgs)?
+                else {
+                    return Err(LibfuncSimulationError::WrongArgType);
+                };
+                let value = core::int_value(&value).ok_or(LibfuncSimulationError::WrongArgType)?;
+                if libfunc.to_range.lower <= value && value < libfunc.to_range.upper {
+                    let value = self
+                        .int_typed_value(&libfunc.to_ty, &value)
+                        .ok_or(LibfuncSimulationError::WrongArgType)?;
+                    Ok((vec![CoreValue::RangeCheck, value], 0))
+                } else {
+                    Ok((vec![CoreValue::RangeCheck], 1))
+                }
+            }
+        }
+    }
+
+    /// Returns the value of type `ty` holding the integer `value`, if `ty` is a supported integer
+    /// type.
+    fn int_typed_value(&self, ty: &ConcreteTypeId, value: &BigInt) -> Option<CoreValue> {
+        match self.registry.get_type(ty).ok()? {
+            CoreTypeConcrete::Felt252(_) => Some(CoreValue::Felt252(Felt252::from(value))),
+            CoreTypeConcrete::Uint8(_) => value.to_u8().map(CoreValue::Uint8),
+            CoreTypeConcrete::Uint16(_) => value.to_u16().map(CoreValue::Uint16),
+            CoreTypeConcrete::Uint32(_) => value.to_u32().map(CoreValue::Uint32),
+            CoreTypeConcrete::Uint64(_) => value.to_u64().map(CoreValue::Uint64),
+            CoreTypeConcrete::Uint128(_) => value.to_u128().map(CoreValue::Uint128),
+            CoreTypeConcrete::BoundedInt(_) => Some(CoreValue::BoundedInt(value.clone())),
+            _ => None,
+        }
+    }
 }
@@ -147,6 +147,9 @@ fn simulate(
 #[test_case("u128_overflowing_sub", vec![], vec![RangeCheck, Uint128(3), Uint128(5)]
              => Ok((vec![RangeCheck, Uint128(u128::MAX - 1)], 1));
             "u128_overflowing_sub(3, 5)")]
+#[test_case("u64_is_zero", vec![], vec![Uint64(2)]
+             => Ok((vec![Uint64(2)], 1)); "u64_is_zero(2)")]
+#[test_case("u64_is_zero", vec![], vec![Uint64(0)] => Ok((vec![], 0)); "u64_is_zero(0)")]
 fn simulate_branch(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -186,6 +189,8 @@ fn simulate_branch(
              => Ok(vec![Uint128(3), Uint128(5)]); "function_call<identity>()")]
 #[test_case("u64_to_felt252", vec![], vec![Uint64(5)]
              => Ok(vec![Felt252(5u64.into())]); "u64_to_felt252(5)")]
+#[test_case("u64_safe_divmod", vec![], vec![RangeCheck, Uint64(32), Uint64(5)]
+             => Ok(vec![RangeCheck, Uint64(6), Uint64(2)]); "u64_safe_divmod(32, 5)")]
 fn simulate_none_branch(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -235,3 +240,17 @@ fn simulate_error(
 ) -> LibfuncSimulationError {
     simulate(id, generic_args, inputs).err().unwrap()
 }
+
+#[test]
+fn blake2s_compress_empty_message() {
+    let mut state = core::BLAKE2S_IV;
+    // The parameter block of an unkeyed hash with a 32 bytes digest.
+    state[0] ^= 0x01010020;
+    assert_eq!(
+        core::blake2s_compress(&state, &[0; 16], 0, true),
+        [
+            0x307A2169, 0x94809079, 0xD02111E1, 0x7C4A3542, 0x48B6551F, 0x1EA5A12C, 0xFD0D251B,
+            0xF9EED01E
+        ]
+    );
+}
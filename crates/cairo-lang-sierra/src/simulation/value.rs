@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use num_bigint::BigInt;
 use starknet_types_core::felt::Felt as Felt252;
 
 /// The logical value of a variable for Sierra simulation.
@@ -13,6 +14,8 @@ pub enum CoreValue {
     Uint32(u32),
     Uint64(u64),
     Uint128(u128),
+    /// A bounded int, or the result of a bounded int operation.
+    BoundedInt(BigInt),
     Array(Vec<CoreValue>),
     Dict(HashMap<Felt252, CoreValue>),
     Enum {
@@ -22,6 +22,7 @@ pub fn build_bijective_mapping() -> BiMap<ConcreteTypeId, ConcreteTypeLongId> {
     elements
         .insert("Option".into(), as_named_type_long_id("Enum", "Option", &["felt252", "Tuple<>"]));
     elements.insert("NonZeroFelt252".into(), as_type_long_id("NonZero", &["felt252"]));
+    elements.insert("NonZeroU64".into(), as_type_long_id("NonZero", &["u64"]));
     elements.insert("NonZeroU128".into(), as_type_long_id("NonZero", &["u128"]));
     elements.insert("ArrayFelt252".into(), as_type_long_id("Array", &["felt252"]));
     elements.insert("ArrayFelt252".into(), as_type_long_id("Array", &["felt252"]));
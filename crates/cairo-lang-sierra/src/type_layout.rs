@@ -0,0 +1,202 @@
+//! Layout information of concrete Sierra types.
+//!
+//! Provides the memory layout of the types of a program - their size in felt252 cells and the
+//! offsets of their members - along with their basic properties, so that tools do not need to
+//! re-derive them from the type declarations.
+
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+
+use crate::extensions::ConcreteType;
+use crate::extensions::circuit::CircuitTypeConcrete;
+use crate::extensions::core::{CoreLibfunc, CoreType, CoreTypeConcrete};
+use crate::extensions::starknet::StarknetTypeConcrete;
+use crate::ids::ConcreteTypeId;
+use crate::program::Program;
+use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
+
+#[cfg(test)]
+#[path = "type_layout_test.rs"]
+mod test;
+
+/// The layout of a concrete type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypeLayout {
+    /// The number of felt252 cells a value of the type occupies.
+    /// `None` for types whose values are never placed in memory, such as consts and circuit gates.
+    pub size: Option<i16>,
+    /// The offsets of the members of the type within its value - the members of a struct, or the
+    /// payloads of the variants of an enum (which are placed after the selector and the padding).
+    /// Empty for any other type.
+    pub member_offsets: Vec<i16>,
+    /// Whether the type is zero-sized.
+    pub zero_sized: bool,
+    /// Whether the type can be dropped.
+    pub droppable: bool,
+    /// Whether the type can be duplicated.
+    pub duplicatable: bool,
+    /// Whether the type can be stored by any of the store commands.
+    pub storable: bool,
+}
+
+pub type TypeLayoutMap = UnorderedHashMap<ConcreteTypeId, TypeLayout>;
+
+/// Returns a mapping of the layouts of all types in the given program.
+pub fn get_type_layout_map(
+    program: &Program,
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+) -> Result<TypeLayoutMap, Box<ProgramRegistryError>> {
+    let mut layouts = TypeLayoutMap::default();
+    for declaration in &program.type_declarations {
+        let ty = registry.get_type(&declaration.id)?;
+        let get_dep_size = |dep: &ConcreteTypeId| {
+            layouts.get(dep).and_then(|layout| layout.size).ok_or_else(|| {
+                Box::new(ProgramRegistryError::TypeSizeDependencyMissing {
+                    ty: declaration.id.clone(),
+                    dep: dep.clone(),
+                })
+            })
+        };
+        let size = type_size(&declaration.id, ty, get_dep_size)?;
+        let member_offsets = match ty {
+            CoreTypeConcrete::Struct(struct_type) if size.is_some() => {
+                let mut offset: i16 = 0;
+                let mut member_offsets = Vec::with_capacity(struct_type.members.len());
+                for member in &struct_type.members {
+                    member_offsets.push(offset);
+                    // Cannot overflow, as the sum of the member sizes is the struct size.
+                    offset += get_dep_size(member)?;
+                }
+                member_offsets
+            }
+            CoreTypeConcrete::Enum(enum_type) => {
+                // The variants are right-aligned after the selector.
+                let size = size.unwrap();
+                let mut member_offsets = Vec::with_capacity(enum_type.variants.len());
+                for variant in &enum_type.variants {
+                    member_offsets.push(size - get_dep_size(variant)?);
+                }
+                member_offsets
+            }
+            _ => vec![],
+        };
+        let info = ty.info();
+        let layout = TypeLayout {
+            size,
+            member_offsets,
+            zero_sized: info.zero_sized,
+            droppable: info.droppable,
+            duplicatable: info.duplicatable,
+            storable: info.storable,
+        };
+        layouts.insert(declaration.id.clone(), layout);
+    }
+    Ok(layouts)
+}
+
+/// Returns the size of the type `ty` with the id `id`, or `None` if it has no size.
+///
+/// `get_dep_size` returns the size of a type the type depends on.
+fn type_size(
+    id: &ConcreteTypeId,
+    ty: &CoreTypeConcrete,
+    get_dep_size: impl Fn(&ConcreteTypeId) -> Result<i16, Box<ProgramRegistryError>>,
+) -> Result<Option<i16>, Box<ProgramRegistryError>> {
+    let size_overflow_error = || Box::new(ProgramRegistryError::TypeSizeOverflow(id.clone()));
+    let size = match ty {
+        // Size 0.
+        CoreTypeConcrete::Coupon(_) => 0,
+        // Size 1.
+        CoreTypeConcrete::Felt252(_)
+        | CoreTypeConcrete::GasBuiltin(_)
+        | CoreTypeConcrete::GasReserve(_)
+        | CoreTypeConcrete::Bitwise(_)
+        | CoreTypeConcrete::BuiltinCosts(_)
+        | CoreTypeConcrete::EcOp(_)
+        | CoreTypeConcrete::Nullable(_)
+        | CoreTypeConcrete::Uint8(_)
+        | CoreTypeConcrete::Uint16(_)
+        | CoreTypeConcrete::Uint32(_)
+        | CoreTypeConcrete::Uint64(_)
+        | CoreTypeConcrete::Uint128(_)
+        | CoreTypeConcrete::Sint8(_)
+        | CoreTypeConcrete::Sint16(_)
+        | CoreTypeConcrete::Sint32(_)
+        | CoreTypeConcrete::Sint64(_)
+        | CoreTypeConcrete::Sint128(_)
+        | CoreTypeConcrete::RangeCheck(_)
+        | CoreTypeConcrete::RangeCheck96(_)
+        | CoreTypeConcrete::Box(_)
+        | CoreTypeConcrete::Starknet(StarknetTypeConcrete::System(_))
+        | CoreTypeConcrete::Starknet(StarknetTypeConcrete::StorageBaseAddress(_))
+        | CoreTypeConcrete::Starknet(StarknetTypeConcrete::StorageAddress(_))
+        | CoreTypeConcrete::Starknet(StarknetTypeConcrete::ContractAddress(_))
+        | CoreTypeConcrete::Starknet(StarknetTypeConcrete::ClassHash(_))
+        | CoreTypeConcrete::Starknet(StarknetTypeConcrete::Secp256Point(_))
+        | CoreTypeConcrete::Starknet(StarknetTypeConcrete::Sha256StateHandle(_))
+        | CoreTypeConcrete::Pedersen(_)
+        | CoreTypeConcrete::Poseidon(_)
+        | CoreTypeConcrete::Felt252Dict(_)
+        | CoreTypeConcrete::Felt252DictEntry(_)
+        | CoreTypeConcrete::SegmentArena(_)
+        | CoreTypeConcrete::Bytes31(_)
+        | CoreTypeConcrete::BoundedInt(_)
+        | CoreTypeConcrete::QM31(_) => 1,
+        // Size 2.
+        CoreTypeConcrete::Array(_)
+        | CoreTypeConcrete::Span(_)
+        | CoreTypeConcrete::EcPoint(_)
+        | CoreTypeConcrete::SquashedFelt252Dict(_)
+        | CoreTypeConcrete::IntRange(_) => 2,
+        // Other.
+        CoreTypeConcrete::NonZero(wrapped_ty)
+        | CoreTypeConcrete::Snapshot(wrapped_ty)
+        | CoreTypeConcrete::Uninitialized(wrapped_ty) => get_dep_size(&wrapped_ty.ty)?,
+        CoreTypeConcrete::EcState(_) => 3,
+        CoreTypeConcrete::Uint128MulGuarantee(_) => 4,
+        CoreTypeConcrete::Enum(enum_type) => {
+            let mut max_variant_size: i16 = 0;
+            for variant in &enum_type.variants {
+                max_variant_size = max_variant_size.max(get_dep_size(variant)?);
+            }
+            max_variant_size.checked_add(1).ok_or_else(size_overflow_error)?
+        }
+        CoreTypeConcrete::Struct(struct_type) => {
+            if !struct_type.info.storable {
+                // If the struct is not storable, it should not have a size.
+                return Ok(None);
+            }
+            let mut size: i16 = 0;
+            for member in &struct_type.members {
+                size = size.checked_add(get_dep_size(member)?).ok_or_else(size_overflow_error)?;
+            }
+            size
+        }
+
+        CoreTypeConcrete::Circuit(CircuitTypeConcrete::CircuitInputAccumulator(_)) => 2,
+        CoreTypeConcrete::Circuit(CircuitTypeConcrete::CircuitDescriptor(_)) => 4,
+        CoreTypeConcrete::Circuit(CircuitTypeConcrete::CircuitFailureGuarantee(_)) => 8,
+        CoreTypeConcrete::Circuit(CircuitTypeConcrete::U96LimbsLessThanGuarantee(g)) => g
+            .limb_count
+            .checked_mul(2)
+            .and_then(|v| v.try_into().ok())
+            .ok_or_else(size_overflow_error)?,
+        CoreTypeConcrete::Circuit(CircuitTypeConcrete::U96Guarantee(_)) => 1,
+        CoreTypeConcrete::Circuit(CircuitTypeConcrete::CircuitOutputs(_)) => 5,
+        CoreTypeConcrete::Circuit(CircuitTypeConcrete::CircuitPartialOutputs(_)) => 6,
+        CoreTypeConcrete::Circuit(CircuitTypeConcrete::CircuitModulus(_)) => 4,
+        CoreTypeConcrete::Circuit(CircuitTypeConcrete::CircuitData(_))
+        | CoreTypeConcrete::Circuit(CircuitTypeConcrete::AddMod(_))
+        | CoreTypeConcrete::Circuit(CircuitTypeConcrete::MulMod(_)) => 1,
+
+        // Const and circuit types are not moved around and should not have a size.
+        CoreTypeConcrete::Const(_)
+        | CoreTypeConcrete::Circuit(CircuitTypeConcrete::Circuit(_))
+        | CoreTypeConcrete::Circuit(CircuitTypeConcrete::CircuitInput(_))
+        | CoreTypeConcrete::Circuit(CircuitTypeConcrete::AddModGate(_))
+        | CoreTypeConcrete::Circuit(CircuitTypeConcrete::InverseGate(_))
+        | CoreTypeConcrete::Circuit(CircuitTypeConcrete::MulModGate(_))
+        | CoreTypeConcrete::Circuit(CircuitTypeConcrete::SubModGate(_)) => return Ok(None),
+        CoreTypeConcrete::Blake(_) => 1,
+    };
+    Ok(Some(size))
+}
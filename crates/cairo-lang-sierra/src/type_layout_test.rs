@@ -0,0 +1,66 @@
+use cairo_lang_test_utils::test;
+use indoc::indoc;
+
+use super::{TypeLayout, get_type_layout_map};
+use crate::ProgramParser;
+use crate::extensions::core::{CoreLibfunc, CoreType};
+use crate::program_registry::ProgramRegistry;
+
+#[test]
+fn type_layouts() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type u128 = u128;
+            type Array<felt252> = Array<felt252>;
+            type Unit = Struct<ut@Tuple>;
+            type Wide = Struct<ut@Wide, felt252, Array<felt252>, u128>;
+            type Option = Enum<ut@Option, Wide, Unit>;
+            type Snapshot<Option> = Snapshot<Option>;
+            type Const<felt252, 5> = Const<felt252, 5>;
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(&program).unwrap();
+    let layouts = get_type_layout_map(&program, &registry).unwrap();
+    let layout = |name: &str| layouts[&name.into()].clone();
+
+    assert_eq!(
+        layout("felt252"),
+        TypeLayout {
+            size: Some(1),
+            member_offsets: vec![],
+            zero_sized: false,
+            droppable: true,
+            duplicatable: true,
+            storable: true,
+        }
+    );
+    assert_eq!(
+        layout("Unit"),
+        TypeLayout {
+            size: Some(0),
+            member_offsets: vec![],
+            zero_sized: true,
+            droppable: true,
+            duplicatable: true,
+            storable: true,
+        }
+    );
+    assert_eq!(
+        layout("Wide"),
+        TypeLayout {
+            size: Some(4),
+            member_offsets: vec![0, 1, 3],
+            zero_sized: false,
+            droppable: true,
+            duplicatable: false,
+            storable: true,
+        }
+    );
+    // The variants are right-aligned, after the selector.
+    assert_eq!(layout("Option").size, Some(5));
+    assert_eq!(layout("Option").member_offsets, vec![1, 5]);
+    assert_eq!(layout("Snapshot<Option>").size, Some(5));
+    assert!(layout("Snapshot<Option>").duplicatable);
+    assert_eq!(layout("Const<felt252, 5>").size, None);
+}
@@ -1,7 +1,16 @@
 use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::try_extract_matches;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use itertools::Itertools;
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
+use crate::keccak::starknet_keccak;
+
+#[cfg(test)]
+#[path = "abi_test.rs"]
+mod test;
+
 /// Contract ABI.
 #[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -93,6 +102,24 @@ pub struct Interface {
     pub name: String,
     pub items: Vec<Item>,
 }
+impl Interface {
+    /// Returns the id of the interface, in the manner of SRC-5 - the XOR of the extended
+    /// selectors of its functions, where the extended selector of a function is the
+    /// `starknet_keccak` of its signature, `name(input types)->(output types)`, e.g.
+    /// `approve(core::felt252,core::integer::u256)->(core::bool)`. The `self` parameter is not a
+    /// part of the signature.
+    pub fn id(&self) -> BigUint {
+        self.items
+            .iter()
+            .filter_map(|item| try_extract_matches!(item, Item::Function))
+            .map(|function| {
+                let inputs = function.inputs.iter().map(|input| &input.ty).join(",");
+                let outputs = function.outputs.iter().map(|output| &output.ty).join(",");
+                starknet_keccak(format!("{}({inputs})->({outputs})", function.name).as_bytes())
+            })
+            .fold(BigUint::default(), |id, selector| id ^ selector)
+    }
+}
 
 /// Contract impl ABI.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
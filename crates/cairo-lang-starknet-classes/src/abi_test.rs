@@ -0,0 +1,34 @@
+use num_bigint::BigUint;
+
+use super::{Function, Input, Interface, Item, Output, StateMutability};
+use crate::keccak::starknet_keccak;
+
+fn function(name: &str, inputs: &[&str], outputs: &[&str]) -> Item {
+    Item::Function(Function {
+        name: name.into(),
+        inputs: inputs
+            .iter()
+            .enumerate()
+            .map(|(idx, ty)| Input { name: format!("arg{idx}"), ty: ty.to_string() })
+            .collect(),
+        outputs: outputs.iter().map(|ty| Output { ty: ty.to_string() }).collect(),
+        state_mutability: StateMutability::View,
+    })
+}
+
+#[test]
+fn test_interface_id() {
+    let interface = |items| Interface { name: "test::IFoo".into(), items };
+    assert_eq!(interface(vec![]).id(), BigUint::default());
+
+    let supports = function("supports_interface", &["core::felt252"], &["core::bool"]);
+    let supports_selector = starknet_keccak(b"supports_interface(core::felt252)->(core::bool)");
+    assert_eq!(interface(vec![supports.clone()]).id(), supports_selector);
+
+    let set = function("set", &["core::felt252", "core::integer::u32"], &[]);
+    let set_selector = starknet_keccak(b"set(core::felt252,core::integer::u32)->()");
+    let id = interface(vec![supports.clone(), set.clone()]).id();
+    assert_eq!(id, &supports_selector ^ &set_selector);
+    // The id does not depend on the order of the functions, or on the parameter names.
+    assert_eq!(interface(vec![set, supports]).id(), id);
+}
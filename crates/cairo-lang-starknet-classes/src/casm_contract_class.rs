@@ -18,8 +18,10 @@ use cairo_lang_sierra::extensions::segment_arena::SegmentArenaType;
 use cairo_lang_sierra::extensions::snapshot::SnapshotType;
 use cairo_lang_sierra::extensions::starknet::syscalls::SystemType;
 use cairo_lang_sierra::extensions::structure::StructType;
-use cairo_lang_sierra::ids::{ConcreteTypeId, GenericTypeId};
-use cairo_lang_sierra::program::{ConcreteTypeLongId, GenericArg, TypeDeclaration};
+use cairo_lang_sierra::ids::{ConcreteTypeId, FunctionId, GenericTypeId};
+use cairo_lang_sierra::program::{
+    ConcreteTypeLongId, Function, GenericArg, Program, StatementIdx, TypeDeclaration,
+};
 use cairo_lang_sierra_to_casm::compiler::{
     CairoProgramDebugInfo, CompilationError, SierraToCasmConfig,
 };
@@ -42,8 +44,11 @@ use starknet_types_core::hash::{Blake2Felt252, Poseidon, StarkHash};
 use thiserror::Error;
 
 use crate::allowed_libfuncs::AllowedLibfuncsError;
+use crate::class_size::{EntryPointType, function_callees, function_statement_ranges};
 use crate::compiler_version::{VersionId, current_compiler_version_id, current_sierra_version_id};
-use crate::contract_class::{ContractClass, ContractEntryPoint, ExtractedSierraProgram};
+use crate::contract_class::{
+    ContractClass, ContractEntryPoint, ContractEntryPoints, ExtractedSierraProgram,
+};
 use crate::contract_segmentation::{
     NestedIntList, SegmentationError, compute_bytecode_segment_lengths,
 };
@@ -92,6 +97,13 @@ pub enum StarknetSierraCompilationError {
          version: {version_of_compiler})"
     )]
     UnsupportedSierraVersion { version_in_contract: VersionId, version_of_compiler: VersionId },
+    #[error(
+        "Entry point `{function}` compiles to {felts} felts, exceeding the budget of {budget} \
+         felts."
+    )]
+    EntryPointSizeBudgetExceeded { function: String, felts: usize, budget: usize },
+    #[error("No {entry_point_type} entry point with selector {selector:#x}.")]
+    MissingEntryPoint { entry_point_type: EntryPointType, selector: BigUint },
 }
 
 fn skip_if_none<T>(opt_field: &Option<T>) -> bool {
@@ -435,7 +447,12 @@ impl CasmContractClass {
             &program,
             &program_info,
             &metadata,
-            SierraToCasmConfig { gas_usage_check: true, max_bytecode_size },
+            SierraToCasmConfig {
+                gas_usage_check: true,
+                max_bytecode_size,
+                record_variable_locations: false,
+                coalesce_ap_updates: false,
+            },
         )?;
 
         let AssembledCairoProgram { bytecode, hints } = cairo_program.assemble();
@@ -582,6 +599,123 @@ impl CasmContractClass {
 
         Ok((casm_contract_class, cairo_program.debug_info))
     }
+
+    /// Compiles a single entry point of a contract class, together with the functions it may call,
+    /// instead of the whole class.
+    ///
+    /// The result has the given entry point as its only entry point, and is meant for debugging
+    /// and analyzing the entry point, as its bytecode differs from the one of the full class.
+    pub fn from_contract_class_entry_point(
+        contract_class: ContractClass,
+        extracted_program: ExtractedSierraProgram,
+        entry_point_type: EntryPointType,
+        selector: &BigUint,
+        add_pythonic_hints: bool,
+        max_bytecode_size: usize,
+    ) -> Result<Self, StarknetSierraCompilationError> {
+        let all_entry_points = &contract_class.entry_points_by_type;
+        let entry_point = match entry_point_type {
+            EntryPointType::Constructor => &all_entry_points.constructor,
+            EntryPointType::External => &all_entry_points.external,
+            EntryPointType::L1Handler => &all_entry_points.l1_handler,
+        }
+        .iter()
+        .find(|entry_point| &entry_point.selector == selector)
+        .ok_or_else(|| StarknetSierraCompilationError::MissingEntryPoint {
+            entry_point_type,
+            selector: selector.clone(),
+        })?;
+        let (program, function_idx) =
+            entry_point_program(&extracted_program.program, entry_point.function_idx)?;
+
+        let mut entry_points_by_type = ContractEntryPoints::default();
+        match entry_point_type {
+            EntryPointType::Constructor => &mut entry_points_by_type.constructor,
+            EntryPointType::External => &mut entry_points_by_type.external,
+            EntryPointType::L1Handler => &mut entry_points_by_type.l1_handler,
+        }
+        .push(ContractEntryPoint { selector: selector.clone(), function_idx });
+        Self::from_contract_class(
+            ContractClass { entry_points_by_type, ..contract_class },
+            ExtractedSierraProgram { program, ..extracted_program },
+            add_pythonic_hints,
+            max_bytecode_size,
+        )
+    }
+}
+
+/// Returns the program with only the functions reachable from the function of index
+/// `function_idx`, and the index of that function in the returned program.
+fn entry_point_program(
+    program: &Program,
+    function_idx: usize,
+) -> Result<(Program, usize), StarknetSierraCompilationError> {
+    if function_idx >= program.funcs.len() {
+        return Err(StarknetSierraCompilationError::EntryPointError);
+    }
+    let ranges = function_statement_ranges(program);
+    let callees = function_callees(program, &ranges);
+    let mut reachable = vec![false; program.funcs.len()];
+    let mut stack = vec![function_idx];
+    while let Some(idx) = stack.pop() {
+        if !reachable[idx] {
+            reachable[idx] = true;
+            stack.extend(&callees[idx]);
+        }
+    }
+
+    // Moving the statements of the reachable functions together, keeping their order.
+    let mut new_statement_idx = vec![None; program.statements.len()];
+    let mut statement_ranges = (0..program.funcs.len())
+        .filter(|idx| reachable[*idx])
+        .map(|idx| ranges[idx].clone())
+        .collect_vec();
+    statement_ranges.sort_by_key(|range| range.start);
+    let mut next_idx = 0;
+    for range in &statement_ranges {
+        for old_idx in range.clone() {
+            new_statement_idx[old_idx] = Some(next_idx);
+            next_idx += 1;
+        }
+    }
+    // Branches out of a function are invalid anyway, so they are mapped to a missing statement,
+    // and reported by the compilation.
+    let remap = |StatementIdx(idx)| StatementIdx(new_statement_idx[idx].unwrap_or(usize::MAX));
+    let statements = statement_ranges
+        .iter()
+        .flat_map(|range| program.statements[range.clone()].iter())
+        .map(|statement| statement.clone().map(remap))
+        .collect();
+
+    let funcs = program
+        .funcs
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| reachable[*idx])
+        .map(|(_, func)| Function { entry_point: remap(func.entry_point), ..func.clone() })
+        .collect_vec();
+    let kept_functions: UnorderedHashSet<&FunctionId> = funcs.iter().map(|func| &func.id).collect();
+    let libfunc_declarations = program
+        .libfunc_declarations
+        .iter()
+        .filter(|declaration| {
+            declaration.long_id.generic_args.iter().all(|arg| match arg {
+                GenericArg::UserFunc(id) => kept_functions.contains(id),
+                _ => true,
+            })
+        })
+        .cloned()
+        .collect();
+    let new_function_idx = reachable[..function_idx].iter().filter(|kept| **kept).count();
+    Ok((
+        Program {
+            type_declarations: program.type_declarations.clone(),
+            libfunc_declarations,
+            statements,
+            funcs,
+        },
+        new_function_idx,
+    ))
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -6,14 +6,16 @@ use cairo_lang_sierra::ids::GenericLibfuncId;
 use cairo_lang_test_utils::compare_contents_or_fix_with_path;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
-use itertools::Itertools;
+use itertools::{Itertools, chain};
+use num_bigint::BigUint;
 use starknet_types_core::felt::Felt as Felt252;
 use test_case::test_case;
 
 use crate::allowed_libfuncs::{
     BUILTIN_AUDITED_LIBFUNCS_LIST, ListSelector, lookup_allowed_libfuncs_list,
 };
-use crate::casm_contract_class::{BigUintAsHex, CasmContractClass};
+use crate::casm_contract_class::{BigUintAsHex, CasmContractClass, StarknetSierraCompilationError};
+use crate::class_size::EntryPointType;
 use crate::compiler_version::current_sierra_version_id;
 use crate::contract_class::ContractClass;
 use crate::felt252_serde::{Felt252SerdeError, sierra_from_felt252s};
@@ -65,6 +67,64 @@ fn test_casm_contract_from_contract_class_from_contracts_crate(name: &str) {
     );
 }
 
+/// Tests compiling each entry point of a contract on its own.
+#[test_case("hello_starknet__hello_starknet")]
+#[test_case("token_bridge__token_bridge")]
+fn test_casm_contract_from_contract_class_entry_point(name: &str) {
+    let contract_path = get_example_file_path(&format!("{name}.contract_class.json"));
+    let contract: ContractClass =
+        serde_json::from_reader(BufReader::new(std::fs::File::open(contract_path).unwrap()))
+            .unwrap();
+    let full_contract = CasmContractClass::from_contract_class(
+        contract.clone(),
+        contract.extract_sierra_program(false).unwrap(),
+        false,
+        usize::MAX,
+    )
+    .unwrap();
+    let entry_points = &contract.entry_points_by_type;
+    for (entry_point_type, entry_point) in chain!(
+        entry_points.external.iter().map(|ep| (EntryPointType::External, ep)),
+        entry_points.l1_handler.iter().map(|ep| (EntryPointType::L1Handler, ep)),
+    ) {
+        let casm_contract = CasmContractClass::from_contract_class_entry_point(
+            contract.clone(),
+            contract.extract_sierra_program(false).unwrap(),
+            entry_point_type,
+            &entry_point.selector,
+            false,
+            usize::MAX,
+        )
+        .unwrap();
+        let casm_entry_points = &casm_contract.entry_points_by_type;
+        let [casm_entry_point] = &chain!(
+            &casm_entry_points.constructor,
+            &casm_entry_points.external,
+            &casm_entry_points.l1_handler
+        )
+        .collect_vec()[..] else {
+            panic!("Expected a single entry point.");
+        };
+        assert_eq!(casm_entry_point.selector, entry_point.selector);
+        assert!(casm_entry_point.offset < casm_contract.bytecode.len());
+        assert!(casm_contract.bytecode.len() < full_contract.bytecode.len());
+    }
+    assert_eq!(
+        CasmContractClass::from_contract_class_entry_point(
+            contract.clone(),
+            contract.extract_sierra_program(false).unwrap(),
+            EntryPointType::Constructor,
+            &BigUint::from(1u32),
+            false,
+            usize::MAX,
+        ),
+        Err(StarknetSierraCompilationError::MissingEntryPoint {
+            entry_point_type: EntryPointType::Constructor,
+            selector: BigUint::from(1u32),
+        })
+    );
+}
+
 // TODO(Tomer-C): Check for more concrete types per libfunc.
 /// Tests that the contract covers part of the libfuncs.
 #[test_case("libfuncs_coverage__libfuncs_coverage")]
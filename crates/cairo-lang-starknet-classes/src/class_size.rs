@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Range;
+
+use cairo_lang_sierra::debug_info::Annotations;
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::program::{GenericArg, Program, Statement};
+use cairo_lang_sierra_to_casm::compiler::CairoProgramDebugInfo;
+use cairo_lang_utils::bigint::{deserialize_big_uint, serialize_big_uint};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use itertools::{Itertools, chain};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+use crate::casm_contract_class::{CasmContractClass, StarknetSierraCompilationError};
+use crate::contract_class::ContractEntryPoints;
+
+#[cfg(test)]
+#[path = "class_size_test.rs"]
+mod test;
+
+/// The annotation holding, per Sierra statement, the Cairo functions that caused it to be
+/// generated, from the innermost (inlined) one to the outermost.
+const STATEMENTS_FUNCTIONS_ANNOTATION: &str = "github.com/software-mansion/cairo-profiler";
+
+/// The type of a contract entry point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryPointType {
+    Constructor,
+    External,
+    L1Handler,
+}
+impl Display for EntryPointType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntryPointType::Constructor => write!(f, "constructor"),
+            EntryPointType::External => write!(f, "external"),
+            EntryPointType::L1Handler => write!(f, "l1_handler"),
+        }
+    }
+}
+
+/// The bytecode size of a single entry point.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryPointSize {
+    /// The type of the entry point.
+    pub entry_point_type: EntryPointType,
+    /// The selector of the entry point.
+    #[serde(serialize_with = "serialize_big_uint", deserialize_with = "deserialize_big_uint")]
+    pub selector: BigUint,
+    /// The name of the Sierra function of the entry point.
+    pub function: String,
+    /// The number of bytecode felts of the entry point function and of all the functions it may
+    /// call. Functions shared between entry points are counted for each of them.
+    pub felts: usize,
+}
+
+/// The bytecode size attributed to a single source function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionSize {
+    /// The full path of the function.
+    pub name: String,
+    /// The number of bytecode felts generated for the function, including its inlined copies.
+    pub felts: usize,
+}
+
+/// A report of the bytecode size of a compiled contract class.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassSizeReport {
+    /// The total number of felts in the bytecode, including the constants segments.
+    pub bytecode_felts: usize,
+    /// The sizes of the entry points, by type and in selector order.
+    pub entry_points: Vec<EntryPointSize>,
+    /// The sizes of the source functions, from the largest.
+    pub functions: Vec<FunctionSize>,
+}
+
+impl ClassSizeReport {
+    /// Creates the size report of a compiled contract class.
+    ///
+    /// # Arguments
+    /// * `program` - The Sierra program of the class. Function names are taken from its debug names
+    ///   when available.
+    /// * `entry_points` - The entry points of the Sierra contract class.
+    /// * `annotations` - The annotations of the Sierra debug info. When they contain the functions
+    ///   of the statements, felts are attributed to the innermost source function instead of the
+    ///   Sierra function, so that inlined functions are reported as well.
+    /// * `casm_contract_class` - The class compiled from `program`.
+    /// * `debug_info` - The debug info of the compilation of `program`.
+    pub fn new(
+        program: &Program,
+        entry_points: &ContractEntryPoints,
+        annotations: Option<&Annotations>,
+        casm_contract_class: &CasmContractClass,
+        debug_info: &CairoProgramDebugInfo,
+    ) -> Self {
+        let statement_felts = |idx: usize| {
+            debug_info
+                .sierra_statement_info
+                .get(idx)
+                .map_or(0, |info| info.end_offset - info.start_offset)
+        };
+
+        let ranges = function_statement_ranges(program);
+        let function_felts = ranges
+            .iter()
+            .map(|range| range.clone().map(statement_felts).sum::<usize>())
+            .collect_vec();
+
+        let statements_functions = annotations.and_then(parse_statements_functions);
+        let mut functions = OrderedHashMap::<String, usize>::default();
+        for (func, range) in program.funcs.iter().zip(&ranges) {
+            for idx in range.clone() {
+                let name = statements_functions
+                    .as_ref()
+                    .and_then(|map| map.get(&idx)?.first().cloned())
+                    .unwrap_or_else(|| func.id.to_string());
+                *functions.entry(name).or_default() += statement_felts(idx);
+            }
+        }
+        let functions = functions
+            .into_iter()
+            .filter(|(_, felts)| *felts > 0)
+            .map(|(name, felts)| FunctionSize { name, felts })
+            .sorted_by(|a, b| b.felts.cmp(&a.felts).then_with(|| a.name.cmp(&b.name)))
+            .collect();
+
+        let callees = function_callees(program, &ranges);
+        let entry_points = chain!(
+            entry_points.constructor.iter().map(|ep| (EntryPointType::Constructor, ep)),
+            entry_points.external.iter().map(|ep| (EntryPointType::External, ep)),
+            entry_points.l1_handler.iter().map(|ep| (EntryPointType::L1Handler, ep)),
+        )
+        .map(|(entry_point_type, entry_point)| {
+            // Collecting all the functions reachable from the entry point.
+            let mut visited = vec![false; program.funcs.len()];
+            let mut stack = vec![entry_point.function_idx];
+            let mut felts = 0;
+            while let Some(func_idx) = stack.pop() {
+                if func_idx >= visited.len() || visited[func_idx] {
+                    continue;
+                }
+                visited[func_idx] = true;
+                felts += function_felts[func_idx];
+                stack.extend(&callees[func_idx]);
+            }
+            EntryPointSize {
+                entry_point_type,
+                selector: entry_point.selector.clone(),
+                function: program
+                    .funcs
+                    .get(entry_point.function_idx)
+                    .map_or_else(|| "<missing>".to_string(), |func| func.id.to_string()),
+                felts,
+            }
+        })
+        .collect();
+
+        Self { bytecode_felts: casm_contract_class.bytecode.len(), entry_points, functions }
+    }
+
+    /// Checks that no entry point exceeds `max_entry_point_felts` felts.
+    pub fn check_budget(
+        &self,
+        max_entry_point_felts: usize,
+    ) -> Result<(), StarknetSierraCompilationError> {
+        match self.entry_points.iter().find(|entry_point| entry_point.felts > max_entry_point_felts)
+        {
+            Some(entry_point) => {
+                Err(StarknetSierraCompilationError::EntryPointSizeBudgetExceeded {
+                    function: entry_point.function.clone(),
+                    felts: entry_point.felts,
+                    budget: max_entry_point_felts,
+                })
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for ClassSizeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Bytecode: {} felts.", self.bytecode_felts)?;
+        writeln!(f, "Entry points:")?;
+        for entry_point in &self.entry_points {
+            writeln!(
+                f,
+                "  {} {:#x} ({}): {} felts.",
+                entry_point.entry_point_type,
+                entry_point.selector,
+                entry_point.function,
+                entry_point.felts
+            )?;
+        }
+        writeln!(f, "Functions:")?;
+        for function in &self.functions {
+            writeln!(f, "  {}: {} felts.", function.name, function.felts)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the range of the statements of each function, by function index.
+pub(crate) fn function_statement_ranges(program: &Program) -> Vec<Range<usize>> {
+    let starts = program.funcs.iter().map(|func| func.entry_point.0).sorted().collect_vec();
+    program
+        .funcs
+        .iter()
+        .map(|func| {
+            let start = func.entry_point.0;
+            let end = starts
+                .iter()
+                .find(|other| **other > start)
+                .copied()
+                .unwrap_or(program.statements.len());
+            start..end
+        })
+        .collect()
+}
+
+/// Returns the indices of the functions referenced by the libfuncs invoked in each function, by
+/// function index.
+pub(crate) fn function_callees(program: &Program, ranges: &[Range<usize>]) -> Vec<Vec<usize>> {
+    let func_idx_by_id: UnorderedHashMap<&FunctionId, usize> =
+        program.funcs.iter().enumerate().map(|(idx, func)| (&func.id, idx)).collect();
+    let referenced_functions: UnorderedHashMap<_, Vec<usize>> = program
+        .libfunc_declarations
+        .iter()
+        .map(|declaration| {
+            let functions = declaration
+                .long_id
+                .generic_args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArg::UserFunc(id) => func_idx_by_id.get(id).copied(),
+                    _ => None,
+                })
+                .collect();
+            (&declaration.id, functions)
+        })
+        .collect();
+    ranges
+        .iter()
+        .map(|range| {
+            program.statements[range.clone()]
+                .iter()
+                .filter_map(|statement| match statement {
+                    Statement::Invocation(invocation) => {
+                        referenced_functions.get(&invocation.libfunc_id)
+                    }
+                    Statement::Return(_) => None,
+                })
+                .flatten()
+                .copied()
+                .unique()
+                .collect()
+        })
+        .collect()
+}
+
+/// Parses the functions of the statements from the annotations, if available.
+fn parse_statements_functions(annotations: &Annotations) -> Option<HashMap<usize, Vec<String>>> {
+    let mapping = annotations.get(STATEMENTS_FUNCTIONS_ANNOTATION)?.get("statements_functions")?;
+    serde_json::from_value(mapping.clone()).ok()
+}
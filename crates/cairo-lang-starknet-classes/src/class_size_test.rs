@@ -0,0 +1,81 @@
+use std::io::BufReader;
+
+use cairo_lang_sierra::debug_info::Annotations;
+use cairo_lang_test_utils::compare_contents_or_fix_with_path;
+use serde_json::json;
+use test_case::test_case;
+
+use super::ClassSizeReport;
+use crate::casm_contract_class::{CasmContractClass, StarknetSierraCompilationError};
+use crate::contract_class::ContractClass;
+use crate::test_utils::get_example_file_path;
+
+/// Compiles the example contract `name` and returns its size report.
+fn size_report(name: &str, annotations: Option<&Annotations>) -> ClassSizeReport {
+    let contract_path = get_example_file_path(&format!("{name}.contract_class.json"));
+    let contract: ContractClass =
+        serde_json::from_reader(BufReader::new(std::fs::File::open(contract_path).unwrap()))
+            .unwrap();
+    let extracted = contract.extract_sierra_program(true).unwrap();
+    let program = extracted.program.clone();
+    let entry_points = contract.entry_points_by_type.clone();
+    let (casm_contract, debug_info) = CasmContractClass::from_contract_class_with_debug_info(
+        contract,
+        extracted,
+        false,
+        usize::MAX,
+    )
+    .unwrap();
+    ClassSizeReport::new(&program, &entry_points, annotations, &casm_contract, &debug_info)
+}
+
+/// Tests the size reports of the example contracts against the files in `class_size_test_data`.
+#[test_case("hello_starknet__hello_starknet")]
+#[test_case("minimal_contract__minimal_contract")]
+#[test_case("with_ownable__ownable_balance")]
+fn test_size_report(name: &str) {
+    let report = size_report(name, None);
+    assert!(
+        report.functions.iter().map(|function| function.felts).sum::<usize>()
+            <= report.bytecode_felts
+    );
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/class_size_test_data")
+        .join(format!("{name}.txt"));
+    compare_contents_or_fix_with_path(&path, report.to_string());
+}
+
+#[test]
+fn test_size_report_with_statements_functions() {
+    // Attributing all the statements of the first function to a single source function.
+    let annotations = Annotations::from_iter([(
+        "github.com/software-mansion/cairo-profiler".to_string(),
+        json!({"statements_functions": {"0": ["a::inlined", "a::outer"], "1": ["a::inlined"]}}),
+    )]);
+    let report = size_report("hello_starknet__hello_starknet", Some(&annotations));
+    let without_annotations = size_report("hello_starknet__hello_starknet", None);
+    assert_eq!(report.entry_points, without_annotations.entry_points);
+    assert!(report.functions.iter().any(|function| function.name == "a::inlined"));
+    assert!(!report.functions.iter().any(|function| function.name == "a::outer"));
+    assert_eq!(
+        report.functions.iter().map(|function| function.felts).sum::<usize>(),
+        without_annotations.functions.iter().map(|function| function.felts).sum::<usize>()
+    );
+}
+
+#[test]
+fn test_size_budget() {
+    let report = size_report("hello_starknet__hello_starknet", None);
+    let max_felts = report.entry_points.iter().map(|entry_point| entry_point.felts).max().unwrap();
+    assert_eq!(report.check_budget(max_felts), Ok(()));
+    let exceeding =
+        report.entry_points.iter().find(|entry_point| entry_point.felts == max_felts).unwrap();
+    assert_eq!(
+        report.check_budget(max_felts - 1),
+        Err(StarknetSierraCompilationError::EntryPointSizeBudgetExceeded {
+            function: exceeding.function.clone(),
+            felts: max_felts,
+            budget: max_felts - 1,
+        })
+    );
+}
@@ -39,7 +39,12 @@ impl TestFileRunner for BytecodeSegmentLengthTest {
                     &sierra_program,
                     &program_info,
                     &metadata,
-                    SierraToCasmConfig { gas_usage_check: false, max_bytecode_size: usize::MAX },
+                    SierraToCasmConfig {
+                        gas_usage_check: false,
+                        max_bytecode_size: usize::MAX,
+                        record_variable_locations: false,
+                        coalesce_ap_updates: false,
+                    },
                 )
                 .ok()
             });
@@ -8,6 +8,7 @@
 pub mod abi;
 pub mod allowed_libfuncs;
 pub mod casm_contract_class;
+pub mod class_size;
 pub mod compiler_version;
 pub mod contract_class;
 mod contract_segmentation;
@@ -432,11 +432,12 @@ impl<'db> AbiBuilder<'db> {
         function_with_body_id: FunctionWithBodyId<'db>,
         storage_type: TypeId<'db>,
     ) -> Result<(), ABIError<'db>> {
+        let source = Source::Function(function_with_body_id);
         let name: String = function_with_body_id.name(self.db).to_string(self.db);
         let signature = self.db.function_with_body_signature(function_with_body_id)?;
 
-        let function = self.function_as_abi(&name, signature, storage_type)?;
-        self.add_abi_item(Item::Function(function), true, Source::Function(function_with_body_id))?;
+        let function = self.function_as_abi(&name, source, signature, storage_type)?;
+        self.add_abi_item(Item::Function(function), true, source)?;
 
         Ok(())
     }
@@ -454,10 +455,15 @@ impl<'db> AbiBuilder<'db> {
         let name = function_with_body_id.name(self.db).to_string(self.db);
         let signature = self.db.function_with_body_signature(function_with_body_id)?;
 
-        let (inputs, state_mutability) =
-            self.get_function_signature_inputs_and_mutability(signature, storage_type)?;
+        let (inputs, state_mutability) = self.get_function_signature_inputs_and_mutability(
+            &name,
+            source,
+            signature,
+            storage_type,
+        )?;
         self.ctor = Some(EntryPointInfo { source, inputs: inputs.clone() });
-        require(state_mutability == StateMutability::External).ok_or(ABIError::UnexpectedType)?;
+        require(state_mutability == StateMutability::External)
+            .ok_or(ABIError::ConstructorMustTakeRefSelf(source))?;
 
         let constructor_item = Item::Constructor(Constructor { name, inputs });
         self.add_abi_item(constructor_item, true, source)?;
@@ -471,37 +477,56 @@ impl<'db> AbiBuilder<'db> {
         function_with_body_id: FunctionWithBodyId<'db>,
         storage_type: TypeId<'db>,
     ) -> Result<(), ABIError<'db>> {
+        let source = Source::Function(function_with_body_id);
         let name = function_with_body_id.name(self.db).to_string(self.db);
         let signature = self.db.function_with_body_signature(function_with_body_id)?;
 
-        let (inputs, state_mutability) =
-            self.get_function_signature_inputs_and_mutability(signature, storage_type)?;
+        let (inputs, state_mutability) = self.get_function_signature_inputs_and_mutability(
+            &name,
+            source,
+            signature,
+            storage_type,
+        )?;
 
         let outputs = self.get_signature_outputs(signature)?;
 
         let l1_handler_item =
             Item::L1Handler(L1Handler { name, inputs, outputs, state_mutability });
-        self.add_abi_item(l1_handler_item, true, Source::Function(function_with_body_id))?;
+        self.add_abi_item(l1_handler_item, true, source)?;
 
         Ok(())
     }
 
-    /// Inspects a free function and returns its inputs and state mutability.
+    /// Inspects the signature of the entry point `name` and returns its inputs and state
+    /// mutability, after validating it is ABI compatible.
     fn get_function_signature_inputs_and_mutability(
         &mut self,
+        name: &str,
+        source: Source<'db>,
         signature: &cairo_lang_semantic::Signature<'db>,
         storage_type: TypeId<'db>,
     ) -> Result<(Vec<Input>, StateMutability), ABIError<'db>> {
         let mut params = signature.params.iter();
         let Some(first_param) = params.next() else {
-            return Err(ABIError::EntrypointMustHaveSelf);
+            return Err(ABIError::EntrypointMustHaveSelf(source));
         };
         require(first_param.name.long(self.db) == SELF_PARAM_KW)
-            .ok_or(ABIError::EntrypointMustHaveSelf)?;
+            .ok_or(ABIError::EntrypointMustHaveSelf(source))?;
         let is_ref = first_param.mutability == Mutability::Reference;
         let expected_storage_ty =
             if is_ref { storage_type } else { TypeLongId::Snapshot(storage_type).intern(self.db) };
-        require(first_param.ty == expected_storage_ty).ok_or(ABIError::UnexpectedType)?;
+        require(first_param.ty == expected_storage_ty)
+            .ok_or(ABIError::UnexpectedSelfType(source))?;
+        // Validating all the types of the signature before adding any of them to the ABI.
+        for ty in params.clone().map(|param| param.ty).chain([signature.return_type]) {
+            if let Some(unsupported) = self.find_unsupported_type(ty)? {
+                return Err(ABIError::UnsupportedEntryPointType {
+                    entry_point: name.to_string(),
+                    ty: unsupported.format(self.db),
+                    source_ptr: source,
+                });
+            }
+        }
         let state_mutability =
             if is_ref { StateMutability::External } else { StateMutability::View };
         let mut inputs = vec![];
@@ -515,6 +540,41 @@ impl<'db> AbiBuilder<'db> {
         Ok((inputs, state_mutability))
     }
 
+    /// Returns a type that can't be a part of the ABI, out of the given type and the types it
+    /// depends on, if there is one.
+    fn find_unsupported_type(&self, type_id: TypeId<'db>) -> Maybe<Option<TypeId<'db>>> {
+        Ok(match type_id.long(self.db) {
+            TypeLongId::Concrete(concrete) => {
+                for generic_arg in concrete.generic_args(self.db) {
+                    if let GenericArgumentId::Type(ty) = generic_arg
+                        && let Some(unsupported) = self.find_unsupported_type(ty)?
+                    {
+                        return Ok(Some(unsupported));
+                    }
+                }
+                None
+            }
+            TypeLongId::Tuple(inner_types) => {
+                for ty in inner_types {
+                    if let Some(unsupported) = self.find_unsupported_type(*ty)? {
+                        return Ok(Some(unsupported));
+                    }
+                }
+                None
+            }
+            TypeLongId::Snapshot(ty) | TypeLongId::FixedSizeArray { type_id: ty, .. } => {
+                self.find_unsupported_type(*ty)?
+            }
+            // Missing types were already reported.
+            TypeLongId::Missing(diag_added) => return Err(*diag_added),
+            TypeLongId::Coupon(_)
+            | TypeLongId::GenericParameter(_)
+            | TypeLongId::Var(_)
+            | TypeLongId::ImplType(_)
+            | TypeLongId::Closure(_) => Some(type_id),
+        })
+    }
+
     /// Gets the output types of the given signature.
     fn get_signature_outputs(
         &mut self,
@@ -538,18 +598,28 @@ impl<'db> AbiBuilder<'db> {
         let name: String = trait_function_id.name(self.db).to_string(self.db);
         let signature = self.db.trait_function_signature(trait_function_id)?;
 
-        self.function_as_abi(&name, signature, storage_type)
+        self.function_as_abi(
+            &name,
+            Source::TraitFunction(trait_function_id),
+            signature,
+            storage_type,
+        )
     }
 
     /// Converts a function name and signature to an ABI::Function.
     fn function_as_abi(
         &mut self,
         name: &str,
+        source: Source<'db>,
         signature: &Signature<'db>,
         storage_type: TypeId<'db>,
     ) -> Result<Function, ABIError<'db>> {
-        let (inputs, state_mutability) =
-            self.get_function_signature_inputs_and_mutability(signature, storage_type)?;
+        let (inputs, state_mutability) = self.get_function_signature_inputs_and_mutability(
+            name,
+            source,
+            signature,
+            storage_type,
+        )?;
 
         let outputs = self.get_signature_outputs(signature)?;
 
@@ -895,7 +965,16 @@ pub enum ABIError<'db> {
     #[error("Got unexpected type.")]
     UnexpectedType,
     #[error("Entrypoints must have a self first param.")]
-    EntrypointMustHaveSelf,
+    EntrypointMustHaveSelf(Source<'db>),
+    #[error(
+        "The self param of entry points must be `ref self: ContractState` or `self: \
+         @ContractState`."
+    )]
+    UnexpectedSelfType(Source<'db>),
+    #[error("Constructors must have a `ref self: ContractState` first param.")]
+    ConstructorMustTakeRefSelf(Source<'db>),
+    #[error("Entry point `{entry_point}` uses type `{ty}`, which can't be a part of the ABI.")]
+    UnsupportedEntryPointType { entry_point: String, ty: String, source_ptr: Source<'db> },
     #[error("An embedded impl must be an impl of a trait marked with #[{INTERFACE_ATTR}].")]
     EmbeddedImplMustBeInterface(Source<'db>),
     #[error("Embedded impls must be annotated with #[starknet::embeddable].")]
@@ -933,7 +1012,6 @@ impl<'db> ABIError<'db> {
             ABIError::EventFlatVariantMustBeEnum(attr) => Some(attr.stable_ptr(db).untyped()),
             ABIError::NoStorage => None,
             ABIError::UnexpectedType => None,
-            ABIError::EntrypointMustHaveSelf => None,
             ABIError::EventNotDerived(source)
             | ABIError::EventSelectorDuplication { source_ptr: source, .. }
             | ABIError::EventMustBeEnum(source)
@@ -947,7 +1025,13 @@ impl<'db> ABIError<'db> {
             | ABIError::InvalidDuplicatedItem { source_ptr: source, .. }
             | ABIError::DuplicateEntryPointName { source_ptr: source, .. }
             | ABIError::EntryPointSupportedOnlyOnAccountContract { source_ptr: source, .. }
-            | ABIError::ValidateDeployMismatchingConstructor(source) => Some(source.location(db)),
+            | ABIError::ValidateDeployMismatchingConstructor(source)
+            | ABIError::EntrypointMustHaveSelf(source)
+            | ABIError::UnexpectedSelfType(source)
+            | ABIError::ConstructorMustTakeRefSelf(source)
+            | ABIError::UnsupportedEntryPointType { source_ptr: source, .. } => {
+                Some(source.location(db))
+            }
             ABIError::IllegalContractAttrArgs => None,
             ABIError::EntryPointMissingForAccountContract { .. } => None,
         }
@@ -970,6 +1054,7 @@ pub enum Source<'db> {
     Enum(cairo_lang_semantic::ConcreteEnumId<'db>),
     Variant(cairo_lang_defs::ids::VariantId<'db>),
     Trait(TraitId<'db>),
+    TraitFunction(TraitFunctionId<'db>),
 }
 impl<'db> Source<'db> {
     fn location(&self, db: &'db dyn Database) -> SyntaxStablePtrId<'db> {
@@ -982,6 +1067,7 @@ impl<'db> Source<'db> {
             Source::Enum(id) => id.enum_id(db).untyped_stable_ptr(db),
             Source::Variant(id) => id.untyped_stable_ptr(db),
             Source::Trait(id) => id.untyped_stable_ptr(db),
+            Source::TraitFunction(id) => id.untyped_stable_ptr(db),
         }
     }
 }
@@ -76,6 +76,7 @@ cairo_lang_test_utils::test_file_test!(
   {
       storage_node: "storage_node",
       storage_path_check: "storage_path_check",
+      component_conflicts: "component_conflicts",
   },
   test_plugin_diagnostics,
   ["expect_diagnostics"]
@@ -1,30 +1,40 @@
-use cairo_lang_defs::ids::{EnumId, LanguageElementId, ModuleId, ModuleItemId, StructId};
+use cairo_lang_defs::ids::{
+    EnumId, FunctionWithBodyId, LanguageElementId, ModuleId, ModuleItemId, NamedLanguageElementId,
+    StructId,
+};
 use cairo_lang_defs::plugin::PluginDiagnostic;
 use cairo_lang_filesystem::ids::{DbJoin, SmolStrId};
+use cairo_lang_parser::macro_helpers::AsLegacyInlineMacro;
 use cairo_lang_semantic::helper::ModuleHelper;
 use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
 use cairo_lang_semantic::items::enm::EnumSemantic;
 use cairo_lang_semantic::items::imp::{ImplLookupContext, ImplSemantic};
+use cairo_lang_semantic::items::impl_alias::ImplAliasSemantic;
 use cairo_lang_semantic::items::module::ModuleSemantic;
 use cairo_lang_semantic::items::structure::{Member, StructSemantic};
+use cairo_lang_semantic::items::trt::TraitSemantic;
 use cairo_lang_semantic::plugin::AnalyzerPlugin;
 use cairo_lang_semantic::types::get_impl_at_context;
 use cairo_lang_semantic::{
     ConcreteTraitId, ConcreteTraitLongId, ConcreteTypeId, GenericArgumentId, TypeId, TypeLongId,
 };
 use cairo_lang_syntax::attribute::consts::ALLOW_ATTR;
-use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::helpers::{GetIdentifier, QueryAttrs};
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
-use cairo_lang_syntax::node::{Terminal, TypedStablePtr, TypedSyntaxNode};
-use cairo_lang_utils::Intern;
+use cairo_lang_syntax::node::{Terminal, TypedStablePtr, TypedSyntaxNode, ast};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::{Intern, try_extract_matches};
+use itertools::Itertools;
 use salsa::Database;
 
 use crate::abi::{ABIError, AbiBuilder, BuilderConfig};
 use crate::contract::module_contract;
 use crate::plugin::consts::{
-    COMPONENT_ATTR, CONTRACT_ATTR, EMBEDDABLE_ATTR, INTERFACE_ATTR, STORAGE_ATTR,
-    STORAGE_NODE_ATTR, STORAGE_STRUCT_NAME, STORE_TRAIT,
+    ABI_ATTR, ABI_ATTR_EMBED_V0_ARG, ABI_ATTR_PER_ITEM_ARG, COMPONENT_ATTR, COMPONENT_INLINE_MACRO,
+    CONSTRUCTOR_ATTR, CONTRACT_ATTR, EMBEDDABLE_ATTR, EXTERNAL_ATTR, HAS_COMPONENT_TRAIT,
+    INTERFACE_ATTR, L1_HANDLER_ATTR, STORAGE_ATTR, STORAGE_NODE_ATTR, STORAGE_STRUCT_NAME,
+    STORE_TRAIT,
 };
 use crate::plugin::storage_interfaces::{StorageMemberKind, get_member_storage_config};
 use crate::plugin::utils::has_derive;
@@ -91,7 +101,8 @@ fn add_abi_diagnostics<'db>(
         return;
     };
     for err in abi_builder.errors() {
-        if !matches!(err, ABIError::SemanticError) {
+        // Duplicate entry points are reported with their sources by the `ComponentsAnalyzer`.
+        if !matches!(err, ABIError::SemanticError | ABIError::DuplicateEntryPointName { .. }) {
             let location = err.location(db).unwrap_or_else(|| {
                 if let Ok(Some(attr)) = contract.module_id().find_attr(db, CONTRACT_ATTR) {
                     attr.stable_ptr.untyped()
@@ -108,6 +119,207 @@ fn add_abi_diagnostics<'db>(
     }
 }
 
+/// Plugin to add diagnostics for the composition of components and embedded impls into contracts.
+///
+/// Reports colliding entry point selectors, and components that are used by a contract without
+/// being declared with `component!`, instead of the resolution failures in the generated code.
+#[derive(Default, Debug)]
+pub struct ComponentsAnalyzer;
+
+impl AnalyzerPlugin for ComponentsAnalyzer {
+    fn diagnostics<'db>(
+        &self,
+        db: &'db dyn Database,
+        module_id: ModuleId<'db>,
+    ) -> Vec<PluginDiagnostic<'db>> {
+        let mut diagnostics = vec![];
+        if module_contract(db, module_id).is_none() {
+            return diagnostics;
+        }
+        let Ok(module_data) = module_id.module_data(db) else {
+            return diagnostics;
+        };
+        add_missing_component_diagnostics(db, module_id, &mut diagnostics);
+        add_entry_point_collision_diagnostics(db, module_data.items(db), &mut diagnostics);
+        diagnostics
+    }
+}
+
+/// Adds diagnostics for impl aliases of a contract to embeddable impls of components that are not
+/// declared in the contract with `component!`, and therefore have no `HasComponent` impl.
+fn add_missing_component_diagnostics<'db>(
+    db: &'db dyn Database,
+    module_id: ModuleId<'db>,
+    diagnostics: &mut Vec<PluginDiagnostic<'db>>,
+) {
+    let Ok(module_data) = module_id.module_data(db) else {
+        return;
+    };
+    let wired_components: OrderedHashSet<ModuleId<'db>> = module_data
+        .impls(db)
+        .keys()
+        .filter_map(|id| db.impl_def_trait(*id).ok())
+        .filter(|trait_id| trait_id.name(db).long(db) == HAS_COMPONENT_TRAIT)
+        .map(|trait_id| trait_id.parent_module(db))
+        .collect();
+    // Components declared with an invalid `component!` have no `HasComponent` impl, but are
+    // already reported by the contract plugin.
+    let declared_component_names = declared_component_names(db, module_id);
+    for (id, item) in module_data.impl_aliases(db).iter() {
+        let Ok(impl_def) = db.impl_alias_impl_def(*id) else { continue };
+        let component = impl_def.parent_module(db);
+        if !component.has_attr(db, COMPONENT_ATTR).unwrap_or_default()
+            || wired_components.contains(&component)
+            || declared_component_names.contains(&component.name(db))
+        {
+            continue;
+        }
+        let component_path = component.full_path(db);
+        diagnostics.push(PluginDiagnostic::warning(
+            id.untyped_stable_ptr(db),
+            format!(
+                "Impl `{}` is embedded from component `{component_path}`, which is not declared \
+                 in the contract. Add `{COMPONENT_INLINE_MACRO}!(path: {component_path}, storage: \
+                 <storage_name>, event: <event_name>);` to the contract.",
+                item.name(db).text(db).long(db)
+            ),
+        ));
+    }
+}
+
+/// Returns the last segments of the paths of the components declared in the contract module with
+/// `component!`.
+fn declared_component_names<'db>(
+    db: &'db dyn Database,
+    module_id: ModuleId<'db>,
+) -> OrderedHashSet<SmolStrId<'db>> {
+    let ModuleId::Submodule(submodule_id) = module_id else {
+        return OrderedHashSet::default();
+    };
+    let ast::MaybeModuleBody::Some(body) = submodule_id.stable_ptr(db).lookup(db).body(db) else {
+        return OrderedHashSet::default();
+    };
+    body.items(db)
+        .elements(db)
+        .filter_map(|item| match item {
+            ast::ModuleItem::InlineMacro(inline_macro)
+                if inline_macro.path(db).as_syntax_node().get_text_without_trivia(db).long(db)
+                    == COMPONENT_INLINE_MACRO =>
+            {
+                inline_macro.as_legacy_inline_macro(db)
+            }
+            _ => None,
+        })
+        .filter_map(|inline_macro| match inline_macro.arguments(db) {
+            ast::WrappedArgList::ParenthesizedArgList(args) => Some(args.arguments(db)),
+            _ => None,
+        })
+        .flat_map(|args| args.elements(db).collect_vec())
+        .filter_map(|arg| match arg.arg_clause(db) {
+            ast::ArgClause::Named(clause) if clause.name(db).text(db).long(db) == "path" => {
+                try_extract_matches!(clause.value(db), ast::Expr::Path)
+            }
+            _ => None,
+        })
+        .map(|path| path.identifier(db))
+        .collect()
+}
+
+/// Adds diagnostics for entry points of a contract with the same name, and therefore the same
+/// selector, naming both of their sources.
+fn add_entry_point_collision_diagnostics<'db>(
+    db: &'db dyn Database,
+    items: &[ModuleItemId<'db>],
+    diagnostics: &mut Vec<PluginDiagnostic<'db>>,
+) {
+    // Maps the name of each entry point to the description of the item defining it.
+    let mut entry_points = OrderedHashMap::<String, String>::default();
+    let mut add_entry_point =
+        |name: SmolStrId<'db>, description: &dyn Fn() -> String, ptr: SyntaxStablePtrId<'db>| {
+            let name = name.to_string(db);
+            if let Some(existing) = entry_points.get(&name) {
+                diagnostics.push(PluginDiagnostic::warning(
+                    ptr,
+                    format!(
+                        "Entry point `{name}` of {} has the same selector as the entry point of \
+                         {}. Entry point names must be unique within a contract.",
+                        description(),
+                        existing
+                    ),
+                ));
+            } else {
+                entry_points.insert(name, description());
+            }
+        };
+    let is_entry_point = |function_with_body_id: FunctionWithBodyId<'db>| {
+        [EXTERNAL_ATTR, L1_HANDLER_ATTR, CONSTRUCTOR_ATTR]
+            .into_iter()
+            .any(|attr| function_with_body_id.has_attr(db, attr).unwrap_or_default())
+    };
+    for item in items {
+        match item {
+            ModuleItemId::Impl(impl_def) => {
+                let description = || format!("impl `{}`", impl_def.name(db).long(db));
+                let ptr = impl_def.untyped_stable_ptr(db);
+                if impl_def.has_attr(db, EXTERNAL_ATTR).unwrap_or_default()
+                    || impl_def
+                        .has_attr_with_arg(db, ABI_ATTR, ABI_ATTR_EMBED_V0_ARG)
+                        .unwrap_or_default()
+                {
+                    let Ok(trait_id) = db.impl_def_trait(*impl_def) else { continue };
+                    let Ok(functions) = db.trait_functions(trait_id) else { continue };
+                    for name in functions.keys() {
+                        add_entry_point(*name, &description, ptr);
+                    }
+                } else if impl_def
+                    .has_attr_with_arg(db, ABI_ATTR, ABI_ATTR_PER_ITEM_ARG)
+                    .unwrap_or_default()
+                {
+                    let Ok(functions) = db.impl_functions(*impl_def) else { continue };
+                    for (name, function) in functions.iter() {
+                        if is_entry_point(FunctionWithBodyId::Impl(*function)) {
+                            add_entry_point(*name, &description, ptr);
+                        }
+                    }
+                }
+            }
+            ModuleItemId::ImplAlias(impl_alias) => {
+                if !impl_alias
+                    .has_attr_with_arg(db, ABI_ATTR, ABI_ATTR_EMBED_V0_ARG)
+                    .unwrap_or_default()
+                {
+                    continue;
+                }
+                let Ok(impl_def) = db.impl_alias_impl_def(*impl_alias) else { continue };
+                let Ok(trait_id) = db.impl_def_trait(impl_def) else { continue };
+                let Ok(functions) = db.trait_functions(trait_id) else { continue };
+                let component = impl_def.parent_module(db);
+                let description = || {
+                    let alias_name = impl_alias.name(db).long(db);
+                    if component.has_attr(db, COMPONENT_ATTR).unwrap_or_default() {
+                        format!("impl `{alias_name}` of component `{}`", component.full_path(db))
+                    } else {
+                        format!("impl `{alias_name}`")
+                    }
+                };
+                for name in functions.keys() {
+                    add_entry_point(*name, &description, impl_alias.untyped_stable_ptr(db));
+                }
+            }
+            ModuleItemId::FreeFunction(free_function) => {
+                if is_entry_point(FunctionWithBodyId::Free(*free_function)) {
+                    add_entry_point(
+                        free_function.name(db),
+                        &|| format!("function `{}`", free_function.name(db).long(db)),
+                        free_function.untyped_stable_ptr(db),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 /// Plugin to add diagnostics for contracts with multiple paths to the same location in storage.
 #[derive(Default, Debug)]
 pub struct StorageAnalyzer;
@@ -178,7 +390,10 @@ fn analyze_storage_struct<'db>(
         db,
     )
     .intern(db);
-    let paths_data = &mut StorageStructMembers { name_to_paths: OrderedHashMap::default() };
+    let paths_data = &mut StorageStructMembers {
+        name_to_paths: OrderedHashMap::default(),
+        current_component: None,
+    };
 
     for (member_name, member) in members.iter() {
         let member_ast = member.id.stable_ptr(db).lookup(db);
@@ -226,8 +441,11 @@ fn analyze_storage_struct<'db>(
 
 /// Helper for the storage analyzer.
 struct StorageStructMembers<'db> {
-    /// Maps the name in actual storage to the path in actual user code.
-    name_to_paths: OrderedHashMap<SmolStrId<'db>, Vec<SmolStrId<'db>>>,
+    /// Maps the name in actual storage to the path in actual user code, and to the component
+    /// the path goes through, if any.
+    name_to_paths: OrderedHashMap<SmolStrId<'db>, (Vec<SmolStrId<'db>>, Option<ModuleId<'db>>)>,
+    /// The component whose storage is currently analyzed, if any.
+    current_component: Option<ModuleId<'db>>,
 }
 
 impl<'db> StorageStructMembers<'db> {
@@ -239,19 +457,30 @@ impl<'db> StorageStructMembers<'db> {
         pointer_to_code: SyntaxStablePtrId<'db>,
         diagnostics: &mut Vec<PluginDiagnostic<'db>>,
     ) {
-        if let Some(existing_path) = self.name_to_paths.get(&member_name) {
-            diagnostics.push(PluginDiagnostic::warning(
-                pointer_to_code,
+        let Some((existing_path, existing_component)) = self.name_to_paths.get(&member_name) else {
+            self.name_to_paths.insert(member_name, (path_to_member, self.current_component));
+            return;
+        };
+        let message = match (self.current_component, existing_component) {
+            (Some(component), Some(existing_component)) if component != *existing_component => {
                 format!(
-                    "The path `{}` collides with existing path `{}`. Fix or add \
+                    "The storage member `{}` of component `{}` collides with the storage member \
+                     `{}` of component `{}`. Rename one of them or add \
                      `#[allow({ALLOW_COLLIDING_PATHS_ATTR})]` if intentional.",
                     path_to_member.join(db, "."),
-                    existing_path.join(db, ".")
-                ),
-            ));
-        } else {
-            self.name_to_paths.insert(member_name, path_to_member);
-        }
+                    component.full_path(db),
+                    existing_path.join(db, "."),
+                    existing_component.full_path(db)
+                )
+            }
+            _ => format!(
+                "The path `{}` collides with existing path `{}`. Fix or add \
+                 `#[allow({ALLOW_COLLIDING_PATHS_ATTR})]` if intentional.",
+                path_to_member.join(db, "."),
+                existing_path.join(db, ".")
+            ),
+        };
+        diagnostics.push(PluginDiagnostic::warning(pointer_to_code, message));
     }
 }
 
@@ -288,6 +517,13 @@ fn member_analyze<'db>(
         user_data_path.pop();
         return;
     };
+    let outer_component = paths_data.current_component;
+    let member_module = member_struct.struct_id(db).parent_module(db);
+    if config.kind == StorageMemberKind::SubStorage
+        && member_module.has_attr(db, COMPONENT_ATTR).unwrap_or_default()
+    {
+        paths_data.current_component = Some(member_module);
+    }
     for (inner_member_name, inner_member) in
         db.struct_members(member_struct.long(db).struct_id).unwrap().iter()
     {
@@ -301,6 +537,7 @@ fn member_analyze<'db>(
             diagnostics,
         );
     }
+    paths_data.current_component = outer_component;
     user_data_path.pop();
 }
 
@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use cairo_lang_compiler::db::RootDatabase;
-use cairo_lang_compiler::project::setup_project;
+use cairo_lang_compiler::project::{setup_project, update_crate_cfgs};
 use cairo_lang_compiler::{CompilerConfig, ensure_diagnostics};
 use cairo_lang_defs::ids::TopLevelLanguageElementId;
 use cairo_lang_diagnostics::ToOption;
@@ -54,6 +54,7 @@ pub fn compile_path(
         .build()?;
 
     let main_crate_inputs = setup_project(&mut db, path)?;
+    update_crate_cfgs(&mut db, &compiler_config.crate_cfgs)?;
     compiler_config.diagnostics_reporter =
         compiler_config.diagnostics_reporter.with_crates(&main_crate_inputs);
     let main_crate_ids = CrateInput::into_crate_ids(&db, main_crate_inputs);
@@ -14,6 +14,8 @@ pub mod compile;
 pub mod contract;
 pub mod inline_macros;
 pub mod plugin;
+pub mod storage_layout;
+pub mod syscall_summary;
 
 /// Gets the suite of plugins for compilation with Starknet.
 pub fn starknet_plugin_suite() -> PluginSuite {
@@ -25,7 +27,8 @@ pub fn starknet_plugin_suite() -> PluginSuite {
         .add_inline_macro_plugin::<inline_macros::get_dep_component::GetDepComponentMacro>()
         .add_inline_macro_plugin::<inline_macros::get_dep_component::GetDepComponentMutMacro>()
         .add_analyzer_plugin::<analyzer::ABIAnalyzer>()
-        .add_analyzer_plugin::<analyzer::StorageAnalyzer>();
+        .add_analyzer_plugin::<analyzer::StorageAnalyzer>()
+        .add_analyzer_plugin::<analyzer::ComponentsAnalyzer>();
     suite
 }
 
@@ -0,0 +1,386 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use cairo_lang_compiler::CompilerConfig;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::project::{setup_project, update_crate_cfgs};
+use cairo_lang_defs::ids::{ModuleItemId, TopLevelLanguageElementId};
+use cairo_lang_filesystem::ids::{CrateInput, SmolStrId};
+use cairo_lang_semantic::items::enm::SemanticEnumEx;
+use cairo_lang_semantic::items::module::ModuleSemantic;
+use cairo_lang_semantic::items::structure::{Member, StructSemantic};
+use cairo_lang_semantic::{ConcreteTypeId, GenericArgumentId, TypeId, TypeLongId};
+use cairo_lang_starknet_classes::keccak::starknet_keccak;
+use cairo_lang_syntax::node::TypedStablePtr;
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_utils::bigint::BigUintAsHex;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use num_bigint::BigUint;
+use salsa::Database;
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt as Felt252;
+use starknet_types_core::hash::{Pedersen, StarkHash};
+
+use crate::contract::{ContractDeclaration, find_contracts};
+use crate::plugin::consts::{STORAGE_NODE_ATTR, STORAGE_STRUCT_NAME, STORE_TRAIT};
+use crate::plugin::storage_interfaces::{StorageMemberKind, get_member_storage_config};
+use crate::plugin::utils::has_derive;
+use crate::starknet_plugin_suite;
+
+#[cfg(test)]
+#[path = "storage_layout_test.rs"]
+mod test;
+
+/// The version of the [StorageLayout] format.
+pub const STORAGE_LAYOUT_VERSION: u32 = 1;
+
+const MAP_TYPE_PATH: &str = "core::starknet::storage::map::Map";
+const VEC_TYPE_PATH: &str = "core::starknet::storage::vec::Vec";
+
+/// The storage layout of a contract, as derived by the Starknet storage plugin.
+///
+/// Addresses are described by their derivation, where `sn_keccak` is the Starknet keccak of the
+/// storage name of a member (its name, unless renamed) and `h` is the Pedersen hash. Hashing with a
+/// Map key made of several felts hashes its felts one after the other. Map keys and Vec indices
+/// are named `k<n>` and `i<n>`, where `n` is the number of keys and indices preceding them in the
+/// path.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageLayout {
+    /// The version of the format, see [STORAGE_LAYOUT_VERSION].
+    pub version: u32,
+    /// The full path of the contract module.
+    pub contract: String,
+    /// The members of the contract storage, with the members of flat and substorage members
+    /// inlined.
+    pub members: Vec<StorageMemberLayout>,
+}
+
+/// The layout of a single storage member.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageMemberLayout {
+    /// The path of the member in the user code, e.g. `ownable.owner` or `balances[k0]`.
+    pub path: String,
+    /// The type of the member.
+    pub ty: String,
+    /// The storage base address of the member, if it does not depend on Map keys or Vec indices.
+    pub address: Option<BigUintAsHex>,
+    /// The derivation of the storage base address of the member.
+    pub derivation: String,
+    /// The structure of the member.
+    #[serde(flatten)]
+    pub node: StorageNodeLayout,
+}
+
+/// The structure of a storage member.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StorageNodeLayout {
+    /// A value stored with `Store`, in consecutive slots starting at the base address.
+    Value {
+        /// The number of slots of the value, if known without evaluating its `Store` impl.
+        size: Option<usize>,
+        /// The members of a struct deriving `Store`, in storage order.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        fields: Vec<StoredFieldLayout>,
+    },
+    /// A `Map`, with each entry at `h(address, key)`.
+    Map {
+        /// The type of the keys.
+        key: String,
+        /// The layout of the entry of key `k<n>`.
+        value: Box<StorageMemberLayout>,
+    },
+    /// A `Vec`, with its length at the base address and each element at `h(address, index)`.
+    Vec {
+        /// The layout of the element of index `i<n>`.
+        element: Box<StorageMemberLayout>,
+    },
+    /// A storage node, with each member at `h(address, sn_keccak(member))`.
+    Node {
+        /// The members of the node.
+        members: Vec<StorageMemberLayout>,
+    },
+}
+
+/// A member of a struct value deriving `Store`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredFieldLayout {
+    /// The name of the member.
+    pub name: String,
+    /// The type of the member.
+    pub ty: String,
+    /// The offset of the member from the base address, if the sizes of the previous members are
+    /// known.
+    pub offset: Option<usize>,
+}
+
+/// Computes the storage layout of the contract given by the path.
+/// Errors if there is ambiguity.
+pub fn storage_layout_path(
+    path: &Path,
+    contract_path: Option<&str>,
+    mut compiler_config: CompilerConfig<'_>,
+) -> Result<StorageLayout> {
+    let mut db = RootDatabase::builder()
+        .detect_corelib()
+        .with_default_plugin_suite(starknet_plugin_suite())
+        .build()?;
+
+    let main_crate_inputs = setup_project(&mut db, path)?;
+    update_crate_cfgs(&mut db, &compiler_config.crate_cfgs)?;
+    compiler_config.diagnostics_reporter =
+        compiler_config.diagnostics_reporter.with_crates(&main_crate_inputs);
+    compiler_config.diagnostics_reporter.ensure(&db)?;
+    let main_crate_ids = CrateInput::into_crate_ids(&db, main_crate_inputs);
+    let contracts = find_contracts(&db, &main_crate_ids);
+    let name = |contract: &ContractDeclaration<'_>| contract.submodule_id.full_path(&db);
+    let contract = match contract_path {
+        Some(contract_path) => contracts
+            .iter()
+            .find(|contract| name(contract) == contract_path)
+            .with_context(|| format!("No contract matching `--contract-path={contract_path}`."))?,
+        None => contracts.iter().exactly_one().map_err(|_| {
+            anyhow::anyhow!("Expected exactly one contract, use --contract-path to specify which.")
+        })?,
+    };
+    storage_layout(&db, contract)
+}
+
+/// Computes the storage layout of a contract.
+pub fn storage_layout<'db>(
+    db: &'db dyn Database,
+    contract: &ContractDeclaration<'db>,
+) -> Result<StorageLayout> {
+    let Ok(Some(ModuleItemId::Struct(storage_struct))) =
+        db.module_item_by_name(contract.module_id(), SmolStrId::from(db, STORAGE_STRUCT_NAME))
+    else {
+        anyhow::bail!("Contracts must have a Storage struct.");
+    };
+    let members = db.struct_members(storage_struct).ok().context("Invalid Storage struct.")?;
+    let mut layouts = vec![];
+    add_members_layouts(db, members, &NodeBase::Contract, "", 0, &mut layouts);
+    Ok(StorageLayout {
+        version: STORAGE_LAYOUT_VERSION,
+        contract: contract.submodule_id.full_path(db),
+        members: layouts,
+    })
+}
+
+/// The location the addresses of the members of a storage node are derived from.
+enum NodeBase {
+    /// The contract storage, where each member is at `sn_keccak(member)`.
+    Contract,
+    /// A storage node, where each member is at `h(address, sn_keccak(member))`.
+    Node {
+        /// The hash state of the node, if it does not depend on Map keys or Vec indices.
+        hash: Option<Felt252>,
+        /// The derivation of the hash state of the node.
+        derivation: String,
+    },
+}
+
+/// Adds the layouts of the `members` of a storage node at `base` to `layouts`.
+/// Members of flat and substorage members are added in place of the member, as they share the base
+/// of the node.
+fn add_members_layouts<'db>(
+    db: &'db dyn Database,
+    members: &OrderedHashMap<SmolStrId<'db>, Member<'db>>,
+    base: &NodeBase,
+    path_prefix: &str,
+    n_dynamic: usize,
+    layouts: &mut Vec<StorageMemberLayout>,
+) {
+    for (name, member) in members.iter() {
+        let name = name.long(db);
+        let path = format!("{path_prefix}{name}");
+        // Ignoring diagnostics as these would have been reported by the plugin.
+        let config =
+            get_member_storage_config(db, &member.id.stable_ptr(db).lookup(db), &mut vec![]);
+        if config.kind != StorageMemberKind::Basic
+            && let TypeLongId::Concrete(ConcreteTypeId::Struct(concrete_struct)) =
+                member.ty.long(db)
+            && let Ok(inner_members) = db.concrete_struct_members(*concrete_struct)
+        {
+            add_members_layouts(db, inner_members, base, &format!("{path}."), n_dynamic, layouts);
+            continue;
+        }
+        let storage_name = config.rename.as_deref().unwrap_or(name);
+        let selector = starknet_keccak(storage_name.as_bytes());
+        let selector_derivation = format!("sn_keccak(\"{storage_name}\")");
+        let (hash, derivation) = match base {
+            NodeBase::Contract => (Some(Felt252::from(selector)), selector_derivation),
+            NodeBase::Node { hash, derivation } => (
+                hash.map(|hash| Pedersen::hash(&hash, &Felt252::from(selector))),
+                format!("h({derivation}, {selector_derivation})"),
+            ),
+        };
+        layouts.push(member_layout(db, path, member.ty, hash, derivation, n_dynamic));
+    }
+}
+
+/// Returns the layout of a member of type `ty`, based at the hash state `hash`.
+fn member_layout<'db>(
+    db: &'db dyn Database,
+    path: String,
+    ty: TypeId<'db>,
+    hash: Option<Felt252>,
+    derivation: String,
+    n_dynamic: usize,
+) -> StorageMemberLayout {
+    let node = match ty.long(db) {
+        TypeLongId::Concrete(ConcreteTypeId::Struct(concrete_struct)) => {
+            let struct_id = concrete_struct.struct_id(db);
+            let generic_args = &concrete_struct.long(db).generic_args;
+            match (struct_id.full_path(db).as_str(), generic_args.as_slice()) {
+                (
+                    MAP_TYPE_PATH,
+                    [GenericArgumentId::Type(key_ty), GenericArgumentId::Type(value_ty)],
+                ) => {
+                    let key = format!("k{n_dynamic}");
+                    StorageNodeLayout::Map {
+                        key: key_ty.format(db),
+                        value: Box::new(member_layout(
+                            db,
+                            format!("{path}[{key}]"),
+                            *value_ty,
+                            None,
+                            format!("h({derivation}, {key})"),
+                            n_dynamic + 1,
+                        )),
+                    }
+                }
+                (VEC_TYPE_PATH, [GenericArgumentId::Type(element_ty)]) => {
+                    let index = format!("i{n_dynamic}");
+                    StorageNodeLayout::Vec {
+                        element: Box::new(member_layout(
+                            db,
+                            format!("{path}[{index}]"),
+                            *element_ty,
+                            None,
+                            format!("h({derivation}, {index})"),
+                            n_dynamic + 1,
+                        )),
+                    }
+                }
+                _ if struct_id.stable_ptr(db).lookup(db).has_attr(db, STORAGE_NODE_ATTR) => {
+                    let mut members = vec![];
+                    if let Ok(node_members) = db.concrete_struct_members(*concrete_struct) {
+                        let base = NodeBase::Node { hash, derivation: derivation.clone() };
+                        add_members_layouts(
+                            db,
+                            node_members,
+                            &base,
+                            &format!("{path}."),
+                            n_dynamic,
+                            &mut members,
+                        );
+                    }
+                    StorageNodeLayout::Node { members }
+                }
+                _ => value_layout(db, ty),
+            }
+        }
+        _ => value_layout(db, ty),
+    };
+    StorageMemberLayout {
+        path,
+        ty: ty.format(db),
+        address: hash.map(|hash| BigUintAsHex { value: storage_address(hash) }),
+        derivation,
+        node,
+    }
+}
+
+/// Returns the layout of a value of type `ty` stored with `Store`.
+fn value_layout<'db>(db: &'db dyn Database, ty: TypeId<'db>) -> StorageNodeLayout {
+    let mut fields = vec![];
+    if let TypeLongId::Concrete(ConcreteTypeId::Struct(concrete_struct)) = ty.long(db)
+        && has_derive(&concrete_struct.struct_id(db).stable_ptr(db).lookup(db), db, STORE_TRAIT)
+            .is_some()
+        && let Ok(members) = db.concrete_struct_members(*concrete_struct)
+    {
+        let mut offset = Some(0);
+        for (name, member) in members.iter() {
+            fields.push(StoredFieldLayout {
+                name: name.long(db).to_string(),
+                ty: member.ty.format(db),
+                offset,
+            });
+            offset = offset.zip(store_size(db, member.ty)).map(|(offset, size)| offset + size);
+        }
+    }
+    StorageNodeLayout::Value { size: store_size(db, ty), fields }
+}
+
+/// Returns the number of storage slots of a value of type `ty`, for the `Store` impls of the
+/// corelib and for types deriving `Store`.
+fn store_size<'db>(db: &'db dyn Database, ty: TypeId<'db>) -> Option<usize> {
+    let sum_sizes =
+        |types: &mut dyn Iterator<Item = TypeId<'db>>| types.map(|ty| store_size(db, ty)).sum();
+    match ty.long(db) {
+        TypeLongId::Tuple(types) => sum_sizes(&mut types.iter().copied()),
+        TypeLongId::Concrete(concrete) => {
+            let full_path = concrete.generic_type(db).full_path(db);
+            match full_path.as_str() {
+                "core::felt252"
+                | "core::bool"
+                | "core::integer::u8"
+                | "core::integer::u16"
+                | "core::integer::u32"
+                | "core::integer::u64"
+                | "core::integer::u128"
+                | "core::integer::i8"
+                | "core::integer::i16"
+                | "core::integer::i32"
+                | "core::integer::i64"
+                | "core::integer::i128"
+                | "core::bytes_31::bytes31"
+                | "core::starknet::contract_address::ContractAddress"
+                | "core::starknet::class_hash::ClassHash"
+                | "core::starknet::storage_access::StorageAddress"
+                // Only the length is at the base address, the data is at hashed addresses.
+                | "core::byte_array::ByteArray" => Some(1),
+                "core::integer::u256" => Some(2),
+                _ => match concrete {
+                    ConcreteTypeId::Struct(concrete_struct) => {
+                        require_store_derive(db, concrete_struct.struct_id(db).stable_ptr(db))?;
+                        let members = db.concrete_struct_members(*concrete_struct).ok()?;
+                        sum_sizes(&mut members.values().map(|member| member.ty))
+                    }
+                    ConcreteTypeId::Enum(concrete_enum) => {
+                        let is_corelib_enum =
+                            matches!(full_path.as_str(), "core::option::Option" | "core::result::Result");
+                        if !is_corelib_enum {
+                            require_store_derive(db, concrete_enum.enum_id(db).stable_ptr(db))?;
+                        }
+                        let variants = db.concrete_enum_variants(*concrete_enum).ok()?;
+                        let sizes: Option<Vec<usize>> =
+                            variants.iter().map(|variant| store_size(db, variant.ty)).collect();
+                        Some(1 + sizes?.into_iter().max().unwrap_or_default())
+                    }
+                    ConcreteTypeId::Extern(_) => None,
+                },
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns `Some` if the item at `stable_ptr` derives `Store`.
+fn require_store_derive<'db, Ptr: TypedStablePtr<'db>>(
+    db: &'db dyn Database,
+    stable_ptr: Ptr,
+) -> Option<()>
+where
+    Ptr::SyntaxNode: QueryAttrs<'db>,
+{
+    has_derive(&stable_ptr.lookup(db), db, STORE_TRAIT).map(|_| ())
+}
+
+/// Returns the storage base address of a hash state, as computed by
+/// `storage_base_address_from_felt252`.
+fn storage_address(hash: Felt252) -> BigUint {
+    let bound = (BigUint::from(1u32) << 251) - BigUint::from(256u32);
+    hash.to_biguint() % bound
+}
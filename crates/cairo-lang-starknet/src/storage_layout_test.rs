@@ -0,0 +1,29 @@
+use cairo_lang_defs::ids::TopLevelLanguageElementId;
+use cairo_lang_test_utils::compare_contents_or_fix_with_path;
+use test_case::test_case;
+
+use super::storage_layout;
+use crate::contract::find_contracts;
+use crate::test_utils::{get_example_file_path, with_contracts_crate};
+
+/// Tests that the storage layout of a contract in the contracts crate is the same as in
+/// <test_case>.storage_layout.json.
+#[test_case("storage_accesses::storage_accesses")]
+#[test_case("with_ownable_mini::ownable_mini_contract")]
+#[test_case("multi_component::contract_with_4_components")]
+fn test_storage_layout(example_contract_path: &str) {
+    let contract_path = format!("cairo_level_tests::contracts::{example_contract_path}");
+    let layout = with_contracts_crate(|db, main_crate_ids| {
+        let contracts = find_contracts(db, &main_crate_ids);
+        let contract = contracts
+            .iter()
+            .find(|contract| contract.submodule_id.full_path(db) == contract_path)
+            .expect("Contract not found.");
+        storage_layout(db, contract).unwrap()
+    });
+    let example_file_name = example_contract_path.replace("::", "__");
+    compare_contents_or_fix_with_path(
+        &get_example_file_path(format!("{example_file_name}.storage_layout.json").as_str()),
+        serde_json::to_string_pretty(&layout).unwrap() + "\n",
+    );
+}
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use cairo_lang_compiler::CompilerConfig;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::project::{setup_project, update_crate_cfgs};
+use cairo_lang_defs::ids::{LanguageElementId, NamedLanguageElementId, TopLevelLanguageElementId};
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::ids::CrateInput;
+use cairo_lang_lowering::LoweringStage;
+use cairo_lang_lowering::graph_algorithms::reachable_externs::reachable_extern_calls;
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_starknet_classes::class_size::EntryPointType;
+use itertools::{Itertools, chain};
+use salsa::Database;
+use serde::{Deserialize, Serialize};
+
+use crate::contract::{ContractDeclaration, find_contracts, get_contract_abi_functions};
+use crate::plugin::consts::{EXTERNAL_MODULE, L1_HANDLER_MODULE};
+use crate::starknet_plugin_suite;
+
+#[cfg(test)]
+#[path = "syscall_summary_test.rs"]
+mod test;
+
+/// The module of the extern functions of the Starknet syscalls.
+const SYSCALLS_MODULE_PATH: &str = "core::starknet::syscalls";
+
+/// The syscalls statically reachable from the entry points of a contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractSyscalls {
+    /// The full path of the contract module.
+    pub contract: String,
+    /// The syscalls of the external and L1 handler entry points, in declaration order.
+    pub entry_points: Vec<EntryPointSyscalls>,
+}
+
+/// The syscalls statically reachable from a single entry point.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryPointSyscalls {
+    /// The type of the entry point.
+    pub entry_point_type: EntryPointType,
+    /// The name of the entry point.
+    pub name: String,
+    /// The number of call sites of each syscall in the functions reachable from the entry point,
+    /// by the name of the syscall extern function, e.g. `storage_read_syscall`. Functions
+    /// reachable through several call paths are counted once.
+    pub syscalls: BTreeMap<String, usize>,
+}
+
+/// Computes the syscalls of the entry points of the contract given by the path.
+/// Errors if there is ambiguity.
+pub fn syscall_summary_path(
+    path: &Path,
+    contract_path: Option<&str>,
+    mut compiler_config: CompilerConfig<'_>,
+) -> Result<ContractSyscalls> {
+    let mut db = RootDatabase::builder()
+        .detect_corelib()
+        .with_default_plugin_suite(starknet_plugin_suite())
+        .build()?;
+
+    let main_crate_inputs = setup_project(&mut db, path)?;
+    update_crate_cfgs(&mut db, &compiler_config.crate_cfgs)?;
+    compiler_config.diagnostics_reporter =
+        compiler_config.diagnostics_reporter.with_crates(&main_crate_inputs);
+    compiler_config.diagnostics_reporter.ensure(&db)?;
+    let main_crate_ids = CrateInput::into_crate_ids(&db, main_crate_inputs);
+    let contracts = find_contracts(&db, &main_crate_ids);
+    let name = |contract: &ContractDeclaration<'_>| contract.submodule_id.full_path(&db);
+    let contract = match contract_path {
+        Some(contract_path) => contracts
+            .iter()
+            .find(|contract| name(contract) == contract_path)
+            .with_context(|| format!("No contract matching `--contract-path={contract_path}`."))?,
+        None => contracts.iter().exactly_one().map_err(|_| {
+            anyhow::anyhow!("Expected exactly one contract, use --contract-path to specify which.")
+        })?,
+    };
+    syscall_summary(&db, contract)
+}
+
+/// Computes the syscalls statically reachable from the external and L1 handler entry points of a
+/// contract, using the call graph of the lowered functions.
+pub fn syscall_summary<'db>(
+    db: &'db dyn Database,
+    contract: &ContractDeclaration<'db>,
+) -> Result<ContractSyscalls> {
+    let entry_points = chain!(
+        get_contract_abi_functions(db, contract, EXTERNAL_MODULE)?
+            .into_iter()
+            .map(|function| (EntryPointType::External, function)),
+        get_contract_abi_functions(db, contract, L1_HANDLER_MODULE)?
+            .into_iter()
+            .map(|function| (EntryPointType::L1Handler, function)),
+    )
+    .map(|(entry_point_type, function)| {
+        let function_id = ConcreteFunctionWithBodyId::from_semantic(db, function.value);
+        let extern_calls = reachable_extern_calls(db, function_id, LoweringStage::Monomorphized)
+            .to_option()
+            .with_context(|| format!("Failed to lower entry point `{}`.", function.alias))?;
+        let syscalls = extern_calls
+            .into_iter()
+            .filter(|(extern_function, _)| {
+                extern_function.parent_module(db).full_path(db) == SYSCALLS_MODULE_PATH
+            })
+            .map(|(extern_function, count)| (extern_function.name(db).to_string(db), count))
+            .collect();
+        Ok(EntryPointSyscalls { entry_point_type, name: function.alias, syscalls })
+    })
+    .collect::<Result<_>>()?;
+    Ok(ContractSyscalls { contract: contract.submodule_id.full_path(db), entry_points })
+}
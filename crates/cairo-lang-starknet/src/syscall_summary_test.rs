@@ -0,0 +1,29 @@
+use cairo_lang_defs::ids::TopLevelLanguageElementId;
+use cairo_lang_test_utils::compare_contents_or_fix_with_path;
+use test_case::test_case;
+
+use super::syscall_summary;
+use crate::contract::find_contracts;
+use crate::test_utils::{get_example_file_path, with_contracts_crate};
+
+/// Tests that the syscall summary of a contract in the contracts crate is the same as in
+/// <test_case>.syscalls.json.
+#[test_case("hello_starknet::hello_starknet")]
+#[test_case("with_ownable::ownable_balance")]
+#[test_case("token_bridge::token_bridge")]
+fn test_syscall_summary(example_contract_path: &str) {
+    let contract_path = format!("cairo_level_tests::contracts::{example_contract_path}");
+    let summary = with_contracts_crate(|db, main_crate_ids| {
+        let contracts = find_contracts(db, &main_crate_ids);
+        let contract = contracts
+            .iter()
+            .find(|contract| contract.submodule_id.full_path(db) == contract_path)
+            .expect("Contract not found.");
+        syscall_summary(db, contract).unwrap()
+    });
+    let example_file_name = example_contract_path.replace("::", "__");
+    compare_contents_or_fix_with_path(
+        &get_example_file_path(format!("{example_file_name}.syscalls.json").as_str()),
+        serde_json::to_string_pretty(&summary).unwrap() + "\n",
+    );
+}
@@ -6,7 +6,7 @@ use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::ProjectConfig;
 use cairo_lang_filesystem::db::FilesGroup;
-use cairo_lang_filesystem::ids::Directory;
+use cairo_lang_filesystem::ids::{CrateId, Directory};
 use cairo_lang_starknet_classes::contract_class::ContractClass;
 use cairo_lang_test_utils::test_lock;
 use itertools::Itertools;
@@ -51,8 +51,10 @@ pub static SHARED_DB_WITH_CONTRACTS: LazyLock<Mutex<RootDatabase>> = LazyLock::n
     )
 });
 
-/// Returns the compiled test contract from the contracts crate, with replaced ids.
-pub fn get_test_contract(example_file_name: &str) -> ContractClass {
+/// Runs `f` with a snapshot of [SHARED_DB_WITH_CONTRACTS] and the crate ids of the contracts crate.
+pub fn with_contracts_crate<T>(
+    f: impl for<'db> FnOnce(&'db RootDatabase, Vec<CrateId<'db>>) -> T,
+) -> T {
     let locked_db = test_lock(&SHARED_DB_WITH_CONTRACTS);
     let db = locked_db.snapshot();
     drop(locked_db);
@@ -70,22 +72,22 @@ pub fn get_test_contract(example_file_name: &str) -> ContractClass {
              {contracts_crate:?}"
         );
     };
-    let main_crate_ids = vec![**contracts_crate_id];
-    let crate_input = contracts_crate_id.long(&db).clone().into_crate_input(&db);
-    let main_crate_inputs = vec![crate_input];
-    let diagnostics_reporter =
-        DiagnosticsReporter::default().with_crates(&main_crate_inputs).allow_warnings();
-    compile_contract_in_prepared_db(
-        &db,
-        Some(example_file_name),
-        main_crate_ids,
-        CompilerConfig {
-            replace_ids: true,
-            diagnostics_reporter,
-            add_statements_functions: false,
-            add_statements_code_locations: false,
-            add_functions_debug_info: false,
-        },
-    )
-    .expect("compile_path failed")
+    f(&db, vec![**contracts_crate_id])
+}
+
+/// Returns the compiled test contract from the contracts crate, with replaced ids.
+pub fn get_test_contract(example_file_name: &str) -> ContractClass {
+    with_contracts_crate(|db, main_crate_ids| {
+        let main_crate_inputs =
+            main_crate_ids.iter().map(|id| id.long(db).clone().into_crate_input(db)).collect_vec();
+        let diagnostics_reporter =
+            DiagnosticsReporter::default().with_crates(&main_crate_inputs).allow_warnings();
+        compile_contract_in_prepared_db(
+            db,
+            Some(example_file_name),
+            main_crate_ids,
+            CompilerConfig { replace_ids: true, diagnostics_reporter, ..CompilerConfig::default() },
+        )
+        .expect("compile_path failed")
+    })
 }
@@ -178,9 +178,15 @@ pub fn get_spec() -> Vec<Node> {
     .add_separated_list("MatchArms", "MatchArm", "TerminalComma")
     .add_struct(StructBuilder::new("MatchArm")
         .node("patterns", "PatternListOr")
+        .node("guard", "OptionMatchArmGuard")
         .node("arrow", "TerminalMatchArrow")
         .node("expression", "Expr")
     )
+    .add_struct(StructBuilder::new("MatchArmGuard")
+        .node("if_kw", "TerminalIf")
+        .node("condition", "Expr")
+    )
+    .add_option("MatchArmGuard")
     .add_struct(StructBuilder::new("ExprIf")
         .node("if_kw", "TerminalIf")
         .node("conditions", "ConditionListAnd")
@@ -304,8 +310,18 @@ pub fn get_spec() -> Vec<Node> {
         .node("Tuple")
         .node("Enum")
         .node("FixedSizeArray")
+        .node("Range")
         .node_with_explicit_kind("Path", "ExprPath")
     )
+    .add_struct(StructBuilder::new("PatternRange")
+        .node("start", "TerminalLiteralNumber")
+        .node("op", "PatternRangeOperator")
+        .node("end", "TerminalLiteralNumber")
+    )
+    .add_enum(EnumBuilder::new("PatternRangeOperator")
+        .node_with_explicit_kind("DotDot", "TerminalDotDot")
+        .node_with_explicit_kind("DotDotEq", "TerminalDotDotEq")
+    )
     .add_struct(StructBuilder::new("PatternIdentifier")
         .node("modifiers", "ModifierList")
         .key_node("name", "TerminalIdentifier")
@@ -600,6 +616,7 @@ pub fn get_spec() -> Vec<Node> {
         .node("type_kw", "TerminalType")
         .key_node("name", "TerminalIdentifier")
         .node("generic_params", "OptionWrappedGenericParamList")
+        .node("default", "OptionTraitItemDefault")
         .node("semicolon", "TerminalSemicolon")
     )
     .add_struct(StructBuilder::new("TraitItemConstant")
@@ -607,8 +624,15 @@ pub fn get_spec() -> Vec<Node> {
         .node("const_kw", "TerminalConst")
         .key_node("name", "TerminalIdentifier")
         .node("type_clause", "TypeClause")
+        .node("default", "OptionTraitItemDefault")
         .node("semicolon", "TerminalSemicolon")
     )
+    // The default of a trait type or constant - `= <Expr>`.
+    .add_struct(StructBuilder::new("TraitItemDefault")
+        .node("eq", "TerminalEq")
+        .node("value", "Expr")
+    )
+    .add_option("TraitItemDefault")
     .add_struct(StructBuilder::new("TraitItemImpl")
         .node("attributes" ,"AttributeList")
         .node("impl_kw", "TerminalImpl")
@@ -26,11 +26,21 @@ pub const INTERNAL_ATTR: &str = "internal";
 /// An attribute to allow code that would normally result in a warning.
 pub const ALLOW_ATTR: &str = "allow";
 
+/// An attribute to report the diagnostics of lints as warnings.
+pub const WARN_ATTR: &str = "warn";
+
+/// An attribute to report the diagnostics of lints as errors.
+pub const DENY_ATTR: &str = "deny";
+
 /// An attribute to represent the unused lint-group, which represents all the unused lints.
 /// An argument to the `allow` attribute that represents a lint-group which suppresses all `unused`
 /// lints.
 pub const UNUSED: &str = "unused";
 
+/// An argument to the `allow`, `warn` and `deny` attributes that represents a lint-group which
+/// configures all the warnings, e.g. `#[deny(warnings)]` reports all warnings as errors.
+pub const WARNINGS: &str = "warnings";
+
 /// An argument to the `allow` attribute that suppresses warnings for unused variables.
 /// Also included in the [`UNUSED`] lint group.
 pub const UNUSED_VARIABLES: &str = "unused_variables";
@@ -39,12 +49,24 @@ pub const UNUSED_VARIABLES: &str = "unused_variables";
 /// Also included in the [`UNUSED`] lint group.
 pub const UNUSED_IMPORTS: &str = "unused_imports";
 
+/// A lint for unhandled results of `#[must_use]` functions and types.
+/// Also included in the [`UNUSED`] lint group.
+pub const UNUSED_MUST_USE: &str = "unused_must_use";
+
+/// A lint for `pub` items which are not reachable from outside the crate, as a module containing
+/// them is not `pub`. Allowed by default.
+pub const UNREACHABLE_PUB: &str = "unreachable_pub";
+
 /// An attribute to allow additional attributes on an item.
 pub const ALLOW_ATTR_ATTR: &str = "allow_attr";
 
 /// An attribute to allow usage of a feature under a statement.
 pub const FEATURE_ATTR: &str = "feature";
 
+/// An attribute to exclude a function from the early insertion of `unsafe_panic` calls, when the
+/// `unsafe_panic` flag is set.
+pub const NO_EARLY_UNSAFE_PANIC_ATTR: &str = "no_early_unsafe_panic";
+
 /// An attribute to define the order of implicit arguments.
 pub const IMPLICIT_PRECEDENCE_ATTR: &str = "implicit_precedence";
 
@@ -55,6 +77,10 @@ pub const IMPLICIT_PRECEDENCE_ATTR: &str = "implicit_precedence";
 /// TODO(Gil): Remove this once `starknet` is removed from corelib.
 pub const STARKNET_INTERFACE_ATTR: &str = "starknet::interface";
 
+/// An attribute to mark an impl as a fallback impl, which is selected only if no other impl
+/// implements the concrete trait. Allows providing a blanket impl along with more specific impls.
+pub const FALLBACK_ATTR: &str = "fallback";
+
 /// An attribute to define a type as a phantom type, phantom types cannot be created at run time and
 /// are typically used for meta-programming.
 pub const PHANTOM_ATTR: &str = "phantom";
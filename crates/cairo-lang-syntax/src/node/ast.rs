@@ -4488,15 +4488,17 @@ pub struct MatchArm<'db> {
 }
 impl<'db> MatchArm<'db> {
     pub const INDEX_PATTERNS: usize = 0;
-    pub const INDEX_ARROW: usize = 1;
-    pub const INDEX_EXPRESSION: usize = 2;
+    pub const INDEX_GUARD: usize = 1;
+    pub const INDEX_ARROW: usize = 2;
+    pub const INDEX_EXPRESSION: usize = 3;
     pub fn new_green(
         db: &'db dyn Database,
         patterns: PatternListOrGreen<'db>,
+        guard: OptionMatchArmGuardGreen<'db>,
         arrow: TerminalMatchArrowGreen<'db>,
         expression: ExprGreen<'db>,
     ) -> MatchArmGreen<'db> {
-        let children = [patterns.0, arrow.0, expression.0];
+        let children = [patterns.0, guard.0, arrow.0, expression.0];
         let width = children.into_iter().map(|id: GreenId<'_>| id.long(db).width(db)).sum();
         MatchArmGreen(
             GreenNode {
@@ -4511,11 +4513,14 @@ impl<'db> MatchArm<'db> {
     pub fn patterns(&self, db: &'db dyn Database) -> PatternListOr<'db> {
         PatternListOr::from_syntax_node(db, self.node.get_children(db)[0])
     }
+    pub fn guard(&self, db: &'db dyn Database) -> OptionMatchArmGuard<'db> {
+        OptionMatchArmGuard::from_syntax_node(db, self.node.get_children(db)[1])
+    }
     pub fn arrow(&self, db: &'db dyn Database) -> TerminalMatchArrow<'db> {
-        TerminalMatchArrow::from_syntax_node(db, self.node.get_children(db)[1])
+        TerminalMatchArrow::from_syntax_node(db, self.node.get_children(db)[2])
     }
     pub fn expression(&self, db: &'db dyn Database) -> Expr<'db> {
-        Expr::from_syntax_node(db, self.node.get_children(db)[2])
+        Expr::from_syntax_node(db, self.node.get_children(db)[3])
     }
 }
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
@@ -4548,6 +4553,7 @@ impl<'db> TypedSyntaxNode<'db> for MatchArm<'db> {
                 details: GreenNodeDetails::Node {
                     children: [
                         PatternListOr::missing(db).0,
+                        OptionMatchArmGuard::missing(db).0,
                         TerminalMatchArrow::missing(db).0,
                         Expr::missing(db).0,
                     ]
@@ -4581,6 +4587,270 @@ impl<'db> TypedSyntaxNode<'db> for MatchArm<'db> {
     }
 }
 #[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
+pub struct MatchArmGuard<'db> {
+    node: SyntaxNode<'db>,
+}
+impl<'db> MatchArmGuard<'db> {
+    pub const INDEX_IF_KW: usize = 0;
+    pub const INDEX_CONDITION: usize = 1;
+    pub fn new_green(
+        db: &'db dyn Database,
+        if_kw: TerminalIfGreen<'db>,
+        condition: ExprGreen<'db>,
+    ) -> MatchArmGuardGreen<'db> {
+        let children = [if_kw.0, condition.0];
+        let width = children.into_iter().map(|id: GreenId<'_>| id.long(db).width(db)).sum();
+        MatchArmGuardGreen(
+            GreenNode {
+                kind: SyntaxKind::MatchArmGuard,
+                details: GreenNodeDetails::Node { children: children.into(), width },
+            }
+            .intern(db),
+        )
+    }
+}
+impl<'db> MatchArmGuard<'db> {
+    pub fn if_kw(&self, db: &'db dyn Database) -> TerminalIf<'db> {
+        TerminalIf::from_syntax_node(db, self.node.get_children(db)[0])
+    }
+    pub fn condition(&self, db: &'db dyn Database) -> Expr<'db> {
+        Expr::from_syntax_node(db, self.node.get_children(db)[1])
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
+pub struct MatchArmGuardPtr<'db>(pub SyntaxStablePtrId<'db>);
+impl<'db> MatchArmGuardPtr<'db> {}
+impl<'db> TypedStablePtr<'db> for MatchArmGuardPtr<'db> {
+    type SyntaxNode = MatchArmGuard<'db>;
+    fn untyped(self) -> SyntaxStablePtrId<'db> {
+        self.0
+    }
+    fn lookup(&self, db: &'db dyn Database) -> MatchArmGuard<'db> {
+        MatchArmGuard::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+impl<'db> From<MatchArmGuardPtr<'db>> for SyntaxStablePtrId<'db> {
+    fn from(ptr: MatchArmGuardPtr<'db>) -> Self {
+        ptr.untyped()
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update)]
+pub struct MatchArmGuardGreen<'db>(pub GreenId<'db>);
+impl<'db> TypedSyntaxNode<'db> for MatchArmGuard<'db> {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::MatchArmGuard);
+    type StablePtr = MatchArmGuardPtr<'db>;
+    type Green = MatchArmGuardGreen<'db>;
+    fn missing(db: &'db dyn Database) -> Self::Green {
+        MatchArmGuardGreen(
+            GreenNode {
+                kind: SyntaxKind::MatchArmGuard,
+                details: GreenNodeDetails::Node {
+                    children: [TerminalIf::missing(db).0, Expr::missing(db).0].into(),
+                    width: TextWidth::default(),
+                },
+            }
+            .intern(db),
+        )
+    }
+    fn from_syntax_node(db: &'db dyn Database, node: SyntaxNode<'db>) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::MatchArmGuard,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::MatchArmGuard
+        );
+        Self { node }
+    }
+    fn cast(db: &'db dyn Database, node: SyntaxNode<'db>) -> Option<Self> {
+        let kind = node.kind(db);
+        if kind == SyntaxKind::MatchArmGuard {
+            Some(Self::from_syntax_node(db, node))
+        } else {
+            None
+        }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode<'db> {
+        self.node
+    }
+    fn stable_ptr(&self, db: &'db dyn Database) -> Self::StablePtr {
+        MatchArmGuardPtr(self.node.stable_ptr(db))
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
+pub enum OptionMatchArmGuard<'db> {
+    Empty(OptionMatchArmGuardEmpty<'db>),
+    MatchArmGuard(MatchArmGuard<'db>),
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
+pub struct OptionMatchArmGuardPtr<'db>(pub SyntaxStablePtrId<'db>);
+impl<'db> TypedStablePtr<'db> for OptionMatchArmGuardPtr<'db> {
+    type SyntaxNode = OptionMatchArmGuard<'db>;
+    fn untyped(self) -> SyntaxStablePtrId<'db> {
+        self.0
+    }
+    fn lookup(&self, db: &'db dyn Database) -> Self::SyntaxNode {
+        OptionMatchArmGuard::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+impl<'db> From<OptionMatchArmGuardPtr<'db>> for SyntaxStablePtrId<'db> {
+    fn from(ptr: OptionMatchArmGuardPtr<'db>) -> Self {
+        ptr.untyped()
+    }
+}
+impl<'db> From<OptionMatchArmGuardEmptyPtr<'db>> for OptionMatchArmGuardPtr<'db> {
+    fn from(value: OptionMatchArmGuardEmptyPtr<'db>) -> Self {
+        Self(value.0)
+    }
+}
+impl<'db> From<MatchArmGuardPtr<'db>> for OptionMatchArmGuardPtr<'db> {
+    fn from(value: MatchArmGuardPtr<'db>) -> Self {
+        Self(value.0)
+    }
+}
+impl<'db> From<OptionMatchArmGuardEmptyGreen<'db>> for OptionMatchArmGuardGreen<'db> {
+    fn from(value: OptionMatchArmGuardEmptyGreen<'db>) -> Self {
+        Self(value.0)
+    }
+}
+impl<'db> From<MatchArmGuardGreen<'db>> for OptionMatchArmGuardGreen<'db> {
+    fn from(value: MatchArmGuardGreen<'db>) -> Self {
+        Self(value.0)
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update)]
+pub struct OptionMatchArmGuardGreen<'db>(pub GreenId<'db>);
+impl<'db> TypedSyntaxNode<'db> for OptionMatchArmGuard<'db> {
+    const OPTIONAL_KIND: Option<SyntaxKind> = None;
+    type StablePtr = OptionMatchArmGuardPtr<'db>;
+    type Green = OptionMatchArmGuardGreen<'db>;
+    fn missing(db: &'db dyn Database) -> Self::Green {
+        panic!("No missing variant.");
+    }
+    fn from_syntax_node(db: &'db dyn Database, node: SyntaxNode<'db>) -> Self {
+        let kind = node.kind(db);
+        match kind {
+            SyntaxKind::OptionMatchArmGuardEmpty => {
+                OptionMatchArmGuard::Empty(OptionMatchArmGuardEmpty::from_syntax_node(db, node))
+            }
+            SyntaxKind::MatchArmGuard => {
+                OptionMatchArmGuard::MatchArmGuard(MatchArmGuard::from_syntax_node(db, node))
+            }
+            _ => panic!(
+                "Unexpected syntax kind {:?} when constructing {}.",
+                kind, "OptionMatchArmGuard"
+            ),
+        }
+    }
+    fn cast(db: &'db dyn Database, node: SyntaxNode<'db>) -> Option<Self> {
+        let kind = node.kind(db);
+        match kind {
+            SyntaxKind::OptionMatchArmGuardEmpty => Some(OptionMatchArmGuard::Empty(
+                OptionMatchArmGuardEmpty::from_syntax_node(db, node),
+            )),
+            SyntaxKind::MatchArmGuard => {
+                Some(OptionMatchArmGuard::MatchArmGuard(MatchArmGuard::from_syntax_node(db, node)))
+            }
+            _ => None,
+        }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode<'db> {
+        match self {
+            OptionMatchArmGuard::Empty(x) => x.as_syntax_node(),
+            OptionMatchArmGuard::MatchArmGuard(x) => x.as_syntax_node(),
+        }
+    }
+    fn stable_ptr(&self, db: &'db dyn Database) -> Self::StablePtr {
+        OptionMatchArmGuardPtr(self.as_syntax_node().stable_ptr(db))
+    }
+}
+impl<'db> OptionMatchArmGuard<'db> {
+    /// Checks if a kind of a variant of [OptionMatchArmGuard].
+    pub fn is_variant(kind: SyntaxKind) -> bool {
+        matches!(kind, SyntaxKind::OptionMatchArmGuardEmpty | SyntaxKind::MatchArmGuard)
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
+pub struct OptionMatchArmGuardEmpty<'db> {
+    node: SyntaxNode<'db>,
+}
+impl<'db> OptionMatchArmGuardEmpty<'db> {
+    pub fn new_green(db: &'db dyn Database) -> OptionMatchArmGuardEmptyGreen<'db> {
+        let children = [];
+        let width = children.into_iter().map(|id: GreenId<'_>| id.long(db).width(db)).sum();
+        OptionMatchArmGuardEmptyGreen(
+            GreenNode {
+                kind: SyntaxKind::OptionMatchArmGuardEmpty,
+                details: GreenNodeDetails::Node { children: children.into(), width },
+            }
+            .intern(db),
+        )
+    }
+}
+impl<'db> OptionMatchArmGuardEmpty<'db> {}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
+pub struct OptionMatchArmGuardEmptyPtr<'db>(pub SyntaxStablePtrId<'db>);
+impl<'db> OptionMatchArmGuardEmptyPtr<'db> {}
+impl<'db> TypedStablePtr<'db> for OptionMatchArmGuardEmptyPtr<'db> {
+    type SyntaxNode = OptionMatchArmGuardEmpty<'db>;
+    fn untyped(self) -> SyntaxStablePtrId<'db> {
+        self.0
+    }
+    fn lookup(&self, db: &'db dyn Database) -> OptionMatchArmGuardEmpty<'db> {
+        OptionMatchArmGuardEmpty::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+impl<'db> From<OptionMatchArmGuardEmptyPtr<'db>> for SyntaxStablePtrId<'db> {
+    fn from(ptr: OptionMatchArmGuardEmptyPtr<'db>) -> Self {
+        ptr.untyped()
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update)]
+pub struct OptionMatchArmGuardEmptyGreen<'db>(pub GreenId<'db>);
+impl<'db> TypedSyntaxNode<'db> for OptionMatchArmGuardEmpty<'db> {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::OptionMatchArmGuardEmpty);
+    type StablePtr = OptionMatchArmGuardEmptyPtr<'db>;
+    type Green = OptionMatchArmGuardEmptyGreen<'db>;
+    fn missing(db: &'db dyn Database) -> Self::Green {
+        OptionMatchArmGuardEmptyGreen(
+            GreenNode {
+                kind: SyntaxKind::OptionMatchArmGuardEmpty,
+                details: GreenNodeDetails::Node {
+                    children: [].into(),
+                    width: TextWidth::default(),
+                },
+            }
+            .intern(db),
+        )
+    }
+    fn from_syntax_node(db: &'db dyn Database, node: SyntaxNode<'db>) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::OptionMatchArmGuardEmpty,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::OptionMatchArmGuardEmpty
+        );
+        Self { node }
+    }
+    fn cast(db: &'db dyn Database, node: SyntaxNode<'db>) -> Option<Self> {
+        let kind = node.kind(db);
+        if kind == SyntaxKind::OptionMatchArmGuardEmpty {
+            Some(Self::from_syntax_node(db, node))
+        } else {
+            None
+        }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode<'db> {
+        self.node
+    }
+    fn stable_ptr(&self, db: &'db dyn Database) -> Self::StablePtr {
+        OptionMatchArmGuardEmptyPtr(self.node.stable_ptr(db))
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
 pub struct ExprIf<'db> {
     node: SyntaxNode<'db>,
 }
@@ -7553,6 +7823,7 @@ pub enum Pattern<'db> {
     Tuple(PatternTuple<'db>),
     Enum(PatternEnum<'db>),
     FixedSizeArray(PatternFixedSizeArray<'db>),
+    Range(PatternRange<'db>),
     Path(ExprPath<'db>),
 }
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
@@ -7626,6 +7897,11 @@ impl<'db> From<PatternFixedSizeArrayPtr<'db>> for PatternPtr<'db> {
         Self(value.0)
     }
 }
+impl<'db> From<PatternRangePtr<'db>> for PatternPtr<'db> {
+    fn from(value: PatternRangePtr<'db>) -> Self {
+        Self(value.0)
+    }
+}
 impl<'db> From<ExprPathPtr<'db>> for PatternPtr<'db> {
     fn from(value: ExprPathPtr<'db>) -> Self {
         Self(value.0)
@@ -7686,6 +7962,11 @@ impl<'db> From<PatternFixedSizeArrayGreen<'db>> for PatternGreen<'db> {
         Self(value.0)
     }
 }
+impl<'db> From<PatternRangeGreen<'db>> for PatternGreen<'db> {
+    fn from(value: PatternRangeGreen<'db>) -> Self {
+        Self(value.0)
+    }
+}
 impl<'db> From<ExprPathGreen<'db>> for PatternGreen<'db> {
     fn from(value: ExprPathGreen<'db>) -> Self {
         Self(value.0)
@@ -7726,6 +8007,7 @@ impl<'db> TypedSyntaxNode<'db> for Pattern<'db> {
             SyntaxKind::PatternFixedSizeArray => {
                 Pattern::FixedSizeArray(PatternFixedSizeArray::from_syntax_node(db, node))
             }
+            SyntaxKind::PatternRange => Pattern::Range(PatternRange::from_syntax_node(db, node)),
             SyntaxKind::ExprPath => Pattern::Path(ExprPath::from_syntax_node(db, node)),
             _ => panic!("Unexpected syntax kind {:?} when constructing {}.", kind, "Pattern"),
         }
@@ -7764,6 +8046,9 @@ impl<'db> TypedSyntaxNode<'db> for Pattern<'db> {
             SyntaxKind::PatternFixedSizeArray => {
                 Some(Pattern::FixedSizeArray(PatternFixedSizeArray::from_syntax_node(db, node)))
             }
+            SyntaxKind::PatternRange => {
+                Some(Pattern::Range(PatternRange::from_syntax_node(db, node)))
+            }
             SyntaxKind::ExprPath => Some(Pattern::Path(ExprPath::from_syntax_node(db, node))),
             _ => None,
         }
@@ -7781,6 +8066,7 @@ impl<'db> TypedSyntaxNode<'db> for Pattern<'db> {
             Pattern::Tuple(x) => x.as_syntax_node(),
             Pattern::Enum(x) => x.as_syntax_node(),
             Pattern::FixedSizeArray(x) => x.as_syntax_node(),
+            Pattern::Range(x) => x.as_syntax_node(),
             Pattern::Path(x) => x.as_syntax_node(),
         }
     }
@@ -7804,11 +8090,203 @@ impl<'db> Pattern<'db> {
                 | SyntaxKind::PatternTuple
                 | SyntaxKind::PatternEnum
                 | SyntaxKind::PatternFixedSizeArray
+                | SyntaxKind::PatternRange
                 | SyntaxKind::ExprPath
         )
     }
 }
 #[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
+pub struct PatternRange<'db> {
+    node: SyntaxNode<'db>,
+}
+impl<'db> PatternRange<'db> {
+    pub const INDEX_START: usize = 0;
+    pub const INDEX_OP: usize = 1;
+    pub const INDEX_END: usize = 2;
+    pub fn new_green(
+        db: &'db dyn Database,
+        start: TerminalLiteralNumberGreen<'db>,
+        op: PatternRangeOperatorGreen<'db>,
+        end: TerminalLiteralNumberGreen<'db>,
+    ) -> PatternRangeGreen<'db> {
+        let children = [start.0, op.0, end.0];
+        let width = children.into_iter().map(|id: GreenId<'_>| id.long(db).width(db)).sum();
+        PatternRangeGreen(
+            GreenNode {
+                kind: SyntaxKind::PatternRange,
+                details: GreenNodeDetails::Node { children: children.into(), width },
+            }
+            .intern(db),
+        )
+    }
+}
+impl<'db> PatternRange<'db> {
+    pub fn start(&self, db: &'db dyn Database) -> TerminalLiteralNumber<'db> {
+        TerminalLiteralNumber::from_syntax_node(db, self.node.get_children(db)[0])
+    }
+    pub fn op(&self, db: &'db dyn Database) -> PatternRangeOperator<'db> {
+        PatternRangeOperator::from_syntax_node(db, self.node.get_children(db)[1])
+    }
+    pub fn end(&self, db: &'db dyn Database) -> TerminalLiteralNumber<'db> {
+        TerminalLiteralNumber::from_syntax_node(db, self.node.get_children(db)[2])
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
+pub struct PatternRangePtr<'db>(pub SyntaxStablePtrId<'db>);
+impl<'db> PatternRangePtr<'db> {}
+impl<'db> TypedStablePtr<'db> for PatternRangePtr<'db> {
+    type SyntaxNode = PatternRange<'db>;
+    fn untyped(self) -> SyntaxStablePtrId<'db> {
+        self.0
+    }
+    fn lookup(&self, db: &'db dyn Database) -> PatternRange<'db> {
+        PatternRange::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+impl<'db> From<PatternRangePtr<'db>> for SyntaxStablePtrId<'db> {
+    fn from(ptr: PatternRangePtr<'db>) -> Self {
+        ptr.untyped()
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update)]
+pub struct PatternRangeGreen<'db>(pub GreenId<'db>);
+impl<'db> TypedSyntaxNode<'db> for PatternRange<'db> {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::PatternRange);
+    type StablePtr = PatternRangePtr<'db>;
+    type Green = PatternRangeGreen<'db>;
+    fn missing(db: &'db dyn Database) -> Self::Green {
+        PatternRangeGreen(
+            GreenNode {
+                kind: SyntaxKind::PatternRange,
+                details: GreenNodeDetails::Node {
+                    children: [
+                        TerminalLiteralNumber::missing(db).0,
+                        PatternRangeOperator::missing(db).0,
+                        TerminalLiteralNumber::missing(db).0,
+                    ]
+                    .into(),
+                    width: TextWidth::default(),
+                },
+            }
+            .intern(db),
+        )
+    }
+    fn from_syntax_node(db: &'db dyn Database, node: SyntaxNode<'db>) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::PatternRange,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::PatternRange
+        );
+        Self { node }
+    }
+    fn cast(db: &'db dyn Database, node: SyntaxNode<'db>) -> Option<Self> {
+        let kind = node.kind(db);
+        if kind == SyntaxKind::PatternRange { Some(Self::from_syntax_node(db, node)) } else { None }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode<'db> {
+        self.node
+    }
+    fn stable_ptr(&self, db: &'db dyn Database) -> Self::StablePtr {
+        PatternRangePtr(self.node.stable_ptr(db))
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
+pub enum PatternRangeOperator<'db> {
+    DotDot(TerminalDotDot<'db>),
+    DotDotEq(TerminalDotDotEq<'db>),
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
+pub struct PatternRangeOperatorPtr<'db>(pub SyntaxStablePtrId<'db>);
+impl<'db> TypedStablePtr<'db> for PatternRangeOperatorPtr<'db> {
+    type SyntaxNode = PatternRangeOperator<'db>;
+    fn untyped(self) -> SyntaxStablePtrId<'db> {
+        self.0
+    }
+    fn lookup(&self, db: &'db dyn Database) -> Self::SyntaxNode {
+        PatternRangeOperator::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+impl<'db> From<PatternRangeOperatorPtr<'db>> for SyntaxStablePtrId<'db> {
+    fn from(ptr: PatternRangeOperatorPtr<'db>) -> Self {
+        ptr.untyped()
+    }
+}
+impl<'db> From<TerminalDotDotPtr<'db>> for PatternRangeOperatorPtr<'db> {
+    fn from(value: TerminalDotDotPtr<'db>) -> Self {
+        Self(value.0)
+    }
+}
+impl<'db> From<TerminalDotDotEqPtr<'db>> for PatternRangeOperatorPtr<'db> {
+    fn from(value: TerminalDotDotEqPtr<'db>) -> Self {
+        Self(value.0)
+    }
+}
+impl<'db> From<TerminalDotDotGreen<'db>> for PatternRangeOperatorGreen<'db> {
+    fn from(value: TerminalDotDotGreen<'db>) -> Self {
+        Self(value.0)
+    }
+}
+impl<'db> From<TerminalDotDotEqGreen<'db>> for PatternRangeOperatorGreen<'db> {
+    fn from(value: TerminalDotDotEqGreen<'db>) -> Self {
+        Self(value.0)
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update)]
+pub struct PatternRangeOperatorGreen<'db>(pub GreenId<'db>);
+impl<'db> TypedSyntaxNode<'db> for PatternRangeOperator<'db> {
+    const OPTIONAL_KIND: Option<SyntaxKind> = None;
+    type StablePtr = PatternRangeOperatorPtr<'db>;
+    type Green = PatternRangeOperatorGreen<'db>;
+    fn missing(db: &'db dyn Database) -> Self::Green {
+        panic!("No missing variant.");
+    }
+    fn from_syntax_node(db: &'db dyn Database, node: SyntaxNode<'db>) -> Self {
+        let kind = node.kind(db);
+        match kind {
+            SyntaxKind::TerminalDotDot => {
+                PatternRangeOperator::DotDot(TerminalDotDot::from_syntax_node(db, node))
+            }
+            SyntaxKind::TerminalDotDotEq => {
+                PatternRangeOperator::DotDotEq(TerminalDotDotEq::from_syntax_node(db, node))
+            }
+            _ => panic!(
+                "Unexpected syntax kind {:?} when constructing {}.",
+                kind, "PatternRangeOperator"
+            ),
+        }
+    }
+    fn cast(db: &'db dyn Database, node: SyntaxNode<'db>) -> Option<Self> {
+        let kind = node.kind(db);
+        match kind {
+            SyntaxKind::TerminalDotDot => {
+                Some(PatternRangeOperator::DotDot(TerminalDotDot::from_syntax_node(db, node)))
+            }
+            SyntaxKind::TerminalDotDotEq => {
+                Some(PatternRangeOperator::DotDotEq(TerminalDotDotEq::from_syntax_node(db, node)))
+            }
+            _ => None,
+        }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode<'db> {
+        match self {
+            PatternRangeOperator::DotDot(x) => x.as_syntax_node(),
+            PatternRangeOperator::DotDotEq(x) => x.as_syntax_node(),
+        }
+    }
+    fn stable_ptr(&self, db: &'db dyn Database) -> Self::StablePtr {
+        PatternRangeOperatorPtr(self.as_syntax_node().stable_ptr(db))
+    }
+}
+impl<'db> PatternRangeOperator<'db> {
+    /// Checks if a kind of a variant of [PatternRangeOperator].
+    pub fn is_variant(kind: SyntaxKind) -> bool {
+        matches!(kind, SyntaxKind::TerminalDotDot | SyntaxKind::TerminalDotDotEq)
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
 pub struct PatternIdentifier<'db> {
     node: SyntaxNode<'db>,
 }
@@ -15824,16 +16302,18 @@ impl<'db> TraitItemType<'db> {
     pub const INDEX_TYPE_KW: usize = 1;
     pub const INDEX_NAME: usize = 2;
     pub const INDEX_GENERIC_PARAMS: usize = 3;
-    pub const INDEX_SEMICOLON: usize = 4;
+    pub const INDEX_DEFAULT: usize = 4;
+    pub const INDEX_SEMICOLON: usize = 5;
     pub fn new_green(
         db: &'db dyn Database,
         attributes: AttributeListGreen<'db>,
         type_kw: TerminalTypeGreen<'db>,
         name: TerminalIdentifierGreen<'db>,
         generic_params: OptionWrappedGenericParamListGreen<'db>,
+        default: OptionTraitItemDefaultGreen<'db>,
         semicolon: TerminalSemicolonGreen<'db>,
     ) -> TraitItemTypeGreen<'db> {
-        let children = [attributes.0, type_kw.0, name.0, generic_params.0, semicolon.0];
+        let children = [attributes.0, type_kw.0, name.0, generic_params.0, default.0, semicolon.0];
         let width = children.into_iter().map(|id: GreenId<'_>| id.long(db).width(db)).sum();
         TraitItemTypeGreen(
             GreenNode {
@@ -15857,8 +16337,11 @@ impl<'db> TraitItemType<'db> {
     pub fn generic_params(&self, db: &'db dyn Database) -> OptionWrappedGenericParamList<'db> {
         OptionWrappedGenericParamList::from_syntax_node(db, self.node.get_children(db)[3])
     }
+    pub fn default(&self, db: &'db dyn Database) -> OptionTraitItemDefault<'db> {
+        OptionTraitItemDefault::from_syntax_node(db, self.node.get_children(db)[4])
+    }
     pub fn semicolon(&self, db: &'db dyn Database) -> TerminalSemicolon<'db> {
-        TerminalSemicolon::from_syntax_node(db, self.node.get_children(db)[4])
+        TerminalSemicolon::from_syntax_node(db, self.node.get_children(db)[5])
     }
 }
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
@@ -15898,6 +16381,7 @@ impl<'db> TypedSyntaxNode<'db> for TraitItemType<'db> {
                         TerminalType::missing(db).0,
                         TerminalIdentifier::missing(db).0,
                         OptionWrappedGenericParamList::missing(db).0,
+                        OptionTraitItemDefault::missing(db).0,
                         TerminalSemicolon::missing(db).0,
                     ]
                     .into(),
@@ -15942,16 +16426,18 @@ impl<'db> TraitItemConstant<'db> {
     pub const INDEX_CONST_KW: usize = 1;
     pub const INDEX_NAME: usize = 2;
     pub const INDEX_TYPE_CLAUSE: usize = 3;
-    pub const INDEX_SEMICOLON: usize = 4;
+    pub const INDEX_DEFAULT: usize = 4;
+    pub const INDEX_SEMICOLON: usize = 5;
     pub fn new_green(
         db: &'db dyn Database,
         attributes: AttributeListGreen<'db>,
         const_kw: TerminalConstGreen<'db>,
         name: TerminalIdentifierGreen<'db>,
         type_clause: TypeClauseGreen<'db>,
+        default: OptionTraitItemDefaultGreen<'db>,
         semicolon: TerminalSemicolonGreen<'db>,
     ) -> TraitItemConstantGreen<'db> {
-        let children = [attributes.0, const_kw.0, name.0, type_clause.0, semicolon.0];
+        let children = [attributes.0, const_kw.0, name.0, type_clause.0, default.0, semicolon.0];
         let width = children.into_iter().map(|id: GreenId<'_>| id.long(db).width(db)).sum();
         TraitItemConstantGreen(
             GreenNode {
@@ -15975,8 +16461,11 @@ impl<'db> TraitItemConstant<'db> {
     pub fn type_clause(&self, db: &'db dyn Database) -> TypeClause<'db> {
         TypeClause::from_syntax_node(db, self.node.get_children(db)[3])
     }
+    pub fn default(&self, db: &'db dyn Database) -> OptionTraitItemDefault<'db> {
+        OptionTraitItemDefault::from_syntax_node(db, self.node.get_children(db)[4])
+    }
     pub fn semicolon(&self, db: &'db dyn Database) -> TerminalSemicolon<'db> {
-        TerminalSemicolon::from_syntax_node(db, self.node.get_children(db)[4])
+        TerminalSemicolon::from_syntax_node(db, self.node.get_children(db)[5])
     }
 }
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
@@ -16016,6 +16505,7 @@ impl<'db> TypedSyntaxNode<'db> for TraitItemConstant<'db> {
                         TerminalConst::missing(db).0,
                         TerminalIdentifier::missing(db).0,
                         TypeClause::missing(db).0,
+                        OptionTraitItemDefault::missing(db).0,
                         TerminalSemicolon::missing(db).0,
                     ]
                     .into(),
@@ -16052,6 +16542,270 @@ impl<'db> TypedSyntaxNode<'db> for TraitItemConstant<'db> {
     }
 }
 #[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
+pub struct TraitItemDefault<'db> {
+    node: SyntaxNode<'db>,
+}
+impl<'db> TraitItemDefault<'db> {
+    pub const INDEX_EQ: usize = 0;
+    pub const INDEX_VALUE: usize = 1;
+    pub fn new_green(
+        db: &'db dyn Database,
+        eq: TerminalEqGreen<'db>,
+        value: ExprGreen<'db>,
+    ) -> TraitItemDefaultGreen<'db> {
+        let children = [eq.0, value.0];
+        let width = children.into_iter().map(|id: GreenId<'_>| id.long(db).width(db)).sum();
+        TraitItemDefaultGreen(
+            GreenNode {
+                kind: SyntaxKind::TraitItemDefault,
+                details: GreenNodeDetails::Node { children: children.into(), width },
+            }
+            .intern(db),
+        )
+    }
+}
+impl<'db> TraitItemDefault<'db> {
+    pub fn eq(&self, db: &'db dyn Database) -> TerminalEq<'db> {
+        TerminalEq::from_syntax_node(db, self.node.get_children(db)[0])
+    }
+    pub fn value(&self, db: &'db dyn Database) -> Expr<'db> {
+        Expr::from_syntax_node(db, self.node.get_children(db)[1])
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
+pub struct TraitItemDefaultPtr<'db>(pub SyntaxStablePtrId<'db>);
+impl<'db> TraitItemDefaultPtr<'db> {}
+impl<'db> TypedStablePtr<'db> for TraitItemDefaultPtr<'db> {
+    type SyntaxNode = TraitItemDefault<'db>;
+    fn untyped(self) -> SyntaxStablePtrId<'db> {
+        self.0
+    }
+    fn lookup(&self, db: &'db dyn Database) -> TraitItemDefault<'db> {
+        TraitItemDefault::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+impl<'db> From<TraitItemDefaultPtr<'db>> for SyntaxStablePtrId<'db> {
+    fn from(ptr: TraitItemDefaultPtr<'db>) -> Self {
+        ptr.untyped()
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update)]
+pub struct TraitItemDefaultGreen<'db>(pub GreenId<'db>);
+impl<'db> TypedSyntaxNode<'db> for TraitItemDefault<'db> {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::TraitItemDefault);
+    type StablePtr = TraitItemDefaultPtr<'db>;
+    type Green = TraitItemDefaultGreen<'db>;
+    fn missing(db: &'db dyn Database) -> Self::Green {
+        TraitItemDefaultGreen(
+            GreenNode {
+                kind: SyntaxKind::TraitItemDefault,
+                details: GreenNodeDetails::Node {
+                    children: [TerminalEq::missing(db).0, Expr::missing(db).0].into(),
+                    width: TextWidth::default(),
+                },
+            }
+            .intern(db),
+        )
+    }
+    fn from_syntax_node(db: &'db dyn Database, node: SyntaxNode<'db>) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::TraitItemDefault,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::TraitItemDefault
+        );
+        Self { node }
+    }
+    fn cast(db: &'db dyn Database, node: SyntaxNode<'db>) -> Option<Self> {
+        let kind = node.kind(db);
+        if kind == SyntaxKind::TraitItemDefault {
+            Some(Self::from_syntax_node(db, node))
+        } else {
+            None
+        }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode<'db> {
+        self.node
+    }
+    fn stable_ptr(&self, db: &'db dyn Database) -> Self::StablePtr {
+        TraitItemDefaultPtr(self.node.stable_ptr(db))
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
+pub enum OptionTraitItemDefault<'db> {
+    Empty(OptionTraitItemDefaultEmpty<'db>),
+    TraitItemDefault(TraitItemDefault<'db>),
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
+pub struct OptionTraitItemDefaultPtr<'db>(pub SyntaxStablePtrId<'db>);
+impl<'db> TypedStablePtr<'db> for OptionTraitItemDefaultPtr<'db> {
+    type SyntaxNode = OptionTraitItemDefault<'db>;
+    fn untyped(self) -> SyntaxStablePtrId<'db> {
+        self.0
+    }
+    fn lookup(&self, db: &'db dyn Database) -> Self::SyntaxNode {
+        OptionTraitItemDefault::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+impl<'db> From<OptionTraitItemDefaultPtr<'db>> for SyntaxStablePtrId<'db> {
+    fn from(ptr: OptionTraitItemDefaultPtr<'db>) -> Self {
+        ptr.untyped()
+    }
+}
+impl<'db> From<OptionTraitItemDefaultEmptyPtr<'db>> for OptionTraitItemDefaultPtr<'db> {
+    fn from(value: OptionTraitItemDefaultEmptyPtr<'db>) -> Self {
+        Self(value.0)
+    }
+}
+impl<'db> From<TraitItemDefaultPtr<'db>> for OptionTraitItemDefaultPtr<'db> {
+    fn from(value: TraitItemDefaultPtr<'db>) -> Self {
+        Self(value.0)
+    }
+}
+impl<'db> From<OptionTraitItemDefaultEmptyGreen<'db>> for OptionTraitItemDefaultGreen<'db> {
+    fn from(value: OptionTraitItemDefaultEmptyGreen<'db>) -> Self {
+        Self(value.0)
+    }
+}
+impl<'db> From<TraitItemDefaultGreen<'db>> for OptionTraitItemDefaultGreen<'db> {
+    fn from(value: TraitItemDefaultGreen<'db>) -> Self {
+        Self(value.0)
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update)]
+pub struct OptionTraitItemDefaultGreen<'db>(pub GreenId<'db>);
+impl<'db> TypedSyntaxNode<'db> for OptionTraitItemDefault<'db> {
+    const OPTIONAL_KIND: Option<SyntaxKind> = None;
+    type StablePtr = OptionTraitItemDefaultPtr<'db>;
+    type Green = OptionTraitItemDefaultGreen<'db>;
+    fn missing(db: &'db dyn Database) -> Self::Green {
+        panic!("No missing variant.");
+    }
+    fn from_syntax_node(db: &'db dyn Database, node: SyntaxNode<'db>) -> Self {
+        let kind = node.kind(db);
+        match kind {
+            SyntaxKind::OptionTraitItemDefaultEmpty => OptionTraitItemDefault::Empty(
+                OptionTraitItemDefaultEmpty::from_syntax_node(db, node),
+            ),
+            SyntaxKind::TraitItemDefault => OptionTraitItemDefault::TraitItemDefault(
+                TraitItemDefault::from_syntax_node(db, node),
+            ),
+            _ => panic!(
+                "Unexpected syntax kind {:?} when constructing {}.",
+                kind, "OptionTraitItemDefault"
+            ),
+        }
+    }
+    fn cast(db: &'db dyn Database, node: SyntaxNode<'db>) -> Option<Self> {
+        let kind = node.kind(db);
+        match kind {
+            SyntaxKind::OptionTraitItemDefaultEmpty => Some(OptionTraitItemDefault::Empty(
+                OptionTraitItemDefaultEmpty::from_syntax_node(db, node),
+            )),
+            SyntaxKind::TraitItemDefault => Some(OptionTraitItemDefault::TraitItemDefault(
+                TraitItemDefault::from_syntax_node(db, node),
+            )),
+            _ => None,
+        }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode<'db> {
+        match self {
+            OptionTraitItemDefault::Empty(x) => x.as_syntax_node(),
+            OptionTraitItemDefault::TraitItemDefault(x) => x.as_syntax_node(),
+        }
+    }
+    fn stable_ptr(&self, db: &'db dyn Database) -> Self::StablePtr {
+        OptionTraitItemDefaultPtr(self.as_syntax_node().stable_ptr(db))
+    }
+}
+impl<'db> OptionTraitItemDefault<'db> {
+    /// Checks if a kind of a variant of [OptionTraitItemDefault].
+    pub fn is_variant(kind: SyntaxKind) -> bool {
+        matches!(kind, SyntaxKind::OptionTraitItemDefaultEmpty | SyntaxKind::TraitItemDefault)
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
+pub struct OptionTraitItemDefaultEmpty<'db> {
+    node: SyntaxNode<'db>,
+}
+impl<'db> OptionTraitItemDefaultEmpty<'db> {
+    pub fn new_green(db: &'db dyn Database) -> OptionTraitItemDefaultEmptyGreen<'db> {
+        let children = [];
+        let width = children.into_iter().map(|id: GreenId<'_>| id.long(db).width(db)).sum();
+        OptionTraitItemDefaultEmptyGreen(
+            GreenNode {
+                kind: SyntaxKind::OptionTraitItemDefaultEmpty,
+                details: GreenNodeDetails::Node { children: children.into(), width },
+            }
+            .intern(db),
+        )
+    }
+}
+impl<'db> OptionTraitItemDefaultEmpty<'db> {}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update, HeapSize)]
+pub struct OptionTraitItemDefaultEmptyPtr<'db>(pub SyntaxStablePtrId<'db>);
+impl<'db> OptionTraitItemDefaultEmptyPtr<'db> {}
+impl<'db> TypedStablePtr<'db> for OptionTraitItemDefaultEmptyPtr<'db> {
+    type SyntaxNode = OptionTraitItemDefaultEmpty<'db>;
+    fn untyped(self) -> SyntaxStablePtrId<'db> {
+        self.0
+    }
+    fn lookup(&self, db: &'db dyn Database) -> OptionTraitItemDefaultEmpty<'db> {
+        OptionTraitItemDefaultEmpty::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+impl<'db> From<OptionTraitItemDefaultEmptyPtr<'db>> for SyntaxStablePtrId<'db> {
+    fn from(ptr: OptionTraitItemDefaultEmptyPtr<'db>) -> Self {
+        ptr.untyped()
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, salsa::Update)]
+pub struct OptionTraitItemDefaultEmptyGreen<'db>(pub GreenId<'db>);
+impl<'db> TypedSyntaxNode<'db> for OptionTraitItemDefaultEmpty<'db> {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::OptionTraitItemDefaultEmpty);
+    type StablePtr = OptionTraitItemDefaultEmptyPtr<'db>;
+    type Green = OptionTraitItemDefaultEmptyGreen<'db>;
+    fn missing(db: &'db dyn Database) -> Self::Green {
+        OptionTraitItemDefaultEmptyGreen(
+            GreenNode {
+                kind: SyntaxKind::OptionTraitItemDefaultEmpty,
+                details: GreenNodeDetails::Node {
+                    children: [].into(),
+                    width: TextWidth::default(),
+                },
+            }
+            .intern(db),
+        )
+    }
+    fn from_syntax_node(db: &'db dyn Database, node: SyntaxNode<'db>) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::OptionTraitItemDefaultEmpty,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::OptionTraitItemDefaultEmpty
+        );
+        Self { node }
+    }
+    fn cast(db: &'db dyn Database, node: SyntaxNode<'db>) -> Option<Self> {
+        let kind = node.kind(db);
+        if kind == SyntaxKind::OptionTraitItemDefaultEmpty {
+            Some(Self::from_syntax_node(db, node))
+        } else {
+            None
+        }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode<'db> {
+        self.node
+    }
+    fn stable_ptr(&self, db: &'db dyn Database) -> Self::StablePtr {
+        OptionTraitItemDefaultEmptyPtr(self.node.stable_ptr(db))
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq, salsa::Update)]
 pub struct TraitItemImpl<'db> {
     node: SyntaxNode<'db>,
 }
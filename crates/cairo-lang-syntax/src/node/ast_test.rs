@@ -11,7 +11,8 @@ use super::ast::{
     TerminalPlus, TokenIdentifier, TokenPlus, TokenWhitespace, Trivia,
 };
 use super::kind::SyntaxKind;
-use super::{SyntaxNode, Terminal, Token};
+use super::serialization::SerializedSyntaxTree;
+use super::{SyntaxNode, Terminal, Token, TypedSyntaxNode};
 use crate::node::ast::{
     ExprPathInner, OptionTerminalDollarEmpty, TerminalLiteralNumber, TokenLiteralNumber,
 };
@@ -138,6 +139,100 @@ fn test_stable_ptr() {
     }
 }
 
+#[test]
+fn test_token_at_offset() {
+    let db_val = DatabaseForTesting::default();
+    let db = &db_val;
+    let root = setup(db);
+    let token_kind_at = |offset: u32| {
+        root.token_at_offset(db, TextWidth::new_for_testing(offset).as_offset())
+            .map(|node| node.kind(db))
+    };
+    assert_eq!(token_kind_at(0), Some(SyntaxKind::TerminalIdentifier));
+    // The trailing trivia of a terminal is a part of it.
+    assert_eq!(token_kind_at(3), Some(SyntaxKind::TerminalIdentifier));
+    assert_eq!(token_kind_at(4), Some(SyntaxKind::TerminalPlus));
+    assert_eq!(token_kind_at(6), Some(SyntaxKind::TerminalLiteralNumber));
+    // The end of the node is a part of its last terminal.
+    assert_eq!(token_kind_at(7), Some(SyntaxKind::TerminalLiteralNumber));
+    assert_eq!(token_kind_at(8), None);
+}
+
+#[test]
+fn test_ancestors_of_kind() {
+    let db_val = DatabaseForTesting::default();
+    let db = &db_val;
+    let root = setup(db);
+    let token = root.token_at_offset(db, TextOffset::START).unwrap();
+    assert_eq!(
+        token.ancestors_of_kind(db, SyntaxKind::ExprPath).collect::<Vec<_>>(),
+        vec![root.get_children(db)[0]]
+    );
+    assert_eq!(
+        token
+            .ancestors_of_type::<ExprBinary<'_>>(db)
+            .map(|expr| expr.as_syntax_node())
+            .collect::<Vec<_>>(),
+        vec![root]
+    );
+    assert_eq!(token.ancestors_of_kind(db, SyntaxKind::TerminalPlus).count(), 0);
+}
+
+#[test]
+fn test_cursor() {
+    let db_val = DatabaseForTesting::default();
+    let db = &db_val;
+    let root = setup(db);
+    let mut cursor = root.cursor(db);
+    assert!(!cursor.goto_parent());
+    assert!(!cursor.goto_next_sibling());
+
+    assert!(cursor.goto_first_child());
+    assert_eq!(cursor.node().kind(db), SyntaxKind::ExprPath);
+    assert!(!cursor.goto_prev_sibling());
+    assert!(cursor.goto_next_sibling());
+    assert_eq!(cursor.node().kind(db), SyntaxKind::TerminalPlus);
+    assert!(cursor.goto_next_sibling());
+    assert_eq!(cursor.node().kind(db), SyntaxKind::TerminalLiteralNumber);
+    assert!(!cursor.goto_next_sibling());
+
+    assert!(cursor.goto_last_child());
+    assert_eq!(cursor.node().kind(db), SyntaxKind::Trivia);
+    assert!(!cursor.goto_first_child());
+    assert_eq!(cursor.depth(), 2);
+    assert!(cursor.goto_prev_sibling());
+    assert_eq!(cursor.node().kind(db), SyntaxKind::TokenLiteralNumber);
+
+    assert!(cursor.goto_parent());
+    assert!(cursor.goto_parent());
+    assert_eq!(cursor.node(), root);
+    assert_eq!(cursor.depth(), 0);
+}
+
+#[test]
+fn test_serialization() {
+    let db_val = DatabaseForTesting::default();
+    let db = &db_val;
+    let root = setup(db);
+    let tree = SerializedSyntaxTree::new(db, root);
+    assert_eq!(tree.root.get_text(), "foo + 5");
+
+    let json = tree.to_json();
+    assert!(json.starts_with(
+        r#"{"version":1,"root":{"kind":"ExprBinary","span":{"start":0,"end":7},"children":["#
+    ));
+    assert!(json.contains(r#"{"kind":"TokenWhitespace","span":{"start":3,"end":4},"text":" "}"#));
+    let read_tree = SerializedSyntaxTree::from_json(&json).unwrap();
+    assert_eq!(read_tree, tree);
+    assert_eq!(read_tree.root.to_green(db), root.green_node(db).clone().intern(db));
+
+    let other_version = json.replacen(r#""version":1"#, r#""version":0"#, 1);
+    assert_eq!(
+        SerializedSyntaxTree::from_json(&other_version).unwrap_err().to_string(),
+        "unsupported syntax tree schema version 0, expected 1"
+    );
+}
+
 fn setup(db: &DatabaseForTesting) -> SyntaxNode<'_> {
     // TODO: Use a builder for easier construction of token.
     // Construct green nodes.
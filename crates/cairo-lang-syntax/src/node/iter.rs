@@ -95,3 +95,83 @@ impl<'a> Iterator for Preorder<'a> {
         }
     }
 }
+
+/// A cursor walking the subtree rooted at the node it was created at.
+///
+/// The cursor keeps the children of the nodes on its path, so moving to a sibling does not require
+/// looking up the children of the parent again.
+pub struct SyntaxCursor<'a> {
+    db: &'a dyn Database,
+    root: SyntaxNode<'a>,
+    /// For each node on the path from the root (exclusive) to the current node (inclusive), its
+    /// siblings and its index among them.
+    path: Vec<(&'a [SyntaxNode<'a>], usize)>,
+}
+
+impl<'a> SyntaxCursor<'a> {
+    pub(super) fn new(root: SyntaxNode<'a>, db: &'a dyn Database) -> Self {
+        Self { db, root, path: Vec::new() }
+    }
+
+    /// Returns the node the cursor points at.
+    pub fn node(&self) -> SyntaxNode<'a> {
+        match self.path.last() {
+            Some((siblings, index)) => siblings[*index],
+            None => self.root,
+        }
+    }
+
+    /// Returns the depth of the current node, relative to the root of the cursor.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Moves to the first child of the current node. Returns false if it has no children.
+    pub fn goto_first_child(&mut self) -> bool {
+        let children = self.node().get_children(self.db);
+        if children.is_empty() {
+            return false;
+        }
+        self.path.push((children, 0));
+        true
+    }
+
+    /// Moves to the last child of the current node. Returns false if it has no children.
+    pub fn goto_last_child(&mut self) -> bool {
+        let children = self.node().get_children(self.db);
+        if children.is_empty() {
+            return false;
+        }
+        self.path.push((children, children.len() - 1));
+        true
+    }
+
+    /// Moves to the next sibling of the current node. Returns false if it is the last child, or
+    /// the root of the cursor.
+    pub fn goto_next_sibling(&mut self) -> bool {
+        match self.path.last_mut() {
+            Some((siblings, index)) if *index + 1 < siblings.len() => {
+                *index += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves to the previous sibling of the current node. Returns false if it is the first child,
+    /// or the root of the cursor.
+    pub fn goto_prev_sibling(&mut self) -> bool {
+        match self.path.last_mut() {
+            Some((_, index)) if *index > 0 => {
+                *index -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves to the parent of the current node. Returns false if it is the root of the cursor.
+    pub fn goto_parent(&mut self) -> bool {
+        self.path.pop().is_some()
+    }
+}
@@ -35,6 +35,8 @@ pub fn key_fields_range(kind: SyntaxKind) -> core::ops::Range<usize> {
         SyntaxKind::ExprMatch => 0..0,
         SyntaxKind::MatchArms => 0..0,
         SyntaxKind::MatchArm => 0..0,
+        SyntaxKind::MatchArmGuard => 0..0,
+        SyntaxKind::OptionMatchArmGuardEmpty => 0..0,
         SyntaxKind::ExprIf => 0..0,
         SyntaxKind::ConditionListAnd => 0..0,
         SyntaxKind::ConditionLet => 0..0,
@@ -61,6 +63,7 @@ pub fn key_fields_range(kind: SyntaxKind) -> core::ops::Range<usize> {
         SyntaxKind::ArgListBraced => 0..0,
         SyntaxKind::ArgListBracketed => 0..0,
         SyntaxKind::WrappedArgListMissing => 0..0,
+        SyntaxKind::PatternRange => 0..0,
         // Key fields: name.
         SyntaxKind::PatternIdentifier => 1..2,
         SyntaxKind::PatternStruct => 0..0,
@@ -139,6 +142,8 @@ pub fn key_fields_range(kind: SyntaxKind) -> core::ops::Range<usize> {
         SyntaxKind::TraitItemType => 2..3,
         // Key fields: name.
         SyntaxKind::TraitItemConstant => 2..3,
+        SyntaxKind::TraitItemDefault => 0..0,
+        SyntaxKind::OptionTraitItemDefaultEmpty => 0..0,
         // Key fields: name.
         SyntaxKind::TraitItemImpl => 2..3,
         // Key fields: name.
@@ -44,6 +44,8 @@ pub enum SyntaxKind {
     ExprMatch,
     MatchArms,
     MatchArm,
+    MatchArmGuard,
+    OptionMatchArmGuardEmpty,
     ExprIf,
     ConditionListAnd,
     ConditionLet,
@@ -68,6 +70,7 @@ pub enum SyntaxKind {
     ArgListBraced,
     ArgListBracketed,
     WrappedArgListMissing,
+    PatternRange,
     PatternIdentifier,
     PatternStruct,
     PatternStructParamList,
@@ -131,6 +134,8 @@ pub enum SyntaxKind {
     TraitItemFunction,
     TraitItemType,
     TraitItemConstant,
+    TraitItemDefault,
+    OptionTraitItemDefaultEmpty,
     TraitItemImpl,
     ItemImpl,
     ItemHeaderDoc,
@@ -13,7 +13,7 @@ use self::green::GreenNode;
 use self::ids::{GreenId, SyntaxStablePtrId};
 use self::kind::SyntaxKind;
 use crate::node::db::SyntaxGroup;
-use crate::node::iter::{Preorder, WalkEvent};
+use crate::node::iter::{Preorder, SyntaxCursor, WalkEvent};
 
 pub mod ast;
 pub mod db;
@@ -24,6 +24,7 @@ pub mod ids;
 pub mod iter;
 pub mod key_fields;
 pub mod kind;
+pub mod serialization;
 pub mod stable_ptr;
 pub mod with_db;
 
@@ -368,6 +369,31 @@ impl<'a> SyntaxNode<'a> {
         *self
     }
 
+    /// Finds the terminal containing the given offset, trivia included.
+    ///
+    /// An offset at the end of the node is considered a part of its last terminal. Returns `None`
+    /// if the offset is outside the span of the node.
+    pub fn token_at_offset(
+        &self,
+        db: &'a dyn Database,
+        offset: TextOffset,
+    ) -> Option<SyntaxNode<'a>> {
+        let span = self.span(db);
+        if offset < span.start || offset > span.end {
+            return None;
+        }
+        let mut node = *self;
+        while !node.kind(db).is_terminal() {
+            let children = node.get_children(db);
+            node = *children.iter().find(|child| offset < child.span(db).end).or_else(|| {
+                children.iter().rev().find(|child| {
+                    child.width(db) != TextWidth::ZERO || child.kind(db).is_terminal()
+                })
+            })?;
+        }
+        Some(node)
+    }
+
     /// Looks up a syntax node using a position.
     pub fn lookup_position(&self, db: &'a dyn Database, position: TextPosition) -> SyntaxNode<'a> {
         match position.offset_in_file(db, self.stable_ptr(db).file_id(db)) {
@@ -493,6 +519,11 @@ impl<'a> SyntaxNode<'a> {
         Preorder::new(*self, db)
     }
 
+    /// Creates a cursor walking the subtree rooted at the current node, starting at this node.
+    pub fn cursor(&self, db: &'a dyn Database) -> SyntaxCursor<'a> {
+        SyntaxCursor::new(*self, db)
+    }
+
     /// Gets all the leaves of the SyntaxTree, where the self node is the root of a tree.
     pub fn tokens(&self, db: &'a dyn Database) -> impl Iterator<Item = Self> + 'a {
         self.preorder(db).filter_map(|event| match event {
@@ -558,6 +589,23 @@ impl<'a> SyntaxNode<'a> {
         self.ancestors(db).find_map(|node| T::cast(db, node))
     }
 
+    /// Creates an iterator that yields the ancestors of a given kind.
+    pub fn ancestors_of_kind(
+        &self,
+        db: &'a dyn Database,
+        kind: SyntaxKind,
+    ) -> impl Iterator<Item = SyntaxNode<'a>> + 'a {
+        self.ancestors(db).filter(move |node| node.kind(db) == kind)
+    }
+
+    /// Creates an iterator that yields the ancestors of a given type, in typed form.
+    pub fn ancestors_of_type<T: TypedSyntaxNode<'a>>(
+        &self,
+        db: &'a dyn Database,
+    ) -> impl Iterator<Item = T> + 'a {
+        self.ancestors(db).filter_map(move |node| T::cast(db, node))
+    }
+
     /// Finds the parent of a given kind.
     pub fn parent_of_kind(&self, db: &'a dyn Database, kind: SyntaxKind) -> Option<SyntaxNode<'a>> {
         self.parent(db).filter(|node| node.kind(db) == kind)
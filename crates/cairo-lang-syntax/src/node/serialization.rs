@@ -0,0 +1,107 @@
+use cairo_lang_filesystem::ids::SmolStrId;
+use cairo_lang_filesystem::span::{TextSpan, TextWidth};
+use cairo_lang_utils::Intern;
+use salsa::Database;
+use serde::{Deserialize, Serialize};
+
+use super::SyntaxNode;
+use super::green::{GreenNode, GreenNodeDetails};
+use super::ids::GreenId;
+use super::kind::SyntaxKind;
+
+/// The version of the schema of [SerializedSyntaxTree].
+///
+/// Bumped on every incompatible change of the schema, including removing or renaming syntax kinds.
+pub const SYNTAX_TREE_SCHEMA_VERSION: u32 = 1;
+
+/// A syntax tree in a stable serializable form, to be consumed by tools outside of the compiler.
+///
+/// The tree contains all the nodes, including the trivia, so the text of the file is the
+/// concatenation of the texts of its tokens.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedSyntaxTree {
+    /// The version of the schema the tree was serialized with.
+    pub version: u32,
+    pub root: SerializedSyntaxNode,
+}
+
+/// A node of a [SerializedSyntaxTree].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedSyntaxNode {
+    pub kind: SyntaxKind,
+    /// The span of the node in the file, in bytes, including its trivia.
+    pub span: TextSpan,
+    /// The text of the node, if it is a token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<SerializedSyntaxNode>,
+}
+
+impl SerializedSyntaxTree {
+    /// Creates the serializable form of the syntax tree rooted at the given node.
+    pub fn new(db: &dyn Database, root: SyntaxNode<'_>) -> Self {
+        Self { version: SYNTAX_TREE_SCHEMA_VERSION, root: SerializedSyntaxNode::new(db, root) }
+    }
+
+    /// Serializes the tree to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Serializing a syntax tree should never fail.")
+    }
+
+    /// Deserializes a tree from JSON, failing if it was serialized with another schema version.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let tree: Self = serde_json::from_str(json)?;
+        if tree.version != SYNTAX_TREE_SCHEMA_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported syntax tree schema version {}, expected {}",
+                tree.version, SYNTAX_TREE_SCHEMA_VERSION
+            )));
+        }
+        Ok(tree)
+    }
+}
+
+impl SerializedSyntaxNode {
+    fn new(db: &dyn Database, node: SyntaxNode<'_>) -> Self {
+        Self {
+            kind: node.kind(db),
+            span: node.span(db),
+            text: node.text(db).map(|text| text.long(db).to_string()),
+            children: node
+                .get_children(db)
+                .iter()
+                .map(|child| SerializedSyntaxNode::new(db, *child))
+                .collect(),
+        }
+    }
+
+    /// Returns the text of the node, including its trivia.
+    pub fn get_text(&self) -> String {
+        let mut text = String::new();
+        self.append_text(&mut text);
+        text
+    }
+
+    fn append_text(&self, text: &mut String) {
+        match &self.text {
+            Some(token_text) => text.push_str(token_text),
+            None => self.children.iter().for_each(|child| child.append_text(text)),
+        }
+    }
+
+    /// Rebuilds the green tree of the node. The spans of the nodes are ignored, as they are
+    /// determined by the texts of the tokens.
+    pub fn to_green<'db>(&self, db: &'db dyn Database) -> GreenId<'db> {
+        let details = match &self.text {
+            Some(text) => GreenNodeDetails::Token(SmolStrId::from(db, text.as_str())),
+            None => {
+                let children: Vec<_> =
+                    self.children.iter().map(|child| child.to_green(db)).collect();
+                let width: TextWidth = children.iter().map(|child| child.width(db)).sum();
+                GreenNodeDetails::Node { children, width }
+            }
+        };
+        GreenNode { kind: self.kind, details }.intern(db)
+    }
+}
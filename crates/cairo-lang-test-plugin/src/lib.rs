@@ -45,6 +45,8 @@ const TEST_ATTR: &str = "test";
 const SHOULD_PANIC_ATTR: &str = "should_panic";
 const IGNORE_ATTR: &str = "ignore";
 const AVAILABLE_GAS_ATTR: &str = "available_gas";
+const FUZZ_ATTR: &str = "fuzz";
+const MAX_STEPS_ATTR: &str = "max_steps";
 const STATIC_GAS_ARG: &str = "static";
 
 /// Configuration for test compilation.
@@ -4,7 +4,9 @@ use cairo_lang_syntax::attribute::structured::AttributeListStructurize;
 use cairo_lang_syntax::node::ast;
 use salsa::Database;
 
-use super::{AVAILABLE_GAS_ATTR, IGNORE_ATTR, SHOULD_PANIC_ATTR, TEST_ATTR};
+use super::{
+    AVAILABLE_GAS_ATTR, FUZZ_ATTR, IGNORE_ATTR, MAX_STEPS_ATTR, SHOULD_PANIC_ATTR, TEST_ATTR,
+};
 use crate::test_config::try_extract_test_config;
 
 /// Plugin to create diagnostics for tests attributes.
@@ -37,6 +39,8 @@ impl MacroPlugin for TestPlugin {
             SmolStrId::from(db, AVAILABLE_GAS_ATTR),
             SmolStrId::from(db, SHOULD_PANIC_ATTR),
             SmolStrId::from(db, IGNORE_ATTR),
+            SmolStrId::from(db, FUZZ_ATTR),
+            SmolStrId::from(db, MAX_STEPS_ATTR),
         ]
     }
 }
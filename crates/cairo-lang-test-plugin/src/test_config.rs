@@ -10,7 +10,13 @@ use salsa::Database;
 use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt as Felt252;
 
-use super::{AVAILABLE_GAS_ATTR, IGNORE_ATTR, SHOULD_PANIC_ATTR, STATIC_GAS_ARG, TEST_ATTR};
+use super::{
+    AVAILABLE_GAS_ATTR, FUZZ_ATTR, IGNORE_ATTR, MAX_STEPS_ATTR, SHOULD_PANIC_ATTR, STATIC_GAS_ARG,
+    TEST_ATTR,
+};
+
+/// The default number of runs of a fuzz test.
+pub const DEFAULT_FUZZ_RUNS: usize = 256;
 
 /// Expectation for a panic case.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
@@ -30,6 +36,15 @@ pub enum TestExpectation {
     Panics(PanicExpectation),
 }
 
+/// The configuration of a fuzz test, run with randomized values for its parameters.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct FuzzConfig {
+    /// The number of runs, each with newly generated parameter values.
+    pub runs: usize,
+    /// The seed of the generated values. If not set, the seed of the test run is used.
+    pub seed: Option<u64>,
+}
+
 /// The configuration for running a single test.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct TestConfig {
@@ -39,6 +54,12 @@ pub struct TestConfig {
     pub expectation: TestExpectation,
     /// Should the test be ignored.
     pub ignored: bool,
+    /// The fuzzing configuration, if the test is a fuzz test.
+    #[serde(default)]
+    pub fuzz: Option<FuzzConfig>,
+    /// The maximal number of steps a run of the test may use, if budgeted.
+    #[serde(default)]
+    pub max_steps: Option<usize>,
 }
 
 /// Extracts the configuration of a tests from attributes, or returns the diagnostics if the
@@ -51,6 +72,8 @@ pub fn try_extract_test_config<'db>(
     let ignore_attr = attrs.iter().find(|attr| attr.id.long(db) == IGNORE_ATTR);
     let available_gas_attr = attrs.iter().find(|attr| attr.id.long(db) == AVAILABLE_GAS_ATTR);
     let should_panic_attr = attrs.iter().find(|attr| attr.id.long(db) == SHOULD_PANIC_ATTR);
+    let fuzz_attr = attrs.iter().find(|attr| attr.id.long(db) == FUZZ_ATTR);
+    let max_steps_attr = attrs.iter().find(|attr| attr.id.long(db) == MAX_STEPS_ATTR);
     let mut diagnostics = vec![];
     if let Some(attr) = test_attr {
         if !attr.args.is_empty() {
@@ -60,7 +83,10 @@ pub fn try_extract_test_config<'db>(
             ));
         }
     } else {
-        for attr in [ignore_attr, available_gas_attr, should_panic_attr].into_iter().flatten() {
+        for attr in [ignore_attr, available_gas_attr, should_panic_attr, fuzz_attr, max_steps_attr]
+            .into_iter()
+            .flatten()
+        {
             diagnostics.push(PluginDiagnostic::error(
                 attr.id_stable_ptr.untyped(),
                 "Attribute should only appear on tests.".into(),
@@ -79,6 +105,8 @@ pub fn try_extract_test_config<'db>(
         false
     };
     let available_gas = extract_available_gas(available_gas_attr, db, &mut diagnostics);
+    let fuzz = fuzz_attr.and_then(|attr| extract_fuzz_config(attr, db, &mut diagnostics));
+    let max_steps = max_steps_attr.and_then(|attr| extract_max_steps(attr, db, &mut diagnostics));
     let (should_panic, expected_panic_felts) = if let Some(attr) = should_panic_attr {
         if attr.args.is_empty() {
             (true, None)
@@ -116,10 +144,71 @@ pub fn try_extract_test_config<'db>(
                 TestExpectation::Success
             },
             ignored,
+            fuzz,
+            max_steps,
         })
     })
 }
 
+/// Extracts the fuzzing configuration from the `fuzz` attribute, of the form
+/// `#[fuzz(runs: <number>, seed: <number>)]`, where both arguments are optional.
+/// Adds a diagnostic and returns `None` if the attribute is malformed.
+fn extract_fuzz_config<'db>(
+    attr: &Attribute<'db>,
+    db: &'db dyn Database,
+    diagnostics: &mut Vec<PluginDiagnostic<'db>>,
+) -> Option<FuzzConfig> {
+    let mut runs = None;
+    let mut seed = None;
+    for arg in &attr.args {
+        let named_value = match &arg.variant {
+            AttributeArgVariant::Named { name, value: ast::Expr::Literal(literal), .. } => {
+                literal.numeric_value(db).and_then(|v| v.to_u64()).map(|v| (name.text.long(db), v))
+            }
+            _ => None,
+        };
+        match named_value {
+            Some((name, value)) if name == "runs" && runs.is_none() && value > 0 => {
+                runs = value.to_usize();
+            }
+            Some((name, value)) if name == "seed" && seed.is_none() => seed = Some(value),
+            _ => {
+                diagnostics.push(PluginDiagnostic::error(
+                    attr.args_stable_ptr.untyped(),
+                    "Attribute should be of the form `fuzz` or `fuzz(runs: <positive number>, \
+                     seed: <number>)`, with both arguments optional."
+                        .into(),
+                ));
+                return None;
+            }
+        }
+    }
+    Some(FuzzConfig { runs: runs.unwrap_or(DEFAULT_FUZZ_RUNS), seed })
+}
+
+/// Extracts the steps budget from the `max_steps` attribute.
+/// Adds a diagnostic and returns `None` if the attribute is malformed.
+fn extract_max_steps<'db>(
+    attr: &Attribute<'db>,
+    db: &'db dyn Database,
+    diagnostics: &mut Vec<PluginDiagnostic<'db>>,
+) -> Option<usize> {
+    match &attr.args[..] {
+        [
+            AttributeArg {
+                variant: AttributeArgVariant::Unnamed(ast::Expr::Literal(literal)), ..
+            },
+        ] => literal.numeric_value(db).and_then(|v| v.to_usize()).filter(|v| *v > 0),
+        _ => None,
+    }
+    .on_none(|| {
+        diagnostics.push(PluginDiagnostic::error(
+            attr.args_stable_ptr.untyped(),
+            "Attribute should have a single positive literal.".into(),
+        ))
+    })
+}
+
 /// Extract the available gas from the attribute.
 /// Adds a diagnostic if the attribute is malformed.
 /// Returns `None` if the attribute is "static", or the attribute is malformed.
@@ -0,0 +1,323 @@
+//! Fuzz tests - tests run repeatedly with generated values for their parameters, where the values
+//! of a failing run are shrunk to simpler ones still failing the test.
+
+use anyhow::{Result, bail};
+use cairo_lang_runnable_utils::builder::RunnableBuilder;
+use cairo_lang_runner::Arg;
+use cairo_lang_sierra::extensions::core::CoreTypeConcrete;
+use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_sierra::program::Function;
+use cairo_lang_sierra_to_casm::invocations::enm::get_variant_selector;
+use cairo_lang_test_plugin::test_config::FuzzConfig;
+use itertools::Itertools;
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Zero};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use starknet_types_core::felt::Felt as Felt252;
+
+use crate::{TestResult, TestStatus};
+
+/// The maximal length of a generated array.
+const MAX_ARRAY_LEN: usize = 16;
+/// The maximal number of runs spent on shrinking the values of a failing run.
+const MAX_SHRINK_RUNS: usize = 1000;
+
+/// The shape of the values of a parameter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputType {
+    /// An integer in the inclusive range `[min, max]`, e.g. a `u8` or, as `[0, P)`, a `felt252`.
+    Int { min: BigInt, max: BigInt },
+    /// A struct of the given member types.
+    Struct(Vec<InputType>),
+    /// An enum of the given variant types, with the sizes of the variants and of the enum.
+    Enum { variants: Vec<(InputType, usize)>, size: usize },
+    /// An array of the given element type.
+    Array(Box<InputType>),
+}
+
+/// A value of a parameter, of the matching [InputType].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputValue {
+    Int(BigInt),
+    Struct(Vec<InputValue>),
+    Enum { variant: usize, value: Box<InputValue> },
+    Array(Vec<InputValue>),
+}
+
+impl InputType {
+    /// Returns the type of the values of the given Sierra type.
+    pub fn from_sierra(builder: &RunnableBuilder, ty: &ConcreteTypeId) -> Result<Self> {
+        let int = |min: BigInt, max: BigInt| Ok(Self::Int { min, max });
+        let unsigned = |bits: u32| int(BigInt::zero(), (BigInt::one() << bits) - 1);
+        let signed =
+            |bits: u32| int(-(BigInt::one() << (bits - 1)), (BigInt::one() << (bits - 1)) - 1);
+        match builder.registry().get_type(ty)? {
+            CoreTypeConcrete::Felt252(_) => int(BigInt::zero(), Felt252::MAX.to_bigint()),
+            CoreTypeConcrete::Uint8(_) => unsigned(8),
+            CoreTypeConcrete::Uint16(_) => unsigned(16),
+            CoreTypeConcrete::Uint32(_) => unsigned(32),
+            CoreTypeConcrete::Uint64(_) => unsigned(64),
+            CoreTypeConcrete::Uint128(_) => unsigned(128),
+            CoreTypeConcrete::Bytes31(_) => unsigned(248),
+            CoreTypeConcrete::Sint8(_) => signed(8),
+            CoreTypeConcrete::Sint16(_) => signed(16),
+            CoreTypeConcrete::Sint32(_) => signed(32),
+            CoreTypeConcrete::Sint64(_) => signed(64),
+            CoreTypeConcrete::Sint128(_) => signed(128),
+            CoreTypeConcrete::BoundedInt(info) => {
+                int(info.range.lower.clone(), &info.range.upper - 1)
+            }
+            CoreTypeConcrete::Struct(info) => Ok(Self::Struct(
+                info.members
+                    .iter()
+                    .map(|member| Self::from_sierra(builder, member))
+                    .try_collect()?,
+            )),
+            CoreTypeConcrete::Enum(info) => Ok(Self::Enum {
+                variants: info
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        Ok((
+                            Self::from_sierra(builder, variant)?,
+                            builder.type_size(variant) as usize,
+                        ))
+                    })
+                    .collect::<Result<_>>()?,
+                size: builder.type_size(ty) as usize,
+            }),
+            CoreTypeConcrete::Array(info) => {
+                Ok(Self::Array(Box::new(Self::from_sierra(builder, &info.ty)?)))
+            }
+            CoreTypeConcrete::Snapshot(info) => Self::from_sierra(builder, &info.ty),
+            _ => bail!("Unsupported type of a fuzzed parameter: `{ty}`."),
+        }
+    }
+
+    /// Generates a random value of the type. Integers are biased towards their bounds and small
+    /// values, which are more likely to trigger edge cases.
+    pub fn generate(&self, rng: &mut StdRng) -> InputValue {
+        match self {
+            Self::Int { min, max } => InputValue::Int(match rng.random_range(0..8) {
+                0 => min.clone(),
+                1 => max.clone(),
+                2 | 3 => {
+                    let small = BigInt::from(rng.random_range(-256..=256));
+                    small.clamp(min.clone(), max.clone())
+                }
+                _ => random_in_range(rng, min, max),
+            }),
+            Self::Struct(members) => {
+                InputValue::Struct(members.iter().map(|member| member.generate(rng)).collect())
+            }
+            Self::Enum { variants, .. } => {
+                let variant = rng.random_range(0..variants.len());
+                InputValue::Enum { variant, value: Box::new(variants[variant].0.generate(rng)) }
+            }
+            Self::Array(ty) => {
+                let len = rng.random_range(0..=MAX_ARRAY_LEN);
+                InputValue::Array((0..len).map(|_| ty.generate(rng)).collect())
+            }
+        }
+    }
+
+    /// Returns the simplest value of the type.
+    fn simplest(&self) -> InputValue {
+        match self {
+            Self::Int { min, max } => {
+                InputValue::Int(BigInt::zero().clamp(min.clone(), max.clone()))
+            }
+            Self::Struct(members) => {
+                InputValue::Struct(members.iter().map(Self::simplest).collect())
+            }
+            Self::Enum { variants, .. } => {
+                InputValue::Enum { variant: 0, value: Box::new(variants[0].0.simplest()) }
+            }
+            Self::Array(_) => InputValue::Array(vec![]),
+        }
+    }
+
+    /// Returns values simpler than the given one, from the simplest.
+    pub fn shrink(&self, value: &InputValue) -> Vec<InputValue> {
+        match (self, value) {
+            (Self::Int { min, max }, InputValue::Int(value)) => {
+                // The target, then values approaching the current one by halving the distance.
+                let target = BigInt::zero().clamp(min.clone(), max.clone());
+                let mut diff: BigInt = value - &target;
+                let mut candidates = vec![];
+                while !diff.is_zero() {
+                    candidates.push(InputValue::Int(value - &diff));
+                    diff /= 2;
+                }
+                candidates
+            }
+            (Self::Struct(members), InputValue::Struct(values)) => {
+                let mut candidates = vec![];
+                for (idx, (member, value)) in members.iter().zip(values).enumerate() {
+                    for candidate in member.shrink(value) {
+                        let mut values = values.clone();
+                        values[idx] = candidate;
+                        candidates.push(InputValue::Struct(values));
+                    }
+                }
+                candidates
+            }
+            (Self::Enum { variants, .. }, InputValue::Enum { variant, value }) => {
+                let mut candidates = vec![];
+                if *variant != 0 {
+                    candidates.push(self.simplest());
+                }
+                for candidate in variants[*variant].0.shrink(value) {
+                    candidates
+                        .push(InputValue::Enum { variant: *variant, value: Box::new(candidate) });
+                }
+                candidates
+            }
+            (Self::Array(ty), InputValue::Array(values)) => {
+                let mut candidates = vec![];
+                if !values.is_empty() {
+                    candidates.push(InputValue::Array(vec![]));
+                }
+                if values.len() > 1 {
+                    candidates.push(InputValue::Array(values[..values.len() / 2].to_vec()));
+                    for idx in 0..values.len() {
+                        let mut values = values.clone();
+                        values.remove(idx);
+                        candidates.push(InputValue::Array(values));
+                    }
+                }
+                for (idx, value) in values.iter().enumerate() {
+                    for candidate in ty.shrink(value) {
+                        let mut values = values.clone();
+                        values[idx] = candidate;
+                        candidates.push(InputValue::Array(values));
+                    }
+                }
+                candidates
+            }
+            _ => unreachable!("Value does not match its type."),
+        }
+    }
+
+    /// Appends the runner arguments representing the given value.
+    pub fn push_args(&self, value: &InputValue, args: &mut Vec<Arg>) {
+        match (self, value) {
+            (Self::Int { .. }, InputValue::Int(value)) => {
+                args.push(Arg::Value(value.clone().into()))
+            }
+            (Self::Struct(members), InputValue::Struct(values)) => {
+                for (member, value) in members.iter().zip(values) {
+                    member.push_args(value, args);
+                }
+            }
+            (Self::Enum { variants, size }, InputValue::Enum { variant, value }) => {
+                let selector = get_variant_selector(variants.len(), *variant)
+                    .expect("Variant index is within the enum.");
+                args.push(Arg::Value(selector.into()));
+                // The variant is aligned to the end of the enum, padded with zeros.
+                let (ty, variant_size) = &variants[*variant];
+                args.extend((0..size - 1 - variant_size).map(|_| Arg::Value(Felt252::ZERO)));
+                ty.push_args(value, args);
+            }
+            (Self::Array(ty), InputValue::Array(values)) => {
+                let mut elements = vec![];
+                for value in values {
+                    ty.push_args(value, &mut elements);
+                }
+                args.push(Arg::Array(elements));
+            }
+            _ => unreachable!("Value does not match its type."),
+        }
+    }
+}
+
+impl std::fmt::Display for InputValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Struct(values) => write!(f, "({})", values.iter().format(", ")),
+            Self::Enum { variant, value } => write!(f, "variant{variant}({value})"),
+            Self::Array(values) => write!(f, "[{}]", values.iter().format(", ")),
+        }
+    }
+}
+
+/// Returns a uniformly random integer in the inclusive range `[min, max]`.
+fn random_in_range(rng: &mut StdRng, min: &BigInt, max: &BigInt) -> BigInt {
+    let width = (max - min + 1u32).magnitude().clone();
+    // Taking extra random bytes, so the bias of the modulo is negligible.
+    let mut bytes = vec![0u8; width.bits().div_ceil(8) as usize + 8];
+    rng.fill(&mut bytes[..]);
+    min + BigInt::from(BigUint::from_bytes_be(&bytes) % width)
+}
+
+/// Runs a fuzz test - `runs` times with generated parameter values, stopping at the first failing
+/// run, whose values are then shrunk.
+///
+/// `run` runs the test with the given arguments. The result is the one of the shrunk failing run
+/// if any, or otherwise the one of the last run, with the maximal gas usage of the runs.
+pub(crate) fn run_fuzz_test(
+    builder: &RunnableBuilder,
+    func: &Function,
+    config: &FuzzConfig,
+    default_seed: u64,
+    run: impl Fn(Vec<Arg>) -> Result<TestResult>,
+) -> Result<TestResult> {
+    let seed = config.seed.unwrap_or(default_seed);
+    let params_ty = InputType::Struct(
+        func.signature
+            .param_types
+            .iter()
+            .filter(|ty| builder.is_user_arg_type(&builder.type_long_id(ty).generic_id))
+            .map(|ty| InputType::from_sierra(builder, ty))
+            .try_collect()?,
+    );
+    let run_values = |values: &InputValue| {
+        let mut args = vec![];
+        params_ty.push_args(values, &mut args);
+        run(args)
+    };
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut max_gas_usage = None;
+    let mut last_result = None;
+    for run_idx in 0..config.runs {
+        let mut values = params_ty.generate(&mut rng);
+        let mut result = run_values(&values)?;
+        max_gas_usage = max_gas_usage.max(result.gas_usage);
+        if !matches!(result.status, TestStatus::Fail(_)) {
+            last_result = Some(result);
+            continue;
+        }
+        // Greedily replacing the values with the first simpler ones still failing the test.
+        let mut shrink_runs = 0;
+        'shrink: while shrink_runs < MAX_SHRINK_RUNS {
+            for candidate in params_ty.shrink(&values) {
+                if shrink_runs == MAX_SHRINK_RUNS {
+                    break 'shrink;
+                }
+                shrink_runs += 1;
+                let candidate_result = run_values(&candidate)?;
+                if matches!(candidate_result.status, TestStatus::Fail(_)) {
+                    values = candidate;
+                    result = candidate_result;
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        let TestStatus::Fail(message) = result.status else {
+            unreachable!("The shrunk run is failing.");
+        };
+        result.status = TestStatus::Fail(format!(
+            "{message} Failing parameters: {values}, found in run {} of {} with seed {seed}.",
+            run_idx + 1,
+            config.runs,
+        ));
+        return Ok(result);
+    }
+    let mut result = last_result.expect("Fuzz tests have at least one run.");
+    result.gas_usage = max_gas_usage;
+    Ok(result)
+}
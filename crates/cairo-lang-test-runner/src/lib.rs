@@ -1,6 +1,8 @@
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::mpsc::channel;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, bail};
 use cairo_lang_compiler::db::RootDatabase;
@@ -9,14 +11,16 @@ use cairo_lang_compiler::project::setup_project;
 use cairo_lang_debug::debug::DebugWithDb;
 use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
 use cairo_lang_filesystem::ids::CrateInput;
-use cairo_lang_runner::casm_run::{StarknetHintProcessor, format_for_panic};
+use cairo_lang_runner::casm_run::{StarknetHintProcessor, format_for_panic, format_panic_data};
+use cairo_lang_runner::coverage::{CoverageInfo, CoverageReport};
 use cairo_lang_runner::profiling::{
     ProfilerConfig, ProfilingInfo, ProfilingInfoProcessor, ProfilingInfoProcessorParams,
 };
 use cairo_lang_runner::{
-    CairoHintProcessor, ProfilingInfoCollectionConfig, RunResultValue, RunnerError,
+    Arg, CairoHintProcessor, ProfilingInfoCollectionConfig, RunResultValue, RunnerError,
     SierraCasmRunner, StarknetExecutionResources,
 };
+use cairo_lang_sierra::program::Function;
 use cairo_lang_sierra_to_casm::metadata::MetadataComputationConfig;
 use cairo_lang_starknet::starknet_plugin_suite;
 use cairo_lang_test_plugin::test_config::{PanicExpectation, TestExpectation};
@@ -24,13 +28,15 @@ use cairo_lang_test_plugin::{
     TestCompilation, TestCompilationMetadata, TestConfig, TestsCompilationConfig,
     compile_test_prepared_db, test_plugin_suite,
 };
+use cairo_lang_utils::CloneableDatabase;
 use cairo_lang_utils::casts::IntoOrPanic;
 use colored::Colorize;
 use itertools::Itertools;
 use num_traits::ToPrimitive;
-use rayon::prelude::{IntoParallelIterator, ParallelIterator};
-use salsa::Database;
+use rayon::prelude::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use serde::Serialize;
 
+mod fuzz;
 #[cfg(test)]
 mod test;
 
@@ -51,7 +57,7 @@ impl<'db> TestRunner<'db> {
     /// # Arguments
     ///
     /// * `path` - The path to compile and run its tests
-    /// * `filter` - Run only tests containing the filter string
+    /// * `filter` - Run only tests matching the filter, see [TestRunConfig::filter]
     /// * `include_ignored` - Include ignored tests as well
     /// * `ignored` - Run ignored tests only
     /// * `starknet` - Add the Starknet plugin to run the tests
@@ -143,8 +149,8 @@ impl<'db> CompiledTestRunner<'db> {
             &self.config.filter,
         );
 
-        let TestsSummary { passed, failed, ignored, failed_run_results } = run_tests(
-            opt_db.map(|db| db as &dyn Database),
+        let TestsSummary { passed, failed, ignored, failure_messages, .. } = run_tests(
+            opt_db.map(|db| db as &dyn CloneableDatabase),
             compiled,
             &self.config,
             self.custom_hint_processor_factory,
@@ -161,19 +167,8 @@ impl<'db> CompiledTestRunner<'db> {
             Ok(None)
         } else {
             println!("failures:");
-            for (failure, run_result) in failed.iter().zip_eq(failed_run_results) {
-                print!("   {failure} - ");
-                match run_result {
-                    Ok(RunResultValue::Success(_)) => {
-                        println!("expected panic but finished successfully.");
-                    }
-                    Ok(RunResultValue::Panic(values)) => {
-                        println!("{}", format_for_panic(values.into_iter()));
-                    }
-                    Err(err) => {
-                        println!("{err}");
-                    }
-                }
+            for (failure, message) in failed.iter().zip_eq(failure_messages) {
+                println!("   {failure} - {message}");
             }
             println!();
             bail!(
@@ -190,6 +185,9 @@ impl<'db> CompiledTestRunner<'db> {
 /// Configuration of compiled tests runner.
 #[derive(Clone, Debug)]
 pub struct TestRunConfig {
+    /// Runs only the tests whose names match the filter. A filter containing `*` is a pattern
+    /// matched against the whole name, where `*` matches any sequence of characters, e.g.
+    /// `*::math::test_*`. Any other filter matches the names containing it.
     pub filter: String,
     pub include_ignored: bool,
     pub ignored: bool,
@@ -199,6 +197,17 @@ pub struct TestRunConfig {
     pub gas_enabled: bool,
     /// Whether to print used resources after each test.
     pub print_resource_usage: bool,
+    /// If set, the line and branch coverage of the tests is written to this path, in the LCOV
+    /// format.
+    pub coverage_output: Option<PathBuf>,
+    /// The number of tests to run concurrently. Defaults to the number of available CPUs.
+    pub jobs: Option<usize>,
+    /// If set, a JSON report of the tests - the status, gas usage and duration of each - is
+    /// written to this path.
+    pub json_report: Option<PathBuf>,
+    /// The seed of the values generated for fuzz tests without a seed of their own. Defaults to a
+    /// random seed.
+    pub fuzz_seed: Option<u64>,
 }
 
 /// The test cases compiler.
@@ -272,7 +281,7 @@ impl<'db> TestCompiler<'db> {
 /// * `compiled` - Compiled test cases with metadata.
 /// * `include_ignored` - Include ignored tests as well.
 /// * `ignored` - Run ignored tests only
-/// * `filter` - Include only tests containing the filter string.
+/// * `filter` - Include only tests matching the filter, see [TestRunConfig::filter].
 /// # Returns
 /// * (`TestCompilation`, `usize`) - The filtered test cases and the number of filtered out cases.
 pub fn filter_test_cases<'db>(
@@ -295,7 +304,7 @@ pub fn filter_test_cases<'db>(
             }
             (func, test)
         })
-        .filter(|(name, _)| name.contains(filter))
+        .filter(|(name, _)| matches_filter(name, filter))
         .collect_vec();
     let filtered_out = total_tests_count - named_tests.len();
     let tests = TestCompilation {
@@ -305,10 +314,37 @@ pub fn filter_test_cases<'db>(
     (tests, filtered_out)
 }
 
+/// Returns true if the test name matches the filter, see [TestRunConfig::filter].
+fn matches_filter(name: &str, filter: &str) -> bool {
+    if !filter.contains('*') {
+        return name.contains(filter);
+    }
+    let mut parts = filter.split('*');
+    let (Some(prefix), Some(suffix)) = (parts.next(), parts.next_back()) else {
+        unreachable!("A filter containing `*` has at least two parts.");
+    };
+    let Some(mut rest) = name.strip_prefix(prefix).filter(|rest| rest.len() >= suffix.len()) else {
+        return false;
+    };
+    rest = &rest[..rest.len() - suffix.len()];
+    if !name.ends_with(suffix) {
+        return false;
+    }
+    // Matching the middle parts greedily from the left is enough, as `*` matches anything.
+    for part in parts {
+        let Some(idx) = rest.find(part) else {
+            return false;
+        };
+        rest = &rest[idx + part.len()..];
+    }
+    true
+}
+
 /// The status of a test run.
 enum TestStatus {
     Success,
-    Fail(RunResultValue),
+    /// The test failed, with the description of the failure.
+    Fail(String),
 }
 
 /// The result of a test run.
@@ -321,6 +357,10 @@ struct TestResult {
     used_resources: StarknetExecutionResources,
     /// The profiling info of the run, if requested.
     profiling_info: Option<ProfilingInfo>,
+    /// The coverage info of the run, if requested.
+    coverage_info: Option<CoverageInfo>,
+    /// The output printed by the run, if it was captured.
+    output: String,
 }
 
 /// Summary data of the tests run.
@@ -328,12 +368,52 @@ pub struct TestsSummary {
     passed: Vec<String>,
     failed: Vec<String>,
     ignored: Vec<String>,
-    failed_run_results: Vec<Result<RunResultValue>>,
+    /// The descriptions of the failures, in the order of `failed`.
+    failure_messages: Vec<String>,
+    /// The coverage info of all the runs, if requested.
+    coverage_info: CoverageInfo,
+    /// The reports of the tests, in the order they were run.
+    reports: Vec<TestReport>,
+}
+
+/// The report of a single test, as written to the JSON report.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TestReport {
+    /// The full name of the test.
+    pub name: String,
+    pub status: TestReportStatus,
+    /// The estimated gas usage of the test, if it ran and gas is enabled.
+    pub gas_usage: Option<i64>,
+    /// The number of steps the test used, if it ran.
+    pub steps: Option<usize>,
+    /// The duration of the test run, in milliseconds.
+    pub duration_ms: f64,
+    /// The description of the failure, if the test failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
+}
+
+/// The status of a test in the JSON report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestReportStatus {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+/// The JSON report of a tests run.
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    passed: usize,
+    failed: usize,
+    ignored: usize,
+    tests: &'a [TestReport],
 }
 
 /// Runs the tests and processes the results for a summary.
 pub fn run_tests(
-    opt_db: Option<&dyn Database>,
+    opt_db: Option<&dyn CloneableDatabase>,
     compiled: TestCompilation<'_>,
     config: &TestRunConfig,
     custom_hint_processor_factory: Option<ArcCustomHintProcessorFactory>,
@@ -380,15 +460,31 @@ pub fn run_tests(
         anyhow::anyhow!("{err}\n{}", locs.join("\n"))
     })
     .with_context(|| "Failed setting up runner.")?;
+    let runner = if config.coverage_output.is_some() { runner.with_coverage() } else { runner };
+    let casm_program = config.coverage_output.is_some().then(|| runner.casm_program().clone());
     let suffix = if named_tests.len() != 1 { "s" } else { "" };
     println!("running {} test{}", named_tests.len(), suffix);
 
+    // Each test runs with its own hint processor and Starknet state, and with its output
+    // captured unless a custom hint processor is used, so the tests are isolated from each other
+    // while sharing the compiled program.
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.jobs.unwrap_or_default())
+        .build()
+        .with_context(|| "Failed creating the tests thread pool.")?;
+    let fuzz_seed = config.fuzz_seed.unwrap_or_else(rand::random);
     let (tx, rx) = channel::<_>();
-    rayon::spawn(move || {
-        named_tests.into_par_iter().for_each(|(name, test)| {
-            let result =
-                run_single_test(test, &name, &runner, custom_hint_processor_factory.clone());
-            tx.send((name, result)).unwrap();
+    pool.spawn(move || {
+        named_tests.into_par_iter().enumerate().for_each(|(idx, (name, test))| {
+            let start = Instant::now();
+            let result = run_single_test(
+                test,
+                &name,
+                &runner,
+                custom_hint_processor_factory.clone(),
+                fuzz_seed,
+            );
+            tx.send((idx, name, result, start.elapsed())).unwrap();
         })
     });
 
@@ -403,6 +499,7 @@ pub fn run_tests(
                 Some(db),
                 &sierra_program,
                 statements_locations
+                    .as_ref()
                     .expect(
                         "statements locations must be present when doing cairo level profiling.",
                     )
@@ -416,54 +513,118 @@ pub fn run_tests(
         passed: vec![],
         failed: vec![],
         ignored: vec![],
-        failed_run_results: vec![],
+        failure_messages: vec![],
+        coverage_info: CoverageInfo::default(),
+        reports: vec![],
     };
-    while let Ok((name, result)) = rx.recv() {
-        update_summary(&mut summary, name, result, &profiler_data, config.print_resource_usage);
+    // Reporting the results in the order of the tests, regardless of the order they finish in.
+    let mut pending_results = BTreeMap::new();
+    let mut next_idx = 0;
+    while let Ok((idx, name, result, duration)) = rx.recv() {
+        pending_results.insert(idx, (name, result, duration));
+        while let Some((name, result, duration)) = pending_results.remove(&next_idx) {
+            update_summary(
+                &mut summary,
+                name,
+                result,
+                duration,
+                &profiler_data,
+                config.print_resource_usage,
+            );
+            next_idx += 1;
+        }
+    }
+
+    if let Some(json_report) = &config.json_report {
+        let report = JsonReport {
+            passed: summary.passed.len(),
+            failed: summary.failed.len(),
+            ignored: summary.ignored.len(),
+            tests: &summary.reports,
+        };
+        let report = serde_json::to_string_pretty(&report)
+            .with_context(|| "Failed serializing the JSON report.")?;
+        std::fs::write(json_report, report).with_context(|| {
+            format!("Failed writing the JSON report to `{}`.", json_report.display())
+        })?;
+    }
+
+    if let (Some(coverage_output), Some(casm_program)) = (&config.coverage_output, casm_program) {
+        let db = opt_db.expect("db must be passed when collecting coverage.");
+        let code_locations = statements_locations
+            .expect("statements locations must be present when collecting coverage.")
+            .extract_statements_source_code_locations(db);
+        let report = CoverageReport::new(
+            &sierra_program,
+            &casm_program,
+            &code_locations,
+            &summary.coverage_info,
+        );
+        std::fs::write(coverage_output, report.to_lcov()).with_context(|| {
+            format!("Failed writing the coverage report to `{}`.", coverage_output.display())
+        })?;
     }
 
     Ok(summary)
 }
 
-/// Runs a single test and returns a tuple of its name and result.
+/// Runs a single test and returns its result, or `None` if it is ignored.
+/// Fuzz tests are run with values generated from `fuzz_seed`, unless they have a seed of their own.
 fn run_single_test(
     test: TestConfig,
     name: &str,
     runner: &SierraCasmRunner,
     custom_hint_processor_factory: Option<ArcCustomHintProcessorFactory>,
+    fuzz_seed: u64,
 ) -> Result<Option<TestResult>> {
     if test.ignored {
         return Ok(None);
     }
     let func = runner.find_function(name)?;
+    let run =
+        |args| run_test_with_args(&test, func, args, runner, custom_hint_processor_factory.clone());
+    Ok(Some(match &test.fuzz {
+        Some(fuzz_config) => {
+            fuzz::run_fuzz_test(runner.builder(), func, fuzz_config, fuzz_seed, run)?
+        }
+        None => run(vec![])?,
+    }))
+}
 
-    let (hint_processor, ctx) =
-        runner.prepare_starknet_context(func, vec![], test.available_gas, Default::default())?;
+/// Runs a test once, with the given arguments.
+fn run_test_with_args(
+    test: &TestConfig,
+    func: &Function,
+    args: Vec<Arg>,
+    runner: &SierraCasmRunner,
+    custom_hint_processor_factory: Option<ArcCustomHintProcessorFactory>,
+) -> Result<TestResult> {
+    let (mut hint_processor, ctx) =
+        runner.prepare_starknet_context(func, args, test.available_gas, Default::default())?;
 
     let mut hint_processor = match custom_hint_processor_factory {
         Some(f) => f(hint_processor),
-        None => Box::new(hint_processor),
+        None => {
+            // The output is only captured by the default hint processor, as custom hint processors
+            // may not forward the captured output, which would then be lost.
+            hint_processor.captured_output = Some(String::new());
+            Box::new(hint_processor)
+        }
     };
 
     let result =
         runner.run_function_with_prepared_starknet_context(func, &mut *hint_processor, ctx)?;
-
-    Ok(Some(TestResult {
-        status: match &result.value {
-            RunResultValue::Success(_) => match test.expectation {
-                TestExpectation::Success => TestStatus::Success,
-                TestExpectation::Panics(_) => TestStatus::Fail(result.value),
-            },
-            RunResultValue::Panic(value) => match test.expectation {
-                TestExpectation::Success => TestStatus::Fail(result.value),
-                TestExpectation::Panics(panic_expectation) => match panic_expectation {
-                    PanicExpectation::Exact(expected) if value != &expected => {
-                        TestStatus::Fail(result.value)
-                    }
-                    _ => TestStatus::Success,
-                },
-            },
-        },
+    let output = hint_processor.take_captured_output().unwrap_or_default();
+
+    let n_steps = result.used_resources.basic_resources.n_steps;
+    let status = match (test_status(result.value, test.expectation.clone()), test.max_steps) {
+        (TestStatus::Success, Some(max_steps)) if n_steps > max_steps => TestStatus::Fail(format!(
+            "used {n_steps} steps, exceeding the budget of {max_steps} steps."
+        )),
+        (status, _) => status,
+    };
+    Ok(TestResult {
+        status,
         gas_usage: test
             .available_gas
             .zip(result.gas_counter)
@@ -473,7 +634,39 @@ fn run_single_test(
             .or_else(|| runner.initial_required_gas(func).map(|gas| gas.into_or_panic::<i64>())),
         used_resources: result.used_resources,
         profiling_info: result.profiling_info,
-    }))
+        coverage_info: result.coverage_info,
+        output,
+    })
+}
+
+/// Returns the status of a test, given the value of its run and its expectation.
+fn test_status(value: RunResultValue, expectation: TestExpectation) -> TestStatus {
+    match (value, expectation) {
+        (RunResultValue::Success(_), TestExpectation::Success)
+        | (RunResultValue::Panic(_), TestExpectation::Panics(PanicExpectation::Any)) => {
+            TestStatus::Success
+        }
+        (RunResultValue::Success(_), TestExpectation::Panics(_)) => {
+            TestStatus::Fail("expected panic but finished successfully.".into())
+        }
+        (RunResultValue::Panic(values), TestExpectation::Success) => {
+            TestStatus::Fail(format_for_panic(values.into_iter()))
+        }
+        (
+            RunResultValue::Panic(values),
+            TestExpectation::Panics(PanicExpectation::Exact(expected)),
+        ) => {
+            if values == expected {
+                TestStatus::Success
+            } else {
+                TestStatus::Fail(format!(
+                    "Panicked with {}, but expected a panic with {}.",
+                    format_panic_data(values.into_iter()),
+                    format_panic_data(expected.into_iter())
+                ))
+            }
+        }
+    }
 }
 
 /// Updates the test summary with the given test result.
@@ -481,23 +674,38 @@ fn update_summary(
     summary: &mut TestsSummary,
     name: String,
     test_result: Result<Option<TestResult>>,
+    duration: Duration,
     profiler_data: &Option<(ProfilingInfoProcessor<'_>, ProfilingInfoProcessorParams)>,
     print_resource_usage: bool,
 ) {
+    let mut failure = None;
+    let mut steps = None;
+    let mut report_status = TestReportStatus::Failed;
     let (res_type, status_str, gas_usage, used_resources, profiling_info) = match test_result {
-        Ok(None) => (&mut summary.ignored, "ignored".bright_yellow(), None, None, None),
+        Ok(None) => {
+            report_status = TestReportStatus::Ignored;
+            (&mut summary.ignored, "ignored".bright_yellow(), None, None, None)
+        }
         Err(err) => {
-            summary.failed_run_results.push(Err(err));
+            failure = Some(err.to_string());
             (&mut summary.failed, "failed to run".bright_magenta(), None, None, None)
         }
         Ok(Some(result)) => {
+            print!("{}", result.output);
+            steps = Some(result.used_resources.basic_resources.n_steps);
             let (res_type, status_str) = match result.status {
-                TestStatus::Success => (&mut summary.passed, "ok".bright_green()),
-                TestStatus::Fail(run_result) => {
-                    summary.failed_run_results.push(Ok(run_result));
+                TestStatus::Success => {
+                    report_status = TestReportStatus::Passed;
+                    (&mut summary.passed, "ok".bright_green())
+                }
+                TestStatus::Fail(message) => {
+                    failure = Some(message);
                     (&mut summary.failed, "fail".bright_red())
                 }
             };
+            if let Some(coverage_info) = &result.coverage_info {
+                summary.coverage_info.merge(coverage_info);
+            }
             (
                 res_type,
                 status_str,
@@ -543,7 +751,16 @@ fn update_summary(
         );
         println!("Profiling info:\n{processed_profiling_info}");
     }
-    res_type.push(name);
+    res_type.push(name.clone());
+    summary.failure_messages.extend(failure.clone());
+    summary.reports.push(TestReport {
+        name,
+        status: report_status,
+        gas_usage,
+        steps,
+        duration_ms: duration.as_secs_f64() * 1000.0,
+        failure,
+    });
 }
 
 /// Given an iterator of (String, usize) pairs, prints a usage map. E.g.:
@@ -3,10 +3,13 @@ use cairo_lang_sierra::program::{Program, ProgramArtifact};
 use cairo_lang_test_plugin::test_config::TestExpectation;
 use cairo_lang_test_plugin::{TestCompilationMetadata, TestConfig, TestsCompilationConfig};
 use cairo_lang_utils::byte_array::BYTE_ARRAY_MAGIC;
+use indoc::indoc;
 use itertools::Itertools;
 use starknet_types_core::felt::Felt as Felt252;
 
-use crate::{TestCompilation, TestCompiler, filter_test_cases};
+use crate::{
+    TestCompilation, TestCompiler, TestRunConfig, TestRunner, filter_test_cases, run_tests,
+};
 
 #[test]
 fn test_compiled_serialization() {
@@ -42,6 +45,37 @@ fn test_compiled_serialization() {
     );
 }
 
+#[test]
+fn test_coverage_report() {
+    use std::path::PathBuf;
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data");
+    let coverage_output = std::env::temp_dir().join("cairo_lang_test_runner_coverage.lcov");
+
+    let config = TestRunConfig {
+        filter: String::new(),
+        include_ignored: false,
+        ignored: false,
+        profiler_config: None,
+        gas_enabled: true,
+        print_resource_usage: false,
+        coverage_output: Some(coverage_output.clone()),
+        jobs: None,
+        json_report: None,
+        fuzz_seed: None,
+    };
+    TestRunner::new(&path, true, true, config).unwrap().run().unwrap();
+    let lcov = std::fs::read_to_string(&coverage_output).unwrap();
+    std::fs::remove_file(&coverage_output).unwrap();
+
+    let test_file = path.join("lib.cairo");
+    let record = lcov
+        .split("end_of_record\n")
+        .find(|record| record.contains(&format!("SF:{}\n", test_file.display())))
+        .expect("Missing the coverage of the tested file.");
+    // The first line of `test_flow`.
+    assert!(record.contains("DA:39,1\n"), "Unexpected coverage record:\n{record}");
+}
+
 #[test]
 fn test_format_for_panic() {
     // Valid short string.
@@ -236,7 +270,13 @@ fn test_format_for_panic() {
 fn to_named_test(test: &(&str, bool)) -> (String, TestConfig) {
     (
         String::from(test.0),
-        TestConfig { available_gas: None, expectation: TestExpectation::Success, ignored: test.1 },
+        TestConfig {
+            available_gas: None,
+            expectation: TestExpectation::Success,
+            ignored: test.1,
+            fuzz: None,
+            max_steps: None,
+        },
     )
 }
 
@@ -309,3 +349,253 @@ fn test_filter_test_cases_include_ignored_and_ignored() {
         (to_test_compilation(&[("test1", false), ("test2", false), ("test3", false)]), 0)
     );
 }
+
+#[test]
+fn test_filter_test_cases_pattern() {
+    let compilation = || {
+        to_test_compilation(&[
+            ("crate::math::test_add", false),
+            ("crate::math::check_sub", false),
+            ("crate::io::test_read", false),
+        ])
+    };
+    assert_eq!(
+        filter_test_cases(compilation(), false, false, "*::math::test_*"),
+        (to_test_compilation(&[("crate::math::test_add", false)]), 2)
+    );
+    assert_eq!(
+        filter_test_cases(compilation(), false, false, "crate::*_read"),
+        (to_test_compilation(&[("crate::io::test_read", false)]), 2)
+    );
+    // A pattern must match the whole name.
+    assert_eq!(
+        filter_test_cases(compilation(), false, false, "math::*"),
+        (to_test_compilation(&[]), 3)
+    );
+}
+
+#[test]
+fn test_json_report() {
+    let dir = std::env::temp_dir().join("cairo_lang_test_runner_json_report");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("lib.cairo");
+    std::fs::write(
+        &path,
+        indoc! {r#"
+            #[test]
+            fn test_pass() {}
+
+            #[test]
+            #[ignore]
+            fn test_ignored() {}
+
+            #[test]
+            #[should_panic(expected: "Out of bounds")]
+            fn test_expected_panic() {
+                panic!("Out of bounds");
+            }
+
+            #[test]
+            #[should_panic(expected: "Out of bounds")]
+            fn test_other_panic() {
+                panic!("Overflow");
+            }
+
+            #[test]
+            #[max_steps(1000000)]
+            fn test_within_steps_budget() {
+                let mut i: u32 = 0;
+                while i != 10 {
+                    i += 1;
+                }
+            }
+
+            #[test]
+            #[max_steps(10)]
+            fn test_exceeding_steps_budget() {
+                let mut i: u32 = 0;
+                while i != 10 {
+                    i += 1;
+                }
+            }
+        "#},
+    )
+    .unwrap();
+    let json_report = dir.join("report.json");
+
+    let config = TestRunConfig {
+        filter: String::new(),
+        include_ignored: false,
+        ignored: false,
+        profiler_config: None,
+        gas_enabled: true,
+        print_resource_usage: false,
+        coverage_output: None,
+        jobs: None,
+        json_report: Some(json_report.clone()),
+        fuzz_seed: None,
+    };
+    let runner = TestRunner::new(&path, false, true, config).unwrap();
+    assert!(runner.run().is_err());
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_report).unwrap()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(report["passed"], 3);
+    assert_eq!(report["failed"], 2);
+    assert_eq!(report["ignored"], 1);
+    let tests = report["tests"].as_array().unwrap();
+    let test = |name: &str| {
+        tests
+            .iter()
+            .find(|test| test["name"].as_str().unwrap().ends_with(name))
+            .unwrap_or_else(|| panic!("Missing test `{name}` in the report: {report}"))
+    };
+    assert_eq!(test("test_pass")["status"], "passed");
+    assert!(test("test_pass")["gas_usage"].is_i64());
+    assert!(test("test_pass")["steps"].is_u64());
+    assert!(test("test_pass")["duration_ms"].as_f64().unwrap() >= 0.0);
+    assert_eq!(test("test_ignored")["status"], "ignored");
+    assert_eq!(test("test_ignored")["gas_usage"], serde_json::Value::Null);
+    assert_eq!(test("test_ignored")["steps"], serde_json::Value::Null);
+    assert_eq!(test("test_expected_panic")["status"], "passed");
+    assert_eq!(test("test_other_panic")["status"], "failed");
+    assert_eq!(
+        test("test_other_panic")["failure"],
+        r#"Panicked with "Overflow", but expected a panic with "Out of bounds"."#
+    );
+    assert_eq!(test("test_within_steps_budget")["status"], "passed");
+    let exceeding = test("test_exceeding_steps_budget");
+    assert_eq!(exceeding["status"], "failed");
+    assert_eq!(
+        exceeding["failure"],
+        format!("used {} steps, exceeding the budget of 10 steps.", exceeding["steps"])
+    );
+}
+
+#[test]
+fn test_fuzz() {
+    let dir = std::env::temp_dir().join("cairo_lang_test_runner_fuzz");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("lib.cairo");
+    std::fs::write(
+        &path,
+        indoc! {r#"
+            #[derive(Drop, Serde)]
+            struct Point {
+                x: felt252,
+                y: i8,
+            }
+
+            #[test]
+            #[fuzz(runs: 20)]
+            fn test_commutative(a: u32, b: u32, point: Point, maybe: Option<u8>) {
+                let (a, b): (u64, u64) = (a.into(), b.into());
+                assert!(a + b == b + a);
+                assert!(point.x + point.y.into() == point.y.into() + point.x);
+                assert!(maybe.unwrap_or(0) <= 255);
+            }
+
+            #[test]
+            #[fuzz(seed: 3)]
+            fn test_small(x: u16, values: Array<u8>) {
+                assert!(x < 1000);
+            }
+
+            #[test]
+            #[fuzz(seed: 5)]
+            fn test_small_elements(values: Array<u8>) {
+                for value in values.span() {
+                    assert!(*value < 200);
+                }
+            }
+        "#},
+    )
+    .unwrap();
+    let json_report = dir.join("report.json");
+
+    let config = TestRunConfig {
+        filter: String::new(),
+        include_ignored: false,
+        ignored: false,
+        profiler_config: None,
+        gas_enabled: true,
+        print_resource_usage: false,
+        coverage_output: None,
+        jobs: None,
+        json_report: Some(json_report.clone()),
+        fuzz_seed: Some(1),
+    };
+    let runner = TestRunner::new(&path, false, true, config).unwrap();
+    assert!(runner.run().is_err());
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_report).unwrap()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let tests = report["tests"].as_array().unwrap();
+    let test = |name: &str| {
+        tests
+            .iter()
+            .find(|test| test["name"].as_str().unwrap().ends_with(name))
+            .unwrap_or_else(|| panic!("Missing test `{name}` in the report: {report}"))
+    };
+    assert_eq!(test("test_commutative")["status"], "passed");
+    // The failing values are shrunk to the simplest ones.
+    let failure = test("test_small")["failure"].as_str().unwrap();
+    assert!(failure.contains("Failing parameters: (1000, []), found in run"), "{failure}");
+    assert!(failure.ends_with("with seed 3."), "{failure}");
+    let failure = test("test_small_elements")["failure"].as_str().unwrap();
+    assert!(failure.contains("Failing parameters: ([200]), found in run"), "{failure}");
+}
+
+#[test]
+fn test_parallel_run_ordering() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("lib.cairo");
+    let tests = (0..8)
+        .map(|i| {
+            // Every third test fails.
+            let outcome = if i % 3 == 0 { "assert!(false);" } else { "" };
+            format!("#[test]\nfn test_{i}() {{\n    println!(\"{i}\");\n    {outcome}\n}}\n")
+        })
+        .join("\n");
+    std::fs::write(&path, tests).unwrap();
+
+    let compiler = TestCompiler::try_new(
+        &path,
+        true,
+        true,
+        TestsCompilationConfig {
+            starknet: false,
+            add_statements_functions: false,
+            add_statements_code_locations: false,
+            add_functions_debug_info: false,
+            contract_declarations: None,
+            contract_crate_ids: None,
+            executable_crate_ids: None,
+            replace_ids: false,
+        },
+    )
+    .unwrap();
+    let config = TestRunConfig {
+        filter: String::new(),
+        include_ignored: false,
+        ignored: false,
+        profiler_config: None,
+        gas_enabled: true,
+        print_resource_usage: false,
+        coverage_output: None,
+        jobs: Some(4),
+        json_report: None,
+        fuzz_seed: None,
+    };
+    let compiled = compiler.build().unwrap();
+    let names = compiled.metadata.named_tests.iter().map(|(name, _)| name.clone()).collect_vec();
+    let summary = run_tests(None, compiled, &config, None).unwrap();
+
+    let (expected_failed, expected_passed): (Vec<_>, Vec<_>) = names
+        .into_iter()
+        .partition(|name| ["test_0", "test_3", "test_6"].iter().any(|t| name.ends_with(t)));
+    assert_eq!(summary.passed, expected_passed);
+    assert_eq!(summary.failed, expected_failed);
+}
@@ -4,8 +4,8 @@
 #[path = "parse_test_file_test.rs"]
 mod test;
 use std::fs;
-use std::io::{self, BufRead};
-use std::path::Path;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
 
 use cairo_lang_formatter::CairoFormatter;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
@@ -98,6 +98,11 @@ pub fn dump_to_test_file(
     tests: OrderedHashMap<String, Test>,
     filename: &str,
 ) -> Result<(), std::io::Error> {
+    fs::write(filename, test_file_content(tests))
+}
+
+/// Returns the content of a test file holding the given tests.
+fn test_file_content(tests: OrderedHashMap<String, Test>) -> String {
     let mut test_strings = Vec::new();
     for (test_name, test) in tests {
         let mut tag_strings = vec![TAG_PREFIX.to_string() + &test_name];
@@ -111,10 +116,7 @@ pub fn dump_to_test_file(
         }
         test_strings.push(tag_strings.join("\n\n"));
     }
-    fs::write(
-        filename,
-        test_strings.join(&("\n\n".to_string() + TAG_PREFIX + TEST_SEPARATOR + "\n\n")) + "\n",
-    )
+    test_strings.join(&("\n\n".to_string() + TAG_PREFIX + TEST_SEPARATOR + "\n\n")) + "\n"
 }
 
 impl TestBuilder {
@@ -357,11 +359,40 @@ impl TestFileRunner for SimpleRunner {
 ///     test_to_upper
 /// );
 /// ```
+///
+/// Adding `parallel` as the last argument (after the allowed arguments, if any) creates a single
+/// test named `all_files` instead, running all the test files in parallel, each on its own thread.
 #[macro_export]
 macro_rules! test_file_test {
     ($suite:ident, $base_dir:expr, { $($test_name:ident : $test_file:expr),* $(,)? }, $test_func:ident) => {
         cairo_lang_test_utils::test_file_test!($suite, $base_dir, { $($test_name : $test_file),* }, $test_func, [,]);
     };
+    ($suite:ident, $base_dir:expr, { $($test_name:ident : $test_file:expr),* $(,)? }, $test_func:ident, parallel) => {
+        cairo_lang_test_utils::test_file_test!($suite, $base_dir, { $($test_name : $test_file),* }, $test_func, [,], parallel);
+    };
+    ($suite:ident, $base_dir:expr, { $($test_name:ident : $test_file:expr),* $(,)? }, $test_func:ident, [$($allowed_arg:expr),* $(,)?], parallel) => {
+        mod $suite {
+            use super::*;
+
+            #[cairo_lang_test_utils::test]
+            fn all_files() -> Result<(), std::io::Error> {
+                let paths: Vec<(&str, std::path::PathBuf)> = vec![$(
+                    (
+                        stringify!($test_name),
+                        [env!("CARGO_MANIFEST_DIR"), $base_dir, $test_file].iter().collect(),
+                    )
+                ),*];
+                cairo_lang_test_utils::parse_test_file::run_test_files_parallel(
+                    &paths,
+                    stringify!($test_func),
+                    || cairo_lang_test_utils::parse_test_file::SimpleRunner {
+                        func: $test_func,
+                        allowed_args: vec![$($allowed_arg),*],
+                    },
+                )
+            }
+        }
+    };
     ($suite:ident, $base_dir:expr, { $($test_name:ident : $test_file:expr),* $(,)? }, $test_func:ident, [$($allowed_arg:expr),* $(,)?]) => {
         mod $suite {
             use super::*;
@@ -388,7 +419,10 @@ macro_rules! test_file_test {
 }
 
 /// Runs a test based on file at `path` named `test_func_name` by running `test_func` on it.
-/// Fixes the test file if the `CAIRO_FIX_TESTS` environment variable is set to `1`.
+/// Fixes the test file if the `CAIRO_FIX_TESTS` environment variable is set to `1` - rewriting the
+/// file only if its content changed, and reporting the updated tags. The report is also appended
+/// to the file at `CAIRO_FIX_TESTS_SUMMARY`, if set, to collect the updates of all the test files
+/// of a run.
 pub fn run_test_file(
     path: &Path,
     runner_name: &str,
@@ -398,6 +432,7 @@ pub fn run_test_file(
     let is_fix_mode = std::env::var("CAIRO_FIX_TESTS") == Ok("1".into());
     let is_format_mode = std::env::var("CAIRO_SKIP_FORMAT_TESTS") != Ok("1".into());
     let filter = std::env::var("CAIRO_TEST_FILTER").unwrap_or_default();
+    let tag_filter = std::env::var("CAIRO_TEST_TAG_FILTER").unwrap_or_default();
 
     let tests = parse_test_file(path)?;
     let full_filename = std::fs::canonicalize(path)?;
@@ -408,8 +443,9 @@ pub fn run_test_file(
     let mut errors = Vec::new();
     let mut passed_tests = 0;
     let mut failed_tests = Vec::new();
+    let mut updated_tests = Vec::new();
     for (test_name, mut test) in tests {
-        if !test_name.contains(&filter) {
+        if !is_test_selected(&test_name, &test, &filter, &tag_filter) {
             new_tests.insert(test_name, test);
             continue;
         }
@@ -447,6 +483,7 @@ pub fn run_test_file(
         pretty_assertions::assert_eq!(name, runner_name);
 
         let mut cur_test_errors = Vec::new();
+        let original_attributes = test.attributes.clone();
         if is_format_mode {
             test.format_inputs(&test_name, is_fix_mode, &mut cur_test_errors);
         }
@@ -461,6 +498,15 @@ pub fn run_test_file(
             for (key, value) in result.outputs.iter() {
                 new_test.attributes.insert(key.to_string(), value.trim_end().to_string());
             }
+            let updated_tags = new_test
+                .attributes
+                .iter()
+                .filter(|(tag, value)| original_attributes.get(*tag) != Some(*value))
+                .map(|(tag, _)| tag.clone())
+                .collect::<Vec<_>>();
+            if !updated_tags.is_empty() {
+                updated_tests.push((test_name.clone(), updated_tags));
+            }
             new_tests.insert(test_name.to_string(), new_test);
         }
 
@@ -501,7 +547,19 @@ pub fn run_test_file(
         passed_tests += 1;
     }
     if is_fix_mode {
-        dump_to_test_file(new_tests, path.to_str().unwrap())?;
+        let content = test_file_content(new_tests);
+        if fs::read_to_string(path)? != content {
+            fs::write(path, content)?;
+        }
+    }
+    if !updated_tests.is_empty() {
+        let summary = fix_summary(full_filename_str, &updated_tests);
+        eprint!("{summary}");
+        if let Ok(summary_path) = std::env::var("CAIRO_FIX_TESTS_SUMMARY") {
+            let mut summary_file =
+                fs::OpenOptions::new().create(true).append(true).open(summary_path)?;
+            summary_file.write_all(summary.as_bytes())?;
+        }
     }
 
     assert!(
@@ -514,8 +572,70 @@ pub fn run_test_file(
     Ok(())
 }
 
+/// Runs the test files at `paths` in parallel, each on its own thread with a runner created by
+/// `make_runner`, and fails with the errors of all the failed files.
+/// `paths` holds the path of each test file along with a name identifying it in the report.
+pub fn run_test_files_parallel<R: TestFileRunner>(
+    paths: &[(&str, PathBuf)],
+    runner_name: &str,
+    make_runner: impl Fn() -> R + Sync,
+) -> Result<(), std::io::Error> {
+    let results = std::thread::scope(|s| {
+        let handles = paths
+            .iter()
+            .map(|(name, path)| {
+                let make_runner = &make_runner;
+                (name, s.spawn(move || run_test_file(path, runner_name, &mut make_runner())))
+            })
+            .collect::<Vec<_>>();
+        handles.into_iter().map(|(name, handle)| (name, handle.join())).collect::<Vec<_>>()
+    });
+
+    let mut failed_files = Vec::new();
+    for (name, result) in results {
+        match result {
+            Ok(result) => result?,
+            Err(panic) => {
+                let message = panic
+                    .downcast_ref::<String>()
+                    .map(String::as_str)
+                    .or_else(|| panic.downcast_ref::<&str>().copied())
+                    .unwrap_or("<unknown panic>");
+                failed_files.push(format!("Test file `{name}` failed:{message}"));
+            }
+        }
+    }
+    assert!(failed_files.is_empty(), "\n\n{}\n", failed_files.join("\n\n"));
+
+    Ok(())
+}
+
+/// Returns true if the test should be run, given the test name filter from `CAIRO_TEST_FILTER`,
+/// and the tag filter from `CAIRO_TEST_TAG_FILTER` - a comma separated list of tags, of which the
+/// test must have at least one.
+fn is_test_selected(test_name: &str, test: &Test, filter: &str, tag_filter: &str) -> bool {
+    if !test_name.contains(filter) {
+        return false;
+    }
+    tag_filter.is_empty()
+        || tag_filter.split(',').any(|tag| test.attributes.contains_key(tag.trim()))
+}
+
 fn summary(passed_tests: usize, failed_tests: &[String]) -> String {
     let passed = format!("{passed_tests} passed").green();
     let failed = format!("{} failed", failed_tests.len()).red();
     format!("Summary: {passed}, {failed}:\n{}", failed_tests.join("\n"))
 }
+
+/// Returns the report of the tests updated in fix mode, with their updated tags, e.g.:
+/// ```text
+/// Updated /path/to/test_file:
+///   "Test name": expected_diagnostics, lowering
+/// ```
+fn fix_summary(filename: &str, updated_tests: &[(String, Vec<String>)]) -> String {
+    let mut summary = format!("Updated {filename}:\n");
+    for (test_name, tags) in updated_tests {
+        summary += &format!("  \"{test_name}\": {}\n", tags.join(", "));
+    }
+    summary
+}
@@ -28,3 +28,30 @@ fn test_dump_to_test_file() -> Result<(), std::io::Error> {
     );
     std::fs::remove_file("test_data/test_example_expected")
 }
+
+#[test]
+fn test_fix_summary() {
+    let updated_tests = [
+        ("Test Example".to_string(), vec!["Expected".to_string()]),
+        ("Another Test Example".to_string(), vec!["Expected".to_string(), "Empty".to_string()]),
+    ];
+    assert_eq!(
+        parse_test_file::fix_summary("test_data/test_example", &updated_tests),
+        "Updated test_data/test_example:\n  \"Test Example\": Expected\n  \"Another Test \
+         Example\": Expected, Empty\n"
+    );
+}
+
+#[test]
+fn test_is_test_selected() -> Result<(), std::io::Error> {
+    let tests = parse_test_file::parse_test_file(Path::new("test_data/test_example"))?;
+    let test = &tests["Another Test Example"];
+    assert!(parse_test_file::is_test_selected("Another Test Example", test, "", ""));
+    assert!(parse_test_file::is_test_selected("Another Test Example", test, "Another", ""));
+    assert!(!parse_test_file::is_test_selected("Another Test Example", test, "Missing", ""));
+    assert!(parse_test_file::is_test_selected("Another Test Example", test, "", "Empty"));
+    assert!(parse_test_file::is_test_selected("Another Test Example", test, "", "Missing, Empty"));
+    assert!(!parse_test_file::is_test_selected("Another Test Example", test, "", "Missing"));
+    assert!(!parse_test_file::is_test_selected("Another Test Example", test, "Missing", "Empty"));
+    Ok(())
+}
@@ -0,0 +1,58 @@
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// A value whose clones share it until one of them is modified.
+///
+/// Cloning is a reference count increment, and the first modification of a shared value clones it.
+/// Useful for states that are cloned often but rarely modified, e.g. the infos of dataflow analyses
+/// flowing through edges.
+#[derive(Clone, Debug, Default)]
+pub struct CopyOnWrite<T>(Arc<T>);
+
+impl<T> CopyOnWrite<T> {
+    /// Wraps a value.
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Returns true if the value is shared with another clone.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.0) > 1
+    }
+}
+
+impl<T: Clone> CopyOnWrite<T> {
+    /// Returns a mutable reference to the value, cloning it first if it is shared.
+    pub fn to_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// Returns the value, cloning it if it is shared.
+    pub fn into_inner(self) -> T {
+        Arc::unwrap_or_clone(self.0)
+    }
+}
+
+impl<T> core::ops::Deref for CopyOnWrite<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: PartialEq> PartialEq for CopyOnWrite<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+impl<T: Eq> Eq for CopyOnWrite<T> {}
+
+impl<T> From<T> for CopyOnWrite<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
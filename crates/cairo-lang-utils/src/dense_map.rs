@@ -0,0 +1,139 @@
+#[cfg(test)]
+#[path = "dense_map_test.rs"]
+mod test;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A key of a [DenseMap] - an id that is a small index, such as the ids of arenas.
+pub trait DenseKey: Copy {
+    /// Returns the index of the id.
+    fn index(self) -> usize;
+}
+
+impl DenseKey for usize {
+    fn index(self) -> usize {
+        self
+    }
+}
+
+#[cfg(feature = "id-arena")]
+impl<T> DenseKey for id_arena::Id<T> {
+    fn index(self) -> usize {
+        id_arena::Id::index(&self)
+    }
+}
+
+/// A map from ids that are small indices, stored in a vector indexed by the ids.
+///
+/// Lookups are a single indexing, and cloning is a copy of the vector, which is cheaper than
+/// cloning a hash map when most of the ids of a range are keys of the map. Iteration is in the
+/// order of the ids, which is deterministic.
+#[derive(Clone, Debug)]
+pub struct DenseMap<Key, Value> {
+    /// The entries of the map, at the indices of their keys.
+    entries: Vec<Option<(Key, Value)>>,
+    /// The number of entries in the map.
+    len: usize,
+}
+
+impl<Key, Value> Default for DenseMap<Key, Value> {
+    fn default() -> Self {
+        Self { entries: Vec::new(), len: 0 }
+    }
+}
+
+impl<Key: DenseKey, Value> DenseMap<Key, Value> {
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a reference to the value of the key.
+    pub fn get(&self, key: Key) -> Option<&Value> {
+        self.entries.get(key.index())?.as_ref().map(|(_, value)| value)
+    }
+
+    /// Returns a mutable reference to the value of the key.
+    pub fn get_mut(&mut self, key: Key) -> Option<&mut Value> {
+        self.entries.get_mut(key.index())?.as_mut().map(|(_, value)| value)
+    }
+
+    /// Returns true if the map contains a value for the key.
+    pub fn contains_key(&self, key: Key) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map, returning the previous value of the key.
+    pub fn insert(&mut self, key: Key, value: Value) -> Option<Value> {
+        let previous = self.slot(key).replace((key, value)).map(|(_, value)| value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    /// Removes a key from the map, returning its value.
+    pub fn remove(&mut self, key: Key) -> Option<Value> {
+        let (_, value) = self.entries.get_mut(key.index())?.take()?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Returns a mutable reference to the value of the key, inserting the value returned by
+    /// `default` if the key is not in the map.
+    pub fn get_or_insert_with(&mut self, key: Key, default: impl FnOnce() -> Value) -> &mut Value {
+        let slot = self.slot(key);
+        if slot.is_none() {
+            self.len += 1;
+        }
+        &mut self.slot(key).get_or_insert_with(|| (key, default())).1
+    }
+
+    /// Returns the slot of the key, growing the entries to include it.
+    fn slot(&mut self, key: Key) -> &mut Option<(Key, Value)> {
+        let index = key.index();
+        if index >= self.entries.len() {
+            self.entries.resize_with(index + 1, || None);
+        }
+        &mut self.entries[index]
+    }
+
+    /// Returns an iterator over the entries of the map, in the order of their keys.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &Value)> {
+        self.entries.iter().flatten().map(|(key, value)| (*key, value))
+    }
+
+    /// Returns an iterator over the keys of the map, in order.
+    pub fn keys(&self) -> impl Iterator<Item = Key> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the values of the map, in the order of their keys.
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<Key: DenseKey, Value: PartialEq> PartialEq for DenseMap<Key, Value> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<Key: DenseKey, Value: Eq> Eq for DenseMap<Key, Value> {}
+
+impl<Key: DenseKey, Value> FromIterator<(Key, Value)> for DenseMap<Key, Value> {
+    fn from_iter<T: IntoIterator<Item = (Key, Value)>>(iter: T) -> Self {
+        let mut map = Self::default();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
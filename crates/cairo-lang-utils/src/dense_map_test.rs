@@ -0,0 +1,44 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use super::DenseMap;
+use crate::copy_on_write::CopyOnWrite;
+
+#[test]
+fn test_dense_map() {
+    let mut map = DenseMap::<usize, &str>::default();
+    assert!(map.is_empty());
+    assert_eq!(map.insert(5, "five"), None);
+    assert_eq!(map.insert(2, "two"), None);
+    assert_eq!(map.insert(5, "FIVE"), Some("five"));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(5), Some(&"FIVE"));
+    assert_eq!(map.get(3), None);
+    assert_eq!(map.get(100), None);
+    *map.get_or_insert_with(3, || "three") = "THREE";
+    assert_eq!(map.get_or_insert_with(3, || "unused"), &"THREE");
+    assert_eq!(map.iter().collect::<Vec<_>>(), [(2, &"two"), (3, &"THREE"), (5, &"FIVE")]);
+
+    assert_eq!(map.remove(2), Some("two"));
+    assert_eq!(map.remove(2), None);
+    assert_eq!(map.len(), 2);
+    assert!(!map.contains_key(2));
+
+    // Equality does not depend on the removed or untouched ids.
+    assert_eq!(map, DenseMap::from_iter([(5, "FIVE"), (3, "THREE")]));
+    map.remove(5);
+    assert_eq!(map, DenseMap::from_iter([(3, "THREE")]));
+}
+
+#[test]
+fn test_copy_on_write() {
+    let mut a = CopyOnWrite::new(DenseMap::<usize, usize>::from_iter([(1, 1)]));
+    let mut b = a.clone();
+    assert!(a.is_shared());
+    b.to_mut().insert(2, 2);
+    assert!(!a.is_shared());
+    assert_eq!(a.len(), 1);
+    assert_eq!(b.len(), 2);
+    a.to_mut().insert(2, 2);
+    assert_eq!(a, b);
+}
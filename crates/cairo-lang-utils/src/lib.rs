@@ -15,6 +15,8 @@ pub mod bigint;
 pub mod byte_array;
 pub mod casts;
 pub mod collection_arithmetics;
+pub mod copy_on_write;
+pub mod dense_map;
 pub mod deque;
 pub mod extract_matches;
 #[cfg(feature = "std")]
@@ -32,7 +32,11 @@ pub fn init_logging(level: tracing::Level) {
 
         let filter = env_filter.and_then(salsa_filter);
 
+        // The system time is unavailable on `wasm32`, so the time is omitted there.
+        #[cfg(not(target_arch = "wasm32"))]
         let timer = tracing_subscriber::fmt::time::SystemTime;
+        #[cfg(target_arch = "wasm32")]
+        let timer = ();
         let fmt_layer = tracing_subscriber::fmt::layer()
             .with_timer(timer)
             .with_ansi(false)
@@ -1,8 +1,12 @@
 use core::hash::{BuildHasher, Hash};
 
-use indexmap::IndexMap;
+use indexmap::{Equivalent, IndexMap};
 use itertools::zip_eq;
 
+#[cfg(test)]
+#[path = "ordered_hash_map_test.rs"]
+mod test;
+
 #[cfg(feature = "std")]
 type BHImpl = std::collections::hash_map::RandomState;
 #[cfg(not(feature = "std"))]
@@ -101,8 +105,43 @@ impl<Key: Eq + Hash, Value, BH: BuildHasher> OrderedHashMap<Key, Value, BH> {
     }
 }
 
+impl<Key: Eq + Hash, Value, BH: BuildHasher> OrderedHashMap<Key, Value, BH> {
+    /// Retains only the entries for which `keep` returns true, keeping the order of the retained
+    /// entries.
+    pub fn retain(&mut self, keep: impl FnMut(&Key, &mut Value) -> bool) {
+        self.0.retain(keep);
+    }
+
+    /// Removes all the entries of the map, returning them in order.
+    ///
+    /// The map is empty once the returned iterator is dropped, even if it was not fully consumed.
+    pub fn drain(&mut self) -> Drain<'_, Key, Value> {
+        self.0.drain(..)
+    }
+
+    /// Returns mutable references to the values of several keys at once, or `None` for the keys
+    /// not in the map.
+    ///
+    /// Panics if any two of the keys are equal.
+    pub fn get_many_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> [Option<&mut Value>; N]
+    where
+        Q: ?Sized + Hash + Equivalent<Key>,
+    {
+        self.0.get_disjoint_mut(keys)
+    }
+}
+
 /// Entry for an existing key-value pair or a vacant location to insert one.
+///
+/// Has the API of the entries of `std` maps, e.g. `or_insert_with` and `and_modify`. New keys are
+/// inserted at the end of the map.
 pub type Entry<'a, Key, Value> = indexmap::map::Entry<'a, Key, Value>;
+/// An entry for an existing key-value pair.
+pub type OccupiedEntry<'a, Key, Value> = indexmap::map::OccupiedEntry<'a, Key, Value>;
+/// An entry for a vacant location to insert a key-value pair.
+pub type VacantEntry<'a, Key, Value> = indexmap::map::VacantEntry<'a, Key, Value>;
+/// A draining iterator over the entries of a map.
+pub type Drain<'a, Key, Value> = indexmap::map::Drain<'a, Key, Value>;
 
 impl<Key, Value, BH> IntoIterator for OrderedHashMap<Key, Value, BH> {
     type Item = (Key, Value);
@@ -0,0 +1,67 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState as HashBuilderType;
+
+#[cfg(not(feature = "std"))]
+use hashbrown::DefaultHashBuilder as HashBuilderType;
+
+use super::{Entry, OrderedHashMap};
+
+type Map = OrderedHashMap<u32, u32, HashBuilderType>;
+
+#[test]
+fn test_retain() {
+    let mut map = Map::from_iter([(5, 1), (3, 2), (7, 3), (1, 4)]);
+    map.retain(|key, value| {
+        *value *= 10;
+        *key != 3
+    });
+    assert_eq!(map.into_iter().collect::<Vec<_>>(), [(5, 10), (7, 30), (1, 40)]);
+}
+
+#[test]
+fn test_drain() {
+    let mut map = Map::from_iter([(5, 1), (3, 2), (7, 3)]);
+    assert_eq!(map.drain().collect::<Vec<_>>(), [(5, 1), (3, 2), (7, 3)]);
+    assert!(map.is_empty());
+
+    map.insert(1, 1);
+    map.insert(2, 2);
+    // Dropping the iterator early still empties the map.
+    assert_eq!(map.drain().next(), Some((1, 1)));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_get_many_mut() {
+    let mut map = Map::from_iter([(5, 1), (3, 2), (7, 3)]);
+    let [a, b, c] = map.get_many_mut([&7, &5, &4]);
+    core::mem::swap(a.unwrap(), b.unwrap());
+    assert_eq!(c, None);
+    assert_eq!(map.into_iter().collect::<Vec<_>>(), [(5, 3), (3, 2), (7, 1)]);
+}
+
+#[test]
+#[should_panic]
+fn test_get_many_mut_equal_keys() {
+    let mut map = Map::from_iter([(5, 1)]);
+    let _ = map.get_many_mut([&5, &5]);
+}
+
+#[test]
+fn test_entry() {
+    let mut map = Map::from_iter([(5, 1), (3, 2)]);
+    map.entry(5).and_modify(|value| *value += 10).or_insert(0);
+    map.entry(4).and_modify(|value| *value += 10).or_insert(0);
+    *map.entry(3).or_default() += 1;
+    match map.entry(9) {
+        Entry::Occupied(_) => panic!("Key 9 should be vacant."),
+        Entry::Vacant(entry) => {
+            assert_eq!(*entry.key(), 9);
+            entry.insert(7);
+        }
+    }
+    // New keys are inserted at the end.
+    assert_eq!(map.into_iter().collect::<Vec<_>>(), [(5, 11), (3, 3), (4, 0), (9, 7)]);
+}
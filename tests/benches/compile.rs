@@ -1,29 +1,247 @@
-use std::path::PathBuf;
+//! Compilation benchmarks.
+//!
+//! The `compile` group measures full compilations of a corpus of representative programs, and the
+//! other groups measure each compilation phase separately, with the previous phases already
+//! computed.
+//!
+//! Setting `CAIRO_BENCH_MAX_REGRESSION` to a percentage makes the run fail if the mean time of any
+//! benchmark regressed by more than it, compared to the criterion baseline named by
+//! `CAIRO_BENCH_BASELINE` (`base` by default - the previous run). E.g.:
+//! `cargo bench --bench compile -- --save-baseline main`, and after a change:
+//! `CAIRO_BENCH_BASELINE=main CAIRO_BENCH_MAX_REGRESSION=5 cargo bench --bench compile`.
 
+use std::path::{Path, PathBuf};
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::project::setup_project;
 use cairo_lang_compiler::{CompilerConfig, compile_cairo_project_at_path};
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_filesystem::ids::CrateInput;
+use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_lowering::utils::InliningStrategy;
-use criterion::{Criterion, criterion_group, criterion_main};
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_to_casm::compiler::{SierraToCasmConfig, compile};
+use cairo_lang_sierra_to_casm::metadata::calc_metadata_ap_change_only;
+use cairo_lang_sierra_type_size::ProgramRegistryInfo;
+use cairo_lang_starknet::compile::compile_path;
+use cairo_lang_starknet::starknet_plugin_suite;
+use criterion::{BatchSize, Criterion, criterion_group};
 
-fn bench_compile(c: &mut Criterion) {
-    let workspace_root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_owned();
+/// A program of the benchmarked corpus.
+struct Corpus {
+    name: &'static str,
+    /// The path of the program - a single file or a project directory, relative to the workspace
+    /// root.
+    path: &'static str,
+    /// Whether the program is a Starknet contract.
+    starknet: bool,
+}
+
+const CORPORA: &[Corpus] = &[
+    Corpus { name: "fib", path: "examples/fib.cairo", starknet: false },
+    Corpus { name: "generics", path: "tests/benches/corpus/generics.cairo", starknet: false },
+    Corpus { name: "recursion", path: "tests/benches/corpus/recursion.cairo", starknet: false },
+    Corpus { name: "erc20", path: "tests/benches/corpus/erc20", starknet: true },
+];
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_owned()
+}
+
+impl Corpus {
+    fn path(&self) -> PathBuf {
+        workspace_root().join(self.path)
+    }
+
+    /// Returns a database with the program set up as its main crate, and the main crate.
+    fn setup_db(&self) -> (RootDatabase, Vec<CrateInput>) {
+        let mut builder = RootDatabase::builder();
+        builder.detect_corelib();
+        if self.starknet {
+            builder.with_default_plugin_suite(starknet_plugin_suite());
+        }
+        let mut db = builder.build().unwrap();
+        let main_crates = setup_project(&mut db, &self.path()).unwrap();
+        (db, main_crates)
+    }
+}
 
+/// A compilation phase, computed on a database where the previous phases were already computed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Finding the modules of the crates and parsing their files.
+    Parse,
+    /// Computing the semantic diagnostics of the modules.
+    Semantic,
+    /// Lowering the functions of the modules, for their diagnostics.
+    Lowering,
+    /// Generating the Sierra program, including the lowering optimizations.
+    Sierra,
+}
+
+impl Phase {
+    const ALL: [Phase; 4] = [Phase::Parse, Phase::Semantic, Phase::Lowering, Phase::Sierra];
+
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Semantic => "semantic",
+            Phase::Lowering => "lowering",
+            Phase::Sierra => "sierra",
+        }
+    }
+
+    fn run(self, db: &RootDatabase, main_crates: &[CrateInput]) {
+        let crate_ids = CrateInput::into_crate_ids(db, main_crates.to_vec());
+        if let Phase::Sierra = self {
+            db.get_sierra_program(crate_ids).as_ref().unwrap();
+            return;
+        }
+        for crate_id in crate_ids {
+            for module_id in db.crate_modules(crate_id).iter() {
+                match self {
+                    Phase::Parse => {
+                        for file_id in db.module_files(*module_id).unwrap().iter() {
+                            db.file_syntax_diagnostics(*file_id);
+                        }
+                    }
+                    Phase::Semantic => {
+                        db.module_semantic_diagnostics(*module_id).unwrap();
+                    }
+                    Phase::Lowering => {
+                        db.module_lowering_diagnostics(*module_id).unwrap();
+                    }
+                    Phase::Sierra => unreachable!(),
+                }
+            }
+        }
+    }
+
+    /// Returns a database where the phases before this one were computed.
+    fn setup_db(self, corpus: &Corpus) -> (RootDatabase, Vec<CrateInput>) {
+        let (db, main_crates) = corpus.setup_db();
+        for phase in Phase::ALL.into_iter().take_while(|phase| *phase != self) {
+            phase.run(&db, &main_crates);
+        }
+        (db, main_crates)
+    }
+}
+
+fn bench_compile(c: &mut Criterion) {
     let mut group = c.benchmark_group("compile");
     group.sample_size(10);
-
-    group.bench_function("fib", |b| {
-        let path = workspace_root.join("examples").join("fib.cairo");
-        b.iter(|| {
-            compile_cairo_project_at_path(
-                &path,
-                CompilerConfig::default(),
-                InliningStrategy::Default,
-            )
-            .unwrap()
+    for corpus in CORPORA {
+        let path = corpus.path();
+        group.bench_function(corpus.name, |b| {
+            b.iter(|| compile_corpus(corpus, &path));
         });
-    });
+    }
+    group.finish();
+}
 
+fn compile_corpus(corpus: &Corpus, path: &Path) {
+    if corpus.starknet {
+        compile_path(path, None, CompilerConfig::default(), InliningStrategy::Default).unwrap();
+    } else {
+        compile_cairo_project_at_path(path, CompilerConfig::default(), InliningStrategy::Default)
+            .unwrap();
+    }
+}
+
+fn bench_phases(c: &mut Criterion) {
+    for phase in Phase::ALL {
+        let mut group = c.benchmark_group(phase.name());
+        group.sample_size(10);
+        for corpus in CORPORA {
+            group.bench_function(corpus.name, |b| {
+                b.iter_batched(
+                    || phase.setup_db(corpus),
+                    |(db, main_crates)| phase.run(&db, &main_crates),
+                    BatchSize::PerIteration,
+                );
+            });
+        }
+        group.finish();
+    }
+}
+
+fn bench_sierra_to_casm(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sierra_to_casm");
+    group.sample_size(10);
+    for corpus in CORPORA {
+        let (db, main_crates) = Phase::Sierra.setup_db(corpus);
+        let crate_ids = CrateInput::into_crate_ids(&db, main_crates);
+        let program = db.get_sierra_program(crate_ids).as_ref().unwrap().program.clone();
+        group.bench_function(corpus.name, |b| b.iter(|| sierra_to_casm(&program)));
+    }
     group.finish();
 }
 
-criterion_group!(benches, bench_compile);
-criterion_main!(benches);
+fn sierra_to_casm(program: &Program) {
+    let program_info = ProgramRegistryInfo::new(program).unwrap();
+    let metadata = calc_metadata_ap_change_only(program, &program_info).unwrap();
+    let config = SierraToCasmConfig {
+        gas_usage_check: false,
+        max_bytecode_size: usize::MAX,
+        record_variable_locations: false,
+        coalesce_ap_updates: false,
+    };
+    compile(program, &program_info, &metadata, config).unwrap();
+}
+
+/// Fails if a benchmark regressed by more than `CAIRO_BENCH_MAX_REGRESSION` percent compared to the
+/// baseline. Does nothing if the variable is not set.
+fn check_regressions() {
+    let Ok(max_regression) = std::env::var("CAIRO_BENCH_MAX_REGRESSION") else {
+        return;
+    };
+    let max_regression: f64 =
+        max_regression.parse().expect("`CAIRO_BENCH_MAX_REGRESSION` must be a percentage.");
+    let baseline = std::env::var("CAIRO_BENCH_BASELINE").unwrap_or_else(|_| "base".into());
+    let criterion_dir =
+        std::env::var_os("CRITERION_HOME").map(PathBuf::from).unwrap_or_else(|| {
+            std::env::var_os("CARGO_TARGET_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| workspace_root().join("target"))
+                .join("criterion")
+        });
+
+    let mut regressions = vec![];
+    for group in ["compile", "parse", "semantic", "lowering", "sierra", "sierra_to_casm"] {
+        for corpus in CORPORA {
+            let bench_dir = criterion_dir.join(group).join(corpus.name);
+            let (Some(new), Some(old)) =
+                (mean_time(&bench_dir.join("new")), mean_time(&bench_dir.join(&baseline)))
+            else {
+                continue;
+            };
+            let change = (new / old - 1.0) * 100.0;
+            if change > max_regression {
+                regressions.push(format!("{group}/{}: {change:+.2}%", corpus.name));
+            }
+        }
+    }
+    assert!(
+        regressions.is_empty(),
+        "Benchmarks regressed by more than {max_regression}% compared to `{baseline}`:\n{}",
+        regressions.join("\n")
+    );
+}
+
+/// Returns the mean time of a benchmark, from the criterion estimates in the directory.
+fn mean_time(dir: &Path) -> Option<f64> {
+    let estimates = std::fs::read_to_string(dir.join("estimates.json")).ok()?;
+    let estimates: serde_json::Value = serde_json::from_str(&estimates).ok()?;
+    estimates["mean"]["point_estimate"].as_f64()
+}
+
+criterion_group!(benches, bench_compile, bench_phases, bench_sierra_to_casm);
+
+fn main() {
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    check_regressions();
+}
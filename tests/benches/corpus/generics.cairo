@@ -0,0 +1,123 @@
+//! A library heavy on generics: generic containers, traits with associated items, and many
+//! instantiations of them.
+
+#[derive(Copy, Drop, PartialEq, Debug)]
+struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+trait Combine<T> {
+    fn combine(self: T, other: T) -> T;
+}
+
+impl Felt252Combine of Combine<felt252> {
+    fn combine(self: felt252, other: felt252) -> felt252 {
+        self + other
+    }
+}
+
+impl U32Combine of Combine<u32> {
+    fn combine(self: u32, other: u32) -> u32 {
+        self + other
+    }
+}
+
+impl U128Combine of Combine<u128> {
+    fn combine(self: u128, other: u128) -> u128 {
+        self + other
+    }
+}
+
+impl PairCombine<
+    A, B, +Combine<A>, +Combine<B>, +Drop<A>, +Drop<B>,
+> of Combine<Pair<A, B>> {
+    fn combine(self: Pair<A, B>, other: Pair<A, B>) -> Pair<A, B> {
+        Pair { first: self.first.combine(other.first), second: self.second.combine(other.second) }
+    }
+}
+
+trait Container<C> {
+    type Item;
+    fn items(self: @C) -> Span<Self::Item>;
+}
+
+#[derive(Drop)]
+struct Bag<T> {
+    items: Array<T>,
+}
+
+impl BagContainer<T> of Container<Bag<T>> {
+    type Item = T;
+    fn items(self: @Bag<T>) -> Span<T> {
+        self.items.span()
+    }
+}
+
+fn fold<T, +Combine<T>, +Copy<T>, +Drop<T>>(items: Span<T>, init: T) -> T {
+    let mut acc = init;
+    for item in items {
+        acc = acc.combine(*item);
+    }
+    acc
+}
+
+fn fold_container<
+    C, impl Cont: Container<C>, +Combine<Cont::Item>, +Copy<Cont::Item>, +Drop<Cont::Item>,
+>(
+    container: @C, init: Cont::Item,
+) -> Cont::Item {
+    fold(container.items(), init)
+}
+
+fn repeat<T, +Copy<T>, +Drop<T>>(value: T, count: u32) -> Array<T> {
+    let mut items = array![];
+    for _ in 0..count {
+        items.append(value);
+    }
+    items
+}
+
+fn pairs<A, B, +Copy<A>, +Drop<A>, +Copy<B>, +Drop<B>>(first: A, second: B, count: u32) -> Array<Pair<A, B>> {
+    repeat(Pair { first, second }, count)
+}
+
+fn nested<T, +Copy<T>, +Drop<T>, +Combine<T>>(value: T) -> Pair<Pair<T, T>, Pair<T, T>> {
+    let inner = Pair { first: value, second: value.combine(value) };
+    Pair { first: inner, second: inner.combine(inner) }
+}
+
+fn sum_felts() -> felt252 {
+    fold_container(@Bag { items: repeat(3, 10) }, 0)
+}
+
+fn sum_u32s() -> u32 {
+    fold_container(@Bag { items: repeat(3_u32, 10) }, 0)
+}
+
+fn sum_u128s() -> u128 {
+    fold_container(@Bag { items: repeat(3_u128, 10) }, 0)
+}
+
+fn sum_pairs() -> Pair<felt252, u32> {
+    fold(pairs(1, 2_u32, 10).span(), Pair { first: 0, second: 0 })
+}
+
+fn sum_nested_pairs() -> Pair<Pair<u128, felt252>, u32> {
+    let items = pairs(Pair { first: 1_u128, second: 2 }, 3_u32, 10);
+    fold(items.span(), Pair { first: Pair { first: 0, second: 0 }, second: 0 })
+}
+
+fn nested_felts() -> Pair<Pair<felt252, felt252>, Pair<felt252, felt252>> {
+    nested(5)
+}
+
+fn nested_u32s() -> Pair<Pair<u32, u32>, Pair<u32, u32>> {
+    nested(5_u32)
+}
+
+fn nested_pairs() -> Pair<
+    Pair<Pair<u128, u32>, Pair<u128, u32>>, Pair<Pair<u128, u32>, Pair<u128, u32>>,
+> {
+    nested(Pair { first: 5_u128, second: 6_u32 })
+}
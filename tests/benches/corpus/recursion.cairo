@@ -0,0 +1,100 @@
+//! A program with deep recursion: self and mutually recursive functions, and long call chains.
+
+fn fib(n: u128) -> u128 {
+    if n < 2 {
+        n
+    } else {
+        fib(n - 1) + fib(n - 2)
+    }
+}
+
+fn ackermann(m: u128, n: u128) -> u128 {
+    if m == 0 {
+        n + 1
+    } else if n == 0 {
+        ackermann(m - 1, 1)
+    } else {
+        ackermann(m - 1, ackermann(m, n - 1))
+    }
+}
+
+fn is_even(n: u32) -> bool {
+    if n == 0 {
+        true
+    } else {
+        is_odd(n - 1)
+    }
+}
+
+fn is_odd(n: u32) -> bool {
+    if n == 0 {
+        false
+    } else {
+        is_even(n - 1)
+    }
+}
+
+#[derive(Drop)]
+enum Tree {
+    Leaf: felt252,
+    Node: (Box<Tree>, Box<Tree>),
+}
+
+fn build_tree(depth: u32, value: felt252) -> Tree {
+    if depth == 0 {
+        Tree::Leaf(value)
+    } else {
+        Tree::Node(
+            (
+                BoxTrait::new(build_tree(depth - 1, value * 2)),
+                BoxTrait::new(build_tree(depth - 1, value * 2 + 1)),
+            ),
+        )
+    }
+}
+
+fn tree_sum(tree: Tree) -> felt252 {
+    match tree {
+        Tree::Leaf(value) => value,
+        Tree::Node((left, right)) => tree_sum(left.unbox()) + tree_sum(right.unbox()),
+    }
+}
+
+fn chain_0(x: felt252) -> felt252 {
+    chain_1(x + 1)
+}
+fn chain_1(x: felt252) -> felt252 {
+    chain_2(x * 2)
+}
+fn chain_2(x: felt252) -> felt252 {
+    chain_3(x + 3)
+}
+fn chain_3(x: felt252) -> felt252 {
+    chain_4(x * 4)
+}
+fn chain_4(x: felt252) -> felt252 {
+    chain_5(x + 5)
+}
+fn chain_5(x: felt252) -> felt252 {
+    chain_6(x * 6)
+}
+fn chain_6(x: felt252) -> felt252 {
+    chain_7(x + 7)
+}
+fn chain_7(x: felt252) -> felt252 {
+    chain_8(x * 8)
+}
+fn chain_8(x: felt252) -> felt252 {
+    chain_9(x + 9)
+}
+fn chain_9(x: felt252) -> felt252 {
+    if x == 0 {
+        x
+    } else {
+        chain_0(x - 1)
+    }
+}
+
+fn main() -> (u128, u128, bool, felt252, felt252) {
+    (fib(20), ackermann(2, 3), is_even(10), tree_sum(build_tree(5, 1)), chain_0(0))
+}
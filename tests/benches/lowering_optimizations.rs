@@ -0,0 +1,144 @@
+//! Lowering optimization benchmarks.
+//!
+//! Measures each phase of the optimization strategies in isolation. The lowering of each program of
+//! the corpus (and of the corelib) before optimizations is serialized into crate cache files, and
+//! is loaded back from them by the benchmarked database. The lowered functions are then captured
+//! right before every phase of the pipeline is applied, and each benchmark applies a single phase
+//! to the captured snapshots - so a benchmark is named after the position of its phase in the
+//! pipeline, e.g. `lowering_optimizations/08_ConstFolding/fib`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::project::setup_project;
+use cairo_lang_filesystem::db::{FilesGroup, files_group_input, set_crate_configs_input};
+use cairo_lang_filesystem::ids::{BlobLongId, CrateInput};
+use cairo_lang_lowering::cache::generate_crate_cache;
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_lowering::optimizations::strategy::OptimizationPhase;
+use cairo_lang_lowering::{Lowered, LoweringStage};
+use cairo_lang_semantic::corelib::CorelibSemantic;
+use cairo_lang_sierra_generator::program_generator::find_all_free_function_ids;
+use cairo_lang_utils::Intern;
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use itertools::{Itertools, chain, zip_eq};
+
+/// The programs the optimizations are measured on, as `(name, path relative to the workspace)`.
+const CORPORA: &[(&str, &str)] = &[
+    ("fib", "examples/fib.cairo"),
+    ("generics", "tests/benches/corpus/generics.cairo"),
+    ("recursion", "tests/benches/corpus/recursion.cairo"),
+];
+
+/// The lowered functions of a program, right before an optimization phase is applied to them.
+struct PhaseSnapshot<'db> {
+    /// The position of the phase in the pipeline and its name, e.g. `08_ConstFolding`.
+    label: String,
+    phase: OptimizationPhase<'db>,
+    functions: Vec<(ConcreteFunctionWithBodyId<'db>, Lowered<'db>)>,
+}
+
+/// Serializes the lowering of the corelib and of the crates of the program at `path` into crate
+/// cache files in `cache_dir`. Returns the input of each crate along with the path of its cache
+/// file, starting with the corelib.
+fn serialize_corpus(path: &Path, cache_dir: &Path) -> Vec<(CrateInput, PathBuf)> {
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let main_crates = setup_project(&mut db, path).unwrap();
+    let core_crate = db.crate_input(db.core_crate()).clone();
+    fs::create_dir_all(cache_dir).unwrap();
+    chain!([core_crate], main_crates)
+        .map(|crate_input| {
+            let crate_id = crate_input.clone().into_crate_long_id(&db).intern(&db);
+            let cache_path =
+                cache_dir.join(format!("{}.cache", crate_id.long(&db).name().long(&db)));
+            fs::write(&cache_path, generate_crate_cache(&db, crate_id).unwrap()).unwrap();
+            (crate_input, cache_path)
+        })
+        .collect()
+}
+
+/// Returns a database of the program at `path`, whose crates load their lowering from the cache
+/// files written by [serialize_corpus] instead of lowering their code.
+fn load_corpus(path: &Path, cache_files: &[(CrateInput, PathBuf)]) -> RootDatabase {
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    setup_project(&mut db, path).unwrap();
+    let mut crate_configs = files_group_input(&db).crate_configs(&db).clone().unwrap();
+    for (crate_input, cache_path) in cache_files {
+        crate_configs.get_mut(crate_input).unwrap().cache_file =
+            Some(BlobLongId::OnDisk(cache_path.clone()));
+    }
+    set_crate_configs_input(&mut db, Some(crate_configs));
+    db
+}
+
+/// Returns the snapshots of the functions before each phase of the baseline and final optimization
+/// strategies, starting from their lowering before optimizations.
+///
+/// The lowering before optimizations is loaded from the cache files of the crates of `db`.
+fn capture_snapshots<'db>(
+    db: &'db RootDatabase,
+    functions: &[ConcreteFunctionWithBodyId<'db>],
+) -> Vec<PhaseSnapshot<'db>> {
+    let mut lowered = functions
+        .iter()
+        .map(|function| {
+            db.lowered_body(*function, LoweringStage::PreOptimizations).unwrap().clone()
+        })
+        .collect_vec();
+    let phases = chain!(
+        &db.baseline_optimization_strategy().long(db).0,
+        &db.final_optimization_strategy().long(db).0,
+    );
+    phases
+        .enumerate()
+        .map(|(index, phase)| {
+            let snapshot = PhaseSnapshot {
+                label: format!("{index:02}_{}", phase.name()),
+                phase: phase.clone(),
+                functions: zip_eq(functions.iter().copied(), lowered.iter().cloned()).collect(),
+            };
+            for (function, lowered) in zip_eq(functions, &mut lowered) {
+                phase.apply(db, *function, lowered).unwrap();
+            }
+            snapshot
+        })
+        .collect()
+}
+
+fn bench_optimizations(c: &mut Criterion) {
+    let workspace_root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_owned();
+    let mut group = c.benchmark_group("lowering_optimizations");
+    let cache_root = Path::new(env!("CARGO_TARGET_TMPDIR")).join("lowering_optimizations");
+    for (name, path) in CORPORA {
+        let path = workspace_root.join(path);
+        let cache_files = serialize_corpus(&path, &cache_root.join(name));
+        let db = load_corpus(&path, &cache_files);
+        let main_crates = cache_files[1..].iter().map(|(crate_input, _)| crate_input.clone());
+        let crate_ids = CrateInput::into_crate_ids(&db, main_crates);
+        let functions = find_all_free_function_ids(&db, crate_ids).unwrap();
+        for snapshot in capture_snapshots(&db, &functions) {
+            group.bench_with_input(
+                BenchmarkId::new(&snapshot.label, name),
+                &snapshot,
+                |b, snapshot| {
+                    b.iter_batched(
+                        || snapshot.functions.clone(),
+                        |mut functions| {
+                            for (function, lowered) in &mut functions {
+                                snapshot.phase.apply(&db, *function, lowered).unwrap();
+                            }
+                            functions
+                        },
+                        BatchSize::SmallInput,
+                    );
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_optimizations);
+criterion_main!(benches);
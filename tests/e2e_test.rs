@@ -4,9 +4,9 @@ use std::sync::{LazyLock, Mutex};
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_filesystem::flag::{Flag, FlagsGroup};
-use cairo_lang_filesystem::ids::FlagLongId;
 use cairo_lang_lowering::db::lowering_group_input;
 use cairo_lang_lowering::optimizations::config::{OptimizationConfig, Optimizations};
+use cairo_lang_runner::differential::run_differential;
 use cairo_lang_runner::{Arg, RunResultValue, SierraCasmRunner};
 use cairo_lang_semantic::test_utils::setup_test_module;
 use cairo_lang_sierra::extensions::gas::{CostTokenMap, CostTokenType};
@@ -55,7 +55,7 @@ static SHARED_DB_FUTURE_SIERRA: LazyLock<Mutex<RootDatabase>> = LazyLock::new(||
     lowering_group_input(&db).set_optimizations(&mut db).to(Some(Optimizations::Enabled(
         OptimizationConfig::default().with_skip_const_folding(true),
     )));
-    db.set_flag(FlagLongId(Flag::FUTURE_SIERRA.into()), Some(Flag::FutureSierra(true)));
+    db.set_flag(Flag::FutureSierra(true));
     Mutex::new(db)
 });
 
@@ -184,8 +184,15 @@ impl TestFileRunner for SmallE2ETestRunner {
     }
 
     fn allowed_arg(&self, arg: &str) -> bool {
-        ["future_sierra", "skip_gas", "test_data_function", "test_data_input", "test_data_output"]
-            .contains(&arg)
+        [
+            "future_sierra",
+            "skip_gas",
+            "test_data_function",
+            "test_data_input",
+            "test_data_output",
+            "test_data_differential",
+        ]
+        .contains(&arg)
     }
 }
 
@@ -242,10 +249,13 @@ struct TestData {
     function_name: String,
     input: Vec<Felt252>,
     expected_output: Vec<Felt252>,
+    /// Whether to also check that simulating the function gives the same result as running it.
+    differential: bool,
 }
 
 impl TestData {
-    /// Parses test_data. Expects three args: test_data_function, test_data_input, test_data_output.
+    /// Parses test_data. Expects three args: test_data_function, test_data_input, test_data_output,
+    /// and optionally test_data_differential.
     fn parse(args: &OrderedHashMap<String, String>) -> Result<Option<TestData>, String> {
         let Some(function_name) = args.get("test_data_function").cloned() else {
             return Ok(None);
@@ -267,6 +277,7 @@ impl TestData {
             function_name,
             input: vec![input],
             expected_output: vec![expected_output],
+            differential: args.get("test_data_differential").is_some_and(|v| v == "true"),
         }))
     }
 }
@@ -353,8 +364,12 @@ fn run_e2e_test(
     let metadata_with_linear =
         calc_metadata(&sierra_program, &program_info, metadata_config.clone()).unwrap();
 
-    let config =
-        compiler::SierraToCasmConfig { gas_usage_check: true, max_bytecode_size: usize::MAX };
+    let config = compiler::SierraToCasmConfig {
+        gas_usage_check: true,
+        max_bytecode_size: usize::MAX,
+        record_variable_locations: false,
+        coalesce_ap_updates: false,
+    };
     // Compile to casm.
     let casm = compiler::compile(&sierra_program, &program_info, &metadata_with_linear, config)
         .map(|x| x.to_string())
@@ -416,6 +431,16 @@ fn run_and_validate_test_data(
         .find_function(&test_data.function_name)
         .map_err(|e| format!("Function '{}' not found: {}", test_data.function_name, e))?;
 
+    if test_data.differential {
+        run_differential(
+            &runner,
+            &test_data.function_name,
+            &test_data.input,
+            withdraw_gas.then_some(u32::MAX as usize),
+        )
+        .map_err(|e| format!("Differential run of '{}' failed: {e}", test_data.function_name))?;
+    }
+
     let result = runner
         .run_function_with_starknet_context(
             func,
@@ -7,8 +7,9 @@ use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::setup_project;
 use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_filesystem::flag::{Flag, FlagsGroup};
-use cairo_lang_filesystem::ids::{CrateInput, FlagLongId};
+use cairo_lang_filesystem::ids::CrateInput;
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_runnable_utils::builder::RunnableBuilder;
 use cairo_lang_runner::{Arg, RunResultValue, SierraCasmRunner, token_gas_cost};
 use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
@@ -61,8 +62,7 @@ fn checked_compile_to_sierra(
     auto_add_withdraw_gas: bool,
 ) -> cairo_lang_sierra::program::Program {
     let mut locked_db = db.lock().unwrap();
-    let add_withdraw_gas_flag_id = FlagLongId(Flag::ADD_WITHDRAW_GAS.into());
-    locked_db.set_flag(add_withdraw_gas_flag_id, Some(Flag::AddWithdrawGas(auto_add_withdraw_gas)));
+    locked_db.set_flag(Flag::AddWithdrawGas(auto_add_withdraw_gas));
     let db = locked_db.snapshot();
     let mut requested_function_ids = vec![];
     for crate_input in crate_inputs {
@@ -162,7 +162,12 @@ fn cairo_to_casm(
             &program,
             &program_info,
             &metadata,
-            SierraToCasmConfig { gas_usage_check, max_bytecode_size: usize::MAX },
+            SierraToCasmConfig {
+                gas_usage_check,
+                max_bytecode_size: usize::MAX,
+                record_variable_locations: false,
+                coalesce_ap_updates: false,
+            },
         )
         .unwrap()
         .to_string(),
@@ -183,7 +188,12 @@ fn cairo_to_casm_auto_gas(#[case] name: &str, example_dir_data: &ExampleDirData)
             &program,
             &program_info,
             &metadata,
-            SierraToCasmConfig { gas_usage_check: true, max_bytecode_size: usize::MAX },
+            SierraToCasmConfig {
+                gas_usage_check: true,
+                max_bytecode_size: usize::MAX,
+                record_variable_locations: false,
+                coalesce_ap_updates: false,
+            },
         )
         .unwrap()
         .to_string(),
@@ -197,14 +207,16 @@ fn run_function(
     expected_cost: Option<usize>,
     example_dir_data: &ExampleDirData,
     auto_add_withdraw_gas: bool,
+    coalesce_ap_updates: bool,
 ) -> RunResultValue {
-    let runner = SierraCasmRunner::new(
+    let builder = RunnableBuilder::new_ex(
         checked_compile_to_sierra(name, example_dir_data, auto_add_withdraw_gas),
         if available_gas.is_some() { Some(Default::default()) } else { None },
-        Default::default(),
-        None,
+        false,
+        coalesce_ap_updates,
     )
     .expect("Failed setting up runner.");
+    let runner = SierraCasmRunner::from_builder(builder, Default::default(), None);
     let result = runner
         .run_function_with_starknet_context(
             // find first
@@ -295,10 +307,19 @@ fn run_function_test(
     #[case] available_gas: Option<usize>,
     #[case] expected_cost: Option<usize>,
     #[case] expected_result: RunResultValue,
+    #[values(false, true)] coalesce_ap_updates: bool,
     example_dir_data: &ExampleDirData,
 ) {
     pretty_assertions::assert_eq!(
-        run_function(name, params, available_gas, expected_cost, example_dir_data, false),
+        run_function(
+            name,
+            params,
+            available_gas,
+            expected_cost,
+            example_dir_data,
+            false,
+            coalesce_ap_updates
+        ),
         expected_result
     );
 }
@@ -323,7 +344,7 @@ fn run_function_auto_gas_test(
     example_dir_data: &ExampleDirData,
 ) {
     pretty_assertions::assert_eq!(
-        run_function(name, params, available_gas, expected_cost, example_dir_data, true),
+        run_function(name, params, available_gas, expected_cost, example_dir_data, true, false),
         expected_result
     );
 }
@@ -341,7 +362,15 @@ fn run_function_auto_gas_test(
 fn run_fib_array_len(#[case] n: usize, #[case] last: usize, example_dir_data: &ExampleDirData) {
     assert_matches!(
         &extract_matches!(
-            run_function("fib_array", &[n].map(Felt252::from), None, None, example_dir_data, false),
+            run_function(
+                "fib_array",
+                &[n].map(Felt252::from),
+                None,
+                None,
+                example_dir_data,
+                false,
+                false
+            ),
             RunResultValue::Success
         )[..],
         [_, _, actual_last, actual_len] if actual_last == &Felt252::from(last) && actual_len == &Felt252::from(n)